@@ -0,0 +1,24 @@
+-- Migration: Add transcript versions for background re-transcription passes
+-- A quality pass stores its aligned result as a 'pending' version. Accepting it replaces
+-- the meeting's transcripts and keeps the replaced rows in previous_segments so the
+-- change can be reverted. Status values: pending, accepted, discarded, reverted, superseded
+
+-- Set when the user changes a segment; background passes never overwrite edited segments
+ALTER TABLE transcripts ADD COLUMN edited_at TEXT;
+
+CREATE TABLE IF NOT EXISTS transcript_versions (
+    id TEXT PRIMARY KEY NOT NULL,
+    meeting_id TEXT NOT NULL,
+    source TEXT NOT NULL,
+    provider TEXT,
+    model TEXT,
+    status TEXT NOT NULL,
+    segments TEXT NOT NULL,
+    previous_segments TEXT,
+    stats TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_versions_meeting_id ON transcript_versions(meeting_id);
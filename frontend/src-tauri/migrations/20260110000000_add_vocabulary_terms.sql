@@ -0,0 +1,16 @@
+-- Migration: Add custom vocabulary for transcription
+-- Rows with a NULL meeting_id form the user-wide list; rows with a meeting_id only
+-- apply when that meeting is (re)transcribed. sounds_like is a JSON array of known
+-- misrecognitions that are always replaced with the term.
+
+CREATE TABLE IF NOT EXISTS vocabulary_terms (
+    id TEXT PRIMARY KEY NOT NULL,
+    meeting_id TEXT,
+    term TEXT NOT NULL,
+    sounds_like TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_vocabulary_terms_meeting_id ON vocabulary_terms(meeting_id);
@@ -0,0 +1,7 @@
+-- Migration: Add per-segment language identification
+-- language holds the detected language code of each segment ("en", "de", ...); NULL for
+-- segments transcribed before detection was enabled or by engines without language ID.
+-- translation holds an optional English translation so summaries can use either track.
+
+ALTER TABLE transcripts ADD COLUMN language TEXT;
+ALTER TABLE transcripts ADD COLUMN translation TEXT;
@@ -0,0 +1,19 @@
+-- Migration: Add translated transcript and summary tracks
+-- One row per meeting and target language. segments is a JSON array of translated
+-- segments that keep the original segment ids and audio timestamps; summary holds the
+-- translated summary markdown. Status values: running, completed, failed, cancelled
+
+CREATE TABLE IF NOT EXISTS meeting_translations (
+    meeting_id TEXT NOT NULL,
+    language TEXT NOT NULL,
+    status TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    segments TEXT,
+    summary TEXT,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, language),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
@@ -0,0 +1,38 @@
+-- Migration: Add watched import folders and a persistent import queue
+-- Files found in watched folders (or enqueued manually) are imported one at a time.
+-- content_hash (SHA-256 of the file) dedupes the same recording copied to several places.
+-- Queue status values: queued, processing, completed, failed
+
+CREATE TABLE IF NOT EXISTS import_watch_folders (
+    id TEXT PRIMARY KEY,
+    path TEXT NOT NULL UNIQUE,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    language TEXT,
+    transcription_provider TEXT,
+    transcription_model TEXT,
+    template_id TEXT,
+    summary_provider TEXT,
+    summary_model TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS import_queue (
+    id TEXT PRIMARY KEY,
+    source_path TEXT NOT NULL,
+    content_hash TEXT NOT NULL UNIQUE,
+    watch_folder_id TEXT,
+    status TEXT NOT NULL DEFAULT 'queued',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    meeting_id TEXT,
+    next_attempt_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (watch_folder_id) REFERENCES import_watch_folders(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_import_queue_status ON import_queue(status, next_attempt_at);
+
+-- Folder (or directory of a manually enqueued file) a meeting was imported from
+ALTER TABLE meetings ADD COLUMN import_source TEXT;
@@ -0,0 +1,5 @@
+-- Migration: Reference the original video of imported meetings
+-- Video imports keep only the selected audio in the meeting folder; source_video_path points
+-- at the video the transcript timestamps line up with.
+
+ALTER TABLE meetings ADD COLUMN source_video_path TEXT;
@@ -0,0 +1,22 @@
+-- Migration: Add segment-level transcript revision history
+-- Every manual edit, insert, delete, split and join of transcript segments, and every forced
+-- re-alignment, is recorded with the affected rows before and after the change.
+-- Operation values: edit, insert, delete, split, join, realign
+
+CREATE TABLE IF NOT EXISTS transcript_revisions (
+    id TEXT PRIMARY KEY NOT NULL,
+    meeting_id TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    segment_ids TEXT NOT NULL,
+    before_segments TEXT NOT NULL,
+    after_segments TEXT NOT NULL,
+    author TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_revisions_meeting_id ON transcript_revisions(meeting_id, created_at);
+
+-- Word timings from forced re-alignment: JSON array of {word, start, end} (recording seconds).
+-- Cleared whenever the segment's text or timing changes.
+ALTER TABLE transcripts ADD COLUMN word_timings TEXT;
@@ -0,0 +1,19 @@
+-- Migration: Add automatic chapters (topic segmentation) per meeting
+-- A chapter covers a contiguous time range of the transcript and has an LLM-written title
+-- and one-line synopsis. Chapters are regenerated as a whole, so rows are replaced per meeting.
+
+CREATE TABLE IF NOT EXISTS meeting_chapters (
+    id TEXT PRIMARY KEY NOT NULL,
+    meeting_id TEXT NOT NULL,
+    chapter_index INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    synopsis TEXT,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    provider TEXT,
+    model TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_meeting_chapters_meeting_id ON meeting_chapters(meeting_id, chapter_index);
@@ -0,0 +1,12 @@
+-- Migration: Add conversation analytics per meeting
+-- Metrics (talk time, pace, interruptions, filler words, ...) are computed from the transcript
+-- and stored as JSON. transcript_hash identifies the transcript they were computed from, so
+-- analytics are recomputed after the transcript changes.
+
+CREATE TABLE IF NOT EXISTS meeting_analytics (
+    meeting_id TEXT PRIMARY KEY NOT NULL,
+    metrics TEXT NOT NULL,
+    transcript_hash TEXT NOT NULL,
+    computed_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
@@ -0,0 +1,15 @@
+-- Migration: Add bookmarks/highlight markers set during recording
+-- time_seconds is relative to the recording's audio. sequence_id is the live transcript
+-- segment that was active when the marker was set (NULL if none had arrived yet).
+
+CREATE TABLE IF NOT EXISTS meeting_markers (
+    id TEXT PRIMARY KEY NOT NULL,
+    meeting_id TEXT NOT NULL,
+    time_seconds REAL NOT NULL,
+    label TEXT,
+    sequence_id INTEGER,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_meeting_markers_meeting_id ON meeting_markers(meeting_id, time_seconds);
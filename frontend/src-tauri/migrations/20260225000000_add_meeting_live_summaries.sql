@@ -0,0 +1,15 @@
+-- Migration: Add the rolling summary written during recording
+-- covered_until is the recording time (seconds) up to which the transcript is summarized.
+-- The final summary reads this summary plus the transcript after covered_until instead of the
+-- whole transcript, as long as the meeting was not changed after created_at.
+
+CREATE TABLE IF NOT EXISTS meeting_live_summaries (
+    meeting_id TEXT PRIMARY KEY NOT NULL,
+    summary TEXT NOT NULL,
+    covered_until REAL NOT NULL,
+    updates INTEGER NOT NULL DEFAULT 0,
+    provider TEXT,
+    model TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
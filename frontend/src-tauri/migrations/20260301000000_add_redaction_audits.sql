@@ -0,0 +1,19 @@
+-- Migration: Add the audit trail of transcript redaction
+-- One row per LLM request that was redacted before it left the machine. entities is a JSON
+-- array of the redacted values with their placeholder, kind, detector and number of
+-- occurrences. meeting_id is NULL for requests made while recording (live summary).
+
+CREATE TABLE IF NOT EXISTS redaction_audits (
+    id TEXT PRIMARY KEY NOT NULL,
+    meeting_id TEXT,
+    purpose TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    ner_model TEXT,
+    entity_count INTEGER NOT NULL,
+    entities TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_redaction_audits_meeting ON redaction_audits(meeting_id, created_at);
@@ -0,0 +1,18 @@
+-- Migration: Link redacted copies of meetings to their original
+-- A redacted copy is a separate meeting (own folder, audio and transcript) made for sharing.
+-- spans is a JSON array of the redacted transcript spans (segment id and character range of
+-- the original transcript, without the redacted text). audio_mode: mute, bleep.
+-- summary_mode: mask, regenerate, none.
+
+CREATE TABLE IF NOT EXISTS redacted_copies (
+    meeting_id TEXT PRIMARY KEY NOT NULL,
+    source_meeting_id TEXT NOT NULL,
+    spans TEXT NOT NULL,
+    audio_mode TEXT NOT NULL,
+    summary_mode TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE,
+    FOREIGN KEY (source_meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_redacted_copies_source ON redacted_copies(source_meeting_id, created_at);
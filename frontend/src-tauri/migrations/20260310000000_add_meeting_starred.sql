@@ -0,0 +1,4 @@
+-- Migration: Star meetings
+-- Starred meetings are exempt from retention rules that keep starred meetings (the default).
+
+ALTER TABLE meetings ADD COLUMN starred INTEGER NOT NULL DEFAULT 0;
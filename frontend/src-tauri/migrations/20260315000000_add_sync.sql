@@ -0,0 +1,38 @@
+-- Migration: Bookkeeping for multi-device sync
+-- sync_records has one row per synced record of a meeting (kind: meeting, transcript, summary,
+-- notes, audio). revision is a JSON revision vector ({device_id: counter}); content_hash is the
+-- SHA-256 of the record's content as last seen locally; dirty marks local changes the server
+-- has not accepted yet. Rows outlive their meeting so deletions can be synced.
+-- source_stamp caches the size, modification time and hash of the audio file (JSON) so an
+-- unchanged file is not hashed again.
+
+CREATE TABLE IF NOT EXISTS sync_records (
+    kind TEXT NOT NULL,
+    meeting_id TEXT NOT NULL,
+    revision TEXT NOT NULL,
+    content_hash TEXT,
+    modified_at TEXT NOT NULL,
+    modified_by TEXT NOT NULL,
+    deleted INTEGER NOT NULL DEFAULT 0,
+    dirty INTEGER NOT NULL DEFAULT 0,
+    source_stamp TEXT,
+    PRIMARY KEY (kind, meeting_id)
+);
+
+-- Concurrent edits of summaries and notes waiting for the user to pick a version.
+-- remote_record is the server's record (JSON), local_content the local version at detection.
+CREATE TABLE IF NOT EXISTS sync_conflicts (
+    kind TEXT NOT NULL,
+    meeting_id TEXT NOT NULL,
+    local_content TEXT,
+    remote_record TEXT NOT NULL,
+    detected_at TEXT NOT NULL,
+    PRIMARY KEY (kind, meeting_id)
+);
+
+-- Change feed position per sync server
+CREATE TABLE IF NOT EXISTS sync_servers (
+    server_url TEXT PRIMARY KEY NOT NULL,
+    cursor INTEGER NOT NULL DEFAULT 0,
+    last_sync_at TEXT
+);
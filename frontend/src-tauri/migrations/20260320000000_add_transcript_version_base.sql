@@ -0,0 +1,5 @@
+-- Migration: Record which transcript a version was computed from
+-- JSON array of the transcript ids the pass started from. Accepting the version drops
+-- segments whose id was deleted from the meeting since then. NULL for older versions.
+
+ALTER TABLE transcript_versions ADD COLUMN base_segment_ids TEXT;
@@ -137,6 +137,11 @@ pub struct MeetingTranscript {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Per-segment language identification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
 }
 
 /// Meeting metadata without transcripts (for pagination)
@@ -188,6 +193,11 @@ pub struct TranscriptSegment {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Per-segment language identification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -878,6 +888,8 @@ pub async fn api_get_meeting_transcripts<R: Runtime>(
                     audio_start_time: t.audio_start_time,
                     audio_end_time: t.audio_end_time,
                     duration: t.duration,
+                    language: t.language,
+                    translation: t.translation,
                 })
                 .collect::<Vec<_>>();
 
@@ -928,7 +940,7 @@ pub async fn api_save_meeting_title<R: Runtime>(
 
 #[tauri::command]
 pub async fn api_save_transcript<R: Runtime>(
-    _app: AppHandle<R>,
+    app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     meeting_title: String,
     transcripts: Vec<serde_json::Value>,
@@ -977,7 +989,7 @@ pub async fn api_save_transcript<R: Runtime>(
         pool,
         &meeting_title,
         &transcripts_to_save,
-        folder_path,
+        folder_path.clone(),
     )
     .await
     {
@@ -986,6 +998,22 @@ pub async fn api_save_transcript<R: Runtime>(
                 "Successfully saved transcript and created meeting with id: {}",
                 meeting_id
             );
+
+            // Bookmarks set during recording were written to markers.json in the meeting folder
+            crate::audio::markers::import_recording_markers(pool, &meeting_id, folder_path.as_deref())
+                .await;
+            // Running summary written during recording seeds the final summary
+            crate::summary::live::import_live_summary(pool, &meeting_id, folder_path.as_deref()).await;
+            // A vocabulary list borrowed for the recording stays with the meeting
+            crate::audio::transcription::vocabulary::finish_recording(&app, &meeting_id).await;
+
+            // Opt-in background re-transcription with the configured quality model
+            crate::audio::quality_pass::schedule_after_recording(
+                app.clone(),
+                meeting_id.clone(),
+                folder_path,
+            );
+
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Transcript saved successfully",
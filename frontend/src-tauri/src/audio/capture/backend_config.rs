@@ -16,6 +16,12 @@ pub enum AudioCaptureBackend {
     /// Uses direct Core Audio API with aggregate device + tap
     #[cfg(target_os = "macos")]
     CoreAudio,
+
+    /// PulseAudio backend (Linux only)
+    /// Records the default sink monitor or a single application stream
+    /// through PulseAudio or PipeWire (via pipewire-pulse)
+    #[cfg(target_os = "linux")]
+    PulseAudio,
 }
 
 impl AudioCaptureBackend {
@@ -25,6 +31,8 @@ impl AudioCaptureBackend {
             AudioCaptureBackend::ScreenCaptureKit => "ScreenCaptureKit",
             #[cfg(target_os = "macos")]
             AudioCaptureBackend::CoreAudio => "Core Audio",
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => "PulseAudio / PipeWire",
         }
     }
 
@@ -38,6 +46,10 @@ impl AudioCaptureBackend {
             AudioCaptureBackend::CoreAudio => {
                 "Direct Core Audio API - Lower latency, more control over audio pipeline"
             }
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => {
+                "Sound server monitor capture - Records system or per-application audio without a loopback device"
+            }
         }
     }
 
@@ -47,6 +59,8 @@ impl AudioCaptureBackend {
             "screencapturekit" => Some(AudioCaptureBackend::ScreenCaptureKit),
             #[cfg(target_os = "macos")]
             "coreaudio" | "core_audio" => Some(AudioCaptureBackend::CoreAudio),
+            #[cfg(target_os = "linux")]
+            "pulseaudio" | "pulse" | "pipewire" => Some(AudioCaptureBackend::PulseAudio),
             _ => None,
         }
     }
@@ -57,6 +71,8 @@ impl AudioCaptureBackend {
             AudioCaptureBackend::ScreenCaptureKit => "screencapturekit".to_string(),
             #[cfg(target_os = "macos")]
             AudioCaptureBackend::CoreAudio => "coreaudio".to_string(),
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => "pulseaudio".to_string(),
         }
     }
 
@@ -67,7 +83,12 @@ impl AudioCaptureBackend {
             vec![AudioCaptureBackend::ScreenCaptureKit, AudioCaptureBackend::CoreAudio]
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        {
+            vec![AudioCaptureBackend::ScreenCaptureKit, AudioCaptureBackend::PulseAudio]
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         {
             vec![AudioCaptureBackend::ScreenCaptureKit]
         }
@@ -78,7 +99,10 @@ impl AudioCaptureBackend {
         #[cfg(target_os = "macos")]
         return AudioCaptureBackend::CoreAudio;
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        return AudioCaptureBackend::PulseAudio;
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         return AudioCaptureBackend::ScreenCaptureKit;
     }
 }
@@ -158,6 +182,8 @@ mod tests {
         assert_eq!(AudioCaptureBackend::ScreenCaptureKit.to_string(), "screencapturekit");
         #[cfg(target_os = "macos")]
         assert_eq!(AudioCaptureBackend::CoreAudio.to_string(), "coreaudio");
+        #[cfg(target_os = "linux")]
+        assert_eq!(AudioCaptureBackend::PulseAudio.to_string(), "pulseaudio");
     }
 
     #[test]
@@ -177,6 +203,17 @@ mod tests {
                 Some(AudioCaptureBackend::CoreAudio)
             );
         }
+        #[cfg(target_os = "linux")]
+        {
+            assert_eq!(
+                AudioCaptureBackend::from_string("pulseaudio"),
+                Some(AudioCaptureBackend::PulseAudio)
+            );
+            assert_eq!(
+                AudioCaptureBackend::from_string("pipewire"),
+                Some(AudioCaptureBackend::PulseAudio)
+            );
+        }
     }
 
     #[test]
@@ -186,6 +223,9 @@ mod tests {
 
         #[cfg(target_os = "macos")]
         assert!(backends.contains(&AudioCaptureBackend::CoreAudio));
+
+        #[cfg(target_os = "linux")]
+        assert!(backends.contains(&AudioCaptureBackend::PulseAudio));
     }
 
     #[test]
@@ -193,7 +233,10 @@ mod tests {
         #[cfg(target_os = "macos")]
         assert_eq!(AudioCaptureBackend::default(), AudioCaptureBackend::CoreAudio);
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        assert_eq!(AudioCaptureBackend::default(), AudioCaptureBackend::PulseAudio);
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         assert_eq!(AudioCaptureBackend::default(), AudioCaptureBackend::ScreenCaptureKit);
     }
 
@@ -205,7 +248,10 @@ mod tests {
         #[cfg(target_os = "macos")]
         assert_eq!(config.get(), AudioCaptureBackend::CoreAudio);
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        assert_eq!(config.get(), AudioCaptureBackend::PulseAudio);
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         assert_eq!(config.get(), AudioCaptureBackend::ScreenCaptureKit);
 
         #[cfg(target_os = "macos")]
@@ -215,12 +261,22 @@ mod tests {
             assert_eq!(config.get(), AudioCaptureBackend::CoreAudio);
         }
 
+        #[cfg(target_os = "linux")]
+        {
+            // Test switching back to the CPAL path
+            config.set(AudioCaptureBackend::ScreenCaptureKit);
+            assert_eq!(config.get(), AudioCaptureBackend::ScreenCaptureKit);
+        }
+
         // Test reset
         config.reset();
         #[cfg(target_os = "macos")]
         assert_eq!(config.get(), AudioCaptureBackend::CoreAudio);
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        assert_eq!(config.get(), AudioCaptureBackend::PulseAudio);
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         assert_eq!(config.get(), AudioCaptureBackend::ScreenCaptureKit);
     }
 }
\ No newline at end of file
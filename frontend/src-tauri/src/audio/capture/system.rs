@@ -12,7 +12,10 @@ use super::core_audio::CoreAudioCapture;
 #[cfg(target_os = "macos")]
 use log::info;
 
-/// System audio capture using Core Audio tap (macOS) or CPAL (other platforms)
+#[cfg(target_os = "linux")]
+use super::pulse_audio::{self, PulseAudioCapture};
+
+/// System audio capture using Core Audio tap (macOS), PulseAudio/PipeWire (Linux) or CPAL (other platforms)
 pub struct SystemAudioCapture {
     _host: cpal::Host,
 }
@@ -24,6 +27,22 @@ impl SystemAudioCapture {
     }
 
     pub fn list_system_devices() -> Result<Vec<String>> {
+        #[cfg(target_os = "linux")]
+        if pulse_audio::is_available() {
+            let mut device_names = vec![format!(
+                "{} {}",
+                pulse_audio::default_monitor_source()?,
+                pulse_audio::SYSTEM_AUDIO_SUFFIX
+            )];
+            for input in pulse_audio::list_sink_inputs()? {
+                let name = input.device_name();
+                if !device_names.contains(&name) {
+                    device_names.push(name);
+                }
+            }
+            return Ok(device_names);
+        }
+
         let host = cpal::default_host();
         let devices = host.output_devices()
             .map_err(|e| anyhow::anyhow!("Failed to enumerate output devices: {}", e))?;
@@ -96,14 +115,35 @@ impl SystemAudioCapture {
             })
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
         {
-            // For non-macOS platforms, you would implement WASAPI/ALSA loopback here
+            // Record the default sink monitor through PulseAudio/PipeWire
+            let pulse_stream = PulseAudioCapture::new()?.stream()?;
+            let sample_rate = pulse_stream.sample_rate();
+            let (drop_tx, _drop_rx) = std::sync::mpsc::channel::<()>();
+
+            Ok(SystemAudioStream {
+                drop_tx,
+                sample_rate,
+                // Dropping the stream kills parec
+                receiver: Box::pin(pulse_stream),
+            })
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+        {
+            // For Windows, you would implement WASAPI loopback here
             anyhow::bail!("System audio capture not yet implemented for this platform")
         }
     }
 
     pub fn check_system_audio_permissions() -> bool {
+        // Linux has no permission prompt; a reachable sound server is enough
+        #[cfg(target_os = "linux")]
+        if pulse_audio::is_available() {
+            return true;
+        }
+
         // Check if we can enumerate audio devices
         match cpal::default_host().output_devices() {
             Ok(_) => true,
@@ -1,5 +1,6 @@
 use crate::api::TranscriptSegment;
-use anyhow::Result;
+use crate::audio::transcription::language::SegmentLanguage;
+use anyhow::{anyhow, Result};
 use log::{debug, info};
 use std::path::Path;
 use uuid::Uuid;
@@ -51,16 +52,24 @@ pub(crate) fn create_transcript_segments(transcripts: &[(String, f64, f64)]) ->
                 audio_start_time: Some(start_seconds),
                 audio_end_time: Some(end_seconds),
                 duration: Some(duration),
+                language: None,
+                translation: None,
             }
         })
         .collect()
 }
 
+/// Attach per-segment language results (same order as the transcripts the segments came from)
+pub(crate) fn apply_segment_languages(segments: &mut [TranscriptSegment], languages: Vec<SegmentLanguage>) {
+    for (segment, language) in segments.iter_mut().zip(languages) {
+        segment.language = language.language;
+        segment.translation = language.translation;
+    }
+}
+
 /// Write transcripts.json to a meeting folder (atomic write with temp file)
 pub(crate) fn write_transcripts_json(folder: &Path, segments: &[TranscriptSegment]) -> Result<()> {
     let transcript_path = folder.join("transcripts.json");
-    let temp_path = folder.join(".transcripts.json.tmp");
-
     let json = serde_json::json!({
         "version": "1.0",
         "last_updated": chrono::Utc::now().to_rfc3339(),
@@ -73,14 +82,15 @@ pub(crate) fn write_transcripts_json(folder: &Path, segments: &[TranscriptSegmen
                 "audio_start_time": s.audio_start_time,
                 "audio_end_time": s.audio_end_time,
                 "duration": s.duration,
+                "language": s.language,
+                "translation": s.translation,
                 "sequence_id": i
             })
         }).collect::<Vec<_>>()
     });
 
     let json_string = serde_json::to_string_pretty(&json)?;
-    std::fs::write(&temp_path, &json_string)?;
-    std::fs::rename(&temp_path, &transcript_path)?;
+    crate::encryption::files::write_file(&transcript_path, json_string.as_bytes()).map_err(|e| anyhow!(e))?;
 
     info!(
         "Wrote transcripts.json with {} segments to {}",
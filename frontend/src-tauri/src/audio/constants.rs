@@ -1,7 +1,13 @@
 /// Supported audio file extensions for import and retranscription.
 ///
 /// Includes native Symphonia formats (MP4, M4A, WAV, MP3, FLAC, OGG, AAC)
-/// and FFmpeg-backed formats (MKV, WebM, WMA).
+/// and FFmpeg-backed formats (MKV, WebM, WMA, MOV, AVI).
 pub const AUDIO_EXTENSIONS: &[&str] = &[
-    "mp4", "m4a", "wav", "mp3", "flac", "ogg", "aac", "mkv", "webm", "wma"
+    "mp4", "m4a", "wav", "mp3", "flac", "ogg", "aac", "mkv", "webm", "wma", "mov", "m4v", "avi"
 ];
+
+/// Container extensions that may carry video (and several audio tracks).
+///
+/// Imports of these are probed with FFmpeg; when a video stream is present only the
+/// selected audio tracks are copied into the meeting folder.
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "m4v", "mkv", "webm", "mov", "avi"];
@@ -1,10 +1,14 @@
 // Audio file decoder for retranscription feature
 // Uses Symphonia to decode MP4/AAC audio files, with ffmpeg fallback for
-// formats Symphonia can't handle (MKV, WebM, WMA)
+// formats Symphonia can't handle (MKV, WebM, WMA, MOV, AVI). ffmpeg is also used
+// to list the audio tracks of video containers and to decode selected tracks.
 
 use anyhow::{anyhow, Result};
 use log::{debug, error, info, warn};
+use once_cell::sync::Lazy;
 use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::path::Path;
 use std::process::{Command, Stdio};
@@ -20,7 +24,16 @@ use super::audio_processing::{audio_to_mono, resample, resample_audio};
 use super::ffmpeg::find_ffmpeg_path;
 
 /// Extensions requiring ffmpeg pre-conversion (Symphonia lacks these demuxers/codecs)
-const FFMPEG_ONLY_EXTENSIONS: &[&str] = &["mkv", "webm", "wma"];
+const FFMPEG_ONLY_EXTENSIONS: &[&str] = &["mkv", "webm", "wma", "mov", "avi"];
+
+/// `Stream #0:1(eng): Audio: ...`, optionally with a stream id (`#0:1[0x2]`) before the language
+static STREAM_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^Stream #\d+:\d+(?:\[[^\]]*\])?(?:\(([^)]*)\))?: (\w+): (.*)$").unwrap()
+});
+
+/// `HH:MM:SS.ss` durations in ffmpeg's input summary and stream tags
+static DURATION_VALUE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d+):(\d{2}):(\d{2}(?:\.\d+)?)").unwrap());
 
 /// Progress callback for long-running operations
 /// Returns current progress (0-100) and a message
@@ -39,6 +52,41 @@ pub struct DecodedAudio {
     pub duration_seconds: f64,
 }
 
+/// One audio stream of a media file, as reported by ffmpeg
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioTrackInfo {
+    /// Position among the file's audio streams (ffmpeg's `0:a:<index>`)
+    pub index: usize,
+    pub codec: String,
+    /// ISO 639 code from the stream tags ("und" is reported as None)
+    pub language: Option<String>,
+    pub title: Option<String>,
+    pub channels: u16,
+    pub sample_rate: Option<u32>,
+    pub duration_seconds: Option<f64>,
+    pub is_default: bool,
+}
+
+/// Streams of a media file relevant for import
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaProbe {
+    pub duration_seconds: Option<f64>,
+    /// True when the file has a real video stream (cover art does not count)
+    pub has_video: bool,
+    pub audio_tracks: Vec<AudioTrackInfo>,
+}
+
+impl MediaProbe {
+    /// Track used when the user does not pick any: the default-flagged one, else the first
+    pub fn default_track(&self) -> Option<usize> {
+        self.audio_tracks
+            .iter()
+            .find(|t| t.is_default)
+            .or_else(|| self.audio_tracks.first())
+            .map(|t| t.index)
+    }
+}
+
 impl DecodedAudio {
     /// Convert decoded audio to Whisper-compatible 16kHz mono f32 format.
     ///
@@ -269,15 +317,55 @@ fn needs_ffmpeg_conversion(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-/// Convert an audio file to WAV using ffmpeg for formats Symphonia can't decode.
-///
-/// Returns a `TempPath` that auto-deletes the temporary WAV file when dropped.
-/// The caller must keep the `TempPath` alive until decoding of the WAV is complete.
-fn convert_to_wav_with_ffmpeg(
-    input_path: &Path,
-    progress_callback: Option<&ProgressCallback>,
-) -> Result<tempfile::TempPath> {
-    let ffmpeg_path = find_ffmpeg_path().ok_or_else(|| {
+/// ffmpeg arguments selecting audio streams: none keeps ffmpeg's default stream, one maps
+/// that stream, several are mixed into a single stream on a shared timeline
+fn audio_track_args(audio_tracks: &[usize]) -> Vec<String> {
+    match audio_tracks {
+        [] => vec!["-vn".to_string()],
+        [track] => vec!["-map".to_string(), format!("0:a:{}", track)],
+        tracks => {
+            let inputs: String = tracks.iter().map(|t| format!("[0:a:{}]", t)).collect();
+            vec![
+                "-filter_complex".to_string(),
+                format!("{}amix=inputs={}:duration=longest[mixed]", inputs, tracks.len()),
+                "-map".to_string(),
+                "[mixed]".to_string(),
+            ]
+        }
+    }
+}
+
+/// Run ffmpeg with the given arguments and wait for it; stderr is returned for parsing
+pub(crate) fn run_ffmpeg(ffmpeg_path: &Path, args: &[String]) -> Result<std::process::Output> {
+    let mut command = Command::new(ffmpeg_path);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    debug!("FFmpeg command: {:?}", command);
+
+    #[allow(clippy::zombie_processes)]
+    let child = command
+        .spawn()
+        .map_err(|e| anyhow!("Failed to spawn ffmpeg process: {}", e))?;
+
+    child
+        .wait_with_output()
+        .map_err(|e| anyhow!("Failed to wait for ffmpeg process: {}", e))
+}
+
+pub(crate) fn require_ffmpeg(input_path: &Path) -> Result<std::path::PathBuf> {
+    find_ffmpeg_path().ok_or_else(|| {
         anyhow!(
             "FFmpeg not found. FFmpeg is required to decode .{} files. \
              It will be downloaded automatically on next launch, or install it manually.",
@@ -286,7 +374,20 @@ fn convert_to_wav_with_ffmpeg(
                 .and_then(|e| e.to_str())
                 .unwrap_or("this format")
         )
-    })?;
+    })
+}
+
+/// Convert an audio file to WAV using ffmpeg for formats Symphonia can't decode.
+///
+/// `audio_tracks` selects which audio streams to convert (see `audio_track_args`).
+/// Returns a `TempPath` that auto-deletes the temporary WAV file when dropped.
+/// The caller must keep the `TempPath` alive until decoding of the WAV is complete.
+fn convert_to_wav_with_ffmpeg(
+    input_path: &Path,
+    audio_tracks: &[usize],
+    progress_callback: Option<&ProgressCallback>,
+) -> Result<tempfile::TempPath> {
+    let ffmpeg_path = require_ffmpeg(input_path)?;
 
     // Create temp file in the same directory as the input to avoid cross-device issues
     let parent_dir = input_path.parent().unwrap_or_else(|| Path::new("."));
@@ -299,11 +400,12 @@ fn convert_to_wav_with_ffmpeg(
     let temp_path = temp_file.into_temp_path();
 
     info!(
-        "Converting .{} to temporary WAV via ffmpeg: {} -> {}",
+        "Converting .{} (audio tracks {:?}) to temporary WAV via ffmpeg: {} -> {}",
         input_path
             .extension()
             .and_then(|e| e.to_str())
             .unwrap_or("unknown"),
+        audio_tracks,
         input_path.display(),
         temp_path.display()
     );
@@ -319,37 +421,15 @@ fn convert_to_wav_with_ffmpeg(
         .to_str()
         .ok_or_else(|| anyhow!("Invalid temp path (non-UTF8)"))?;
 
-    let mut command = Command::new(&ffmpeg_path);
-    command
-        .args([
-            "-i", input_str,
-            "-vn",                  // Strip video tracks
-            "-acodec", "pcm_s16le", // Output PCM WAV (Symphonia handles natively)
-            "-y",                   // Overwrite without prompt
-            output_str,
-        ])
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    // Hide console window on Windows
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        command.creation_flags(CREATE_NO_WINDOW);
-    }
-
-    debug!("FFmpeg conversion command: {:?}", command);
-
-    #[allow(clippy::zombie_processes)]
-    let child = command
-        .spawn()
-        .map_err(|e| anyhow!("Failed to spawn ffmpeg process: {}", e))?;
+    let mut args = vec!["-i".to_string(), input_str.to_string()];
+    args.extend(audio_track_args(audio_tracks));
+    args.extend([
+        "-acodec".to_string(), "pcm_s16le".to_string(), // Output PCM WAV (Symphonia handles natively)
+        "-y".to_string(),                               // Overwrite without prompt
+        output_str.to_string(),
+    ]);
 
-    let output = child
-        .wait_with_output()
-        .map_err(|e| anyhow!("Failed to wait for ffmpeg process: {}", e))?;
+    let output = run_ffmpeg(&ffmpeg_path, &args)?;
 
     let stderr_text = String::from_utf8_lossy(&output.stderr);
     debug!("FFmpeg stderr: {}", stderr_text);
@@ -388,11 +468,268 @@ fn convert_to_wav_with_ffmpeg(
     Ok(temp_path)
 }
 
+/// Extract (and mix, if several are given) audio tracks of a media file into a FLAC file.
+/// Used for video imports so the meeting folder keeps a compact audio copy on the video's timeline.
+pub fn extract_audio_tracks(input_path: &Path, audio_tracks: &[usize], output_path: &Path) -> Result<()> {
+    let ffmpeg_path = require_ffmpeg(input_path)?;
+
+    let mut args = vec!["-i".to_string(), input_path.to_string_lossy().to_string()];
+    args.extend(audio_track_args(audio_tracks));
+    args.extend([
+        "-vn".to_string(),
+        "-c:a".to_string(),
+        "flac".to_string(),
+        "-y".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ]);
+
+    info!(
+        "Extracting audio tracks {:?} from {} to {}",
+        audio_tracks,
+        input_path.display(),
+        output_path.display()
+    );
+    let output = run_ffmpeg(&ffmpeg_path, &args)?;
+    if !output.status.success() {
+        error!(
+            "FFmpeg audio extraction failed (exit code: {}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return Err(anyhow!(
+            "Failed to extract audio from {} (ffmpeg exit code: {})",
+            input_path.display(),
+            output.status
+        ));
+    }
+    Ok(())
+}
+
+/// List the audio tracks (and whether there is video) of a media file using ffmpeg
+pub fn probe_media(path: &Path) -> Result<MediaProbe> {
+    let ffmpeg_path = require_ffmpeg(path)?;
+    let args = vec![
+        "-hide_banner".to_string(),
+        "-i".to_string(),
+        path.to_string_lossy().to_string(),
+    ];
+
+    // Without an output file ffmpeg exits with an error after printing the input summary
+    let output = run_ffmpeg(&ffmpeg_path, &args)?;
+    let probe = parse_ffmpeg_probe(&String::from_utf8_lossy(&output.stderr));
+    if probe.audio_tracks.is_empty() && probe.duration_seconds.is_none() {
+        return Err(anyhow!("Could not read media information from {}", path.display()));
+    }
+
+    debug!(
+        "Probed {}: video={}, {} audio track(s)",
+        path.display(),
+        probe.has_video,
+        probe.audio_tracks.len()
+    );
+    Ok(probe)
+}
+
+fn parse_duration(value: &str) -> Option<f64> {
+    let captures = DURATION_VALUE.captures(value)?;
+    let hours: f64 = captures[1].parse().ok()?;
+    let minutes: f64 = captures[2].parse().ok()?;
+    let seconds: f64 = captures[3].parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// Channel count from ffmpeg's layout description ("mono", "stereo", "5.1(side)", "6 channels")
+fn parse_channel_layout(layout: &str) -> Option<u16> {
+    let layout = layout.trim();
+    let base = layout.split('(').next().unwrap_or(layout).trim();
+    match base {
+        "mono" => return Some(1),
+        "stereo" | "downmix" => return Some(2),
+        "quad" => return Some(4),
+        _ => {}
+    }
+    if let Some(count) = base.strip_suffix(" channels") {
+        return count.trim().parse().ok();
+    }
+    // "5.1", "7.1", "2.1", "4.0", "6.1" ...
+    let mut parts = base.split('.');
+    let main: u16 = parts.next()?.parse().ok()?;
+    let lfe: u16 = parts.next()?.parse().ok()?;
+    Some(main + lfe)
+}
+
+/// Parse the input summary ffmpeg prints to stderr for `ffmpeg -i <file>`
+fn parse_ffmpeg_probe(stderr: &str) -> MediaProbe {
+    let mut probe = MediaProbe::default();
+    // Index into audio_tracks of the stream whose metadata block is being read
+    let mut current_audio: Option<usize> = None;
+
+    for line in stderr.lines() {
+        let trimmed = line.trim();
+
+        if let Some(captures) = STREAM_LINE.captures(trimmed) {
+            let kind = &captures[2];
+            let details = &captures[3];
+            current_audio = None;
+
+            match kind {
+                "Video" if !details.contains("(attached pic)") => probe.has_video = true,
+                "Audio" => {
+                    let parts: Vec<&str> = details.split(", ").collect();
+                    let codec = parts
+                        .first()
+                        .and_then(|p| p.split_whitespace().next())
+                        .unwrap_or("unknown")
+                        .to_string();
+                    let sample_rate = parts
+                        .iter()
+                        .find_map(|p| p.trim().strip_suffix(" Hz"))
+                        .and_then(|hz| hz.trim().parse().ok());
+                    let channels = parts
+                        .iter()
+                        .skip(1)
+                        .filter(|p| !p.trim().ends_with(" Hz"))
+                        .find_map(|p| parse_channel_layout(p))
+                        .unwrap_or(1);
+                    let language = captures
+                        .get(1)
+                        .map(|m| m.as_str().to_string())
+                        .filter(|code| !code.is_empty() && code != "und");
+
+                    probe.audio_tracks.push(AudioTrackInfo {
+                        index: probe.audio_tracks.len(),
+                        codec,
+                        language,
+                        title: None,
+                        channels,
+                        sample_rate,
+                        duration_seconds: None,
+                        is_default: details.contains("(default)"),
+                    });
+                    current_audio = Some(probe.audio_tracks.len() - 1);
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("Duration:") && probe.duration_seconds.is_none() {
+            let value = trimmed.trim_start_matches("Duration:");
+            probe.duration_seconds = parse_duration(value.split(',').next().unwrap_or(value));
+            continue;
+        }
+
+        // "key : value" tags of the current audio stream
+        let Some(track) = current_audio.and_then(|i| probe.audio_tracks.get_mut(i)) else {
+            continue;
+        };
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let value = value.trim();
+            match key.trim().to_lowercase().as_str() {
+                "title" if !value.is_empty() => track.title = Some(value.to_string()),
+                "duration" => track.duration_seconds = parse_duration(value),
+                _ => {}
+            }
+        }
+    }
+
+    // Streams without their own duration tag last as long as the container
+    for track in probe.audio_tracks.iter_mut() {
+        if track.duration_seconds.is_none() {
+            track.duration_seconds = probe.duration_seconds;
+        }
+    }
+
+    probe
+}
+
 /// Decode an audio file (MP4, M4A, WAV, etc.) to raw samples
 pub fn decode_audio_file(path: &Path) -> Result<DecodedAudio> {
     decode_audio_file_with_progress(path, None)
 }
 
+/// Decode only `start..end` (seconds) of an audio file, as 16kHz mono.
+/// ffmpeg seeks to the range; without ffmpeg the whole file is decoded and the range cut out.
+pub fn decode_audio_range(path: &Path, start: f64, end: f64) -> Result<DecodedAudio> {
+    if crate::encryption::files::is_encrypted_path(path) {
+        let decrypted = crate::encryption::files::decrypted_copy(path).map_err(|e| anyhow!(e))?;
+        return decode_audio_range(&decrypted, start, end);
+    }
+
+    let Some(ffmpeg_path) = find_ffmpeg_path() else {
+        warn!("FFmpeg not found; decoding all of {} to cut {:.1}-{:.1}s", path.display(), start, end);
+        let decoded = decode_audio_file(path)?;
+        let frames = decoded.samples.len() / decoded.channels.max(1) as usize;
+        let to_index = |t: f64| ((t * decoded.sample_rate as f64) as usize).min(frames) * decoded.channels as usize;
+        let samples = decoded.samples[to_index(start)..to_index(end.max(start))].to_vec();
+        return Ok(DecodedAudio {
+            duration_seconds: samples.len() as f64 / (decoded.sample_rate as f64 * decoded.channels as f64),
+            samples,
+            ..decoded
+        });
+    };
+
+    // Next to the input, like convert_to_wav_with_ffmpeg, so decrypted audio stays out of the library
+    let parent_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let temp_path = tempfile::Builder::new()
+        .prefix(".meetily_decode_")
+        .suffix(".wav")
+        .tempfile_in(parent_dir)
+        .map_err(|e| anyhow!("Failed to create temporary WAV file: {}", e))?
+        .into_temp_path();
+
+    let output = run_ffmpeg(&ffmpeg_path, &range_args(path, start, end, &temp_path))?;
+    if !output.status.success() {
+        error!(
+            "FFmpeg range decode failed (exit code: {}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return Err(anyhow!(
+            "Failed to decode {:.1}-{:.1}s of {} (ffmpeg exit code: {})",
+            start,
+            end,
+            path.display(),
+            output.status
+        ));
+    }
+    decode_audio_file(&temp_path)
+}
+
+/// ffmpeg arguments writing `start..end` of `input` to a 16kHz mono WAV.
+/// -ss before -i seeks in the container; when transcoding ffmpeg still trims to the exact sample.
+fn range_args(input: &Path, start: f64, end: f64, output: &Path) -> Vec<String> {
+    vec![
+        "-ss".to_string(),
+        format!("{:.3}", start),
+        "-t".to_string(),
+        format!("{:.3}", (end - start).max(0.0)),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-vn".to_string(),
+        "-ac".to_string(),
+        "1".to_string(),
+        "-ar".to_string(),
+        "16000".to_string(),
+        "-acodec".to_string(),
+        "pcm_s16le".to_string(),
+        "-y".to_string(),
+        output.to_string_lossy().to_string(),
+    ]
+}
+
+/// Decode selected audio tracks of a media file (mixed into one stream when several are given)
+pub fn decode_audio_tracks_with_progress(
+    path: &Path,
+    audio_tracks: &[usize],
+    progress_callback: Option<ProgressCallback>,
+) -> Result<DecodedAudio> {
+    info!("Decoding audio tracks {:?} of {}", audio_tracks, path.display());
+    // The temp WAV is deleted when the guard is dropped at the end of this function
+    let temp_wav = convert_to_wav_with_ffmpeg(path, audio_tracks, progress_callback.as_ref())?;
+    decode_audio_file_with_progress(&temp_wav, progress_callback)
+}
+
 /// Decode an audio file with optional progress callback
 pub fn decode_audio_file_with_progress(
     path: &Path,
@@ -400,6 +737,13 @@ pub fn decode_audio_file_with_progress(
 ) -> Result<DecodedAudio> {
     info!("Decoding audio file: {}", path.display());
 
+    // Encrypted library audio is decoded from a short-lived decrypted copy outside the library,
+    // deleted when the guard drops
+    if crate::encryption::files::is_encrypted_path(path) {
+        let decrypted = crate::encryption::files::decrypted_copy(path).map_err(|e| anyhow!(e))?;
+        return decode_audio_file_with_progress(&decrypted, progress_callback);
+    }
+
     // FFmpeg pre-conversion for unsupported formats (MKV, WebM, WMA).
     // If the file is in a format Symphonia can't decode, use ffmpeg to convert
     // it to a temporary WAV file first, then decode the WAV with Symphonia.
@@ -413,7 +757,7 @@ pub fn decode_audio_file_with_progress(
                     .and_then(|e| e.to_str())
                     .unwrap_or("unknown")
             );
-            let temp_path = convert_to_wav_with_ffmpeg(path, progress_callback.as_ref())?;
+            let temp_path = convert_to_wav_with_ffmpeg(path, &[], progress_callback.as_ref())?;
             let wav_path = temp_path.to_path_buf();
             (Some(temp_path), Cow::Owned(wav_path))
         } else {
@@ -819,7 +1163,98 @@ mod tests {
         assert!(!needs_ffmpeg_conversion(Path::new("audio.ogg")));
         assert!(!needs_ffmpeg_conversion(Path::new("audio.aac")));
         assert!(!needs_ffmpeg_conversion(Path::new("audio.m4a")));
+        assert!(needs_ffmpeg_conversion(Path::new("screen.MOV")));
+        assert!(needs_ffmpeg_conversion(Path::new("capture.avi")));
         // No extension
         assert!(!needs_ffmpeg_conversion(Path::new("noext")));
     }
+
+    const MKV_PROBE: &str = r#"Input #0, matroska,webm, from 'standup.mkv':
+  Metadata:
+    title           : Weekly standup
+    ENCODER         : Lavf60.3.100
+  Duration: 00:42:10.53, start: 0.000000, bitrate: 2512 kb/s
+  Stream #0:0: Video: h264 (High), yuv420p(progressive), 1920x1080, 30 fps, 30 tbr, 1k tbn (default)
+    Metadata:
+      DURATION        : 00:42:10.500000000
+  Stream #0:1(eng): Audio: aac (LC), 48000 Hz, stereo, fltp (default)
+    Metadata:
+      title           : Microphone
+      DURATION        : 00:42:10.530000000
+  Stream #0:2[0x3](und): Audio: opus, 48000 Hz, 5.1(side), fltp
+    Metadata:
+      title           : Desktop audio
+At least one output file must be specified"#;
+
+    #[test]
+    fn test_parse_ffmpeg_probe_lists_audio_tracks() {
+        let probe = parse_ffmpeg_probe(MKV_PROBE);
+        assert!(probe.has_video);
+        assert!((probe.duration_seconds.unwrap() - 2530.53).abs() < 0.001);
+        assert_eq!(probe.audio_tracks.len(), 2);
+
+        let mic = &probe.audio_tracks[0];
+        assert_eq!(mic.index, 0);
+        assert_eq!(mic.codec, "aac");
+        assert_eq!(mic.language.as_deref(), Some("eng"));
+        assert_eq!(mic.title.as_deref(), Some("Microphone"));
+        assert_eq!(mic.channels, 2);
+        assert_eq!(mic.sample_rate, Some(48000));
+        assert!(mic.is_default);
+
+        let desktop = &probe.audio_tracks[1];
+        assert_eq!(desktop.index, 1);
+        assert_eq!(desktop.language, None);
+        assert_eq!(desktop.title.as_deref(), Some("Desktop audio"));
+        assert_eq!(desktop.channels, 6);
+        assert!(!desktop.is_default);
+        // No own duration tag: falls back to the container duration
+        assert_eq!(desktop.duration_seconds, probe.duration_seconds);
+
+        assert_eq!(probe.default_track(), Some(0));
+    }
+
+    #[test]
+    fn test_parse_ffmpeg_probe_ignores_cover_art() {
+        let probe = parse_ffmpeg_probe(
+            "  Duration: 00:03:00.00, start: 0.000000, bitrate: 320 kb/s\n  \
+             Stream #0:0: Audio: mp3, 44100 Hz, mono, fltp, 320 kb/s\n  \
+             Stream #0:1: Video: mjpeg (Baseline), yuvj420p, 500x500 (attached pic)",
+        );
+        assert!(!probe.has_video);
+        assert_eq!(probe.audio_tracks.len(), 1);
+        assert_eq!(probe.audio_tracks[0].channels, 1);
+        assert_eq!(probe.default_track(), Some(0));
+    }
+
+    #[test]
+    fn test_parse_channel_layout() {
+        assert_eq!(parse_channel_layout("mono"), Some(1));
+        assert_eq!(parse_channel_layout(" stereo"), Some(2));
+        assert_eq!(parse_channel_layout("7.1"), Some(8));
+        assert_eq!(parse_channel_layout("3 channels (FL+FR+LFE)"), Some(3));
+        assert_eq!(parse_channel_layout("fltp"), None);
+    }
+
+    #[test]
+    fn test_audio_track_args() {
+        assert_eq!(audio_track_args(&[]), vec!["-vn"]);
+        assert_eq!(audio_track_args(&[2]), vec!["-map", "0:a:2"]);
+        assert_eq!(
+            audio_track_args(&[0, 2]),
+            vec![
+                "-filter_complex",
+                "[0:a:0][0:a:2]amix=inputs=2:duration=longest[mixed]",
+                "-map",
+                "[mixed]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_args_seek_before_input() {
+        let args = range_args(Path::new("audio.mp4"), 12.5, 15.25, Path::new("out.wav"));
+        assert_eq!(&args[..6], &["-ss", "12.500", "-t", "2.750", "-i", "audio.mp4"]);
+        assert_eq!(args.last().map(String::as_str), Some("out.wav"));
+    }
 }
@@ -154,11 +154,16 @@ pub async fn get_device_and_config(
 
                 #[cfg(target_os = "linux")]
                 {
-                    // For Linux, we use PulseAudio monitor sources for system audio
+                    // For Linux, we use PulseAudio monitor sources for system audio.
+                    // Monitor sources are listed with a "(System Audio)" suffix.
+                    let monitor_name = audio_device
+                        .name
+                        .trim_end_matches(crate::audio::capture::pulse_audio::SYSTEM_AUDIO_SUFFIX)
+                        .trim();
                     if let Ok(pulse_host) = cpal::host_from_id(cpal::HostId::Alsa) {
                         for device in pulse_host.input_devices()? {
                             if let Ok(name) = device.name() {
-                                if name == audio_device.name {
+                                if name == audio_device.name || name == monitor_name {
                                     let default_config = device
                                         .default_input_config()
                                         .map_err(|e| anyhow!("Failed to get default input config: {}", e))?;
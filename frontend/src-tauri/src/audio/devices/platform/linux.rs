@@ -1,6 +1,8 @@
 use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait};
+use log::{info, warn};
 
+use crate::audio::capture::pulse_audio;
 use crate::audio::devices::configuration::{AudioDevice, DeviceType};
 
 /// Configure Linux audio devices using ALSA/PulseAudio
@@ -14,20 +16,52 @@ pub fn configure_linux_audio(host: &cpal::Host) -> Result<Vec<AudioDevice>> {
         }
     }
 
+    // Add the default sink monitor and per-application streams from the sound server.
+    // These are captured by the PulseAudio backend, no loopback device needed.
+    if pulse_audio::is_available() {
+        add_pulse_audio_devices(&mut devices);
+    }
+
     // Add PulseAudio monitor sources for system audio
     if let Ok(pulse_host) = cpal::host_from_id(cpal::HostId::Alsa) {
         for device in pulse_host.input_devices()? {
             if let Ok(name) = device.name() {
                 // Check if it's a monitor source
                 if name.contains("monitor") {
-                    devices.push(AudioDevice::new(
-                        format!("{} (System Audio)", name),
-                        DeviceType::Output
-                    ));
+                    let name = format!("{} {}", name, pulse_audio::SYSTEM_AUDIO_SUFFIX);
+                    if !devices.iter().any(|d| d.name == name) {
+                        devices.push(AudioDevice::new(name, DeviceType::Output));
+                    }
                 }
             }
         }
     }
 
     Ok(devices)
-}
\ No newline at end of file
+}
+
+/// Add sound server devices: the default sink monitor first, then one entry per application
+fn add_pulse_audio_devices(devices: &mut Vec<AudioDevice>) {
+    match pulse_audio::default_monitor_source() {
+        Ok(source) => {
+            info!("PulseAudio: default sink monitor is {}", source);
+            devices.push(AudioDevice::new(
+                format!("{} {}", source, pulse_audio::SYSTEM_AUDIO_SUFFIX),
+                DeviceType::Output,
+            ));
+        }
+        Err(e) => warn!("PulseAudio: could not resolve default sink monitor: {}", e),
+    }
+
+    match pulse_audio::list_sink_inputs() {
+        Ok(inputs) => {
+            for input in inputs {
+                let name = input.device_name();
+                if !devices.iter().any(|d| d.name == name) {
+                    devices.push(AudioDevice::new(name, DeviceType::Output));
+                }
+            }
+        }
+        Err(e) => warn!("PulseAudio: could not list application streams: {}", e),
+    }
+}
@@ -1,7 +1,10 @@
 // Audio file import module - allows importing external audio files as new meetings
 
 use crate::api::TranscriptSegment;
-use crate::audio::decoder::{decode_audio_file, decode_audio_file_with_progress};
+use crate::audio::decoder::{
+    decode_audio_file, decode_audio_file_with_progress, decode_audio_tracks_with_progress, extract_audio_tracks,
+    probe_media, AudioTrackInfo, MediaProbe,
+};
 use crate::audio::vad::get_speech_chunks_with_progress;
 use crate::config::{DEFAULT_WHISPER_MODEL, DEFAULT_PARAKEET_MODEL};
 use crate::parakeet_engine::ParakeetEngine;
@@ -18,8 +21,12 @@ use tauri_plugin_dialog::DialogExt;
 use uuid::Uuid;
 
 use super::audio_processing::create_meeting_folder;
-use super::common::{create_transcript_segments, split_segment_at_silence, write_transcripts_json};
-use super::constants::AUDIO_EXTENSIONS;
+use super::common::{
+    apply_segment_languages, create_transcript_segments, split_segment_at_silence, write_transcripts_json,
+};
+use super::constants::{AUDIO_EXTENSIONS, VIDEO_EXTENSIONS};
+use super::transcription::language::{self, SegmentLanguage};
+use super::transcription::vocabulary::{self, Vocabulary};
 use super::recording_preferences::get_default_recordings_folder;
 
 /// Global flag to track if import is in progress
@@ -68,6 +75,29 @@ pub struct AudioFileInfo {
     pub duration_seconds: f64,
     pub size_bytes: u64,
     pub format: String,
+    /// True for video containers; only the selected audio is imported
+    #[serde(default)]
+    pub has_video: bool,
+    /// Audio tracks of video/multi-track containers (empty for plain audio files)
+    #[serde(default)]
+    pub audio_tracks: Vec<AudioTrackInfo>,
+}
+
+/// Audio tracks to import from a multi-track file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrackSelection {
+    /// `AudioTrackInfo::index` values; empty imports the default track
+    pub tracks: Vec<usize>,
+    /// Transcribe every track on its own (segments labelled with the track) instead of mixing them
+    pub separate: bool,
+}
+
+/// Original video of an imported meeting, recorded in metadata.json and the meetings table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceVideo {
+    pub path: String,
+    pub audio_tracks: Vec<usize>,
+    pub separate_tracks: bool,
 }
 
 /// Progress update emitted during import
@@ -160,8 +190,27 @@ pub fn validate_audio_file(path: &Path) -> Result<AudioFileInfo> {
         .unwrap_or("Imported Audio")
         .to_string();
 
-    // Try fast metadata-only validation first
-    let duration_seconds = match extract_duration_from_metadata(path) {
+    // Video containers: list their audio tracks with ffmpeg
+    let probe = if VIDEO_EXTENSIONS.contains(&extension.as_str()) {
+        match probe_media(path) {
+            Ok(probe) => Some(probe),
+            Err(e) => {
+                warn!("Could not list media tracks: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(probe) = &probe {
+        if probe.audio_tracks.is_empty() {
+            return Err(anyhow!("No audio track found in file"));
+        }
+    }
+
+    // Try fast metadata-only validation first (ffmpeg's duration for probed containers)
+    let probed_duration = probe.as_ref().and_then(|p| p.duration_seconds);
+    let duration_seconds = match probed_duration.map(Ok).unwrap_or_else(|| extract_duration_from_metadata(path)) {
         Ok(duration) => {
             debug!(
                 "Got duration from metadata: {:.2}s (fast path)",
@@ -186,6 +235,8 @@ pub fn validate_audio_file(path: &Path) -> Result<AudioFileInfo> {
         duration_seconds,
         size_bytes,
         format: extension.to_uppercase(),
+        has_video: probe.as_ref().is_some_and(|p| p.has_video),
+        audio_tracks: probe.map(|p| p.audio_tracks).unwrap_or_default(),
     })
 }
 
@@ -251,6 +302,7 @@ fn extract_duration_from_metadata(path: &Path) -> Result<f64> {
 }
 
 /// Start import of an audio file
+#[allow(clippy::too_many_arguments)]
 pub async fn start_import<R: Runtime>(
     app: AppHandle<R>,
     source_path: String,
@@ -258,6 +310,8 @@ pub async fn start_import<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    track_selection: Option<TrackSelection>,
+    vocabulary_meeting_id: Option<String>,
 ) -> Result<ImportResult> {
     // Acquire guard - ensures flag is cleared even on panic/early return
     let _guard = ImportGuard::acquire().map_err(|e| anyhow!(e))?;
@@ -266,6 +320,8 @@ pub async fn start_import<R: Runtime>(
     IMPORT_CANCELLED.store(false, Ordering::SeqCst);
 
     let use_parakeet = provider.as_deref() == Some("parakeet");
+    // The imported meeting has no list of its own yet; it may borrow another meeting's list
+    let vocabulary = vocabulary::load_for_meeting(&app, vocabulary_meeting_id.as_deref()).await;
     let result = run_import(
         app.clone(),
         source_path,
@@ -273,8 +329,13 @@ pub async fn start_import<R: Runtime>(
         language,
         model,
         provider,
+        track_selection,
+        vocabulary,
     )
     .await;
+    if let (Ok(res), Some(source)) = (&result, vocabulary_meeting_id.as_deref()) {
+        vocabulary::adopt_for_new_meeting(&app, source, &res.meeting_id).await;
+    }
 
     // Unload the engine after the batch job (success, failure, or cancellation)
     super::common::unload_engine_after_batch(use_parakeet).await;
@@ -307,7 +368,96 @@ pub async fn start_import<R: Runtime>(
     result
 }
 
+/// One audio stream to transcribe during an import
+struct ImportAudioSource {
+    path: PathBuf,
+    /// Audio track of `path` to decode; None decodes the file's default audio
+    track: Option<usize>,
+    /// Speaker label for the segments when tracks are transcribed separately
+    label: Option<String>,
+}
+
+/// Transcription output of one audio source
+#[derive(Debug, Default)]
+struct SourceTranscript {
+    transcripts: Vec<(String, f64, f64)>,
+    languages: Vec<SegmentLanguage>,
+    speaker: Option<String>,
+    total_confidence: f32,
+    speech_segments: usize,
+    duration_seconds: f64,
+}
+
+/// Tracks to import: the user's selection (validated against the probe) or the default track.
+/// An empty result means the file is imported as a plain audio file.
+fn resolve_track_selection(probe: Option<&MediaProbe>, selection: Option<&TrackSelection>) -> Result<Vec<usize>> {
+    let requested = selection.map(|s| s.tracks.as_slice()).unwrap_or_default();
+    if requested.is_empty() {
+        return Ok(probe.and_then(|p| p.default_track()).into_iter().collect());
+    }
+
+    let probe = probe.ok_or_else(|| anyhow!("Could not read the audio tracks of this file"))?;
+    let mut tracks: Vec<usize> = Vec::new();
+    for &track in requested {
+        if track >= probe.audio_tracks.len() {
+            return Err(anyhow!(
+                "Audio track {} does not exist (file has {} audio tracks)",
+                track + 1,
+                probe.audio_tracks.len()
+            ));
+        }
+        if !tracks.contains(&track) {
+            tracks.push(track);
+        }
+    }
+    Ok(tracks)
+}
+
+/// Speaker label for a separately transcribed track: its title, else "Track N (language)"
+fn track_label(probe: Option<&MediaProbe>, track: usize) -> String {
+    let info = probe.and_then(|p| p.audio_tracks.get(track));
+    if let Some(title) = info.and_then(|t| t.title.as_deref()).filter(|t| !t.trim().is_empty()) {
+        return title.trim().to_string();
+    }
+    match info.and_then(|t| t.language.as_deref()) {
+        Some(language) => format!("Track {} ({})", track + 1, language),
+        None => format!("Track {}", track + 1),
+    }
+}
+
+/// Interleave the segments of separately transcribed tracks by start time.
+/// Returns (transcripts, languages, speakers) in matching order.
+fn merge_source_transcripts(
+    sources: Vec<SourceTranscript>,
+) -> (Vec<(String, f64, f64)>, Vec<SegmentLanguage>, Vec<Option<String>>) {
+    let mut merged: Vec<((String, f64, f64), SegmentLanguage, Option<String>)> = sources
+        .into_iter()
+        .flat_map(|source| {
+            let speaker = source.speaker;
+            source
+                .transcripts
+                .into_iter()
+                .zip(source.languages)
+                .map(move |(transcript, language)| (transcript, language, speaker.clone()))
+        })
+        .collect();
+
+    // Stable sort keeps track order for segments starting at the same time
+    merged.sort_by(|a, b| a.0 .1.partial_cmp(&b.0 .1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut transcripts = Vec::with_capacity(merged.len());
+    let mut languages = Vec::with_capacity(merged.len());
+    let mut speakers = Vec::with_capacity(merged.len());
+    for (transcript, language, speaker) in merged {
+        transcripts.push(transcript);
+        languages.push(language);
+        speakers.push(speaker);
+    }
+    (transcripts, languages, speakers)
+}
+
 /// Internal function to run import
+#[allow(clippy::too_many_arguments)]
 async fn run_import<R: Runtime>(
     app: AppHandle<R>,
     source_path: String,
@@ -315,6 +465,8 @@ async fn run_import<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    track_selection: Option<TrackSelection>,
+    vocabulary: Arc<Vocabulary>,
 ) -> Result<ImportResult> {
     let source = PathBuf::from(&source_path);
 
@@ -324,13 +476,41 @@ async fn run_import<R: Runtime>(
     }
 
     info!(
-        "Starting import for '{}' from {} with language {:?}, model {:?}, provider {:?}",
-        title, source_path, language, model, provider
+        "Starting import for '{}' from {} with language {:?}, model {:?}, provider {:?}, tracks {:?}",
+        title, source_path, language, model, provider, track_selection
     );
 
     // Determine which provider to use (default to whisper)
     let use_parakeet = provider.as_deref() == Some("parakeet");
 
+    let extension = source
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    // Video containers may hold several audio tracks; find them (and whether there is video)
+    let probe = if VIDEO_EXTENSIONS.contains(&extension.as_str()) || track_selection.is_some() {
+        let src = source.clone();
+        match tokio::task::spawn_blocking(move || probe_media(&src)).await {
+            Ok(Ok(probe)) => Some(probe),
+            Ok(Err(e)) => {
+                warn!("Could not probe media tracks ({}), importing as plain audio", e);
+                None
+            }
+            Err(e) => {
+                warn!("Media probe task failed ({}), importing as plain audio", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let selected_tracks = resolve_track_selection(probe.as_ref(), track_selection.as_ref())?;
+    let is_video = probe.as_ref().is_some_and(|p| p.has_video);
+    let explicit_tracks = track_selection.as_ref().is_some_and(|s| !s.tracks.is_empty());
+    let separate_tracks = track_selection.as_ref().is_some_and(|s| s.separate) && selected_tracks.len() > 1;
+
     emit_progress(&app, "copying", 5, "Creating meeting folder...");
 
     // Check for cancellation
@@ -342,26 +522,34 @@ async fn run_import<R: Runtime>(
     let base_folder = get_default_recordings_folder();
     let meeting_folder = create_meeting_folder(&base_folder, &title, false)?;
 
-    // Copy audio file to meeting folder
-    emit_progress(&app, "copying", 10, "Copying audio file...");
-
-    let dest_filename = format!(
-        "audio.{}",
-        source
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("mp4")
-    );
+    // Videos (and explicit track picks) keep only the selected audio, mixed into one file on
+    // the video's timeline; the video itself stays where it is and is referenced instead
+    let extract_audio = (is_video || explicit_tracks) && !selected_tracks.is_empty();
+    let dest_filename = if extract_audio {
+        "audio.flac".to_string()
+    } else {
+        format!("audio.{}", if extension.is_empty() { "mp4" } else { extension.as_str() })
+    };
     let dest_path = meeting_folder.join(&dest_filename);
 
     let src = source.clone();
     let dst = dest_path.clone();
-    tokio::task::spawn_blocking(move || std::fs::copy(&src, &dst))
-        .await
-        .map_err(|e| anyhow!("Copy task join error: {}", e))?
-        .map_err(|e| anyhow!("Failed to copy audio file: {}", e))?;
-
-    info!("Copied audio to: {}", dest_path.display());
+    if extract_audio {
+        emit_progress(&app, "copying", 10, "Extracting audio tracks...");
+        let tracks = selected_tracks.clone();
+        tokio::task::spawn_blocking(move || extract_audio_tracks(&src, &tracks, &dst))
+            .await
+            .map_err(|e| anyhow!("Extract task join error: {}", e))??;
+        info!("Extracted audio tracks {:?} to: {}", selected_tracks, dest_path.display());
+    } else {
+        // Copy audio file to meeting folder
+        emit_progress(&app, "copying", 10, "Copying audio file...");
+        tokio::task::spawn_blocking(move || std::fs::copy(&src, &dst))
+            .await
+            .map_err(|e| anyhow!("Copy task join error: {}", e))?
+            .map_err(|e| anyhow!("Failed to copy audio file: {}", e))?;
+        info!("Copied audio to: {}", dest_path.display());
+    }
 
     // Check for cancellation
     if IMPORT_CANCELLED.load(Ordering::SeqCst) {
@@ -370,34 +558,207 @@ async fn run_import<R: Runtime>(
         return Err(anyhow!("Import cancelled"));
     }
 
-    emit_progress(&app, "decoding", 15, "Decoding audio file...");
+    // Separate tracks are decoded one by one from the original file; otherwise the
+    // (possibly mixed) copy in the meeting folder is transcribed
+    let sources: Vec<ImportAudioSource> = if separate_tracks {
+        selected_tracks
+            .iter()
+            .map(|&track| ImportAudioSource {
+                path: source.clone(),
+                track: Some(track),
+                label: Some(track_label(probe.as_ref(), track)),
+            })
+            .collect()
+    } else {
+        vec![ImportAudioSource {
+            path: dest_path.clone(),
+            track: None,
+            label: None,
+        }]
+    };
+
+    // Transcription stages share 15%..80% of the overall progress, split evenly across sources
+    let source_count = sources.len() as u32;
+    let mut results: Vec<SourceTranscript> = Vec::with_capacity(sources.len());
+    for (n, audio_source) in sources.into_iter().enumerate() {
+        let n = n as u32;
+        let range = (15 + 65 * n / source_count, 15 + 65 * (n + 1) / source_count);
+        let result = transcribe_audio_source(
+            &app,
+            audio_source,
+            language.clone(),
+            model.as_deref(),
+            use_parakeet,
+            range,
+            &vocabulary,
+        )
+        .await;
+
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                if IMPORT_CANCELLED.load(Ordering::SeqCst) {
+                    let _ = std::fs::remove_dir_all(&meeting_folder);
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    let duration_seconds = results.iter().map(|r| r.duration_seconds).fold(0.0, f64::max);
+    let total_speech_segments: usize = results.iter().map(|r| r.speech_segments).sum();
+    let total_confidence: f32 = results.iter().map(|r| r.total_confidence).sum();
+
+    if total_speech_segments == 0 {
+        warn!("No speech detected in audio");
+
+        // Emit warning to frontend
+        let _ = app.emit(
+            "import-warning",
+            ImportWarning {
+                warning: "No speech detected in audio file".to_string(),
+                details: Some(
+                    "The file was imported successfully, but VAD did not detect any speech. \
+                     The meeting was created but contains no transcripts.".to_string()
+                ),
+            },
+        );
+        // Still create the meeting, just with no transcripts
+    }
+
+    let (all_transcripts, segment_languages, speakers) = merge_source_transcripts(results);
+
+    let transcribed_count = all_transcripts.len();
+    let avg_confidence = if transcribed_count > 0 {
+        total_confidence / transcribed_count as f32
+    } else {
+        0.0
+    };
+
+    info!(
+        "Transcription complete: {} segments transcribed, avg confidence: {:.2}",
+        transcribed_count, avg_confidence
+    );
+
+    // Check for cancellation
+    if IMPORT_CANCELLED.load(Ordering::SeqCst) {
+        let _ = std::fs::remove_dir_all(&meeting_folder);
+        return Err(anyhow!("Import cancelled"));
+    }
+
+    emit_progress(&app, "saving", 85, "Creating meeting...");
+
+    // Create transcript segments
+    let mut segments = create_transcript_segments(&all_transcripts);
+    apply_segment_languages(&mut segments, segment_languages);
+
+    // Reference to the original video so transcripts and subtitles can be matched to it
+    let source_video = is_video.then(|| SourceVideo {
+        path: source.to_string_lossy().to_string(),
+        audio_tracks: selected_tracks.clone(),
+        separate_tracks,
+    });
+
+    // Save to database
+    let app_state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| anyhow!("App state not available"))?;
+
+    let meeting_id = create_meeting_with_transcripts(
+        app_state.db_manager.pool(),
+        &title,
+        &segments,
+        &speakers,
+        meeting_folder.to_string_lossy().to_string(),
+        source_video.as_ref().map(|v| v.path.as_str()),
+    )
+    .await?;
+
+    // Write transcripts.json and metadata.json to the meeting folder
+    emit_progress(&app, "saving", 90, "Writing transcript files...");
+
+    if let Err(e) = write_transcripts_json(&meeting_folder, &segments) {
+        warn!("Failed to write transcripts.json: {}", e);
+    }
+
+    if let Err(e) = write_import_metadata(
+        &meeting_folder,
+        &meeting_id,
+        &title,
+        duration_seconds,
+        &dest_filename,
+        "import",
+        source_video.as_ref(),
+    ) {
+        warn!("Failed to write metadata.json: {}", e);
+    }
+
+    // The imported audio copy is plaintext; encrypt it in encrypted library mode
+    crate::encryption::files::seal_folder(&meeting_folder);
+
+    emit_progress(&app, "complete", 100, "Import complete");
+
+    Ok(ImportResult {
+        meeting_id,
+        title,
+        segments_count: segments.len(),
+        duration_seconds,
+    })
+}
+
+/// Decode, detect speech in and transcribe one audio source.
+///
+/// `progress_range` is the slice of the overall import progress this source reports into;
+/// stage widths match the single-source layout (decode 15-20%, resample 20-25%, VAD 25-30%,
+/// transcription 30-80%).
+async fn transcribe_audio_source<R: Runtime>(
+    app: &AppHandle<R>,
+    audio_source: ImportAudioSource,
+    language: Option<String>,
+    model: Option<&str>,
+    use_parakeet: bool,
+    progress_range: (u32, u32),
+    vocabulary: &Vocabulary,
+) -> Result<SourceTranscript> {
+    let (range_start, range_end) = progress_range;
+    let scale = (range_end - range_start) as f32 / 65.0;
+    // Overall progress for a point measured on the 15..80 single-source scale
+    let at = move |single_source_progress: f32| range_start + ((single_source_progress - 15.0) * scale) as u32;
+    let suffix = audio_source
+        .label
+        .as_deref()
+        .map(|label| format!(" ({})", label))
+        .unwrap_or_default();
+
+    emit_progress(app, "decoding", at(15.0), &format!("Decoding audio file{}...", suffix));
 
     // Decode the audio file with progress updates
     let app_for_decode = app.clone();
     let decode_progress = Box::new(move |progress: u32, msg: &str| {
         // Map decode progress: 15% + (progress * 0.05) to go from 15% to 20%
-        let overall_progress = 15 + ((progress as f32 * 0.05) as u32);
+        let overall_progress = at(15.0 + progress as f32 * 0.05);
         emit_progress(&app_for_decode, "decoding", overall_progress, msg);
     });
 
-    let path_for_decode = dest_path.clone();
-    let decoded = tokio::task::spawn_blocking(move || {
-        decode_audio_file_with_progress(&path_for_decode, Some(decode_progress))
+    let path_for_decode = audio_source.path.clone();
+    let track = audio_source.track;
+    let decoded = tokio::task::spawn_blocking(move || match track {
+        Some(track) => decode_audio_tracks_with_progress(&path_for_decode, &[track], Some(decode_progress)),
+        None => decode_audio_file_with_progress(&path_for_decode, Some(decode_progress)),
     })
     .await
     .map_err(|e| anyhow!("Decode task join error: {}", e))??;
     let duration_seconds = decoded.duration_seconds;
 
     info!(
-        "Decoded audio: {:.2}s, {}Hz, {} channels",
-        duration_seconds, decoded.sample_rate, decoded.channels
+        "Decoded audio{}: {:.2}s, {}Hz, {} channels",
+        suffix, duration_seconds, decoded.sample_rate, decoded.channels
     );
 
-    emit_progress(&app, "resampling", 20, "Converting audio format...");
+    emit_progress(app, "resampling", at(20.0), "Converting audio format...");
 
     // Check for cancellation
     if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        let _ = std::fs::remove_dir_all(&meeting_folder);
         return Err(anyhow!("Import cancelled"));
     }
 
@@ -405,7 +766,7 @@ async fn run_import<R: Runtime>(
     let app_for_resample = app.clone();
     let resample_progress = Box::new(move |progress: u32, msg: &str| {
         // Map resample progress: 20% + (progress * 0.05) to go from 20% to 25%
-        let overall_progress = 20 + ((progress as f32 * 0.05) as u32);
+        let overall_progress = at(20.0 + progress as f32 * 0.05);
         emit_progress(&app_for_resample, "resampling", overall_progress, msg);
     });
 
@@ -419,11 +780,10 @@ async fn run_import<R: Runtime>(
         audio_samples.len()
     );
 
-    emit_progress(&app, "vad", 25, "Detecting speech segments...");
+    emit_progress(app, "vad", at(25.0), &format!("Detecting speech segments{}...", suffix));
 
     // Check for cancellation
     if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        let _ = std::fs::remove_dir_all(&meeting_folder);
         return Err(anyhow!("Import cancelled"));
     }
 
@@ -435,7 +795,7 @@ async fn run_import<R: Runtime>(
             &audio_samples,
             VAD_REDEMPTION_TIME_MS,
             |vad_progress, segments_found| {
-                let overall_progress = 25 + (vad_progress as f32 * 0.05) as u32;
+                let overall_progress = at(25.0 + vad_progress as f32 * 0.05);
                 emit_progress(
                     &app_for_vad,
                     "vad",
@@ -482,39 +842,21 @@ async fn run_import<R: Runtime>(
         }
     }
 
-    if total_segments == 0 {
-        warn!("No speech detected in audio");
-
-        // Emit warning to frontend
-        let _ = app.emit(
-            "import-warning",
-            ImportWarning {
-                warning: "No speech detected in audio file".to_string(),
-                details: Some(
-                    "The file was imported successfully, but VAD did not detect any speech. \
-                     The meeting was created but contains no transcripts.".to_string()
-                ),
-            },
-        );
-        // Still create the meeting, just with no transcripts
-    }
-
     // Check for cancellation
     if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        let _ = std::fs::remove_dir_all(&meeting_folder);
         return Err(anyhow!("Import cancelled"));
     }
 
-    emit_progress(&app, "transcribing", 30, "Loading transcription engine...");
+    emit_progress(app, "transcribing", at(30.0), "Loading transcription engine...");
 
     // Initialize the appropriate engine
     let whisper_engine = if !use_parakeet && total_segments > 0 {
-        Some(get_or_init_whisper(&app, model.as_deref()).await?)
+        Some(get_or_init_whisper(app, model).await?)
     } else {
         None
     };
     let parakeet_engine = if use_parakeet && total_segments > 0 {
-        Some(get_or_init_parakeet(&app, model.as_deref()).await?)
+        Some(get_or_init_parakeet(app, model).await?)
     } else {
         None
     };
@@ -546,25 +888,30 @@ async fn run_import<R: Runtime>(
 
     // Process each speech segment
     let mut all_transcripts: Vec<(String, f64, f64)> = Vec::new();
+    let mut segment_languages: Vec<SegmentLanguage> = Vec::new();
     let mut total_confidence = 0.0f32;
 
+    // Code-switched recordings: identify each segment's language among the configured candidates
+    let detection = language::load_settings(app);
+    let per_segment_language = !use_parakeet && detection.applies_to(language.as_deref());
+
     for (i, segment) in processable_segments.iter().enumerate() {
         if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-            let _ = std::fs::remove_dir_all(&meeting_folder);
             return Err(anyhow!("Import cancelled"));
         }
 
-        let progress = 30 + ((i as f32 / processable_count.max(1) as f32) * 50.0) as u32;
+        let progress = at(30.0 + (i as f32 / processable_count.max(1) as f32) * 50.0);
         let segment_duration_sec = (segment.end_timestamp_ms - segment.start_timestamp_ms) / 1000.0;
         emit_progress(
-            &app,
+            app,
             "transcribing",
             progress,
             &format!(
-                "Transcribing segment {} of {} ({:.1}s)...",
+                "Transcribing segment {} of {} ({:.1}s){}...",
                 i + 1,
                 processable_count,
-                segment_duration_sec
+                segment_duration_sec,
+                suffix
             ),
         );
 
@@ -579,22 +926,36 @@ async fn run_import<R: Runtime>(
         }
 
         // Transcribe
-        let (text, conf) = if use_parakeet {
+        let (text, conf, segment_language) = if use_parakeet {
             let engine = parakeet_engine.as_ref().unwrap();
             let text = engine
                 .transcribe_audio(segment.samples.clone())
                 .await
                 .map_err(|e| anyhow!("Parakeet transcription failed on segment {}: {}", i, e))?;
-            (text, 0.9f32)
+            (text, 0.9f32, SegmentLanguage::default())
+        } else if per_segment_language {
+            let engine = whisper_engine.as_ref().unwrap();
+            let result = engine
+                .transcribe_multilingual(
+                    segment.samples.clone(),
+                    &detection.candidates,
+                    detection.store_translation,
+                    None,
+                    vocabulary,
+                )
+                .await
+                .map_err(|e| anyhow!("Whisper transcription failed on segment {}: {}", i, e))?;
+            (result.text, result.confidence, result.language)
         } else {
             let engine = whisper_engine.as_ref().unwrap();
             let (text, conf, _) = engine
-                .transcribe_audio_with_confidence(segment.samples.clone(), language.clone())
+                .transcribe_audio_with_confidence(segment.samples.clone(), language.clone(), vocabulary)
                 .await
                 .map_err(|e| anyhow!("Whisper transcription failed on segment {}: {}", i, e))?;
-            (text, conf)
+            (text, conf, SegmentLanguage::default())
         };
 
+        let text = vocabulary.correct(&text);
         let trimmed = text.trim();
         if !trimmed.is_empty() {
             debug!(
@@ -603,72 +964,26 @@ async fn run_import<R: Runtime>(
                 if trimmed.len() > 80 { let mut end = 80; while !trimmed.is_char_boundary(end) { end -= 1; } &trimmed[..end] } else { trimmed }
             );
             all_transcripts.push((text, segment.start_timestamp_ms, segment.end_timestamp_ms));
+            segment_languages.push(segment_language);
             total_confidence += conf;
         } else {
             debug!("Segment {}/{}: {:.1}s — empty transcription", i + 1, processable_count, segment_duration_sec);
         }
     }
 
-    let transcribed_count = all_transcripts.len();
-    let avg_confidence = if transcribed_count > 0 {
-        total_confidence / transcribed_count as f32
-    } else {
-        0.0
-    };
-
     info!(
-        "Transcription complete: {} segments transcribed out of {}, avg confidence: {:.2}",
-        transcribed_count, processable_count, avg_confidence
+        "Transcribed {} of {} segments{}",
+        all_transcripts.len(),
+        processable_count,
+        suffix
     );
 
-    // Check for cancellation
-    if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        let _ = std::fs::remove_dir_all(&meeting_folder);
-        return Err(anyhow!("Import cancelled"));
-    }
-
-    emit_progress(&app, "saving", 85, "Creating meeting...");
-
-    // Create transcript segments
-    let segments = create_transcript_segments(&all_transcripts);
-
-    // Save to database
-    let app_state = app
-        .try_state::<AppState>()
-        .ok_or_else(|| anyhow!("App state not available"))?;
-
-    let meeting_id = create_meeting_with_transcripts(
-        app_state.db_manager.pool(),
-        &title,
-        &segments,
-        meeting_folder.to_string_lossy().to_string(),
-    )
-    .await?;
-
-    // Write transcripts.json and metadata.json to the meeting folder
-    emit_progress(&app, "saving", 90, "Writing transcript files...");
-
-    if let Err(e) = write_transcripts_json(&meeting_folder, &segments) {
-        warn!("Failed to write transcripts.json: {}", e);
-    }
-
-    if let Err(e) = write_import_metadata(
-        &meeting_folder,
-        &meeting_id,
-        &title,
-        duration_seconds,
-        &dest_filename,
-        "import",
-    ) {
-        warn!("Failed to write metadata.json: {}", e);
-    }
-
-    emit_progress(&app, "complete", 100, "Import complete");
-
-    Ok(ImportResult {
-        meeting_id,
-        title,
-        segments_count: segments.len(),
+    Ok(SourceTranscript {
+        transcripts: all_transcripts,
+        languages: segment_languages,
+        speaker: audio_source.label,
+        total_confidence,
+        speech_segments: total_segments,
         duration_seconds,
     })
 }
@@ -691,7 +1006,9 @@ async fn create_meeting_with_transcripts(
     pool: &sqlx::SqlitePool,
     title: &str,
     segments: &[TranscriptSegment],
+    speakers: &[Option<String>],
     folder_path: String,
+    source_video_path: Option<&str>,
 ) -> Result<String> {
     let meeting_id = format!("meeting-{}", Uuid::new_v4());
     let now = chrono::Utc::now();
@@ -704,23 +1021,24 @@ async fn create_meeting_with_transcripts(
 
     // Insert meeting
     sqlx::query(
-        "INSERT INTO meetings (id, title, created_at, updated_at, folder_path)
-         VALUES (?, ?, ?, ?, ?)",
+        "INSERT INTO meetings (id, title, created_at, updated_at, folder_path, source_video_path)
+         VALUES (?, ?, ?, ?, ?, ?)",
     )
     .bind(&meeting_id)
     .bind(title)
     .bind(now)
     .bind(now)
     .bind(&folder_path)
+    .bind(source_video_path)
     .execute(&mut *tx)
     .await
     .map_err(|e| anyhow!("Failed to create meeting: {}", e))?;
 
     // Insert transcripts
-    for segment in segments {
+    for (i, segment) in segments.iter().enumerate() {
         sqlx::query(
-            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, language, translation, speaker)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&segment.id)
         .bind(&meeting_id)
@@ -729,6 +1047,9 @@ async fn create_meeting_with_transcripts(
         .bind(segment.audio_start_time)
         .bind(segment.audio_end_time)
         .bind(segment.duration)
+        .bind(&segment.language)
+        .bind(&segment.translation)
+        .bind(speakers.get(i).cloned().flatten())
         .execute(&mut *tx)
         .await
         .map_err(|e| anyhow!("Failed to insert transcript: {}", e))?;
@@ -883,9 +1204,9 @@ fn write_import_metadata(
     duration_seconds: f64,
     audio_filename: &str,
     source: &str,
+    source_video: Option<&SourceVideo>,
 ) -> Result<()> {
     let metadata_path = folder.join("metadata.json");
-    let temp_path = folder.join(".metadata.json.tmp");
     let now = chrono::Utc::now().to_rfc3339();
 
     let json = serde_json::json!({
@@ -898,12 +1219,12 @@ fn write_import_metadata(
         "audio_file": audio_filename,
         "transcript_file": "transcripts.json",
         "status": "completed",
-        "source": source
+        "source": source,
+        "source_video": source_video
     });
 
     let json_string = serde_json::to_string_pretty(&json)?;
-    std::fs::write(&temp_path, &json_string)?;
-    std::fs::rename(&temp_path, &metadata_path)?;
+    crate::encryption::files::write_file(&metadata_path, json_string.as_bytes()).map_err(|e| anyhow!(e))?;
 
     info!("Wrote metadata.json to {}", metadata_path.display());
     Ok(())
@@ -926,7 +1247,7 @@ pub async fn select_and_validate_audio_command<R: Runtime>(
         app_clone
             .dialog()
             .file()
-            .add_filter("Audio Files", &AUDIO_EXTENSIONS.iter().map(|s| *s).collect::<Vec<_>>())
+            .add_filter("Audio and Video Files", &AUDIO_EXTENSIONS.iter().map(|s| *s).collect::<Vec<_>>())
             .blocking_pick_file()
     })
     .await
@@ -968,15 +1289,34 @@ pub async fn start_import_audio_command<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    audio_tracks: Option<Vec<usize>>,
+    separate_tracks: Option<bool>,
+    vocabulary_meeting_id: Option<String>,
 ) -> Result<ImportStarted, String> {
     // Check if import is already in progress (guard will be acquired in start_import)
     if IMPORT_IN_PROGRESS.load(Ordering::SeqCst) {
         return Err("Import already in progress".to_string());
     }
 
+    // Only multi-track (video) files come with a track selection
+    let track_selection = audio_tracks.map(|tracks| TrackSelection {
+        tracks,
+        separate: separate_tracks.unwrap_or(false),
+    });
+
     // Spawn import in background
     tauri::async_runtime::spawn(async move {
-        let result = start_import(app, source_path, title, language, model, provider).await;
+        let result = start_import(
+            app,
+            source_path,
+            title,
+            language,
+            model,
+            provider,
+            track_selection,
+            vocabulary_meeting_id,
+        )
+        .await;
 
         if let Err(e) = result {
             error!("Import failed: {}", e);
@@ -1004,6 +1344,21 @@ pub async fn is_import_in_progress_command() -> bool {
     is_import_in_progress()
 }
 
+/// Original video a meeting was imported from, if any
+#[tauri::command]
+pub async fn get_meeting_source_video(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<String>, String> {
+    let path: Option<Option<String>> =
+        sqlx::query_scalar("SELECT source_video_path FROM meetings WHERE id = ?")
+            .bind(&meeting_id)
+            .fetch_optional(state.db_manager.pool())
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+    Ok(path.flatten())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1034,6 +1389,75 @@ mod tests {
         assert_eq!(segments[0].audio_end_time, Some(1.5));
     }
 
+    fn probe_with_tracks() -> MediaProbe {
+        let track = |index: usize, language: Option<&str>, title: Option<&str>, is_default: bool| AudioTrackInfo {
+            index,
+            codec: "aac".to_string(),
+            language: language.map(String::from),
+            title: title.map(String::from),
+            channels: 2,
+            sample_rate: Some(48000),
+            duration_seconds: Some(60.0),
+            is_default,
+        };
+        MediaProbe {
+            duration_seconds: Some(60.0),
+            has_video: true,
+            audio_tracks: vec![
+                track(0, Some("eng"), Some("Microphone"), false),
+                track(1, Some("deu"), None, true),
+                track(2, None, None, false),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_resolve_track_selection() {
+        let probe = probe_with_tracks();
+
+        // No selection: the default-flagged track; plain audio files: none
+        assert_eq!(resolve_track_selection(Some(&probe), None).unwrap(), vec![1]);
+        assert!(resolve_track_selection(None, None).unwrap().is_empty());
+
+        let selection = TrackSelection { tracks: vec![2, 0, 2], separate: true };
+        assert_eq!(resolve_track_selection(Some(&probe), Some(&selection)).unwrap(), vec![2, 0]);
+
+        let out_of_range = TrackSelection { tracks: vec![3], separate: false };
+        assert!(resolve_track_selection(Some(&probe), Some(&out_of_range)).is_err());
+        assert!(resolve_track_selection(None, Some(&selection)).is_err());
+    }
+
+    #[test]
+    fn test_track_label() {
+        let probe = probe_with_tracks();
+        assert_eq!(track_label(Some(&probe), 0), "Microphone");
+        assert_eq!(track_label(Some(&probe), 1), "Track 2 (deu)");
+        assert_eq!(track_label(Some(&probe), 2), "Track 3");
+        assert_eq!(track_label(None, 4), "Track 5");
+    }
+
+    #[test]
+    fn test_merge_source_transcripts_interleaves_by_start() {
+        let source = |speaker: &str, starts: &[f64]| SourceTranscript {
+            transcripts: starts
+                .iter()
+                .map(|start| (format!("{} at {}", speaker, start), *start, start + 500.0))
+                .collect(),
+            languages: starts.iter().map(|_| SegmentLanguage::default()).collect(),
+            speaker: Some(speaker.to_string()),
+            ..Default::default()
+        };
+
+        let (transcripts, languages, speakers) =
+            merge_source_transcripts(vec![source("mic", &[0.0, 4000.0]), source("system", &[1000.0, 4000.0])]);
+
+        let starts: Vec<f64> = transcripts.iter().map(|t| t.1).collect();
+        assert_eq!(starts, vec![0.0, 1000.0, 4000.0, 4000.0]);
+        assert_eq!(languages.len(), 4);
+        let speakers: Vec<&str> = speakers.iter().map(|s| s.as_deref().unwrap()).collect();
+        assert_eq!(speakers, vec!["mic", "system", "mic", "system"]);
+    }
+
     #[test]
     fn test_cancellation_flag() {
         IMPORT_CANCELLED.store(false, Ordering::SeqCst);
@@ -1181,6 +1605,8 @@ mod tests {
                 audio_start_time: Some(0.0),
                 audio_end_time: Some(1.5),
                 duration: Some(1.5),
+                language: None,
+                translation: None,
             },
             TranscriptSegment {
                 id: "t-2".to_string(),
@@ -1189,6 +1615,8 @@ mod tests {
                 audio_start_time: Some(2.0),
                 audio_end_time: Some(3.5),
                 duration: Some(1.5),
+                language: None,
+                translation: None,
             },
         ];
 
@@ -1223,6 +1651,7 @@ mod tests {
             1800.0,
             "audio.mp4",
             "import",
+            None,
         );
         assert!(result.is_ok(), "write_import_metadata failed: {:?}", result);
 
@@ -0,0 +1,623 @@
+// Persistent import queue fed by watched folders
+//
+// Recordings from other devices (Zoom cloud downloads, dictaphones, phones) are dropped into
+// shared or cloud-synced folders. Those folders are polled rather than watched with file system
+// events, which network and sync clients do not deliver reliably. A file is queued once its
+// size and modification time stop changing between two scans, deduped by SHA-256 of its
+// content, and imported through `import::start_import` one at a time. The queue lives in the
+// database so pending and failed items survive restarts.
+
+use crate::database::models::{ImportFolderOptions, ImportQueueItem, ImportWatchFolder};
+use crate::database::repositories::{
+    import_queue::ImportQueueRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
+    transcript::TranscriptsRepository, transcript_chunk::TranscriptChunksRepository,
+};
+use crate::state::AppState;
+use crate::summary::SummaryService;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::Notify;
+
+use super::constants::AUDIO_EXTENSIONS;
+use super::import::{self, is_import_in_progress};
+use super::retranscription::is_retranscription_in_progress;
+
+/// How often watched folders are scanned and the queue is checked
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Failed imports are retried this many times in total before the item is marked failed
+const MAX_ATTEMPTS: i64 = 3;
+
+/// First retry delay; doubles with every further attempt
+const RETRY_BASE_DELAY_SECS: i64 = 60;
+
+/// Delay before retrying an item that could not start because the engine was busy
+const BUSY_RETRY_DELAY_SECS: i64 = 30;
+
+/// Number of items returned by get_import_queue when no limit is given
+const DEFAULT_LIST_LIMIT: i64 = 200;
+
+static QUEUE_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Wakes the queue loop early (folder added, files enqueued, item retried)
+static WAKE: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// Size and modification time of a file, used to tell when a copy has finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(FileStamp {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+/// What the scanner knows about a file between polls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScanState {
+    /// Seen once with this stamp; queued if unchanged at the next scan
+    Settling(FileStamp),
+    /// Hashed and handed to the queue (or found to be a duplicate) at this stamp
+    Handled(FileStamp),
+}
+
+/// Starts the background queue loop once per app run
+pub fn start_import_queue<R: Runtime>(app: AppHandle<R>) {
+    if QUEUE_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let Some(pool) = app.try_state::<AppState>().map(|state| state.db_manager.pool().clone()) else {
+            error!("Import queue not started: app state unavailable");
+            QUEUE_STARTED.store(false, Ordering::SeqCst);
+            return;
+        };
+
+        match ImportQueueRepository::requeue_interrupted(&pool).await {
+            Ok(0) => {}
+            Ok(count) => info!("Requeued {} import(s) interrupted by the last shutdown", count),
+            Err(e) => warn!("Failed to requeue interrupted imports: {}", e),
+        }
+
+        info!("Import queue started (poll interval {:?})", POLL_INTERVAL);
+        let mut scanned: HashMap<PathBuf, ScanState> = HashMap::new();
+        loop {
+            scan_watch_folders(&app, &pool, &mut scanned).await;
+            process_ready_items(&app, &pool).await;
+
+            tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                _ = WAKE.notified() => {}
+            }
+        }
+    });
+}
+
+/// Audio files below `dir` (recursively), skipping hidden files and directories
+fn collect_audio_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let visible = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| !name.starts_with('.'));
+            if !visible {
+                continue;
+            }
+
+            if path.is_dir() {
+                pending.push(path);
+            } else if is_audio_file(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// SHA-256 of the file content as lowercase hex
+fn hash_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+async fn hash_file_async(path: PathBuf) -> Result<String> {
+    tokio::task::spawn_blocking(move || hash_file(&path))
+        .await
+        .map_err(|e| anyhow!("Hash task join error: {}", e))?
+        .map_err(|e| anyhow!("Failed to read file: {}", e))
+}
+
+/// Delay before the next attempt after `attempts` failed ones, or None once they are used up
+fn retry_delay(attempts: i64) -> Option<ChronoDuration> {
+    if attempts >= MAX_ATTEMPTS {
+        return None;
+    }
+    let exponent = (attempts - 1).clamp(0, 10) as u32;
+    Some(ChronoDuration::seconds(RETRY_BASE_DELAY_SECS * 2i64.pow(exponent)))
+}
+
+/// Meeting title for an imported file: its name without extension
+fn title_for(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(|stem| stem.trim().to_string())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| "Imported recording".to_string())
+}
+
+async fn scan_watch_folders<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    scanned: &mut HashMap<PathBuf, ScanState>,
+) {
+    let folders = match ImportQueueRepository::list_watch_folders(pool).await {
+        Ok(folders) => folders,
+        Err(e) => {
+            warn!("Failed to load watched import folders: {}", e);
+            return;
+        }
+    };
+
+    let mut queued_any = false;
+    for folder in folders.iter().filter(|f| f.enabled) {
+        let root = PathBuf::from(&folder.path);
+        if !root.is_dir() {
+            // Unmounted network share or sync folder; try again at the next scan
+            continue;
+        }
+
+        let files = tokio::task::spawn_blocking(move || collect_audio_files(&root))
+            .await
+            .unwrap_or_default();
+
+        for path in files {
+            let Some(stamp) = FileStamp::of(&path) else {
+                continue;
+            };
+
+            match scanned.get(&path) {
+                Some(ScanState::Handled(previous)) if *previous == stamp => continue,
+                Some(ScanState::Settling(previous)) if *previous == stamp => {}
+                _ => {
+                    // New or still being written; wait for it to settle
+                    scanned.insert(path, ScanState::Settling(stamp));
+                    continue;
+                }
+            }
+
+            match enqueue_file(pool, &path, Some(&folder.id)).await {
+                Ok(Some(_)) => queued_any = true,
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Failed to queue {}: {}", path.display(), e);
+                    continue;
+                }
+            }
+            scanned.insert(path, ScanState::Handled(stamp));
+        }
+    }
+
+    // Forget files that disappeared so the map does not grow forever
+    scanned.retain(|path, _| path.exists());
+
+    if queued_any {
+        emit_queue_updated(app);
+    }
+}
+
+/// Hashes and queues a file; None means the same content was queued before
+async fn enqueue_file(
+    pool: &SqlitePool,
+    path: &Path,
+    watch_folder_id: Option<&str>,
+) -> Result<Option<ImportQueueItem>> {
+    let hash = hash_file_async(path.to_path_buf()).await?;
+    let item = ImportQueueRepository::enqueue(pool, &path.to_string_lossy(), &hash, watch_folder_id).await?;
+    Ok(item)
+}
+
+/// Imports ready items one after another until the queue is empty or the engine is needed elsewhere
+async fn process_ready_items<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool) {
+    loop {
+        // Live recordings and user-started jobs take priority over the queue
+        if super::recording_commands::is_recording().await
+            || is_import_in_progress()
+            || is_retranscription_in_progress()
+        {
+            return;
+        }
+
+        let item = match ImportQueueRepository::next_ready(pool).await {
+            Ok(Some(item)) => item,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Failed to read import queue: {}", e);
+                return;
+            }
+        };
+
+        process_item(app, pool, item).await;
+    }
+}
+
+async fn process_item<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, item: ImportQueueItem) {
+    let source = PathBuf::from(&item.source_path);
+    if !source.is_file() {
+        warn!("Queued import {} no longer exists", item.source_path);
+        if let Err(e) = ImportQueueRepository::mark_attempt_failed(pool, &item.id, "File no longer exists", None).await {
+            warn!("Failed to update import queue item {}: {}", item.id, e);
+        }
+        emit_queue_updated(app);
+        return;
+    }
+
+    let folder = match item.watch_folder_id.as_deref() {
+        Some(id) => ImportQueueRepository::get_watch_folder(pool, id).await.ok().flatten(),
+        None => None,
+    };
+    let options = folder.as_ref().map(|f| f.options.clone()).unwrap_or_default();
+
+    if let Err(e) = ImportQueueRepository::mark_processing(pool, &item.id).await {
+        warn!("Failed to mark import {} as processing: {}", item.id, e);
+        return;
+    }
+    emit_queue_updated(app);
+
+    info!(
+        "Importing queued file {} (attempt {}/{})",
+        item.source_path,
+        item.attempts + 1,
+        MAX_ATTEMPTS
+    );
+    let result = import::start_import(
+        app.clone(),
+        item.source_path.clone(),
+        title_for(&source),
+        options.language.clone().or_else(crate::get_language_preference_internal),
+        options.transcription_model.clone(),
+        options.transcription_provider.clone(),
+        None,
+        None,
+    )
+    .await;
+
+    match result {
+        Ok(imported) => {
+            // Tag the meeting with the watched folder it came from (or the file's directory)
+            let import_source = folder.as_ref().map(|f| f.path.clone()).unwrap_or_else(|| {
+                source
+                    .parent()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|| item.source_path.clone())
+            });
+            if let Err(e) =
+                ImportQueueRepository::set_meeting_import_source(pool, &imported.meeting_id, &import_source).await
+            {
+                warn!("Failed to tag meeting {} with its import source: {}", imported.meeting_id, e);
+            }
+            if let Err(e) = ImportQueueRepository::mark_completed(pool, &item.id, &imported.meeting_id).await {
+                warn!("Failed to mark import {} as completed: {}", item.id, e);
+            }
+            emit_queue_updated(app);
+            info!("Queued import {} created meeting {}", item.source_path, imported.meeting_id);
+
+            if let Some(template_id) = options.template_id.as_deref() {
+                if let Err(e) = summarize_import(app, pool, &imported.meeting_id, &options, template_id).await {
+                    warn!("Failed to summarize imported meeting {}: {}", imported.meeting_id, e);
+                }
+            }
+        }
+        Err(e) => {
+            let message = e.to_string();
+            let update = if message.contains("already in progress") {
+                // Someone started an import between our check and the guard; not a real failure
+                ImportQueueRepository::defer(pool, &item.id, Utc::now() + ChronoDuration::seconds(BUSY_RETRY_DELAY_SECS))
+                    .await
+            } else {
+                let attempts = item.attempts + 1;
+                // A user cancelling the import from the UI should not trigger a retry
+                let retry_at: Option<DateTime<Utc>> = if message.contains("cancelled") {
+                    None
+                } else {
+                    retry_delay(attempts).map(|delay| Utc::now() + delay)
+                };
+                error!(
+                    "Queued import {} failed (attempt {}/{}): {}",
+                    item.source_path, attempts, MAX_ATTEMPTS, message
+                );
+                ImportQueueRepository::mark_attempt_failed(pool, &item.id, &message, retry_at).await
+            };
+            if let Err(e) = update {
+                warn!("Failed to update import queue item {}: {}", item.id, e);
+            }
+            emit_queue_updated(app);
+        }
+    }
+}
+
+/// Generates a summary for an imported meeting with the folder's template. Runs inline so
+/// the queue keeps processing one recording at a time.
+async fn summarize_import<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    options: &ImportFolderOptions,
+    template_id: &str,
+) -> Result<()> {
+    let (provider, model) = match (options.summary_provider.clone(), options.summary_model.clone()) {
+        (Some(provider), Some(model)) => (provider, model),
+        _ => {
+            let config = SettingsRepository::get_model_config(pool)
+                .await?
+                .ok_or_else(|| anyhow!("No summary model configured"))?;
+            (config.provider, config.model)
+        }
+    };
+
+    let text = TranscriptsRepository::get_meeting_text(pool, meeting_id, false).await?;
+    if text.trim().is_empty() {
+        info!("Imported meeting {} has no transcript, skipping summary", meeting_id);
+        return Ok(());
+    }
+
+    info!(
+        "Summarizing imported meeting {} with template '{}' ({} / {})",
+        meeting_id, template_id, provider, model
+    );
+    SummaryProcessesRepository::create_or_reset_process(pool, meeting_id).await?;
+    TranscriptChunksRepository::save_transcript_data(pool, meeting_id, &text, &provider, &model, 40000, 1000)
+        .await?;
+    SummaryService::process_transcript_background(
+        app.clone(),
+        pool.clone(),
+        meeting_id.to_string(),
+        text,
+        provider,
+        model,
+        String::new(),
+        template_id.to_string(),
+        None,
+    )
+    .await;
+    Ok(())
+}
+
+fn emit_queue_updated<R: Runtime>(app: &AppHandle<R>) {
+    let _ = app.emit("import-queue-updated", ());
+}
+
+fn pool_from(state: &tauri::State<'_, AppState>) -> SqlitePool {
+    state.db_manager.pool().clone()
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub async fn get_import_watch_folders(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ImportWatchFolder>, String> {
+    ImportQueueRepository::list_watch_folders(&pool_from(&state))
+        .await
+        .map_err(|e| format!("Failed to load watched folders: {}", e))
+}
+
+/// Watches a folder for new recordings, or updates the options of an already watched one
+#[tauri::command]
+pub async fn add_import_watch_folder<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    options: Option<ImportFolderOptions>,
+) -> Result<ImportWatchFolder, String> {
+    let dir = PathBuf::from(path.trim());
+    if !dir.is_dir() {
+        return Err(format!("Not a directory: {}", dir.display()));
+    }
+    let dir = dir.canonicalize().unwrap_or(dir);
+
+    let folder = ImportQueueRepository::upsert_watch_folder(
+        &pool_from(&state),
+        &dir.to_string_lossy(),
+        &options.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| format!("Failed to add watched folder: {}", e))?;
+
+    start_import_queue(app);
+    WAKE.notify_one();
+    Ok(folder)
+}
+
+#[tauri::command]
+pub async fn set_import_watch_folder_enabled(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let updated = ImportQueueRepository::set_watch_folder_enabled(&pool_from(&state), &id, enabled)
+        .await
+        .map_err(|e| format!("Failed to update watched folder: {}", e))?;
+    if !updated {
+        return Err(format!("Watched folder not found: {}", id));
+    }
+    if enabled {
+        WAKE.notify_one();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_import_watch_folder(state: tauri::State<'_, AppState>, id: String) -> Result<bool, String> {
+    ImportQueueRepository::remove_watch_folder(&pool_from(&state), &id)
+        .await
+        .map_err(|e| format!("Failed to remove watched folder: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_import_queue(
+    state: tauri::State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<ImportQueueItem>, String> {
+    ImportQueueRepository::list_items(&pool_from(&state), limit.unwrap_or(DEFAULT_LIST_LIMIT))
+        .await
+        .map_err(|e| format!("Failed to load import queue: {}", e))
+}
+
+/// Adds files to the queue by hand; returns the newly queued items (duplicates are skipped)
+#[tauri::command]
+pub async fn enqueue_import_files<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    paths: Vec<String>,
+) -> Result<Vec<ImportQueueItem>, String> {
+    let pool = pool_from(&state);
+    let mut queued = Vec::new();
+
+    for path in paths {
+        let path = PathBuf::from(path);
+        if !path.is_file() {
+            return Err(format!("File does not exist: {}", path.display()));
+        }
+        if !is_audio_file(&path) {
+            return Err(format!("Unsupported file format: {}", path.display()));
+        }
+        match enqueue_file(&pool, &path, None).await {
+            Ok(Some(item)) => queued.push(item),
+            Ok(None) => info!("{} is already in the import queue", path.display()),
+            Err(e) => return Err(format!("Failed to queue {}: {}", path.display(), e)),
+        }
+    }
+
+    start_import_queue(app.clone());
+    emit_queue_updated(&app);
+    WAKE.notify_one();
+    Ok(queued)
+}
+
+#[tauri::command]
+pub async fn retry_import_queue_item(state: tauri::State<'_, AppState>, id: String) -> Result<(), String> {
+    let retried = ImportQueueRepository::retry_item(&pool_from(&state), &id)
+        .await
+        .map_err(|e| format!("Failed to retry import: {}", e))?;
+    if !retried {
+        return Err("Only failed imports can be retried".to_string());
+    }
+    WAKE.notify_one();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_import_queue_item(state: tauri::State<'_, AppState>, id: String) -> Result<bool, String> {
+    ImportQueueRepository::remove_item(&pool_from(&state), &id)
+        .await
+        .map_err(|e| format!("Failed to remove import: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_meeting_import_source(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<String>, String> {
+    ImportQueueRepository::get_meeting_import_source(&pool_from(&state), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load import source: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collect_audio_files_recurses_and_filters() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("call.M4A"), b"a").unwrap();
+        fs::write(dir.path().join("notes.txt"), b"b").unwrap();
+        fs::write(dir.path().join(".partial.mp3"), b"c").unwrap();
+        fs::create_dir(dir.path().join("dictaphone")).unwrap();
+        fs::write(dir.path().join("dictaphone").join("memo.wav"), b"d").unwrap();
+        fs::create_dir(dir.path().join(".sync")).unwrap();
+        fs::write(dir.path().join(".sync").join("cache.mp3"), b"e").unwrap();
+
+        let files = collect_audio_files(dir.path());
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.strip_prefix(dir.path()).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(names, vec!["call.M4A".to_string(), "dictaphone/memo.wav".to_string()]);
+    }
+
+    #[test]
+    fn test_hash_file_matches_for_identical_content() {
+        let dir = TempDir::new().unwrap();
+        let a = dir.path().join("a.mp3");
+        let b = dir.path().join("b.mp3");
+        let c = dir.path().join("c.mp3");
+        fs::write(&a, b"same recording").unwrap();
+        fs::write(&b, b"same recording").unwrap();
+        fs::write(&c, b"other recording").unwrap();
+
+        let hash = hash_file(&a).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_file(&b).unwrap());
+        assert_ne!(hash, hash_file(&c).unwrap());
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_then_gives_up() {
+        assert_eq!(retry_delay(1), Some(ChronoDuration::seconds(60)));
+        assert_eq!(retry_delay(2), Some(ChronoDuration::seconds(120)));
+        assert_eq!(retry_delay(MAX_ATTEMPTS), None);
+    }
+
+    #[test]
+    fn test_title_for() {
+        assert_eq!(title_for(Path::new("/shared/Zoom Weekly Sync.m4a")), "Zoom Weekly Sync");
+        assert_eq!(title_for(Path::new("/shared/ .wav")), "Imported recording");
+    }
+}
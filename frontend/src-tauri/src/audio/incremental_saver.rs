@@ -6,6 +6,7 @@ use super::recording_state::AudioChunk;
 use serde::{Serialize, Deserialize};
 
 use super::ffmpeg::find_ffmpeg_path;
+use crate::encryption::files as library_files;
 
 /// Audio data without device type (we only store mixed audio)
 #[derive(Clone)]
@@ -92,13 +93,14 @@ impl IncrementalAudioSaver {
         let checkpoint_path = self.checkpoints_dir
             .join(format!("audio_chunk_{:03}.mp4", self.checkpoint_count));
 
-        // Encode and save checkpoint
+        // Encode and save checkpoint (encrypted right away in encrypted library mode)
         encode_single_audio(
             bytemuck::cast_slice(&audio_data),
             self.sample_rate,
             1,  // mono
             &checkpoint_path
         )?;
+        library_files::seal_file(&checkpoint_path).map_err(|e| anyhow!(e))?;
 
         let duration_seconds = audio_data.len() as f32 / self.sample_rate as f32;
         self.checkpoint_count += 1;
@@ -129,8 +131,9 @@ impl IncrementalAudioSaver {
         }
 
         // Merge all checkpoints using FFmpeg concat
-        let final_audio_path = self.meeting_folder.join("audio.mp4");
-        self.merge_checkpoints(&final_audio_path).await?;
+        let merged_audio_path = self.meeting_folder.join("audio.mp4");
+        self.merge_checkpoints(&merged_audio_path).await?;
+        let final_audio_path = library_files::seal_file(&merged_audio_path).map_err(|e| anyhow!(e))?;
 
         // Clean up checkpoints directory
         info!("Cleaning up {} checkpoint files", self.checkpoint_count);
@@ -152,10 +155,18 @@ impl IncrementalAudioSaver {
         // Create concat list file for FFmpeg
         let list_file = self.checkpoints_dir.join("concat_list.txt");
         let mut list_content = String::new();
+        // Decrypted copies of encrypted checkpoints, deleted when dropped after the merge
+        let mut decrypted = Vec::new();
 
         for i in 0..self.checkpoint_count {
-            let checkpoint_path = self.checkpoints_dir
+            let mut checkpoint_path = self.checkpoints_dir
                 .join(format!("audio_chunk_{:03}.mp4", i));
+            if !checkpoint_path.exists() && library_files::encrypted_path(&checkpoint_path).exists() {
+                let copy = library_files::decrypted_copy(&library_files::encrypted_path(&checkpoint_path))
+                    .map_err(|e| anyhow!(e))?;
+                checkpoint_path = copy.to_path_buf();
+                decrypted.push(copy);
+            }
 
             // Verify checkpoint exists
             if !checkpoint_path.exists() {
@@ -225,6 +236,11 @@ impl IncrementalAudioSaver {
     }
 }
 
+/// A checkpoint audio file, `audio_chunk_NNN.mp4` or its encrypted form
+fn is_checkpoint_file(path: &std::path::Path) -> bool {
+    library_files::plaintext_path(path).extension().and_then(|s| s.to_str()) == Some("mp4")
+}
+
 /// Audio recovery status for transcript recovery feature
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioRecoveryStatus {
@@ -259,13 +275,11 @@ pub async fn recover_audio_from_checkpoints(
         });
     }
 
-    // Scan for checkpoint files
+    // Scan for checkpoint files (plain or encrypted)
     let mut checkpoint_files: Vec<_> = std::fs::read_dir(&checkpoints_dir)
         .map_err(|e| format!("Failed to read checkpoints directory: {}", e))?
         .filter_map(|entry| entry.ok())
-        .filter(|entry| {
-            entry.path().extension().and_then(|s| s.to_str()) == Some("mp4")
-        })
+        .filter(|entry| is_checkpoint_file(&entry.path()))
         .collect();
 
     if checkpoint_files.is_empty() {
@@ -291,8 +305,15 @@ pub async fn recover_audio_from_checkpoints(
     let concat_file_path = checkpoints_dir.join("concat_list.txt");
     let mut concat_content = String::new();
 
+    let mut decrypted = Vec::new();
     for entry in &checkpoint_files {
-        let path = entry.path().canonicalize()
+        let mut path = entry.path();
+        if library_files::is_encrypted_path(&path) {
+            let copy = library_files::decrypted_copy(&path)?;
+            path = copy.to_path_buf();
+            decrypted.push(copy);
+        }
+        let path = path.canonicalize()
             .map_err(|e| format!("Failed to canonicalize path: {}", e))?;
         concat_content.push_str(&format!("file '{}'\n", path.display()));
     }
@@ -331,11 +352,17 @@ pub async fn recover_audio_from_checkpoints(
 
     let ffmpeg_result = command.output();
 
+    drop(decrypted);
+
     match ffmpeg_result {
         Ok(output) if output.status.success() => {
             // Clean up concat file
             let _ = std::fs::remove_file(concat_file_path);
 
+            let output_path_str = library_files::seal_file(&output_path)?
+                .to_string_lossy()
+                .to_string();
+
             info!("Successfully recovered audio: {}", output_path_str);
 
             Ok(AudioRecoveryStatus {
@@ -402,13 +429,11 @@ pub async fn has_audio_checkpoints(meeting_folder: String) -> Result<bool, Strin
         return Ok(false);
     }
 
-    // Scan for .mp4 checkpoint files
+    // Scan for .mp4 checkpoint files (plain or encrypted)
     let has_mp4_files = std::fs::read_dir(&checkpoints_dir)
         .map_err(|e| format!("Failed to read checkpoints directory: {}", e))?
         .filter_map(|entry| entry.ok())
-        .any(|entry| {
-            entry.path().extension().and_then(|s| s.to_str()) == Some("mp4")
-        });
+        .any(|entry| is_checkpoint_file(&entry.path()));
 
     Ok(has_mp4_files)
 }
@@ -0,0 +1,419 @@
+// Bookmarks and highlight markers set during recording
+//
+// A marker says "this was important" without typing: it records the recording-relative time,
+// an optional label and the sequence_id of the live transcript segment that was active. While
+// recording, markers live with the recording session and in markers.json in the meeting
+// folder; when the meeting is saved they are stored in the database. Summary generation gets
+// them as emphasis hints, and they can be exported as short audio clips with the transcript
+// text around them.
+
+use crate::database::models::{MeetingMarker, TranscriptVersionSegment};
+use crate::database::repositories::{
+    marker::MarkersRepository, meeting::MeetingsRepository, transcript_version::TranscriptVersionsRepository,
+};
+use crate::encryption::files as library_files;
+use crate::state::AppState;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use super::meeting_edit::{cut_args, run_audio_edit};
+use super::recording_saver::TranscriptSegment;
+use super::retranscription::find_audio_file;
+
+/// File next to transcripts.json holding the markers of a recording
+const MARKERS_FILE: &str = "markers.json";
+
+/// Transcript before a marker that is quoted in summary hints; markers are usually set just
+/// after the important moment
+const HINT_SECONDS_BEFORE: f64 = 20.0;
+const HINT_SECONDS_AFTER: f64 = 5.0;
+
+/// Longest transcript quote per marker in summary hints
+const HINT_MAX_CHARS: usize = 300;
+
+/// Default clip length around a marker
+const CLIP_SECONDS_BEFORE: f64 = 20.0;
+const CLIP_SECONDS_AFTER: f64 = 10.0;
+
+/// Longest clip either side of a marker
+const MAX_CLIP_SIDE_SECONDS: f64 = 300.0;
+
+/// Marker of the recording in progress
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordingMarker {
+    pub id: String,
+    /// Seconds into the recording's audio (pauses excluded)
+    pub time_seconds: f64,
+    pub label: Option<String>,
+    /// Live transcript segment that was active when the marker was set
+    pub sequence_id: Option<u64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RecordingMarker {
+    pub fn new(time_seconds: f64, label: Option<String>, sequence_id: Option<u64>) -> Self {
+        Self {
+            id: format!("marker-{}", Uuid::new_v4()),
+            time_seconds: time_seconds.max(0.0),
+            label: label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()),
+            sequence_id,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MarkersFile {
+    markers: Vec<RecordingMarker>,
+}
+
+/// Audio clip and transcript text exported around one marker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkerClip {
+    pub marker_id: String,
+    pub label: Option<String>,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    /// None when the meeting has no audio
+    pub audio_path: Option<String>,
+    pub text_path: String,
+}
+
+/// The segment being spoken at `time_seconds`, or the most recent one before it
+pub fn active_sequence_id(segments: &[TranscriptSegment], time_seconds: f64) -> Option<u64> {
+    segments
+        .iter()
+        .find(|s| s.audio_start_time <= time_seconds && time_seconds <= s.audio_end_time)
+        .or_else(|| {
+            segments
+                .iter()
+                .filter(|s| s.audio_start_time <= time_seconds)
+                .max_by(|a, b| a.audio_start_time.total_cmp(&b.audio_start_time))
+        })
+        .map(|s| s.sequence_id)
+}
+
+/// MM:SS, or H:MM:SS from one hour on
+fn format_time(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    if total >= 3600 {
+        format!("{}:{:02}:{:02}", total / 3600, (total / 60) % 60, total % 60)
+    } else {
+        format!("{:02}:{:02}", total / 60, total % 60)
+    }
+}
+
+/// Transcript text of the segments overlapping `start..end`
+fn text_between(segments: &[TranscriptVersionSegment], start: f64, end: f64) -> String {
+    segments
+        .iter()
+        .filter(|s| match (s.audio_start_time, s.audio_end_time) {
+            (Some(s_start), Some(s_end)) => s_start < end && s_end > start,
+            _ => false,
+        })
+        .map(|s| s.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}...", truncated.trim_end())
+}
+
+/// Marker list offered to summary generation as emphasis hints (`{{markers}}` in templates)
+pub fn to_summary_hints(markers: &[MeetingMarker], segments: &[TranscriptVersionSegment]) -> String {
+    markers
+        .iter()
+        .map(|marker| {
+            let mut line = format!("- [{}]", format_time(marker.time_seconds));
+            if let Some(label) = marker.label.as_deref().filter(|l| !l.trim().is_empty()) {
+                line.push_str(&format!(" {}", label.trim()));
+            }
+            let quote = text_between(
+                segments,
+                marker.time_seconds - HINT_SECONDS_BEFORE,
+                marker.time_seconds + HINT_SECONDS_AFTER,
+            );
+            if !quote.is_empty() {
+                line.push_str(&format!(": \"{}\"", truncate_chars(&quote, HINT_MAX_CHARS)));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Clip range around a marker, clamped to the start of the recording
+fn clip_range(time_seconds: f64, seconds_before: f64, seconds_after: f64) -> (f64, f64) {
+    ((time_seconds - seconds_before).max(0.0), time_seconds + seconds_after)
+}
+
+/// "clip-01-05m12s" style file name without extension
+fn clip_file_stem(index: usize, time_seconds: f64) -> String {
+    let total = time_seconds.max(0.0) as u64;
+    format!("clip-{:02}-{:02}m{:02}s", index + 1, total / 60, total % 60)
+}
+
+fn read_markers_file(folder: &Path) -> Result<Vec<RecordingMarker>> {
+    let path = folder.join(MARKERS_FILE);
+    if !library_files::exists(&path) {
+        return Ok(Vec::new());
+    }
+    let content = library_files::read_to_string(&path).map_err(|e| anyhow!(e))?;
+    let file: MarkersFile =
+        serde_json::from_str(&content).map_err(|e| anyhow!("Invalid {}: {}", path.display(), e))?;
+    Ok(file.markers)
+}
+
+/// Stores the markers written during recording (markers.json in the meeting folder) with the
+/// saved meeting. Missing or unreadable files are logged and skipped.
+pub async fn import_recording_markers(pool: &SqlitePool, meeting_id: &str, folder_path: Option<&str>) {
+    let Some(folder) = folder_path.map(PathBuf::from) else {
+        return;
+    };
+    let markers = match read_markers_file(&folder) {
+        Ok(markers) if markers.is_empty() => return,
+        Ok(markers) => markers,
+        Err(e) => {
+            warn!("Failed to read recording markers for meeting {}: {}", meeting_id, e);
+            return;
+        }
+    };
+
+    let rows: Vec<MeetingMarker> = markers
+        .into_iter()
+        .map(|marker| MeetingMarker {
+            id: marker.id,
+            meeting_id: meeting_id.to_string(),
+            time_seconds: marker.time_seconds,
+            label: marker.label,
+            sequence_id: marker.sequence_id.map(|id| id as i64),
+            created_at: marker.created_at,
+        })
+        .collect();
+    if let Err(e) = MarkersRepository::save_markers(pool, meeting_id, &rows).await {
+        warn!("Failed to save recording markers for meeting {}: {}", meeting_id, e);
+    }
+}
+
+async fn export_clips(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    output_dir: Option<PathBuf>,
+    seconds_before: f64,
+    seconds_after: f64,
+) -> Result<Vec<MarkerClip>> {
+    let markers = MarkersRepository::get_markers(pool, meeting_id).await?;
+    if markers.is_empty() {
+        return Err(anyhow!("This meeting has no markers"));
+    }
+    let meeting = MeetingsRepository::get_meeting_metadata(pool, meeting_id)
+        .await?
+        .ok_or_else(|| anyhow!("Meeting not found: {}", meeting_id))?;
+    let folder = meeting.folder_path.map(PathBuf::from).filter(|f| f.is_dir());
+    let output_dir = output_dir
+        .or_else(|| folder.as_ref().map(|f| f.join("clips")))
+        .ok_or_else(|| anyhow!("Meeting '{}' has no recording folder; choose an output folder", meeting.title))?;
+    std::fs::create_dir_all(&output_dir)?;
+
+    let stored_audio = folder.as_deref().and_then(|f| find_audio_file(f).ok());
+    // ffmpeg reads encrypted library audio from a decrypted copy, deleted after the export
+    let decrypted_audio = match &stored_audio {
+        Some(path) if library_files::is_encrypted_path(path) => {
+            Some(library_files::decrypted_copy(path).map_err(|e| anyhow!(e))?)
+        }
+        _ => None,
+    };
+    let audio = match &decrypted_audio {
+        Some(copy) => Some(copy.to_path_buf()),
+        None => stored_audio,
+    };
+    if audio.is_none() {
+        warn!("Meeting {} has no audio; exporting marker text only", meeting_id);
+    }
+    let segments = TranscriptVersionsRepository::get_current_segments(pool, meeting_id).await?;
+
+    let mut clips = Vec::with_capacity(markers.len());
+    for (index, marker) in markers.iter().enumerate() {
+        let (start, end) = clip_range(marker.time_seconds, seconds_before, seconds_after);
+        let stem = clip_file_stem(index, marker.time_seconds);
+        let text = text_between(&segments, start, end);
+
+        let audio_path = match &audio {
+            Some(input) => {
+                let output = output_dir.join(format!("{}.m4a", stem));
+                run_audio_edit(cut_args(input, start, Some(end), &output), "cut marker clip").await?;
+                Some(output.to_string_lossy().to_string())
+            }
+            None => None,
+        };
+
+        let mut text_file = format!("[{} - {}]", format_time(start), format_time(end));
+        if let Some(label) = &marker.label {
+            text_file.push_str(&format!(" {}", label));
+        }
+        text_file.push_str(&format!("\n\n{}\n", text));
+        let text_path = output_dir.join(format!("{}.txt", stem));
+        std::fs::write(&text_path, text_file)?;
+
+        clips.push(MarkerClip {
+            marker_id: marker.id.clone(),
+            label: marker.label.clone(),
+            start_time: start,
+            end_time: end,
+            text,
+            audio_path,
+            text_path: text_path.to_string_lossy().to_string(),
+        });
+    }
+
+    info!(
+        "Exported {} marker clips for meeting {} to {}",
+        clips.len(),
+        meeting_id,
+        output_dir.display()
+    );
+    Ok(clips)
+}
+
+#[tauri::command]
+pub async fn api_get_meeting_markers(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<MeetingMarker>, String> {
+    MarkersRepository::get_markers(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load markers: {}", e))
+}
+
+/// Changes a marker's label; an empty label clears it
+#[tauri::command]
+pub async fn api_update_meeting_marker(
+    state: tauri::State<'_, AppState>,
+    marker_id: String,
+    label: Option<String>,
+) -> Result<bool, String> {
+    let label = label.as_deref().map(str::trim).filter(|l| !l.is_empty());
+    MarkersRepository::update_marker_label(state.db_manager.pool(), &marker_id, label)
+        .await
+        .map_err(|e| format!("Failed to update marker: {}", e))
+}
+
+#[tauri::command]
+pub async fn api_delete_meeting_marker(
+    state: tauri::State<'_, AppState>,
+    marker_id: String,
+) -> Result<bool, String> {
+    MarkersRepository::delete_marker(state.db_manager.pool(), &marker_id)
+        .await
+        .map_err(|e| format!("Failed to delete marker: {}", e))
+}
+
+/// Cuts the meeting audio around each marker into a clip (.m4a) with a text file of the
+/// transcript in that range. Clips go to `output_dir`, or a clips folder in the meeting folder.
+#[tauri::command]
+pub async fn api_export_marker_clips(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    output_dir: Option<String>,
+    seconds_before: Option<f64>,
+    seconds_after: Option<f64>,
+) -> Result<Vec<MarkerClip>, String> {
+    info!("api_export_marker_clips called for meeting_id: {}", meeting_id);
+    let side = |seconds: Option<f64>, default: f64| seconds.unwrap_or(default).clamp(0.0, MAX_CLIP_SIDE_SECONDS);
+    let (before, after) = (side(seconds_before, CLIP_SECONDS_BEFORE), side(seconds_after, CLIP_SECONDS_AFTER));
+    if before + after < 1.0 {
+        return Err("Clips must be at least one second long".to_string());
+    }
+
+    export_clips(
+        state.db_manager.pool(),
+        &meeting_id,
+        output_dir.filter(|d| !d.trim().is_empty()).map(PathBuf::from),
+        before,
+        after,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live_segment(sequence_id: u64, start: f64, end: f64) -> TranscriptSegment {
+        TranscriptSegment {
+            id: format!("seg_{}", sequence_id),
+            text: String::new(),
+            audio_start_time: start,
+            audio_end_time: end,
+            duration: end - start,
+            display_time: String::new(),
+            confidence: 1.0,
+            sequence_id,
+            language: None,
+            translation: None,
+        }
+    }
+
+    fn segment(text: &str, start: f64, end: f64) -> TranscriptVersionSegment {
+        crate::database::models::segment(&format!("t-{}", start), text, start, end)
+    }
+
+    fn marker(time_seconds: f64, label: Option<&str>) -> MeetingMarker {
+        MeetingMarker {
+            id: format!("marker-{}", time_seconds),
+            meeting_id: "meeting-1".to_string(),
+            time_seconds,
+            label: label.map(String::from),
+            sequence_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_active_sequence_id() {
+        let segments = vec![live_segment(1, 0.0, 4.0), live_segment(2, 5.0, 9.0)];
+        assert_eq!(active_sequence_id(&segments, 6.0), Some(2));
+        // Between segments: the most recent one
+        assert_eq!(active_sequence_id(&segments, 4.5), Some(1));
+        assert_eq!(active_sequence_id(&segments, 30.0), Some(2));
+        assert_eq!(active_sequence_id(&[], 3.0), None);
+    }
+
+    #[test]
+    fn test_new_marker_normalizes_label() {
+        assert_eq!(RecordingMarker::new(3.0, Some("  ".to_string()), None).label, None);
+        assert_eq!(RecordingMarker::new(-1.0, Some(" Decision ".to_string()), Some(4)).label.as_deref(), Some("Decision"));
+        assert_eq!(RecordingMarker::new(-1.0, None, None).time_seconds, 0.0);
+    }
+
+    #[test]
+    fn test_summary_hints() {
+        let segments = vec![
+            segment("We agreed to ship on Friday.", 100.0, 110.0),
+            segment("Next topic.", 200.0, 205.0),
+        ];
+        let hints = to_summary_hints(&[marker(112.0, Some("Release date")), marker(400.0, None)], &segments);
+        assert_eq!(hints, "- [01:52] Release date: \"We agreed to ship on Friday.\"\n- [06:40]");
+    }
+
+    #[test]
+    fn test_clip_range_and_names() {
+        assert_eq!(clip_range(5.0, 20.0, 10.0), (0.0, 15.0));
+        assert_eq!(clip_range(312.0, 20.0, 10.0), (292.0, 322.0));
+        assert_eq!(clip_file_stem(0, 312.4), "clip-01-05m12s");
+        assert_eq!(truncate_chars("abcdef", 3), "abc...");
+    }
+}
@@ -0,0 +1,868 @@
+// Meeting merge, split and trim
+//
+// Fixes recordings that were stopped and restarted (merge), that cover two calls (split) or
+// that kept running after the call ended (trim). Each operation re-encodes the audio with
+// ffmpeg into a temporary file next to the original and keeps it under a staging name, applies
+// all database changes (transcripts re-offset, notes, derived data invalidated) in one
+// transaction, and only then renames the staged audio into place and rewrites
+// transcripts.json and metadata.json. A failure before the commit leaves the meetings
+// untouched and removes the staged audio; a failed rename puts the previous audio back.
+
+use crate::api::TranscriptSegment;
+use crate::database::models::Transcript;
+use crate::database::repositories::{meeting::MeetingsRepository, transcript::TranscriptsRepository};
+use crate::encryption::files as library_files;
+use crate::state::AppState;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Runtime};
+use uuid::Uuid;
+
+use super::audio_processing::create_meeting_folder;
+use super::common::write_transcripts_json;
+use super::decoder::{decode_audio_file, probe_media, run_ffmpeg};
+use super::ffmpeg::find_ffmpeg_path;
+use super::recording_preferences::get_default_recordings_folder;
+use super::retranscription::{find_audio_file, is_retranscription_in_progress};
+
+/// Audio encoding of edited recordings; matches what the recorder writes (see encode.rs)
+const AAC_OUTPUT_ARGS: &[&str] = &[
+    "-vn", "-c:a", "aac", "-b:a", "192k", "-profile:a", "aac_low", "-movflags", "+faststart", "-f", "mp4",
+];
+
+/// File name of the edited audio inside the meeting folder
+const EDITED_AUDIO_FILE: &str = "audio.mp4";
+
+/// Edited audio waiting for the database commit (hidden, and not an audio extension)
+const STAGED_AUDIO_FILE: &str = ".audio.mp4.staged";
+
+/// The audio being replaced, kept until the edited audio is in place
+const PREVIOUS_AUDIO_FILE: &str = ".audio.mp4.previous";
+
+/// Markdown separator between the notes of merged meetings
+const NOTES_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Shortest part a split or trim may leave, in seconds
+const MIN_PART_SECONDS: f64 = 1.0;
+
+/// Only one edit at a time; edits rewrite audio files and transcripts
+static EDIT_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// RAII guard for EDIT_IN_PROGRESS
+struct EditGuard;
+
+impl EditGuard {
+    fn acquire() -> Result<Self> {
+        if EDIT_IN_PROGRESS
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err(anyhow!("Another meeting edit is in progress"));
+        }
+        Ok(EditGuard)
+    }
+}
+
+impl Drop for EditGuard {
+    fn drop(&mut self) {
+        EDIT_IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Result of a merge, split or trim
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingEditResult {
+    /// Meetings that exist after the edit (the new meeting of a split comes second)
+    pub meeting_ids: Vec<String>,
+    /// Duration of the first meeting's audio after the edit
+    pub duration_seconds: f64,
+}
+
+/// A meeting with its folder and audio resolved
+struct EditableMeeting {
+    id: String,
+    title: String,
+    folder: PathBuf,
+    /// Audio to read from (a decrypted copy in encrypted library mode)
+    audio: Option<PathBuf>,
+    /// Audio file as stored in the folder
+    stored_audio: Option<PathBuf>,
+    /// Keeps the decrypted copy of encrypted audio until the edit is done
+    _decrypted_audio: Option<tempfile::TempPath>,
+    duration_seconds: f64,
+}
+
+async fn ensure_editable() -> Result<EditGuard> {
+    if super::recording_commands::is_recording().await {
+        return Err(anyhow!("Meetings cannot be edited while recording"));
+    }
+    if is_retranscription_in_progress() {
+        return Err(anyhow!("Meetings cannot be edited while a retranscription is running"));
+    }
+    EditGuard::acquire()
+}
+
+async fn load_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<EditableMeeting> {
+    let meeting = MeetingsRepository::get_meeting_metadata(pool, meeting_id)
+        .await?
+        .ok_or_else(|| anyhow!("Meeting not found: {}", meeting_id))?;
+    let folder = meeting
+        .folder_path
+        .map(PathBuf::from)
+        .filter(|folder| folder.is_dir())
+        .ok_or_else(|| anyhow!("Meeting '{}' has no recording folder", meeting.title))?;
+
+    let stored_audio = find_audio_file(&folder).ok();
+    let decrypted_audio = match stored_audio.as_deref().filter(|path| library_files::is_encrypted_path(path)) {
+        Some(path) => Some(library_files::decrypted_copy(path).map_err(|e| anyhow!(e))?),
+        None => None,
+    };
+    let audio = match &decrypted_audio {
+        Some(copy) => Some(copy.to_path_buf()),
+        None => stored_audio.clone(),
+    };
+    let duration_seconds = match &audio {
+        Some(path) => {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || audio_duration(&path))
+                .await
+                .map_err(|e| anyhow!("Duration task join error: {}", e))??
+        }
+        // No audio: the transcript defines the timeline
+        None => TranscriptsRepository::get_meeting_transcripts(pool, meeting_id)
+            .await?
+            .iter()
+            .filter_map(|t| t.audio_end_time)
+            .fold(0.0, f64::max),
+    };
+
+    Ok(EditableMeeting {
+        id: meeting.id,
+        title: meeting.title,
+        folder,
+        audio,
+        stored_audio,
+        _decrypted_audio: decrypted_audio,
+        duration_seconds,
+    })
+}
+
+fn audio_duration(path: &Path) -> Result<f64> {
+    if let Some(duration) = probe_media(path).ok().and_then(|p| p.duration_seconds) {
+        return Ok(duration);
+    }
+    Ok(decode_audio_file(path)?.duration_seconds)
+}
+
+/// Start of each meeting in the merged recording
+fn merge_offsets(durations: &[f64]) -> Vec<f64> {
+    durations
+        .iter()
+        .scan(0.0, |elapsed, duration| {
+            let offset = *elapsed;
+            *elapsed += duration;
+            Some(offset)
+        })
+        .collect()
+}
+
+fn validate_split_point(at_seconds: f64, duration_seconds: f64) -> Result<()> {
+    if !at_seconds.is_finite()
+        || at_seconds < MIN_PART_SECONDS
+        || at_seconds > duration_seconds - MIN_PART_SECONDS
+    {
+        return Err(anyhow!(
+            "Split point {:.1}s must lie inside the recording (0s-{:.1}s)",
+            at_seconds,
+            duration_seconds
+        ));
+    }
+    Ok(())
+}
+
+/// Range to keep; missing bounds default to the start/end of the recording
+fn resolve_trim_range(start: Option<f64>, end: Option<f64>, duration_seconds: f64) -> Result<(f64, f64)> {
+    let start = start.unwrap_or(0.0).max(0.0);
+    let end = end.unwrap_or(duration_seconds).min(duration_seconds);
+    if !start.is_finite() || !end.is_finite() || end - start < MIN_PART_SECONDS {
+        return Err(anyhow!("Nothing would be left after trimming to {:.1}s-{:.1}s", start, end));
+    }
+    if start <= 0.0 && end >= duration_seconds {
+        return Err(anyhow!("The trim range covers the whole recording"));
+    }
+    Ok((start, end))
+}
+
+/// Notes of merged meetings (markdown, editor JSON) in merge order. Markdown is joined with a
+/// separator; editor JSON is concatenated only if every meeting with notes stores a block array,
+/// otherwise it is dropped so the editor rebuilds it from the markdown.
+fn merge_notes(notes: &[Option<(Option<String>, Option<String>)>]) -> Option<(Option<String>, Option<String>)> {
+    let present: Vec<&(Option<String>, Option<String>)> = notes
+        .iter()
+        .flatten()
+        .filter(|(markdown, json)| {
+            markdown.as_deref().is_some_and(|m| !m.trim().is_empty()) || json.as_deref().is_some_and(|j| !j.trim().is_empty())
+        })
+        .collect();
+    if present.is_empty() {
+        return None;
+    }
+
+    let markdown: Vec<&str> = present
+        .iter()
+        .filter_map(|(markdown, _)| markdown.as_deref())
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect();
+    let markdown = (!markdown.is_empty()).then(|| markdown.join(NOTES_SEPARATOR));
+
+    let blocks: Option<Vec<serde_json::Value>> = present
+        .iter()
+        .map(|(_, json)| {
+            let value: serde_json::Value = serde_json::from_str(json.as_deref()?).ok()?;
+            value.as_array().cloned()
+        })
+        .collect::<Option<Vec<Vec<serde_json::Value>>>>()
+        .map(|arrays| arrays.into_iter().flatten().collect());
+    let json = blocks.and_then(|blocks| serde_json::to_string(&blocks).ok());
+
+    Some((markdown, json))
+}
+
+/// ffmpeg arguments cutting `start..end` (seconds; None = to the end) out of `input`
+pub(super) fn cut_args(input: &Path, start: f64, end: Option<f64>, output: &Path) -> Vec<String> {
+    // -ss after -i seeks by decoding, which is exact rather than keyframe-aligned
+    let mut args = vec![
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", start),
+    ];
+    if let Some(end) = end {
+        args.extend(["-t".to_string(), format!("{:.3}", end - start)]);
+    }
+    args.extend(AAC_OUTPUT_ARGS.iter().map(|a| a.to_string()));
+    args.extend(["-y".to_string(), output.to_string_lossy().to_string()]);
+    args
+}
+
+/// ffmpeg arguments concatenating the audio of `inputs` in order
+fn concat_args(inputs: &[PathBuf], output: &Path) -> Vec<String> {
+    let mut args = Vec::new();
+    for input in inputs {
+        args.extend(["-i".to_string(), input.to_string_lossy().to_string()]);
+    }
+    let streams: String = (0..inputs.len()).map(|i| format!("[{}:a]", i)).collect();
+    args.extend([
+        "-filter_complex".to_string(),
+        format!("{}concat=n={}:v=0:a=1[joined]", streams, inputs.len()),
+        "-map".to_string(),
+        "[joined]".to_string(),
+    ]);
+    args.extend(AAC_OUTPUT_ARGS.iter().map(|a| a.to_string()));
+    args.extend(["-y".to_string(), output.to_string_lossy().to_string()]);
+    args
+}
+
+/// Runs ffmpeg in a blocking task and fails on a non-zero exit
+pub(super) async fn run_audio_edit(args: Vec<String>, description: &str) -> Result<()> {
+    let ffmpeg_path = find_ffmpeg_path()
+        .ok_or_else(|| anyhow!("FFmpeg not found. FFmpeg is required to edit meeting audio."))?;
+    let output = tokio::task::spawn_blocking(move || run_ffmpeg(&ffmpeg_path, &args))
+        .await
+        .map_err(|e| anyhow!("ffmpeg task join error: {}", e))??;
+    if !output.status.success() {
+        warn!(
+            "ffmpeg failed to {}: {}",
+            description,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return Err(anyhow!("Failed to {} (ffmpeg exit code: {})", description, output.status));
+    }
+    Ok(())
+}
+
+/// Temporary output next to the meeting's audio; deleted automatically unless persisted
+fn temp_audio_path(folder: &Path) -> Result<tempfile::TempPath> {
+    Ok(tempfile::Builder::new()
+        .prefix(".meetily_edit_")
+        .suffix(".mp4")
+        .tempfile_in(folder)
+        .map_err(|e| anyhow!("Failed to create temporary audio file: {}", e))?
+        .into_temp_path())
+}
+
+/// Keeps an edited audio file under the staging name until the database changes are committed
+fn stage_audio(folder: &Path, edited: tempfile::TempPath) -> Result<PathBuf> {
+    let staged = folder.join(STAGED_AUDIO_FILE);
+    edited
+        .persist(&staged)
+        .map_err(|e| anyhow!("Failed to stage edited audio: {}", e))?;
+    Ok(staged)
+}
+
+/// Removes staged audio whose edit was not committed
+fn discard_staged_audio(staged: &Path) {
+    if let Err(e) = std::fs::remove_file(staged) {
+        warn!("Failed to remove staged audio {}: {}", staged.display(), e);
+    }
+}
+
+/// Renames staged audio into place, then removes the original. If the rename fails the
+/// original is put back and the staged audio removed.
+fn replace_audio(folder: &Path, original: Option<&Path>, staged: &Path) -> Result<()> {
+    let destination = folder.join(EDITED_AUDIO_FILE);
+    let previous = folder.join(PREVIOUS_AUDIO_FILE);
+    let original = original.filter(|original| original.exists());
+
+    // An original under the destination name is moved aside rather than overwritten
+    let moved_aside = match original {
+        Some(original) if original == destination.as_path() => {
+            if let Err(e) = std::fs::rename(original, &previous) {
+                discard_staged_audio(staged);
+                return Err(anyhow!("Failed to replace audio file: {}", e));
+            }
+            true
+        }
+        _ => false,
+    };
+
+    if let Err(e) = std::fs::rename(staged, &destination) {
+        if moved_aside {
+            if let Err(restore_error) = std::fs::rename(&previous, &destination) {
+                warn!(
+                    "Failed to restore previous audio {}: {}",
+                    previous.display(),
+                    restore_error
+                );
+            }
+        }
+        discard_staged_audio(staged);
+        return Err(anyhow!("Failed to replace audio file: {}", e));
+    }
+
+    let replaced = if moved_aside { Some(previous.as_path()) } else { original };
+    if let Some(replaced) = replaced {
+        if let Err(e) = std::fs::remove_file(replaced) {
+            warn!("Failed to remove previous audio file {}: {}", replaced.display(), e);
+        }
+    }
+    Ok(())
+}
+
+fn to_segment(transcript: Transcript) -> TranscriptSegment {
+    TranscriptSegment {
+        id: transcript.id,
+        text: transcript.transcript,
+        timestamp: transcript.timestamp,
+        audio_start_time: transcript.audio_start_time,
+        audio_end_time: transcript.audio_end_time,
+        duration: transcript.duration,
+        language: transcript.language,
+        translation: transcript.translation,
+    }
+}
+
+/// Rewrites transcripts.json and metadata.json of an edited meeting from the database
+async fn refresh_meeting_files(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    title: &str,
+    folder: &Path,
+    has_audio: bool,
+    duration_seconds: f64,
+    edit: serde_json::Value,
+) -> Result<()> {
+    let transcripts = TranscriptsRepository::get_meeting_transcripts(pool, meeting_id)
+        .await
+        .map_err(|e| anyhow!("Failed to load transcripts of {}: {}", meeting_id, e))?;
+    let segments: Vec<TranscriptSegment> = transcripts.into_iter().map(to_segment).collect();
+    write_transcripts_json(folder, &segments)
+        .map_err(|e| anyhow!("Failed to write transcripts.json for {}: {}", meeting_id, e))?;
+
+    let audio_file = has_audio.then_some(EDITED_AUDIO_FILE);
+    write_edit_metadata(folder, meeting_id, title, duration_seconds, audio_file, edit)
+        .map_err(|e| anyhow!("Failed to update metadata.json for {}: {}", meeting_id, e))?;
+
+    // Edited audio is written in plaintext; encrypt it in encrypted library mode
+    library_files::seal_folder(folder);
+    Ok(())
+}
+
+/// Write or update metadata.json after an edit (preserves existing fields, appends to "edits")
+pub(super) fn write_edit_metadata(
+    folder: &Path,
+    meeting_id: &str,
+    title: &str,
+    duration_seconds: f64,
+    audio_file: Option<&str>,
+    edit: serde_json::Value,
+) -> Result<()> {
+    let metadata_path = folder.join("metadata.json");
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let mut json = match library_files::read_to_string(&metadata_path) {
+        Ok(existing) => serde_json::from_str(&existing).unwrap_or_else(|_| serde_json::json!({})),
+        Err(_) => serde_json::json!({
+            "version": "1.0",
+            "created_at": now,
+            "completed_at": now,
+            "transcript_file": "transcripts.json",
+            "status": "completed"
+        }),
+    };
+
+    if let Some(obj) = json.as_object_mut() {
+        obj.insert("meeting_id".to_string(), serde_json::json!(meeting_id));
+        obj.insert("meeting_name".to_string(), serde_json::json!(title));
+        obj.insert("duration_seconds".to_string(), serde_json::json!(duration_seconds));
+        if let Some(audio_file) = audio_file {
+            obj.insert("audio_file".to_string(), serde_json::json!(audio_file));
+        }
+        obj.insert("edited_at".to_string(), serde_json::json!(now));
+        let edits = obj.entry("edits").or_insert_with(|| serde_json::json!([]));
+        if let Some(edits) = edits.as_array_mut() {
+            edits.push(edit);
+        }
+    }
+
+    let json_string = serde_json::to_string_pretty(&json)?;
+    library_files::write_file(&metadata_path, json_string.as_bytes()).map_err(|e| anyhow!(e))?;
+
+    info!("Wrote metadata.json to {}", metadata_path.display());
+    Ok(())
+}
+
+fn emit_edited<R: Runtime>(app: &AppHandle<R>, operation: &str, meeting_ids: &[String]) {
+    let _ = app.emit(
+        "meeting-edited",
+        serde_json::json!({ "operation": operation, "meeting_ids": meeting_ids }),
+    );
+}
+
+/// Merges meetings in the given order into the first one
+pub async fn merge_meetings<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_ids: Vec<String>,
+    title: Option<String>,
+) -> Result<MeetingEditResult> {
+    let _guard = ensure_editable().await?;
+
+    let mut ids: Vec<String> = Vec::new();
+    for id in meeting_ids {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.len() < 2 {
+        return Err(anyhow!("Select at least two meetings to merge"));
+    }
+
+    let mut meetings = Vec::with_capacity(ids.len());
+    for id in &ids {
+        meetings.push(load_meeting(pool, id).await?);
+    }
+
+    let with_audio = meetings.iter().filter(|m| m.audio.is_some()).count();
+    if with_audio > 0 && with_audio < meetings.len() {
+        let missing = meetings.iter().find(|m| m.audio.is_none()).map(|m| m.title.as_str()).unwrap_or_default();
+        return Err(anyhow!(
+            "Meeting '{}' has no audio file; merging needs audio for all meetings or none",
+            missing
+        ));
+    }
+
+    let durations: Vec<f64> = meetings.iter().map(|m| m.duration_seconds).collect();
+    let offsets = merge_offsets(&durations);
+    let total_duration: f64 = durations.iter().sum();
+    let target = &meetings[0];
+    info!(
+        "Merging {} meetings into {} (offsets {:?})",
+        meetings.len(),
+        target.id,
+        offsets
+    );
+
+    // Concatenate the audio first; nothing is changed if this fails
+    let merged_audio = if with_audio > 0 {
+        let temp = temp_audio_path(&target.folder)?;
+        let inputs: Vec<PathBuf> = meetings.iter().filter_map(|m| m.audio.clone()).collect();
+        run_audio_edit(concat_args(&inputs, &temp), "concatenate the recordings").await?;
+        Some(stage_audio(&target.folder, temp)?)
+    } else {
+        None
+    };
+
+    let title = title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| target.title.clone());
+    let sources: Vec<(String, f64)> = meetings[1..]
+        .iter()
+        .zip(&offsets[1..])
+        .map(|(meeting, offset)| (meeting.id.clone(), *offset))
+        .collect();
+
+    let committed: Result<()> = async {
+        let mut notes = Vec::with_capacity(meetings.len());
+        for meeting in &meetings {
+            notes.push(MeetingsRepository::get_meeting_notes(pool, &meeting.id).await?);
+        }
+        MeetingsRepository::merge_meetings(pool, &target.id, &title, &sources, merge_notes(&notes)).await?;
+        Ok(())
+    }
+    .await;
+    if let Err(e) = committed {
+        if let Some(staged) = &merged_audio {
+            discard_staged_audio(staged);
+        }
+        return Err(e);
+    }
+
+    if let Some(merged_audio) = &merged_audio {
+        replace_audio(&target.folder, target.stored_audio.as_deref(), merged_audio)?;
+    }
+    let refreshed = refresh_meeting_files(
+        pool,
+        &target.id,
+        &title,
+        &target.folder,
+        with_audio > 0,
+        total_duration,
+        serde_json::json!({
+            "operation": "merge",
+            "at": chrono::Utc::now().to_rfc3339(),
+            "merged_meeting_ids": &ids[1..],
+            "offsets_seconds": &offsets[1..],
+        }),
+    )
+    .await;
+
+    // The merged meetings are gone; remove their folders (only real meeting folders)
+    for meeting in &meetings[1..] {
+        if meeting.folder == target.folder {
+            continue;
+        }
+        if library_files::exists(&meeting.folder.join("metadata.json")) {
+            if let Err(e) = std::fs::remove_dir_all(&meeting.folder) {
+                warn!("Failed to remove folder of merged meeting {}: {}", meeting.id, e);
+            }
+        } else if let Some(audio) = &meeting.stored_audio {
+            let _ = std::fs::remove_file(audio);
+        }
+    }
+
+    emit_edited(app, "merge", &ids);
+    refreshed?;
+    Ok(MeetingEditResult {
+        meeting_ids: vec![target.id.clone()],
+        duration_seconds: total_duration,
+    })
+}
+
+/// Splits a meeting at `at_seconds`; everything after it becomes a new meeting
+pub async fn split_meeting<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    at_seconds: f64,
+    title: Option<String>,
+) -> Result<MeetingEditResult> {
+    let _guard = ensure_editable().await?;
+    let meeting = load_meeting(pool, meeting_id).await?;
+    validate_split_point(at_seconds, meeting.duration_seconds)?;
+
+    let new_title = title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| format!("{} (part 2)", meeting.title));
+    let base_folder = meeting
+        .folder
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(get_default_recordings_folder);
+    let new_folder = create_meeting_folder(&base_folder, &new_title, false)?;
+    let new_id = format!("meeting-{}", Uuid::new_v4());
+    info!(
+        "Splitting meeting {} at {:.2}s into new meeting {} ({})",
+        meeting.id,
+        at_seconds,
+        new_id,
+        new_folder.display()
+    );
+
+    let mut first_part: Option<PathBuf> = None;
+    let result: Result<()> = async {
+        if let Some(audio) = &meeting.audio {
+            let second_part = new_folder.join(EDITED_AUDIO_FILE);
+            run_audio_edit(cut_args(audio, at_seconds, None, &second_part), "cut the second part").await?;
+            let temp = temp_audio_path(&meeting.folder)?;
+            run_audio_edit(cut_args(audio, 0.0, Some(at_seconds), &temp), "cut the first part").await?;
+            first_part = Some(stage_audio(&meeting.folder, temp)?);
+        }
+        // Without audio to cut only the database changes
+
+        MeetingsRepository::split_meeting(
+            pool,
+            &meeting.id,
+            at_seconds,
+            &new_id,
+            &new_title,
+            &new_folder.to_string_lossy(),
+        )
+        .await?;
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        if let Some(staged) = &first_part {
+            discard_staged_audio(staged);
+        }
+        let _ = std::fs::remove_dir_all(&new_folder);
+        return Err(e);
+    }
+
+    let has_audio = first_part.is_some();
+    if let Some(first_part) = &first_part {
+        replace_audio(&meeting.folder, meeting.stored_audio.as_deref(), first_part)?;
+    }
+
+    let edit = |part: u32| {
+        serde_json::json!({
+            "operation": "split",
+            "at": chrono::Utc::now().to_rfc3339(),
+            "split_at_seconds": at_seconds,
+            "original_meeting_id": &meeting.id,
+            "part": part,
+        })
+    };
+    let first_refreshed =
+        refresh_meeting_files(pool, &meeting.id, &meeting.title, &meeting.folder, has_audio, at_seconds, edit(1)).await;
+    let second_refreshed = refresh_meeting_files(
+        pool,
+        &new_id,
+        &new_title,
+        &new_folder,
+        has_audio,
+        meeting.duration_seconds - at_seconds,
+        edit(2),
+    )
+    .await;
+
+    let meeting_ids = vec![meeting.id.clone(), new_id];
+    emit_edited(app, "split", &meeting_ids);
+    first_refreshed.and(second_refreshed)?;
+    Ok(MeetingEditResult {
+        meeting_ids,
+        duration_seconds: at_seconds,
+    })
+}
+
+/// Keeps only `start_seconds..end_seconds` of a meeting (missing bounds keep that end)
+pub async fn trim_meeting<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    start_seconds: Option<f64>,
+    end_seconds: Option<f64>,
+) -> Result<MeetingEditResult> {
+    let _guard = ensure_editable().await?;
+    let meeting = load_meeting(pool, meeting_id).await?;
+    let (start, end) = resolve_trim_range(start_seconds, end_seconds, meeting.duration_seconds)?;
+    info!("Trimming meeting {} to {:.2}s..{:.2}s", meeting.id, start, end);
+
+    let trimmed_audio = match &meeting.audio {
+        Some(audio) => {
+            let temp = temp_audio_path(&meeting.folder)?;
+            run_audio_edit(cut_args(audio, start, Some(end), &temp), "trim the recording").await?;
+            Some(stage_audio(&meeting.folder, temp)?)
+        }
+        None => None,
+    };
+
+    if let Err(e) = MeetingsRepository::trim_meeting(pool, &meeting.id, start, end).await {
+        if let Some(staged) = &trimmed_audio {
+            discard_staged_audio(staged);
+        }
+        return Err(e.into());
+    }
+
+    let has_audio = trimmed_audio.is_some();
+    if let Some(trimmed_audio) = &trimmed_audio {
+        replace_audio(&meeting.folder, meeting.stored_audio.as_deref(), trimmed_audio)?;
+    }
+    let refreshed = refresh_meeting_files(
+        pool,
+        &meeting.id,
+        &meeting.title,
+        &meeting.folder,
+        has_audio,
+        end - start,
+        serde_json::json!({
+            "operation": "trim",
+            "at": chrono::Utc::now().to_rfc3339(),
+            "kept_from_seconds": start,
+            "kept_to_seconds": end,
+        }),
+    )
+    .await;
+
+    let meeting_ids = vec![meeting.id.clone()];
+    emit_edited(app, "trim", &meeting_ids);
+    refreshed?;
+    Ok(MeetingEditResult {
+        meeting_ids,
+        duration_seconds: end - start,
+    })
+}
+
+// Tauri commands
+
+/// Merge meetings in the given order into the first one (audio concatenated, transcripts re-offset)
+#[tauri::command]
+pub async fn merge_meetings_command<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_ids: Vec<String>,
+    title: Option<String>,
+) -> Result<MeetingEditResult, String> {
+    merge_meetings(&app, state.db_manager.pool(), meeting_ids, title)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Split a meeting into two at a timestamp (seconds into the recording)
+#[tauri::command]
+pub async fn split_meeting_command<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    at_seconds: f64,
+    title: Option<String>,
+) -> Result<MeetingEditResult, String> {
+    split_meeting(&app, state.db_manager.pool(), &meeting_id, at_seconds, title)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Trim leading and/or trailing parts of a meeting
+#[tauri::command]
+pub async fn trim_meeting_command<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    start_seconds: Option<f64>,
+    end_seconds: Option<f64>,
+) -> Result<MeetingEditResult, String> {
+    trim_meeting(&app, state.db_manager.pool(), &meeting_id, start_seconds, end_seconds)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notes(markdown: Option<&str>, json: Option<&str>) -> Option<(Option<String>, Option<String>)> {
+        Some((markdown.map(String::from), json.map(String::from)))
+    }
+
+    #[test]
+    fn test_merge_offsets() {
+        assert_eq!(merge_offsets(&[600.0, 300.5, 10.0]), vec![0.0, 600.0, 900.5]);
+        assert!(merge_offsets(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_validate_split_point() {
+        assert!(validate_split_point(120.0, 600.0).is_ok());
+        assert!(validate_split_point(0.0, 600.0).is_err());
+        assert!(validate_split_point(600.0, 600.0).is_err());
+        assert!(validate_split_point(f64::NAN, 600.0).is_err());
+    }
+
+    #[test]
+    fn test_resolve_trim_range() {
+        // Trailing silence only
+        assert_eq!(resolve_trim_range(None, Some(3000.0), 3600.0).unwrap(), (0.0, 3000.0));
+        // Leading range only; end clamps to the recording
+        assert_eq!(resolve_trim_range(Some(30.0), Some(9999.0), 3600.0).unwrap(), (30.0, 3600.0));
+        assert!(resolve_trim_range(None, None, 3600.0).is_err());
+        assert!(resolve_trim_range(Some(100.0), Some(100.5), 3600.0).is_err());
+    }
+
+    #[test]
+    fn test_merge_notes() {
+        let merged = merge_notes(&[
+            notes(Some("First call"), Some(r#"[{"type":"paragraph","id":"a"}]"#)),
+            None,
+            notes(Some(" Second call "), Some(r#"[{"type":"paragraph","id":"b"}]"#)),
+        ])
+        .unwrap();
+        assert_eq!(merged.0.as_deref(), Some("First call\n\n---\n\nSecond call"));
+        let blocks: serde_json::Value = serde_json::from_str(merged.1.as_deref().unwrap()).unwrap();
+        assert_eq!(blocks.as_array().unwrap().len(), 2);
+
+        // Editor JSON that cannot be combined is dropped, markdown is kept
+        let merged = merge_notes(&[notes(Some("A"), Some(r#"{"doc":1}"#)), notes(Some("B"), None)]).unwrap();
+        assert_eq!(merged.0.as_deref(), Some("A\n\n---\n\nB"));
+        assert_eq!(merged.1, None);
+
+        assert_eq!(merge_notes(&[None, notes(Some("  "), None)]), None);
+    }
+
+    #[test]
+    fn test_cut_and_concat_args() {
+        let args = cut_args(Path::new("in.mp4"), 12.5, Some(72.5), Path::new("out.mp4"));
+        assert_eq!(&args[..6], &["-i", "in.mp4", "-ss", "12.500", "-t", "60.000"]);
+        assert_eq!(args.last().unwrap(), "out.mp4");
+
+        let args = cut_args(Path::new("in.mp4"), 30.0, None, Path::new("out.mp4"));
+        assert!(!args.contains(&"-t".to_string()));
+
+        let args = concat_args(&[PathBuf::from("a.mp4"), PathBuf::from("b.wav")], Path::new("out.mp4"));
+        assert_eq!(&args[..4], &["-i", "a.mp4", "-i", "b.wav"]);
+        assert!(args.contains(&"[0:a][1:a]concat=n=2:v=0:a=1[joined]".to_string()));
+    }
+
+    #[test]
+    fn test_replace_audio_swaps_in_staged_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join(EDITED_AUDIO_FILE);
+        std::fs::write(&audio, b"original").unwrap();
+        let staged = dir.path().join(STAGED_AUDIO_FILE);
+        std::fs::write(&staged, b"edited").unwrap();
+
+        replace_audio(dir.path(), Some(&audio), &staged).unwrap();
+        assert_eq!(std::fs::read(&audio).unwrap(), b"edited");
+        assert!(!staged.exists());
+        assert!(!dir.path().join(PREVIOUS_AUDIO_FILE).exists());
+
+        // An original under another name is removed once the edited audio is in place
+        let wav = dir.path().join("audio.wav");
+        std::fs::write(&wav, b"original").unwrap();
+        std::fs::write(&staged, b"edited again").unwrap();
+        replace_audio(dir.path(), Some(&wav), &staged).unwrap();
+        assert_eq!(std::fs::read(&audio).unwrap(), b"edited again");
+        assert!(!wav.exists());
+    }
+
+    #[test]
+    fn test_replace_audio_restores_the_original_when_the_rename_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join(EDITED_AUDIO_FILE);
+        std::fs::write(&audio, b"original").unwrap();
+
+        // A missing staged file makes the rename fail
+        let staged = dir.path().join(STAGED_AUDIO_FILE);
+        assert!(replace_audio(dir.path(), Some(&audio), &staged).is_err());
+        assert_eq!(std::fs::read(&audio).unwrap(), b"original");
+        assert!(!dir.path().join(PREVIOUS_AUDIO_FILE).exists());
+    }
+}
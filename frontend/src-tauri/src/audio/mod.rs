@@ -36,6 +36,11 @@ pub mod system_audio_commands;
 pub mod device_monitor;  // NEW: Device disconnect/reconnect monitoring
 pub mod playback_monitor; // NEW: Playback device detection for BT warnings
 pub mod import;  // Audio/video file import with transcription
+pub mod import_queue;  // Persistent watch-folder import queue
+pub mod meeting_edit;  // Meeting merge, split and trim
+pub mod transcript_edit;  // Segment-level transcript editing and re-alignment
+pub mod markers;  // Bookmarks set during recording and clip export
+pub mod redacted_copy;  // Redacted copies of meetings for sharing
 
 // Transcription module (provider abstraction, engine management, worker pool)
 pub mod transcription;
@@ -49,6 +54,12 @@ pub mod constants;
 // Retranscription module (re-process stored audio with different settings)
 pub mod retranscription;
 
+// Post-recording quality pass (background re-transcription with a more accurate model)
+pub mod quality_pass;
+
+// Confidence-driven re-decoding of weak transcript segments
+pub mod segment_repair;
+
 pub use devices::{
     default_input_device, default_output_device, get_device_and_config, list_audio_devices,
     parse_audio_device, trigger_audio_permission,
@@ -72,7 +83,7 @@ pub use system_detector::{
 // Export system audio commands
 pub use system_audio_commands::{
     start_system_audio_capture_command, list_system_audio_devices_command,
-    check_system_audio_permissions_command, start_system_audio_monitoring,
+    list_system_audio_applications_command, check_system_audio_permissions_command, start_system_audio_monitoring,
     stop_system_audio_monitoring, get_system_audio_monitoring_status,
     init_system_audio_state
 };
@@ -15,6 +15,7 @@ use super::recording_state::{AudioChunk, AudioError, RecordingState, DeviceType}
 use super::audio_processing::{audio_to_mono, LoudnessNormalizer, NoiseSuppressionProcessor, HighPassFilter};
 use super::vad::{ContinuousVadProcessor};
 use super::common::split_segment_at_silence;
+use super::transcription::interim::{self, InterimConfig, InterimSnapshot};
 
 /// Thread-safe sample counter (replaces unsafe static mut)
 static RING_BUFFER_SAMPLE_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -696,6 +697,9 @@ pub struct AudioPipeline {
     mixer: ProfessionalAudioMixer,
     // Recording sender for pre-mixed audio
     recording_sender_for_mixed: Option<mpsc::UnboundedSender<AudioChunk>>,
+    // Live interim transcripts: snapshot cadence for the in-progress VAD segment
+    interim_config: InterimConfig,
+    last_interim_publish: std::time::Instant,
 }
 
 impl AudioPipeline {
@@ -763,6 +767,8 @@ impl AudioPipeline {
             ring_buffer,
             mixer,
             recording_sender_for_mixed: None,  // Will be set by manager
+            interim_config: interim::config(),
+            last_interim_publish: std::time::Instant::now(),
         }
     }
 
@@ -930,6 +936,11 @@ impl AudioPipeline {
                                 }
                             }
 
+                            // STEP 3b: Publish the still-open segment for live interim decoding
+                            if self.interim_config.enabled {
+                                self.publish_interim_snapshot();
+                            }
+
                             // STEP 4: Send mixed audio for recording (WAV file)
                             // Move the buffer instead of cloning — avoids ~115KB copy per 600ms window
                             if let Some(ref sender) = self.recording_sender_for_mixed {
@@ -963,9 +974,34 @@ impl AudioPipeline {
         Ok(())
     }
 
+    /// Publish the in-progress VAD segment at the configured cadence.
+    /// The worker keeps only the newest snapshot, so a slow decoder never builds a backlog.
+    fn publish_interim_snapshot(&mut self) {
+        if self.last_interim_publish.elapsed().as_millis() < self.interim_config.interval_ms as u128 {
+            return;
+        }
+
+        let duration_ms = self.vad_processor.in_progress_duration_ms();
+        if duration_ms < interim::MIN_INTERIM_AUDIO_MS || duration_ms > interim::MAX_INTERIM_AUDIO_MS {
+            return;
+        }
+
+        if let Some(segment) = self.vad_processor.in_progress_segment() {
+            self.last_interim_publish = std::time::Instant::now();
+            interim::publish(InterimSnapshot {
+                samples: segment.samples,
+                segment_start: segment.start_timestamp_ms / 1000.0,
+                segment_end: segment.end_timestamp_ms / 1000.0,
+            });
+        }
+    }
+
     fn flush_remaining_audio(&mut self) -> Result<()> {
         info!("Flushing remaining audio from pipeline (processed {} chunks)", self.processed_chunks);
 
+        // The open segment is about to be finalized; an interim decode of it would be stale
+        interim::clear();
+
         // Flush any remaining audio from VAD processor and send segments to transcription
         match self.vad_processor.flush() {
             Ok(final_segments) => {
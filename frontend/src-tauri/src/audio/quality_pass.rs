@@ -0,0 +1,936 @@
+// Post-recording quality pass - re-transcribes a saved recording with a slower, more
+// accurate model at low priority. The result is aligned to the live transcript by
+// timestamp and stored as a pending transcript version that the user can accept
+// (and later revert) or discard. User edits and speaker labels are never overwritten.
+
+use super::common::{split_segment_at_silence, write_transcripts_json};
+use super::decoder::decode_audio_file;
+use super::recording_preferences::RecordingPreferences;
+use super::transcription::vocabulary;
+use super::retranscription::{
+    find_audio_file, get_or_init_parakeet, get_or_init_whisper, RetranscriptionGuard,
+    VAD_REDEMPTION_TIME_MS,
+};
+use super::vad::get_speech_chunks_with_progress;
+use crate::api::TranscriptSegment;
+use crate::config::DEFAULT_PARAKEET_MODEL;
+use crate::database::models::{TranscriptVersion, TranscriptVersionSegment};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::transcript_version::TranscriptVersionsRepository;
+use crate::parakeet_engine::ParakeetEngine;
+use crate::state::AppState;
+use crate::whisper_engine::system_monitor::{create_system_monitor_with_limits, SystemMonitor};
+use crate::whisper_engine::WhisperEngine;
+use anyhow::{anyhow, Result};
+use log::{debug, error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use uuid::Uuid;
+
+/// Source tag stored on transcript versions created by this module
+const QUALITY_PASS_SOURCE: &str = "quality_pass";
+
+/// Default high-accuracy Whisper model for the quality pass
+pub const DEFAULT_QUALITY_MODEL: &str = "large-v3";
+
+/// How often to re-check while a recording, import or retranscription is running
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Pause before re-checking resources while the machine is busy
+const RESOURCE_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Back off whenever the user's own work pushes CPU above this (low priority)
+const MAX_CPU_PERCENT: f32 = 50.0;
+
+/// The quality model itself is large; only back off when memory is nearly exhausted
+const MAX_MEMORY_PERCENT: f32 = 90.0;
+
+/// Words this close to a live segment boundary still belong to that segment
+const BOUNDARY_TOLERANCE_SECS: f64 = 1.0;
+
+/// A live segment this much covered by re-decoded speech that received no words is dropped
+const COVERED_FRACTION: f64 = 0.5;
+
+/// A version segment sharing more than this with a segment the user edited is displaced by it
+const EDIT_OVERLAP_TOLERANCE_SECS: f64 = 0.2;
+
+const MAX_SEGMENT_SAMPLES: usize = 25 * 16000; // 25 seconds at 16kHz
+
+// Passes run one at a time, in the order recordings were saved
+static QUALITY_PASS_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+static QUALITY_PASS_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Quality pass settings derived from recording preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityPassConfig {
+    pub enabled: bool,
+    pub provider: String, // "whisper" or "parakeet"
+    pub model: String,
+}
+
+impl From<&RecordingPreferences> for QualityPassConfig {
+    fn from(prefs: &RecordingPreferences) -> Self {
+        let provider = prefs
+            .quality_pass_provider
+            .clone()
+            .unwrap_or_else(|| "whisper".to_string());
+        let model = prefs.quality_pass_model.clone().unwrap_or_else(|| {
+            if provider == "parakeet" {
+                DEFAULT_PARAKEET_MODEL.to_string()
+            } else {
+                DEFAULT_QUALITY_MODEL.to_string()
+            }
+        });
+
+        Self {
+            enabled: prefs.quality_pass_enabled,
+            provider,
+            model,
+        }
+    }
+}
+
+/// Progress update emitted during a quality pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityPassProgress {
+    pub meeting_id: String,
+    pub stage: String, // "waiting", "decoding", "transcribing", "aligning"
+    pub progress_percentage: u32,
+    pub message: String,
+}
+
+/// How the re-decoded transcript was merged into the live one
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AlignmentStats {
+    pub updated: usize,
+    pub unchanged: usize,
+    pub kept_edited: usize,
+    pub removed: usize,
+    pub inserted: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityPassResult {
+    pub meeting_id: String,
+    pub version_id: String,
+    pub model: String,
+    pub stats: AlignmentStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityPassError {
+    pub meeting_id: String,
+    pub error: String,
+}
+
+/// Re-decoded speech range, in seconds from recording start
+#[derive(Debug, Clone)]
+pub(crate) struct FreshSegment {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+enum QualityEngine {
+    Whisper(Arc<WhisperEngine>),
+    Parakeet(Arc<ParakeetEngine>),
+}
+
+/// Queue a quality pass for a freshly saved recording if the user opted in.
+/// The pass waits until no recording, import or retranscription is running.
+pub fn schedule_after_recording<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    folder_path: Option<String>,
+) {
+    let folder_path = match folder_path {
+        Some(path) => PathBuf::from(path),
+        None => return,
+    };
+
+    tauri::async_runtime::spawn(async move {
+        let config = match super::recording_preferences::load_recording_preferences(&app).await {
+            Ok(prefs) => QualityPassConfig::from(&prefs),
+            Err(e) => {
+                warn!("Quality pass skipped, failed to load recording preferences: {}", e);
+                return;
+            }
+        };
+
+        if !config.enabled {
+            return;
+        }
+
+        info!(
+            "Queued quality pass for meeting {} ({} / {})",
+            meeting_id, config.provider, config.model
+        );
+
+        if let Err(e) = run_queued(app.clone(), meeting_id.clone(), folder_path, config).await {
+            error!("Quality pass for meeting {} failed: {}", meeting_id, e);
+            let _ = app.emit(
+                "quality-pass-error",
+                QualityPassError {
+                    meeting_id,
+                    error: e.to_string(),
+                },
+            );
+        }
+    });
+}
+
+/// Cancel the quality pass that is currently running (queued passes still run)
+pub fn cancel_quality_pass() {
+    QUALITY_PASS_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+async fn run_queued<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    folder_path: PathBuf,
+    config: QualityPassConfig,
+) -> Result<()> {
+    let _queue = QUALITY_PASS_LOCK.lock().await;
+    QUALITY_PASS_CANCELLED.store(false, Ordering::SeqCst);
+
+    if live_model_matches(&app, &config).await {
+        info!(
+            "Skipping quality pass for meeting {}: live transcript already used {}",
+            meeting_id, config.model
+        );
+        return Ok(());
+    }
+
+    emit_progress(&app, &meeting_id, "waiting", 0, "Waiting for the transcription engine to be free...");
+    let _guard = wait_until_idle().await?;
+
+    let use_parakeet = config.provider == "parakeet";
+    let result = run_quality_pass(&app, &meeting_id, &folder_path, &config).await;
+
+    // Unload the quality model so the next recording loads the live model again
+    super::common::unload_engine_after_batch(use_parakeet).await;
+
+    let result = result?;
+    info!(
+        "Quality pass for meeting {} stored as version {}: {:?}",
+        meeting_id, result.version_id, result.stats
+    );
+    let _ = app.emit("quality-pass-complete", &result);
+    Ok(())
+}
+
+async fn run_quality_pass<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: &str,
+    folder_path: &Path,
+    config: &QualityPassConfig,
+) -> Result<QualityPassResult> {
+    let audio_path = find_audio_file(folder_path)?;
+    let monitor = create_system_monitor_with_limits(MAX_MEMORY_PERCENT, MAX_CPU_PERCENT, 85.0);
+    let vocabulary = vocabulary::load_for_meeting(app, Some(meeting_id)).await;
+
+    emit_progress(app, meeting_id, "decoding", 5, "Decoding recording...");
+    wait_for_resources(&monitor).await?;
+
+    let decoded = tokio::task::spawn_blocking(move || decode_audio_file(&audio_path))
+        .await
+        .map_err(|e| anyhow!("Decode task panicked: {}", e))??;
+    let audio_samples = tokio::task::spawn_blocking(move || decoded.to_whisper_format())
+        .await
+        .map_err(|e| anyhow!("Resample task panicked: {}", e))?;
+
+    check_interrupted().await?;
+    emit_progress(app, meeting_id, "decoding", 10, "Detecting speech segments...");
+
+    let speech_segments = tokio::task::spawn_blocking(move || {
+        get_speech_chunks_with_progress(&audio_samples, VAD_REDEMPTION_TIME_MS, |_, _| {
+            !QUALITY_PASS_CANCELLED.load(Ordering::SeqCst)
+        })
+    })
+    .await
+    .map_err(|e| anyhow!("VAD task panicked: {}", e))?
+    .map_err(|e| anyhow!("VAD processing failed: {}", e))?;
+
+    let mut segments = Vec::new();
+    for segment in speech_segments {
+        if segment.samples.len() > MAX_SEGMENT_SAMPLES {
+            segments.extend(split_segment_at_silence(&segment, MAX_SEGMENT_SAMPLES));
+        } else {
+            segments.push(segment);
+        }
+    }
+
+    if segments.is_empty() {
+        return Err(anyhow!("No speech detected in recording"));
+    }
+
+    check_interrupted().await?;
+    emit_progress(app, meeting_id, "transcribing", 15, &format!("Loading {}...", config.model));
+
+    let engine = if config.provider == "parakeet" {
+        QualityEngine::Parakeet(get_or_init_parakeet(app, Some(config.model.as_str())).await?)
+    } else {
+        QualityEngine::Whisper(get_or_init_whisper(app, Some(config.model.as_str())).await?)
+    };
+    let language = crate::get_language_preference_internal();
+
+    // One segment at a time, backing off whenever the machine is busy
+    let total = segments.len();
+    let mut fresh = Vec::with_capacity(total);
+    let mut previous_text: Option<String> = None;
+
+    for (i, segment) in segments.into_iter().enumerate() {
+        wait_for_resources(&monitor).await?;
+
+        if segment.samples.len() < 1600 {
+            continue;
+        }
+
+        let progress = 15 + ((i as f32 / total as f32) * 75.0) as u32;
+        emit_progress(
+            app,
+            meeting_id,
+            "transcribing",
+            progress,
+            &format!("Transcribing segment {} of {}...", i + 1, total),
+        );
+
+        let text = match &engine {
+            QualityEngine::Whisper(engine) => {
+                engine
+                    .transcribe_batch(segment.samples, language.clone(), previous_text.as_deref(), &vocabulary)
+                    .await
+                    .map_err(|e| anyhow!("Whisper failed on segment {}: {}", i, e))?
+                    .0
+            }
+            QualityEngine::Parakeet(engine) => engine
+                .transcribe_audio(segment.samples)
+                .await
+                .map_err(|e| anyhow!("Parakeet failed on segment {}: {}", i, e))?,
+        };
+
+        let text = vocabulary.correct(text.trim());
+        if text.is_empty() {
+            continue;
+        }
+
+        previous_text = Some(text.clone());
+        fresh.push(FreshSegment {
+            text,
+            start: segment.start_timestamp_ms / 1000.0,
+            end: segment.end_timestamp_ms / 1000.0,
+        });
+    }
+
+    check_interrupted().await?;
+    emit_progress(app, meeting_id, "aligning", 95, "Aligning with the live transcript...");
+
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| anyhow!("App state not available"))?;
+    let pool = state.db_manager.pool();
+
+    let existing = TranscriptVersionsRepository::get_current_segments(pool, meeting_id)
+        .await
+        .map_err(|e| anyhow!("Failed to load transcripts: {}", e))?;
+    if existing.is_empty() {
+        return Err(anyhow!("Meeting {} has no transcript to improve", meeting_id));
+    }
+
+    let (aligned, stats) = align_segments(&existing, &fresh);
+    let stats_json = serde_json::to_value(&stats)?;
+
+    let version_id = TranscriptVersionsRepository::create_version(
+        pool,
+        meeting_id,
+        QUALITY_PASS_SOURCE,
+        Some(config.provider.as_str()),
+        Some(config.model.as_str()),
+        &aligned,
+        &existing,
+        &stats_json,
+    )
+    .await
+    .map_err(|e| anyhow!("Failed to store transcript version: {}", e))?;
+
+    emit_progress(app, meeting_id, "complete", 100, "Improved transcript ready for review");
+
+    Ok(QualityPassResult {
+        meeting_id: meeting_id.to_string(),
+        version_id,
+        model: config.model.clone(),
+        stats,
+    })
+}
+
+/// Whether the live transcript was already produced by the quality model
+async fn live_model_matches<R: Runtime>(app: &AppHandle<R>, config: &QualityPassConfig) -> bool {
+    let state = match app.try_state::<AppState>() {
+        Some(state) => state,
+        None => return false,
+    };
+
+    let live: Option<(String, String)> =
+        sqlx::query_as("SELECT provider, model FROM transcript_settings WHERE id = '1'")
+            .fetch_optional(state.db_manager.pool())
+            .await
+            .unwrap_or(None);
+
+    match live {
+        Some((provider, model)) => {
+            let same_provider = match config.provider.as_str() {
+                "parakeet" => provider == "parakeet",
+                _ => provider == "localWhisper" || provider == "whisper",
+            };
+            same_provider && model == config.model
+        }
+        None => false,
+    }
+}
+
+/// Wait until the shared transcription engine is free, then claim it
+async fn wait_until_idle() -> Result<RetranscriptionGuard> {
+    loop {
+        if QUALITY_PASS_CANCELLED.load(Ordering::SeqCst) {
+            return Err(anyhow!("Quality pass cancelled"));
+        }
+
+        let busy = super::recording_commands::is_recording().await
+            || super::import::is_import_in_progress();
+        if !busy {
+            if let Ok(guard) = RetranscriptionGuard::acquire() {
+                return Ok(guard);
+            }
+        }
+
+        tokio::time::sleep(IDLE_POLL_INTERVAL).await;
+    }
+}
+
+/// Block until SystemMonitor reports headroom, so the pass never competes with the user
+async fn wait_for_resources(monitor: &SystemMonitor) -> Result<()> {
+    loop {
+        check_interrupted().await?;
+
+        monitor.refresh_system_info().await?;
+        let status = monitor.check_resource_constraints().await?;
+        if status.can_proceed {
+            return Ok(());
+        }
+
+        debug!(
+            "Quality pass paused: {}",
+            status.get_primary_constraint().unwrap_or_default()
+        );
+        tokio::time::sleep(RESOURCE_BACKOFF).await;
+    }
+}
+
+/// A new recording needs the engine with its own model loaded
+async fn check_interrupted() -> Result<()> {
+    if QUALITY_PASS_CANCELLED.load(Ordering::SeqCst) {
+        return Err(anyhow!("Quality pass cancelled"));
+    }
+    if super::recording_commands::is_recording().await {
+        return Err(anyhow!("Quality pass interrupted by a new recording"));
+    }
+    Ok(())
+}
+
+/// Merge re-decoded speech into the live transcript by timestamp.
+///
+/// Words of each fresh segment are spread evenly over its time range and handed to the
+/// live segment that contains them (or lies within BOUNDARY_TOLERANCE_SECS). Live segments
+/// keep their id, speaker label and timing; edited segments keep their text. Speech no
+/// live segment covers becomes a new segment, and live segments whose audio was
+/// re-decoded without producing any words (typically hallucinations) are dropped.
+pub(crate) fn align_segments(
+    existing: &[TranscriptVersionSegment],
+    fresh: &[FreshSegment],
+) -> (Vec<TranscriptVersionSegment>, AlignmentStats) {
+    let timed: Vec<(usize, f64, f64)> = existing
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| match (s.audio_start_time, s.audio_end_time) {
+            (Some(start), Some(end)) if end > start => Some((i, start, end)),
+            _ => None,
+        })
+        .collect();
+
+    let mut assigned: Vec<Vec<&str>> = vec![Vec::new(); existing.len()];
+    let mut orphans: Vec<(String, f64, f64)> = Vec::new();
+
+    for segment in fresh {
+        let words: Vec<&str> = segment.text.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+
+        let word_span = (segment.end - segment.start).max(0.0) / words.len() as f64;
+        let mut orphan_words: Vec<&str> = Vec::new();
+        let mut orphan_start = segment.start;
+        let mut orphan_end = segment.start;
+
+        for (w, word) in words.iter().enumerate() {
+            let at = segment.start + (w as f64 + 0.5) * word_span;
+            match owning_segment(&timed, at) {
+                Some(index) => {
+                    if !orphan_words.is_empty() {
+                        orphans.push((orphan_words.join(" "), orphan_start, orphan_end));
+                        orphan_words.clear();
+                    }
+                    assigned[index].push(word);
+                }
+                None => {
+                    if orphan_words.is_empty() {
+                        orphan_start = at - word_span / 2.0;
+                    }
+                    orphan_end = at + word_span / 2.0;
+                    orphan_words.push(word);
+                }
+            }
+        }
+
+        if !orphan_words.is_empty() {
+            orphans.push((orphan_words.join(" "), orphan_start, orphan_end));
+        }
+    }
+
+    let mut stats = AlignmentStats::default();
+    let mut aligned = Vec::with_capacity(existing.len() + orphans.len());
+
+    for (i, segment) in existing.iter().enumerate() {
+        if segment.edited_at.is_some() {
+            stats.kept_edited += 1;
+            aligned.push(segment.clone());
+            continue;
+        }
+
+        if !assigned[i].is_empty() {
+            let text = assigned[i].join(" ");
+            if text == segment.text {
+                stats.unchanged += 1;
+                aligned.push(segment.clone());
+            } else {
+                stats.updated += 1;
+                aligned.push(TranscriptVersionSegment {
+                    text,
+                    word_timings: None,
+                    ..segment.clone()
+                });
+            }
+        } else if coverage(segment, fresh) >= COVERED_FRACTION {
+            stats.removed += 1;
+        } else {
+            stats.unchanged += 1;
+            aligned.push(segment.clone());
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for (text, start, end) in orphans {
+        stats.inserted += 1;
+        aligned.push(TranscriptVersionSegment {
+            id: format!("transcript-{}", Uuid::new_v4()),
+            text,
+            timestamp: now.clone(),
+            audio_start_time: Some(start),
+            audio_end_time: Some(end),
+            duration: Some(end - start),
+            speaker: None,
+            edited_at: None,
+            language: None,
+            translation: None,
+            word_timings: None,
+        });
+    }
+
+    sort_by_audio_time(&mut aligned);
+    (aligned, stats)
+}
+
+/// Carry over changes made to the transcript after the pass finished.
+/// Speaker labels always come from the current transcript; edited segments win outright and
+/// displace the version's segments they overlap. Segments deleted since the pass started
+/// (in `base_ids` but no longer current) stay deleted.
+pub(crate) fn carry_over_edits(
+    version: Vec<TranscriptVersionSegment>,
+    current: &[TranscriptVersionSegment],
+    base_ids: Option<&[String]>,
+) -> Vec<TranscriptVersionSegment> {
+    let by_id: HashMap<&str, &TranscriptVersionSegment> =
+        current.iter().map(|s| (s.id.as_str(), s)).collect();
+    let base_ids: HashSet<&str> = base_ids.unwrap_or_default().iter().map(|id| id.as_str()).collect();
+    let edited: Vec<&TranscriptVersionSegment> = current.iter().filter(|s| s.edited_at.is_some()).collect();
+
+    let mut merged: Vec<TranscriptVersionSegment> = version
+        .into_iter()
+        .filter_map(|mut segment| match by_id.get(segment.id.as_str()) {
+            // Added back below with the user's changes
+            Some(current_segment) if current_segment.edited_at.is_some() => None,
+            Some(current_segment) => {
+                segment.speaker = current_segment.speaker.clone();
+                Some(segment)
+            }
+            None if base_ids.contains(segment.id.as_str()) => None,
+            None => Some(segment),
+        })
+        .filter(|segment| !edited.iter().any(|e| overlap(segment, e) > EDIT_OVERLAP_TOLERANCE_SECS))
+        .collect();
+    merged.extend(edited.into_iter().cloned());
+
+    sort_by_audio_time(&mut merged);
+    merged
+}
+
+/// Seconds two segments share
+fn overlap(a: &TranscriptVersionSegment, b: &TranscriptVersionSegment) -> f64 {
+    match (a.audio_start_time, a.audio_end_time, b.audio_start_time, b.audio_end_time) {
+        (Some(a_start), Some(a_end), Some(b_start), Some(b_end)) => {
+            (a_end.min(b_end) - a_start.max(b_start)).max(0.0)
+        }
+        _ => 0.0,
+    }
+}
+
+/// Live segment that owns a word spoken at `at` seconds
+fn owning_segment(timed: &[(usize, f64, f64)], at: f64) -> Option<usize> {
+    if let Some((index, _, _)) = timed.iter().find(|(_, start, end)| at >= *start && at <= *end) {
+        return Some(*index);
+    }
+
+    timed
+        .iter()
+        .map(|(index, start, end)| {
+            let distance = if at < *start { start - at } else { at - end };
+            (*index, distance)
+        })
+        .filter(|(_, distance)| *distance <= BOUNDARY_TOLERANCE_SECS)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(index, _)| index)
+}
+
+/// Fraction of a live segment's time range that was re-decoded
+fn coverage(segment: &TranscriptVersionSegment, fresh: &[FreshSegment]) -> f64 {
+    let (start, end) = match (segment.audio_start_time, segment.audio_end_time) {
+        (Some(start), Some(end)) if end > start => (start, end),
+        _ => return 0.0,
+    };
+
+    let covered: f64 = fresh
+        .iter()
+        .filter(|f| !f.text.trim().is_empty())
+        .map(|f| (end.min(f.end) - start.max(f.start)).max(0.0))
+        .sum();
+    covered / (end - start)
+}
+
+fn sort_by_audio_time(segments: &mut [TranscriptVersionSegment]) {
+    segments.sort_by(|a, b| {
+        let a = a.audio_start_time.unwrap_or(f64::INFINITY);
+        let b = b.audio_start_time.unwrap_or(f64::INFINITY);
+        a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+/// Emit progress event
+fn emit_progress<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: &str,
+    stage: &str,
+    progress: u32,
+    message: &str,
+) {
+    let _ = app.emit(
+        "quality-pass-progress",
+        QualityPassProgress {
+            meeting_id: meeting_id.to_string(),
+            stage: stage.to_string(),
+            progress_percentage: progress,
+            message: message.to_string(),
+        },
+    );
+}
+
+/// Keep transcripts.json in the meeting folder in sync with the database
+pub(crate) async fn sync_transcripts_file<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: &str,
+    segments: &[TranscriptVersionSegment],
+) {
+    let state = match app.try_state::<AppState>() {
+        Some(state) => state,
+        None => return,
+    };
+
+    let folder = match MeetingsRepository::get_meeting_metadata(state.db_manager.pool(), meeting_id).await {
+        Ok(Some(meeting)) => meeting.folder_path,
+        _ => None,
+    };
+
+    if let Some(folder) = folder {
+        let file_segments: Vec<TranscriptSegment> = segments
+            .iter()
+            .map(|s| TranscriptSegment {
+                id: s.id.clone(),
+                text: s.text.clone(),
+                timestamp: s.timestamp.clone(),
+                audio_start_time: s.audio_start_time,
+                audio_end_time: s.audio_end_time,
+                duration: s.duration,
+                language: s.language.clone(),
+                translation: s.translation.clone(),
+            })
+            .collect();
+
+        if let Err(e) = write_transcripts_json(Path::new(&folder), &file_segments) {
+            warn!("Failed to write transcripts.json for meeting {}: {}", meeting_id, e);
+        }
+    }
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub async fn get_transcript_versions_command<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+) -> Result<Vec<TranscriptVersion>, String> {
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| "App state not available".to_string())?;
+
+    TranscriptVersionsRepository::list_versions(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript versions: {}", e))
+}
+
+/// Replace the meeting's transcript with a pending version
+#[tauri::command]
+pub async fn accept_transcript_version_command<R: Runtime>(
+    app: AppHandle<R>,
+    version_id: String,
+) -> Result<(), String> {
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| "App state not available".to_string())?;
+    let pool = state.db_manager.pool();
+
+    let version = TranscriptVersionsRepository::get_version(pool, &version_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Transcript version {} not found", version_id))?;
+
+    let applied = TranscriptVersionsRepository::accept_version(pool, &version_id, carry_over_edits)
+        .await
+        .map_err(|e| format!("Failed to accept transcript version: {}", e))?;
+
+    sync_transcripts_file(&app, &version.meeting_id, &applied).await;
+    let _ = app.emit(
+        "transcript-version-changed",
+        serde_json::json!({
+            "meeting_id": version.meeting_id,
+            "version_id": version_id,
+            "status": "accepted"
+        }),
+    );
+    Ok(())
+}
+
+/// Restore the transcript an accepted version replaced
+#[tauri::command]
+pub async fn revert_transcript_version_command<R: Runtime>(
+    app: AppHandle<R>,
+    version_id: String,
+) -> Result<(), String> {
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| "App state not available".to_string())?;
+    let pool = state.db_manager.pool();
+
+    let version = TranscriptVersionsRepository::get_version(pool, &version_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Transcript version {} not found", version_id))?;
+
+    let restored = TranscriptVersionsRepository::revert_version(pool, &version_id)
+        .await
+        .map_err(|e| format!("Failed to revert transcript version: {}", e))?;
+
+    sync_transcripts_file(&app, &version.meeting_id, &restored).await;
+    let _ = app.emit(
+        "transcript-version-changed",
+        serde_json::json!({
+            "meeting_id": version.meeting_id,
+            "version_id": version_id,
+            "status": "reverted"
+        }),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn discard_transcript_version_command<R: Runtime>(
+    app: AppHandle<R>,
+    version_id: String,
+) -> Result<(), String> {
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| "App state not available".to_string())?;
+
+    let discarded = TranscriptVersionsRepository::discard_version(state.db_manager.pool(), &version_id)
+        .await
+        .map_err(|e| format!("Failed to discard transcript version: {}", e))?;
+
+    if !discarded {
+        return Err(format!("Transcript version {} is not pending", version_id));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn cancel_quality_pass_command() -> Result<(), String> {
+    cancel_quality_pass();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live(id: &str, text: &str, start: f64, end: f64) -> TranscriptVersionSegment {
+        crate::database::models::segment(id, text, start, end)
+    }
+
+    fn fresh(text: &str, start: f64, end: f64) -> FreshSegment {
+        FreshSegment {
+            text: text.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_align_spreads_long_segment_over_live_segments() {
+        let existing = vec![live("a", "helo world", 0.0, 2.0), live("b", "how r you", 2.5, 4.0)];
+        // Batch VAD bridges the pause, producing one longer segment
+        let (aligned, stats) = align_segments(&existing, &[fresh("hello world how are you", 0.0, 4.0)]);
+
+        assert_eq!(aligned.len(), 2);
+        assert_eq!(aligned[0].id, "a");
+        assert_eq!(aligned[0].text, "hello world");
+        assert_eq!(aligned[1].id, "b");
+        assert_eq!(aligned[1].text, "how are you");
+        assert_eq!(stats.updated, 2);
+    }
+
+    #[test]
+    fn test_align_keeps_edits_and_speakers() {
+        let mut edited = live("a", "Acme Corp quarterly review", 0.0, 2.0);
+        edited.edited_at = Some("2025-01-01T00:10:00Z".to_string());
+        let mut labelled = live("b", "sounds gud", 2.0, 4.0);
+        labelled.speaker = Some("system".to_string());
+
+        let (aligned, stats) = align_segments(
+            &[edited.clone(), labelled],
+            &[fresh("acne corp quarterly review", 0.0, 2.0), fresh("sounds good", 2.0, 4.0)],
+        );
+
+        assert_eq!(aligned[0], edited);
+        assert_eq!(aligned[1].text, "sounds good");
+        assert_eq!(aligned[1].speaker.as_deref(), Some("system"));
+        assert_eq!(stats.kept_edited, 1);
+        assert_eq!(stats.updated, 1);
+    }
+
+    #[test]
+    fn test_align_inserts_missed_speech_and_drops_hallucinations() {
+        let existing = vec![
+            live("a", "thanks for watching", 0.0, 2.0),
+            live("b", "let's start", 10.0, 12.0),
+        ];
+
+        // Speech the live pass missed becomes a new segment; an empty decode removes nothing
+        let (aligned, stats) = align_segments(
+            &existing,
+            &[fresh("", 0.0, 2.0), fresh("quick intro first", 5.0, 7.0), fresh("let's start", 10.0, 12.0)],
+        );
+        assert_eq!(stats.inserted, 1);
+        assert_eq!(stats.removed, 0);
+        assert_eq!(aligned.len(), 3);
+        assert_eq!(aligned[1].text, "quick intro first");
+        assert_eq!(aligned[1].speaker, None);
+
+        // Re-decoded audio over "a" produced words that all belong to "c"
+        let (aligned, stats) = align_segments(
+            &[live("a", "thanks for watching", 0.0, 2.0), live("c", "right", 3.5, 4.5)],
+            &[fresh("right", 1.0, 5.0)],
+        );
+        assert_eq!(stats.removed, 1);
+        assert_eq!(aligned.len(), 1);
+        assert_eq!(aligned[0].id, "c");
+    }
+
+    #[test]
+    fn test_carry_over_edits_made_after_pass() {
+        let version = vec![live("a", "improved text", 0.0, 2.0), live("b", "more text", 2.0, 4.0)];
+
+        let mut edited = live("a", "user fixed this", 0.0, 2.0);
+        edited.edited_at = Some("2025-01-02T00:00:00Z".to_string());
+        let mut labelled = live("b", "mor text", 2.0, 4.0);
+        labelled.speaker = Some("mic".to_string());
+        let mut inserted = live("x", "added by user", 5.0, 6.0);
+        inserted.edited_at = Some("2025-01-02T00:00:00Z".to_string());
+
+        let merged = carry_over_edits(version, &[edited.clone(), labelled, inserted.clone()], None);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0], edited);
+        assert_eq!(merged[1].text, "more text");
+        assert_eq!(merged[1].speaker.as_deref(), Some("mic"));
+        assert_eq!(merged[2], inserted);
+    }
+
+    #[test]
+    fn test_carry_over_keeps_segments_deleted_after_pass_deleted() {
+        let base = vec!["a".to_string(), "b".to_string()];
+        let version = vec![
+            live("a", "improved text", 0.0, 2.0),
+            live("b", "deleted by user", 2.0, 4.0),
+            live("n", "speech the live pass missed", 5.0, 6.0),
+        ];
+
+        let merged = carry_over_edits(version, &[live("a", "improvd text", 0.0, 2.0)], Some(base.as_slice()));
+
+        // "n" is new in the version; "b" was removed from the transcript after the pass
+        let ids: Vec<&str> = merged.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["a", "n"]);
+    }
+
+    #[test]
+    fn test_carry_over_drops_segments_overlapping_an_edit() {
+        let base = vec!["a".to_string(), "b".to_string()];
+        let version = vec![
+            live("a", "first half", 0.0, 2.0),
+            live("n", "second half", 2.0, 4.0),
+            live("b", "next sentence", 4.0, 6.0),
+        ];
+
+        // The user stretched "a" over the speech the pass split into a new segment
+        let mut edited = live("a", "first half and second half", 0.0, 4.0);
+        edited.edited_at = Some("2025-01-02T00:00:00Z".to_string());
+
+        let current = [edited.clone(), live("b", "next sentence", 4.0, 6.0)];
+        let merged = carry_over_edits(version, &current, Some(base.as_slice()));
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0], edited);
+        assert_eq!(merged[1].id, "b");
+    }
+}
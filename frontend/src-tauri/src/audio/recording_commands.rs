@@ -191,14 +191,20 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
             Ok(prefs) => {
                 info!("📋 Loaded recording preferences: auto_save={}, preferred_mic={:?}, preferred_system={:?}",
                       prefs.auto_save, prefs.preferred_mic_device, prefs.preferred_system_device);
+                transcription::interim::configure(transcription::interim::InterimConfig::from(&prefs));
                 (prefs.auto_save, prefs.preferred_mic_device, prefs.preferred_system_device)
             }
             Err(e) => {
                 warn!("Failed to load recording preferences, using defaults: {}", e);
+                transcription::interim::configure(transcription::interim::InterimConfig::default());
                 (true, None, None)
             }
         };
 
+    // Live recordings have no meeting id yet; the user-wide list applies, plus the list of
+    // the meeting chosen with set_recording_vocabulary
+    transcription::vocabulary::activate_for_recording(&app).await;
+
     // ============================================================================
     // MICROPHONE DEVICE RESOLUTION: Preference → Default → Error
     // ============================================================================
@@ -338,6 +344,11 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
         let listener_id = app.listen("transcript-update", move |event: tauri::Event| {
             // Parse the transcript update from the event payload
             if let Ok(update) = serde_json::from_str::<TranscriptUpdate>(event.payload()) {
+                // Interim text is display-only; the final update with the same sequence_id is saved
+                if update.is_partial {
+                    return;
+                }
+
                 // Create structured transcript segment
                 let segment = crate::audio::recording_saver::TranscriptSegment {
                     id: format!("seg_{}", update.sequence_id),
@@ -348,6 +359,8 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    language: update.language.clone(),
+                    translation: update.translation.clone(),
                 };
 
                 // Save to recording manager
@@ -373,6 +386,9 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
     // Update tray menu to reflect recording state
     crate::tray::update_tray_menu(&app);
 
+    // Opt-in rolling summary while recording
+    crate::summary::live::start_live_summary(&app);
+
     info!("✅ Recording started successfully with async-first approach");
 
     // Track recording session started analytics (fire-and-forget)
@@ -501,14 +517,20 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     let auto_save = match super::recording_preferences::load_recording_preferences(&app).await {
         Ok(prefs) => {
             info!("📋 Loaded recording preferences: auto_save={}", prefs.auto_save);
+            transcription::interim::configure(transcription::interim::InterimConfig::from(&prefs));
             prefs.auto_save
         }
         Err(e) => {
             warn!("Failed to load recording preferences, defaulting to auto_save=true: {}", e);
+            transcription::interim::configure(transcription::interim::InterimConfig::default());
             true // Default to saving if preferences can't be loaded
         }
     };
 
+    // Live recordings have no meeting id yet; the user-wide list applies, plus the list of
+    // the meeting chosen with set_recording_vocabulary
+    transcription::vocabulary::activate_for_recording(&app).await;
+
     // Always ensure a meeting name is set so incremental saver initializes
     let effective_meeting_name = meeting_name.clone().unwrap_or_else(|| {
         let now = chrono::Local::now();
@@ -557,6 +579,11 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
         let listener_id = app.listen("transcript-update", move |event: tauri::Event| {
             // Parse the transcript update from the event payload
             if let Ok(update) = serde_json::from_str::<TranscriptUpdate>(event.payload()) {
+                // Interim text is display-only; the final update with the same sequence_id is saved
+                if update.is_partial {
+                    return;
+                }
+
                 // Create structured transcript segment
                 let segment = crate::audio::recording_saver::TranscriptSegment {
                     id: format!("seg_{}", update.sequence_id),
@@ -567,6 +594,8 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    language: update.language.clone(),
+                    translation: update.translation.clone(),
                 };
 
                 // Save to recording manager
@@ -595,6 +624,9 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     // Update tray menu to reflect recording state
     crate::tray::update_tray_menu(&app);
 
+    // Opt-in rolling summary while recording
+    crate::summary::live::start_live_summary(&app);
+
     info!("✅ Recording started with custom devices using async-first approach");
 
     // Track recording session started analytics (fire-and-forget)
@@ -710,6 +742,7 @@ pub async fn stop_recording<R: Runtime>(
             info!("✅ Transcript-update listener removed");
         }
     }
+    transcription::interim::clear();
 
     // Step 2: Signal transcription workers to finish processing ALL queued chunks
     let _ = app.emit(
@@ -1000,6 +1033,7 @@ pub async fn stop_recording<R: Runtime>(
     // Set recording flag to false
     info!("🔍 Setting IS_RECORDING to false");
     IS_RECORDING.store(false, Ordering::SeqCst);
+    crate::summary::live::stop_live_summary();
 
     // Step 4.5: Prepare metadata for frontend (NO database save)
     // NOTE: We do NOT save to database here. The frontend will save after all transcripts are displayed.
@@ -1207,6 +1241,53 @@ pub async fn get_recording_meeting_name() -> Result<Option<String>, String> {
     }
 }
 
+/// Add a bookmark marker at the current point of the recording
+/// Callable from the UI, a global shortcut or the tray menu; emits recording-marker-added
+#[tauri::command]
+pub async fn add_recording_marker<R: Runtime>(
+    app: AppHandle<R>,
+    label: Option<String>,
+) -> Result<super::markers::RecordingMarker, String> {
+    if !IS_RECORDING.load(Ordering::SeqCst) {
+        return Err("No recording in progress".to_string());
+    }
+
+    let marker = {
+        let manager_guard = RECORDING_MANAGER.lock().unwrap();
+        let manager = manager_guard
+            .as_ref()
+            .ok_or_else(|| "No recording in progress".to_string())?;
+        let time_seconds = manager.get_active_recording_duration().unwrap_or(0.0);
+        let sequence_id =
+            super::markers::active_sequence_id(&manager.get_transcript_segments(), time_seconds);
+        let marker = super::markers::RecordingMarker::new(time_seconds, label, sequence_id);
+        manager.add_marker(marker.clone());
+        marker
+    };
+
+    info!(
+        "Added recording marker {} at {:.1}s (segment: {:?})",
+        marker.id, marker.time_seconds, marker.sequence_id
+    );
+    if let Err(e) = app.emit("recording-marker-added", &marker) {
+        warn!("Failed to emit recording-marker-added event: {}", e);
+    }
+    Ok(marker)
+}
+
+/// Get markers added during the current recording session
+/// Used for syncing frontend state after page reload during active recording
+#[tauri::command]
+pub async fn get_recording_markers() -> Result<Vec<super::markers::RecordingMarker>, String> {
+    let manager_guard = RECORDING_MANAGER.lock().unwrap();
+
+    if let Some(manager) = manager_guard.as_ref() {
+        Ok(manager.get_markers())
+    } else {
+        Ok(Vec::new())
+    }
+}
+
 // ============================================================================
 // DEVICE MONITORING COMMANDS (AirPods/Bluetooth disconnect/reconnect support)
 // ============================================================================
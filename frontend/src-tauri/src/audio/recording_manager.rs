@@ -462,6 +462,16 @@ impl RecordingManager {
         self.recording_saver.get_meeting_name()
     }
 
+    /// Add a bookmark marker to the current recording session
+    pub fn add_marker(&self, marker: super::markers::RecordingMarker) {
+        self.recording_saver.add_marker(marker);
+    }
+
+    /// Get markers added during the current recording session
+    pub fn get_markers(&self) -> Vec<super::markers::RecordingMarker> {
+        self.recording_saver.get_markers()
+    }
+
     /// Cleanup all resources without saving
     pub async fn cleanup_without_save(&mut self) {
         if self.is_recording() {
@@ -8,7 +8,7 @@ use anyhow::Result;
 #[cfg(target_os = "macos")]
 use log::error;
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 use crate::audio::capture::AudioCaptureBackend;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -20,9 +20,34 @@ pub struct RecordingPreferences {
     pub preferred_mic_device: Option<String>,
     #[serde(default)]
     pub preferred_system_device: Option<String>,
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     #[serde(default)]
     pub system_audio_backend: Option<String>,
+    /// Stream partial transcripts of the segment still being spoken
+    #[serde(default)]
+    pub live_interim_transcripts: bool,
+    #[serde(default = "default_interim_interval_ms")]
+    pub interim_interval_ms: u64,
+    /// Re-transcribe each saved recording with a more accurate model in the background
+    #[serde(default)]
+    pub quality_pass_enabled: bool,
+    #[serde(default)]
+    pub quality_pass_provider: Option<String>,
+    #[serde(default)]
+    pub quality_pass_model: Option<String>,
+    /// Keep a rolling summary of the recording with the configured summary model
+    #[serde(default)]
+    pub live_summary_enabled: bool,
+    #[serde(default = "default_live_summary_interval_minutes")]
+    pub live_summary_interval_minutes: u64,
+}
+
+fn default_interim_interval_ms() -> u64 {
+    crate::audio::transcription::interim::DEFAULT_INTERIM_INTERVAL_MS
+}
+
+fn default_live_summary_interval_minutes() -> u64 {
+    crate::summary::live::DEFAULT_INTERVAL_MINUTES
 }
 
 impl Default for RecordingPreferences {
@@ -35,6 +60,15 @@ impl Default for RecordingPreferences {
             preferred_system_device: None,
             #[cfg(target_os = "macos")]
             system_audio_backend: Some("coreaudio".to_string()),
+            #[cfg(target_os = "linux")]
+            system_audio_backend: Some("pulseaudio".to_string()),
+            live_interim_transcripts: false,
+            interim_interval_ms: default_interim_interval_ms(),
+            quality_pass_enabled: false,
+            quality_pass_provider: None,
+            quality_pass_model: None,
+            live_summary_enabled: false,
+            live_summary_interval_minutes: default_live_summary_interval_minutes(),
         }
     }
 }
@@ -110,8 +144,8 @@ pub async fn load_recording_preferences<R: Runtime>(
         match serde_json::from_value::<RecordingPreferences>(value.clone()) {
             Ok(mut p) => {
                 info!("Loaded recording preferences from store");
-                // Update macOS backend to current value if needed
-                #[cfg(target_os = "macos")]
+                // Update macOS/Linux backend to current value if needed
+                #[cfg(any(target_os = "macos", target_os = "linux"))]
                 {
                     let backend = crate::audio::capture::get_current_backend();
                     p.system_audio_backend = Some(backend.to_string());
@@ -163,7 +197,7 @@ pub async fn save_recording_preferences<R: Runtime>(
     info!("Successfully persisted recording preferences to disk");
 
     // Save backend preference to global config
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     if let Some(backend_str) = &preferences.system_audio_backend {
         if let Some(backend) = AudioCaptureBackend::from_string(backend_str) {
             info!("Setting audio capture backend to: {:?}", backend);
@@ -259,15 +293,15 @@ pub async fn select_recording_folder<R: Runtime>(
 /// Get available audio capture backends for the current platform
 #[tauri::command]
 pub async fn get_available_audio_backends() -> Result<Vec<String>, String> {
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
         let backends = crate::audio::capture::get_available_backends();
         Ok(backends.iter().map(|b| b.to_string()).collect())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
-        // Only ScreenCaptureKit available on non-macOS
+        // Only ScreenCaptureKit available on Windows
         Ok(vec!["screencapturekit".to_string()])
     }
 }
@@ -275,13 +309,13 @@ pub async fn get_available_audio_backends() -> Result<Vec<String>, String> {
 /// Get current audio capture backend
 #[tauri::command]
 pub async fn get_current_audio_backend() -> Result<String, String> {
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
         let backend = crate::audio::capture::get_current_backend();
         Ok(backend.to_string())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         Ok("screencapturekit".to_string())
     }
@@ -332,7 +366,28 @@ pub async fn set_audio_backend(backend: String) -> Result<(), String> {
         Ok(())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        let backend_enum = AudioCaptureBackend::from_string(&backend)
+            .ok_or_else(|| format!("Invalid backend: {}", backend))?;
+
+        // No permission prompt on Linux, but warn early if the sound server is missing
+        if backend_enum == AudioCaptureBackend::PulseAudio
+            && !crate::audio::capture::pulse_audio::is_available()
+        {
+            return Err(
+                "No PulseAudio or PipeWire server found. \
+                Install pactl/parec (pulseaudio-utils) and make sure pipewire-pulse is running."
+                    .to_string(),
+            );
+        }
+
+        info!("Setting audio backend to: {:?}", backend_enum);
+        crate::audio::capture::set_current_backend(backend_enum);
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         if backend != "screencapturekit" {
             return Err(format!(
@@ -375,7 +430,20 @@ pub async fn get_audio_backend_info() -> Result<Vec<BackendInfo>, String> {
         Ok(backends)
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        let backends = crate::audio::capture::get_available_backends()
+            .into_iter()
+            .map(|backend| BackendInfo {
+                id: backend.to_string(),
+                name: backend.name().to_string(),
+                description: backend.description().to_string(),
+            })
+            .collect();
+        Ok(backends)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         Ok(vec![BackendInfo {
             id: "screencapturekit".to_string(),
@@ -7,6 +7,7 @@ use tokio::sync::mpsc;
 use serde::{Serialize, Deserialize};
 use std::path::PathBuf;
 
+use super::markers::RecordingMarker;
 use super::recording_state::AudioChunk;
 use super::audio_processing::create_meeting_folder;
 use super::incremental_saver::IncrementalAudioSaver;
@@ -22,6 +23,10 @@ pub struct TranscriptSegment {
     pub display_time: String,   // Formatted time for display like "[02:15]"
     pub confidence: f32,
     pub sequence_id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
 }
 
 /// Meeting metadata structure
@@ -53,6 +58,7 @@ pub struct RecordingSaver {
     meeting_name: Option<String>,
     metadata: Option<MeetingMetadata>,
     transcript_segments: Arc<RwLock<Vec<TranscriptSegment>>>,
+    markers: Arc<RwLock<Vec<RecordingMarker>>>,
     chunk_receiver: Option<mpsc::UnboundedReceiver<AudioChunk>>,
     is_saving: Arc<Mutex<bool>>,
 }
@@ -65,6 +71,7 @@ impl RecordingSaver {
             meeting_name: None,
             metadata: None,
             transcript_segments: Arc::new(RwLock::new(Vec::new())),
+            markers: Arc::new(RwLock::new(Vec::new())),
             chunk_receiver: None,
             is_saving: Arc::new(Mutex::new(false)),
         }
@@ -118,6 +125,31 @@ impl RecordingSaver {
         }
     }
 
+    /// Add a bookmark marker and save markers.json next to the transcript
+    pub fn add_marker(&self, marker: RecordingMarker) {
+        if let Ok(mut markers) = self.markers.write() {
+            markers.push(marker);
+        } else {
+            error!("Failed to lock markers for adding marker {}", marker.id);
+            return;
+        }
+
+        if let Some(folder) = &self.meeting_folder {
+            if let Err(e) = self.write_markers_json(folder) {
+                warn!("Failed to write markers.json: {}", e);
+            }
+        }
+    }
+
+    /// Get markers added during this recording session
+    pub fn get_markers(&self) -> Vec<RecordingMarker> {
+        if let Ok(markers) = self.markers.read() {
+            markers.clone()
+        } else {
+            Vec::new()
+        }
+    }
+
     /// Legacy method for backward compatibility - converts text to basic segment
     pub fn add_transcript_chunk(&self, text: String) {
         let segment = TranscriptSegment {
@@ -129,6 +161,8 @@ impl RecordingSaver {
             display_time: "[00:00]".to_string(),
             confidence: 1.0,
             sequence_id: 0,
+            language: None,
+            translation: None,
         };
         self.add_transcript_segment(segment);
     }
@@ -273,11 +307,10 @@ impl RecordingSaver {
     /// Write metadata.json to disk (atomic write with temp file)
     fn write_metadata(&self, folder: &PathBuf, metadata: &MeetingMetadata) -> Result<()> {
         let metadata_path = folder.join("metadata.json");
-        let temp_path = folder.join(".metadata.json.tmp");
 
         let json_string = serde_json::to_string_pretty(metadata)?;
-        std::fs::write(&temp_path, json_string)?;
-        std::fs::rename(&temp_path, &metadata_path)?;  // Atomic
+        crate::encryption::files::write_file(&metadata_path, json_string.as_bytes())
+            .map_err(|e| anyhow::anyhow!(e))?;  // Atomic
 
         Ok(())
     }
@@ -295,7 +328,6 @@ impl RecordingSaver {
         info!("Writing {} transcript segments to JSON", segments_clone.len());
 
         let transcript_path = folder.join("transcripts.json");
-        let temp_path = folder.join(".transcripts.json.tmp");
 
         // Create JSON structure
         let json = serde_json::json!({
@@ -312,28 +344,30 @@ impl RecordingSaver {
                 anyhow::anyhow!("JSON serialization failed: {}", e)
             })?;
 
-        // Write to temp file with error handling
-        std::fs::write(&temp_path, &json_string)
+        // Atomic write (temp file + rename), encrypted in encrypted library mode
+        crate::encryption::files::write_file(&transcript_path, json_string.as_bytes())
             .map_err(|e| {
-                error!("Failed to write transcript temp file to {}: {}", temp_path.display(), e);
-                anyhow::anyhow!("Failed to write temp file: {}", e)
+                error!("Failed to write transcript file to {}: {}", transcript_path.display(), e);
+                anyhow::anyhow!("Failed to write transcript file: {}", e)
             })?;
 
-        // Verify temp file was written correctly
-        if !temp_path.exists() {
-            error!("Temp transcript file does not exist after write: {}", temp_path.display());
-            return Err(anyhow::anyhow!("Temp file verification failed"));
-        }
+        info!("✅ Successfully wrote transcripts.json with {} segments", segments_clone.len());
+        Ok(())
+    }
 
-        // Atomic rename
-        std::fs::rename(&temp_path, &transcript_path)
-            .map_err(|e| {
-                error!("Failed to rename transcript file from {} to {}: {}",
-                       temp_path.display(), transcript_path.display(), e);
-                anyhow::anyhow!("Failed to rename transcript file: {}", e)
-            })?;
+    /// Write markers.json to disk (atomic write with temp file)
+    fn write_markers_json(&self, folder: &PathBuf) -> Result<()> {
+        let markers = self.get_markers();
+        let json = serde_json::json!({
+            "version": "1.0",
+            "markers": markers,
+        });
 
-        info!("✅ Successfully wrote transcripts.json with {} segments", segments_clone.len());
+        let markers_path = folder.join("markers.json");
+        crate::encryption::files::write_file(&markers_path, serde_json::to_string_pretty(&json)?.as_bytes())
+            .map_err(|e| anyhow::anyhow!(e))?; // Atomic
+
+        info!("Wrote markers.json with {} markers", markers.len());
         Ok(())
     }
 
@@ -406,7 +440,7 @@ impl RecordingSaver {
 
             // Verify transcripts were written correctly
             let transcript_path = folder.join("transcripts.json");
-            if !transcript_path.exists() {
+            if !crate::encryption::files::exists(&transcript_path) {
                 error!("❌ Transcript file was not created at: {}", transcript_path.display());
                 return Err("Transcript file verification failed".to_string());
             }
@@ -0,0 +1,751 @@
+// Redacted copies of meetings for sharing
+//
+// A redacted copy is a new meeting made from an original and a list of transcript spans,
+// selected by hand or suggested by the personal-detail detectors of summary::redaction. In the
+// copy each span reads "[REDACTED]" in the transcript, the audio under it is muted or bleeped
+// (decoded, edited as samples and re-encoded like a recording), and the summary is masked or
+// regenerated from the redacted transcript. Notes, markers and chapters are not copied. The
+// original is only read; the copy is linked to it in `redacted_copies`.
+
+use crate::api::TranscriptSegment;
+use crate::database::models::{RedactedCopy, Transcript};
+use crate::database::repositories::{
+    meeting::MeetingsRepository, redacted_copy::RedactedCopiesRepository, summary::SummaryProcessesRepository,
+    transcript::TranscriptsRepository, transcript_chunk::TranscriptChunksRepository,
+    transcript_revision::TranscriptRevisionsRepository,
+};
+use crate::encryption::files as library_files;
+use crate::state::AppState;
+use crate::summary::redaction::{self, Detector, EntityKind};
+use crate::summary::service::SummaryService;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::audio_processing::create_meeting_folder;
+use super::common::write_transcripts_json;
+use super::decoder::decode_audio_file;
+use super::encode::encode_single_audio;
+use super::meeting_edit::write_edit_metadata;
+use super::recording_preferences::get_default_recordings_folder;
+use super::retranscription::find_audio_file;
+use super::transcript_edit::WordTiming;
+
+/// Replacement of redacted transcript text
+const REDACTED: &str = "[REDACTED]";
+
+/// File name of the redacted audio inside the copy's folder
+const COPY_AUDIO_FILE: &str = "audio.mp4";
+
+/// Extra audio redacted on each side of a span, in seconds. Word timings from re-alignment
+/// are tight; positions estimated from the length of the segment text are not.
+const ALIGNED_PADDING_SECONDS: f64 = 0.1;
+const ESTIMATED_PADDING_SECONDS: f64 = 0.4;
+
+/// Fade at the edges of a redacted audio range, so the cut does not click
+const FADE_SECONDS: f64 = 0.005;
+
+const BLEEP_FREQUENCY_HZ: f64 = 1000.0;
+const BLEEP_AMPLITUDE: f32 = 0.2;
+
+/// Template of regenerated summaries when none is chosen
+const DEFAULT_TEMPLATE_ID: &str = "standard_meeting";
+
+/// Part of a transcript segment to redact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSpan {
+    pub segment_id: String,
+    /// Character offsets into the segment text (end exclusive)
+    pub start: usize,
+    pub end: usize,
+    /// What a detector found; None for spans selected by hand
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<EntityKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detector: Option<Detector>,
+    /// The spanned text (suggestions only, never stored)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// How redacted parts of the audio sound in the copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioRedaction {
+    Mute,
+    Bleep,
+}
+
+impl AudioRedaction {
+    fn as_str(self) -> &'static str {
+        match self {
+            AudioRedaction::Mute => "mute",
+            AudioRedaction::Bleep => "bleep",
+        }
+    }
+}
+
+/// What the copy gets as summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum SummaryRedaction {
+    /// The original summary with every redacted value replaced
+    Mask,
+    /// A new summary generated from the redacted transcript
+    Regenerate {
+        provider: String,
+        model: String,
+        template_id: Option<String>,
+    },
+    /// No summary
+    None,
+}
+
+impl SummaryRedaction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SummaryRedaction::Mask => "mask",
+            SummaryRedaction::Regenerate { .. } => "regenerate",
+            SummaryRedaction::None => "none",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RedactedCopyRequest {
+    pub spans: Vec<TranscriptSpan>,
+    pub audio: AudioRedaction,
+    pub summary: SummaryRedaction,
+    /// Title of the copy; defaults to "<title> (redacted)"
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactedCopyResult {
+    pub meeting_id: String,
+    /// Redacted ranges of the transcript after overlapping spans are joined
+    pub redacted_spans: usize,
+    /// Length of the muted or bleeped audio, in seconds (0 without audio)
+    pub redacted_audio_seconds: f64,
+}
+
+/// Redacted copies a meeting is linked to
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactedCopyLinks {
+    /// Set when the meeting is itself a redacted copy
+    pub source: Option<RedactedCopy>,
+    /// Redacted copies of the meeting, newest first
+    pub copies: Vec<RedactedCopy>,
+}
+
+/// Byte offset of character `index` of `text` (the end of the text for index == char count)
+fn byte_offset(text: &str, index: usize) -> Option<usize> {
+    text.char_indices().map(|(i, _)| i).chain(std::iter::once(text.len())).nth(index)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '\'' || c == '-'
+}
+
+/// Byte range of the characters `start..end` of `text`, widened to whole words so no part of a
+/// partly selected word is left
+fn word_byte_range(text: &str, start: usize, end: usize) -> Option<(usize, usize)> {
+    if start >= end {
+        return None;
+    }
+    let (mut from, mut to) = (byte_offset(text, start)?, byte_offset(text, end)?);
+    if text[from..].starts_with(is_word_char) {
+        while let Some(c) = text[..from].chars().next_back().filter(|c| is_word_char(*c)) {
+            from -= c.len_utf8();
+        }
+    }
+    if text[..to].ends_with(is_word_char) {
+        while let Some(c) = text[to..].chars().next().filter(|c| is_word_char(*c)) {
+            to += c.len_utf8();
+        }
+    }
+    Some((from, to))
+}
+
+/// Sorts ranges and joins the overlapping and touching ones
+fn merge_ranges<T: PartialOrd + Copy>(mut ranges: Vec<(T, T)>) -> Vec<(T, T)> {
+    ranges.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    let mut merged: Vec<(T, T)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => {
+                if end > last.1 {
+                    last.1 = end;
+                }
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// `text` with the (merged) byte ranges replaced by "[REDACTED]"
+fn redact_text(text: &str, ranges: &[(usize, usize)]) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut position = 0;
+    for &(from, to) in ranges {
+        redacted.push_str(&text[position..from]);
+        redacted.push_str(REDACTED);
+        position = to;
+    }
+    redacted.push_str(&text[position..]);
+    redacted
+}
+
+/// Recording time of the bytes `from..to` of a segment spoken during `bounds`, and whether it
+/// is estimated. Re-aligned segments use their word timings; otherwise, or when a word timing
+/// lies outside the segment (stale after the segment moved), the time is placed by the
+/// position of the span in the text.
+fn span_time_range(
+    text: &str,
+    bounds: (f64, f64),
+    from: usize,
+    to: usize,
+    timings: Option<&[WordTiming]>,
+) -> (f64, f64, bool) {
+    // Word timings follow the whitespace-separated words of the text (see transcript_edit.rs)
+    let words: Vec<(usize, usize)> = text
+        .split_whitespace()
+        .map(|word| {
+            let start = word.as_ptr() as usize - text.as_ptr() as usize;
+            (start, start + word.len())
+        })
+        .collect();
+    if let Some(timings) = timings.filter(|timings| timings.len() == words.len()) {
+        let covered: Vec<&WordTiming> = words
+            .iter()
+            .zip(timings)
+            .filter(|((start, end), _)| *start < to && *end > from)
+            .map(|(_, timing)| timing)
+            .collect();
+        let (lower, upper) = bounds;
+        let inside = |t: &&WordTiming| t.start >= lower - 1e-6 && t.end <= upper + 1e-6;
+        if !covered.is_empty() && covered.iter().all(inside) {
+            let start = covered.iter().map(|t| t.start).fold(f64::INFINITY, f64::min);
+            let end = covered.iter().map(|t| t.end).fold(f64::NEG_INFINITY, f64::max);
+            return (start, end, false);
+        }
+    }
+
+    let total = text.chars().count().max(1) as f64;
+    let (lower, upper) = bounds;
+    let at = |byte: usize| lower + (upper - lower) * text[..byte].chars().count() as f64 / total;
+    (at(from), at(to), true)
+}
+
+/// Mutes or bleeps `ranges` (seconds) of interleaved samples, fading the original out and in at
+/// the edges
+fn redact_samples(samples: &mut [f32], sample_rate: u32, channels: u16, ranges: &[(f64, f64)], mode: AudioRedaction) {
+    let channels = channels.max(1) as usize;
+    let frames = samples.len() / channels;
+    let rate = sample_rate as f64;
+    let fade = ((FADE_SECONDS * rate) as usize).max(1);
+
+    for &(start, end) in ranges {
+        let first = ((start.max(0.0) * rate) as usize).min(frames);
+        let last = ((end.max(0.0) * rate).ceil() as usize).min(frames);
+        for frame in first..last {
+            // Gain of the original audio: 1 at the edges, 0 after the fade
+            let edge = (frame - first).min(last - 1 - frame);
+            let keep = if edge < fade { 1.0 - edge as f32 / fade as f32 } else { 0.0 };
+            let tone = match mode {
+                AudioRedaction::Mute => 0.0,
+                AudioRedaction::Bleep => {
+                    let phase = 2.0 * std::f64::consts::PI * BLEEP_FREQUENCY_HZ * frame as f64 / rate;
+                    BLEEP_AMPLITUDE * (1.0 - keep) * phase.sin() as f32
+                }
+            };
+            for sample in &mut samples[frame * channels..(frame + 1) * channels] {
+                *sample = *sample * keep + tone;
+            }
+        }
+    }
+}
+
+/// Case-insensitive patterns for the redacted values, longest first
+fn value_patterns(values: &[String]) -> Vec<Regex> {
+    let mut values: Vec<&str> = values.iter().map(|v| v.trim()).filter(|v| !v.is_empty()).collect();
+    values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+    values.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    values
+        .into_iter()
+        .filter_map(|value| {
+            // Whole words only where the value starts or ends with a word character
+            let prefix = if value.starts_with(char::is_alphanumeric) { r"\b" } else { "" };
+            let suffix = if value.ends_with(char::is_alphanumeric) { r"\b" } else { "" };
+            Regex::new(&format!("(?i){}{}{}", prefix, regex::escape(value), suffix)).ok()
+        })
+        .collect()
+}
+
+fn mask_text(text: &str, patterns: &[Regex]) -> String {
+    patterns
+        .iter()
+        .fold(text.to_string(), |text, pattern| pattern.replace_all(&text, REDACTED).into_owned())
+}
+
+/// Masks the redacted values in every string of a summary (markdown and editor JSON)
+fn mask_summary(summary: &mut serde_json::Value, patterns: &[Regex]) {
+    match summary {
+        serde_json::Value::String(text) => *text = mask_text(text, patterns),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|item| mask_summary(item, patterns)),
+        serde_json::Value::Object(fields) => fields.values_mut().for_each(|value| mask_summary(value, patterns)),
+        _ => {}
+    }
+}
+
+/// Word timings of a meeting's re-aligned segments, keyed by segment id
+async fn load_word_timings(pool: &SqlitePool, meeting_id: &str) -> Result<HashMap<String, Vec<WordTiming>>> {
+    Ok(TranscriptRevisionsRepository::get_word_timings(pool, meeting_id)
+        .await?
+        .into_iter()
+        .filter_map(|(id, json)| serde_json::from_str(&json).ok().map(|timings| (id, timings)))
+        .collect())
+}
+
+/// Character span of a byte range of `text`
+fn char_span(text: &str, from: usize, to: usize) -> (usize, usize) {
+    let start = text[..from].chars().count();
+    (start, start + text[from..to].chars().count())
+}
+
+/// Spans the personal-detail detectors find in a meeting's transcript, using the redaction
+/// settings (including the NER model) whether or not LLM redaction is enabled
+pub async fn suggest_spans<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+) -> Result<Vec<TranscriptSpan>> {
+    let transcripts = TranscriptsRepository::get_meeting_transcripts(pool, meeting_id).await?;
+    let texts: Vec<&str> = transcripts.iter().map(|t| t.transcript.as_str()).collect();
+    let settings = redaction::load_settings(app);
+    let redactor = redaction::build_redactor(app, pool, &settings, &texts)
+        .await
+        .map_err(|e| anyhow!(e))?;
+
+    let spans: Vec<TranscriptSpan> = transcripts
+        .iter()
+        .flat_map(|transcript| {
+            let text = &transcript.transcript;
+            redactor.find(text).into_iter().map(move |(range, kind, detector)| {
+                let (start, end) = char_span(text, range.start, range.end);
+                TranscriptSpan {
+                    segment_id: transcript.id.clone(),
+                    start,
+                    end,
+                    kind: Some(kind),
+                    detector: Some(detector),
+                    text: Some(text[range].to_string()),
+                }
+            })
+        })
+        .collect();
+    info!("Suggested {} redaction spans for meeting {}", spans.len(), meeting_id);
+    Ok(spans)
+}
+
+/// The redacted transcript of a meeting with the audio ranges and values to redact
+struct RedactionPlan {
+    segments: Vec<TranscriptSegment>,
+    redacted_spans: usize,
+    audio_ranges: Vec<(f64, f64)>,
+    values: Vec<String>,
+}
+
+fn plan_redaction(
+    transcripts: Vec<Transcript>,
+    spans: &[TranscriptSpan],
+    word_timings: &HashMap<String, Vec<WordTiming>>,
+) -> Result<RedactionPlan> {
+    let mut ranges: HashMap<&str, Vec<(usize, usize)>> = HashMap::new();
+    for span in spans {
+        let transcript = transcripts
+            .iter()
+            .find(|t| t.id == span.segment_id)
+            .ok_or_else(|| anyhow!("Transcript segment not found: {}", span.segment_id))?;
+        let range = word_byte_range(&transcript.transcript, span.start, span.end).ok_or_else(|| {
+            anyhow!(
+                "Invalid span {}..{} in transcript segment {}",
+                span.start,
+                span.end,
+                span.segment_id
+            )
+        })?;
+        ranges.entry(transcript.id.as_str()).or_default().push(range);
+    }
+
+    let mut plan = RedactionPlan {
+        segments: Vec::with_capacity(transcripts.len()),
+        redacted_spans: 0,
+        audio_ranges: Vec::new(),
+        values: Vec::new(),
+    };
+    let mut translations: Vec<Option<String>> = Vec::with_capacity(transcripts.len());
+    for transcript in &transcripts {
+        let text = &transcript.transcript;
+        let segment_ranges = merge_ranges(ranges.remove(transcript.id.as_str()).unwrap_or_default());
+        for &(from, to) in &segment_ranges {
+            plan.values.push(text[from..to].to_string());
+            if let (Some(start), Some(end)) = (transcript.audio_start_time, transcript.audio_end_time) {
+                let timings = word_timings.get(&transcript.id).map(Vec::as_slice);
+                let (span_start, span_end, estimated) = span_time_range(text, (start, end), from, to, timings);
+                let padding = if estimated { ESTIMATED_PADDING_SECONDS } else { ALIGNED_PADDING_SECONDS };
+                plan.audio_ranges.push(((span_start - padding).max(start), (span_end + padding).min(end)));
+            }
+        }
+        plan.redacted_spans += segment_ranges.len();
+        translations.push(transcript.translation.clone());
+        plan.segments.push(TranscriptSegment {
+            id: transcript.id.clone(),
+            text: redact_text(text, &segment_ranges),
+            timestamp: transcript.timestamp.clone(),
+            audio_start_time: transcript.audio_start_time,
+            audio_end_time: transcript.audio_end_time,
+            duration: transcript.duration,
+            language: transcript.language.clone(),
+            translation: None,
+        });
+    }
+
+    // Translations cannot be mapped span by span; the redacted values are masked wherever they occur
+    let patterns = value_patterns(&plan.values);
+    for (segment, translation) in plan.segments.iter_mut().zip(translations) {
+        segment.translation = translation.map(|translation| mask_text(&translation, &patterns));
+    }
+    plan.audio_ranges = merge_ranges(std::mem::take(&mut plan.audio_ranges));
+    Ok(plan)
+}
+
+/// Decodes the original audio, redacts the ranges and encodes the result to `output`;
+/// returns the duration of the audio
+fn write_redacted_audio(input: &Path, output: &Path, ranges: &[(f64, f64)], mode: AudioRedaction) -> Result<f64> {
+    let mut decoded = decode_audio_file(input)?;
+    redact_samples(&mut decoded.samples, decoded.sample_rate, decoded.channels, ranges, mode);
+    let bytes: Vec<u8> = decoded.samples.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+    encode_single_audio(&bytes, decoded.sample_rate, decoded.channels, &output.to_path_buf())?;
+    Ok(decoded.duration_seconds)
+}
+
+/// Gives the copy a summary according to `mode`; failures are logged, the copy is kept
+async fn copy_summary<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    source_id: &str,
+    copy_id: &str,
+    mode: &SummaryRedaction,
+    patterns: &[Regex],
+) -> Result<()> {
+    let text = TranscriptsRepository::get_meeting_text(pool, copy_id, false).await?;
+    match mode {
+        SummaryRedaction::None => {}
+        SummaryRedaction::Mask => {
+            let Some(result) = SummaryProcessesRepository::get_summary_data(pool, source_id)
+                .await?
+                .and_then(|process| process.result)
+            else {
+                info!("Meeting {} has no summary to mask", source_id);
+                return Ok(());
+            };
+            let mut summary: serde_json::Value = serde_json::from_str(&result)?;
+            mask_summary(&mut summary, patterns);
+
+            let (model, model_name) = match TranscriptChunksRepository::get_transcript_data(pool, source_id).await? {
+                Some(chunk) => (chunk.model, chunk.model_name),
+                None => ("none".to_string(), "none".to_string()),
+            };
+            TranscriptChunksRepository::save_transcript_data(pool, copy_id, &text, &model, &model_name, 40000, 1000)
+                .await?;
+            SummaryProcessesRepository::create_or_reset_process(pool, copy_id).await?;
+            SummaryProcessesRepository::update_process_completed(pool, copy_id, summary, 0, 0.0).await?;
+        }
+        SummaryRedaction::Regenerate {
+            provider,
+            model,
+            template_id,
+        } => {
+            TranscriptChunksRepository::save_transcript_data(pool, copy_id, &text, provider, model, 40000, 1000)
+                .await?;
+            SummaryProcessesRepository::create_or_reset_process(pool, copy_id).await?;
+
+            let (app, pool, copy_id) = (app.clone(), pool.clone(), copy_id.to_string());
+            let (provider, model) = (provider.clone(), model.clone());
+            let template_id = template_id.clone().unwrap_or_else(|| DEFAULT_TEMPLATE_ID.to_string());
+            tauri::async_runtime::spawn(async move {
+                SummaryService::process_transcript_background(
+                    app,
+                    pool,
+                    copy_id,
+                    text,
+                    provider,
+                    model,
+                    String::new(),
+                    template_id,
+                    None,
+                )
+                .await;
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Creates a redacted copy of a meeting; the original is left untouched
+pub async fn create_redacted_copy<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    request: RedactedCopyRequest,
+) -> Result<RedactedCopyResult> {
+    if request.spans.is_empty() {
+        return Err(anyhow!("Select at least one part of the transcript to redact"));
+    }
+    let meeting = MeetingsRepository::get_meeting_metadata(pool, meeting_id)
+        .await?
+        .ok_or_else(|| anyhow!("Meeting not found: {}", meeting_id))?;
+    let folder = meeting.folder_path.as_deref().map(PathBuf::from).filter(|folder| folder.is_dir());
+
+    let transcripts = TranscriptsRepository::get_meeting_transcripts(pool, meeting_id).await?;
+    let word_timings = load_word_timings(pool, meeting_id).await?;
+    let plan = plan_redaction(transcripts, &request.spans, &word_timings)?;
+
+    let title = request
+        .title
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| format!("{} (redacted)", meeting.title));
+    let base_folder = folder
+        .as_deref()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(get_default_recordings_folder);
+    let copy_folder = create_meeting_folder(&base_folder, &title, false)?;
+    info!(
+        "Creating redacted copy of meeting {} ({} spans, {} audio ranges) in {}",
+        meeting_id,
+        plan.redacted_spans,
+        plan.audio_ranges.len(),
+        copy_folder.display()
+    );
+
+    let result: Result<(String, Option<f64>)> = async {
+        let audio = folder.as_deref().and_then(|folder| find_audio_file(folder).ok());
+        let audio_duration = match audio {
+            Some(input) => {
+                let output = copy_folder.join(COPY_AUDIO_FILE);
+                let ranges = plan.audio_ranges.clone();
+                let mode = request.audio;
+                let duration = tokio::task::spawn_blocking(move || write_redacted_audio(&input, &output, &ranges, mode))
+                    .await
+                    .map_err(|e| anyhow!("Audio redaction task join error: {}", e))??;
+                Some(duration)
+            }
+            None => None,
+        };
+        let copy_id =
+            TranscriptsRepository::save_transcript(pool, &title, &plan.segments, Some(copy_folder.to_string_lossy().to_string()))
+                .await?;
+        Ok((copy_id, audio_duration))
+    }
+    .await;
+
+    let (copy_id, audio_duration) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&copy_folder);
+            return Err(e);
+        }
+    };
+
+    // The link never stores the redacted text
+    let stored_spans: Vec<TranscriptSpan> = request
+        .spans
+        .iter()
+        .map(|span| TranscriptSpan { text: None, ..span.clone() })
+        .collect();
+    let link = RedactedCopy {
+        meeting_id: copy_id.clone(),
+        source_meeting_id: meeting_id.to_string(),
+        spans: serde_json::to_string(&stored_spans)?,
+        audio_mode: request.audio.as_str().to_string(),
+        summary_mode: request.summary.as_str().to_string(),
+        created_at: chrono::Utc::now(),
+    };
+    RedactedCopiesRepository::save_copy(pool, &link).await?;
+
+    let duration_seconds = audio_duration.unwrap_or_else(|| {
+        plan.segments
+            .iter()
+            .filter_map(|s| s.audio_end_time)
+            .fold(0.0, f64::max)
+    });
+    if let Err(e) = write_transcripts_json(&copy_folder, &plan.segments) {
+        warn!("Failed to write transcripts.json for {}: {}", copy_id, e);
+    }
+    let edit = serde_json::json!({
+        "operation": "redact",
+        "at": chrono::Utc::now().to_rfc3339(),
+        "source_meeting_id": meeting_id,
+        "redacted_spans": plan.redacted_spans,
+        "audio_mode": request.audio.as_str(),
+    });
+    let audio_file = audio_duration.is_some().then_some(COPY_AUDIO_FILE);
+    if let Err(e) = write_edit_metadata(&copy_folder, &copy_id, &title, duration_seconds, audio_file, edit) {
+        warn!("Failed to write metadata.json for {}: {}", copy_id, e);
+    }
+    // The redacted audio is written in plaintext; encrypt it in encrypted library mode
+    library_files::seal_folder(&copy_folder);
+
+    let patterns = value_patterns(&plan.values);
+    if let Err(e) = copy_summary(app, pool, meeting_id, &copy_id, &request.summary, &patterns).await {
+        warn!("Failed to {} the summary of redacted copy {}: {}", request.summary.as_str(), copy_id, e);
+    }
+
+    let _ = app.emit(
+        "redacted-copy-created",
+        serde_json::json!({ "meeting_id": &copy_id, "source_meeting_id": meeting_id }),
+    );
+    Ok(RedactedCopyResult {
+        meeting_id: copy_id,
+        redacted_spans: plan.redacted_spans,
+        redacted_audio_seconds: if audio_duration.is_some() {
+            plan.audio_ranges.iter().map(|(start, end)| end - start).sum()
+        } else {
+            0.0
+        },
+    })
+}
+
+// Tauri commands
+
+/// Spans of personal details the detectors suggest redacting in a meeting's transcript
+#[tauri::command]
+pub async fn api_suggest_redaction_spans<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<TranscriptSpan>, String> {
+    suggest_spans(&app, state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a redacted copy of a meeting for sharing
+#[tauri::command]
+pub async fn api_create_redacted_copy<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    request: RedactedCopyRequest,
+) -> Result<RedactedCopyResult, String> {
+    create_redacted_copy(&app, state.db_manager.pool(), &meeting_id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The original of a redacted copy and the redacted copies of a meeting
+#[tauri::command]
+pub async fn api_get_redacted_copies(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<RedactedCopyLinks, String> {
+    let pool = state.db_manager.pool();
+    let source = RedactedCopiesRepository::get_copy(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load redacted copy link: {}", e))?;
+    let copies = RedactedCopiesRepository::get_copies(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load redacted copies: {}", e))?;
+    Ok(RedactedCopyLinks { source, copies })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(word: &str, start: f64, end: f64) -> WordTiming {
+        WordTiming {
+            word: word.to_string(),
+            start,
+            end,
+            aligned: true,
+        }
+    }
+
+    #[test]
+    fn test_redact_text_widens_to_words() {
+        let text = "Call Jörg Berg-Olsen, he knows.";
+        // "rg Ber" selected by hand covers both names
+        let range = word_byte_range(text, 7, 13).unwrap();
+        assert_eq!(&text[range.0..range.1], "Jörg Berg-Olsen");
+        assert_eq!(redact_text(text, &[range]), "Call [REDACTED], he knows.");
+
+        // A span starting at punctuation does not pull in the word before it
+        let range = word_byte_range(text, 20, 24).unwrap();
+        assert_eq!(&text[range.0..range.1], ", he");
+        assert!(word_byte_range(text, 5, 5).is_none());
+        assert!(word_byte_range(text, 5, 99).is_none());
+
+        assert_eq!(merge_ranges(vec![(8, 12), (0, 4), (3, 6), (12, 14)]), vec![(0, 6), (8, 14)]);
+    }
+
+    #[test]
+    fn test_span_time_range() {
+        let text = "ask Maria Lund today";
+        let from = text.find("Maria").unwrap();
+        let to = from + "Maria Lund".len();
+        let timings = vec![
+            timing("ask", 10.0, 10.3),
+            timing("Maria", 10.4, 10.8),
+            timing("Lund", 10.8, 11.2),
+            timing("today", 11.3, 11.7),
+        ];
+        assert_eq!(span_time_range(text, (10.0, 12.0), from, to, Some(&timings)), (10.4, 11.2, false));
+
+        // Without usable timings the span is placed by its position in the text
+        let (start, end, estimated) = span_time_range(text, (10.0, 12.0), from, to, Some(&timings[..2]));
+        assert!(estimated);
+        assert!((start - 10.4).abs() < 1e-9 && (end - 11.4).abs() < 1e-9);
+
+        // Timings outside the segment belong to another place in the recording
+        let (start, end, estimated) = span_time_range(text, (20.0, 22.0), from, to, Some(&timings));
+        assert!(estimated);
+        assert!((start - 20.4).abs() < 1e-9 && (end - 21.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_redact_samples() {
+        let mut samples = vec![0.5f32; 2 * 1000];
+        redact_samples(&mut samples, 1000, 2, &[(0.2, 0.4)], AudioRedaction::Mute);
+        assert_eq!(samples[2 * 100], 0.5);
+        assert!(samples[2 * 250..2 * 350].iter().all(|s| *s == 0.0));
+        assert_eq!(samples[2 * 500 + 1], 0.5);
+
+        let mut samples = vec![0.0f32; 8000];
+        redact_samples(&mut samples, 8000, 1, &[(0.5, 0.75)], AudioRedaction::Bleep);
+        assert!(samples[..4000].iter().all(|s| *s == 0.0));
+        let peak = samples[4000..6000].iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        assert!(peak > 0.1 && peak <= BLEEP_AMPLITUDE);
+    }
+
+    #[test]
+    fn test_mask_summary() {
+        let patterns = value_patterns(&["maria".to_string(), "Maria Lund".to_string(), "+44 20 7946 0958".to_string()]);
+        let mut summary = serde_json::json!({
+            "markdown": "- Maria Lund calls +44 20 7946 0958\n- Mariana stays",
+            "summary_json": [{ "content": [{ "text": "Owner: MARIA" }] }],
+        });
+        mask_summary(&mut summary, &patterns);
+        assert_eq!(summary["markdown"], "- [REDACTED] calls [REDACTED]\n- Mariana stays");
+        assert_eq!(summary["summary_json"][0]["content"][0]["text"], "Owner: [REDACTED]");
+    }
+}
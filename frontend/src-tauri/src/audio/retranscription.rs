@@ -2,9 +2,14 @@
 
 use crate::audio::decoder::decode_audio_file;
 use crate::audio::vad::get_speech_chunks_with_progress;
-use super::common::{create_transcript_segments, split_segment_at_silence, write_transcripts_json};
+use super::common::{
+    apply_segment_languages, create_transcript_segments, split_segment_at_silence, write_transcripts_json,
+};
 use super::constants::AUDIO_EXTENSIONS;
+use super::transcription::language::{self, SegmentLanguage};
+use super::transcription::vocabulary;
 use crate::config::{DEFAULT_WHISPER_MODEL, DEFAULT_PARAKEET_MODEL};
+use crate::encryption::files as library_files;
 use crate::parakeet_engine::ParakeetEngine;
 use crate::state::AppState;
 use crate::whisper_engine::WhisperEngine;
@@ -24,11 +29,11 @@ static RETRANSCRIPTION_CANCELLED: AtomicBool = AtomicBool::new(false);
 
 /// RAII guard for RETRANSCRIPTION_IN_PROGRESS flag
 /// Ensures flag is cleared even if retranscription panics or returns early
-struct RetranscriptionGuard;
+pub(crate) struct RetranscriptionGuard;
 
 impl RetranscriptionGuard {
     /// Create guard and set flag atomically
-    fn acquire() -> Result<Self, String> {
+    pub(crate) fn acquire() -> Result<Self, String> {
         if RETRANSCRIPTION_IN_PROGRESS
             .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
             .is_err()
@@ -49,7 +54,7 @@ impl Drop for RetranscriptionGuard {
 /// Batch processing needs longer redemption (2000ms) than live pipeline (400ms)
 /// because the entire file is processed at once by VAD, and 400ms fragments
 /// speech at every natural sentence/topic pause (500ms-2s)
-const VAD_REDEMPTION_TIME_MS: u32 = 2000;
+pub(crate) const VAD_REDEMPTION_TIME_MS: u32 = 2000;
 
 /// Progress update emitted during retranscription
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -138,7 +143,7 @@ pub async fn start_retranscription<R: Runtime>(
 
 /// Find audio file in meeting folder
 /// Tries common names first, then scans for any file with an audio extension
-fn find_audio_file(folder: &Path) -> Result<PathBuf> {
+pub(crate) fn find_audio_file(folder: &Path) -> Result<PathBuf> {
     let candidates = [
         "audio.mp4", "audio.m4a", "audio.wav", "audio.mp3",
         "audio.flac", "audio.ogg", "recording.mp4",
@@ -150,13 +155,17 @@ fn find_audio_file(folder: &Path) -> Result<PathBuf> {
         if path.exists() {
             return Ok(path);
         }
+        let encrypted = library_files::encrypted_path(&path);
+        if encrypted.exists() {
+            return Ok(encrypted);
+        }
     }
 
-    // Fallback: scan folder for any file with an audio extension
+    // Fallback: scan folder for any file with an audio extension (encrypted files by inner extension)
     if let Ok(entries) = std::fs::read_dir(folder) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if let Some(ext) = path.extension() {
+            if let Some(ext) = library_files::plaintext_path(&path).extension() {
                 let ext = ext.to_string_lossy().to_lowercase();
                 if AUDIO_EXTENSIONS.contains(&ext.as_str()) {
                     return Ok(path);
@@ -179,6 +188,7 @@ async fn run_retranscription<R: Runtime>(
 ) -> Result<RetranscriptionResult> {
     let folder_path = PathBuf::from(&meeting_folder_path);
     let audio_path = find_audio_file(&folder_path)?;
+    let vocabulary = vocabulary::load_for_meeting(&app, Some(&meeting_id)).await;
 
     // Determine which provider to use (default to whisper)
     let use_parakeet = provider.as_deref() == Some("parakeet");
@@ -342,10 +352,18 @@ async fn run_retranscription<R: Runtime>(
     const BATCH_SIZE: usize = 3;
 
     let mut all_transcripts: Vec<(String, f64, f64)> = Vec::new();
+    let mut segment_languages: Vec<SegmentLanguage> = Vec::new();
     let mut total_confidence = 0.0f32;
     let mut previous_text: Option<String> = None;
     let mut global_idx = 0usize;
 
+    // Code-switched meetings: identify each segment's language among the configured candidates
+    let detection = language::load_settings(&app);
+    let per_segment_language = !use_parakeet && detection.applies_to(language.as_deref());
+    if per_segment_language {
+        info!("Per-segment language identification enabled (candidates: {:?})", detection.candidates);
+    }
+
     for batch in processable_segments.chunks(BATCH_SIZE) {
         // Check for cancellation before each batch
         if RETRANSCRIPTION_CANCELLED.load(Ordering::SeqCst) {
@@ -387,23 +405,36 @@ async fn run_retranscription<R: Runtime>(
                 handles.push((seg_idx, start_ms, end_ms, tokio::spawn(async move {
                     let text = engine.transcribe_audio(samples).await
                         .map_err(|e| anyhow!("Parakeet failed on segment {}: {}", seg_idx, e))?;
-                    Ok::<(String, f32), anyhow::Error>((text, 0.9))
+                    Ok::<(String, f32, SegmentLanguage), anyhow::Error>((text, 0.9, SegmentLanguage::default()))
+                })));
+            } else if per_segment_language {
+                let engine = whisper_engine.as_ref().unwrap().clone();
+                let detection = detection.clone();
+                let vocabulary = vocabulary.clone();
+                handles.push((seg_idx, start_ms, end_ms, tokio::spawn(async move {
+                    let result = engine
+                        .transcribe_multilingual(samples, &detection.candidates, detection.store_translation, prev.as_deref(), &vocabulary)
+                        .await
+                        .map_err(|e| anyhow!("Whisper failed on segment {}: {}", seg_idx, e))?;
+                    Ok::<(String, f32, SegmentLanguage), anyhow::Error>((result.text, result.confidence, result.language))
                 })));
             } else {
                 let engine = whisper_engine.as_ref().unwrap().clone();
+                let vocabulary = vocabulary.clone();
                 handles.push((seg_idx, start_ms, end_ms, tokio::spawn(async move {
-                    let (text, conf) = engine.transcribe_batch(samples, lang, prev.as_deref()).await
+                    let (text, conf) = engine.transcribe_batch(samples, lang, prev.as_deref(), &vocabulary).await
                         .map_err(|e| anyhow!("Whisper failed on segment {}: {}", seg_idx, e))?;
-                    Ok::<(String, f32), anyhow::Error>((text, conf))
+                    Ok::<(String, f32, SegmentLanguage), anyhow::Error>((text, conf, SegmentLanguage::default()))
                 })));
             }
         }
 
         // Collect results in order
         for (seg_idx, start_ms, end_ms, handle) in handles {
-            let (text, conf) = handle.await
+            let (text, conf, segment_language) = handle.await
                 .map_err(|e| anyhow!("Task join error on segment {}: {}", seg_idx, e))??;
 
+            let text = vocabulary.correct(&text);
             let trimmed = text.trim();
             if !trimmed.is_empty() {
                 let segment_duration_sec = (end_ms - start_ms) / 1000.0;
@@ -414,6 +445,7 @@ async fn run_retranscription<R: Runtime>(
                 );
                 previous_text = Some(text.clone());
                 all_transcripts.push((text, start_ms, end_ms));
+                segment_languages.push(segment_language);
                 total_confidence += conf;
             } else {
                 debug!("Segment {}/{}: empty transcription", seg_idx + 1, processable_count);
@@ -443,7 +475,8 @@ async fn run_retranscription<R: Runtime>(
     emit_progress(&app, &meeting_id, "saving", 80, "Saving transcripts...");
 
     // Create transcript segments with proper timestamps from VAD
-    let segments = create_transcript_segments(&all_transcripts);
+    let mut segments = create_transcript_segments(&all_transcripts);
+    apply_segment_languages(&mut segments, segment_languages);
 
     // Save to database
     let app_state = app
@@ -465,8 +498,8 @@ async fn run_retranscription<R: Runtime>(
 
     for segment in &segments {
         sqlx::query(
-            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-             VALUES (?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, language, translation)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&segment.id)
         .bind(&meeting_id)
@@ -475,6 +508,8 @@ async fn run_retranscription<R: Runtime>(
         .bind(segment.audio_start_time)
         .bind(segment.audio_end_time)
         .bind(segment.duration)
+        .bind(&segment.language)
+        .bind(&segment.translation)
         .execute(&mut *tx)
         .await
         .map_err(|e| anyhow!("Failed to insert transcript: {}", e))?;
@@ -497,7 +532,7 @@ async fn run_retranscription<R: Runtime>(
     }
 
     // Find audio filename for metadata
-    let audio_filename = audio_path
+    let audio_filename = library_files::plaintext_path(&audio_path)
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("audio.mp4")
@@ -543,7 +578,7 @@ fn emit_progress<R: Runtime>(
 
 /// Get or initialize the Whisper engine, auto-loading the model if needed
 /// If `requested_model` is provided, ensures that specific model is loaded
-async fn get_or_init_whisper<R: Runtime>(
+pub(crate) async fn get_or_init_whisper<R: Runtime>(
     app: &AppHandle<R>,
     requested_model: Option<&str>,
 ) -> Result<Arc<WhisperEngine>> {
@@ -645,7 +680,7 @@ async fn get_configured_whisper_model<R: Runtime>(app: &AppHandle<R>) -> Result<
 }
 
 /// Get or initialize the Parakeet engine, auto-loading the model if needed
-async fn get_or_init_parakeet<R: Runtime>(
+pub(crate) async fn get_or_init_parakeet<R: Runtime>(
     app: &AppHandle<R>,
     requested_model: Option<&str>,
 ) -> Result<Arc<ParakeetEngine>> {
@@ -752,12 +787,11 @@ fn write_retranscription_metadata(
     audio_filename: &str,
 ) -> Result<()> {
     let metadata_path = folder.join("metadata.json");
-    let temp_path = folder.join(".metadata.json.tmp");
     let now = chrono::Utc::now().to_rfc3339();
 
     // Try to read existing metadata and update it
-    let json = if metadata_path.exists() {
-        let existing = std::fs::read_to_string(&metadata_path)?;
+    let json = if library_files::exists(&metadata_path) {
+        let existing = library_files::read_to_string(&metadata_path).map_err(|e| anyhow!(e))?;
         let mut value: serde_json::Value = serde_json::from_str(&existing)?;
         if let Some(obj) = value.as_object_mut() {
             obj.insert("retranscribed_at".to_string(), serde_json::json!(now));
@@ -781,8 +815,7 @@ fn write_retranscription_metadata(
     };
 
     let json_string = serde_json::to_string_pretty(&json)?;
-    std::fs::write(&temp_path, &json_string)?;
-    std::fs::rename(&temp_path, &metadata_path)?;
+    library_files::write_file(&metadata_path, json_string.as_bytes()).map_err(|e| anyhow!(e))?;
 
     info!("Wrote metadata.json to {}", metadata_path.display());
     Ok(())
@@ -1004,6 +1037,21 @@ mod tests {
         assert_eq!(found.file_name().unwrap(), "audio.mp4");
     }
 
+    #[test]
+    fn test_find_audio_file_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("audio.mp4.enc"), b"fake").unwrap();
+        let found = find_audio_file(dir.path()).unwrap();
+        assert_eq!(found.file_name().unwrap(), "audio.mp4.enc");
+
+        // Fallback scan goes by the extension inside .enc
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt.enc"), b"text").unwrap();
+        std::fs::write(dir.path().join("my_recording.flac.enc"), b"fake").unwrap();
+        let found = find_audio_file(dir.path()).unwrap();
+        assert_eq!(found.file_name().unwrap(), "my_recording.flac.enc");
+    }
+
     #[test]
     fn test_find_audio_file_empty_folder() {
         let dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,541 @@
+// Segment repair pass - re-decodes only the doubtful parts of a transcript.
+//
+// Segments are selected when their live confidence (persisted in transcripts.json) is below
+// a threshold or their text looks like a Whisper repetition loop / stock hallucination.
+// Each selected audio range is re-decoded with a larger model, a wider beam and optionally
+// a forced language, and replaced only when the new hypothesis scores better. Changes are
+// applied as an accepted transcript version, so the whole repair can be reverted.
+
+use super::decoder::decode_audio_file;
+use super::quality_pass::{carry_over_edits, sync_transcripts_file, DEFAULT_QUALITY_MODEL};
+use super::retranscription::{find_audio_file, get_or_init_whisper, RetranscriptionGuard};
+use super::transcription::vocabulary::{self, Vocabulary};
+use crate::database::models::TranscriptVersionSegment;
+use crate::database::repositories::transcript_version::TranscriptVersionsRepository;
+use crate::state::AppState;
+use crate::whisper_engine::WhisperEngine;
+use anyhow::{anyhow, Result};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Source tag stored on transcript versions created by this module
+const REPAIR_SOURCE: &str = "repair";
+
+/// Segments whose live confidence is below this are re-decoded
+const DEFAULT_CONFIDENCE_THRESHOLD: f32 = 0.5;
+
+/// Wider than the hardware batch default (3-5); doubtful segments are few and short
+const DEFAULT_REPAIR_BEAM_SIZE: usize = 8;
+
+/// A new hypothesis must beat the old score by at least this much to replace it
+const DEFAULT_MIN_IMPROVEMENT: f32 = 0.05;
+
+/// Fraction of looping words at which a segment is flagged as a repetition loop
+const REPETITION_FLAG_FRACTION: f32 = 0.3;
+
+/// Audio context added around each re-decoded range so word edges are not clipped
+const RANGE_PADDING_SECS: f64 = 0.2;
+
+/// Live segment start times within this distance refer to the same segment
+const START_MATCH_TOLERANCE_SECS: f64 = 0.1;
+
+static REPAIR_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Options for a repair run (all optional, defaults favour accuracy)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepairOptions {
+    pub confidence_threshold: Option<f32>,
+    pub model: Option<String>,
+    pub beam_size: Option<usize>,
+    /// Force a language for re-decoding instead of the global preference
+    pub language: Option<String>,
+    pub min_improvement: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairReason {
+    LowConfidence,
+    Repetition,
+}
+
+/// Segment selected for re-decoding
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RepairCandidate {
+    pub index: usize,
+    pub reason: RepairReason,
+    pub live_confidence: Option<f32>,
+}
+
+/// One replaced segment, for the change log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairChange {
+    pub segment_id: String,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+    pub reason: RepairReason,
+    pub old_text: String,
+    pub new_text: String,
+    pub old_score: f32,
+    pub new_score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairResult {
+    pub meeting_id: String,
+    pub candidates: usize,
+    pub replaced: usize,
+    pub version_id: Option<String>,
+    pub changes: Vec<RepairChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairProgress {
+    pub meeting_id: String,
+    pub progress_percentage: u32,
+    pub message: String,
+}
+
+/// Cancel the running repair pass
+pub fn cancel_segment_repair() {
+    REPAIR_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+async fn run_segment_repair<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: &str,
+    folder_path: &Path,
+    options: &RepairOptions,
+) -> Result<RepairResult> {
+    let threshold = options.confidence_threshold.unwrap_or(DEFAULT_CONFIDENCE_THRESHOLD);
+    let min_improvement = options.min_improvement.unwrap_or(DEFAULT_MIN_IMPROVEMENT);
+    let beam_size = options.beam_size.unwrap_or(DEFAULT_REPAIR_BEAM_SIZE);
+    let model = options
+        .model
+        .clone()
+        .unwrap_or_else(|| DEFAULT_QUALITY_MODEL.to_string());
+    let language = options
+        .language
+        .clone()
+        .or_else(crate::get_language_preference_internal);
+    let vocabulary = vocabulary::load_for_meeting(app, Some(meeting_id)).await;
+
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| anyhow!("App state not available"))?;
+    let pool = state.db_manager.pool();
+
+    let segments = TranscriptVersionsRepository::get_current_segments(pool, meeting_id)
+        .await
+        .map_err(|e| anyhow!("Failed to load transcripts: {}", e))?;
+    let live_confidences = load_live_confidences(folder_path);
+    let candidates = select_candidates(&segments, &live_confidences, threshold);
+
+    info!(
+        "Segment repair for meeting {}: {} of {} segments selected (threshold {:.2})",
+        meeting_id,
+        candidates.len(),
+        segments.len(),
+        threshold
+    );
+
+    if candidates.is_empty() {
+        return Ok(RepairResult {
+            meeting_id: meeting_id.to_string(),
+            candidates: 0,
+            replaced: 0,
+            version_id: None,
+            changes: Vec::new(),
+        });
+    }
+
+    emit_progress(app, meeting_id, 5, "Decoding recording...");
+    let audio_path = find_audio_file(folder_path)?;
+    let decoded = tokio::task::spawn_blocking(move || decode_audio_file(&audio_path))
+        .await
+        .map_err(|e| anyhow!("Decode task panicked: {}", e))??;
+    let audio_samples = tokio::task::spawn_blocking(move || decoded.to_whisper_format())
+        .await
+        .map_err(|e| anyhow!("Resample task panicked: {}", e))?;
+
+    emit_progress(app, meeting_id, 15, &format!("Loading {}...", model));
+    let engine = get_or_init_whisper(app, Some(model.as_str())).await?;
+
+    let mut repaired = segments.clone();
+    let mut changes = Vec::new();
+
+    for (n, candidate) in candidates.iter().enumerate() {
+        if REPAIR_CANCELLED.load(Ordering::SeqCst) {
+            return Err(anyhow!("Segment repair cancelled"));
+        }
+
+        let progress = 15 + ((n as f32 / candidates.len() as f32) * 75.0) as u32;
+        emit_progress(
+            app,
+            meeting_id,
+            progress,
+            &format!("Re-decoding segment {} of {}...", n + 1, candidates.len()),
+        );
+
+        let segment = &segments[candidate.index];
+        let previous_text = candidate
+            .index
+            .checked_sub(1)
+            .map(|i| segments[i].text.as_str());
+
+        // Segments with an identified language are re-decoded in that language
+        let segment_language = match &options.language {
+            Some(forced) => Some(forced.clone()),
+            None => segment.language.clone().or_else(|| language.clone()),
+        };
+
+        match redecode(&engine, &audio_samples, segment, segment_language, previous_text, beam_size, &vocabulary).await {
+            Ok(Some((new_text, new_confidence))) => {
+                let old_score = hypothesis_score(
+                    &segment.text,
+                    candidate.live_confidence.unwrap_or(threshold),
+                );
+                let new_score = hypothesis_score(&new_text, new_confidence);
+
+                if should_replace(&segment.text, old_score, &new_text, new_score, min_improvement) {
+                    info!(
+                        "Repaired segment {} ({:?}, {:.2} -> {:.2}): '{}' -> '{}'",
+                        segment.id, candidate.reason, old_score, new_score, segment.text, new_text
+                    );
+                    repaired[candidate.index].text = new_text.clone();
+                    repaired[candidate.index].word_timings = None;
+                    changes.push(RepairChange {
+                        segment_id: segment.id.clone(),
+                        audio_start_time: segment.audio_start_time,
+                        audio_end_time: segment.audio_end_time,
+                        reason: candidate.reason,
+                        old_text: segment.text.clone(),
+                        new_text,
+                        old_score,
+                        new_score,
+                    });
+                } else {
+                    info!(
+                        "Kept segment {} ({:?}): new hypothesis scored {:.2} vs {:.2}",
+                        segment.id, candidate.reason, new_score, old_score
+                    );
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to re-decode segment {}: {}", segment.id, e),
+        }
+    }
+
+    let version_id = if changes.is_empty() {
+        None
+    } else {
+        emit_progress(app, meeting_id, 95, "Applying repaired segments...");
+
+        let stats = serde_json::json!({
+            "candidates": candidates.len(),
+            "replaced": changes.len(),
+            "model": model,
+            "beam_size": beam_size,
+            "changes": changes,
+        });
+        let version_id = TranscriptVersionsRepository::create_version(
+            pool,
+            meeting_id,
+            REPAIR_SOURCE,
+            Some("whisper"),
+            Some(model.as_str()),
+            &repaired,
+            &segments,
+            &stats,
+        )
+        .await
+        .map_err(|e| anyhow!("Failed to store repaired transcript: {}", e))?;
+
+        let applied = TranscriptVersionsRepository::accept_version(pool, &version_id, carry_over_edits)
+            .await
+            .map_err(|e| anyhow!("Failed to apply repaired transcript: {}", e))?;
+        sync_transcripts_file(app, meeting_id, &applied).await;
+        Some(version_id)
+    };
+
+    emit_progress(app, meeting_id, 100, "Segment repair complete");
+
+    Ok(RepairResult {
+        meeting_id: meeting_id.to_string(),
+        candidates: candidates.len(),
+        replaced: changes.len(),
+        version_id,
+        changes,
+    })
+}
+
+/// Re-decode one segment's audio range. Returns None when the range is too short or silent.
+async fn redecode(
+    engine: &WhisperEngine,
+    audio_samples: &[f32],
+    segment: &TranscriptVersionSegment,
+    language: Option<String>,
+    previous_text: Option<&str>,
+    beam_size: usize,
+    vocabulary: &Vocabulary,
+) -> Result<Option<(String, f32)>> {
+    let (start, end) = match (segment.audio_start_time, segment.audio_end_time) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Ok(None),
+    };
+
+    let from = (((start - RANGE_PADDING_SECS).max(0.0)) * 16000.0) as usize;
+    let to = ((((end + RANGE_PADDING_SECS) * 16000.0) as usize).min(audio_samples.len())).max(from);
+    if to - from < 1600 {
+        return Ok(None);
+    }
+
+    let (text, confidence) = engine
+        .transcribe_batch_with_beam(audio_samples[from..to].to_vec(), language, previous_text, Some(beam_size), vocabulary)
+        .await?;
+
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some((vocabulary.correct(&text), confidence)))
+}
+
+/// Live confidences from the recording's transcripts.json, as (audio_start_time, confidence)
+fn load_live_confidences(folder: &Path) -> Vec<(f64, f32)> {
+    let content = match crate::encryption::files::read_to_string(&folder.join("transcripts.json")) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+    let value: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Failed to parse transcripts.json for confidences: {}", e);
+            return Vec::new();
+        }
+    };
+
+    value["segments"]
+        .as_array()
+        .map(|segments| {
+            segments
+                .iter()
+                .filter_map(|s| {
+                    let start = s["audio_start_time"].as_f64()?;
+                    let confidence = s["confidence"].as_f64()? as f32;
+                    Some((start, confidence))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Pick segments to re-decode. Edited and untimed segments are never touched.
+pub(crate) fn select_candidates(
+    segments: &[TranscriptVersionSegment],
+    live_confidences: &[(f64, f32)],
+    threshold: f32,
+) -> Vec<RepairCandidate> {
+    segments
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.edited_at.is_none() && s.audio_start_time.is_some() && s.audio_end_time.is_some())
+        .filter_map(|(index, segment)| {
+            let start = segment.audio_start_time?;
+            let live_confidence = live_confidences
+                .iter()
+                .find(|(s, _)| (s - start).abs() < START_MATCH_TOLERANCE_SECS)
+                .map(|(_, c)| *c);
+
+            let reason = if is_repetition_suspect(&segment.text) {
+                RepairReason::Repetition
+            } else if live_confidence.map_or(false, |c| c < threshold) {
+                RepairReason::LowConfidence
+            } else {
+                return None;
+            };
+
+            Some(RepairCandidate {
+                index,
+                reason,
+                live_confidence,
+            })
+        })
+        .collect()
+}
+
+/// Whether text looks like a repetition loop or a stock Whisper hallucination
+pub(crate) fn is_repetition_suspect(text: &str) -> bool {
+    WhisperEngine::is_meaningless_output(text) || looping_fraction(text) >= REPETITION_FLAG_FRACTION
+}
+
+/// Fraction of words inside an n-gram (n = 1..=4) repeated three or more times in a row
+fn looping_fraction(text: &str) -> f32 {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| c.is_ascii_punctuation()).to_lowercase())
+        .collect();
+    if words.len() < 3 {
+        return 0.0;
+    }
+
+    let mut looping = vec![false; words.len()];
+    for n in 1..=4 {
+        let mut i = 0;
+        while i + n <= words.len() {
+            let mut repeats = 1;
+            while i + (repeats + 1) * n <= words.len()
+                && words[i + repeats * n..i + (repeats + 1) * n] == words[i..i + n]
+            {
+                repeats += 1;
+            }
+
+            if repeats >= 3 {
+                looping[i..i + repeats * n].iter_mut().for_each(|w| *w = true);
+                i += repeats * n;
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    looping.iter().filter(|&&w| w).count() as f32 / words.len() as f32
+}
+
+/// Confidence minus a penalty for looping text
+fn hypothesis_score(text: &str, confidence: f32) -> f32 {
+    let penalty = if WhisperEngine::is_meaningless_output(text) {
+        1.0
+    } else {
+        looping_fraction(text)
+    };
+    confidence - penalty
+}
+
+pub(crate) fn should_replace(
+    old_text: &str,
+    old_score: f32,
+    new_text: &str,
+    new_score: f32,
+    min_improvement: f32,
+) -> bool {
+    !new_text.trim().is_empty() && new_text.trim() != old_text.trim() && new_score >= old_score + min_improvement
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, progress: u32, message: &str) {
+    let _ = app.emit(
+        "segment-repair-progress",
+        RepairProgress {
+            meeting_id: meeting_id.to_string(),
+            progress_percentage: progress,
+            message: message.to_string(),
+        },
+    );
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub async fn start_segment_repair_command<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    meeting_folder_path: String,
+    options: Option<RepairOptions>,
+) -> Result<(), String> {
+    if super::recording_commands::is_recording().await {
+        return Err("Cannot repair segments while recording".to_string());
+    }
+
+    // Shares the transcription engine with retranscription and the quality pass
+    let guard = RetranscriptionGuard::acquire()?;
+    REPAIR_CANCELLED.store(false, Ordering::SeqCst);
+
+    tauri::async_runtime::spawn(async move {
+        let _guard = guard;
+        let options = options.unwrap_or_default();
+        let folder_path = PathBuf::from(&meeting_folder_path);
+
+        let result = run_segment_repair(&app, &meeting_id, &folder_path, &options).await;
+        super::common::unload_engine_after_batch(false).await;
+
+        match result {
+            Ok(result) => {
+                info!(
+                    "Segment repair for meeting {} replaced {} of {} candidates",
+                    meeting_id, result.replaced, result.candidates
+                );
+                let _ = app.emit("segment-repair-complete", &result);
+            }
+            Err(e) => {
+                error!("Segment repair for meeting {} failed: {}", meeting_id, e);
+                let _ = app.emit(
+                    "segment-repair-error",
+                    serde_json::json!({ "meeting_id": meeting_id, "error": e.to_string() }),
+                );
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn cancel_segment_repair_command() -> Result<(), String> {
+    cancel_segment_repair();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: &str, text: &str, start: f64) -> TranscriptVersionSegment {
+        crate::database::models::segment(id, text, start, start + 2.0)
+    }
+
+    #[test]
+    fn test_looping_fraction() {
+        assert_eq!(looping_fraction("we should ship it on friday"), 0.0);
+        assert!(looping_fraction("I think I think I think so") > 0.8);
+        assert!(looping_fraction("yes yes yes") >= 1.0);
+        // Two repeats is normal speech
+        assert_eq!(looping_fraction("very very good"), 0.0);
+    }
+
+    #[test]
+    fn test_select_candidates() {
+        let mut edited = segment("c", "thanks for watching", 4.0);
+        edited.edited_at = Some("2025-01-01T00:00:00Z".to_string());
+        let segments = vec![
+            segment("a", "fine words", 0.0),
+            segment("b", "muffled words", 2.0),
+            edited,
+            segment("d", "go go go go", 6.0),
+        ];
+        let confidences = vec![(0.0, 0.9), (2.02, 0.31), (4.0, 0.1)];
+
+        let candidates = select_candidates(&segments, &confidences, 0.5);
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].index, 1);
+        assert_eq!(candidates[0].reason, RepairReason::LowConfidence);
+        assert_eq!(candidates[0].live_confidence, Some(0.31));
+        assert_eq!(candidates[1].index, 3);
+        assert_eq!(candidates[1].reason, RepairReason::Repetition);
+        assert_eq!(candidates[1].live_confidence, None);
+    }
+
+    #[test]
+    fn test_should_replace_requires_better_score() {
+        let old = hypothesis_score("the the the the plan", 0.6);
+        let new = hypothesis_score("the plan", 0.55);
+        assert!(should_replace("the the the the plan", old, "the plan", new, 0.05));
+
+        assert!(!should_replace("project plan", 0.40, "project plant", 0.42, 0.05));
+        assert!(!should_replace("project plan", 0.40, "project plan", 0.90, 0.05));
+        assert!(!should_replace("project plan", 0.40, "  ", 0.90, 0.05));
+    }
+}
@@ -13,6 +13,9 @@ use super::capture::{AudioCaptureBackend, get_current_backend};
 #[cfg(target_os = "macos")]
 use super::capture::CoreAudioCapture;
 
+#[cfg(target_os = "linux")]
+use super::capture::{pulse_audio, PulseAudioCapture};
+
 /// Stream backend implementation
 pub enum StreamBackend {
     /// CPAL-based stream (ScreenCaptureKit or default)
@@ -22,6 +25,11 @@ pub enum StreamBackend {
     CoreAudio {
         task: Option<tokio::task::JoinHandle<()>>,
     },
+    /// PulseAudio/PipeWire monitor capture via parec (Linux only)
+    #[cfg(target_os = "linux")]
+    PulseAudio {
+        task: Option<tokio::task::JoinHandle<()>>,
+    },
 }
 
 // SAFETY: While Stream doesn't implement Send, we ensure it's only accessed
@@ -87,6 +95,23 @@ impl AudioStream {
             return Self::create_core_audio_stream(device, state, device_type, recording_sender).await;
         }
 
+        #[cfg(target_os = "linux")]
+        if device_type == DeviceType::System && backend_type == AudioCaptureBackend::PulseAudio {
+            info!("🎵 Stream: Using PulseAudio/PipeWire backend for system audio");
+            match Self::create_pulse_audio_stream(
+                device.clone(),
+                state.clone(),
+                device_type,
+                recording_sender.clone(),
+            ).await {
+                Ok(stream) => return Ok(stream),
+                Err(e) => {
+                    // No sound server (e.g. bare ALSA) - the CPAL path can still use monitor devices
+                    warn!("⚠️ Stream: PulseAudio capture unavailable, falling back to CPAL: {}", e);
+                }
+            }
+        }
+
         // Default path: use CPAL
         #[cfg(target_os = "macos")]
         let backend_name = if backend_type == AudioCaptureBackend::ScreenCaptureKit {
@@ -233,6 +258,71 @@ impl AudioStream {
         })
     }
 
+    /// Create a PulseAudio/PipeWire monitor stream (Linux only)
+    #[cfg(target_os = "linux")]
+    async fn create_pulse_audio_stream(
+        device: Arc<AudioDevice>,
+        state: Arc<RecordingState>,
+        device_type: DeviceType,
+        recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
+    ) -> Result<Self> {
+        info!("🔊 Stream: Creating PulseAudio stream for device: {}", device.name);
+
+        let target = pulse_audio::resolve_target(&device.name);
+        let pulse_stream = PulseAudioCapture::with_target(target)
+            .and_then(|capture| capture.stream())
+            .map_err(|e| {
+                error!("❌ Stream: PulseAudio capture failed: {}", e);
+                anyhow::anyhow!("Failed to create PulseAudio stream: {}", e)
+            })?;
+
+        // parec is asked for mono at a fixed rate, the server handles conversion
+        let capture = AudioCapture::new(
+            device.clone(),
+            state.clone(),
+            pulse_stream.sample_rate(),
+            pulse_stream.channels(),
+            device_type,
+            recording_sender,
+        );
+
+        let device_name = device.name.clone();
+        let task = tokio::spawn({
+            let capture = capture.clone();
+            let mut stream = pulse_stream;
+
+            async move {
+                use futures_util::StreamExt;
+
+                let mut buffer = Vec::with_capacity(1024);
+                let frames_per_chunk = 1024;
+
+                info!("✅ Stream: PulseAudio processing task started for {}", device_name);
+
+                while let Some(sample) = stream.next().await {
+                    buffer.push(sample);
+                    if buffer.len() >= frames_per_chunk {
+                        capture.process_audio_data(&buffer);
+                        buffer.clear();
+                    }
+                }
+
+                if !buffer.is_empty() {
+                    capture.process_audio_data(&buffer);
+                }
+
+                info!("⚠️ Stream: PulseAudio processing task ended for {}", device_name);
+            }
+        });
+
+        Ok(Self {
+            device,
+            backend: StreamBackend::PulseAudio {
+                task: Some(task),
+            },
+        })
+    }
+
     /// Build stream based on sample format
     fn build_stream(
         device: &Device,
@@ -343,6 +433,16 @@ impl AudioStream {
                     info!("Core Audio task aborted");
                 }
             }
+            #[cfg(target_os = "linux")]
+            StreamBackend::PulseAudio { task } => {
+                // Aborting drops the PulseAudioStream, which kills parec
+                if let Some(task_handle) = task {
+                    info!("Aborting PulseAudio task...");
+                    task_handle.abort();
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                    info!("PulseAudio task aborted");
+                }
+            }
         }
 
         // Explicitly drop self.device Arc reference
@@ -28,6 +28,38 @@ pub async fn list_system_audio_devices_command() -> Result<Vec<String>, String>
         .map_err(|e| format!("Failed to list system audio devices: {}", e))
 }
 
+/// List applications currently playing audio that can be recorded individually.
+/// Only supported with the PulseAudio/PipeWire backend; other platforms return an empty list.
+#[command]
+pub async fn list_system_audio_applications_command() -> Result<Vec<SystemAudioApplication>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        use crate::audio::capture::pulse_audio;
+
+        if !pulse_audio::is_available() {
+            return Ok(Vec::new());
+        }
+
+        let inputs = pulse_audio::list_sink_inputs()
+            .map_err(|e| format!("Failed to list application audio streams: {}", e))?;
+
+        Ok(inputs
+            .into_iter()
+            .map(|input| SystemAudioApplication {
+                device_name: input.device_name(),
+                name: input.application_name,
+                media_name: input.media_name,
+                is_playing: !input.corked,
+            })
+            .collect())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
 /// Check if the app has permission to access system audio
 #[command]
 pub async fn check_system_audio_permissions_command() -> bool {
@@ -110,6 +142,16 @@ pub struct SystemAudioStartedPayload {
 #[derive(serde::Serialize, Clone)]
 pub struct SystemAudioStoppedPayload;
 
+/// Application audio stream that can be selected as the system device
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct SystemAudioApplication {
+    pub name: String,
+    /// Name to pass as `system_device_name` when starting a recording
+    pub device_name: String,
+    pub media_name: Option<String>,
+    pub is_playing: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
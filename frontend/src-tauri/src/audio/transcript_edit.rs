@@ -0,0 +1,778 @@
+// Segment-level transcript editing with revision history and forced re-alignment.
+//
+// Edit, insert, delete, split and join operate on single segments of the current transcript.
+// Each change is applied together with a transcript_revisions row holding the affected rows
+// before and after, so there is a record of what changed, when and by whom. Manually changed
+// segments get edited_at, which keeps background passes from overwriting them.
+//
+// Re-alignment recomputes segment and word timings after text corrections: the loaded Whisper
+// engine decodes the segment's audio constrained to the tokens of the corrected text, so each
+// corrected word gets its own token timestamps. The decoded words are matched back to the
+// corrected words by edit distance; a word the decoder could not place (e.g. it stopped early)
+// is put between its aligned neighbours. Segments longer than one Whisper window (30s) keep
+// their timing.
+
+use super::decoder::decode_audio_range;
+use super::quality_pass::sync_transcripts_file;
+use super::retranscription::{find_audio_file, get_or_init_whisper, RetranscriptionGuard};
+use crate::database::models::{TranscriptRevision, TranscriptVersionSegment};
+use crate::database::repositories::{
+    meeting::MeetingsRepository, transcript_revision::TranscriptRevisionsRepository,
+    transcript_version::TranscriptVersionsRepository,
+};
+use crate::state::AppState;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use uuid::Uuid;
+
+/// Author recorded for edits when the caller does not name one
+const DEFAULT_AUTHOR: &str = "user";
+
+/// Audio context decoded around a segment so words at its edges are not clipped
+const REALIGN_PADDING_SECS: f64 = 0.3;
+
+/// Revisions returned by get_transcript_revisions when no limit is given
+const DEFAULT_REVISION_LIMIT: i64 = 200;
+
+/// Changes to one segment; missing fields are left as they are. An empty speaker clears it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SegmentUpdate {
+    pub text: Option<String>,
+    pub speaker: Option<String>,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+}
+
+/// A segment added by hand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewSegment {
+    pub text: String,
+    pub audio_start_time: f64,
+    pub audio_end_time: f64,
+    pub speaker: Option<String>,
+    pub language: Option<String>,
+}
+
+/// Timing of one word after re-alignment, in recording seconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+    /// False when the word had no recognized counterpart and was placed by interpolation
+    pub aligned: bool,
+}
+
+/// Result of an edit: the recorded revision and the segment rows it wrote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptEditResult {
+    pub revision: TranscriptRevision,
+    pub segments: Vec<TranscriptVersionSegment>,
+}
+
+fn now_rfc3339() -> String {
+    chrono::Utc::now().to_rfc3339()
+}
+
+fn non_empty_text(text: &str) -> Result<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(anyhow!("Segment text cannot be empty"));
+    }
+    Ok(text.to_string())
+}
+
+fn validate_timing(start: Option<f64>, end: Option<f64>) -> Result<()> {
+    match (start, end) {
+        (Some(start), _) if !start.is_finite() || start < 0.0 => {
+            Err(anyhow!("Segment start must be a non-negative time"))
+        }
+        (Some(start), Some(end)) if !end.is_finite() || end <= start => {
+            Err(anyhow!("Segment end must be after its start"))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn segment_duration(start: Option<f64>, end: Option<f64>) -> Option<f64> {
+    match (start, end) {
+        (Some(start), Some(end)) => Some(end - start),
+        _ => None,
+    }
+}
+
+fn find_segment<'a>(segments: &'a [TranscriptVersionSegment], segment_id: &str) -> Result<&'a TranscriptVersionSegment> {
+    segments
+        .iter()
+        .find(|s| s.id == segment_id)
+        .ok_or_else(|| anyhow!("Transcript segment not found: {}", segment_id))
+}
+
+fn apply_update(segment: &TranscriptVersionSegment, update: &SegmentUpdate) -> Result<TranscriptVersionSegment> {
+    let mut updated = segment.clone();
+    if let Some(text) = &update.text {
+        updated.text = non_empty_text(text)?;
+        if updated.text != segment.text {
+            // The translation described the old text
+            updated.translation = None;
+        }
+    }
+    if let Some(speaker) = &update.speaker {
+        updated.speaker = Some(speaker.trim().to_string()).filter(|s| !s.is_empty());
+    }
+    if update.audio_start_time.is_some() {
+        updated.audio_start_time = update.audio_start_time;
+    }
+    if update.audio_end_time.is_some() {
+        updated.audio_end_time = update.audio_end_time;
+    }
+    validate_timing(updated.audio_start_time, updated.audio_end_time)?;
+    updated.duration = segment_duration(updated.audio_start_time, updated.audio_end_time).or(segment.duration);
+
+    if updated == *segment {
+        return Err(anyhow!("The segment is unchanged"));
+    }
+    if updated.text != segment.text
+        || updated.audio_start_time != segment.audio_start_time
+        || updated.audio_end_time != segment.audio_end_time
+    {
+        // The word timings described the old text and times
+        updated.word_timings = None;
+    }
+    updated.edited_at = Some(now_rfc3339());
+    Ok(updated)
+}
+
+fn new_segment(segment: &NewSegment) -> Result<TranscriptVersionSegment> {
+    let text = non_empty_text(&segment.text)?;
+    validate_timing(Some(segment.audio_start_time), Some(segment.audio_end_time))?;
+    Ok(TranscriptVersionSegment {
+        id: format!("transcript-{}", Uuid::new_v4()),
+        text,
+        timestamp: crate::utils::format_timestamp(segment.audio_start_time),
+        audio_start_time: Some(segment.audio_start_time),
+        audio_end_time: Some(segment.audio_end_time),
+        duration: Some(segment.audio_end_time - segment.audio_start_time),
+        speaker: segment.speaker.clone().filter(|s| !s.trim().is_empty()),
+        edited_at: Some(now_rfc3339()),
+        language: segment.language.clone(),
+        translation: None,
+        word_timings: None,
+    })
+}
+
+/// Split a segment before the character at `at_char`. The split time defaults to the
+/// same fraction of the segment's duration as the text before the split.
+fn split_segment(
+    segment: &TranscriptVersionSegment,
+    at_char: usize,
+    at_seconds: Option<f64>,
+) -> Result<(TranscriptVersionSegment, TranscriptVersionSegment)> {
+    let chars: Vec<char> = segment.text.chars().collect();
+    let first_text: String = chars[..at_char.min(chars.len())].iter().collect();
+    let second_text: String = chars[at_char.min(chars.len())..].iter().collect();
+    let (first_text, second_text) = (first_text.trim().to_string(), second_text.trim().to_string());
+    if first_text.is_empty() || second_text.is_empty() {
+        return Err(anyhow!("Both parts of a split segment need text"));
+    }
+
+    let split_time = match (segment.audio_start_time, segment.audio_end_time) {
+        (Some(start), Some(end)) => {
+            let at = at_seconds.unwrap_or_else(|| {
+                let fraction = first_text.chars().count() as f64
+                    / (first_text.chars().count() + second_text.chars().count()) as f64;
+                start + (end - start) * fraction
+            });
+            if !at.is_finite() || at <= start || at >= end {
+                return Err(anyhow!("Split time must lie inside the segment"));
+            }
+            Some(at)
+        }
+        _ => None,
+    };
+
+    let edited_at = Some(now_rfc3339());
+    let first = TranscriptVersionSegment {
+        text: first_text,
+        audio_end_time: split_time.or(segment.audio_end_time),
+        duration: segment_duration(segment.audio_start_time, split_time.or(segment.audio_end_time)),
+        edited_at: edited_at.clone(),
+        translation: None,
+        word_timings: None,
+        ..segment.clone()
+    };
+    let second = TranscriptVersionSegment {
+        id: format!("transcript-{}", Uuid::new_v4()),
+        text: second_text,
+        timestamp: split_time
+            .map(crate::utils::format_timestamp)
+            .unwrap_or_else(|| segment.timestamp.clone()),
+        audio_start_time: split_time.or(segment.audio_start_time),
+        duration: segment_duration(split_time.or(segment.audio_start_time), segment.audio_end_time),
+        edited_at,
+        translation: None,
+        word_timings: None,
+        ..segment.clone()
+    };
+    Ok((first, second))
+}
+
+/// Join segments that are adjacent in the transcript into the first of them
+fn join_segments(
+    current: &[TranscriptVersionSegment],
+    segment_ids: &[String],
+) -> Result<(Vec<TranscriptVersionSegment>, TranscriptVersionSegment)> {
+    if segment_ids.len() < 2 {
+        return Err(anyhow!("Select at least two segments to join"));
+    }
+    let mut indices = segment_ids
+        .iter()
+        .map(|id| current.iter().position(|s| &s.id == id).ok_or_else(|| anyhow!("Transcript segment not found: {}", id)))
+        .collect::<Result<Vec<usize>>>()?;
+    indices.sort_unstable();
+    indices.dedup();
+    if indices.len() < 2 || indices.windows(2).any(|w| w[1] != w[0] + 1) {
+        return Err(anyhow!("Only adjacent segments can be joined"));
+    }
+
+    let parts: Vec<TranscriptVersionSegment> = indices.iter().map(|&i| current[i].clone()).collect();
+    let first = &parts[0];
+    let all_same = |f: fn(&TranscriptVersionSegment) -> &Option<String>| parts.iter().all(|p| f(p) == f(first));
+
+    let start = parts.iter().filter_map(|p| p.audio_start_time).reduce(f64::min);
+    let end = parts.iter().filter_map(|p| p.audio_end_time).reduce(f64::max);
+    let translation = parts
+        .iter()
+        .map(|p| p.translation.as_deref())
+        .collect::<Option<Vec<&str>>>()
+        .map(|t| t.join(" "));
+
+    let joined = TranscriptVersionSegment {
+        text: parts.iter().map(|p| p.text.trim()).collect::<Vec<_>>().join(" "),
+        audio_start_time: start,
+        audio_end_time: end,
+        duration: segment_duration(start, end),
+        speaker: if all_same(|p| &p.speaker) { first.speaker.clone() } else { None },
+        language: if all_same(|p| &p.language) { first.language.clone() } else { None },
+        translation,
+        edited_at: Some(now_rfc3339()),
+        word_timings: None,
+        ..first.clone()
+    };
+    Ok((parts, joined))
+}
+
+fn normalize_word(word: &str) -> String {
+    word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Align corrected words to recognized (word, start, end) timings by edit distance.
+/// Matched and substituted words take the recognized timing; the remaining words are spread
+/// between their aligned neighbours, within `bounds`.
+fn align_words(text: &str, recognized: &[(String, f64, f64)], bounds: (f64, f64)) -> Vec<WordTiming> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (n, m) = (words.len(), recognized.len());
+    let expected: Vec<String> = words.iter().map(|w| normalize_word(w)).collect();
+    let heard: Vec<String> = recognized.iter().map(|(w, _, _)| normalize_word(w)).collect();
+
+    // cost[i][j]: edit distance between the first i corrected and first j recognized words
+    let mut cost = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in cost.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=m {
+        cost[0][j] = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitution = cost[i - 1][j - 1] + usize::from(expected[i - 1] != heard[j - 1]);
+            cost[i][j] = substitution.min(cost[i - 1][j] + 1).min(cost[i][j - 1] + 1);
+        }
+    }
+
+    // Backtrack: which recognized word each corrected word maps to
+    let mut mapping: Vec<Option<usize>> = vec![None; n];
+    let (mut i, mut j) = (n, m);
+    while i > 0 && j > 0 {
+        let substitution = cost[i - 1][j - 1] + usize::from(expected[i - 1] != heard[j - 1]);
+        if cost[i][j] == substitution {
+            mapping[i - 1] = Some(j - 1);
+            i -= 1;
+            j -= 1;
+        } else if cost[i][j] == cost[i - 1][j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    let (lower, upper) = bounds;
+    let mut timings: Vec<WordTiming> = words
+        .iter()
+        .zip(&mapping)
+        .map(|(word, mapped)| match mapped {
+            Some(j) => {
+                let (_, start, end) = recognized[*j];
+                let start = start.clamp(lower, upper);
+                WordTiming {
+                    word: word.to_string(),
+                    start,
+                    end: end.clamp(start, upper),
+                    aligned: true,
+                }
+            }
+            None => WordTiming {
+                word: word.to_string(),
+                start: lower,
+                end: lower,
+                aligned: false,
+            },
+        })
+        .collect();
+
+    // Spread each run of unaligned words evenly over the gap between its neighbours
+    let mut k = 0;
+    while k < timings.len() {
+        if timings[k].aligned {
+            k += 1;
+            continue;
+        }
+        let run_start = k;
+        while k < timings.len() && !timings[k].aligned {
+            k += 1;
+        }
+        let gap_start = if run_start > 0 { timings[run_start - 1].end } else { lower };
+        let gap_end = if k < timings.len() { timings[k].start } else { upper };
+        let gap_end = gap_end.max(gap_start);
+        let step = (gap_end - gap_start) / (k - run_start) as f64;
+        for (offset, timing) in timings[run_start..k].iter_mut().enumerate() {
+            timing.start = gap_start + step * offset as f64;
+            timing.end = timing.start + step;
+        }
+    }
+
+    timings
+}
+
+/// Segment bounds from re-aligned words: first and last aligned word, if any
+fn aligned_bounds(timings: &[WordTiming]) -> Option<(f64, f64)> {
+    let first = timings.iter().find(|t| t.aligned)?;
+    let last = timings.iter().rev().find(|t| t.aligned)?;
+    (last.end > first.start).then_some((first.start, last.end))
+}
+
+fn author_or_default(author: Option<String>) -> String {
+    author
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .unwrap_or_else(|| DEFAULT_AUTHOR.to_string())
+}
+
+/// Apply a change, record it and bring transcripts.json up to date
+async fn commit_change<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: &str,
+    operation: &str,
+    author: &str,
+    before: &[TranscriptVersionSegment],
+    after: &[TranscriptVersionSegment],
+    word_timings: &[(String, String)],
+) -> Result<TranscriptEditResult> {
+    let pool = pool_from(app).map_err(|e| anyhow!(e))?;
+    let revision =
+        TranscriptRevisionsRepository::apply(&pool, meeting_id, operation, Some(author), before, after, word_timings)
+            .await
+            .map_err(|e| anyhow!("Failed to save transcript change: {}", e))?;
+
+    let current = current_segments(&pool, meeting_id).await?;
+    sync_transcripts_file(app, meeting_id, &current).await;
+
+    let _ = app.emit(
+        "transcript-edited",
+        serde_json::json!({
+            "meeting_id": meeting_id,
+            "operation": operation,
+            "revision_id": &revision.id,
+        }),
+    );
+    Ok(TranscriptEditResult {
+        revision,
+        segments: after.to_vec(),
+    })
+}
+
+async fn current_segments(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<TranscriptVersionSegment>> {
+    TranscriptVersionsRepository::get_current_segments(pool, meeting_id)
+        .await
+        .map_err(|e| anyhow!("Failed to load transcripts: {}", e))
+}
+
+/// Recompute timings of the given segments (all segments when empty) against the audio
+async fn realign_segments<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    segment_ids: &[String],
+    author: &str,
+) -> Result<TranscriptEditResult> {
+    let current = current_segments(pool, meeting_id).await?;
+    let targets: Vec<&TranscriptVersionSegment> = if segment_ids.is_empty() {
+        current.iter().collect()
+    } else {
+        segment_ids
+            .iter()
+            .map(|id| find_segment(&current, id))
+            .collect::<Result<Vec<_>>>()?
+    };
+    let targets: Vec<&TranscriptVersionSegment> = targets
+        .into_iter()
+        .filter(|s| s.audio_start_time.is_some() && s.audio_end_time.is_some())
+        .collect();
+    if targets.is_empty() {
+        return Err(anyhow!("No timed segments to re-align"));
+    }
+
+    let folder = MeetingsRepository::get_meeting_metadata(pool, meeting_id)
+        .await?
+        .and_then(|m| m.folder_path)
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("Meeting has no recording folder"))?;
+    let audio_path = find_audio_file(&folder)?;
+
+    let engine = get_or_init_whisper(app, None).await?;
+    let default_language = crate::get_language_preference_internal();
+
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    let mut word_timings = Vec::new();
+
+    for segment in targets {
+        let (start, end) = (segment.audio_start_time.unwrap_or_default(), segment.audio_end_time.unwrap_or_default());
+        let window_start = (start - REALIGN_PADDING_SECS).max(0.0);
+        let window_end = end + REALIGN_PADDING_SECS;
+
+        // Only the segment's own window is decoded, not the whole recording
+        let path = audio_path.clone();
+        let decoded = tokio::task::spawn_blocking(move || {
+            decode_audio_range(&path, window_start, window_end).map(|audio| audio.to_whisper_format())
+        })
+        .await
+        .map_err(|e| anyhow!("Decode task panicked: {}", e))?;
+        let samples = match decoded {
+            Ok(samples) => samples,
+            Err(e) => {
+                warn!("Failed to decode audio of segment {}: {}", segment.id, e);
+                continue;
+            }
+        };
+        if samples.len() <= 1600 {
+            continue;
+        }
+        // The recording may end before the padded window does
+        let window_end = window_start + samples.len() as f64 / 16000.0;
+
+        let language = segment.language.clone().or_else(|| default_language.clone());
+        let recognized = match engine.align_word_timings(&samples, language, &segment.text).await {
+            Ok(words) => words
+                .into_iter()
+                .map(|(word, s, e)| (word, window_start + s, window_start + e))
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                warn!("Failed to re-align segment {}: {}", segment.id, e);
+                continue;
+            }
+        };
+
+        let timings = align_words(&segment.text, &recognized, (window_start, window_end));
+        let Some((new_start, new_end)) = aligned_bounds(&timings) else {
+            info!("No words of segment {} could be aligned; keeping its timing", segment.id);
+            continue;
+        };
+
+        let mut realigned = segment.clone();
+        realigned.audio_start_time = Some(new_start);
+        realigned.audio_end_time = Some(new_end);
+        realigned.duration = Some(new_end - new_start);
+        realigned.timestamp = crate::utils::format_timestamp(new_start);
+        let timings_json = serde_json::to_string(&timings)?;
+        realigned.word_timings = Some(timings_json.clone());
+
+        word_timings.push((segment.id.clone(), timings_json));
+        before.push(segment.clone());
+        after.push(realigned);
+    }
+
+    if after.is_empty() {
+        return Err(anyhow!("None of the segments could be re-aligned"));
+    }
+
+    info!("Re-aligned {} segments of meeting {}", after.len(), meeting_id);
+    commit_change(app, meeting_id, "realign", author, &before, &after, &word_timings).await
+}
+
+fn pool_from<R: Runtime>(app: &AppHandle<R>) -> Result<SqlitePool, String> {
+    app.try_state::<AppState>()
+        .map(|state| state.db_manager.pool().clone())
+        .ok_or_else(|| "App state not available".to_string())
+}
+
+// Tauri commands
+
+/// Change a segment's text, speaker and/or timing
+#[tauri::command]
+pub async fn edit_transcript_segment<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    segment_id: String,
+    update: SegmentUpdate,
+    author: Option<String>,
+) -> Result<TranscriptEditResult, String> {
+    let pool = pool_from(&app)?;
+    let result: Result<TranscriptEditResult> = async {
+        let current = current_segments(&pool, &meeting_id).await?;
+        let segment = find_segment(&current, &segment_id)?;
+        let updated = apply_update(segment, &update)?;
+        commit_change(&app, &meeting_id, "edit", &author_or_default(author), &[segment.clone()], &[updated], &[]).await
+    }
+    .await;
+    result.map_err(|e| e.to_string())
+}
+
+/// Add a segment the transcription missed
+#[tauri::command]
+pub async fn insert_transcript_segment<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    segment: NewSegment,
+    author: Option<String>,
+) -> Result<TranscriptEditResult, String> {
+    let pool = pool_from(&app)?;
+    let result: Result<TranscriptEditResult> = async {
+        let inserted = new_segment(&segment)?;
+        commit_change(&app, &meeting_id, "insert", &author_or_default(author), &[], &[inserted], &[]).await
+    }
+    .await;
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_transcript_segment<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    segment_id: String,
+    author: Option<String>,
+) -> Result<TranscriptEditResult, String> {
+    let pool = pool_from(&app)?;
+    let result: Result<TranscriptEditResult> = async {
+        let current = current_segments(&pool, &meeting_id).await?;
+        let segment = find_segment(&current, &segment_id)?;
+        commit_change(&app, &meeting_id, "delete", &author_or_default(author), &[segment.clone()], &[], &[]).await
+    }
+    .await;
+    result.map_err(|e| e.to_string())
+}
+
+/// Split a segment before character `at_char` of its text, optionally at an explicit time
+#[tauri::command]
+pub async fn split_transcript_segment<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    segment_id: String,
+    at_char: usize,
+    at_seconds: Option<f64>,
+    author: Option<String>,
+) -> Result<TranscriptEditResult, String> {
+    let pool = pool_from(&app)?;
+    let result: Result<TranscriptEditResult> = async {
+        let current = current_segments(&pool, &meeting_id).await?;
+        let segment = find_segment(&current, &segment_id)?;
+        let (first, second) = split_segment(segment, at_char, at_seconds)?;
+        commit_change(&app, &meeting_id, "split", &author_or_default(author), &[segment.clone()], &[first, second], &[])
+            .await
+    }
+    .await;
+    result.map_err(|e| e.to_string())
+}
+
+/// Join adjacent segments into one
+#[tauri::command]
+pub async fn join_transcript_segments<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    segment_ids: Vec<String>,
+    author: Option<String>,
+) -> Result<TranscriptEditResult, String> {
+    let pool = pool_from(&app)?;
+    let result: Result<TranscriptEditResult> = async {
+        let current = current_segments(&pool, &meeting_id).await?;
+        let (parts, joined) = join_segments(&current, &segment_ids)?;
+        commit_change(&app, &meeting_id, "join", &author_or_default(author), &parts, &[joined], &[]).await
+    }
+    .await;
+    result.map_err(|e| e.to_string())
+}
+
+/// Recompute segment and word timings after text corrections (all segments when none given)
+#[tauri::command]
+pub async fn realign_transcript_segments<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    segment_ids: Option<Vec<String>>,
+    author: Option<String>,
+) -> Result<TranscriptEditResult, String> {
+    if super::recording_commands::is_recording().await {
+        return Err("Cannot re-align segments while recording".to_string());
+    }
+
+    // Shares the transcription engine with retranscription and the quality pass
+    let _guard = RetranscriptionGuard::acquire()?;
+    let pool = pool_from(&app)?;
+
+    let result = realign_segments(
+        &app,
+        &pool,
+        &meeting_id,
+        &segment_ids.unwrap_or_default(),
+        &author_or_default(author),
+    )
+    .await;
+    super::common::unload_engine_after_batch(false).await;
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_transcript_revisions<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    limit: Option<i64>,
+) -> Result<Vec<TranscriptRevision>, String> {
+    let pool = pool_from(&app)?;
+    TranscriptRevisionsRepository::list_revisions(&pool, &meeting_id, limit.unwrap_or(DEFAULT_REVISION_LIMIT))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Word timings of re-aligned segments, keyed by segment id
+#[tauri::command]
+pub async fn get_transcript_word_timings<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+) -> Result<std::collections::HashMap<String, Vec<WordTiming>>, String> {
+    let pool = pool_from(&app)?;
+    let rows = TranscriptRevisionsRepository::get_word_timings(&pool, &meeting_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, json)| serde_json::from_str(&json).ok().map(|timings| (id, timings)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: &str, text: &str, start: f64, end: f64) -> TranscriptVersionSegment {
+        TranscriptVersionSegment {
+            speaker: Some("mic".to_string()),
+            language: Some("en".to_string()),
+            ..crate::database::models::segment(id, text, start, end)
+        }
+    }
+
+    fn heard(words: &[(&str, f64, f64)]) -> Vec<(String, f64, f64)> {
+        words.iter().map(|(w, s, e)| (w.to_string(), *s, *e)).collect()
+    }
+
+    #[test]
+    fn test_apply_update() {
+        let original = segment("a", "helo world", 1.0, 3.0);
+        let update = SegmentUpdate {
+            text: Some(" hello world ".to_string()),
+            speaker: Some(String::new()),
+            ..Default::default()
+        };
+        let updated = apply_update(&original, &update).unwrap();
+        assert_eq!(updated.text, "hello world");
+        assert_eq!(updated.speaker, None);
+        assert!(updated.edited_at.is_some());
+
+        assert!(apply_update(&original, &SegmentUpdate::default()).is_err());
+        let backwards = SegmentUpdate {
+            audio_end_time: Some(0.5),
+            ..Default::default()
+        };
+        assert!(apply_update(&original, &backwards).is_err());
+    }
+
+    #[test]
+    fn test_split_segment() {
+        let original = segment("a", "first part second part", 10.0, 20.0);
+        let (first, second) = split_segment(&original, 11, None).unwrap();
+        assert_eq!(first.id, "a");
+        assert_eq!(first.text, "first part");
+        assert_eq!(second.text, "second part");
+        assert_ne!(second.id, "a");
+        assert_eq!(first.audio_end_time, second.audio_start_time);
+        assert!(first.audio_end_time.unwrap() > 10.0 && first.audio_end_time.unwrap() < 20.0);
+
+        let (first, _) = split_segment(&original, 11, Some(12.5)).unwrap();
+        assert_eq!(first.audio_end_time, Some(12.5));
+        assert!(split_segment(&original, 0, None).is_err());
+        assert!(split_segment(&original, 11, Some(25.0)).is_err());
+    }
+
+    #[test]
+    fn test_join_segments() {
+        let mut current = vec![
+            segment("a", "one", 0.0, 1.0),
+            segment("b", "two", 1.0, 2.0),
+            segment("c", "three", 2.0, 3.0),
+        ];
+        current[1].speaker = Some("system".to_string());
+
+        let (parts, joined) = join_segments(&current, &["b".to_string(), "a".to_string()]).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(joined.id, "a");
+        assert_eq!(joined.text, "one two");
+        assert_eq!((joined.audio_start_time, joined.audio_end_time), (Some(0.0), Some(2.0)));
+        assert_eq!(joined.speaker, None);
+        assert_eq!(joined.language.as_deref(), Some("en"));
+
+        assert!(join_segments(&current, &["a".to_string(), "c".to_string()]).is_err());
+        assert!(join_segments(&current, &["a".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_align_words_matches_and_interpolates() {
+        // "really" was not heard at all
+        let recognized = heard(&[("We", 1.0, 1.2), ("deploy", 1.2, 1.6), ("Kubernetes", 1.7, 2.4), ("fast.", 2.8, 3.2)]);
+        let timings = align_words("We deploy Kubernetes really fast.", &recognized, (0.7, 3.5));
+        assert_eq!(timings.len(), 5);
+        assert_eq!((timings[0].start, timings[0].end), (1.0, 1.2));
+        assert!(timings[2].aligned);
+        assert!(!timings[3].aligned);
+        // The unheard word sits between its neighbours
+        assert!(timings[3].start >= timings[2].end && timings[3].end <= timings[4].start);
+        assert_eq!(aligned_bounds(&timings), Some((1.0, 3.2)));
+
+        // A misrecognized word takes the timing of what was heard in its place
+        let recognized = heard(&[("We", 1.0, 1.2), ("deploy", 1.2, 1.6), ("Cooper", 1.7, 2.4)]);
+        let timings = align_words("We deploy Kubernetes", &recognized, (0.7, 3.5));
+        assert!(timings[2].aligned);
+        assert_eq!((timings[2].start, timings[2].end), (1.7, 2.4));
+    }
+
+    #[test]
+    fn test_align_words_without_recognition() {
+        let timings = align_words("nothing was heard", &[], (5.0, 8.0));
+        assert!(timings.iter().all(|t| !t.aligned));
+        assert_eq!(timings[0].start, 5.0);
+        assert!((timings[2].end - 8.0).abs() < 1e-9);
+        assert_eq!(aligned_bounds(&timings), None);
+    }
+}
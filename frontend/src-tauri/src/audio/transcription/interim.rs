@@ -0,0 +1,261 @@
+// audio/transcription/interim.rs
+//
+// Live interim transcripts: the pipeline publishes snapshots of the speech segment
+// that VAD is still accumulating, and the worker transcribes the newest snapshot
+// whenever no finished segment is waiting. Partial updates share the sequence_id
+// of the final segment that later replaces them.
+
+use log::info;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, RwLock};
+use tokio::sync::Notify;
+
+/// Default cadence between interim decodes of the in-progress segment
+pub const DEFAULT_INTERIM_INTERVAL_MS: u64 = 1000;
+
+/// Minimum in-progress audio before the first interim decode
+pub const MIN_INTERIM_AUDIO_MS: f64 = 1000.0;
+
+/// Longest in-progress segment that is still decoded live (finals are split at 25s)
+pub const MAX_INTERIM_AUDIO_MS: f64 = 25_000.0;
+
+/// Two snapshots whose segment start differs by less than this belong to the same segment
+const SAME_SEGMENT_TOLERANCE_SECS: f64 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InterimConfig {
+    pub enabled: bool,
+    pub interval_ms: u64,
+}
+
+impl Default for InterimConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_ms: DEFAULT_INTERIM_INTERVAL_MS,
+        }
+    }
+}
+
+impl From<&crate::audio::recording_preferences::RecordingPreferences> for InterimConfig {
+    fn from(prefs: &crate::audio::recording_preferences::RecordingPreferences) -> Self {
+        Self {
+            enabled: prefs.live_interim_transcripts,
+            // Anything faster than 250ms just burns CPU re-decoding the same audio
+            interval_ms: prefs.interim_interval_ms.max(250),
+        }
+    }
+}
+
+/// In-progress speech audio published by the pipeline (16kHz mono)
+#[derive(Debug, Clone)]
+pub struct InterimSnapshot {
+    pub samples: Vec<f32>,
+    /// Seconds from recording start where the in-progress segment began
+    pub segment_start: f64,
+    /// Seconds from recording start of the newest sample in the snapshot
+    pub segment_end: f64,
+}
+
+static CONFIG: Lazy<RwLock<InterimConfig>> = Lazy::new(|| RwLock::new(InterimConfig::default()));
+
+// Latest-only slot: publishing overwrites an undecoded snapshot instead of queueing it
+static LATEST: Lazy<Mutex<Option<InterimSnapshot>>> = Lazy::new(|| Mutex::new(None));
+static PUBLISHED: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// Set the interim configuration for the next recording session
+pub fn configure(config: InterimConfig) {
+    info!(
+        "Live interim transcripts: enabled={}, interval={}ms",
+        config.enabled, config.interval_ms
+    );
+    if let Ok(mut current) = CONFIG.write() {
+        *current = config;
+    }
+    clear();
+}
+
+/// Current interim configuration
+pub fn config() -> InterimConfig {
+    CONFIG.read().map(|c| *c).unwrap_or_default()
+}
+
+/// Publish the newest in-progress snapshot, replacing any that was not decoded yet
+pub fn publish(snapshot: InterimSnapshot) {
+    if let Ok(mut latest) = LATEST.lock() {
+        *latest = Some(snapshot);
+    }
+    PUBLISHED.notify_one();
+}
+
+/// Drop any pending snapshot (segment finished or recording stopped)
+pub fn clear() {
+    if let Ok(mut latest) = LATEST.lock() {
+        *latest = None;
+    }
+}
+
+/// Wait for the next published snapshot and take it out of the slot
+pub async fn next_snapshot() -> InterimSnapshot {
+    loop {
+        if let Some(snapshot) = LATEST.lock().ok().and_then(|mut latest| latest.take()) {
+            return snapshot;
+        }
+        PUBLISHED.notified().await;
+    }
+}
+
+/// Tracks the segment currently shown as interim text in the worker
+pub struct InterimSegment {
+    pub sequence_id: u64,
+    pub segment_start: f64,
+    stabilizer: PrefixStabilizer,
+}
+
+impl InterimSegment {
+    pub fn new(sequence_id: u64, segment_start: f64) -> Self {
+        Self {
+            sequence_id,
+            segment_start,
+            stabilizer: PrefixStabilizer::default(),
+        }
+    }
+
+    /// Whether a snapshot continues this segment
+    pub fn matches(&self, snapshot: &InterimSnapshot) -> bool {
+        (self.segment_start - snapshot.segment_start).abs() < SAME_SEGMENT_TOLERANCE_SECS
+    }
+
+    /// Whether a finished segment ending at `final_end` closes this interim segment.
+    /// Finals are decoded in order, so the first one that reaches past the interim start is it.
+    pub fn is_finalized_by(&self, final_end: f64) -> bool {
+        final_end > self.segment_start
+    }
+
+    pub fn stabilize(&mut self, hypothesis: &str) -> StabilizedText {
+        self.stabilizer.update(hypothesis)
+    }
+}
+
+/// Interim text split into the part that no longer changes and the volatile tail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StabilizedText {
+    pub stable: String,
+    pub unstable: String,
+}
+
+impl StabilizedText {
+    pub fn text(&self) -> String {
+        match (self.stable.is_empty(), self.unstable.is_empty()) {
+            (true, _) => self.unstable.clone(),
+            (false, true) => self.stable.clone(),
+            (false, false) => format!("{} {}", self.stable, self.unstable),
+        }
+    }
+}
+
+/// Local-agreement prefix stabilizer.
+///
+/// A word becomes stable once two consecutive hypotheses agree on it (and on every
+/// word before it). Stable words are never retracted, so re-decoding the growing
+/// segment only ever changes the tail of the displayed text.
+#[derive(Debug, Default)]
+pub struct PrefixStabilizer {
+    committed: Vec<String>,
+    previous: Vec<String>,
+}
+
+impl PrefixStabilizer {
+    pub fn update(&mut self, hypothesis: &str) -> StabilizedText {
+        let words: Vec<String> = hypothesis.split_whitespace().map(str::to_string).collect();
+
+        let agreed = common_prefix_len(&self.previous, &words);
+        if agreed > self.committed.len() && common_prefix_len(&self.committed, &words) == self.committed.len() {
+            self.committed = words[..agreed].to_vec();
+        }
+        self.previous = words.clone();
+
+        // Keep committed words even if this hypothesis rewrote them; show only what lies beyond
+        let tail = if words.len() > self.committed.len() {
+            words[self.committed.len()..].join(" ")
+        } else {
+            String::new()
+        };
+
+        StabilizedText {
+            stable: self.committed.join(" "),
+            unstable: tail,
+        }
+    }
+}
+
+fn common_prefix_len(a: &[String], b: &[String]) -> usize {
+    a.iter()
+        .zip(b.iter())
+        .take_while(|(x, y)| normalize_word(x) == normalize_word(y))
+        .count()
+}
+
+/// Compare words ignoring case and trailing punctuation, which Whisper changes freely mid-sentence
+fn normalize_word(word: &str) -> String {
+    word.trim_end_matches(|c: char| c.is_ascii_punctuation())
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stabilizer_commits_agreed_prefix() {
+        let mut stabilizer = PrefixStabilizer::default();
+
+        let first = stabilizer.update("so the plan");
+        assert_eq!(first.stable, "");
+        assert_eq!(first.text(), "so the plan");
+
+        let second = stabilizer.update("so the plan is to");
+        assert_eq!(second.stable, "so the plan");
+        assert_eq!(second.unstable, "is to");
+
+        let third = stabilizer.update("so the plan is to ship");
+        assert_eq!(third.stable, "so the plan is to");
+        assert_eq!(third.text(), "so the plan is to ship");
+    }
+
+    #[test]
+    fn test_stabilizer_never_retracts_committed_words() {
+        let mut stabilizer = PrefixStabilizer::default();
+        stabilizer.update("we should deploy");
+        stabilizer.update("we should deploy today");
+
+        // Decoder changes its mind about an already committed word
+        let flicker = stabilizer.update("we could deploy today and");
+        assert_eq!(flicker.stable, "we should deploy");
+        assert_eq!(flicker.unstable, "today and");
+    }
+
+    #[test]
+    fn test_stabilizer_ignores_punctuation_and_case() {
+        let mut stabilizer = PrefixStabilizer::default();
+        stabilizer.update("Hello everyone");
+        let result = stabilizer.update("hello everyone, welcome");
+        assert_eq!(result.stable, "hello everyone,");
+        assert_eq!(result.unstable, "welcome");
+    }
+
+    #[test]
+    fn test_interim_segment_matching() {
+        let segment = InterimSegment::new(7, 12.0);
+        let same = InterimSnapshot { samples: vec![], segment_start: 12.02, segment_end: 14.0 };
+        let other = InterimSnapshot { samples: vec![], segment_start: 15.0, segment_end: 16.0 };
+
+        assert!(segment.matches(&same));
+        assert!(!segment.matches(&other));
+
+        // Earlier finals end before the interim segment starts
+        assert!(!segment.is_finalized_by(11.5));
+        assert!(segment.is_finalized_by(13.8));
+    }
+}
@@ -0,0 +1,183 @@
+// audio/transcription/language.rs
+//
+// Per-segment language identification for code-switched meetings. Instead of letting
+// Whisper's auto mode pick any of ~100 languages per chunk, detection is restricted to a
+// small candidate set ("en", "de", ...) and each segment is then transcribed in the
+// language it was spoken in. Optionally an English translation is stored alongside the
+// original text so summaries can use either track.
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "language_detection.json";
+const STORE_KEY: &str = "settings";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LanguageDetectionSettings {
+    /// Languages a segment may be detected as; empty disables per-segment detection
+    pub candidates: Vec<String>,
+    /// Also store an English translation of every non-English segment
+    pub store_translation: bool,
+}
+
+impl LanguageDetectionSettings {
+    /// Per-segment detection takes over from Whisper's own auto modes once candidates are set
+    pub fn applies_to(&self, language_preference: Option<&str>) -> bool {
+        !self.candidates.is_empty() && matches!(language_preference, Some("auto") | Some("auto-translate"))
+    }
+}
+
+/// Language and optional English translation of one transcript segment
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SegmentLanguage {
+    pub language: Option<String>,
+    pub translation: Option<String>,
+}
+
+/// Result of a multilingual decode: original text plus its language information
+#[derive(Debug, Clone)]
+pub struct MultilingualTranscript {
+    pub text: String,
+    pub confidence: f32,
+    pub language: SegmentLanguage,
+}
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> LanguageDetectionSettings {
+    let store = match app.store(STORE_FILE) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to access language detection settings store: {}, using defaults", e);
+            return LanguageDetectionSettings::default();
+        }
+    };
+    match store.get(STORE_KEY) {
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            warn!("Failed to deserialize language detection settings: {}, using defaults", e);
+            LanguageDetectionSettings::default()
+        }),
+        None => LanguageDetectionSettings::default(),
+    }
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &LanguageDetectionSettings) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to access language detection settings store: {}", e))?;
+    let value = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize language detection settings: {}", e))?;
+    store.set(STORE_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save language detection settings: {}", e))
+}
+
+/// Most likely language among the candidates, with its probability renormalized over the
+/// candidate set. `scores` are (language code, probability) pairs from the detector.
+/// Without candidates the overall most likely language is returned.
+pub fn pick_language(scores: &[(String, f32)], candidates: &[String]) -> Option<(String, f32)> {
+    let allowed: Vec<&(String, f32)> = scores
+        .iter()
+        .filter(|(code, _)| candidates.is_empty() || candidates.iter().any(|c| c == code))
+        .collect();
+
+    let total: f32 = allowed.iter().map(|(_, p)| *p).sum();
+    let (code, probability) = allowed
+        .into_iter()
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))?;
+
+    let normalized = if total > 0.0 { probability / total } else { 0.0 };
+    Some((code.clone(), normalized))
+}
+
+/// Lowercase, trim and deduplicate candidate codes, keeping the user's order
+fn normalize_candidates(candidates: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for code in candidates {
+        let code = code.trim().to_lowercase();
+        if !code.is_empty() && !normalized.contains(&code) {
+            normalized.push(code);
+        }
+    }
+    normalized
+}
+
+// Tauri commands
+
+#[tauri::command]
+pub async fn get_language_detection_settings<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<LanguageDetectionSettings, String> {
+    Ok(load_settings(&app))
+}
+
+#[tauri::command]
+pub async fn set_language_detection_settings<R: Runtime>(
+    app: AppHandle<R>,
+    candidates: Vec<String>,
+    store_translation: bool,
+) -> Result<LanguageDetectionSettings, String> {
+    let candidates = normalize_candidates(candidates);
+    if let Some(unknown) = candidates.iter().find(|c| whisper_rs::get_lang_id(c).is_none()) {
+        return Err(format!("Unsupported language code: {}", unknown));
+    }
+
+    info!(
+        "Setting language detection candidates to {:?} (store translation: {})",
+        candidates, store_translation
+    );
+    let updated = LanguageDetectionSettings {
+        candidates,
+        store_translation,
+    };
+    save_settings(&app, &updated)?;
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scores(pairs: &[(&str, f32)]) -> Vec<(String, f32)> {
+        pairs.iter().map(|(c, p)| (c.to_string(), *p)).collect()
+    }
+
+    #[test]
+    fn test_pick_language_respects_candidates() {
+        // Detector leans towards Dutch, but the meeting is English/German
+        let scores = scores(&[("en", 0.2), ("de", 0.3), ("nl", 0.5)]);
+        let candidates = vec!["en".to_string(), "de".to_string()];
+
+        let (code, probability) = pick_language(&scores, &candidates).unwrap();
+        assert_eq!(code, "de");
+        assert!((probability - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_pick_language_without_candidates() {
+        let scores = scores(&[("en", 0.7), ("fr", 0.3)]);
+        assert_eq!(pick_language(&scores, &[]).unwrap().0, "en");
+        assert!(pick_language(&[], &[]).is_none());
+        assert!(pick_language(&scores, &["ja".to_string()]).is_none());
+    }
+
+    #[test]
+    fn test_applies_only_to_auto_modes() {
+        let settings = LanguageDetectionSettings {
+            candidates: vec!["en".to_string(), "es".to_string()],
+            store_translation: false,
+        };
+        assert!(settings.applies_to(Some("auto")));
+        assert!(settings.applies_to(Some("auto-translate")));
+        assert!(!settings.applies_to(Some("es")));
+        assert!(!settings.applies_to(None));
+        assert!(!LanguageDetectionSettings::default().applies_to(Some("auto")));
+    }
+
+    #[test]
+    fn test_normalize_candidates() {
+        let normalized = normalize_candidates(vec![" EN ".to_string(), "de".to_string(), "en".to_string(), "".to_string()]);
+        assert_eq!(normalized, vec!["en".to_string(), "de".to_string()]);
+    }
+}
@@ -3,6 +3,7 @@
 // Parakeet transcription provider implementation.
 
 use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+use super::vocabulary::Vocabulary;
 use async_trait::async_trait;
 use log::warn;
 use std::sync::Arc;
@@ -24,6 +25,7 @@ impl TranscriptionProvider for ParakeetProvider {
         &self,
         audio: Vec<f32>,
         language: Option<String>,
+        _vocabulary: &Vocabulary,
     ) -> std::result::Result<TranscriptResult, TranscriptionError> {
         // Log language preference warning if set (Parakeet doesn't support it yet)
         if let Some(ref lang) = language {
@@ -3,6 +3,7 @@
 // Defines the unified TranscriptionProvider trait and common types for all
 // transcription engines (Whisper, Parakeet, future providers).
 
+use super::vocabulary::Vocabulary;
 use async_trait::async_trait;
 
 // ============================================================================
@@ -53,6 +54,7 @@ pub trait TranscriptionProvider: Send + Sync {
     /// # Arguments
     /// * `audio` - Audio samples (16kHz mono, f32 format)
     /// * `language` - Optional language hint (e.g., "en", "es", "fr")
+    /// * `vocabulary` - Custom vocabulary of this job (providers that take a prompt use its glossary)
     ///
     /// # Returns
     /// * `TranscriptResult` with text, optional confidence, and partial flag
@@ -60,6 +62,7 @@ pub trait TranscriptionProvider: Send + Sync {
         &self,
         audio: Vec<f32>,
         language: Option<String>,
+        vocabulary: &Vocabulary,
     ) -> std::result::Result<TranscriptResult, TranscriptionError>;
 
     /// Check if a model is currently loaded
@@ -0,0 +1,592 @@
+// audio/transcription/vocabulary.rs
+//
+// Custom vocabulary (product names, customer names, acronyms). Terms come from a
+// user-wide list plus an optional per-meeting list stored in SQLite. Whisper receives
+// them as a glossary in its initial prompt; every provider's output then goes through
+// a fuzzy phonetic correction pass that snaps near-misses back to the exact term.
+//
+// A recording or import has no meeting yet, so it can borrow the list of an existing
+// meeting (e.g. the last occurrence of a recurring meeting); the list is copied onto
+// the meeting it creates, where retranscription and the quality pass find it.
+
+use crate::database::models::VocabularyTerm;
+use crate::database::repositories::vocabulary::VocabularyRepository;
+use crate::state::AppState;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Upper bound for the glossary appended to Whisper's initial prompt (prompt is ~224 tokens)
+const MAX_GLOSSARY_CHARS: usize = 400;
+
+/// Terms shorter than this are only replaced on exact or "sounds like" matches
+const MIN_FUZZY_TERM_LEN: usize = 4;
+
+/// Edit-distance similarity that is accepted on its own
+const SPELLING_SIMILARITY: f64 = 0.85;
+
+/// Edit-distance similarity that is accepted when the phonetic keys agree
+/// (split words and doubled letters cost edits but keep the sound). Many ordinary words
+/// share a key with some term, so this stays close to the spelling threshold.
+const PHONETIC_SIMILARITY: f64 = 0.8;
+
+/// Longest spoken form, relative to the term, that a fuzzy match may replace (and vice versa)
+const MAX_LENGTH_RATIO: f64 = 1.25;
+
+/// Spoken words up to this length are only replaced on exact or "sounds like" matches
+const MAX_SHORT_WORD_LEN: usize = 3;
+
+/// Common words that are never replaced by a fuzzy match
+const STOP_WORDS: &[&str] = &[
+    "about", "above", "after", "again", "also", "because", "been", "before", "being", "below", "between", "both",
+    "could", "does", "doing", "down", "during", "each", "from", "further", "going", "have", "having", "here",
+    "hers", "into", "just", "know", "like", "make", "many", "more", "most", "much", "need", "okay", "once",
+    "only", "other", "over", "really", "right", "same", "should", "some", "such", "sure", "take", "than",
+    "that", "their", "them", "then", "there", "these", "they", "thing", "things", "think", "this", "those",
+    "through", "time", "under", "until", "very", "want", "well", "were", "what", "when", "where", "which",
+    "while", "will", "with", "would", "yeah", "your", "yours",
+];
+
+// Vocabulary of the live recording; batch jobs load their own (see load_for_meeting)
+static RECORDING: Lazy<RwLock<Arc<Vocabulary>>> = Lazy::new(|| RwLock::new(Arc::new(Vocabulary::default())));
+
+// Meeting whose list the live recording uses (see set_recording_vocabulary)
+static RECORDING_SOURCE: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+
+/// A term and the misrecognitions it should replace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VocabularyEntry {
+    pub term: String,
+    #[serde(default)]
+    pub sounds_like: Vec<String>,
+}
+
+impl From<&VocabularyTerm> for VocabularyEntry {
+    fn from(row: &VocabularyTerm) -> Self {
+        Self {
+            term: row.term.clone(),
+            sounds_like: row.sounds_like_list(),
+        }
+    }
+}
+
+struct PreparedEntry {
+    term: String,
+    word_count: usize,
+    /// Widest window of spoken words that can match (term or longest alias)
+    max_words: usize,
+    normalized: String,
+    phonetic: String,
+    aliases: Vec<String>,
+}
+
+/// Prepared vocabulary: glossary for Whisper plus the correction pass
+#[derive(Default)]
+pub struct Vocabulary {
+    entries: Vec<PreparedEntry>,
+}
+
+impl Vocabulary {
+    pub fn new(entries: &[VocabularyEntry]) -> Self {
+        let mut prepared: Vec<PreparedEntry> = entries
+            .iter()
+            .filter(|e| !e.term.trim().is_empty())
+            .map(|e| {
+                let normalized = normalize(&e.term);
+                let word_count = e.term.split_whitespace().count();
+                let longest_alias = e
+                    .sounds_like
+                    .iter()
+                    .map(|a| a.split_whitespace().count())
+                    .max()
+                    .unwrap_or(0);
+                PreparedEntry {
+                    term: e.term.trim().to_string(),
+                    word_count,
+                    max_words: (word_count + 1).max(longest_alias),
+                    phonetic: phonetic_key(&normalized),
+                    normalized,
+                    aliases: e
+                        .sounds_like
+                        .iter()
+                        .map(|a| normalize(a))
+                        .filter(|a| !a.is_empty())
+                        .collect(),
+                }
+            })
+            .collect();
+
+        // Longer terms first so "Acme Cloud" wins over "Acme"
+        prepared.sort_by(|a, b| b.word_count.cmp(&a.word_count));
+        Self { entries: prepared }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whisper initial prompt with the glossary appended
+    pub fn prompt(&self, base: &str) -> String {
+        match self.glossary() {
+            Some(glossary) => format!("{} {}", base, glossary),
+            None => base.to_string(),
+        }
+    }
+
+    /// "Glossary: A, B, C." capped to fit Whisper's prompt window
+    pub fn glossary(&self) -> Option<String> {
+        let mut glossary = String::new();
+        for entry in &self.entries {
+            if glossary.len() + entry.term.len() + 2 > MAX_GLOSSARY_CHARS {
+                break;
+            }
+            if !glossary.is_empty() {
+                glossary.push_str(", ");
+            }
+            glossary.push_str(&entry.term);
+        }
+
+        if glossary.is_empty() {
+            None
+        } else {
+            Some(format!("Glossary: {}.", glossary))
+        }
+    }
+
+    /// Replace near-misses of vocabulary terms with the exact term
+    pub fn correct(&self, text: &str) -> String {
+        if self.entries.is_empty() || text.trim().is_empty() {
+            return text.to_string();
+        }
+
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let cores: Vec<String> = tokens.iter().map(|t| normalize(t)).collect();
+        let mut output: Vec<String> = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+
+        while i < tokens.len() {
+            match self.match_at(&cores, i) {
+                Some((entry, n)) => {
+                    let prefix = leading_punctuation(tokens[i]);
+                    let suffix = trailing_punctuation(tokens[i + n - 1]);
+                    output.push(format!("{}{}{}", prefix, entry.term, suffix));
+                    i += n;
+                }
+                None => {
+                    output.push(tokens[i].to_string());
+                    i += 1;
+                }
+            }
+        }
+
+        output.join(" ")
+    }
+
+    /// Longest vocabulary match starting at word `i`, as (entry, words consumed)
+    fn match_at(&self, cores: &[String], i: usize) -> Option<(&PreparedEntry, usize)> {
+        if cores[i].is_empty() {
+            return None;
+        }
+
+        for entry in &self.entries {
+            // Recognizers split and merge words freely ("cooper netties" for "Kubernetes")
+            let min_words = entry.word_count.saturating_sub(1).max(1);
+
+            for n in (min_words..=entry.max_words).rev() {
+                if i + n > cores.len() {
+                    continue;
+                }
+                let window = &cores[i..i + n];
+                if window.iter().any(|w| w.is_empty()) {
+                    continue;
+                }
+
+                let candidate = window.join("");
+                if candidate == entry.normalized || entry.aliases.contains(&candidate) {
+                    return Some((entry, n));
+                }
+                if entry.normalized.len() < MIN_FUZZY_TERM_LEN || !fuzzy_candidate(window, &candidate, &entry.normalized) {
+                    continue;
+                }
+
+                let similarity = similarity(&candidate, &entry.normalized);
+                let same_word_count = n == entry.word_count;
+                let phonetic_match = phonetic_key(&candidate) == entry.phonetic;
+
+                if (same_word_count && similarity >= SPELLING_SIMILARITY)
+                    || (phonetic_match && similarity >= PHONETIC_SIMILARITY)
+                {
+                    return Some((entry, n));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Load the user-wide list plus the meeting's own list (if any) for one transcription job
+pub async fn load_for_meeting<R: Runtime>(app: &AppHandle<R>, meeting_id: Option<&str>) -> Arc<Vocabulary> {
+    let state = match app.try_state::<AppState>() {
+        Some(state) => state,
+        None => return Arc::new(Vocabulary::default()),
+    };
+
+    match VocabularyRepository::get_terms_for_meeting(state.db_manager.pool(), meeting_id).await {
+        Ok(rows) => {
+            let entries: Vec<VocabularyEntry> = rows.iter().map(VocabularyEntry::from).collect();
+            info!(
+                "Custom vocabulary loaded: {} terms (meeting: {:?})",
+                entries.len(),
+                meeting_id
+            );
+            Arc::new(Vocabulary::new(&entries))
+        }
+        Err(e) => {
+            warn!("Failed to load custom vocabulary: {}", e);
+            Arc::new(Vocabulary::default())
+        }
+    }
+}
+
+/// Vocabulary of the live recording, read by its worker for every segment
+pub fn recording() -> Arc<Vocabulary> {
+    RECORDING
+        .read()
+        .map(|v| v.clone())
+        .unwrap_or_else(|_| Arc::new(Vocabulary::default()))
+}
+
+/// Whether spoken words may be replaced by a term they only resemble: no short or common
+/// words, and about the term's length
+fn fuzzy_candidate(window: &[String], candidate: &str, term: &str) -> bool {
+    let ordinary_word = window
+        .iter()
+        .any(|word| word.chars().count() <= MAX_SHORT_WORD_LEN || STOP_WORDS.contains(&word.as_str()));
+    if ordinary_word {
+        return false;
+    }
+    let (candidate_len, term_len) = (candidate.chars().count() as f64, term.chars().count() as f64);
+    candidate_len.max(term_len) <= candidate_len.min(term_len) * MAX_LENGTH_RATIO
+}
+
+/// Meeting whose list the live recording uses, if one was chosen
+pub fn recording_source() -> Option<String> {
+    RECORDING_SOURCE.read().ok().and_then(|source| source.clone())
+}
+
+/// Reload the live recording's vocabulary (at recording start and after edits of its lists)
+pub async fn activate_for_recording<R: Runtime>(app: &AppHandle<R>) {
+    let vocabulary = load_for_meeting(app, recording_source().as_deref()).await;
+    if let Ok(mut recording) = RECORDING.write() {
+        *recording = vocabulary;
+    }
+}
+
+/// Copies the borrowed list onto the meeting a recording or import created
+pub async fn adopt_for_new_meeting<R: Runtime>(app: &AppHandle<R>, source_meeting_id: &str, meeting_id: &str) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    if let Err(e) = VocabularyRepository::copy_meeting_terms(state.db_manager.pool(), source_meeting_id, meeting_id).await {
+        warn!("Failed to copy vocabulary of meeting {} to {}: {}", source_meeting_id, meeting_id, e);
+    }
+}
+
+/// Called when the live recording's meeting is saved: it keeps the borrowed list, and the
+/// next recording starts with the user-wide list again
+pub async fn finish_recording<R: Runtime>(app: &AppHandle<R>, meeting_id: &str) {
+    let source = RECORDING_SOURCE.write().ok().and_then(|mut source| source.take());
+    if let Some(source) = source {
+        adopt_for_new_meeting(app, &source, meeting_id).await;
+    }
+}
+
+/// Lowercase alphanumerics only; word boundaries are dropped so split/merged words compare equal
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+fn leading_punctuation(token: &str) -> &str {
+    let start = token.find(|c: char| c.is_alphanumeric()).unwrap_or(token.len());
+    &token[..start]
+}
+
+fn trailing_punctuation(token: &str) -> &str {
+    let end = token
+        .rfind(|c: char| c.is_alphanumeric())
+        .map(|i| i + token[i..].chars().next().map_or(1, |c| c.len_utf8()))
+        .unwrap_or(0);
+    &token[end..]
+}
+
+/// Soundex-style key over the whole word: consonants grouped by sound, vowels dropped,
+/// repeated groups collapsed. "Kubernetes" and "coopernetties" share a key.
+fn phonetic_key(normalized: &str) -> String {
+    let text = normalized.replace("ph", "f");
+    let mut key = String::new();
+    let mut last: Option<char> = None;
+
+    for (i, c) in text.chars().enumerate() {
+        let code = match c {
+            'b' | 'f' | 'p' | 'v' => Some('1'),
+            'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+            'd' | 't' => Some('3'),
+            'l' => Some('4'),
+            'm' | 'n' => Some('5'),
+            'r' => Some('6'),
+            '0'..='9' => Some(c),
+            _ => None,
+        };
+
+        match code {
+            Some(code) => {
+                if last != Some(code) {
+                    key.push(code);
+                }
+                last = Some(code);
+            }
+            None => {
+                if i == 0 {
+                    key.push('a');
+                }
+                // Vowels separate repeats, h/w/y do not (as in Soundex)
+                if !matches!(c, 'h' | 'w' | 'y') {
+                    last = None;
+                }
+            }
+        }
+    }
+
+    key
+}
+
+/// 1.0 for identical strings, falling with Levenshtein distance
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+// Tauri commands
+
+/// List vocabulary terms. Without a meeting id only the user-wide list is returned.
+#[tauri::command]
+pub async fn get_vocabulary_terms<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: Option<String>,
+) -> Result<Vec<VocabularyTerm>, String> {
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| "App state not available".to_string())?;
+
+    VocabularyRepository::get_terms(state.db_manager.pool(), meeting_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to load vocabulary: {}", e))
+}
+
+#[tauri::command]
+pub async fn add_vocabulary_term<R: Runtime>(
+    app: AppHandle<R>,
+    term: String,
+    sounds_like: Option<Vec<String>>,
+    meeting_id: Option<String>,
+) -> Result<VocabularyTerm, String> {
+    if term.trim().is_empty() {
+        return Err("Vocabulary term cannot be empty".to_string());
+    }
+
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| "App state not available".to_string())?;
+
+    let row = VocabularyRepository::add_term(
+        state.db_manager.pool(),
+        term.trim(),
+        &sounds_like.unwrap_or_default(),
+        meeting_id.as_deref(),
+    )
+    .await
+    .map_err(|e| format!("Failed to add vocabulary term: {}", e))?;
+
+    refresh_if_user_wide(&app, row.meeting_id.as_deref()).await;
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn update_vocabulary_term<R: Runtime>(
+    app: AppHandle<R>,
+    id: String,
+    term: String,
+    sounds_like: Option<Vec<String>>,
+) -> Result<VocabularyTerm, String> {
+    if term.trim().is_empty() {
+        return Err("Vocabulary term cannot be empty".to_string());
+    }
+
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| "App state not available".to_string())?;
+
+    let row = VocabularyRepository::update_term(
+        state.db_manager.pool(),
+        &id,
+        term.trim(),
+        &sounds_like.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| format!("Failed to update vocabulary term: {}", e))?
+    .ok_or_else(|| format!("Vocabulary term {} not found", id))?;
+
+    refresh_if_user_wide(&app, row.meeting_id.as_deref()).await;
+    Ok(row)
+}
+
+#[tauri::command]
+pub async fn delete_vocabulary_term<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    let state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| "App state not available".to_string())?;
+
+    let deleted = VocabularyRepository::delete_term(state.db_manager.pool(), &id)
+        .await
+        .map_err(|e| format!("Failed to delete vocabulary term: {}", e))?;
+
+    match deleted {
+        Some(meeting_id) => {
+            refresh_if_user_wide(&app, meeting_id.as_deref()).await;
+            Ok(())
+        }
+        None => Err(format!("Vocabulary term {} not found", id)),
+    }
+}
+
+/// Uses the list of an existing meeting for the next (or running) recording; None for the
+/// user-wide list only
+#[tauri::command]
+pub async fn set_recording_vocabulary<R: Runtime>(app: AppHandle<R>, meeting_id: Option<String>) -> Result<(), String> {
+    let meeting_id = meeting_id.filter(|id| !id.trim().is_empty());
+    if let Ok(mut source) = RECORDING_SOURCE.write() {
+        source.clone_from(&meeting_id);
+    }
+    info!("Recording vocabulary set to meeting {:?}", meeting_id);
+    if crate::audio::recording_commands::is_recording().await {
+        activate_for_recording(&app).await;
+    }
+    Ok(())
+}
+
+/// Edits of the user-wide list or the recording's list take effect immediately, including
+/// in a running recording
+async fn refresh_if_user_wide<R: Runtime>(app: &AppHandle<R>, meeting_id: Option<&str>) {
+    if meeting_id.is_none() || meeting_id == recording_source().as_deref() {
+        activate_for_recording(app).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocabulary(terms: &[(&str, &[&str])]) -> Vocabulary {
+        let entries: Vec<VocabularyEntry> = terms
+            .iter()
+            .map(|(term, aliases)| VocabularyEntry {
+                term: term.to_string(),
+                sounds_like: aliases.iter().map(|a| a.to_string()).collect(),
+            })
+            .collect();
+        Vocabulary::new(&entries)
+    }
+
+    #[test]
+    fn test_corrects_phonetic_near_misses() {
+        let vocab = vocabulary(&[("Kubernetes", &["cooper netties"]), ("Zendesk", &[])]);
+
+        assert_eq!(
+            vocab.correct("we moved the cooper netties cluster."),
+            "we moved the Kubernetes cluster."
+        );
+        assert_eq!(vocab.correct("the kubernetties cluster"), "the Kubernetes cluster");
+        assert_eq!(vocab.correct("open a ticket in zen desk,"), "open a ticket in Zendesk,");
+        assert_eq!(vocab.correct("nothing to fix here"), "nothing to fix here");
+    }
+
+    #[test]
+    fn test_common_words_stay_unchanged() {
+        let vocab = vocabulary(&[("Tableau", &[]), ("Miro", &[]), ("Kubernetes", &[]), ("Salesforce", &[])]);
+
+        // Same phonetic key as a term, but not similar enough ("table"/"Tableau")
+        assert_eq!(vocab.correct("put it on the table"), "put it on the table");
+        // Common and short words are never replaced by a fuzzy match ("more"/"Miro")
+        assert_eq!(vocab.correct("we need more time"), "we need more time");
+        assert_eq!(vocab.correct("the cube net is down"), "the cube net is down");
+        assert_eq!(vocab.correct("sales forecast for march"), "sales forecast for march");
+        assert_eq!(
+            vocab.correct("nothing here should change at all"),
+            "nothing here should change at all"
+        );
+    }
+
+    #[test]
+    fn test_short_terms_need_exact_or_alias_match() {
+        let vocab = vocabulary(&[("SLA", &["ess ell ay", "sla's"]), ("Acme", &[])]);
+
+        assert_eq!(vocab.correct("check the sla"), "check the SLA");
+        assert_eq!(vocab.correct("per the ess ell ay terms"), "per the SLA terms");
+        // "sea" is not close enough to a three-letter acronym
+        assert_eq!(vocab.correct("the sea level"), "the sea level");
+        assert_eq!(vocab.correct("acme renewal"), "Acme renewal");
+    }
+
+    #[test]
+    fn test_prefers_longer_terms() {
+        let vocab = vocabulary(&[("Acme", &[]), ("Acme Cloud", &[])]);
+        assert_eq!(vocab.correct("on acme cloud today"), "on Acme Cloud today");
+    }
+
+    #[test]
+    fn test_glossary_is_capped() {
+        let terms: Vec<VocabularyEntry> = (0..200)
+            .map(|i| VocabularyEntry {
+                term: format!("Term{}", i),
+                sounds_like: Vec::new(),
+            })
+            .collect();
+        let glossary = Vocabulary::new(&terms).glossary().unwrap();
+
+        assert!(glossary.starts_with("Glossary: Term0, Term1"));
+        assert!(glossary.len() <= MAX_GLOSSARY_CHARS + "Glossary: .".len());
+        assert!(Vocabulary::default().glossary().is_none());
+    }
+
+    #[test]
+    fn test_phonetic_key() {
+        assert_eq!(phonetic_key("kubernetes"), phonetic_key("coopernetties"));
+        assert_eq!(phonetic_key("philips"), phonetic_key("filips"));
+        assert_ne!(phonetic_key("zendesk"), phonetic_key("zoom"));
+    }
+}
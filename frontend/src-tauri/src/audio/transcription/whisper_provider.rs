@@ -3,6 +3,7 @@
 // Whisper transcription provider implementation.
 
 use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+use super::vocabulary::Vocabulary;
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -23,10 +24,11 @@ impl TranscriptionProvider for WhisperProvider {
         &self,
         audio: Vec<f32>,
         language: Option<String>,
+        vocabulary: &Vocabulary,
     ) -> std::result::Result<TranscriptResult, TranscriptionError> {
         match self
             .engine
-            .transcribe_audio_with_confidence(audio, language)
+            .transcribe_audio_with_confidence(audio, language, vocabulary)
             .await
         {
             Ok((text, confidence, is_partial)) => Ok(TranscriptResult {
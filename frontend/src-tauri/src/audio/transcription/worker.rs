@@ -3,8 +3,11 @@
 // Parallel transcription worker pool and chunk processing logic.
 
 use super::engine::TranscriptionEngine;
+use super::interim::{self, InterimSegment, InterimSnapshot};
+use super::language::{self, SegmentLanguage};
 use super::provider::TranscriptionError;
-use crate::audio::AudioChunk;
+use super::vocabulary::{self, Vocabulary};
+use crate::audio::{AudioChunk, RecordingDeviceType};
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -36,11 +39,24 @@ pub struct TranscriptUpdate {
     pub audio_start_time: f64, // Seconds from recording start (e.g., 125.3)
     pub audio_end_time: f64,   // Seconds from recording start (e.g., 128.6)
     pub duration: f64,          // Segment duration in seconds (e.g., 3.3)
+    // Per-segment language identification (final updates only, when enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
 }
 
 // NOTE: get_transcript_history and get_recording_meeting_name functions
 // have been moved to recording_commands.rs where they have access to RECORDING_MANAGER
 
+/// Next unit of work for a transcription worker
+enum WorkItem {
+    /// Finished VAD segment (None when the channel is closed)
+    Final(Option<AudioChunk>),
+    /// Snapshot of the segment VAD is still accumulating
+    Interim(InterimSnapshot),
+}
+
 /// Optimized parallel transcription task ensuring ZERO chunk loss
 pub fn start_transcription_task<R: Runtime>(
     app: AppHandle<R>,
@@ -110,11 +126,40 @@ pub fn start_transcription_task<R: Runtime>(
                     warn!("⚠️ Worker {} pre-validation: {} model not loaded - chunks may be skipped", worker_id, engine_name);
                 }
 
+                // Live interim transcripts (configured per recording session)
+                let interim_enabled = interim::config().enabled;
+                let mut interim_segment: Option<InterimSegment> = None;
+                let mut last_final_end = 0.0f64;
+
                 loop {
                     // Try to get a chunk to process
-                    let chunk = {
+                    let work = {
                         let mut receiver = work_receiver_clone.lock().await;
-                        receiver.recv().await
+                        if interim_enabled {
+                            tokio::select! {
+                                // Finished segments always win; interim decoding only fills idle time
+                                biased;
+                                chunk = receiver.recv() => WorkItem::Final(chunk),
+                                snapshot = interim::next_snapshot() => WorkItem::Interim(snapshot),
+                            }
+                        } else {
+                            WorkItem::Final(receiver.recv().await)
+                        }
+                    };
+
+                    let chunk = match work {
+                        WorkItem::Final(chunk) => chunk,
+                        WorkItem::Interim(snapshot) => {
+                            process_interim_snapshot(
+                                &engine_clone,
+                                snapshot,
+                                &mut interim_segment,
+                                last_final_end,
+                                &app_clone,
+                            )
+                            .await;
+                            continue;
+                        }
                     };
 
                     match chunk {
@@ -143,6 +188,19 @@ pub fn start_transcription_task<R: Runtime>(
                             let chunk_timestamp = chunk.timestamp;
                             let chunk_duration = chunk.data.len() as f64 / chunk.sample_rate as f64;
 
+                            // A finished segment replaces the interim text shown for it
+                            last_final_end = last_final_end.max(chunk_timestamp + chunk_duration);
+                            let finalized_interim_id = match interim_segment.take() {
+                                Some(segment) if segment.is_finalized_by(chunk_timestamp + chunk_duration) => {
+                                    Some(segment.sequence_id)
+                                }
+                                other => {
+                                    interim_segment = other;
+                                    None
+                                }
+                            };
+                            let mut final_emitted = false;
+
                             // Timing + energy for advanced logging
                             let transcription_start = std::time::Instant::now();
                             let energy = if !chunk.data.is_empty() {
@@ -151,15 +209,23 @@ pub fn start_transcription_task<R: Runtime>(
                                 0.0
                             };
 
+                            // Read per segment so vocabulary edits apply to the running recording
+                            let recording_vocabulary = vocabulary::recording();
+
                             // Transcribe with provider-agnostic approach
                             match transcribe_chunk_with_provider(
                                 &engine_clone,
                                 chunk,
                                 &app_clone,
+                                true,
+                                &recording_vocabulary,
                             )
                             .await
                             {
-                                Ok((transcript, confidence_opt, is_partial)) => {
+                                Ok((transcript, confidence_opt, is_partial, segment_language)) => {
+                                    // Snap misheard product/customer names to the custom vocabulary
+                                    let transcript = recording_vocabulary.correct(&transcript);
+
                                     // Provider-aware confidence threshold
                                     let confidence_threshold = match &engine_clone {
                                         TranscriptionEngine::Whisper(_) | TranscriptionEngine::Provider(_) => 0.3,
@@ -200,7 +266,9 @@ pub fn start_transcription_task<R: Runtime>(
                                         }
 
                                         // Generate sequence ID and calculate timestamps FIRST
-                                        let sequence_id = SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst);
+                                        // (reuse the interim segment's ID so the final replaces it)
+                                        let sequence_id = finalized_interim_id
+                                            .unwrap_or_else(|| SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst));
                                         let audio_start_time = chunk_timestamp; // Already in seconds from recording start
                                         let audio_end_time = chunk_timestamp + chunk_duration;
 
@@ -219,12 +287,15 @@ pub fn start_transcription_task<R: Runtime>(
                                             source: "Audio".to_string(),
                                             sequence_id,
                                             chunk_start_time: chunk_timestamp, // Legacy compatibility
-                                            is_partial,
+                                            // Finished VAD segments are final; only interim decodes are partial
+                                            is_partial: false,
                                             confidence: confidence_opt.unwrap_or(0.85), // Default for providers without confidence
                                             // NEW: Recording-relative timestamps for sync
                                             audio_start_time,
                                             audio_end_time,
                                             duration: chunk_duration,
+                                            language: segment_language.language,
+                                            translation: segment_language.translation,
                                         };
 
                                         if let Err(e) = app_clone.emit("transcript-update", &update)
@@ -234,6 +305,7 @@ pub fn start_transcription_task<R: Runtime>(
                                                 worker_id, e
                                             );
                                         }
+                                        final_emitted = true;
 
                                         // Advanced logging: send transcription chunk details
                                         if crate::device_registry::is_advanced_logging_enabled() {
@@ -266,11 +338,13 @@ pub fn start_transcription_task<R: Runtime>(
                                         TranscriptionError::AudioTooShort { .. } => {
                                             // Skip silently, this is expected for very short chunks
                                             info!("Worker {}: {}", worker_id, e);
+                                            discard_interim(&app_clone, finalized_interim_id);
                                             chunks_completed_clone.fetch_add(1, Ordering::SeqCst);
                                             continue;
                                         }
                                         TranscriptionError::ModelNotLoaded => {
                                             warn!("Worker {}: Model unloaded during transcription", worker_id);
+                                            discard_interim(&app_clone, finalized_interim_id);
                                             chunks_completed_clone.fetch_add(1, Ordering::SeqCst);
                                             continue;
                                         }
@@ -294,6 +368,11 @@ pub fn start_transcription_task<R: Runtime>(
                                 }
                             }
 
+                            // Final text was empty, low-confidence or failed - drop the stale interim text
+                            if !final_emitted {
+                                discard_interim(&app_clone, finalized_interim_id);
+                            }
+
                             // Mark chunk as completed
                             let completed =
                                 chunks_completed_clone.fetch_add(1, Ordering::SeqCst) + 1;
@@ -440,13 +519,105 @@ pub fn start_transcription_task<R: Runtime>(
     })
 }
 
+/// Decode the in-progress segment and emit a partial update.
+/// The partial carries the sequence_id that the finished segment will reuse.
+async fn process_interim_snapshot<R: Runtime>(
+    engine: &TranscriptionEngine,
+    snapshot: InterimSnapshot,
+    interim_segment: &mut Option<InterimSegment>,
+    last_final_end: f64,
+    app: &AppHandle<R>,
+) {
+    // Published before VAD closed the segment, and the final is already out
+    if snapshot.segment_start < last_final_end {
+        return;
+    }
+
+    if !engine.is_model_loaded().await {
+        return;
+    }
+
+    // A new segment started while the previous one never got its final (e.g. dispatch lag)
+    if let Some(previous) = interim_segment.as_ref() {
+        if !previous.matches(&snapshot) {
+            discard_interim(app, interim_segment.take().map(|s| s.sequence_id));
+        }
+    }
+
+    let segment_start = snapshot.segment_start;
+    let duration = snapshot.segment_end - snapshot.segment_start;
+    let chunk = AudioChunk {
+        data: snapshot.samples,
+        sample_rate: 16000,
+        timestamp: segment_start,
+        chunk_id: u64::MAX - 100, // Not a queued chunk; used for logging only
+        device_type: RecordingDeviceType::Microphone,
+    };
+
+    // Interim decodes skip language identification; the final decode detects it
+    let recording_vocabulary = vocabulary::recording();
+    let (text, confidence) = match transcribe_chunk_with_provider(engine, chunk, app, false, &recording_vocabulary).await {
+        Ok((text, confidence, _, _)) => (recording_vocabulary.correct(&text), confidence),
+        Err(e) => {
+            warn!("Interim transcription failed: {}", e);
+            return;
+        }
+    };
+
+    let below_threshold = match engine {
+        TranscriptionEngine::Parakeet(_) => false,
+        _ => confidence.map_or(false, |c| c < 0.3),
+    };
+    if text.trim().is_empty() || below_threshold {
+        return;
+    }
+
+    let segment = interim_segment.get_or_insert_with(|| {
+        InterimSegment::new(SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst), segment_start)
+    });
+    let stabilized = segment.stabilize(&text);
+
+    let update = TranscriptUpdate {
+        text: stabilized.text(),
+        timestamp: format_current_timestamp(),
+        source: "Audio".to_string(),
+        sequence_id: segment.sequence_id,
+        chunk_start_time: segment_start,
+        is_partial: true,
+        confidence: confidence.unwrap_or(0.85),
+        audio_start_time: segment_start,
+        audio_end_time: segment_start + duration,
+        duration,
+        language: None,
+        translation: None,
+    };
+
+    if let Err(e) = app.emit("transcript-update", &update) {
+        error!("Failed to emit interim transcript update: {}", e);
+    }
+}
+
+/// Tell the frontend to drop interim text whose segment produced no final transcript
+fn discard_interim<R: Runtime>(app: &AppHandle<R>, sequence_id: Option<u64>) {
+    if let Some(sequence_id) = sequence_id {
+        let _ = app.emit(
+            "transcript-interim-discarded",
+            serde_json::json!({ "sequence_id": sequence_id }),
+        );
+    }
+}
+
 /// Transcribe audio chunk using the appropriate provider (Whisper, Parakeet, or trait-based)
-/// Returns: (text, confidence Option, is_partial)
+/// `identify_language` enables per-segment language detection (Whisper only, when configured)
+/// `vocabulary` provides Whisper's glossary prompt
+/// Returns: (text, confidence Option, is_partial, segment language)
 async fn transcribe_chunk_with_provider<R: Runtime>(
     engine: &TranscriptionEngine,
     chunk: AudioChunk,
     app: &AppHandle<R>,
-) -> std::result::Result<(String, Option<f32>, bool), TranscriptionError> {
+    identify_language: bool,
+    vocabulary: &Vocabulary,
+) -> std::result::Result<(String, Option<f32>, bool, SegmentLanguage), TranscriptionError> {
     // Convert to 16kHz mono for transcription
     let transcription_data = if chunk.sample_rate != 16000 {
         crate::audio::audio_processing::resample_audio(&chunk.data, chunk.sample_rate, 16000)
@@ -484,23 +655,41 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
         TranscriptionEngine::Whisper(whisper_engine) => {
             // Get language preference from global state
             let language = crate::get_language_preference_internal();
+            let detection = language::load_settings(app);
+
+            let result = if identify_language && detection.applies_to(language.as_deref()) {
+                whisper_engine
+                    .transcribe_multilingual(
+                        speech_samples,
+                        &detection.candidates,
+                        detection.store_translation,
+                        None,
+                        vocabulary,
+                    )
+                    .await
+                    .map(|r| (r.text, r.confidence, false, r.language))
+            } else {
+                whisper_engine
+                    .transcribe_audio_with_confidence(speech_samples, language, vocabulary)
+                    .await
+                    .map(|(text, confidence, is_partial)| {
+                        (text, confidence, is_partial, SegmentLanguage::default())
+                    })
+            };
 
-            match whisper_engine
-                .transcribe_audio_with_confidence(speech_samples, language)
-                .await
-            {
-                Ok((text, confidence, is_partial)) => {
+            match result {
+                Ok((text, confidence, is_partial, segment_language)) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), Some(confidence), is_partial));
+                        return Ok((String::new(), Some(confidence), is_partial, SegmentLanguage::default()));
                     }
 
                     info!(
-                        "Whisper transcription complete for chunk {}: '{}' (confidence: {:.2}, partial: {})",
-                        chunk.chunk_id, cleaned_text, confidence, is_partial
+                        "Whisper transcription complete for chunk {}: '{}' (confidence: {:.2}, partial: {}, language: {:?})",
+                        chunk.chunk_id, cleaned_text, confidence, is_partial, segment_language.language
                     );
 
-                    Ok((cleaned_text, Some(confidence), is_partial))
+                    Ok((cleaned_text, Some(confidence), is_partial, segment_language))
                 }
                 Err(e) => {
                     error!(
@@ -527,7 +716,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                 Ok(text) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), None, false));
+                        return Ok((String::new(), None, false, SegmentLanguage::default()));
                     }
 
                     info!(
@@ -535,8 +724,8 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         chunk.chunk_id, cleaned_text
                     );
 
-                    // Parakeet doesn't provide confidence or partial results
-                    Ok((cleaned_text, None, false))
+                    // Parakeet doesn't provide confidence, partial results or language ID
+                    Ok((cleaned_text, None, false, SegmentLanguage::default()))
                 }
                 Err(e) => {
                     error!(
@@ -562,11 +751,11 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
             // NEW: Trait-based provider (clean, unified interface)
             let language = crate::get_language_preference_internal();
 
-            match provider.transcribe(speech_samples, language).await {
+            match provider.transcribe(speech_samples, language, vocabulary).await {
                 Ok(result) => {
                     let cleaned_text = result.text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), result.confidence, result.is_partial));
+                        return Ok((String::new(), result.confidence, result.is_partial, SegmentLanguage::default()));
                     }
 
                     let confidence_str = match result.confidence {
@@ -583,7 +772,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         result.is_partial
                     );
 
-                    Ok((cleaned_text, result.confidence, result.is_partial))
+                    Ok((cleaned_text, result.confidence, result.is_partial, SegmentLanguage::default()))
                 }
                 Err(e) => {
                     error!(
@@ -109,6 +109,31 @@ impl ContinuousVadProcessor {
         Ok(completed_segments)
     }
 
+    /// Duration of the speech segment VAD is still accumulating (0 when not in speech)
+    pub fn in_progress_duration_ms(&self) -> f64 {
+        if self.in_speech {
+            (self.current_speech.len() as f64 / 16000.0) * 1000.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Snapshot of the speech segment VAD is still accumulating, for live interim decoding.
+    /// The segment is not consumed; the finished version is still returned by process_audio.
+    pub fn in_progress_segment(&self) -> Option<SpeechSegment> {
+        if !self.in_speech || self.current_speech.is_empty() {
+            return None;
+        }
+
+        // processed_samples and speech_start_sample always count 16kHz samples (post-resampling)
+        Some(SpeechSegment {
+            samples: self.current_speech.clone(),
+            start_timestamp_ms: (self.speech_start_sample as f64 / 16000.0) * 1000.0,
+            end_timestamp_ms: (self.processed_samples as f64 / 16000.0) * 1000.0,
+            confidence: 0.5, // Segment still open
+        })
+    }
+
     /// Resample from input sample rate to 16kHz using high-quality sinc resampler.
     /// VAD makes critical speech/silence decisions — poor resampling causes aliasing
     /// that introduces false positives (noise→speech) and false negatives (missed speech).
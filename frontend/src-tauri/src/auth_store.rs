@@ -1,13 +1,67 @@
-//! Secure JWT token storage using Tauri's store plugin.
+//! Secure JWT token storage.
 //!
-//! Tokens are persisted in `auth.json` in the app data directory,
-//! separate from analytics data. The frontend calls these commands
-//! to save/retrieve/clear tokens after login/logout.
+//! Access and refresh tokens are kept in the secret store (OS keyring or
+//! encrypted vault, see `crate::secrets`). The non-secret user ID stays in
+//! `auth.json` in the app data directory, separate from analytics data.
+//! The frontend calls these commands to save/retrieve/clear tokens after
+//! login/logout.
 
+use std::path::Path;
 use tauri::{AppHandle, Manager, Runtime};
 
 const STORE_FILE: &str = "auth.json";
 
+const ACCESS_TOKEN_SECRET: &str = "auth.access_token";
+const REFRESH_TOKEN_SECRET: &str = "auth.refresh_token";
+
+/// `auth.json` keys older versions stored the tokens under, with their secret names
+const LEGACY_TOKEN_KEYS: [(&str, &str); 2] = [
+    ("access_token", ACCESS_TOKEN_SECRET),
+    ("refresh_token", REFRESH_TOKEN_SECRET),
+];
+
+/// Moves tokens that older versions wrote to `auth.json` in plaintext into the secret store.
+pub fn migrate_plaintext_tokens(app_data_dir: &Path) {
+    let store_path = app_data_dir.join(STORE_FILE);
+    let Ok(contents) = std::fs::read_to_string(&store_path) else {
+        return;
+    };
+    let Ok(mut data) = serde_json::from_str::<serde_json::Value>(&contents) else {
+        return;
+    };
+    let Some(object) = data.as_object_mut() else {
+        return;
+    };
+
+    let mut moved = 0;
+    for (key, secret) in LEGACY_TOKEN_KEYS {
+        let Some(token) = object.get(key).and_then(|v| v.as_str()).map(|s| s.to_string()) else {
+            continue;
+        };
+        if let Err(e) = crate::secrets::set_secret(secret, &token) {
+            log::warn!("Failed to move {} into the secret store: {}", key, e);
+            return;
+        }
+        object.remove(key);
+        moved += 1;
+    }
+    if moved == 0 {
+        return;
+    }
+
+    match serde_json::to_string_pretty(&data) {
+        Ok(json_str) => {
+            let temp_path = store_path.with_extension("json.tmp");
+            let result = std::fs::write(&temp_path, &json_str).and_then(|_| std::fs::rename(&temp_path, &store_path));
+            match result {
+                Ok(()) => log::info!("Moved {} auth tokens into the secret store", moved),
+                Err(e) => log::warn!("Failed to rewrite {} without tokens: {}", STORE_FILE, e),
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize {}: {}", STORE_FILE, e),
+    }
+}
+
 /// Read a string value from the auth store.
 fn read_store_value<R: Runtime>(app: &AppHandle<R>, key: &str) -> Option<String> {
     let data_dir = app.path().app_data_dir().ok()?;
@@ -73,24 +127,26 @@ pub fn auth_save_tokens<R: Runtime>(
     access_token: String,
     refresh_token: String,
 ) -> Result<(), String> {
-    write_store_value(&app, "access_token", &access_token)?;
-    write_store_value(&app, "refresh_token", &refresh_token)?;
+    crate::secrets::set_secret(ACCESS_TOKEN_SECRET, &access_token)?;
+    crate::secrets::set_secret(REFRESH_TOKEN_SECRET, &refresh_token)?;
     log::info!("Auth tokens saved to secure store");
     Ok(())
 }
 
 #[tauri::command]
-pub fn auth_get_access_token<R: Runtime>(app: AppHandle<R>) -> Result<Option<String>, String> {
-    Ok(read_store_value(&app, "access_token"))
+pub fn auth_get_access_token<R: Runtime>(_app: AppHandle<R>) -> Result<Option<String>, String> {
+    crate::secrets::get_secret(ACCESS_TOKEN_SECRET)
 }
 
 #[tauri::command]
-pub fn auth_get_refresh_token<R: Runtime>(app: AppHandle<R>) -> Result<Option<String>, String> {
-    Ok(read_store_value(&app, "refresh_token"))
+pub fn auth_get_refresh_token<R: Runtime>(_app: AppHandle<R>) -> Result<Option<String>, String> {
+    crate::secrets::get_secret(REFRESH_TOKEN_SECRET)
 }
 
 #[tauri::command]
 pub fn auth_clear_tokens<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    crate::secrets::delete_secret(ACCESS_TOKEN_SECRET)?;
+    crate::secrets::delete_secret(REFRESH_TOKEN_SECRET)?;
     clear_store(&app)?;
     log::info!("Auth tokens cleared from secure store");
     Ok(())
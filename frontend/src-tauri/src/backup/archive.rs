@@ -0,0 +1,380 @@
+//! Reading and writing backup entries, in a backup directory or a `.zip` archive.
+
+use super::{BackupFileEntry, BackupManifest, MANIFEST_FILE};
+use crate::encryption::files as library_files;
+use crate::secrets::cipher::KEY_LEN;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Suffix of a file being written; renamed into place once complete
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// Whether `destination` names a single-file archive rather than a backup directory
+pub fn is_zip_path(destination: &Path) -> bool {
+    destination
+        .extension()
+        .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("zip"))
+}
+
+/// Relative filesystem path of an archive path, or None if it could leave the backup
+/// (absolute, `..`, empty)
+pub fn relative_path(archive_path: &str) -> Option<PathBuf> {
+    if archive_path.is_empty() || archive_path.contains('\\') {
+        return None;
+    }
+    let path = PathBuf::from(archive_path);
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+        .then_some(path)
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(PARTIAL_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Reader or writer that hashes and counts what passes through it
+struct Hashing<T> {
+    inner: T,
+    hasher: Sha256,
+    len: u64,
+}
+
+impl<T> Hashing<T> {
+    fn new(inner: T) -> Self {
+        Self { inner, hasher: Sha256::new(), len: 0 }
+    }
+
+    fn finish(self) -> (T, u64, String) {
+        (self.inner, self.len, hex::encode(self.hasher.finalize()))
+    }
+}
+
+impl<W: Write> Write for Hashing<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.len += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: Read> Read for Hashing<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        self.len += read as u64;
+        Ok(read)
+    }
+}
+
+/// Writes the plaintext of a library file, decrypting it when it is stored encrypted
+fn copy_plaintext(source: &Path, writer: &mut impl Write) -> Result<(), String> {
+    let input = File::open(source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    let mut reader = BufReader::new(input);
+    if library_files::is_encrypted_path(source) {
+        let key = crate::encryption::library_key().ok_or_else(|| "The encrypted library is locked".to_string())?;
+        library_files::decrypt_stream(&key, &mut reader, writer).map_err(|e| format!("{}: {}", source.display(), e))
+    } else {
+        std::io::copy(&mut reader, writer)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))
+    }
+}
+
+/// Copies a library file into `writer`, encrypted with `key` for a passphrase-protected
+/// backup. Returns the size and SHA-256 of what was written.
+fn copy_source<W: Write>(source: &Path, writer: W, key: Option<&[u8; KEY_LEN]>) -> Result<(W, u64, String), String> {
+    let mut hashing = Hashing::new(writer);
+    match key {
+        Some(key) => {
+            let mut encrypting = library_files::EncryptingWriter::new(key, &mut hashing)?;
+            copy_plaintext(source, &mut encrypting)?;
+            encrypting.finish()?;
+        }
+        None => copy_plaintext(source, &mut hashing)?,
+    }
+    Ok(hashing.finish())
+}
+
+/// Copies an entry into `writer`, decrypting it with `key` for a passphrase-protected
+/// backup, and checks its size and SHA-256 against the manifest
+fn copy_entry(
+    archive_path: &str,
+    reader: impl Read,
+    entry: &BackupFileEntry,
+    key: Option<&[u8; KEY_LEN]>,
+    writer: &mut impl Write,
+) -> Result<(), String> {
+    let mut hashing = Hashing::new(reader);
+    match key {
+        Some(key) => library_files::decrypt_stream(key, &mut hashing, writer)
+            .map_err(|e| format!("Backup file {} is damaged: {}", archive_path, e))?,
+        None => {
+            std::io::copy(&mut hashing, writer).map_err(|e| format!("Failed to extract {}: {}", archive_path, e))?;
+        }
+    }
+    let (_, len, hash) = hashing.finish();
+    if len != entry.size || hash != entry.sha256 {
+        return Err(format!("Backup file {} is damaged (checksum mismatch)", archive_path));
+    }
+    Ok(())
+}
+
+/// Where a backup is written
+pub enum BackupSink {
+    Directory(PathBuf),
+    /// Written to a `.partial` file that replaces the destination when the backup completes
+    Zip {
+        destination: PathBuf,
+        writer: zip::ZipWriter<BufWriter<File>>,
+    },
+}
+
+impl BackupSink {
+    pub fn open(destination: &Path) -> Result<Self, String> {
+        if is_zip_path(destination) {
+            if let Some(parent) = destination.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let partial = partial_path(destination);
+            let file = File::create(&partial).map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+            Ok(BackupSink::Zip {
+                destination: destination.to_path_buf(),
+                writer: zip::ZipWriter::new(BufWriter::new(file)),
+            })
+        } else {
+            std::fs::create_dir_all(destination)
+                .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+            Ok(BackupSink::Directory(destination.to_path_buf()))
+        }
+    }
+
+    /// Manifest of the previous backup in this directory, for an incremental backup
+    pub fn previous_manifest(&self) -> Option<BackupManifest> {
+        let BackupSink::Directory(dir) = self else {
+            return None;
+        };
+        let content = std::fs::read_to_string(dir.join(MANIFEST_FILE)).ok()?;
+        match serde_json::from_str::<BackupManifest>(&content) {
+            Ok(manifest) if manifest.format_version <= super::BACKUP_FORMAT_VERSION => Some(manifest),
+            Ok(_) => None,
+            Err(e) => {
+                log::warn!("Ignoring unreadable manifest in {}: {}", dir.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Whether the copy of a previous backup's entry is still in place
+    pub fn has_copy(&self, archive_path: &str, entry: &BackupFileEntry) -> bool {
+        match (self, relative_path(archive_path)) {
+            (BackupSink::Directory(dir), Some(relative)) => {
+                std::fs::metadata(dir.join(relative)).is_ok_and(|m| m.is_file() && m.len() == entry.size)
+            }
+            _ => false,
+        }
+    }
+
+    /// Copies `source` to `archive_path`, encrypted when `key` is set; returns the size and
+    /// SHA-256 of the stored copy
+    pub fn add_file(
+        &mut self,
+        archive_path: &str,
+        source: &Path,
+        compress: bool,
+        key: Option<&[u8; KEY_LEN]>,
+    ) -> Result<(u64, String), String> {
+        let relative = relative_path(archive_path).ok_or_else(|| format!("Invalid backup path: {}", archive_path))?;
+        match self {
+            BackupSink::Directory(dir) => {
+                let target = dir.join(relative);
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                }
+                let partial = partial_path(&target);
+                let file = File::create(&partial).map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+                let result = copy_source(source, BufWriter::new(file), key).and_then(|(mut writer, len, hash)| {
+                    writer.flush().map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+                    Ok((len, hash))
+                });
+                match result {
+                    Ok(copied) => {
+                        std::fs::rename(&partial, &target)
+                            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+                        Ok(copied)
+                    }
+                    Err(e) => {
+                        let _ = std::fs::remove_file(&partial);
+                        Err(e)
+                    }
+                }
+            }
+            BackupSink::Zip { writer, .. } => {
+                let method = if compress {
+                    zip::CompressionMethod::Deflated
+                } else {
+                    zip::CompressionMethod::Stored
+                };
+                let options = zip::write::SimpleFileOptions::default()
+                    .compression_method(method)
+                    .large_file(true);
+                writer
+                    .start_file(archive_path, options)
+                    .map_err(|e| format!("Failed to add {}: {}", archive_path, e))?;
+                let (_, len, hash) = copy_source(source, &mut *writer, key)?;
+                Ok((len, hash))
+            }
+        }
+    }
+
+    /// Removes files of the previous backup that are no longer part of the library
+    pub fn remove_stale(&self, archive_path: &str) -> bool {
+        let (BackupSink::Directory(dir), Some(relative)) = (self, relative_path(archive_path)) else {
+            return false;
+        };
+        let target = dir.join(relative);
+        if std::fs::remove_file(&target).is_err() {
+            return false;
+        }
+        // Drop directories left empty, up to the backup directory
+        let mut parent = target.parent();
+        while let Some(current) = parent.filter(|p| *p != dir.as_path()) {
+            if std::fs::remove_dir(current).is_err() {
+                break;
+            }
+            parent = current.parent();
+        }
+        true
+    }
+
+    /// Writes the manifest (last, so an interrupted backup keeps the previous manifest) and
+    /// completes the backup
+    pub fn finish(self, manifest: &BackupManifest) -> Result<(), String> {
+        let json = serde_json::to_vec_pretty(manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        match self {
+            BackupSink::Directory(dir) => {
+                let path = dir.join(MANIFEST_FILE);
+                let partial = partial_path(&path);
+                std::fs::write(&partial, &json).map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+                std::fs::rename(&partial, &path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+            }
+            BackupSink::Zip { destination, mut writer } => {
+                writer
+                    .start_file(MANIFEST_FILE, zip::write::SimpleFileOptions::default())
+                    .and_then(|_| writer.write_all(&json).map_err(Into::into))
+                    .map_err(|e| format!("Failed to write manifest: {}", e))?;
+                let mut file = writer.finish().map_err(|e| format!("Failed to finish archive: {}", e))?;
+                file.flush().map_err(|e| format!("Failed to write archive: {}", e))?;
+                drop(file);
+                std::fs::rename(partial_path(&destination), &destination)
+                    .map_err(|e| format!("Failed to write {}: {}", destination.display(), e))
+            }
+        }
+    }
+
+    /// Drops an unfinished archive; an unfinished directory backup keeps its previous manifest
+    pub fn abandon(self) {
+        if let BackupSink::Zip { destination, writer } = self {
+            drop(writer);
+            let _ = std::fs::remove_file(partial_path(&destination));
+        }
+    }
+}
+
+/// A backup being restored
+pub enum BackupSource {
+    Directory(PathBuf),
+    Zip(zip::ZipArchive<BufReader<File>>),
+}
+
+impl BackupSource {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if path.is_dir() {
+            return Ok(BackupSource::Directory(path.to_path_buf()));
+        }
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        zip::ZipArchive::new(BufReader::new(file))
+            .map(BackupSource::Zip)
+            .map_err(|e| format!("{} is not a backup archive: {}", path.display(), e))
+    }
+
+    fn reader(&mut self, archive_path: &str) -> Result<Box<dyn Read + '_>, String> {
+        match self {
+            BackupSource::Directory(dir) => {
+                let relative =
+                    relative_path(archive_path).ok_or_else(|| format!("Invalid backup path: {}", archive_path))?;
+                let file = File::open(dir.join(relative))
+                    .map_err(|e| format!("Backup file {} is missing: {}", archive_path, e))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            BackupSource::Zip(archive) => archive
+                .by_name(archive_path)
+                .map(|file| Box::new(file) as Box<dyn Read + '_>)
+                .map_err(|e| format!("Backup file {} is missing: {}", archive_path, e)),
+        }
+    }
+
+    pub fn read_manifest(&mut self) -> Result<BackupManifest, String> {
+        let mut content = String::new();
+        self.reader(MANIFEST_FILE)
+            .map_err(|_| "Not a backup: manifest.json is missing".to_string())?
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read manifest: {}", e))?;
+        let manifest: BackupManifest =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid backup manifest: {}", e))?;
+        if manifest.format_version > super::BACKUP_FORMAT_VERSION {
+            return Err(format!(
+                "This backup was made by a newer version of the app (format {}); update the app to restore it",
+                manifest.format_version
+            ));
+        }
+        Ok(manifest)
+    }
+
+    /// Extracts an entry to `target` (decrypted with `key` for a passphrase-protected backup),
+    /// checking its size and SHA-256 against the manifest
+    pub fn extract(
+        &mut self,
+        archive_path: &str,
+        entry: &BackupFileEntry,
+        target: &Path,
+        key: Option<&[u8; KEY_LEN]>,
+    ) -> Result<(), String> {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let partial = partial_path(target);
+        let result = (|| {
+            let file = File::create(&partial).map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+            let mut writer = BufWriter::new(file);
+            copy_entry(archive_path, self.reader(archive_path)?, entry, key, &mut writer)?;
+            writer.flush().map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+            std::fs::rename(&partial, target).map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&partial);
+        }
+        result
+    }
+
+    /// Reads a small entry (settings) into memory, checking its SHA-256
+    pub fn read_bytes(
+        &mut self,
+        archive_path: &str,
+        entry: &BackupFileEntry,
+        key: Option<&[u8; KEY_LEN]>,
+    ) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        copy_entry(archive_path, self.reader(archive_path)?, entry, key, &mut bytes)?;
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,51 @@
+use super::{BackupResult, BackupSummary, RestoreResult};
+use crate::state::AppState;
+use log::error;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+
+/// Backs up the library to `destination`: a `.zip` archive, or a directory that later
+/// backups to the same path update incrementally. `passphrase` encrypts the backup; an
+/// encrypted library is only backed up without one when `allow_unencrypted` is set.
+#[tauri::command]
+pub async fn api_create_backup<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    destination: String,
+    include_audio: bool,
+    passphrase: Option<String>,
+    allow_unencrypted: Option<bool>,
+) -> Result<BackupResult, String> {
+    super::create_backup(
+        &app,
+        state.db_manager.pool(),
+        PathBuf::from(destination),
+        include_audio,
+        passphrase,
+        allow_unencrypted.unwrap_or(false),
+    )
+    .await
+    .inspect_err(|e| error!("Backup failed: {}", e))
+}
+
+/// What a backup directory or archive contains
+#[tauri::command]
+pub async fn api_inspect_backup(path: String) -> Result<BackupSummary, String> {
+    tokio::task::spawn_blocking(move || super::inspect_backup(&PathBuf::from(path)))
+        .await
+        .map_err(|e| format!("Backup inspection task join error: {}", e))?
+}
+
+/// Restores a backup on a fresh install (offered next to the legacy database import) and
+/// opens the restored library; a passphrase-protected backup needs its `passphrase`
+#[tauri::command]
+pub async fn api_restore_backup(
+    app: AppHandle,
+    source: String,
+    recordings_folder: Option<String>,
+    passphrase: Option<String>,
+) -> Result<RestoreResult, String> {
+    super::restore_backup(&app, PathBuf::from(source), recordings_folder.map(PathBuf::from), passphrase)
+        .await
+        .inspect_err(|e| error!("Restore failed: {}", e))
+}
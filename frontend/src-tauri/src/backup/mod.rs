@@ -0,0 +1,965 @@
+//! Library backup and restore.
+//!
+//! A backup holds the meeting database (with meetings, transcripts, summaries and notes), the
+//! meeting folders (optionally without audio), the user's custom and brand templates and the
+//! app settings. Secrets are left out: API keys and auth tokens stay in the secret store of
+//! the machine, and license activations and credential-like settings are removed.
+//!
+//! With a backup passphrase every file is encrypted (the `files` layout of
+//! `crate::encryption`) under a key derived from it; the manifest records the salt and a key
+//! check value and stays readable, so file and meeting folder names are not hidden. An
+//! encrypted library is only backed up without a passphrase when the user explicitly allows
+//! an unencrypted backup.
+//!
+//! The same layout is used in a backup directory and in a single `.zip` archive:
+//!
+//! ```text
+//! manifest.json            format version, meeting folders, size and SHA-256 of every file
+//! database.sqlite
+//! meetings/<folder>/...
+//! templates/...
+//! brand_templates/...
+//! settings/<store>.json
+//! ```
+//!
+//! Backups into a directory are incremental: files whose source is unchanged since the last
+//! backup into that directory are kept, files no longer in the library are removed. A restore
+//! (on a fresh install, before a library exists) puts the meeting folders under the new
+//! recordings folder and points the meetings at them.
+
+mod archive;
+pub mod commands;
+
+use crate::audio::recording_preferences::get_default_recordings_folder;
+use crate::database::manager::DatabaseManager;
+use crate::database::models::MeetingRetentionInfo;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::encryption::files as library_files;
+use crate::retention::storage::is_audio_file;
+use crate::secrets::cipher::{self, KEY_LEN};
+use crate::state::AppState;
+use crate::utils::unique_path;
+use archive::{BackupSink, BackupSource};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+/// Version of the backup layout; restores refuse backups from a newer format. Format 2 added
+/// passphrase-protected backups.
+pub const BACKUP_FORMAT_VERSION: u32 = 2;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const DATABASE_FILE: &str = "database.sqlite";
+const MEETINGS_DIR: &str = "meetings";
+const TEMPLATES_DIR: &str = "templates";
+const BRAND_TEMPLATES_DIR: &str = "brand_templates";
+const SETTINGS_DIR: &str = "settings";
+
+/// Settings stores carried over. Sign-in (`auth.json`), onboarding and model download state,
+/// analytics identity and the library encryption settings belong to the machine.
+const SETTINGS_STORES: &[&str] = &[
+    "store.json",
+    "recording_preferences.json",
+    "retention.json",
+    "redaction.json",
+];
+
+/// Settings keys containing one of these (case-insensitive) are never backed up
+const SECRET_KEY_MARKERS: &[&str] = &["token", "apikey", "api_key", "secret", "password"];
+
+/// Sealed with the backup key to tell a wrong passphrase from the right one
+const KEY_CHECK_PLAINTEXT: &[u8] = b"iqcapture-backup-key";
+
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Only one backup or restore at a time
+static BACKUP_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// A file in the backup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupFileEntry {
+    /// Size of the stored copy in bytes (encrypted in a passphrase-protected backup)
+    pub size: u64,
+    pub sha256: String,
+    /// Size and modification time (ms since the epoch) of the source when it was copied; an
+    /// incremental backup keeps the copy while both are unchanged
+    #[serde(default)]
+    pub source_size: u64,
+    #[serde(default)]
+    pub source_modified: i64,
+}
+
+/// A meeting folder in the backup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupMeetingFolder {
+    /// Folder name under `meetings/`
+    pub name: String,
+    /// Folder path on the machine the backup was made on
+    pub original_path: String,
+    /// Meetings stored in this folder (more than one for redacted copies or split meetings
+    /// sharing a folder)
+    pub meeting_ids: Vec<String>,
+}
+
+/// Key derivation parameters of a passphrase-protected backup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupEncryption {
+    /// Argon2 salt, hex
+    pub salt: String,
+    /// `KEY_CHECK_PLAINTEXT` sealed with the backup key, hex
+    pub key_check: String,
+}
+
+impl BackupEncryption {
+    fn new(passphrase: &str) -> Result<([u8; KEY_LEN], Self), String> {
+        if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+            return Err(format!("The backup passphrase needs at least {} characters", MIN_PASSPHRASE_LEN));
+        }
+        let salt = cipher::random_salt();
+        let key = cipher::derive_key(passphrase.as_bytes(), &salt)?;
+        let encryption = BackupEncryption {
+            salt: hex::encode(salt),
+            key_check: hex::encode(cipher::seal(&key, KEY_CHECK_PLAINTEXT, b"")?),
+        };
+        Ok((key, encryption))
+    }
+
+    /// The backup key, or an error for a wrong passphrase
+    fn key(&self, passphrase: &str) -> Result<[u8; KEY_LEN], String> {
+        let salt = hex::decode(&self.salt).map_err(|_| "The backup salt is malformed".to_string())?;
+        let sealed = hex::decode(&self.key_check).map_err(|_| "The backup key check is malformed".to_string())?;
+        let key = cipher::derive_key(passphrase.as_bytes(), &salt)?;
+        match cipher::open(&key, &sealed, b"") {
+            Ok(plaintext) if plaintext == KEY_CHECK_PLAINTEXT => Ok(key),
+            _ => Err("Wrong backup passphrase".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: String,
+    pub includes_audio: bool,
+    /// Set for a passphrase-protected backup
+    #[serde(default)]
+    pub encryption: Option<BackupEncryption>,
+    pub meeting_folders: Vec<BackupMeetingFolder>,
+    /// Every file in the backup except the manifest, by archive path
+    pub files: BTreeMap<String, BackupFileEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupResult {
+    pub destination: String,
+    pub format_version: u32,
+    pub meeting_folders: usize,
+    pub file_count: usize,
+    pub total_bytes: u64,
+    /// Files written by this backup
+    pub copied_files: usize,
+    /// Files kept from the previous backup in the directory
+    pub unchanged_files: usize,
+    /// Files of the previous backup removed because they left the library
+    pub removed_files: usize,
+}
+
+/// What a backup contains, shown before restoring it
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupSummary {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: String,
+    pub includes_audio: bool,
+    /// Restoring needs the backup passphrase
+    pub encrypted: bool,
+    pub meeting_folders: usize,
+    pub template_count: usize,
+    pub brand_template_count: usize,
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreResult {
+    pub recordings_folder: String,
+    pub meeting_folders: usize,
+    /// Meetings pointed at their restored folder
+    pub remapped_meetings: usize,
+    /// Folders restored under another name because the original name was taken
+    pub renamed_folders: Vec<String>,
+    pub includes_audio: bool,
+}
+
+struct BackupGuard;
+
+impl BackupGuard {
+    fn acquire() -> Result<Self, String> {
+        if BACKUP_IN_PROGRESS
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err("A backup or restore is already running".to_string());
+        }
+        Ok(BackupGuard)
+    }
+}
+
+impl Drop for BackupGuard {
+    fn drop(&mut self) {
+        BACKUP_IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+/// A file to copy into the backup
+struct SourceFile {
+    archive_path: String,
+    path: PathBuf,
+}
+
+fn modified_ms(metadata: &std::fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Names for the meeting folders in the backup: one entry per folder (meetings sharing a folder
+/// share the entry), named after the folder and made unique with a `_2`, `_3`... suffix
+fn plan_meeting_folders(meetings: &[(String, PathBuf)]) -> Vec<(BackupMeetingFolder, PathBuf)> {
+    let mut folders: Vec<(BackupMeetingFolder, PathBuf)> = Vec::new();
+    let mut by_path: HashMap<PathBuf, usize> = HashMap::new();
+    let mut used_names: HashMap<String, usize> = HashMap::new();
+
+    for (meeting_id, folder) in meetings {
+        if let Some(&index) = by_path.get(folder) {
+            folders[index].0.meeting_ids.push(meeting_id.clone());
+            continue;
+        }
+        let base = folder
+            .file_name()
+            .map(|n| n.to_string_lossy().replace(['/', '\\'], "_"))
+            .filter(|n| !n.is_empty() && n != "." && n != "..")
+            .unwrap_or_else(|| "meeting".to_string());
+        let count = used_names.entry(base.to_lowercase()).or_insert(0);
+        *count += 1;
+        let name = if *count == 1 { base } else { format!("{}_{}", base, count) };
+
+        by_path.insert(folder.clone(), folders.len());
+        folders.push((
+            BackupMeetingFolder {
+                name,
+                original_path: folder.to_string_lossy().to_string(),
+                meeting_ids: vec![meeting_id.clone()],
+            },
+            folder.clone(),
+        ));
+    }
+    folders
+}
+
+/// Files under `dir` as archive paths below `prefix`. Hidden files and directories (recording
+/// checkpoints, temporary files) are skipped, and audio when `include_audio` is false.
+/// Encrypted files are listed under their plaintext name.
+fn collect_files(dir: &Path, prefix: &str, include_audio: bool, out: &mut Vec<SourceFile>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if name.is_empty() || name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, &format!("{}/{}", prefix, name), include_audio, out);
+        } else if path.is_file() {
+            if !include_audio && is_audio_file(&path) {
+                continue;
+            }
+            let plain_name = library_files::plaintext_path(Path::new(&name)).to_string_lossy().to_string();
+            out.push(SourceFile {
+                archive_path: format!("{}/{}", prefix, plain_name),
+                path,
+            });
+        }
+    }
+}
+
+/// Removes credential-like keys from a settings value, at any depth
+fn strip_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|key, _| {
+                let key = key.to_lowercase();
+                !SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+            });
+            map.values_mut().for_each(strip_secrets);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_secrets),
+        _ => {}
+    }
+}
+
+fn sql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Writes a plaintext copy of the meeting database to `target` (in a private directory for an
+/// encrypted library) and removes what must not leave the machine
+async fn snapshot_database(pool: &SqlitePool, target: &Path) -> Result<(), String> {
+    let target_sql = sql_string(&target.to_string_lossy());
+    let mut conn = pool.acquire().await.map_err(|e| format!("Database unavailable: {}", e))?;
+    if crate::encryption::library_key().is_some() {
+        // An encrypted (SQLCipher) database is exported into a plaintext one
+        sqlx::query(&format!("ATTACH DATABASE {} AS backup KEY ''", target_sql))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to create database copy: {}", e))?;
+        let exported = sqlx::query("SELECT sqlcipher_export('backup')").execute(&mut *conn).await;
+        let _ = sqlx::query("DETACH DATABASE backup").execute(&mut *conn).await;
+        exported.map_err(|e| format!("Failed to copy database: {}", e))?;
+    } else {
+        sqlx::query(&format!("VACUUM INTO {}", target_sql))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to copy database: {}", e))?;
+    }
+    drop(conn);
+
+    let options = SqliteConnectOptions::from_str(&target.to_string_lossy())
+        .map_err(|e| format!("Failed to open database copy: {}", e))?;
+    let copy = SqlitePool::connect_with(options)
+        .await
+        .map_err(|e| format!("Failed to open database copy: {}", e))?;
+    let scrubbed = async {
+        SettingsRepository::clear_plaintext_api_keys(&copy).await?;
+        // License activations are bound to this machine
+        sqlx::query("DELETE FROM licensing").execute(&copy).await?;
+        sqlx::query("VACUUM").execute(&copy).await?;
+        Ok::<(), sqlx::Error>(())
+    }
+    .await;
+    copy.close().await;
+    scrubbed.map_err(|e| format!("Failed to remove credentials from the database copy: {}", e))
+}
+
+/// Settings stores with credential-like keys removed, as (archive path, JSON)
+fn settings_snapshot<R: Runtime>(app: &AppHandle<R>) -> Vec<(String, Vec<u8>)> {
+    SETTINGS_STORES
+        .iter()
+        .filter_map(|name| {
+            let store = app.store(*name).ok()?;
+            let mut value = serde_json::Value::Object(store.entries().into_iter().collect());
+            strip_secrets(&mut value);
+            let bytes = serde_json::to_vec_pretty(&value).ok()?;
+            Some((format!("{}/{}", SETTINGS_DIR, name), bytes))
+        })
+        .collect()
+}
+
+/// Creates a backup at `destination`: a `.zip` archive, or otherwise a directory that is
+/// updated incrementally. The backup is encrypted with `passphrase` when one is given; an
+/// encrypted library needs a passphrase unless `allow_unencrypted` is set.
+pub async fn create_backup<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    destination: PathBuf,
+    include_audio: bool,
+    passphrase: Option<String>,
+    allow_unencrypted: bool,
+) -> Result<BackupResult, String> {
+    let _guard = BackupGuard::acquire()?;
+    if crate::encryption::is_locked() {
+        return Err("Unlock the library before backing it up".to_string());
+    }
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    if passphrase.is_none() && crate::encryption::is_enabled() && !allow_unencrypted {
+        return Err(
+            "The library is encrypted. Set a backup passphrase, or confirm that the backup may be stored unencrypted"
+                .to_string(),
+        );
+    }
+
+    let meetings: Vec<MeetingRetentionInfo> = MeetingsRepository::get_retention_info(pool)
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?;
+
+    // The database copy is plaintext until it is written into the backup
+    let work_dir = if crate::encryption::is_enabled() {
+        library_files::plaintext_work_dir()?
+    } else {
+        tempfile::tempdir().map_err(|e| format!("Failed to create temporary directory: {}", e))?
+    };
+    let database_copy = work_dir.path().join(DATABASE_FILE);
+    snapshot_database(pool, &database_copy).await?;
+
+    let settings_dir = work_dir.path().join(SETTINGS_DIR);
+    std::fs::create_dir_all(&settings_dir).map_err(|e| format!("Failed to create temporary directory: {}", e))?;
+    let mut sources = vec![SourceFile {
+        archive_path: DATABASE_FILE.to_string(),
+        path: database_copy,
+    }];
+    for (archive_path, bytes) in settings_snapshot(app) {
+        let path = work_dir.path().join(&archive_path);
+        std::fs::write(&path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        sources.push(SourceFile { archive_path, path });
+    }
+
+    let app_version = app.package_info().version.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        let _work_dir = work_dir;
+        let with_folders: Vec<(String, PathBuf)> = meetings
+            .iter()
+            .filter_map(|m| {
+                let folder = PathBuf::from(m.folder_path.as_deref()?);
+                folder.is_dir().then(|| (m.id.clone(), folder))
+            })
+            .collect();
+        let folders = plan_meeting_folders(&with_folders);
+        for (folder, path) in &folders {
+            collect_files(path, &format!("{}/{}", MEETINGS_DIR, folder.name), include_audio, &mut sources);
+        }
+        if let Some(dir) = crate::summary::templates::get_custom_templates_dir() {
+            collect_files(&dir, TEMPLATES_DIR, true, &mut sources);
+        }
+        if let Some(dir) = crate::summary::brand_templates::get_user_dir() {
+            collect_files(&dir, BRAND_TEMPLATES_DIR, true, &mut sources);
+        }
+
+        write_backup(
+            &destination,
+            sources,
+            folders.into_iter().map(|(f, _)| f).collect(),
+            include_audio,
+            app_version,
+            passphrase.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Backup task join error: {}", e))??;
+
+    info!(
+        "Backup written to {}: {} files ({} copied, {} unchanged, {} removed)",
+        result.destination, result.file_count, result.copied_files, result.unchanged_files, result.removed_files
+    );
+    Ok(result)
+}
+
+/// Key and manifest record for a backup with `passphrase` over `previous`. A directory backup
+/// that is already protected keeps its salt (so unchanged files stay valid) and must be updated
+/// with the same passphrase.
+fn backup_key(
+    previous: Option<&BackupManifest>,
+    passphrase: Option<&str>,
+) -> Result<Option<([u8; KEY_LEN], BackupEncryption)>, String> {
+    let Some(passphrase) = passphrase else {
+        return Ok(None);
+    };
+    match previous.and_then(|p| p.encryption.as_ref()) {
+        Some(encryption) => {
+            let key = encryption.key(passphrase).map_err(|_| {
+                "The passphrase does not match the backup already in this directory".to_string()
+            })?;
+            Ok(Some((key, encryption.clone())))
+        }
+        None => BackupEncryption::new(passphrase).map(Some),
+    }
+}
+
+fn write_backup(
+    destination: &Path,
+    sources: Vec<SourceFile>,
+    meeting_folders: Vec<BackupMeetingFolder>,
+    includes_audio: bool,
+    app_version: String,
+    passphrase: Option<&str>,
+) -> Result<BackupResult, String> {
+    let mut sink = BackupSink::open(destination)?;
+    let previous = sink.previous_manifest();
+    let protection = backup_key(previous.as_ref(), passphrase)?;
+    let (key, encryption) = match protection {
+        Some((key, encryption)) => (Some(key), Some(encryption)),
+        None => (None, None),
+    };
+    // Copies stored with another key (or none) are rewritten
+    let reusable = previous.as_ref().filter(|p| p.encryption == encryption);
+    let mut manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        includes_audio,
+        encryption,
+        meeting_folders,
+        files: BTreeMap::new(),
+    };
+    let (mut copied_files, mut unchanged_files) = (0, 0);
+
+    for source in sources {
+        let metadata = match std::fs::metadata(&source.path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                // Deleted since the folder was listed (e.g. by a retention run)
+                warn!("Skipping {} in backup: {}", source.path.display(), e);
+                continue;
+            }
+        };
+        let (source_size, source_modified) = (metadata.len(), modified_ms(&metadata));
+
+        let unchanged = reusable
+            .and_then(|p| p.files.get(&source.archive_path))
+            .filter(|e| e.source_size == source_size && e.source_modified == source_modified)
+            .filter(|e| sink.has_copy(&source.archive_path, e))
+            .cloned();
+        if let Some(entry) = unchanged {
+            manifest.files.insert(source.archive_path, entry);
+            unchanged_files += 1;
+            continue;
+        }
+
+        let compress = !is_audio_file(&source.path);
+        match sink.add_file(&source.archive_path, &source.path, compress, key.as_ref()) {
+            Ok((size, sha256)) => {
+                manifest.files.insert(
+                    source.archive_path,
+                    BackupFileEntry { size, sha256, source_size, source_modified },
+                );
+                copied_files += 1;
+            }
+            Err(e) => {
+                sink.abandon();
+                return Err(e);
+            }
+        }
+    }
+
+    let mut removed_files = 0;
+    if let Some(previous) = &previous {
+        for archive_path in previous.files.keys().filter(|path| !manifest.files.contains_key(*path)) {
+            if sink.remove_stale(archive_path) {
+                removed_files += 1;
+            }
+        }
+    }
+
+    let result = BackupResult {
+        destination: destination.to_string_lossy().to_string(),
+        format_version: BACKUP_FORMAT_VERSION,
+        meeting_folders: manifest.meeting_folders.len(),
+        file_count: manifest.files.len(),
+        total_bytes: manifest.files.values().map(|e| e.size).sum(),
+        copied_files,
+        unchanged_files,
+        removed_files,
+    };
+    sink.finish(&manifest)?;
+    Ok(result)
+}
+
+/// Reads the manifest of a backup directory or archive
+pub fn inspect_backup(path: &Path) -> Result<BackupSummary, String> {
+    let manifest = BackupSource::open(path)?.read_manifest()?;
+    let count_under = |prefix: &str| manifest.files.keys().filter(|p| p.starts_with(prefix)).count();
+    Ok(BackupSummary {
+        format_version: manifest.format_version,
+        app_version: manifest.app_version.clone(),
+        created_at: manifest.created_at.clone(),
+        includes_audio: manifest.includes_audio,
+        encrypted: manifest.encryption.is_some(),
+        meeting_folders: manifest.meeting_folders.len(),
+        template_count: count_under(&format!("{}/", TEMPLATES_DIR)),
+        brand_template_count: count_under(&format!("{}/", BRAND_TEMPLATES_DIR)),
+        file_count: manifest.files.len(),
+        total_bytes: manifest.files.values().map(|e| e.size).sum(),
+    })
+}
+
+/// Where each backup file goes on this machine, with the new folder of each meeting folder
+struct RestorePlan {
+    targets: Vec<(String, PathBuf)>,
+    settings: Vec<(String, String)>,
+    folders: Vec<(BackupMeetingFolder, PathBuf)>,
+}
+
+fn plan_restore(
+    manifest: &BackupManifest,
+    recordings_root: &Path,
+    templates_dir: Option<&Path>,
+    brand_templates_dir: Option<&Path>,
+) -> Result<RestorePlan, String> {
+    let folders: Vec<(BackupMeetingFolder, PathBuf)> = manifest
+        .meeting_folders
+        .iter()
+        .map(|folder| {
+            archive::relative_path(&folder.name)
+                .filter(|p| p.components().count() == 1)
+                .ok_or_else(|| format!("Invalid meeting folder name in backup: {}", folder.name))?;
+            Ok((folder.clone(), unique_path(recordings_root, &folder.name)))
+        })
+        .collect::<Result<_, String>>()?;
+    let folder_targets: HashMap<&str, &Path> =
+        folders.iter().map(|(folder, target)| (folder.name.as_str(), target.as_path())).collect();
+
+    let mut plan = RestorePlan { targets: Vec::new(), settings: Vec::new(), folders: Vec::new() };
+    for archive_path in manifest.files.keys() {
+        let relative =
+            archive::relative_path(archive_path).ok_or_else(|| format!("Invalid path in backup: {}", archive_path))?;
+        let mut components = relative.iter();
+        let top = components.next().map(|c| c.to_string_lossy().to_string()).unwrap_or_default();
+        let rest: PathBuf = components.collect();
+        let target = match top.as_str() {
+            MEETINGS_DIR => {
+                let mut rest_components = rest.iter();
+                let folder = rest_components.next().map(|c| c.to_string_lossy().to_string()).unwrap_or_default();
+                let file: PathBuf = rest_components.collect();
+                folder_targets
+                    .get(folder.as_str())
+                    .filter(|_| !file.as_os_str().is_empty())
+                    .map(|root| root.join(file))
+            }
+            TEMPLATES_DIR => templates_dir.map(|dir| dir.join(&rest)),
+            BRAND_TEMPLATES_DIR => brand_templates_dir.map(|dir| dir.join(&rest)),
+            SETTINGS_DIR => {
+                let name = rest.to_string_lossy().to_string();
+                if SETTINGS_STORES.contains(&name.as_str()) {
+                    plan.settings.push((archive_path.clone(), name));
+                }
+                None
+            }
+            _ => None,
+        };
+        match target {
+            Some(target) => plan.targets.push((archive_path.clone(), target)),
+            None if top != SETTINGS_DIR && archive_path != DATABASE_FILE => {
+                warn!("Not restoring {} from backup", archive_path)
+            }
+            None => {}
+        }
+    }
+    plan.folders = folders;
+    Ok(plan)
+}
+
+/// Writes restored settings into their stores, pointing the recordings folder at `recordings_root`
+fn apply_settings<R: Runtime>(app: &AppHandle<R>, store_name: &str, bytes: &[u8], recordings_root: &Path) {
+    let mut value: serde_json::Value = match serde_json::from_slice(bytes) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Skipping settings {} from backup: {}", store_name, e);
+            return;
+        }
+    };
+    if store_name == "recording_preferences.json" {
+        if let Some(preferences) = value.get_mut("preferences").and_then(|p| p.as_object_mut()) {
+            preferences.insert("save_folder".to_string(), serde_json::json!(recordings_root));
+        }
+    }
+    let serde_json::Value::Object(entries) = value else {
+        return;
+    };
+    match app.store(store_name) {
+        Ok(store) => {
+            for (key, value) in entries {
+                store.set(key, value);
+            }
+            if let Err(e) = store.save() {
+                warn!("Failed to save restored settings {}: {}", store_name, e);
+            }
+        }
+        Err(e) => warn!("Failed to open settings store {}: {}", store_name, e),
+    }
+}
+
+/// Restores a backup into a fresh install: meeting folders under `recordings_folder` (default
+/// recordings folder if None), templates, settings and the database, whose meetings are then
+/// pointed at their restored folders. A passphrase-protected backup needs `passphrase`.
+pub async fn restore_backup(
+    app: &AppHandle,
+    source: PathBuf,
+    recordings_folder: Option<PathBuf>,
+    passphrase: Option<String>,
+) -> Result<RestoreResult, String> {
+    let _guard = BackupGuard::acquire()?;
+    if app.try_state::<AppState>().is_some() {
+        return Err(
+            "A library is already open. Restore a backup on a fresh install, before creating or importing a database"
+                .to_string(),
+        );
+    }
+    if crate::encryption::is_locked() {
+        return Err("Unlock the library before restoring a backup".to_string());
+    }
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let db_path = app_data_dir.join("meeting_minutes.sqlite");
+    if db_path.exists() {
+        return Err("A database already exists; restore is only available on a fresh install".to_string());
+    }
+    let recordings_root = recordings_folder.unwrap_or_else(get_default_recordings_folder);
+
+    let staged_db = app_data_dir.join("meeting_minutes.sqlite.restoring");
+    let (manifest, plan, settings) = {
+        let recordings_root = recordings_root.clone();
+        let staged_db = staged_db.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut backup = BackupSource::open(&source)?;
+            let manifest = backup.read_manifest()?;
+            let key = match (&manifest.encryption, passphrase.as_deref().filter(|p| !p.is_empty())) {
+                (Some(encryption), Some(passphrase)) => Some(encryption.key(passphrase)?),
+                (Some(_), None) => return Err("This backup is protected; enter its passphrase to restore it".to_string()),
+                (None, _) => None,
+            };
+            let database = manifest
+                .files
+                .get(DATABASE_FILE)
+                .ok_or_else(|| "The backup has no database".to_string())?;
+            std::fs::create_dir_all(&recordings_root)
+                .map_err(|e| format!("Failed to create {}: {}", recordings_root.display(), e))?;
+            let plan = plan_restore(
+                &manifest,
+                &recordings_root,
+                crate::summary::templates::get_custom_templates_dir().as_deref(),
+                crate::summary::brand_templates::get_user_dir().as_deref(),
+            )?;
+
+            backup.extract(DATABASE_FILE, database, &staged_db, key.as_ref())?;
+            for (archive_path, target) in &plan.targets {
+                backup.extract(archive_path, &manifest.files[archive_path], target, key.as_ref())?;
+            }
+            let settings = plan
+                .settings
+                .iter()
+                .filter_map(|(archive_path, name)| {
+                    match backup.read_bytes(archive_path, &manifest.files[archive_path], key.as_ref()) {
+                        Ok(bytes) => Some((name.clone(), bytes)),
+                        Err(e) => {
+                            warn!("Skipping settings {}: {}", name, e);
+                            None
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            Ok::<_, String>((manifest, plan, settings))
+        })
+        .await
+        .map_err(|e| format!("Restore task join error: {}", e))?
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&staged_db);
+        })?
+    };
+
+    for (name, bytes) in &settings {
+        apply_settings(app, name, bytes, &recordings_root);
+    }
+
+    std::fs::rename(&staged_db, &db_path).map_err(|e| format!("Failed to put the restored database in place: {}", e))?;
+    let db_manager = DatabaseManager::new_from_app_handle(app)
+        .await
+        .map_err(|e| format!("Failed to open the restored database: {}", e))?;
+    let pool = db_manager.pool().clone();
+
+    let mut remapped_meetings = 0;
+    let mut renamed_folders = Vec::new();
+    for (folder, target) in &plan.folders {
+        if target.file_name().is_some_and(|name| name.to_string_lossy() != folder.name) {
+            renamed_folders.push(target.to_string_lossy().to_string());
+        }
+        let target = target.to_string_lossy();
+        for meeting_id in &folder.meeting_ids {
+            match MeetingsRepository::update_folder_path(&pool, meeting_id, &target).await {
+                Ok(true) => remapped_meetings += 1,
+                Ok(false) => warn!("Restored folder {} has no meeting {}", folder.name, meeting_id),
+                Err(e) => warn!("Failed to update the folder of meeting {}: {}", meeting_id, e),
+            }
+        }
+    }
+
+    app.manage(AppState { db_manager });
+    // An encrypted library encrypts the restored files like any plaintext leftovers
+    crate::encryption::spawn_library_sweep(pool);
+    crate::start_background_tasks(app);
+    app.emit("database-initialized", ())
+        .map_err(|e| format!("Failed to emit database-initialized event: {}", e))?;
+
+    info!(
+        "Restored backup from {} ({} meeting folders, {} meetings remapped to {})",
+        manifest.created_at,
+        plan.folders.len(),
+        remapped_meetings,
+        recordings_root.display()
+    );
+    Ok(RestoreResult {
+        recordings_folder: recordings_root.to_string_lossy().to_string(),
+        meeting_folders: plan.folders.len(),
+        remapped_meetings,
+        renamed_folders,
+        includes_audio: manifest.includes_audio,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> BackupFileEntry {
+        BackupFileEntry { size: 1, sha256: String::new(), source_size: 1, source_modified: 0 }
+    }
+
+    #[test]
+    fn meeting_folders_are_shared_and_named_uniquely() {
+        let meetings = vec![
+            ("a".to_string(), PathBuf::from("/old/Standup")),
+            ("b".to_string(), PathBuf::from("/old/Standup")),
+            ("c".to_string(), PathBuf::from("/elsewhere/Standup")),
+        ];
+        let folders = plan_meeting_folders(&meetings);
+        assert_eq!(folders.len(), 2);
+        assert_eq!(folders[0].0.name, "Standup");
+        assert_eq!(folders[0].0.meeting_ids, vec!["a", "b"]);
+        assert_eq!(folders[1].0.name, "Standup_2");
+        assert_eq!(folders[1].0.original_path, "/elsewhere/Standup");
+    }
+
+    #[test]
+    fn restore_maps_folders_to_new_root() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir(root.path().join("Taken")).unwrap();
+        let mut files = BTreeMap::new();
+        for path in [
+            "database.sqlite",
+            "meetings/Standup/transcripts.json",
+            "meetings/Taken/audio.mp4",
+            "templates/custom.json",
+            "settings/retention.json",
+        ] {
+            files.insert(path.to_string(), entry());
+        }
+        let folder = |name: &str| BackupMeetingFolder {
+            name: name.to_string(),
+            original_path: format!("C:\\Users\\me\\{}", name),
+            meeting_ids: vec![name.to_lowercase()],
+        };
+        let manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            app_version: "1.0.0".to_string(),
+            created_at: String::new(),
+            includes_audio: true,
+            encryption: None,
+            meeting_folders: vec![folder("Standup"), folder("Taken")],
+            files,
+        };
+
+        let plan = plan_restore(&manifest, root.path(), None, None).unwrap();
+        assert_eq!(plan.folders[0].1, root.path().join("Standup"));
+        assert_eq!(plan.folders[1].1, root.path().join("Taken_2"));
+        let targets: HashMap<&str, &Path> = plan.targets.iter().map(|(a, t)| (a.as_str(), t.as_path())).collect();
+        assert_eq!(
+            targets["meetings/Standup/transcripts.json"],
+            root.path().join("Standup").join("transcripts.json")
+        );
+        assert_eq!(targets["meetings/Taken/audio.mp4"], root.path().join("Taken_2").join("audio.mp4"));
+        // No templates directory on this machine
+        assert!(!targets.contains_key("templates/custom.json"));
+        assert_eq!(plan.settings, vec![("settings/retention.json".to_string(), "retention.json".to_string())]);
+    }
+
+    #[test]
+    fn rejects_paths_leaving_the_backup() {
+        assert!(archive::relative_path("meetings/a/audio.mp4").is_some());
+        assert!(archive::relative_path("../outside").is_none());
+        assert!(archive::relative_path("/etc/passwd").is_none());
+        assert!(archive::relative_path("meetings\\..\\x").is_none());
+        assert!(archive::relative_path("").is_none());
+    }
+
+    #[test]
+    fn strips_credentials_from_settings() {
+        let mut value = serde_json::json!({
+            "authToken": "abc",
+            "theme": "dark",
+            "nested": {"refresh_token": "x", "openaiApiKey": "y", "language": "en"}
+        });
+        strip_secrets(&mut value);
+        assert_eq!(value, serde_json::json!({"theme": "dark", "nested": {"language": "en"}}));
+    }
+
+    #[test]
+    fn directory_backup_is_incremental() {
+        let library = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        std::fs::write(library.path().join("kept.json"), b"{}").unwrap();
+        std::fs::write(library.path().join("gone.json"), b"[]").unwrap();
+        let sources = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| SourceFile {
+                    archive_path: format!("meetings/m/{}", name),
+                    path: library.path().join(name),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first = write_backup(target.path(), sources(&["kept.json", "gone.json"]), vec![], true, "1".into(), None).unwrap();
+        assert_eq!((first.copied_files, first.unchanged_files), (2, 0));
+
+        std::fs::remove_file(library.path().join("gone.json")).unwrap();
+        let second = write_backup(target.path(), sources(&["kept.json"]), vec![], true, "1".into(), None).unwrap();
+        assert_eq!((second.copied_files, second.unchanged_files, second.removed_files), (0, 1, 1));
+        assert!(!target.path().join("meetings/m/gone.json").exists());
+
+        let summary = inspect_backup(target.path()).unwrap();
+        assert_eq!(summary.file_count, 1);
+    }
+
+    #[test]
+    fn passphrase_backup_is_encrypted_and_keeps_its_key() {
+        let library = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        std::fs::write(library.path().join("transcripts.json"), b"{\"text\":\"secret plans\"}").unwrap();
+        let sources = || {
+            vec![SourceFile {
+                archive_path: "meetings/m/transcripts.json".to_string(),
+                path: library.path().join("transcripts.json"),
+            }]
+        };
+
+        let passphrase = Some("correct horse");
+        write_backup(target.path(), sources(), vec![], true, "1".into(), passphrase).unwrap();
+        let stored = std::fs::read(target.path().join("meetings/m/transcripts.json")).unwrap();
+        assert!(!String::from_utf8_lossy(&stored).contains("secret plans"));
+
+        // Updating needs the same passphrase; unchanged files are kept
+        assert!(write_backup(target.path(), sources(), vec![], true, "1".into(), Some("wrong horse")).is_err());
+        let second = write_backup(target.path(), sources(), vec![], true, "1".into(), passphrase).unwrap();
+        assert_eq!((second.copied_files, second.unchanged_files), (0, 1));
+
+        let mut source = BackupSource::open(target.path()).unwrap();
+        let manifest = source.read_manifest().unwrap();
+        assert!(inspect_backup(target.path()).unwrap().encrypted);
+        let encryption = manifest.encryption.as_ref().unwrap();
+        assert!(encryption.key("wrong horse").is_err());
+        let key = encryption.key("correct horse").unwrap();
+        let entry = &manifest.files["meetings/m/transcripts.json"];
+        let bytes = source.read_bytes("meetings/m/transcripts.json", entry, Some(&key)).unwrap();
+        assert_eq!(bytes, b"{\"text\":\"secret plans\"}");
+
+        // An unencrypted backup into the same directory rewrites every file
+        let plain = write_backup(target.path(), sources(), vec![], true, "1".into(), None).unwrap();
+        assert_eq!((plain.copied_files, plain.unchanged_files), (1, 0));
+    }
+}
@@ -0,0 +1,96 @@
+use crate::chapters::export;
+use crate::chapters::service::{ChapterRequest, ChapterService};
+use crate::database::models::MeetingChapter;
+use crate::database::repositories::{chapter::ChaptersRepository, summary::SummaryProcessesRepository};
+use crate::state::AppState;
+use log::info as log_info;
+use tauri::{AppHandle, Runtime};
+
+/// Starts dividing a meeting into titled topic chapters
+///
+/// Runs in the background; progress is reported through chapters-progress events and the
+/// result through chapters-complete / chapters-error.
+#[tauri::command]
+pub async fn api_generate_meeting_chapters<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    model: String,
+    model_name: String,
+) -> Result<(), String> {
+    log_info!(
+        "api_generate_meeting_chapters called for meeting_id: {}, model: {}",
+        meeting_id,
+        model
+    );
+
+    let request = ChapterRequest {
+        meeting_id,
+        model_provider: model,
+        model_name,
+    };
+
+    let pool = state.db_manager.pool().clone();
+    tauri::async_runtime::spawn(async move {
+        ChapterService::generate_chapters_background(app, pool, request).await;
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn api_get_meeting_chapters(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<MeetingChapter>, String> {
+    ChaptersRepository::get_chapters(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load chapters: {}", e))
+}
+
+#[tauri::command]
+pub async fn api_delete_meeting_chapters(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<bool, String> {
+    ChaptersRepository::delete_chapters(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to delete chapters: {}", e))
+}
+
+/// Exports a meeting's chapters
+///
+/// Formats: "webvtt" (chapter track) or "markdown" (the summary with a chapter table of
+/// contents after its title, or only the table of contents when there is no summary).
+#[tauri::command]
+pub async fn api_export_meeting_chapters(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    format: String,
+) -> Result<String, String> {
+    let pool = state.db_manager.pool();
+    let chapters = ChaptersRepository::get_chapters(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load chapters: {}", e))?;
+    if chapters.is_empty() {
+        return Err("This meeting has no chapters yet".to_string());
+    }
+
+    match format.trim().to_lowercase().as_str() {
+        "webvtt" | "vtt" => Ok(export::to_webvtt(&chapters)),
+        "markdown" | "md" => {
+            let summary = SummaryProcessesRepository::get_summary_data(pool, &meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load summary: {}", e))?
+                .and_then(|p| p.result)
+                .and_then(|result| serde_json::from_str::<serde_json::Value>(&result).ok())
+                .and_then(|json| json.get("markdown").and_then(|m| m.as_str()).map(str::to_string))
+                .filter(|markdown| !markdown.trim().is_empty());
+            Ok(match summary {
+                Some(markdown) => export::insert_toc(&markdown, &chapters),
+                None => export::to_markdown_toc(&chapters),
+            })
+        }
+        other => Err(format!("Unsupported chapter export format: {}", other)),
+    }
+}
@@ -0,0 +1,150 @@
+//! Chapter exports: WebVTT chapter tracks, a markdown table of contents, and the plain-text
+//! chapter list offered to summary templates as the `{{chapters}}` variable.
+
+use crate::database::models::MeetingChapter;
+
+/// HH:MM:SS.mmm as required by WebVTT cue timings
+fn vtt_time(seconds: f64) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        (millis / 60_000) % 60,
+        (millis / 1000) % 60,
+        millis % 1000
+    )
+}
+
+/// MM:SS, or H:MM:SS from one hour on
+fn short_time(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    if total >= 3600 {
+        format!("{}:{:02}:{:02}", total / 3600, (total / 60) % 60, total % 60)
+    } else {
+        format!("{:02}:{:02}", total / 60, total % 60)
+    }
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// WebVTT chapters track (kind="chapters"): one cue per chapter with its title as text
+pub fn to_webvtt(chapters: &[MeetingChapter]) -> String {
+    let mut vtt = String::from("WEBVTT\n");
+    for (n, chapter) in chapters.iter().enumerate() {
+        // "-->" is not allowed in cue text
+        let title = single_line(&chapter.title).replace("-->", "->");
+        vtt.push_str(&format!(
+            "\nchapter-{}\n{} --> {}\n{}\n",
+            n + 1,
+            vtt_time(chapter.start_time),
+            vtt_time(chapter.end_time),
+            title
+        ));
+    }
+    vtt
+}
+
+/// Markdown table of contents with start times and synopses
+pub fn to_markdown_toc(chapters: &[MeetingChapter]) -> String {
+    let mut toc = String::from("## Chapters\n\n");
+    for (n, chapter) in chapters.iter().enumerate() {
+        toc.push_str(&format!(
+            "{}. `{}` **{}**",
+            n + 1,
+            short_time(chapter.start_time),
+            single_line(&chapter.title)
+        ));
+        if let Some(synopsis) = chapter.synopsis.as_deref().map(single_line).filter(|s| !s.is_empty()) {
+            toc.push_str(&format!(" — {}", synopsis));
+        }
+        toc.push('\n');
+    }
+    toc
+}
+
+/// Summary markdown with the chapter table of contents after its title heading
+pub fn insert_toc(summary_markdown: &str, chapters: &[MeetingChapter]) -> String {
+    let toc = to_markdown_toc(chapters);
+    match summary_markdown.split_once('\n') {
+        Some((title, rest)) if title.starts_with("# ") => format!("{}\n\n{}\n{}", title, toc, rest.trim_start()),
+        None if summary_markdown.starts_with("# ") => format!("{}\n\n{}", summary_markdown, toc),
+        _ => format!("{}\n{}", toc, summary_markdown),
+    }
+}
+
+/// Chapter list as substituted for `{{chapters}}` in summary templates
+pub fn to_template_variable(chapters: &[MeetingChapter]) -> String {
+    chapters
+        .iter()
+        .map(|chapter| {
+            let mut line = format!(
+                "- [{}-{}] {}",
+                short_time(chapter.start_time),
+                short_time(chapter.end_time),
+                single_line(&chapter.title)
+            );
+            if let Some(synopsis) = chapter.synopsis.as_deref().filter(|s| !s.trim().is_empty()) {
+                line.push_str(&format!(": {}", single_line(synopsis)));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(index: i64, title: &str, synopsis: Option<&str>, start: f64, end: f64) -> MeetingChapter {
+        MeetingChapter {
+            id: format!("chapter-{}", index),
+            meeting_id: "meeting-1".to_string(),
+            chapter_index: index,
+            title: title.to_string(),
+            synopsis: synopsis.map(String::from),
+            start_time: start,
+            end_time: end,
+            provider: None,
+            model: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn sample() -> Vec<MeetingChapter> {
+        vec![
+            chapter(0, "Launch budget", Some("Marketing asks for a larger budget."), 0.0, 482.25),
+            chapter(1, "Staging outage", None, 482.25, 3725.5),
+        ]
+    }
+
+    #[test]
+    fn test_to_webvtt() {
+        let vtt = to_webvtt(&sample());
+        assert!(vtt.starts_with("WEBVTT\n"));
+        assert!(vtt.contains("chapter-1\n00:00:00.000 --> 00:08:02.250\nLaunch budget\n"));
+        assert!(vtt.contains("chapter-2\n00:08:02.250 --> 01:02:05.500\nStaging outage\n"));
+    }
+
+    #[test]
+    fn test_markdown_toc_and_insert() {
+        let toc = to_markdown_toc(&sample());
+        assert!(toc.contains("1. `00:00` **Launch budget** — Marketing asks for a larger budget.\n"));
+        assert!(toc.contains("2. `08:02` **Staging outage**\n"));
+
+        let summary = "# Weekly sync\n\n**Summary**\n\nText";
+        let with_toc = insert_toc(summary, &sample());
+        assert!(with_toc.starts_with("# Weekly sync\n\n## Chapters\n"));
+        assert!(with_toc.ends_with("**Summary**\n\nText"));
+    }
+
+    #[test]
+    fn test_to_template_variable() {
+        assert_eq!(
+            to_template_variable(&sample()),
+            "- [00:00-08:02] Launch budget: Marketing asks for a larger budget.\n- [08:02-1:02:05] Staging outage"
+        );
+    }
+}
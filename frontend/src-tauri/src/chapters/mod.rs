@@ -0,0 +1,18 @@
+/// Chapters module - automatic topic segmentation of meetings
+///
+/// This module contains:
+/// - Lexical-cohesion (TextTiling) segmentation of the transcript into topic chapters
+/// - Service layer that titles each chapter and writes a one-line synopsis with the
+///   configured LLM provider
+/// - Exports: WebVTT chapter tracks, a markdown table of contents, and the `{{chapters}}`
+///   variable for summary templates
+/// - Tauri commands for generating, reading, exporting and deleting chapters
+///
+/// Chapters are stored per meeting in the `meeting_chapters` table.
+
+pub mod commands;
+pub mod export;
+pub mod segmentation;
+pub mod service;
+
+pub use service::ChapterService;
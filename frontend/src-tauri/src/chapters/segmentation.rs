@@ -0,0 +1,305 @@
+//! Topic segmentation by lexical cohesion (TextTiling).
+//!
+//! The transcript is cut into fixed-length time blocks. For every gap between blocks the
+//! vocabulary of the blocks before it is compared with the vocabulary after it; where the
+//! similarity dips deeply relative to its surroundings, the topic changed. The deepest dips
+//! become chapter boundaries, subject to a minimum chapter length.
+
+use crate::database::models::TranscriptVersionSegment;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Length of one comparison block, in seconds
+const BLOCK_SECONDS: f64 = 45.0;
+
+/// Blocks compared on each side of a gap
+const WINDOW_BLOCKS: usize = 3;
+
+/// Smallest similarity dip that counts as a topic change (cosine similarity is 0..1, a dip
+/// is measured on both sides, so depths range 0..2)
+const MIN_DEPTH: f64 = 0.15;
+
+/// Shortest chapter, in seconds
+pub const MIN_CHAPTER_SECONDS: f64 = 180.0;
+
+/// Upper bound on chapters per meeting
+pub const MAX_CHAPTERS: usize = 20;
+
+/// Words shorter than this are ignored
+const MIN_TERM_CHARS: usize = 3;
+
+/// Frequent English function words and meeting filler that carry no topic
+const STOP_WORDS: &[&str] = &[
+    "about", "actually", "after", "again", "all", "also", "and", "any", "are", "because", "been", "before",
+    "being", "but", "can", "could", "did", "does", "doing", "don't", "down", "each", "even", "for", "from",
+    "get", "going", "gonna", "got", "had", "has", "have", "having", "her", "here", "him", "his", "how",
+    "i'm", "into", "it's", "its", "just", "know", "like", "let's", "maybe", "mean", "more", "most", "much",
+    "need", "not", "now", "okay", "one", "only", "other", "our", "out", "over", "really", "right", "said",
+    "say", "see", "she", "should", "some", "something", "still", "such", "sure", "than", "that", "that's",
+    "the", "their", "them", "then", "there", "there's", "these", "they", "thing", "things", "think", "this",
+    "those", "through", "too", "uh", "um", "very", "want", "was", "way", "we're", "well", "were", "what",
+    "when", "where", "which", "while", "who", "why", "will", "with", "would", "yeah", "yes", "you", "you're",
+    "your",
+];
+
+/// A chapter found by segmentation: time range and the segments it covers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChapterSpan {
+    pub start_time: f64,
+    pub end_time: f64,
+    /// Indices into the segments passed to `segment_topics`
+    pub segments: Range<usize>,
+}
+
+struct Block {
+    start_time: f64,
+    /// First segment index of the block
+    first_segment: usize,
+    terms: HashMap<String, f64>,
+}
+
+/// Content words of a text, lowercased
+pub fn content_terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .to_lowercase()
+        })
+        .filter(|word| word.chars().count() >= MIN_TERM_CHARS && !STOP_WORDS.contains(&word.as_str()))
+        .filter(|word| !word.chars().all(|c| c.is_numeric()))
+}
+
+/// Most frequent content words of a text, most frequent first
+pub fn top_terms(text: &str, n: usize) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut first_seen: Vec<String> = Vec::new();
+    for term in content_terms(text) {
+        let count = counts.entry(term.clone()).or_insert(0);
+        if *count == 0 {
+            first_seen.push(term);
+        }
+        *count += 1;
+    }
+    // Stable by first occurrence among equal counts
+    first_seen.sort_by(|a, b| counts[b].cmp(&counts[a]));
+    first_seen.truncate(n);
+    first_seen
+}
+
+fn build_blocks(segments: &[TranscriptVersionSegment]) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    for (index, segment) in segments.iter().enumerate() {
+        let (Some(start), Some(_)) = (segment.audio_start_time, segment.audio_end_time) else {
+            continue;
+        };
+        let open_new = match blocks.last() {
+            Some(block) => start - block.start_time >= BLOCK_SECONDS,
+            None => true,
+        };
+        if open_new {
+            blocks.push(Block {
+                start_time: start,
+                first_segment: index,
+                terms: HashMap::new(),
+            });
+        }
+        let block = blocks.last_mut().expect("block was just ensured");
+        for term in content_terms(&segment.text) {
+            *block.terms.entry(term).or_insert(0.0) += 1.0;
+        }
+    }
+    blocks
+}
+
+fn cosine(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let dot: f64 = a.iter().filter_map(|(term, x)| b.get(term).map(|y| x * y)).sum();
+    let norm = |v: &HashMap<String, f64>| v.values().map(|x| x * x).sum::<f64>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        0.0
+    } else {
+        dot / denominator
+    }
+}
+
+fn merged_terms(blocks: &[Block]) -> HashMap<String, f64> {
+    let mut terms = HashMap::new();
+    for block in blocks {
+        for (term, count) in &block.terms {
+            *terms.entry(term.clone()).or_insert(0.0) += count;
+        }
+    }
+    terms
+}
+
+/// Similarity across each gap; gap g lies between block g and block g + 1
+fn gap_similarities(blocks: &[Block]) -> Vec<f64> {
+    (0..blocks.len().saturating_sub(1))
+        .map(|gap| {
+            let left = &blocks[(gap + 1).saturating_sub(WINDOW_BLOCKS)..=gap];
+            let right = &blocks[gap + 1..(gap + 1 + WINDOW_BLOCKS).min(blocks.len())];
+            cosine(&merged_terms(left), &merged_terms(right))
+        })
+        .collect()
+}
+
+/// TextTiling depth: how far the similarity at each gap lies below the peaks on both sides
+fn depth_scores(similarities: &[f64]) -> Vec<f64> {
+    (0..similarities.len())
+        .map(|gap| {
+            let value = similarities[gap];
+            let mut left_peak = value;
+            for &s in similarities[..gap].iter().rev() {
+                if s < left_peak {
+                    break;
+                }
+                left_peak = s;
+            }
+            let mut right_peak = value;
+            for &s in &similarities[gap + 1..] {
+                if s < right_peak {
+                    break;
+                }
+                right_peak = s;
+            }
+            (left_peak - value) + (right_peak - value)
+        })
+        .collect()
+}
+
+/// Split a transcript into topic chapters. Segments without timing are ignored; a transcript
+/// that is too short to split, or has no clear topic changes, becomes a single chapter.
+pub fn segment_topics(segments: &[TranscriptVersionSegment]) -> Vec<ChapterSpan> {
+    let timed: Vec<usize> = (0..segments.len())
+        .filter(|&i| segments[i].audio_start_time.is_some() && segments[i].audio_end_time.is_some())
+        .collect();
+    let (Some(&first), Some(&last)) = (timed.first(), timed.last()) else {
+        return Vec::new();
+    };
+    let meeting_start = segments[first].audio_start_time.unwrap_or_default();
+    let meeting_end = timed
+        .iter()
+        .filter_map(|&i| segments[i].audio_end_time)
+        .fold(meeting_start, f64::max);
+
+    let blocks = build_blocks(segments);
+    let similarities = gap_similarities(&blocks);
+    let depths = depth_scores(&similarities);
+
+    let mut boundaries: Vec<usize> = Vec::new(); // block indices that start a chapter
+    if !depths.is_empty() {
+        let mean = depths.iter().sum::<f64>() / depths.len() as f64;
+        let variance = depths.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / depths.len() as f64;
+        let threshold = mean - variance.sqrt() / 2.0;
+
+        let mut candidates: Vec<usize> = (0..depths.len())
+            .filter(|&gap| depths[gap] >= MIN_DEPTH && depths[gap] >= threshold)
+            .collect();
+        candidates.sort_by(|a, b| depths[*b].total_cmp(&depths[*a]));
+
+        for gap in candidates {
+            if boundaries.len() + 1 >= MAX_CHAPTERS {
+                break;
+            }
+            let at = blocks[gap + 1].start_time;
+            let far_from_edges = at - meeting_start >= MIN_CHAPTER_SECONDS && meeting_end - at >= MIN_CHAPTER_SECONDS;
+            let far_from_others = boundaries
+                .iter()
+                .all(|&b| (blocks[b].start_time - at).abs() >= MIN_CHAPTER_SECONDS);
+            if far_from_edges && far_from_others {
+                boundaries.push(gap + 1);
+            }
+        }
+        boundaries.sort_unstable();
+    }
+
+    let mut starts: Vec<usize> = vec![first];
+    starts.extend(boundaries.iter().map(|&b| blocks[b].first_segment));
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start_segment)| {
+            let end_segment = starts.get(n + 1).copied().unwrap_or(last + 1);
+            let start_time = if n == 0 {
+                meeting_start
+            } else {
+                segments[start_segment].audio_start_time.unwrap_or(meeting_start)
+            };
+            let end_time = match starts.get(n + 1) {
+                Some(&next) => segments[next].audio_start_time.unwrap_or(meeting_end),
+                None => meeting_end,
+            };
+            ChapterSpan {
+                start_time,
+                end_time,
+                segments: start_segment..end_segment,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start: f64) -> TranscriptVersionSegment {
+        crate::database::models::segment(&format!("s-{}", start), text, start, start + 14.0)
+    }
+
+    /// `minutes` minutes of segments (one per 15s) cycling through `sentences`
+    fn topic(sentences: &[&str], from: f64, minutes: usize) -> Vec<TranscriptVersionSegment> {
+        (0..minutes * 4)
+            .map(|i| segment(sentences[i % sentences.len()], from + i as f64 * 15.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_content_terms_and_top_terms() {
+        let terms: Vec<String> = content_terms("So, the Budget... we really need the budget for Q3 hiring!").collect();
+        assert_eq!(terms, vec!["budget", "budget", "hiring"]);
+        assert_eq!(top_terms("hiring budget budget roadmap budget hiring", 2), vec!["budget", "hiring"]);
+    }
+
+    #[test]
+    fn test_segment_topics_finds_topic_change() {
+        let mut segments = topic(
+            &[
+                "The marketing budget for the campaign launch is too small",
+                "Campaign launch budget needs approval from marketing",
+                "Marketing wants more budget for the launch campaign",
+            ],
+            0.0,
+            8,
+        );
+        segments.extend(topic(
+            &[
+                "The database migration broke the staging server",
+                "Staging server database needs a rollback of the migration",
+                "Migration scripts for the database server are flaky",
+            ],
+            480.0,
+            8,
+        ));
+
+        let chapters = segment_topics(&segments);
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].start_time, 0.0);
+        // The boundary lands within one block of the real change at 8 minutes
+        assert!((chapters[1].start_time - 480.0).abs() <= BLOCK_SECONDS);
+        assert_eq!(chapters[0].end_time, chapters[1].start_time);
+        assert_eq!(chapters[0].segments.end, chapters[1].segments.start);
+        assert_eq!(chapters[1].segments.end, segments.len());
+    }
+
+    #[test]
+    fn test_segment_topics_short_or_uniform_meeting_is_one_chapter() {
+        let segments = topic(&["Quarterly planning for the roadmap and hiring"], 0.0, 4);
+        let chapters = segment_topics(&segments);
+        assert_eq!(chapters.len(), 1);
+        assert_eq!(chapters[0].segments, 0..segments.len());
+        assert_eq!(chapters[0].end_time, segments.last().unwrap().audio_end_time.unwrap());
+
+        assert!(segment_topics(&[]).is_empty());
+    }
+}
@@ -0,0 +1,309 @@
+use crate::chapters::segmentation::{segment_topics, top_terms, ChapterSpan};
+use crate::database::models::{MeetingChapter, TranscriptVersionSegment};
+use crate::database::repositories::{chapter::ChaptersRepository, transcript_version::TranscriptVersionsRepository};
+use crate::summary::llm_client::generate_summary;
+use crate::summary::processor::clean_llm_markdown_output;
+use crate::summary::redaction::{self, Redactor};
+use crate::summary::service::{LlmConnection, SummaryService, HTTP_CLIENT};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Transcript text sent to the model per chapter (rough tokens); long chapters are sampled
+const CHAPTER_PROMPT_TOKENS: usize = 1500;
+
+/// Keywords used as a fallback title when the model's reply cannot be parsed
+const FALLBACK_TITLE_TERMS: usize = 3;
+
+const TITLE_SYSTEM_PROMPT: &str = "You title one section of a meeting transcript. Reply with exactly two lines:\n\
+Title: <a specific title of at most six words>\n\
+Synopsis: <one sentence saying what was discussed or decided>\n\
+Write in the language of the transcript. Do not add anything else.";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterProgress {
+    pub meeting_id: String,
+    pub progress: u32,
+    pub message: String,
+}
+
+/// What to segment and which model titles the chapters
+#[derive(Debug, Clone)]
+pub struct ChapterRequest {
+    pub meeting_id: String,
+    pub model_provider: String,
+    pub model_name: String,
+}
+
+/// Chapter service - topic segmentation and LLM chapter titles
+pub struct ChapterService;
+
+impl ChapterService {
+    pub async fn generate_chapters_background<R: Runtime>(
+        app: AppHandle<R>,
+        pool: SqlitePool,
+        request: ChapterRequest,
+    ) {
+        match Self::generate_chapters(&app, &pool, &request).await {
+            Ok(chapters) => {
+                info!("Generated {} chapters for meeting {}", chapters.len(), request.meeting_id);
+                let _ = app.emit(
+                    "chapters-complete",
+                    serde_json::json!({ "meeting_id": request.meeting_id, "chapters": chapters }),
+                );
+            }
+            Err(e) => {
+                error!("Chapter generation for meeting {} failed: {}", request.meeting_id, e);
+                let _ = app.emit(
+                    "chapters-error",
+                    serde_json::json!({ "meeting_id": request.meeting_id, "error": e }),
+                );
+            }
+        }
+    }
+
+    async fn generate_chapters<R: Runtime>(
+        app: &AppHandle<R>,
+        pool: &SqlitePool,
+        request: &ChapterRequest,
+    ) -> Result<Vec<MeetingChapter>, String> {
+        let meeting_id = request.meeting_id.as_str();
+        emit_progress(app, meeting_id, 5, "Finding topic changes...");
+
+        let segments = TranscriptVersionsRepository::get_current_segments(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load transcript: {}", e))?;
+        let spans = segment_topics(&segments);
+        if spans.is_empty() {
+            return Err("The transcript has no timed segments to divide into chapters".to_string());
+        }
+        info!("Meeting {}: {} topic chapters found", meeting_id, spans.len());
+
+        let connection = SummaryService::resolve_connection(pool, &request.model_provider).await?;
+        let app_data_dir = app.path().app_data_dir().ok();
+        let created_at = chrono::Utc::now();
+
+        let texts: Vec<String> = spans.iter().map(|span| chapter_text(&segments, span)).collect();
+        // One redactor for every chapter keeps the placeholders consistent across requests
+        let text_refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let mut redactor = redaction::redactor_for(app, pool, &connection.provider, &text_refs).await?;
+        let system_prompt = if redactor.is_some() {
+            format!("{} {}", TITLE_SYSTEM_PROMPT, redaction::PLACEHOLDER_NOTE)
+        } else {
+            TITLE_SYSTEM_PROMPT.to_string()
+        };
+
+        let mut replies = Vec::with_capacity(spans.len());
+        for (n, text) in texts.iter().enumerate() {
+            emit_progress(
+                app,
+                meeting_id,
+                10 + (n * 85 / spans.len()) as u32,
+                &format!("Titling chapter {} of {}...", n + 1, spans.len()),
+            );
+
+            let text = match redactor.as_mut() {
+                Some(redactor) => redactor.redact(text),
+                None => text.clone(),
+            };
+            let reply = complete(
+                &connection,
+                &request.model_name,
+                app_data_dir.as_ref(),
+                &system_prompt,
+                &format!("<transcript_section>\n{}\n</transcript_section>", text),
+            )
+            .await;
+            match reply {
+                Ok(reply) => replies.push(reply),
+                Err(e) => {
+                    record_redactions(pool, request, redactor.as_ref()).await;
+                    return Err(e);
+                }
+            }
+        }
+        record_redactions(pool, request, redactor.as_ref()).await;
+
+        let mut chapters = Vec::with_capacity(spans.len());
+        for (n, (span, (text, reply))) in spans.iter().zip(texts.iter().zip(replies)).enumerate() {
+            let output = match &redactor {
+                Some(redactor) => redactor.restore(&reply),
+                None => reply,
+            };
+            let (title, synopsis) = parse_title_reply(&clean_llm_markdown_output(&output)).unwrap_or_else(|| {
+                warn!("Could not parse chapter title reply for meeting {}: {}", meeting_id, output);
+                (fallback_title(text, n), None)
+            });
+
+            chapters.push(MeetingChapter {
+                id: format!("chapter-{}", Uuid::new_v4()),
+                meeting_id: meeting_id.to_string(),
+                chapter_index: n as i64,
+                title,
+                synopsis,
+                start_time: span.start_time,
+                end_time: span.end_time,
+                provider: Some(request.model_provider.clone()),
+                model: Some(request.model_name.clone()),
+                created_at,
+            });
+        }
+
+        ChaptersRepository::replace_chapters(pool, meeting_id, &chapters)
+            .await
+            .map_err(|e| format!("Failed to save chapters: {}", e))?;
+        emit_progress(app, meeting_id, 100, "Chapters ready");
+        Ok(chapters)
+    }
+}
+
+async fn record_redactions(pool: &SqlitePool, request: &ChapterRequest, redactor: Option<&Redactor>) {
+    if let Some(redactor) = redactor {
+        redaction::record_audit(
+            pool,
+            Some(&request.meeting_id),
+            "chapters",
+            &request.model_provider,
+            &request.model_name,
+            redactor,
+        )
+        .await;
+    }
+}
+
+async fn complete(
+    connection: &LlmConnection,
+    model_name: &str,
+    app_data_dir: Option<&PathBuf>,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> Result<String, String> {
+    let client = HTTP_CLIENT.clone();
+    generate_summary(
+        &client,
+        &connection.provider,
+        model_name,
+        &connection.api_key,
+        system_prompt,
+        user_prompt,
+        connection.ollama_endpoint.as_deref(),
+        connection.custom_openai_endpoint.as_deref(),
+        connection.max_tokens,
+        connection.temperature,
+        connection.top_p,
+        app_data_dir,
+        None,
+    )
+    .await
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, progress: u32, message: &str) {
+    let _ = app.emit(
+        "chapters-progress",
+        ChapterProgress {
+            meeting_id: meeting_id.to_string(),
+            progress,
+            message: message.to_string(),
+        },
+    );
+}
+
+/// Transcript text of a chapter within the prompt budget. Long chapters keep evenly spaced
+/// segments so the whole chapter is represented, not just its beginning.
+fn chapter_text(segments: &[TranscriptVersionSegment], span: &ChapterSpan) -> String {
+    let lines: Vec<&str> = segments[span.segments.clone()]
+        .iter()
+        .map(|s| s.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let total_chars: usize = lines.iter().map(|l| l.len() + 1).sum();
+    let budget_chars = CHAPTER_PROMPT_TOKENS * 4;
+    if total_chars <= budget_chars {
+        return lines.join("\n");
+    }
+
+    let keep_every = total_chars.div_ceil(budget_chars);
+    lines
+        .iter()
+        .step_by(keep_every)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse "Title: ..." / "Synopsis: ..." lines; tolerates markdown bold and missing synopsis
+fn parse_title_reply(reply: &str) -> Option<(String, Option<String>)> {
+    let field = |name: &str| {
+        reply.lines().find_map(|line| {
+            let line = line.trim().trim_start_matches(['-', '*', '#', ' ']);
+            let (key, value) = line.split_once(':')?;
+            let key = key.trim_matches(|c: char| c == '*' || c.is_whitespace());
+            if !key.eq_ignore_ascii_case(name) {
+                return None;
+            }
+            let value = value.trim().trim_matches(|c: char| c == '*' || c == '"' || c.is_whitespace());
+            (!value.is_empty()).then(|| value.to_string())
+        })
+    };
+    Some((field("title")?, field("synopsis")))
+}
+
+fn fallback_title(text: &str, index: usize) -> String {
+    let terms = top_terms(text, FALLBACK_TITLE_TERMS);
+    if terms.is_empty() {
+        return format!("Chapter {}", index + 1);
+    }
+    let mut title = terms.join(", ");
+    if let Some(first) = title.get(..1) {
+        title = first.to_uppercase() + &title[1..];
+    }
+    title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::segment;
+
+    #[test]
+    fn test_parse_title_reply() {
+        assert_eq!(
+            parse_title_reply("Title: Launch budget\nSynopsis: Marketing asks for more money."),
+            Some(("Launch budget".to_string(), Some("Marketing asks for more money.".to_string())))
+        );
+        assert_eq!(
+            parse_title_reply("**Title:** \"Staging outage\"\n"),
+            Some(("Staging outage".to_string(), None))
+        );
+        assert_eq!(parse_title_reply("Here are my thoughts"), None);
+    }
+
+    #[test]
+    fn test_fallback_title() {
+        assert_eq!(fallback_title("budget budget hiring roadmap hiring budget", 0), "Budget, hiring, roadmap");
+        assert_eq!(fallback_title("uh um", 2), "Chapter 3");
+    }
+
+    #[test]
+    fn test_chapter_text_samples_long_chapters() {
+        let segments: Vec<TranscriptVersionSegment> = (0..2000)
+            .map(|i| {
+                let text = format!("segment number {} with some words", i);
+                segment(&i.to_string(), &text, i as f64, i as f64 + 1.0)
+            })
+            .collect();
+        let span = ChapterSpan {
+            start_time: 0.0,
+            end_time: 2000.0,
+            segments: 0..2000,
+        };
+        let text = chapter_text(&segments, &span);
+        assert!(text.len() <= CHAPTER_PROMPT_TOKENS * 4 + 100);
+        assert!(text.starts_with("segment number 0 "));
+        // The end of the chapter is represented too
+        assert!(text.contains("segment number 1992 "));
+    }
+}
@@ -0,0 +1,38 @@
+use crate::conversation_analytics::metrics::ConversationMetrics;
+use crate::conversation_analytics::service::{ConversationAnalyticsService, SpeakerTrendPoint};
+use crate::state::AppState;
+use log::info as log_info;
+
+/// Conversation analytics of a meeting, computed on first request and after transcript changes
+#[tauri::command]
+pub async fn api_get_meeting_analytics(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    recompute: Option<bool>,
+) -> Result<ConversationMetrics, String> {
+    log_info!("api_get_meeting_analytics called for meeting_id: {}", meeting_id);
+    ConversationAnalyticsService::analytics_for_meeting(
+        state.db_manager.pool(),
+        &meeting_id,
+        recompute.unwrap_or(false),
+    )
+    .await
+}
+
+/// A speaker's metrics across all meetings they spoke in, oldest first
+#[tauri::command]
+pub async fn api_get_speaker_trend(
+    state: tauri::State<'_, AppState>,
+    speaker: String,
+) -> Result<Vec<SpeakerTrendPoint>, String> {
+    log_info!("api_get_speaker_trend called for speaker: {}", speaker);
+    if speaker.trim().is_empty() {
+        return Err("Speaker name cannot be empty".to_string());
+    }
+    ConversationAnalyticsService::speaker_trend(state.db_manager.pool(), &speaker).await
+}
+
+#[tauri::command]
+pub async fn api_list_analytics_speakers(state: tauri::State<'_, AppState>) -> Result<Vec<String>, String> {
+    ConversationAnalyticsService::speakers(state.db_manager.pool()).await
+}
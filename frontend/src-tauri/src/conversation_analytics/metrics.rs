@@ -0,0 +1,390 @@
+//! Conversation metrics computed from a transcript's timed segments.
+//!
+//! Segments are grouped into turns: a speaker's turn continues across short pauses and ends
+//! when they pause longer than `TURN_GAP_SECONDS` or when another speaker starts talking in
+//! the pause. Turns of different speakers that overlap are overlaps; an overlap where the
+//! newcomer keeps talking after the other speaker stopped is an interruption.
+
+use crate::database::models::TranscriptVersionSegment;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Label for segments without a speaker
+pub const UNKNOWN_SPEAKER: &str = "Unknown";
+
+/// Longest pause within one turn, in seconds
+const TURN_GAP_SECONDS: f64 = 1.5;
+
+/// Overlaps shorter than this are segment-boundary jitter, not talking over each other
+const MIN_OVERLAP_SECONDS: f64 = 0.3;
+
+/// Hesitation words, matched as whole words
+const FILLER_WORDS: &[&str] = &[
+    "um", "umm", "uh", "uhm", "uhh", "erm", "er", "ah", "hmm", "mhm", "basically", "literally",
+];
+
+/// Filler phrases, matched as consecutive words
+const FILLER_PHRASES: &[&[&str]] = &[&["you", "know"], &["i", "mean"]];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerMetrics {
+    pub speaker: String,
+    pub talk_seconds: f64,
+    /// Share of the total talk time of all speakers, 0..1
+    pub talk_share: f64,
+    pub words: u64,
+    pub words_per_minute: f64,
+    pub turns: u32,
+    pub longest_monologue_seconds: f64,
+    /// Times this speaker took the floor by talking over someone
+    pub interruptions: u32,
+    /// Times this speaker was talked over and stopped
+    pub interrupted: u32,
+    pub filler_words: u32,
+    pub questions: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Monologue {
+    pub speaker: String,
+    pub start_time: f64,
+    pub duration_seconds: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationMetrics {
+    /// From the first segment's start to the last segment's end
+    pub duration_seconds: f64,
+    /// Time in which at least one speaker talks
+    pub speech_seconds: f64,
+    /// Share of the duration in which nobody talks, 0..1
+    pub silence_ratio: f64,
+    pub overlap_count: u32,
+    pub overlap_seconds: f64,
+    pub interruption_count: u32,
+    pub words: u64,
+    pub words_per_minute: f64,
+    pub filler_word_count: u32,
+    pub question_count: u32,
+    pub longest_monologue: Option<Monologue>,
+    /// Most talk time first
+    pub speakers: Vec<SpeakerMetrics>,
+}
+
+struct Turn {
+    speaker: usize,
+    start: f64,
+    end: f64,
+}
+
+fn speaker_label(segment: &TranscriptVersionSegment) -> String {
+    segment
+        .speaker
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(UNKNOWN_SPEAKER)
+        .to_string()
+}
+
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+pub fn count_filler_words(text: &str) -> u32 {
+    let words = normalized_words(text);
+    let single = words.iter().filter(|w| FILLER_WORDS.contains(&w.as_str())).count();
+    let phrases = FILLER_PHRASES
+        .iter()
+        .map(|phrase| {
+            words
+                .windows(phrase.len())
+                .filter(|window| window.iter().zip(phrase.iter()).all(|(w, p)| w == p))
+                .count()
+        })
+        .sum::<usize>();
+    (single + phrases) as u32
+}
+
+/// Questions asked: runs of question marks ("??" is one question)
+pub fn count_questions(text: &str) -> u32 {
+    let mut count = 0;
+    let mut in_run = false;
+    for c in text.chars() {
+        let is_mark = c == '?' || c == '？';
+        if is_mark && !in_run {
+            count += 1;
+        }
+        in_run = is_mark;
+    }
+    count
+}
+
+/// Total length of the union of (start, end) intervals
+fn union_seconds(mut intervals: Vec<(f64, f64)>) -> f64 {
+    intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut total = 0.0;
+    let mut current: Option<(f64, f64)> = None;
+    for (start, end) in intervals {
+        match current {
+            Some((s, e)) if start <= e => current = Some((s, e.max(end))),
+            Some((s, e)) => {
+                total += e - s;
+                current = Some((start, end));
+            }
+            None => current = Some((start, end)),
+        }
+    }
+    if let Some((s, e)) = current {
+        total += e - s;
+    }
+    total
+}
+
+fn per_minute(count: u64, seconds: f64) -> f64 {
+    if seconds > 0.0 {
+        count as f64 * 60.0 / seconds
+    } else {
+        0.0
+    }
+}
+
+fn build_turns(timed: &[(usize, f64, f64)]) -> Vec<Turn> {
+    let mut open: HashMap<usize, Turn> = HashMap::new();
+    let mut turns = Vec::new();
+    for &(speaker, start, end) in timed {
+        // Another speaker talking in someone's pause ends their turn
+        let ended: Vec<usize> = open
+            .iter()
+            .filter(|(other, turn)| **other != speaker && start >= turn.end)
+            .map(|(other, _)| *other)
+            .collect();
+        for other in ended {
+            turns.extend(open.remove(&other));
+        }
+
+        match open.get_mut(&speaker) {
+            Some(turn) if start - turn.end <= TURN_GAP_SECONDS => turn.end = turn.end.max(end),
+            _ => {
+                let previous = open.insert(speaker, Turn { speaker, start, end });
+                turns.extend(previous);
+            }
+        }
+    }
+    turns.extend(open.into_values());
+    turns.sort_by(|a, b| a.start.total_cmp(&b.start));
+    turns
+}
+
+/// Compute conversation metrics. Segments without timing are ignored.
+pub fn compute_metrics(segments: &[TranscriptVersionSegment]) -> ConversationMetrics {
+    let mut timed: Vec<&TranscriptVersionSegment> = segments
+        .iter()
+        .filter(|s| matches!((s.audio_start_time, s.audio_end_time), (Some(start), Some(end)) if end >= start))
+        .collect();
+    timed.sort_by(|a, b| a.audio_start_time.unwrap_or_default().total_cmp(&b.audio_start_time.unwrap_or_default()));
+
+    let mut names: Vec<String> = Vec::new();
+    let mut spans: Vec<(usize, f64, f64)> = Vec::with_capacity(timed.len());
+    let mut words = Vec::new();
+    let mut fillers = Vec::new();
+    let mut questions = Vec::new();
+    let mut intervals: Vec<Vec<(f64, f64)>> = Vec::new();
+
+    for segment in &timed {
+        let label = speaker_label(segment);
+        let speaker = match names.iter().position(|n| *n == label) {
+            Some(index) => index,
+            None => {
+                names.push(label);
+                words.push(0u64);
+                fillers.push(0u32);
+                questions.push(0u32);
+                intervals.push(Vec::new());
+                names.len() - 1
+            }
+        };
+        let (start, end) = (segment.audio_start_time.unwrap_or_default(), segment.audio_end_time.unwrap_or_default());
+        spans.push((speaker, start, end));
+        intervals[speaker].push((start, end));
+        words[speaker] += segment.text.split_whitespace().count() as u64;
+        fillers[speaker] += count_filler_words(&segment.text);
+        questions[speaker] += count_questions(&segment.text);
+    }
+
+    let turns = build_turns(&spans);
+    let mut turn_counts = vec![0u32; names.len()];
+    let mut longest = vec![0f64; names.len()];
+    for turn in &turns {
+        turn_counts[turn.speaker] += 1;
+        longest[turn.speaker] = longest[turn.speaker].max(turn.end - turn.start);
+    }
+
+    let mut interruptions = vec![0u32; names.len()];
+    let mut interrupted = vec![0u32; names.len()];
+    let mut overlap_count = 0;
+    let mut overlap_seconds = 0.0;
+    for (i, earlier) in turns.iter().enumerate() {
+        for later in turns[i + 1..].iter().take_while(|t| t.start < earlier.end) {
+            if later.speaker == earlier.speaker {
+                continue;
+            }
+            let overlap = earlier.end.min(later.end) - later.start;
+            if overlap < MIN_OVERLAP_SECONDS {
+                continue;
+            }
+            overlap_count += 1;
+            overlap_seconds += overlap;
+            if earlier.end < later.end {
+                interruptions[later.speaker] += 1;
+                interrupted[earlier.speaker] += 1;
+            }
+        }
+    }
+
+    let talk: Vec<f64> = intervals.iter().map(|i| union_seconds(i.clone())).collect();
+    let total_talk: f64 = talk.iter().sum();
+    let mut speakers: Vec<SpeakerMetrics> = (0..names.len())
+        .map(|n| SpeakerMetrics {
+            speaker: names[n].clone(),
+            talk_seconds: talk[n],
+            talk_share: if total_talk > 0.0 { talk[n] / total_talk } else { 0.0 },
+            words: words[n],
+            words_per_minute: per_minute(words[n], talk[n]),
+            turns: turn_counts[n],
+            longest_monologue_seconds: longest[n],
+            interruptions: interruptions[n],
+            interrupted: interrupted[n],
+            filler_words: fillers[n],
+            questions: questions[n],
+        })
+        .collect();
+    speakers.sort_by(|a, b| b.talk_seconds.total_cmp(&a.talk_seconds));
+
+    let first_start = spans.iter().map(|s| s.1).fold(f64::INFINITY, f64::min);
+    let last_end = spans.iter().map(|s| s.2).fold(f64::NEG_INFINITY, f64::max);
+    let duration_seconds = if spans.is_empty() { 0.0 } else { last_end - first_start };
+    let speech_seconds = union_seconds(spans.iter().map(|s| (s.1, s.2)).collect());
+    let total_words: u64 = words.iter().sum();
+
+    let longest_monologue = turns
+        .iter()
+        .max_by(|a, b| (a.end - a.start).total_cmp(&(b.end - b.start)))
+        .map(|turn| Monologue {
+            speaker: names[turn.speaker].clone(),
+            start_time: turn.start,
+            duration_seconds: turn.end - turn.start,
+        });
+
+    ConversationMetrics {
+        duration_seconds,
+        speech_seconds,
+        silence_ratio: if duration_seconds > 0.0 {
+            (1.0 - speech_seconds / duration_seconds).clamp(0.0, 1.0)
+        } else {
+            0.0
+        },
+        overlap_count,
+        overlap_seconds,
+        interruption_count: interruptions.iter().sum(),
+        words: total_words,
+        words_per_minute: per_minute(total_words, speech_seconds),
+        filler_word_count: fillers.iter().sum(),
+        question_count: questions.iter().sum(),
+        longest_monologue,
+        speakers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(speaker: Option<&str>, text: &str, start: f64, end: f64) -> TranscriptVersionSegment {
+        TranscriptVersionSegment {
+            speaker: speaker.map(String::from),
+            ..crate::database::models::segment(&format!("s-{}", start), text, start, end)
+        }
+    }
+
+    #[test]
+    fn test_filler_and_question_counts() {
+        assert_eq!(count_filler_words("Um, so, you know, it's uh basically done. I mean it."), 5);
+        assert_eq!(count_filler_words("Do you have the answer?"), 0);
+        assert_eq!(count_filler_words("The umbrella is here"), 0);
+        assert_eq!(count_questions("Ready? Really?? Yes."), 2);
+        assert_eq!(count_questions("No questions."), 0);
+    }
+
+    #[test]
+    fn test_talk_time_turns_and_silence() {
+        let segments = vec![
+            segment(Some("Alice"), "one two three four five six", 0.0, 4.0),
+            segment(Some("Alice"), "seven eight nine", 5.0, 8.0),
+            segment(Some("Bob"), "ten eleven twelve", 10.0, 12.0),
+            segment(Some("Alice"), "thirteen fourteen", 12.5, 14.0),
+            segment(None, "untimed", 0.0, 0.0),
+        ];
+        let metrics = compute_metrics(&segments[..4]);
+
+        assert_eq!(metrics.duration_seconds, 14.0);
+        assert_eq!(metrics.speech_seconds, 10.5);
+        assert!((metrics.silence_ratio - 3.5 / 14.0).abs() < 1e-9);
+        assert_eq!(metrics.words, 14);
+
+        let alice = &metrics.speakers[0];
+        assert_eq!(alice.speaker, "Alice");
+        assert_eq!(alice.talk_seconds, 8.5);
+        assert!((alice.talk_share - 8.5 / 10.5).abs() < 1e-9);
+        assert_eq!(alice.words, 11);
+        // 0-8 (pause of 1s is within the turn) and 12.5-14 after Bob spoke
+        assert_eq!(alice.turns, 2);
+        assert_eq!(alice.longest_monologue_seconds, 8.0);
+        assert_eq!(metrics.speakers[1].turns, 1);
+
+        let monologue = metrics.longest_monologue.unwrap();
+        assert_eq!((monologue.speaker.as_str(), monologue.start_time, monologue.duration_seconds), ("Alice", 0.0, 8.0));
+        assert_eq!(metrics.overlap_count, 0);
+
+        let with_unknown = compute_metrics(&segments);
+        assert!(with_unknown.speakers.iter().any(|s| s.speaker == UNKNOWN_SPEAKER));
+    }
+
+    #[test]
+    fn test_overlaps_and_interruptions() {
+        let segments = vec![
+            segment(Some("mic"), "I think we should ship", 0.0, 10.0),
+            // Backchannel inside the turn: overlap, not an interruption
+            segment(Some("system"), "right", 3.0, 3.5),
+            // Takes the floor while mic is still talking; mic stops first
+            segment(Some("system"), "wait, what about the tests?", 9.0, 15.0),
+            // Jitter below the overlap threshold
+            segment(Some("mic"), "good point", 14.9, 17.0),
+        ];
+        let metrics = compute_metrics(&segments);
+
+        assert_eq!(metrics.overlap_count, 2);
+        assert!((metrics.overlap_seconds - 1.5).abs() < 1e-9);
+        assert_eq!(metrics.interruption_count, 1);
+        let system = metrics.speakers.iter().find(|s| s.speaker == "system").unwrap();
+        let mic = metrics.speakers.iter().find(|s| s.speaker == "mic").unwrap();
+        assert_eq!((system.interruptions, system.interrupted), (1, 0));
+        assert_eq!((mic.interruptions, mic.interrupted), (0, 1));
+        assert_eq!(system.questions, 1);
+    }
+
+    #[test]
+    fn test_empty_transcript() {
+        let metrics = compute_metrics(&[]);
+        assert_eq!(metrics.duration_seconds, 0.0);
+        assert_eq!(metrics.silence_ratio, 0.0);
+        assert!(metrics.speakers.is_empty());
+        assert!(metrics.longest_monologue.is_none());
+    }
+}
@@ -0,0 +1,16 @@
+/// Conversation analytics module - how a meeting went, computed from its transcript
+///
+/// This module contains:
+/// - Metrics per meeting and per speaker: talk time and share, words per minute, turns and
+///   longest monologue, overlaps and interruptions, silence ratio, filler words and questions
+/// - Service layer that stores metrics per meeting (recomputed when the transcript changes)
+///   and collects a speaker's metrics across meetings for trends
+/// - Tauri commands for reading meeting analytics and speaker trends
+///
+/// Metrics are stored as JSON per meeting in the `meeting_analytics` table.
+
+pub mod commands;
+pub mod metrics;
+pub mod service;
+
+pub use service::ConversationAnalyticsService;
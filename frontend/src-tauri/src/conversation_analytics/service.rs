@@ -0,0 +1,124 @@
+use crate::conversation_analytics::metrics::{compute_metrics, ConversationMetrics, SpeakerMetrics};
+use crate::database::models::{MeetingModel, TranscriptVersionSegment};
+use crate::database::repositories::{
+    conversation_analytics::ConversationAnalyticsRepository, meeting::MeetingsRepository,
+    transcript_version::TranscriptVersionsRepository,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use tracing::{info, warn};
+
+/// One speaker's metrics in one meeting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerTrendPoint {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub meeting_date: chrono::DateTime<chrono::Utc>,
+    pub metrics: SpeakerMetrics,
+}
+
+/// Conversation analytics service - computes, stores and aggregates meeting metrics
+pub struct ConversationAnalyticsService;
+
+impl ConversationAnalyticsService {
+    /// Analytics of a meeting. Stored analytics are returned while the transcript is unchanged;
+    /// otherwise (or when `recompute` is set) they are computed and stored again.
+    pub async fn analytics_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        recompute: bool,
+    ) -> Result<ConversationMetrics, String> {
+        let segments = TranscriptVersionsRepository::get_current_segments(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load transcript: {}", e))?;
+        let hash = transcript_hash(&segments);
+
+        if !recompute {
+            let stored = ConversationAnalyticsRepository::get_analytics(pool, meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load analytics: {}", e))?;
+            if let Some(stored) = stored.filter(|a| a.transcript_hash == hash) {
+                match serde_json::from_str::<ConversationMetrics>(&stored.metrics) {
+                    Ok(metrics) => return Ok(metrics),
+                    Err(e) => warn!("Stored analytics of meeting {} are unreadable, recomputing: {}", meeting_id, e),
+                }
+            }
+        }
+
+        let metrics = compute_metrics(&segments);
+        let json = serde_json::to_string(&metrics).map_err(|e| format!("Failed to serialize analytics: {}", e))?;
+        ConversationAnalyticsRepository::save_analytics(pool, meeting_id, &json, &hash)
+            .await
+            .map_err(|e| format!("Failed to save analytics: {}", e))?;
+        info!(
+            "Computed conversation analytics for meeting {} ({} speakers)",
+            meeting_id,
+            metrics.speakers.len()
+        );
+        Ok(metrics)
+    }
+
+    /// Metrics of one speaker across all meetings they spoke in, oldest meeting first.
+    /// Speaker names match case-insensitively.
+    pub async fn speaker_trend(pool: &SqlitePool, speaker: &str) -> Result<Vec<SpeakerTrendPoint>, String> {
+        let wanted = speaker.trim().to_lowercase();
+        let mut points = Vec::new();
+        for meeting in Self::meetings_oldest_first(pool).await? {
+            let metrics = Self::analytics_for_meeting(pool, &meeting.id, false).await?;
+            if let Some(speaker_metrics) = metrics
+                .speakers
+                .into_iter()
+                .find(|s| s.speaker.to_lowercase() == wanted)
+            {
+                points.push(SpeakerTrendPoint {
+                    meeting_id: meeting.id,
+                    meeting_title: meeting.title,
+                    meeting_date: meeting.created_at.0,
+                    metrics: speaker_metrics,
+                });
+            }
+        }
+        Ok(points)
+    }
+
+    /// Speaker names across all meetings, the one in most meetings first
+    pub async fn speakers(pool: &SqlitePool) -> Result<Vec<String>, String> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for meeting in Self::meetings_oldest_first(pool).await? {
+            let metrics = Self::analytics_for_meeting(pool, &meeting.id, false).await?;
+            for speaker in metrics.speakers {
+                match counts.iter_mut().find(|(name, _)| name.to_lowercase() == speaker.speaker.to_lowercase()) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((speaker.speaker, 1)),
+                }
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(counts.into_iter().map(|(name, _)| name).collect())
+    }
+
+    async fn meetings_oldest_first(pool: &SqlitePool) -> Result<Vec<MeetingModel>, String> {
+        let mut meetings = MeetingsRepository::get_meetings(pool)
+            .await
+            .map_err(|e| format!("Failed to load meetings: {}", e))?;
+        meetings.reverse();
+        Ok(meetings)
+    }
+}
+
+/// SHA-256 over the fields the metrics depend on, as lowercase hex
+fn transcript_hash(segments: &[TranscriptVersionSegment]) -> String {
+    let mut hasher = Sha256::new();
+    for segment in segments {
+        hasher.update(segment.id.as_bytes());
+        hasher.update([0]);
+        hasher.update(segment.text.as_bytes());
+        hasher.update([0]);
+        hasher.update(format!("{:?}|{:?}", segment.audio_start_time, segment.audio_end_time).as_bytes());
+        hasher.update([0]);
+        hasher.update(segment.speaker.as_deref().unwrap_or_default().as_bytes());
+        hasher.update([0xff]);
+    }
+    format!("{:x}", hasher.finalize())
+}
@@ -162,6 +162,7 @@ pub async fn import_and_initialize_database(
 
     // Update app state with the new manager
     app.manage(AppState { db_manager });
+    crate::start_background_tasks(&app);
 
     info!("Legacy database imported and initialized successfully");
 
@@ -186,6 +187,7 @@ pub async fn initialize_fresh_database(app: AppHandle) -> Result<(), String> {
 
     // Update app state with the new manager
     app.manage(AppState { db_manager: db_manager.clone() });
+    crate::start_background_tasks(&app);
 
     // Set default model configuration for fresh installs
     let pool = db_manager.pool();
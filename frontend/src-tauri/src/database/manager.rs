@@ -30,10 +30,19 @@ impl DatabaseManager {
             }
         }
 
-        let pool = SqlitePool::connect(tauri_db_path).await?;
+        // Encrypted library: convert a plaintext database once, then open it with the library key
+        crate::encryption::database::encrypt_plaintext_database(tauri_db_path).await?;
+        let pool = SqlitePool::connect_with(crate::encryption::database::connect_options(tauri_db_path)?).await?;
 
         sqlx::migrate!("./migrations").run(&pool).await?;
 
+        // Move plaintext API keys left by older versions into the secret store
+        match crate::database::repositories::setting::SettingsRepository::migrate_api_keys_to_secrets(&pool).await {
+            Ok(0) => {}
+            Ok(moved) => log::info!("Moved {} API keys from the database into the secret store", moved),
+            Err(e) => log::warn!("API key migration to the secret store incomplete: {}", e),
+        }
+
         Ok(DatabaseManager { pool })
     }
 
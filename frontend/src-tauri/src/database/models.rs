@@ -35,6 +35,9 @@ pub struct Transcript {
     pub audio_start_time: Option<f64>,
     pub audio_end_time: Option<f64>,
     pub duration: Option<f64>,
+    // Per-segment language identification
+    pub language: Option<String>,
+    pub translation: Option<String>,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -54,6 +57,276 @@ pub struct SummaryProcess {
     pub result_backup_timestamp: Option<chrono::DateTime<chrono::Utc>>, // When backup was created
 }
 
+/// Transcript produced by a background pass (e.g. post-recording quality pass)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptVersion {
+    pub id: String,
+    pub meeting_id: String,
+    pub source: String, // "quality_pass"
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub status: String, // pending | accepted | discarded | reverted | superseded
+    pub segments: String, // JSON array of TranscriptVersionSegment
+    pub previous_segments: Option<String>, // JSON, captured on accept for revert
+    pub stats: Option<String>, // JSON alignment stats
+    pub base_segment_ids: Option<String>, // JSON, ids of the transcript the version was computed from
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Full transcripts row as stored in (and restored from) a transcript version
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct TranscriptVersionSegment {
+    pub id: String,
+    pub text: String,
+    pub timestamp: String,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+    pub duration: Option<f64>,
+    pub speaker: Option<String>,
+    pub edited_at: Option<String>,
+    // Versions stored before language identification existed have no language fields
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub translation: Option<String>,
+    /// Word timings (JSON) from re-alignment, valid for this text and these times
+    #[serde(default)]
+    pub word_timings: Option<String>,
+}
+
+/// Test fixture: an unedited segment timed `start..end`
+#[cfg(test)]
+pub fn segment(id: &str, text: &str, start: f64, end: f64) -> TranscriptVersionSegment {
+    TranscriptVersionSegment {
+        id: id.to_string(),
+        text: text.to_string(),
+        timestamp: String::new(),
+        audio_start_time: Some(start),
+        audio_end_time: Some(end),
+        duration: Some(end - start),
+        speaker: None,
+        edited_at: None,
+        language: None,
+        translation: None,
+        word_timings: None,
+    }
+}
+
+/// One segment-level change to a meeting's transcript
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptRevision {
+    pub id: String,
+    pub meeting_id: String,
+    pub operation: String, // edit | insert | delete | split | join | realign
+    pub segment_ids: String, // JSON array of affected segment ids
+    pub before_segments: String, // JSON TranscriptVersionSegment rows before the change
+    pub after_segments: String, // JSON TranscriptVersionSegment rows after the change
+    pub author: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Translated transcript/summary track of a meeting in one target language
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingTranslation {
+    pub meeting_id: String,
+    pub language: String,
+    pub status: String, // running | completed | failed | cancelled
+    pub provider: String,
+    pub model: String,
+    pub segments: Option<String>, // JSON array of TranslatedSegment
+    pub summary: Option<String>,  // Translated summary markdown
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// One transcript segment of a translation track; id and timing match the original segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslatedSegment {
+    pub id: String,
+    pub text: String,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+}
+
+/// Topic chapter of a meeting; times are recording seconds
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct MeetingChapter {
+    pub id: String,
+    pub meeting_id: String,
+    pub chapter_index: i64,
+    pub title: String,
+    pub synopsis: Option<String>,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Bookmark set during recording; `time_seconds` is relative to the recording's audio
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct MeetingMarker {
+    pub id: String,
+    pub meeting_id: String,
+    pub time_seconds: f64,
+    pub label: Option<String>,
+    pub sequence_id: Option<i64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Rolling summary written during recording, kept to seed the final summary
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingLiveSummary {
+    pub meeting_id: String,
+    pub summary: String,
+    pub covered_until: f64,
+    pub updates: i64,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Stored conversation analytics of a meeting; `metrics` is the JSON of
+/// `conversation_analytics::metrics::ConversationMetrics`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingAnalytics {
+    pub meeting_id: String,
+    pub metrics: String,
+    pub transcript_hash: String,
+    pub computed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Audit record of one redacted LLM request; `entities` is the JSON of
+/// `Vec<summary::redaction::RedactedEntity>`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RedactionAudit {
+    pub id: String,
+    pub meeting_id: Option<String>,
+    /// "summary" or "live_summary"
+    pub purpose: String,
+    pub provider: String,
+    pub model: String,
+    pub ner_model: Option<String>,
+    pub entity_count: i64,
+    pub entities: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Link from a redacted copy of a meeting to its original; `spans` is the JSON of
+/// `Vec<audio::redacted_copy::TranscriptSpan>` without the redacted text
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RedactedCopy {
+    pub meeting_id: String,
+    pub source_meeting_id: String,
+    pub spans: String,
+    /// "mute" or "bleep"
+    pub audio_mode: String,
+    /// "mask", "regenerate" or "none"
+    pub summary_mode: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A meeting as seen by the retention rules and the storage breakdown
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingRetentionInfo {
+    pub id: String,
+    pub title: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub folder_path: Option<String>,
+    pub starred: bool,
+}
+
+/// Sync bookkeeping of one record of a meeting; `revision` is the JSON of
+/// `sync::protocol::RevisionVector`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SyncRecordRow {
+    /// "meeting", "transcript", "summary", "notes" or "audio"
+    pub kind: String,
+    pub meeting_id: String,
+    pub revision: String,
+    pub content_hash: Option<String>,
+    pub modified_at: chrono::DateTime<chrono::Utc>,
+    /// Device that made the last change
+    pub modified_by: String,
+    pub deleted: bool,
+    pub dirty: bool,
+    pub source_stamp: Option<String>,
+}
+
+/// Summary or notes edited on two devices at once; `remote_record` is the JSON of the
+/// server's `sync::protocol::SyncRecord`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SyncConflictRow {
+    pub kind: String,
+    pub meeting_id: String,
+    pub local_content: Option<String>,
+    pub remote_record: String,
+    pub detected_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Directory watched for new recordings, with the options applied to its imports
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ImportWatchFolder {
+    pub id: String,
+    pub path: String,
+    pub enabled: bool,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub options: ImportFolderOptions,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Per-folder import options; unset values fall back to the app defaults
+#[derive(Debug, Clone, Default, FromRow, Serialize, Deserialize)]
+pub struct ImportFolderOptions {
+    pub language: Option<String>,
+    pub transcription_provider: Option<String>,
+    pub transcription_model: Option<String>,
+    /// Summarize each import with this template when set
+    pub template_id: Option<String>,
+    pub summary_provider: Option<String>,
+    pub summary_model: Option<String>,
+}
+
+/// File waiting in (or processed by) the persistent import queue
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ImportQueueItem {
+    pub id: String,
+    pub source_path: String,
+    pub content_hash: String,
+    pub watch_folder_id: Option<String>,
+    pub status: String, // queued | processing | completed | failed
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub meeting_id: Option<String>,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Custom vocabulary term; a missing meeting_id means the term is user-wide
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct VocabularyTerm {
+    pub id: String,
+    pub meeting_id: Option<String>,
+    pub term: String,
+    pub sounds_like: Option<String>, // JSON array of misrecognitions
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl VocabularyTerm {
+    pub fn sounds_like_list(&self) -> Vec<String> {
+        self.sounds_like
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct TranscriptChunk {
     pub meeting_id: String,
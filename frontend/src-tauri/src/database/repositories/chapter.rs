@@ -0,0 +1,65 @@
+use crate::database::models::MeetingChapter;
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct ChaptersRepository;
+
+impl ChaptersRepository {
+    /// Chapters of a meeting in order
+    pub async fn get_chapters(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<MeetingChapter>, SqlxError> {
+        sqlx::query_as::<_, MeetingChapter>(
+            "SELECT * FROM meeting_chapters WHERE meeting_id = ? ORDER BY chapter_index ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace all chapters of a meeting with a new segmentation
+    pub async fn replace_chapters(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        chapters: &[MeetingChapter],
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query("DELETE FROM meeting_chapters WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        for chapter in chapters {
+            sqlx::query(
+                "INSERT INTO meeting_chapters (id, meeting_id, chapter_index, title, synopsis, start_time, end_time,
+                    provider, model, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&chapter.id)
+            .bind(meeting_id)
+            .bind(chapter.chapter_index)
+            .bind(&chapter.title)
+            .bind(&chapter.synopsis)
+            .bind(chapter.start_time)
+            .bind(chapter.end_time)
+            .bind(&chapter.provider)
+            .bind(&chapter.model)
+            .bind(chapter.created_at)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        info!("Saved {} chapters for meeting {}", chapters.len(), meeting_id);
+        Ok(())
+    }
+
+    pub async fn delete_chapters(pool: &SqlitePool, meeting_id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM meeting_chapters WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
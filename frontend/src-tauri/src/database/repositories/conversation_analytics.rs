@@ -0,0 +1,51 @@
+use crate::database::models::MeetingAnalytics;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct ConversationAnalyticsRepository;
+
+impl ConversationAnalyticsRepository {
+    pub async fn get_analytics(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingAnalytics>, SqlxError> {
+        sqlx::query_as::<_, MeetingAnalytics>("SELECT * FROM meeting_analytics WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Insert or replace the analytics of a meeting
+    pub async fn save_analytics(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        metrics: &str,
+        transcript_hash: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO meeting_analytics (meeting_id, metrics, transcript_hash, computed_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(meeting_id) DO UPDATE SET
+                metrics = excluded.metrics, transcript_hash = excluded.transcript_hash,
+                computed_at = excluded.computed_at",
+        )
+        .bind(meeting_id)
+        .bind(metrics)
+        .bind(transcript_hash)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        info!("Saved conversation analytics for meeting {}", meeting_id);
+        Ok(())
+    }
+
+    pub async fn delete_analytics(pool: &SqlitePool, meeting_id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM meeting_analytics WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,282 @@
+use crate::database::models::{ImportFolderOptions, ImportQueueItem, ImportWatchFolder};
+use chrono::{DateTime, Utc};
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+use uuid::Uuid;
+
+pub struct ImportQueueRepository;
+
+impl ImportQueueRepository {
+    pub async fn list_watch_folders(pool: &SqlitePool) -> Result<Vec<ImportWatchFolder>, SqlxError> {
+        sqlx::query_as::<_, ImportWatchFolder>("SELECT * FROM import_watch_folders ORDER BY path")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_watch_folder(
+        pool: &SqlitePool,
+        id: &str,
+    ) -> Result<Option<ImportWatchFolder>, SqlxError> {
+        sqlx::query_as::<_, ImportWatchFolder>("SELECT * FROM import_watch_folders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Adds a watched folder, or updates the options of an already watched path
+    pub async fn upsert_watch_folder(
+        pool: &SqlitePool,
+        path: &str,
+        options: &ImportFolderOptions,
+    ) -> Result<ImportWatchFolder, SqlxError> {
+        let now = Utc::now();
+
+        sqlx::query(
+            "INSERT INTO import_watch_folders (id, path, enabled, language, transcription_provider,
+                transcription_model, template_id, summary_provider, summary_model, created_at, updated_at)
+             VALUES (?, ?, 1, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(path) DO UPDATE SET
+                language = excluded.language,
+                transcription_provider = excluded.transcription_provider,
+                transcription_model = excluded.transcription_model,
+                template_id = excluded.template_id,
+                summary_provider = excluded.summary_provider,
+                summary_model = excluded.summary_model,
+                updated_at = excluded.updated_at",
+        )
+        .bind(format!("watch-{}", Uuid::new_v4()))
+        .bind(path)
+        .bind(&options.language)
+        .bind(&options.transcription_provider)
+        .bind(&options.transcription_model)
+        .bind(&options.template_id)
+        .bind(&options.summary_provider)
+        .bind(&options.summary_model)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        info!("Watching import folder {}", path);
+        sqlx::query_as::<_, ImportWatchFolder>("SELECT * FROM import_watch_folders WHERE path = ?")
+            .bind(path)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Returns false when the folder does not exist
+    pub async fn set_watch_folder_enabled(
+        pool: &SqlitePool,
+        id: &str,
+        enabled: bool,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query("UPDATE import_watch_folders SET enabled = ?, updated_at = ? WHERE id = ?")
+            .bind(enabled)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Stops watching a folder; queue items already found in it are kept
+    pub async fn remove_watch_folder(pool: &SqlitePool, id: &str) -> Result<bool, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query("UPDATE import_queue SET watch_folder_id = NULL WHERE watch_folder_id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        let result = sqlx::query("DELETE FROM import_watch_folders WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Queues a file; returns None when a file with the same content hash was queued before
+    pub async fn enqueue(
+        pool: &SqlitePool,
+        source_path: &str,
+        content_hash: &str,
+        watch_folder_id: Option<&str>,
+    ) -> Result<Option<ImportQueueItem>, SqlxError> {
+        let id = format!("import-{}", Uuid::new_v4());
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO import_queue (id, source_path, content_hash, watch_folder_id, status,
+                attempts, next_attempt_at, created_at, updated_at)
+             VALUES (?, ?, ?, ?, 'queued', 0, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(source_path)
+        .bind(content_hash)
+        .bind(watch_folder_id)
+        .bind(now)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        info!("Queued {} for import", source_path);
+        Self::get_item(pool, &id).await
+    }
+
+    pub async fn get_item(pool: &SqlitePool, id: &str) -> Result<Option<ImportQueueItem>, SqlxError> {
+        sqlx::query_as::<_, ImportQueueItem>("SELECT * FROM import_queue WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Most recent items first
+    pub async fn list_items(pool: &SqlitePool, limit: i64) -> Result<Vec<ImportQueueItem>, SqlxError> {
+        sqlx::query_as::<_, ImportQueueItem>("SELECT * FROM import_queue ORDER BY created_at DESC LIMIT ?")
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Oldest queued item whose retry time has come
+    pub async fn next_ready(pool: &SqlitePool) -> Result<Option<ImportQueueItem>, SqlxError> {
+        sqlx::query_as::<_, ImportQueueItem>(
+            "SELECT * FROM import_queue WHERE status = 'queued' AND next_attempt_at <= ?
+             ORDER BY created_at LIMIT 1",
+        )
+        .bind(Utc::now())
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn mark_processing(pool: &SqlitePool, id: &str) -> Result<(), SqlxError> {
+        sqlx::query("UPDATE import_queue SET status = 'processing', updated_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn mark_completed(pool: &SqlitePool, id: &str, meeting_id: &str) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE import_queue SET status = 'completed', meeting_id = ?, last_error = NULL, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(meeting_id)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Records a failed attempt. With `retry_at` the item is queued again for that time,
+    /// otherwise it is marked failed for good.
+    pub async fn mark_attempt_failed(
+        pool: &SqlitePool,
+        id: &str,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), SqlxError> {
+        let now = Utc::now();
+        let status = if retry_at.is_some() { "queued" } else { "failed" };
+
+        sqlx::query(
+            "UPDATE import_queue SET status = ?, attempts = attempts + 1, last_error = ?,
+                next_attempt_at = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(status)
+        .bind(error)
+        .bind(retry_at.unwrap_or(now))
+        .bind(now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Puts an item back in the queue without counting an attempt (e.g. the engine was busy)
+    pub async fn defer(pool: &SqlitePool, id: &str, retry_at: DateTime<Utc>) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE import_queue SET status = 'queued', next_attempt_at = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(retry_at)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Requeues a failed item with a fresh attempt budget; returns false if it is not failed
+    pub async fn retry_item(pool: &SqlitePool, id: &str) -> Result<bool, SqlxError> {
+        let now = Utc::now();
+        let result = sqlx::query(
+            "UPDATE import_queue SET status = 'queued', attempts = 0, next_attempt_at = ?, updated_at = ?
+             WHERE id = ? AND status = 'failed'",
+        )
+        .bind(now)
+        .bind(now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Removes an item that is not being processed. Removing it also forgets its content hash,
+    /// so the same file will be picked up again if it is still in a watched folder.
+    pub async fn remove_item(pool: &SqlitePool, id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM import_queue WHERE id = ? AND status != 'processing'")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Items left in processing by a previous run go back to the queue
+    pub async fn requeue_interrupted(pool: &SqlitePool) -> Result<u64, SqlxError> {
+        let now = Utc::now();
+        let result = sqlx::query(
+            "UPDATE import_queue SET status = 'queued', next_attempt_at = ?, updated_at = ?
+             WHERE status = 'processing'",
+        )
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn set_meeting_import_source(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        source: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query("UPDATE meetings SET import_source = ? WHERE id = ?")
+            .bind(source)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_meeting_import_source(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<String>, SqlxError> {
+        let source: Option<Option<String>> =
+            sqlx::query_scalar("SELECT import_source FROM meetings WHERE id = ?")
+                .bind(meeting_id)
+                .fetch_optional(pool)
+                .await?;
+        Ok(source.flatten())
+    }
+}
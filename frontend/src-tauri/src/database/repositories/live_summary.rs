@@ -0,0 +1,43 @@
+use crate::database::models::MeetingLiveSummary;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct LiveSummariesRepository;
+
+impl LiveSummariesRepository {
+    pub async fn get_live_summary(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingLiveSummary>, SqlxError> {
+        sqlx::query_as::<_, MeetingLiveSummary>("SELECT * FROM meeting_live_summaries WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Insert or replace the rolling summary of a meeting
+    pub async fn save_live_summary(pool: &SqlitePool, live_summary: &MeetingLiveSummary) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO meeting_live_summaries (meeting_id, summary, covered_until, updates, provider, model, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(meeting_id) DO UPDATE SET
+                summary = excluded.summary, covered_until = excluded.covered_until, updates = excluded.updates,
+                provider = excluded.provider, model = excluded.model, created_at = excluded.created_at",
+        )
+        .bind(&live_summary.meeting_id)
+        .bind(&live_summary.summary)
+        .bind(live_summary.covered_until)
+        .bind(live_summary.updates)
+        .bind(&live_summary.provider)
+        .bind(&live_summary.model)
+        .bind(live_summary.created_at)
+        .execute(pool)
+        .await?;
+
+        info!(
+            "Saved live summary for meeting {} (covers {:.0}s)",
+            live_summary.meeting_id, live_summary.covered_until
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::database::models::MeetingMarker;
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct MarkersRepository;
+
+impl MarkersRepository {
+    /// Markers of a meeting in recording order
+    pub async fn get_markers(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<MeetingMarker>, SqlxError> {
+        sqlx::query_as::<_, MeetingMarker>(
+            "SELECT * FROM meeting_markers WHERE meeting_id = ? ORDER BY time_seconds ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Store markers of a meeting; markers that already exist (same id) are left unchanged
+    pub async fn save_markers(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        markers: &[MeetingMarker],
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        for marker in markers {
+            sqlx::query(
+                "INSERT OR IGNORE INTO meeting_markers (id, meeting_id, time_seconds, label, sequence_id, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&marker.id)
+            .bind(meeting_id)
+            .bind(marker.time_seconds)
+            .bind(&marker.label)
+            .bind(marker.sequence_id)
+            .bind(marker.created_at)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        info!("Saved {} markers for meeting {}", markers.len(), meeting_id);
+        Ok(())
+    }
+
+    pub async fn update_marker_label(
+        pool: &SqlitePool,
+        marker_id: &str,
+        label: Option<&str>,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query("UPDATE meeting_markers SET label = ? WHERE id = ?")
+            .bind(label)
+            .bind(marker_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_marker(pool: &SqlitePool, marker_id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM meeting_markers WHERE id = ?")
+            .bind(marker_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::api::{MeetingDetails, MeetingTranscript};
-use crate::database::models::{MeetingModel, Transcript};
+use crate::database::models::{MeetingModel, MeetingRetentionInfo, Transcript};
 use chrono::Utc;
 use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
 use tracing::{error, info};
@@ -92,6 +92,8 @@ impl MeetingsRepository {
                     audio_start_time: t.audio_start_time,
                     audio_end_time: t.audio_end_time,
                     duration: t.duration,
+                    language: t.language,
+                    translation: t.translation,
                 })
                 .collect::<Vec<_>>();
 
@@ -228,6 +230,422 @@ impl MeetingsRepository {
         transaction.commit().await?;
         Ok(true)
     }
+
+    /// Stars or unstars a meeting; returns false if the meeting does not exist
+    pub async fn set_starred(pool: &SqlitePool, meeting_id: &str, starred: bool) -> Result<bool, SqlxError> {
+        let result = sqlx::query("UPDATE meetings SET starred = ? WHERE id = ?")
+            .bind(starred)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_starred_meeting_ids(pool: &SqlitePool) -> Result<Vec<String>, SqlxError> {
+        sqlx::query_scalar("SELECT id FROM meetings WHERE starred = 1 ORDER BY created_at DESC")
+            .fetch_all(pool)
+            .await
+    }
+
+    /// All meetings with their folder and star, oldest first
+    pub async fn get_retention_info(pool: &SqlitePool) -> Result<Vec<MeetingRetentionInfo>, SqlxError> {
+        sqlx::query_as::<_, MeetingRetentionInfo>(
+            "SELECT id, title, created_at, folder_path, starred FROM meetings ORDER BY created_at ASC",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Points a meeting at a new folder (e.g. after a restore on another machine)
+    pub async fn update_folder_path(pool: &SqlitePool, meeting_id: &str, folder_path: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query("UPDATE meetings SET folder_path = ? WHERE id = ?")
+            .bind(folder_path)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Notes of a meeting as (markdown, editor JSON)
+    pub async fn get_meeting_notes(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<(Option<String>, Option<String>)>, SqlxError> {
+        sqlx::query_as("SELECT notes_markdown, notes_json FROM meeting_notes WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Merges `sources` (meeting id, offset in seconds of its audio in the merged recording)
+    /// into `target_id` and deletes the source meetings, all in one transaction.
+    /// Transcripts and markers are moved and re-offset, per-meeting vocabulary follows its
+    /// transcripts, and the notes are replaced by `notes` (already merged by the caller).
+    pub async fn merge_meetings(
+        pool: &SqlitePool,
+        target_id: &str,
+        title: &str,
+        sources: &[(String, f64)],
+        notes: Option<(Option<String>, Option<String>)>,
+    ) -> Result<(), SqlxError> {
+        let mut transaction = pool.begin().await?;
+        let now = Utc::now();
+
+        for (source_id, offset) in sources {
+            shift_word_timings(&mut transaction, "meeting_id = ?", source_id, None, *offset).await?;
+            sqlx::query(
+                "UPDATE transcripts SET meeting_id = ?,
+                    audio_start_time = audio_start_time + ?, audio_end_time = audio_end_time + ?
+                 WHERE meeting_id = ?",
+            )
+            .bind(target_id)
+            .bind(offset)
+            .bind(offset)
+            .bind(source_id)
+            .execute(&mut *transaction)
+            .await?;
+
+            sqlx::query("UPDATE vocabulary_terms SET meeting_id = ? WHERE meeting_id = ?")
+                .bind(target_id)
+                .bind(source_id)
+                .execute(&mut *transaction)
+                .await?;
+
+            sqlx::query(
+                "UPDATE meeting_markers SET meeting_id = ?, time_seconds = time_seconds + ? WHERE meeting_id = ?",
+            )
+            .bind(target_id)
+            .bind(offset)
+            .bind(source_id)
+            .execute(&mut *transaction)
+            .await?;
+
+            sqlx::query("DELETE FROM meeting_notes WHERE meeting_id = ?")
+                .bind(source_id)
+                .execute(&mut *transaction)
+                .await?;
+
+            if !delete_meeting_with_transaction(&mut transaction, source_id).await? {
+                return Err(SqlxError::RowNotFound);
+            }
+        }
+
+        if let Some((markdown, json)) = notes {
+            sqlx::query(
+                "INSERT INTO meeting_notes (meeting_id, notes_markdown, notes_json, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?)
+                 ON CONFLICT(meeting_id) DO UPDATE SET
+                    notes_markdown = excluded.notes_markdown,
+                    notes_json = excluded.notes_json,
+                    updated_at = excluded.updated_at",
+            )
+            .bind(target_id)
+            .bind(markdown)
+            .bind(json)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        let updated = sqlx::query("UPDATE meetings SET title = ?, updated_at = ? WHERE id = ?")
+            .bind(title)
+            .bind(now)
+            .bind(target_id)
+            .execute(&mut *transaction)
+            .await?;
+        if updated.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+
+        invalidate_derived_data(&mut transaction, target_id).await?;
+        transaction.commit().await?;
+
+        info!("Merged {} meeting(s) into {}", sources.len(), target_id);
+        Ok(())
+    }
+
+    /// Moves everything from `at_seconds` on into a new meeting (timestamps shifted to start at 0).
+    /// Segments and markers before the split point stay; the notes are copied to both parts.
+    pub async fn split_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        at_seconds: f64,
+        new_meeting_id: &str,
+        new_title: &str,
+        new_folder_path: &str,
+    ) -> Result<(), SqlxError> {
+        let mut transaction = pool.begin().await?;
+        let now = Utc::now();
+
+        // The second part starts `at_seconds` into the original recording
+        let created_at: Option<chrono::DateTime<Utc>> =
+            sqlx::query_scalar("SELECT created_at FROM meetings WHERE id = ?")
+                .bind(meeting_id)
+                .fetch_optional(&mut *transaction)
+                .await?;
+        let created_at = created_at.ok_or(SqlxError::RowNotFound)?
+            + chrono::Duration::milliseconds((at_seconds * 1000.0) as i64);
+
+        sqlx::query(
+            "INSERT INTO meetings (id, title, created_at, updated_at, folder_path) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(new_meeting_id)
+        .bind(new_title)
+        .bind(created_at)
+        .bind(now)
+        .bind(new_folder_path)
+        .execute(&mut *transaction)
+        .await?;
+
+        shift_word_timings(
+            &mut transaction,
+            "meeting_id = ? AND audio_start_time >= ?",
+            meeting_id,
+            Some(at_seconds),
+            -at_seconds,
+        )
+        .await?;
+        sqlx::query(
+            "UPDATE transcripts SET meeting_id = ?,
+                audio_start_time = audio_start_time - ?, audio_end_time = audio_end_time - ?
+             WHERE meeting_id = ? AND audio_start_time >= ?",
+        )
+        .bind(new_meeting_id)
+        .bind(at_seconds)
+        .bind(at_seconds)
+        .bind(meeting_id)
+        .bind(at_seconds)
+        .execute(&mut *transaction)
+        .await?;
+
+        // Segments running past the split point end with the first part's audio
+        sqlx::query(
+            "UPDATE transcripts SET audio_end_time = ?, duration = ? - audio_start_time
+             WHERE meeting_id = ? AND audio_end_time > ?",
+        )
+        .bind(at_seconds)
+        .bind(at_seconds)
+        .bind(meeting_id)
+        .bind(at_seconds)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query(
+            "UPDATE meeting_markers SET meeting_id = ?, time_seconds = time_seconds - ?
+             WHERE meeting_id = ? AND time_seconds >= ?",
+        )
+        .bind(new_meeting_id)
+        .bind(at_seconds)
+        .bind(meeting_id)
+        .bind(at_seconds)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO meeting_notes (meeting_id, notes_markdown, notes_json, created_at, updated_at)
+             SELECT ?, notes_markdown, notes_json, ?, ? FROM meeting_notes WHERE meeting_id = ?",
+        )
+        .bind(new_meeting_id)
+        .bind(now)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        invalidate_derived_data(&mut transaction, meeting_id).await?;
+        transaction.commit().await?;
+
+        info!(
+            "Split meeting {} at {:.2}s into new meeting {}",
+            meeting_id, at_seconds, new_meeting_id
+        );
+        Ok(())
+    }
+
+    /// Keeps only `start_seconds..end_seconds` of a meeting's transcript, shifted to start at 0.
+    /// Segments partly inside the range are clipped to it; markers outside it are dropped.
+    /// A segment without an end time counts as an instant at its start; segments without a
+    /// start time have no place on the timeline and are kept unchanged.
+    pub async fn trim_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        start_seconds: f64,
+        end_seconds: f64,
+    ) -> Result<(), SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query(
+            "DELETE FROM transcripts WHERE meeting_id = ? AND audio_start_time IS NOT NULL AND (
+                audio_start_time >= ?
+                OR (audio_end_time IS NOT NULL AND audio_end_time <= ?)
+                OR (audio_end_time IS NULL AND audio_start_time < ?)
+             )",
+        )
+        .bind(meeting_id)
+        .bind(end_seconds)
+        .bind(start_seconds)
+        .bind(start_seconds)
+        .execute(&mut *transaction)
+        .await?;
+
+        shift_word_timings(
+            &mut transaction,
+            "meeting_id = ? AND audio_start_time IS NOT NULL",
+            meeting_id,
+            None,
+            -start_seconds,
+        )
+        .await?;
+        sqlx::query(
+            "UPDATE transcripts SET
+                audio_start_time = MAX(audio_start_time, ?) - ?,
+                audio_end_time = MIN(audio_end_time, ?) - ?,
+                duration = MIN(audio_end_time, ?) - MAX(audio_start_time, ?)
+             WHERE meeting_id = ? AND audio_start_time IS NOT NULL AND audio_end_time IS NOT NULL",
+        )
+        .bind(start_seconds)
+        .bind(start_seconds)
+        .bind(end_seconds)
+        .bind(start_seconds)
+        .bind(end_seconds)
+        .bind(start_seconds)
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query(
+            "UPDATE transcripts SET audio_start_time = audio_start_time - ?
+             WHERE meeting_id = ? AND audio_start_time IS NOT NULL AND audio_end_time IS NULL",
+        )
+        .bind(start_seconds)
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query("DELETE FROM meeting_markers WHERE meeting_id = ? AND (time_seconds < ? OR time_seconds > ?)")
+            .bind(meeting_id)
+            .bind(start_seconds)
+            .bind(end_seconds)
+            .execute(&mut *transaction)
+            .await?;
+
+        sqlx::query("UPDATE meeting_markers SET time_seconds = time_seconds - ? WHERE meeting_id = ?")
+            .bind(start_seconds)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let updated = sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        if updated.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+
+        invalidate_derived_data(&mut transaction, meeting_id).await?;
+        transaction.commit().await?;
+
+        info!(
+            "Trimmed meeting {} to {:.2}s..{:.2}s",
+            meeting_id, start_seconds, end_seconds
+        );
+        Ok(())
+    }
+}
+
+/// Drops data derived from a meeting's transcript after its timeline changed: the summary
+/// (and its transcript chunks), translated tracks, chapters, conversation analytics and
+/// background-pass versions. Pending versions are built on the old timeline, and the
+/// transcript an accepted version would revert to is too, so both are superseded.
+pub(super) async fn invalidate_derived_data(
+    transaction: &mut SqliteConnection,
+    meeting_id: &str,
+) -> Result<(), SqlxError> {
+    sqlx::query("DELETE FROM summary_processes WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query("DELETE FROM transcript_chunks WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query("DELETE FROM meeting_translations WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query("DELETE FROM meeting_chapters WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query("DELETE FROM meeting_analytics WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query(
+        "UPDATE transcript_versions SET status = 'superseded', previous_segments = NULL, updated_at = ?
+         WHERE meeting_id = ? AND status IN ('pending', 'accepted')",
+    )
+    .bind(Utc::now())
+    .bind(meeting_id)
+    .execute(&mut *transaction)
+    .await?;
+
+    Ok(())
+}
+
+/// Moves the word timings of the transcripts matching `filter` (bound to `meeting_id` and,
+/// if given, `at_seconds`) by `offset`. Word timings are recording times, so they have to move
+/// with their segment; unreadable ones are dropped.
+async fn shift_word_timings(
+    transaction: &mut SqliteConnection,
+    filter: &str,
+    meeting_id: &str,
+    at_seconds: Option<f64>,
+    offset: f64,
+) -> Result<(), SqlxError> {
+    let query = format!(
+        "SELECT id, word_timings FROM transcripts WHERE {} AND word_timings IS NOT NULL",
+        filter
+    );
+    let mut select = sqlx::query_as::<_, (String, String)>(&query).bind(meeting_id);
+    if let Some(at_seconds) = at_seconds {
+        select = select.bind(at_seconds);
+    }
+    let rows = select.fetch_all(&mut *transaction).await?;
+
+    for (id, json) in rows {
+        sqlx::query("UPDATE transcripts SET word_timings = ? WHERE id = ?")
+            .bind(shifted_word_timings(&json, offset))
+            .bind(&id)
+            .execute(&mut *transaction)
+            .await?;
+    }
+    Ok(())
+}
+
+fn shifted_word_timings(json: &str, offset: f64) -> Option<String> {
+    let mut words: Vec<serde_json::Value> = serde_json::from_str(json).ok()?;
+    for word in &mut words {
+        for key in ["start", "end"] {
+            let time = word.get(key)?.as_f64()?;
+            word[key] = serde_json::json!(time + offset);
+        }
+    }
+    serde_json::to_string(&words).ok()
 }
 
 async fn delete_meeting_with_transaction(
@@ -264,7 +682,55 @@ async fn delete_meeting_with_transaction(
         .execute(&mut *transaction)
         .await?;
 
-    // 4. Finally, delete the meeting
+    // 4. Delete transcript versions from background passes
+    sqlx::query("DELETE FROM transcript_versions WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 5. Delete the meeting's vocabulary terms
+    sqlx::query("DELETE FROM vocabulary_terms WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 6. Delete translated tracks
+    sqlx::query("DELETE FROM meeting_translations WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 7. Delete the transcript revision history
+    sqlx::query("DELETE FROM transcript_revisions WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 8. Delete chapters
+    sqlx::query("DELETE FROM meeting_chapters WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 9. Delete conversation analytics
+    sqlx::query("DELETE FROM meeting_analytics WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 10. Delete recording markers
+    sqlx::query("DELETE FROM meeting_markers WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 11. Delete the rolling summary from recording
+    sqlx::query("DELETE FROM meeting_live_summaries WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 12. Finally, delete the meeting
     let result = sqlx::query("DELETE FROM meetings WHERE id = ?")
         .bind(meeting_id)
         .execute(&mut *transaction)
@@ -272,3 +738,127 @@ async fn delete_meeting_with_transaction(
 
     Ok(result.rows_affected() > 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::TranscriptVersionSegment;
+    use crate::database::repositories::transcript_version::TranscriptVersionsRepository;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn test_pool() -> SqlitePool {
+        // One connection, so every query sees the same in-memory database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES ('m', 'Meeting', ?, ?)")
+            .bind(Utc::now())
+            .bind(Utc::now())
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    fn segment(id: &str, text: &str, start: Option<f64>, end: Option<f64>) -> TranscriptVersionSegment {
+        TranscriptVersionSegment {
+            audio_start_time: start,
+            audio_end_time: end,
+            duration: start.zip(end).map(|(start, end)| end - start),
+            ..crate::database::models::segment(id, text, 0.0, 0.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trim_handles_missing_times() {
+        let pool = test_pool().await;
+        let segments = [
+            segment("before", "cut", Some(0.0), Some(5.0)),
+            segment("across", "clipped", Some(8.0), Some(12.0)),
+            segment("instant_before", "cut", Some(9.0), None),
+            segment("instant_inside", "kept", Some(15.0), None),
+            segment("after", "cut", Some(30.0), Some(32.0)),
+            segment("untimed", "kept", None, None),
+        ];
+        TranscriptVersionsRepository::replace_current_segments(&pool, "m", &segments).await.unwrap();
+
+        MeetingsRepository::trim_meeting(&pool, "m", 10.0, 20.0).await.unwrap();
+
+        let trimmed = TranscriptVersionsRepository::get_current_segments(&pool, "m").await.unwrap();
+        let by_id = |id: &str| trimmed.iter().find(|s| s.id == id);
+        assert_eq!(trimmed.len(), 3);
+        let across = by_id("across").unwrap();
+        assert_eq!(
+            (across.audio_start_time, across.audio_end_time, across.duration),
+            (Some(0.0), Some(2.0), Some(2.0))
+        );
+        let instant = by_id("instant_inside").unwrap();
+        assert_eq!((instant.audio_start_time, instant.audio_end_time), (Some(5.0), None));
+        assert_eq!(by_id("untimed").unwrap().audio_start_time, None);
+    }
+
+    #[tokio::test]
+    async fn test_trim_blocks_reverting_an_accepted_version() {
+        let pool = test_pool().await;
+        let original = [
+            segment("a", "first draft", Some(0.0), Some(10.0)),
+            segment("b", "second draft", Some(10.0), Some(20.0)),
+        ];
+        TranscriptVersionsRepository::replace_current_segments(&pool, "m", &original).await.unwrap();
+        let improved = [
+            segment("a", "first", Some(0.0), Some(10.0)),
+            segment("b", "second", Some(10.0), Some(20.0)),
+        ];
+        let version_id = TranscriptVersionsRepository::create_version(
+            &pool,
+            "m",
+            "quality_pass",
+            None,
+            None,
+            &improved,
+            &original,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+        TranscriptVersionsRepository::accept_version(&pool, &version_id, |version, _, _| version)
+            .await
+            .unwrap();
+
+        MeetingsRepository::trim_meeting(&pool, "m", 10.0, 20.0).await.unwrap();
+
+        // Reverting would write back the untrimmed draft
+        assert!(TranscriptVersionsRepository::revert_version(&pool, &version_id).await.is_err());
+        let current = TranscriptVersionsRepository::get_current_segments(&pool, "m").await.unwrap();
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].text, "second");
+        assert_eq!(current[0].audio_start_time, Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn test_trim_moves_word_timings_with_their_segment() {
+        let pool = test_pool().await;
+        let segments = [segment("a", "hello world", Some(12.0), Some(14.0))];
+        TranscriptVersionsRepository::replace_current_segments(&pool, "m", &segments).await.unwrap();
+        sqlx::query("UPDATE transcripts SET word_timings = ? WHERE id = 'a'")
+            .bind(r#"[{"word":"hello","start":12.0,"end":12.5,"aligned":true},{"word":"world","start":12.5,"end":14.0,"aligned":false}]"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        MeetingsRepository::trim_meeting(&pool, "m", 10.0, 20.0).await.unwrap();
+
+        let json: String = sqlx::query_scalar("SELECT word_timings FROM transcripts WHERE id = 'a'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let words: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!((words[0]["start"].as_f64(), words[0]["end"].as_f64()), (Some(2.0), Some(2.5)));
+        assert_eq!((words[1]["start"].as_f64(), words[1]["end"].as_f64()), (Some(2.5), Some(4.0)));
+        assert_eq!(words[1]["aligned"], serde_json::json!(false));
+        assert_eq!(shifted_word_timings("not json", 1.0), None);
+    }
+}
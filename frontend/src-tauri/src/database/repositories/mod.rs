@@ -3,3 +3,15 @@ pub mod setting;
 pub mod summary;
 pub mod transcript;
 pub mod transcript_chunk;
+pub mod transcript_version;
+pub mod vocabulary;
+pub mod translation;
+pub mod import_queue;
+pub mod transcript_revision;
+pub mod chapter;
+pub mod conversation_analytics;
+pub mod marker;
+pub mod live_summary;
+pub mod redaction_audit;
+pub mod redacted_copy;
+pub mod sync;
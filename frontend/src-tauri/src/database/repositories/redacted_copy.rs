@@ -0,0 +1,46 @@
+use crate::database::models::RedactedCopy;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct RedactedCopiesRepository;
+
+impl RedactedCopiesRepository {
+    pub async fn save_copy(pool: &SqlitePool, copy: &RedactedCopy) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO redacted_copies (meeting_id, source_meeting_id, spans, audio_mode, summary_mode, created_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&copy.meeting_id)
+        .bind(&copy.source_meeting_id)
+        .bind(&copy.spans)
+        .bind(&copy.audio_mode)
+        .bind(&copy.summary_mode)
+        .bind(copy.created_at)
+        .execute(pool)
+        .await?;
+
+        info!(
+            "Linked redacted copy {} to meeting {}",
+            copy.meeting_id, copy.source_meeting_id
+        );
+        Ok(())
+    }
+
+    /// Redacted copies made of a meeting, newest first
+    pub async fn get_copies(pool: &SqlitePool, source_meeting_id: &str) -> Result<Vec<RedactedCopy>, SqlxError> {
+        sqlx::query_as::<_, RedactedCopy>(
+            "SELECT * FROM redacted_copies WHERE source_meeting_id = ? ORDER BY created_at DESC",
+        )
+        .bind(source_meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The link of a meeting that is itself a redacted copy
+    pub async fn get_copy(pool: &SqlitePool, meeting_id: &str) -> Result<Option<RedactedCopy>, SqlxError> {
+        sqlx::query_as::<_, RedactedCopy>("SELECT * FROM redacted_copies WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+}
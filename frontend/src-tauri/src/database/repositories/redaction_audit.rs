@@ -0,0 +1,51 @@
+use crate::database::models::RedactionAudit;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct RedactionAuditsRepository;
+
+impl RedactionAuditsRepository {
+    pub async fn save_audit(pool: &SqlitePool, audit: &RedactionAudit) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO redaction_audits (id, meeting_id, purpose, provider, model, ner_model, entity_count, entities, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&audit.id)
+        .bind(&audit.meeting_id)
+        .bind(&audit.purpose)
+        .bind(&audit.provider)
+        .bind(&audit.model)
+        .bind(&audit.ner_model)
+        .bind(audit.entity_count)
+        .bind(&audit.entities)
+        .bind(audit.created_at)
+        .execute(pool)
+        .await?;
+
+        info!(
+            "Recorded redaction audit for {} request ({} entities)",
+            audit.purpose, audit.entity_count
+        );
+        Ok(())
+    }
+
+    /// Audit records of a meeting, newest first
+    pub async fn get_audits(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<RedactionAudit>, SqlxError> {
+        sqlx::query_as::<_, RedactionAudit>(
+            "SELECT * FROM redaction_audits WHERE meeting_id = ? ORDER BY created_at DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Links the audit records of requests made while recording (live summary) to the meeting
+    /// the recording was saved as
+    pub async fn assign_recording_audits(pool: &SqlitePool, meeting_id: &str) -> Result<u64, SqlxError> {
+        let result = sqlx::query("UPDATE redaction_audits SET meeting_id = ? WHERE meeting_id IS NULL AND purpose = 'live_summary'")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::database::models::{Setting, TranscriptSetting};
+use crate::secrets;
 use crate::summary::CustomOpenAIConfig;
 use sqlx::SqlitePool;
 
@@ -24,6 +25,39 @@ pub struct SaveTranscriptConfigRequest {
 
 pub struct SettingsRepository;
 
+/// API key columns of `settings` that older versions stored keys in (in plaintext)
+const SUMMARY_KEY_COLUMNS: &[(&str, &str)] = &[
+    ("openai", "openaiApiKey"),
+    ("claude", "anthropicApiKey"),
+    ("ollama", "ollamaApiKey"),
+    ("groq", "groqApiKey"),
+    ("openrouter", "openRouterApiKey"),
+];
+
+/// API key columns of `transcript_settings` that older versions stored keys in (in plaintext)
+const TRANSCRIPT_KEY_COLUMNS: &[(&str, &str)] = &[
+    ("localWhisper", "whisperApiKey"),
+    ("deepgram", "deepgramApiKey"),
+    ("elevenLabs", "elevenLabsApiKey"),
+    ("groq", "groqApiKey"),
+    ("openai", "openaiApiKey"),
+];
+
+fn secret_error(e: String) -> sqlx::Error {
+    sqlx::Error::Protocol(e)
+}
+
+/// Reads a secret; a store error is logged and treated as missing so the legacy column is tried
+fn read_secret(name: &str) -> Option<String> {
+    match secrets::get_secret(name) {
+        Ok(value) => value,
+        Err(e) => {
+            log::warn!("Failed to read secret {}: {}", name, e);
+            None
+        }
+    }
+}
+
 // Transcript providers: localWhisper, deepgram, elevenLabs, groq, openai
 // Summary providers: openai, claude, ollama, groq, added openrouter
 // NOTE: Handle data exclusion in the higher layer as this is database abstraction layer(using SELECT *)
@@ -93,16 +127,19 @@ impl SettingsRepository {
             }
         };
 
+        secrets::set_secret(&secrets::summary_api_key_name(provider), api_key).map_err(secret_error)?;
+
+        // Keys live in the secret store; make sure the row exists and no plaintext copy is left
         let query = format!(
             r#"
-            INSERT INTO settings (id, provider, model, whisperModel, "{}")
-            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3', $1)
+            INSERT INTO settings (id, provider, model, whisperModel)
+            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3')
             ON CONFLICT(id) DO UPDATE SET
-                "{}" = $1
+                "{}" = NULL
             "#,
-            api_key_column, api_key_column
+            api_key_column
         );
-        sqlx::query(&query).bind(api_key).execute(pool).await?;
+        sqlx::query(&query).execute(pool).await?;
 
         Ok(())
     }
@@ -131,12 +168,17 @@ impl SettingsRepository {
             }
         };
 
+        if let Some(api_key) = read_secret(&secrets::summary_api_key_name(provider)) {
+            return Ok(Some(api_key));
+        }
+
+        // Not moved to the secret store yet (store was unavailable at startup)
         let query = format!(
             "SELECT {} FROM settings WHERE id = '1' LIMIT 1",
             api_key_column
         );
-        let api_key = sqlx::query_scalar(&query).fetch_optional(pool).await?;
-        Ok(api_key)
+        let api_key: Option<Option<String>> = sqlx::query_scalar(&query).fetch_optional(pool).await?;
+        Ok(api_key.flatten())
     }
 
     pub async fn get_transcript_config(
@@ -191,16 +233,19 @@ impl SettingsRepository {
             }
         };
 
+        secrets::set_secret(&secrets::transcript_api_key_name(provider), api_key).map_err(secret_error)?;
+
+        // Keys live in the secret store; make sure the row exists and no plaintext copy is left
         let query = format!(
             r#"
-            INSERT INTO transcript_settings (id, provider, model, "{}")
-            VALUES ('1', 'parakeet', '{}', $1)
+            INSERT INTO transcript_settings (id, provider, model)
+            VALUES ('1', 'parakeet', '{}')
             ON CONFLICT(id) DO UPDATE SET
-                "{}" = $1
+                "{}" = NULL
             "#,
-            api_key_column, crate::config::DEFAULT_PARAKEET_MODEL, api_key_column
+            crate::config::DEFAULT_PARAKEET_MODEL, api_key_column
         );
-        sqlx::query(&query).bind(api_key).execute(pool).await?;
+        sqlx::query(&query).execute(pool).await?;
 
         Ok(())
     }
@@ -223,12 +268,17 @@ impl SettingsRepository {
             }
         };
 
+        if let Some(api_key) = read_secret(&secrets::transcript_api_key_name(provider)) {
+            return Ok(Some(api_key));
+        }
+
+        // Not moved to the secret store yet (store was unavailable at startup)
         let query = format!(
             "SELECT {} FROM transcript_settings WHERE id = '1' LIMIT 1",
             api_key_column
         );
-        let api_key = sqlx::query_scalar(&query).fetch_optional(pool).await?;
-        Ok(api_key)
+        let api_key: Option<Option<String>> = sqlx::query_scalar(&query).fetch_optional(pool).await?;
+        Ok(api_key.flatten())
     }
 
     pub async fn delete_api_key(
@@ -237,6 +287,7 @@ impl SettingsRepository {
     ) -> std::result::Result<(), sqlx::Error> {
         // Custom OpenAI uses JSON config - clear the entire config
         if provider == "custom-openai" {
+            secrets::delete_secret(secrets::CUSTOM_OPENAI_API_KEY).map_err(secret_error)?;
             sqlx::query("UPDATE settings SET customOpenAIConfig = NULL WHERE id = '1'")
                 .execute(pool)
                 .await?;
@@ -257,6 +308,8 @@ impl SettingsRepository {
             }
         };
 
+        secrets::delete_secret(&secrets::summary_api_key_name(provider)).map_err(secret_error)?;
+
         let query = format!(
             "UPDATE settings SET {} = NULL WHERE id = '1'",
             api_key_column
@@ -296,11 +349,16 @@ impl SettingsRepository {
 
                 if let Some(json) = config_json {
                     // Parse JSON into CustomOpenAIConfig
-                    let config: CustomOpenAIConfig = serde_json::from_str(&json)
+                    let mut config: CustomOpenAIConfig = serde_json::from_str(&json)
                         .map_err(|e| sqlx::Error::Protocol(
                             format!("Invalid JSON in customOpenAIConfig: {}", e).into()
                         ))?;
 
+                    // The API key is kept in the secret store (JSON only has it before migration)
+                    if let Some(api_key) = read_secret(secrets::CUSTOM_OPENAI_API_KEY) {
+                        config.api_key = Some(api_key);
+                    }
+
                     Ok(Some(config))
                 } else {
                     Ok(None)
@@ -310,7 +368,7 @@ impl SettingsRepository {
         }
     }
 
-    /// Saves the custom OpenAI configuration as JSON
+    /// Saves the custom OpenAI configuration as JSON; the API key goes to the secret store
     ///
     /// # Arguments
     /// * `pool` - Database connection pool
@@ -318,13 +376,23 @@ impl SettingsRepository {
     ///
     /// # Returns
     /// * `Ok(())` - Config saved successfully
-    /// * `Err(sqlx::Error)` - Database or JSON serialization error
+    /// * `Err(sqlx::Error)` - Database, secret store or JSON serialization error
     pub async fn save_custom_openai_config(
         pool: &SqlitePool,
         config: &CustomOpenAIConfig,
     ) -> std::result::Result<(), sqlx::Error> {
+        secrets::set_secret(
+            secrets::CUSTOM_OPENAI_API_KEY,
+            config.api_key.as_deref().unwrap_or_default(),
+        )
+        .map_err(secret_error)?;
+        let stored = CustomOpenAIConfig {
+            api_key: None,
+            ..config.clone()
+        };
+
         // Serialize config to JSON
-        let config_json = serde_json::to_string(config)
+        let config_json = serde_json::to_string(&stored)
             .map_err(|e| sqlx::Error::Protocol(
                 format!("Failed to serialize config to JSON: {}", e).into()
             ))?;
@@ -345,4 +413,91 @@ impl SettingsRepository {
 
         Ok(())
     }
+
+    // ===== SECRET STORE MIGRATION =====
+
+    /// Moves API keys that older versions stored in plaintext columns (and in the custom
+    /// OpenAI JSON) into the secret store, clearing each column once its key is stored.
+    /// Runs at every startup and does nothing when no plaintext key is left.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of keys moved
+    /// * `Err(sqlx::Error)` - Database or secret store error; keys not moved stay readable
+    pub async fn migrate_api_keys_to_secrets(
+        pool: &SqlitePool,
+    ) -> std::result::Result<usize, sqlx::Error> {
+        let mut moved = Self::move_column_keys(pool, "settings", SUMMARY_KEY_COLUMNS, secrets::summary_api_key_name).await?
+            + Self::move_column_keys(pool, "transcript_settings", TRANSCRIPT_KEY_COLUMNS, secrets::transcript_api_key_name)
+                .await?;
+
+        let config_json: Option<Option<String>> =
+            sqlx::query_scalar("SELECT customOpenAIConfig FROM settings WHERE id = '1' LIMIT 1")
+                .fetch_optional(pool)
+                .await?;
+        if let Some(json) = config_json.flatten() {
+            match serde_json::from_str::<CustomOpenAIConfig>(&json) {
+                Ok(config) if config.api_key.as_deref().is_some_and(|key| !key.is_empty()) => {
+                    if secrets::get_secret(secrets::CUSTOM_OPENAI_API_KEY).map_err(secret_error)?.is_none() {
+                        Self::save_custom_openai_config(pool, &config).await?;
+                    } else {
+                        let stored = CustomOpenAIConfig { api_key: None, ..config };
+                        let stored_json = serde_json::to_string(&stored)
+                            .map_err(|e| secret_error(format!("Failed to serialize config to JSON: {}", e)))?;
+                        sqlx::query("UPDATE settings SET customOpenAIConfig = $1 WHERE id = '1'")
+                            .bind(stored_json)
+                            .execute(pool)
+                            .await?;
+                    }
+                    moved += 1;
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Skipping custom OpenAI key migration, invalid config JSON: {}", e),
+            }
+        }
+
+        Ok(moved)
+    }
+
+    /// Clears every plaintext API key column and the key inside the custom OpenAI JSON,
+    /// without touching the secret store. Used on database copies leaving the machine.
+    pub async fn clear_plaintext_api_keys(pool: &SqlitePool) -> std::result::Result<(), sqlx::Error> {
+        for (table, columns) in [("settings", SUMMARY_KEY_COLUMNS), ("transcript_settings", TRANSCRIPT_KEY_COLUMNS)] {
+            for (_, column) in columns {
+                let query = format!("UPDATE {} SET {} = NULL", table, column);
+                sqlx::query(&query).execute(pool).await?;
+            }
+        }
+        sqlx::query(
+            "UPDATE settings SET customOpenAIConfig = json_remove(customOpenAIConfig, '$.apiKey') \
+             WHERE customOpenAIConfig IS NOT NULL AND json_valid(customOpenAIConfig)",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn move_column_keys(
+        pool: &SqlitePool,
+        table: &str,
+        columns: &[(&str, &str)],
+        secret_name: fn(&str) -> String,
+    ) -> std::result::Result<usize, sqlx::Error> {
+        let mut moved = 0;
+        for (provider, column) in columns {
+            let query = format!("SELECT {} FROM {} WHERE id = '1' LIMIT 1", column, table);
+            let value: Option<Option<String>> = sqlx::query_scalar(&query).fetch_optional(pool).await?;
+            let Some(api_key) = value.flatten().filter(|key| !key.is_empty()) else {
+                continue;
+            };
+            // A key saved through the secret store since then wins over the old column
+            let name = secret_name(provider);
+            if secrets::get_secret(&name).map_err(secret_error)?.is_none() {
+                secrets::set_secret(&name, &api_key).map_err(secret_error)?;
+            }
+            let query = format!("UPDATE {} SET {} = NULL WHERE id = '1'", table, column);
+            sqlx::query(&query).execute(pool).await?;
+            moved += 1;
+        }
+        Ok(moved)
+    }
 }
@@ -118,6 +118,39 @@ impl SummaryProcessesRepository {
         Ok(())
     }
 
+    /// Sets one key of the process metadata object, keeping the other keys
+    pub async fn set_metadata_entry(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        key: &str,
+        value: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+        let current: Option<Option<String>> =
+            sqlx::query_scalar("SELECT metadata FROM summary_processes WHERE meeting_id = ?")
+                .bind(meeting_id)
+                .fetch_optional(&mut *transaction)
+                .await?;
+        let Some(current) = current else {
+            transaction.rollback().await?;
+            return Ok(());
+        };
+
+        let mut metadata = current
+            .and_then(|json| serde_json::from_str::<serde_json::Map<String, Value>>(&json).ok())
+            .unwrap_or_default();
+        metadata.insert(key.to_string(), value.clone());
+        let metadata = serde_json::to_string(&metadata)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize metadata: {}", e)))?;
+
+        sqlx::query("UPDATE summary_processes SET metadata = ? WHERE meeting_id = ?")
+            .bind(metadata)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await
+    }
+
     pub async fn update_process_completed(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -0,0 +1,255 @@
+use super::meeting::invalidate_derived_data;
+use super::transcript_revision::record_revision;
+use super::transcript_version::{fetch_current_segments, replace_transcripts};
+use crate::database::models::{SyncConflictRow, SyncRecordRow, TranscriptVersionSegment};
+use chrono::{DateTime, Utc};
+use sqlx::{Connection, Error as SqlxError, SqlitePool};
+
+pub struct SyncRepository;
+
+impl SyncRepository {
+    pub async fn get_records(pool: &SqlitePool) -> Result<Vec<SyncRecordRow>, SqlxError> {
+        sqlx::query_as::<_, SyncRecordRow>("SELECT * FROM sync_records ORDER BY meeting_id, kind")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_record(pool: &SqlitePool, kind: &str, meeting_id: &str) -> Result<Option<SyncRecordRow>, SqlxError> {
+        sqlx::query_as::<_, SyncRecordRow>("SELECT * FROM sync_records WHERE kind = ? AND meeting_id = ?")
+            .bind(kind)
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn save_record(pool: &SqlitePool, record: &SyncRecordRow) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO sync_records (kind, meeting_id, revision, content_hash, modified_at, modified_by, deleted, dirty, source_stamp)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(kind, meeting_id) DO UPDATE SET
+                revision = excluded.revision,
+                content_hash = excluded.content_hash,
+                modified_at = excluded.modified_at,
+                modified_by = excluded.modified_by,
+                deleted = excluded.deleted,
+                dirty = excluded.dirty,
+                source_stamp = excluded.source_stamp",
+        )
+        .bind(&record.kind)
+        .bind(&record.meeting_id)
+        .bind(&record.revision)
+        .bind(&record.content_hash)
+        .bind(record.modified_at)
+        .bind(&record.modified_by)
+        .bind(record.deleted)
+        .bind(record.dirty)
+        .bind(&record.source_stamp)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_record(pool: &SqlitePool, kind: &str, meeting_id: &str) -> Result<(), SqlxError> {
+        sqlx::query("DELETE FROM sync_records WHERE kind = ? AND meeting_id = ?")
+            .bind(kind)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Marks every record as changed, e.g. before the first sync with another server
+    pub async fn mark_all_dirty(pool: &SqlitePool) -> Result<u64, SqlxError> {
+        let result = sqlx::query("UPDATE sync_records SET dirty = 1").execute(pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Change feed position on a server; None if this server was never synced with
+    pub async fn get_cursor(pool: &SqlitePool, server_url: &str) -> Result<Option<i64>, SqlxError> {
+        sqlx::query_scalar("SELECT cursor FROM sync_servers WHERE server_url = ?")
+            .bind(server_url)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn save_cursor(pool: &SqlitePool, server_url: &str, cursor: i64) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO sync_servers (server_url, cursor, last_sync_at) VALUES (?, ?, ?)
+             ON CONFLICT(server_url) DO UPDATE SET cursor = excluded.cursor, last_sync_at = excluded.last_sync_at",
+        )
+        .bind(server_url)
+        .bind(cursor)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn save_conflict(
+        pool: &SqlitePool,
+        kind: &str,
+        meeting_id: &str,
+        local_content: Option<&str>,
+        remote_record: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO sync_conflicts (kind, meeting_id, local_content, remote_record, detected_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(kind, meeting_id) DO UPDATE SET
+                local_content = excluded.local_content,
+                remote_record = excluded.remote_record,
+                detected_at = excluded.detected_at",
+        )
+        .bind(kind)
+        .bind(meeting_id)
+        .bind(local_content)
+        .bind(remote_record)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_conflicts(pool: &SqlitePool) -> Result<Vec<SyncConflictRow>, SqlxError> {
+        sqlx::query_as::<_, SyncConflictRow>("SELECT * FROM sync_conflicts ORDER BY detected_at DESC")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_conflict(
+        pool: &SqlitePool,
+        kind: &str,
+        meeting_id: &str,
+    ) -> Result<Option<SyncConflictRow>, SqlxError> {
+        sqlx::query_as::<_, SyncConflictRow>("SELECT * FROM sync_conflicts WHERE kind = ? AND meeting_id = ?")
+            .bind(kind)
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn delete_conflict(pool: &SqlitePool, kind: &str, meeting_id: &str) -> Result<(), SqlxError> {
+        sqlx::query("DELETE FROM sync_conflicts WHERE kind = ? AND meeting_id = ?")
+            .bind(kind)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    // ===== APPLYING CHANGES FROM OTHER DEVICES =====
+
+    /// Creates a meeting received from another device (without a local folder) or updates its
+    /// title and star
+    pub async fn upsert_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        title: &str,
+        created_at: DateTime<Utc>,
+        starred: bool,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO meetings (id, title, created_at, updated_at, starred) VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                starred = excluded.starred,
+                updated_at = excluded.updated_at",
+        )
+        .bind(meeting_id)
+        .bind(title)
+        .bind(created_at)
+        .bind(Utc::now())
+        .bind(starred)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Replaces a meeting's transcript with the one received from another device. Like a local
+    /// edit of the timeline it drops the data derived from the previous transcript, and it is
+    /// recorded as a revision (author "sync"), in the same transaction.
+    pub async fn apply_transcript(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        segments: &[TranscriptVersionSegment],
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let current = fetch_current_segments(&mut transaction, meeting_id).await?;
+        if current.as_slice() == segments {
+            return Ok(());
+        }
+
+        replace_transcripts(&mut transaction, meeting_id, segments).await?;
+        // Nothing is derived from a transcript this device did not have yet; a summary that
+        // arrived with it stays
+        if !current.is_empty() {
+            invalidate_derived_data(&mut transaction, meeting_id).await?;
+        }
+        record_revision(&mut transaction, meeting_id, "sync", Some("sync"), &current, segments).await?;
+        transaction.commit().await
+    }
+
+    /// Stores a completed summary (JSON) received from another device
+    pub async fn upsert_summary_result(pool: &SqlitePool, meeting_id: &str, result: &str) -> Result<(), SqlxError> {
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO summary_processes (meeting_id, status, created_at, updated_at, result)
+             VALUES (?, 'completed', ?, ?, ?)
+             ON CONFLICT(meeting_id) DO UPDATE SET
+                status = 'completed',
+                result = excluded.result,
+                updated_at = excluded.updated_at,
+                error = NULL",
+        )
+        .bind(meeting_id)
+        .bind(now)
+        .bind(now)
+        .bind(result)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_summary(pool: &SqlitePool, meeting_id: &str) -> Result<(), SqlxError> {
+        sqlx::query("DELETE FROM summary_processes WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn upsert_notes(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        markdown: Option<&str>,
+        json: Option<&str>,
+    ) -> Result<(), SqlxError> {
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO meeting_notes (meeting_id, notes_markdown, notes_json, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(meeting_id) DO UPDATE SET
+                notes_markdown = excluded.notes_markdown,
+                notes_json = excluded.notes_json,
+                updated_at = excluded.updated_at",
+        )
+        .bind(meeting_id)
+        .bind(markdown)
+        .bind(json)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_notes(pool: &SqlitePool, meeting_id: &str) -> Result<(), SqlxError> {
+        sqlx::query("DELETE FROM meeting_notes WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
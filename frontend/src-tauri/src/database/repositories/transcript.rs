@@ -1,4 +1,5 @@
 use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::database::models::Transcript;
 use chrono::Utc;
 use sqlx::{Connection, Error as SqlxError, SqlitePool};
 use tracing::{error, info};
@@ -47,8 +48,8 @@ impl TranscriptsRepository {
         for segment in transcripts {
             let transcript_id = format!("transcript-{}", Uuid::new_v4());
             let result = sqlx::query(
-                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, language, translation)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(&transcript_id)
             .bind(&meeting_id)
@@ -57,6 +58,8 @@ impl TranscriptsRepository {
             .bind(segment.audio_start_time)
             .bind(segment.audio_end_time)
             .bind(segment.duration)
+            .bind(&segment.language)
+            .bind(&segment.translation)
             .execute(&mut *transaction)
             .await;
 
@@ -82,6 +85,45 @@ impl TranscriptsRepository {
         Ok(meeting_id)
     }
 
+    /// Transcript text of a meeting in audio order, one segment per line.
+    /// With `use_translation`, segments that have an English translation contribute it
+    /// instead of the original text.
+    pub async fn get_meeting_text(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        use_translation: bool,
+    ) -> Result<String, SqlxError> {
+        let rows = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT transcript, translation FROM transcripts WHERE meeting_id = ? ORDER BY audio_start_time ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await?;
+
+        let lines: Vec<String> = rows
+            .into_iter()
+            .map(|(transcript, translation)| match translation {
+                Some(translation) if use_translation && !translation.trim().is_empty() => translation,
+                _ => transcript,
+            })
+            .collect();
+
+        Ok(lines.join("\n"))
+    }
+
+    /// All transcript rows of a meeting in audio order
+    pub async fn get_meeting_transcripts(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<Transcript>, SqlxError> {
+        sqlx::query_as::<_, Transcript>(
+            "SELECT * FROM transcripts WHERE meeting_id = ? ORDER BY audio_start_time ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
     /// Searches for a query string within the transcripts.
     /// It returns a list of matching transcripts with context.
     pub async fn search_transcripts(
@@ -1,5 +1,6 @@
 // src/database/repo/transcript_chunks.rs
 
+use crate::database::models::TranscriptChunk;
 use chrono::Utc;
 use log::info as log_info;
 use sqlx::SqlitePool;
@@ -46,4 +47,15 @@ impl TranscriptChunksRepository {
 
         Ok(())
     }
+
+    /// The transcript text and processing parameters last saved for a meeting.
+    pub async fn get_transcript_data(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<TranscriptChunk>, sqlx::Error> {
+        sqlx::query_as::<_, TranscriptChunk>("SELECT * FROM transcript_chunks WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
 }
@@ -0,0 +1,305 @@
+use super::transcript_version::fetch_current_segments;
+use crate::database::models::{TranscriptRevision, TranscriptVersionSegment};
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+use tracing::{error, info};
+use uuid::Uuid;
+
+pub struct TranscriptRevisionsRepository;
+
+impl TranscriptRevisionsRepository {
+    /// Apply a segment-level change and record it as a revision, in one transaction.
+    /// Rows only in `before` are deleted, rows only in `after` are inserted and rows in both
+    /// are updated. `word_timings` stores (segment id, JSON) pairs from re-alignment; updated
+    /// rows whose text or timing changed otherwise lose their word timings.
+    ///
+    /// `before` is what the caller based the change on. The rows are read again inside the
+    /// transaction and the change is refused if they no longer match, so the recorded
+    /// snapshot is always the state that was replaced.
+    pub async fn apply(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        operation: &str,
+        author: Option<&str>,
+        before: &[TranscriptVersionSegment],
+        after: &[TranscriptVersionSegment],
+        word_timings: &[(String, String)],
+    ) -> Result<TranscriptRevision, SqlxError> {
+        let before_ids: HashSet<&str> = before.iter().map(|s| s.id.as_str()).collect();
+        let after_ids: HashSet<&str> = after.iter().map(|s| s.id.as_str()).collect();
+
+        let now = Utc::now();
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        // Writing first takes SQLite's write lock, so no other change can land between
+        // reading the snapshot below and applying this one
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let snapshot: Vec<TranscriptVersionSegment> = fetch_current_segments(&mut transaction, meeting_id)
+            .await?
+            .into_iter()
+            .filter(|s| before_ids.contains(s.id.as_str()))
+            .collect();
+        let unchanged = snapshot.len() == before.len()
+            && before
+                .iter()
+                .all(|expected| snapshot.iter().any(|current| current == expected));
+        if !unchanged {
+            return Err(SqlxError::Protocol(
+                "The transcript changed while it was being edited; reload it and try again".to_string(),
+            ));
+        }
+
+        for segment in before.iter().filter(|s| !after_ids.contains(s.id.as_str())) {
+            sqlx::query("DELETE FROM transcripts WHERE id = ? AND meeting_id = ?")
+                .bind(&segment.id)
+                .bind(meeting_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        for segment in after {
+            let result = if before_ids.contains(segment.id.as_str()) {
+                sqlx::query(
+                    "UPDATE transcripts SET
+                        word_timings = CASE WHEN transcript = ? AND audio_start_time IS ? AND audio_end_time IS ?
+                                            THEN word_timings ELSE NULL END,
+                        transcript = ?, timestamp = ?, audio_start_time = ?, audio_end_time = ?, duration = ?,
+                        speaker = ?, edited_at = ?, language = ?, translation = ?
+                     WHERE id = ? AND meeting_id = ?",
+                )
+                .bind(&segment.text)
+                .bind(segment.audio_start_time)
+                .bind(segment.audio_end_time)
+                .bind(&segment.text)
+                .bind(&segment.timestamp)
+                .bind(segment.audio_start_time)
+                .bind(segment.audio_end_time)
+                .bind(segment.duration)
+                .bind(&segment.speaker)
+                .bind(&segment.edited_at)
+                .bind(&segment.language)
+                .bind(&segment.translation)
+                .bind(&segment.id)
+                .bind(meeting_id)
+                .execute(&mut *transaction)
+                .await
+            } else {
+                sqlx::query(
+                    "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker, edited_at, language, translation)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                )
+                .bind(&segment.id)
+                .bind(meeting_id)
+                .bind(&segment.text)
+                .bind(&segment.timestamp)
+                .bind(segment.audio_start_time)
+                .bind(segment.audio_end_time)
+                .bind(segment.duration)
+                .bind(&segment.speaker)
+                .bind(&segment.edited_at)
+                .bind(&segment.language)
+                .bind(&segment.translation)
+                .execute(&mut *transaction)
+                .await
+            };
+
+            if let Err(e) = result {
+                error!("Failed to write transcript segment {} for meeting {}: {}", segment.id, meeting_id, e);
+                return Err(e);
+            }
+        }
+
+        for (segment_id, timings) in word_timings {
+            sqlx::query("UPDATE transcripts SET word_timings = ? WHERE id = ? AND meeting_id = ?")
+                .bind(timings)
+                .bind(segment_id)
+                .bind(meeting_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        let revision = record_revision(&mut transaction, meeting_id, operation, author, &snapshot, after).await?;
+        transaction.commit().await?;
+
+        info!(
+            "Recorded transcript revision {} ({}) for meeting {}: {} -> {} segments",
+            revision.id,
+            operation,
+            meeting_id,
+            before.len(),
+            after.len()
+        );
+        Ok(revision)
+    }
+
+    /// Most recent revisions first
+    pub async fn list_revisions(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        limit: i64,
+    ) -> Result<Vec<TranscriptRevision>, SqlxError> {
+        sqlx::query_as::<_, TranscriptRevision>(
+            "SELECT * FROM transcript_revisions WHERE meeting_id = ? ORDER BY created_at DESC LIMIT ?",
+        )
+        .bind(meeting_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Word timings (JSON) of a meeting's re-aligned segments, as (segment id, JSON)
+    pub async fn get_word_timings(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<(String, String)>, SqlxError> {
+        sqlx::query_as::<_, (String, String)>(
+            "SELECT id, word_timings FROM transcripts
+             WHERE meeting_id = ? AND word_timings IS NOT NULL ORDER BY audio_start_time ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Records a revision from `before` to `after` (the caller applies the change itself)
+pub(super) async fn record_revision(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+    operation: &str,
+    author: Option<&str>,
+    before: &[TranscriptVersionSegment],
+    after: &[TranscriptVersionSegment],
+) -> Result<TranscriptRevision, SqlxError> {
+    let before_json = serde_json::to_string(before)
+        .map_err(|e| SqlxError::Protocol(format!("Failed to serialize segments: {}", e)))?;
+    let after_json = serde_json::to_string(after)
+        .map_err(|e| SqlxError::Protocol(format!("Failed to serialize segments: {}", e)))?;
+
+    let mut segment_ids: Vec<&str> = Vec::new();
+    for segment in before.iter().chain(after) {
+        if !segment_ids.contains(&segment.id.as_str()) {
+            segment_ids.push(&segment.id);
+        }
+    }
+    let segment_ids_json = serde_json::to_string(&segment_ids)
+        .map_err(|e| SqlxError::Protocol(format!("Failed to serialize segment ids: {}", e)))?;
+
+    let revision = TranscriptRevision {
+        id: format!("revision-{}", Uuid::new_v4()),
+        meeting_id: meeting_id.to_string(),
+        operation: operation.to_string(),
+        segment_ids: segment_ids_json,
+        before_segments: before_json,
+        after_segments: after_json,
+        author: author.map(String::from),
+        created_at: Utc::now(),
+    };
+
+    sqlx::query(
+        "INSERT INTO transcript_revisions (id, meeting_id, operation, segment_ids, before_segments, after_segments, author, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&revision.id)
+    .bind(&revision.meeting_id)
+    .bind(&revision.operation)
+    .bind(&revision.segment_ids)
+    .bind(&revision.before_segments)
+    .bind(&revision.after_segments)
+    .bind(&revision.author)
+    .bind(revision.created_at)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(revision)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::sync::SyncRepository;
+    use crate::database::repositories::transcript_version::TranscriptVersionsRepository;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn test_pool() -> SqlitePool {
+        // One connection, so every query sees the same in-memory database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES ('m', 'Meeting', ?, ?)")
+            .bind(Utc::now())
+            .bind(Utc::now())
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    fn segment(id: &str, text: &str) -> TranscriptVersionSegment {
+        crate::database::models::segment(id, text, 0.0, 5.0)
+    }
+
+    #[tokio::test]
+    async fn test_apply_refuses_a_stale_snapshot() {
+        let pool = test_pool().await;
+        let original = segment("a", "original");
+        TranscriptVersionsRepository::replace_current_segments(&pool, "m", &[original.clone()])
+            .await
+            .unwrap();
+
+        let first = segment("a", "first edit");
+        let revision =
+            TranscriptRevisionsRepository::apply(&pool, "m", "edit", None, &[original.clone()], &[first.clone()], &[])
+                .await
+                .unwrap();
+        let recorded: Vec<TranscriptVersionSegment> = serde_json::from_str(&revision.before_segments).unwrap();
+        assert_eq!(recorded, vec![original.clone()]);
+
+        // A second edit based on the original text must not overwrite the first one
+        let second = segment("a", "second edit");
+        assert!(TranscriptRevisionsRepository::apply(&pool, "m", "edit", None, &[original], &[second], &[])
+            .await
+            .is_err());
+        let current = TranscriptVersionsRepository::get_current_segments(&pool, "m").await.unwrap();
+        assert_eq!(current, vec![first]);
+        let revisions = TranscriptRevisionsRepository::list_revisions(&pool, "m", 10).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_synced_transcript_is_recorded_and_drops_the_summary() {
+        let pool = test_pool().await;
+        let original = segment("a", "original");
+        TranscriptVersionsRepository::replace_current_segments(&pool, "m", &[original.clone()])
+            .await
+            .unwrap();
+        SyncRepository::upsert_summary_result(&pool, "m", "{}").await.unwrap();
+
+        let synced = segment("a", "edited on another device");
+        SyncRepository::apply_transcript(&pool, "m", &[synced.clone()]).await.unwrap();
+        // Receiving the same transcript again changes nothing
+        SyncRepository::apply_transcript(&pool, "m", &[synced.clone()]).await.unwrap();
+
+        let revisions = TranscriptRevisionsRepository::list_revisions(&pool, "m", 10).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].author.as_deref(), Some("sync"));
+        let before: Vec<TranscriptVersionSegment> = serde_json::from_str(&revisions[0].before_segments).unwrap();
+        assert_eq!(before, vec![original]);
+
+        let summaries: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM summary_processes WHERE meeting_id = 'm'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(summaries, 0);
+    }
+}
@@ -0,0 +1,383 @@
+use crate::database::models::{TranscriptVersion, TranscriptVersionSegment};
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
+use tracing::{error, info};
+use uuid::Uuid;
+
+pub struct TranscriptVersionsRepository;
+
+impl TranscriptVersionsRepository {
+    /// Current transcripts of a meeting, ordered by audio position
+    pub async fn get_current_segments(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptVersionSegment>, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        fetch_current_segments(&mut conn, meeting_id).await
+    }
+
+    /// Replaces the transcripts of a meeting with `segments`
+    pub async fn replace_current_segments(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        segments: &[TranscriptVersionSegment],
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        replace_transcripts(&mut transaction, meeting_id, segments).await?;
+        transaction.commit().await
+    }
+
+    /// Store a new pending version computed from the transcript `base`. Older pending
+    /// versions from the same source are marked superseded so only the newest result is
+    /// offered to the user.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_version(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        source: &str,
+        provider: Option<&str>,
+        model: Option<&str>,
+        segments: &[TranscriptVersionSegment],
+        base: &[TranscriptVersionSegment],
+        stats: &serde_json::Value,
+    ) -> Result<String, SqlxError> {
+        let version_id = format!("version-{}", Uuid::new_v4());
+        let segments_json = serde_json::to_string(segments)
+            .map_err(|e| SqlxError::Protocol(format!("Failed to serialize segments: {}", e)))?;
+        let base_ids: Vec<&str> = base.iter().map(|s| s.id.as_str()).collect();
+        let base_ids_json = serde_json::to_string(&base_ids)
+            .map_err(|e| SqlxError::Protocol(format!("Failed to serialize segment ids: {}", e)))?;
+        let now = Utc::now();
+
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query(
+            "UPDATE transcript_versions SET status = 'superseded', updated_at = ?
+             WHERE meeting_id = ? AND source = ? AND status = 'pending'",
+        )
+        .bind(now)
+        .bind(meeting_id)
+        .bind(source)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO transcript_versions (id, meeting_id, source, provider, model, status, segments, stats, base_segment_ids, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, 'pending', ?, ?, ?, ?, ?)",
+        )
+        .bind(&version_id)
+        .bind(meeting_id)
+        .bind(source)
+        .bind(provider)
+        .bind(model)
+        .bind(&segments_json)
+        .bind(stats.to_string())
+        .bind(&base_ids_json)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        info!(
+            "Stored pending transcript version {} for meeting {} ({} segments)",
+            version_id,
+            meeting_id,
+            segments.len()
+        );
+        Ok(version_id)
+    }
+
+    pub async fn list_versions(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptVersion>, SqlxError> {
+        sqlx::query_as::<_, TranscriptVersion>(
+            "SELECT * FROM transcript_versions WHERE meeting_id = ? ORDER BY created_at DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_version(
+        pool: &SqlitePool,
+        version_id: &str,
+    ) -> Result<Option<TranscriptVersion>, SqlxError> {
+        sqlx::query_as::<_, TranscriptVersion>("SELECT * FROM transcript_versions WHERE id = ?")
+            .bind(version_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Replace the meeting's transcripts with a pending version.
+    /// `merge` receives (version segments, current segments, ids the version was computed
+    /// from if recorded) and returns the rows to write, so edits made after the pass finished
+    /// can still be carried over.
+    /// Returns the rows written.
+    pub async fn accept_version<F>(
+        pool: &SqlitePool,
+        version_id: &str,
+        merge: F,
+    ) -> Result<Vec<TranscriptVersionSegment>, SqlxError>
+    where
+        F: FnOnce(
+            Vec<TranscriptVersionSegment>,
+            &[TranscriptVersionSegment],
+            Option<&[String]>,
+        ) -> Vec<TranscriptVersionSegment>,
+    {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let version = fetch_version_with_status(&mut transaction, version_id, "pending").await?;
+        let version_segments: Vec<TranscriptVersionSegment> = parse_segments(&version.segments)?;
+
+        let base_ids: Option<Vec<String>> = match version.base_segment_ids.as_deref() {
+            Some(json) => Some(serde_json::from_str(json).map_err(|e| {
+                SqlxError::Protocol(format!("Failed to parse segment ids: {}", e))
+            })?),
+            None => None,
+        };
+
+        let current = fetch_current_segments(&mut transaction, &version.meeting_id).await?;
+        let merged = merge(version_segments, &current, base_ids.as_deref());
+        let previous_json = serde_json::to_string(&current)
+            .map_err(|e| SqlxError::Protocol(format!("Failed to serialize segments: {}", e)))?;
+
+        replace_transcripts(&mut transaction, &version.meeting_id, &merged).await?;
+
+        let now = Utc::now();
+
+        // Only the latest accepted version can be reverted
+        sqlx::query(
+            "UPDATE transcript_versions SET status = 'superseded', updated_at = ?
+             WHERE meeting_id = ? AND status = 'accepted'",
+        )
+        .bind(now)
+        .bind(&version.meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query(
+            "UPDATE transcript_versions SET status = 'accepted', previous_segments = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&previous_json)
+        .bind(now)
+        .bind(version_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        info!(
+            "Accepted transcript version {} for meeting {} ({} segments)",
+            version_id,
+            version.meeting_id,
+            merged.len()
+        );
+        Ok(merged)
+    }
+
+    /// Restore the transcripts that an accepted version replaced. Returns the rows written.
+    pub async fn revert_version(
+        pool: &SqlitePool,
+        version_id: &str,
+    ) -> Result<Vec<TranscriptVersionSegment>, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let version = fetch_version_with_status(&mut transaction, version_id, "accepted").await?;
+        let previous = match version.previous_segments.as_deref() {
+            Some(json) => parse_segments(json)?,
+            None => {
+                transaction.rollback().await?;
+                return Err(SqlxError::Protocol(format!(
+                    "Transcript version {} has no previous transcript to restore",
+                    version_id
+                )));
+            }
+        };
+
+        replace_transcripts(&mut transaction, &version.meeting_id, &previous).await?;
+
+        sqlx::query("UPDATE transcript_versions SET status = 'reverted', updated_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(version_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        info!(
+            "Reverted transcript version {} for meeting {} ({} segments restored)",
+            version_id,
+            version.meeting_id,
+            previous.len()
+        );
+        Ok(previous)
+    }
+
+    /// Drop a pending version without touching the transcripts
+    pub async fn discard_version(pool: &SqlitePool, version_id: &str) -> Result<bool, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE transcript_versions SET status = 'discarded', updated_at = ? WHERE id = ? AND status = 'pending'",
+        )
+        .bind(Utc::now())
+        .bind(version_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+pub(super) async fn fetch_current_segments(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+) -> Result<Vec<TranscriptVersionSegment>, SqlxError> {
+    sqlx::query_as::<_, TranscriptVersionSegment>(
+        "SELECT id, transcript AS text, timestamp, audio_start_time, audio_end_time, duration, speaker, edited_at,
+                language, translation, word_timings
+         FROM transcripts WHERE meeting_id = ? ORDER BY audio_start_time ASC",
+    )
+    .bind(meeting_id)
+    .fetch_all(&mut *conn)
+    .await
+}
+
+async fn fetch_version_with_status(
+    conn: &mut SqliteConnection,
+    version_id: &str,
+    expected_status: &str,
+) -> Result<TranscriptVersion, SqlxError> {
+    let version = sqlx::query_as::<_, TranscriptVersion>("SELECT * FROM transcript_versions WHERE id = ?")
+        .bind(version_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    if version.status != expected_status {
+        return Err(SqlxError::Protocol(format!(
+            "Transcript version {} is {}, expected {}",
+            version_id, version.status, expected_status
+        )));
+    }
+    Ok(version)
+}
+
+pub(super) async fn replace_transcripts(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+    segments: &[TranscriptVersionSegment],
+) -> Result<(), SqlxError> {
+    // Segments without word timings keep the current ones while their text and times are unchanged
+    let current = fetch_current_segments(&mut *conn, meeting_id).await?;
+    let word_timings = |segment: &TranscriptVersionSegment| {
+        segment.word_timings.clone().or_else(|| {
+            current
+                .iter()
+                .find(|c| {
+                    c.id == segment.id
+                        && c.text == segment.text
+                        && c.audio_start_time == segment.audio_start_time
+                        && c.audio_end_time == segment.audio_end_time
+                })
+                .and_then(|c| c.word_timings.clone())
+        })
+    };
+
+    sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for segment in segments {
+        let result = sqlx::query(
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker, edited_at, language, translation, word_timings)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&segment.id)
+        .bind(meeting_id)
+        .bind(&segment.text)
+        .bind(&segment.timestamp)
+        .bind(segment.audio_start_time)
+        .bind(segment.audio_end_time)
+        .bind(segment.duration)
+        .bind(&segment.speaker)
+        .bind(&segment.edited_at)
+        .bind(&segment.language)
+        .bind(&segment.translation)
+        .bind(word_timings(segment))
+        .execute(&mut *conn)
+        .await;
+
+        if let Err(e) = result {
+            error!("Failed to write transcript segment for meeting {}: {}", meeting_id, e);
+            return Err(e);
+        }
+    }
+
+    sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+fn parse_segments(json: &str) -> Result<Vec<TranscriptVersionSegment>, SqlxError> {
+    serde_json::from_str(json)
+        .map_err(|e| SqlxError::Protocol(format!("Invalid transcript version segments: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn test_pool() -> SqlitePool {
+        // One connection, so every query sees the same in-memory database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES ('m', 'Meeting', ?, ?)")
+            .bind(Utc::now())
+            .bind(Utc::now())
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    fn segment(id: &str, text: &str) -> TranscriptVersionSegment {
+        crate::database::models::segment(id, text, 0.0, 5.0)
+    }
+
+    #[tokio::test]
+    async fn test_replace_keeps_word_timings_of_unchanged_segments() {
+        let pool = test_pool().await;
+        let original = [segment("a", "hello"), segment("b", "world")];
+        TranscriptVersionsRepository::replace_current_segments(&pool, "m", &original).await.unwrap();
+        sqlx::query("UPDATE transcripts SET word_timings = '[]' WHERE meeting_id = 'm'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // As a version accepted from a background pass: "a" untouched, "b" re-recognized
+        let accepted = [segment("a", "hello"), segment("b", "word")];
+        TranscriptVersionsRepository::replace_current_segments(&pool, "m", &accepted).await.unwrap();
+
+        let current = TranscriptVersionsRepository::get_current_segments(&pool, "m").await.unwrap();
+        let timings = |id: &str| current.iter().find(|s| s.id == id).unwrap().word_timings.clone();
+        assert_eq!(timings("a").as_deref(), Some("[]"));
+        assert_eq!(timings("b"), None);
+    }
+}
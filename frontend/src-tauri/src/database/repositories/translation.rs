@@ -0,0 +1,132 @@
+use crate::database::models::{MeetingTranslation, TranslatedSegment};
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct TranslationsRepository;
+
+impl TranslationsRepository {
+    /// Create (or restart) the translation of a meeting into `language`.
+    /// Existing tracks are kept until the new run completes.
+    pub async fn start_translation(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        language: &str,
+        provider: &str,
+        model: &str,
+    ) -> Result<(), SqlxError> {
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO meeting_translations (meeting_id, language, status, provider, model, created_at, updated_at)
+             VALUES (?, ?, 'running', ?, ?, ?, ?)
+             ON CONFLICT(meeting_id, language) DO UPDATE SET
+                status = 'running', provider = excluded.provider, model = excluded.model,
+                error = NULL, updated_at = excluded.updated_at",
+        )
+        .bind(meeting_id)
+        .bind(language)
+        .bind(provider)
+        .bind(model)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        info!("Started translation of meeting {} into {}", meeting_id, language);
+        Ok(())
+    }
+
+    pub async fn complete_translation(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        language: &str,
+        segments: &[TranslatedSegment],
+        summary: Option<&str>,
+    ) -> Result<(), SqlxError> {
+        let segments_json = serde_json::to_string(segments)
+            .map_err(|e| SqlxError::Protocol(format!("Failed to serialize translated segments: {}", e)))?;
+
+        sqlx::query(
+            "UPDATE meeting_translations
+             SET status = 'completed', segments = ?, summary = ?, error = NULL, updated_at = ?
+             WHERE meeting_id = ? AND language = ?",
+        )
+        .bind(&segments_json)
+        .bind(summary)
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .bind(language)
+        .execute(pool)
+        .await?;
+
+        info!(
+            "Stored {} translation for meeting {} ({} segments, summary: {})",
+            language,
+            meeting_id,
+            segments.len(),
+            summary.is_some()
+        );
+        Ok(())
+    }
+
+    /// Mark a run as failed or cancelled; tracks from an earlier successful run are kept
+    pub async fn finish_with_status(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        language: &str,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE meeting_translations SET status = ?, error = ?, updated_at = ?
+             WHERE meeting_id = ? AND language = ?",
+        )
+        .bind(status)
+        .bind(error)
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .bind(language)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_translation(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        language: &str,
+    ) -> Result<Option<MeetingTranslation>, SqlxError> {
+        sqlx::query_as::<_, MeetingTranslation>(
+            "SELECT * FROM meeting_translations WHERE meeting_id = ? AND language = ?",
+        )
+        .bind(meeting_id)
+        .bind(language)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn list_translations(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<MeetingTranslation>, SqlxError> {
+        sqlx::query_as::<_, MeetingTranslation>(
+            "SELECT * FROM meeting_translations WHERE meeting_id = ? ORDER BY language",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn delete_translation(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        language: &str,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM meeting_translations WHERE meeting_id = ? AND language = ?")
+            .bind(meeting_id)
+            .bind(language)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::database::models::VocabularyTerm;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+use uuid::Uuid;
+
+pub struct VocabularyRepository;
+
+impl VocabularyRepository {
+    /// Terms of one list: the user-wide list when `meeting_id` is None, otherwise the meeting's
+    pub async fn get_terms(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<VocabularyTerm>, SqlxError> {
+        match meeting_id {
+            Some(meeting_id) => {
+                sqlx::query_as::<_, VocabularyTerm>(
+                    "SELECT * FROM vocabulary_terms WHERE meeting_id = ? ORDER BY term COLLATE NOCASE",
+                )
+                .bind(meeting_id)
+                .fetch_all(pool)
+                .await
+            }
+            None => {
+                sqlx::query_as::<_, VocabularyTerm>(
+                    "SELECT * FROM vocabulary_terms WHERE meeting_id IS NULL ORDER BY term COLLATE NOCASE",
+                )
+                .fetch_all(pool)
+                .await
+            }
+        }
+    }
+
+    /// Terms that apply when transcribing a meeting: user-wide plus the meeting's own
+    pub async fn get_terms_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<VocabularyTerm>, SqlxError> {
+        sqlx::query_as::<_, VocabularyTerm>(
+            "SELECT * FROM vocabulary_terms WHERE meeting_id IS NULL OR meeting_id = ?
+             ORDER BY term COLLATE NOCASE",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn add_term(
+        pool: &SqlitePool,
+        term: &str,
+        sounds_like: &[String],
+        meeting_id: Option<&str>,
+    ) -> Result<VocabularyTerm, SqlxError> {
+        let id = format!("vocab-{}", Uuid::new_v4());
+        let now = Utc::now();
+
+        sqlx::query(
+            "INSERT INTO vocabulary_terms (id, meeting_id, term, sounds_like, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(term)
+        .bind(sounds_like_json(sounds_like)?)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        info!("Added vocabulary term '{}' (meeting: {:?})", term, meeting_id);
+        fetch_term(pool, &id).await?.ok_or(SqlxError::RowNotFound)
+    }
+
+    /// Returns None when the term does not exist
+    pub async fn update_term(
+        pool: &SqlitePool,
+        id: &str,
+        term: &str,
+        sounds_like: &[String],
+    ) -> Result<Option<VocabularyTerm>, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE vocabulary_terms SET term = ?, sounds_like = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(term)
+        .bind(sounds_like_json(sounds_like)?)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        fetch_term(pool, id).await
+    }
+
+    /// Returns the deleted term's meeting_id, or None when the term does not exist
+    pub async fn delete_term(pool: &SqlitePool, id: &str) -> Result<Option<Option<String>>, SqlxError> {
+        let existing = match fetch_term(pool, id).await? {
+            Some(existing) => existing,
+            None => return Ok(None),
+        };
+
+        sqlx::query("DELETE FROM vocabulary_terms WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        info!("Deleted vocabulary term '{}'", existing.term);
+        Ok(Some(existing.meeting_id))
+    }
+
+    /// Copies the list of meeting `from` onto meeting `to`; returns the number of terms copied
+    pub async fn copy_meeting_terms(pool: &SqlitePool, from: &str, to: &str) -> Result<usize, SqlxError> {
+        let terms = Self::get_terms(pool, Some(from)).await?;
+        let now = Utc::now();
+        let mut transaction = pool.begin().await?;
+        for term in &terms {
+            sqlx::query(
+                "INSERT INTO vocabulary_terms (id, meeting_id, term, sounds_like, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(format!("vocab-{}", Uuid::new_v4()))
+            .bind(to)
+            .bind(&term.term)
+            .bind(&term.sounds_like)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+
+        info!("Copied {} vocabulary terms from meeting {} to {}", terms.len(), from, to);
+        Ok(terms.len())
+    }
+}
+
+async fn fetch_term(pool: &SqlitePool, id: &str) -> Result<Option<VocabularyTerm>, SqlxError> {
+    sqlx::query_as::<_, VocabularyTerm>("SELECT * FROM vocabulary_terms WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+fn sounds_like_json(sounds_like: &[String]) -> Result<Option<String>, SqlxError> {
+    let cleaned: Vec<&str> = sounds_like
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    if cleaned.is_empty() {
+        return Ok(None);
+    }
+    serde_json::to_string(&cleaned)
+        .map(Some)
+        .map_err(|e| SqlxError::Protocol(format!("Failed to serialize sounds_like: {}", e)))
+}
@@ -7,6 +7,19 @@ use crate::state::AppState;
 /// Initialize database on app startup
 /// Handles first launch detection and conditional initialization
 pub async fn initialize_database_on_startup(app: &AppHandle) -> Result<(), String> {
+    // A passphrase-protected library stays closed until the frontend unlocks it
+    if crate::encryption::is_locked() {
+        info!("Encrypted library is locked - waiting for the passphrase");
+        let app_handle = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+            app_handle
+                .emit("library-locked", ())
+                .expect("Failed to emit library-locked event");
+        });
+        return Ok(());
+    }
+
     // Check if this is the first launch (no database exists yet)
     let is_first_launch = DatabaseManager::is_first_launch(app)
         .await
@@ -30,8 +43,12 @@ pub async fn initialize_database_on_startup(app: &AppHandle) -> Result<(), Strin
             .await
             .map_err(|e| format!("Failed to initialize database manager: {}", e))?;
 
+        let pool = db_manager.pool().clone();
         app.manage(AppState { db_manager });
         info!("Database initialized successfully");
+
+        // Encrypt meeting files still stored in plaintext
+        crate::encryption::spawn_library_sweep(pool);
     }
 
     Ok(())
@@ -0,0 +1,54 @@
+use super::LibraryEncryptionStatus;
+use crate::database::manager::DatabaseManager;
+use crate::state::AppState;
+use log::info;
+use tauri::{AppHandle, Emitter, Manager};
+
+#[tauri::command]
+pub async fn api_get_library_encryption_status() -> Result<LibraryEncryptionStatus, String> {
+    Ok(super::status())
+}
+
+/// Turns on encrypted library mode. Without a passphrase the key is kept in the OS keyring
+/// (or secrets vault). Meeting files are encrypted in the background; the database is
+/// encrypted on the next start, reported as `pending_restart` in the returned status.
+#[tauri::command]
+pub async fn api_enable_library_encryption(
+    app: AppHandle,
+    passphrase: Option<String>,
+) -> Result<LibraryEncryptionStatus, String> {
+    super::enable(passphrase.as_deref().filter(|p| !p.is_empty()))?;
+    if let Some(state) = app.try_state::<AppState>() {
+        super::spawn_library_sweep(state.db_manager.pool().clone());
+    }
+    Ok(super::status())
+}
+
+/// Unlocks a passphrase-protected library and opens the database
+#[tauri::command]
+pub async fn api_unlock_library(app: AppHandle, passphrase: String) -> Result<(), String> {
+    super::unlock(&passphrase)?;
+    open_unlocked_library(&app).await
+}
+
+/// Opens the database of a library that was just unlocked, unless it is open already
+pub(crate) async fn open_unlocked_library(app: &AppHandle) -> Result<(), String> {
+    if app.try_state::<AppState>().is_some() {
+        return Ok(());
+    }
+
+    let db_manager = DatabaseManager::new_from_app_handle(app)
+        .await
+        .map_err(|e| format!("Failed to open the encrypted database: {}", e))?;
+    let pool = db_manager.pool().clone();
+    app.manage(AppState { db_manager });
+    info!("Encrypted library unlocked and database initialized");
+    super::spawn_library_sweep(pool);
+
+    // The background tasks bail out at startup while the library is locked
+    crate::start_background_tasks(app);
+
+    app.emit("database-initialized", ())
+        .map_err(|e| format!("Failed to emit database-initialized event: {}", e))?;
+    Ok(())
+}
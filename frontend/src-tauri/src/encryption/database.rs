@@ -0,0 +1,70 @@
+//! SQLCipher encryption of the meeting database.
+//!
+//! The database is opened with the library key as a raw SQLCipher key. A plaintext database
+//! (from before the library was encrypted, or a copied legacy database) is converted with
+//! `sqlcipher_export` before it is opened.
+
+use crate::secrets::cipher::KEY_LEN;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection};
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+const PLAINTEXT_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Value for `PRAGMA key` / `ATTACH ... KEY` passing the key as raw bytes
+fn key_literal(key: &[u8; KEY_LEN]) -> String {
+    format!("\"x'{}'\"", hex::encode(key))
+}
+
+/// Connection options for the meeting database, keyed while the library is encrypted
+pub fn connect_options(db_path: &str) -> Result<SqliteConnectOptions, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(db_path)?;
+    Ok(match super::library_key() {
+        Some(key) => options.pragma("key", key_literal(&key)),
+        None => options,
+    })
+}
+
+fn is_plaintext_database(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map(|_| &header == PLAINTEXT_HEADER)
+        .unwrap_or(false)
+}
+
+/// Converts a plaintext database to SQLCipher when the library is encrypted; no-op otherwise
+pub async fn encrypt_plaintext_database(db_path: &str) -> Result<(), sqlx::Error> {
+    let Some(key) = super::library_key() else {
+        return Ok(());
+    };
+    let path = Path::new(db_path);
+    if !is_plaintext_database(path) {
+        return Ok(());
+    }
+
+    log::info!("Encrypting meeting database {}", db_path);
+    let encrypted_path = format!("{}.encrypting", db_path);
+    let _ = std::fs::remove_file(&encrypted_path);
+
+    let mut conn = SqliteConnectOptions::from_str(db_path)?.connect().await?;
+    sqlx::query(&format!(
+        "ATTACH DATABASE '{}' AS encrypted KEY {}",
+        encrypted_path.replace('\'', "''"),
+        key_literal(&key)
+    ))
+    .execute(&mut conn)
+    .await?;
+    sqlx::query("SELECT sqlcipher_export('encrypted')").execute(&mut conn).await?;
+    sqlx::query("DETACH DATABASE encrypted").execute(&mut conn).await?;
+    conn.close().await?;
+
+    std::fs::rename(&encrypted_path, db_path).map_err(sqlx::Error::Io)?;
+    for suffix in ["-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", db_path, suffix));
+    }
+    log::info!("Meeting database encrypted");
+    Ok(())
+}
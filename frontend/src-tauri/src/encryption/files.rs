@@ -0,0 +1,515 @@
+//! Encrypted library files.
+//!
+//! An encrypted file keeps its name with `.enc` appended (`audio.mp4.enc`). Layout: `IQENC`,
+//! a version byte and a random 16-byte nonce prefix, then the contents in 64 KiB chunks, each
+//! sealed with XChaCha20-Poly1305 (nonce = prefix + chunk index, the last chunk flagged in the
+//! associated data), so reordered, truncated or modified files fail to decrypt.
+
+use crate::audio::constants::AUDIO_EXTENSIONS;
+use crate::secrets::cipher::KEY_LEN;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use log::{info, warn};
+use rand::RngCore;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 5] = b"IQENC";
+const VERSION: u8 = 1;
+const PREFIX_LEN: usize = 16;
+const HEADER_LEN: usize = MAGIC.len() + 1 + PREFIX_LEN;
+const CHUNK_LEN: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+
+/// Extension appended to encrypted files
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Name prefix of decrypted temporary copies
+const PLAINTEXT_COPY_PREFIX: &str = ".iqcapture_plain_";
+
+/// Meeting folder files (besides audio) holding meeting content
+const LIBRARY_FILES: &[&str] = &["transcripts.json", "metadata.json", "markers.json", "live_summary.json"];
+
+fn chunk_nonce(prefix: &[u8], index: u64) -> XNonce {
+    let mut nonce = [0u8; PREFIX_LEN + 8];
+    nonce[..PREFIX_LEN].copy_from_slice(prefix);
+    nonce[PREFIX_LEN..].copy_from_slice(&index.to_be_bytes());
+    XNonce::clone_from_slice(&nonce)
+}
+
+fn chunk_aad(header: &[u8], last: bool) -> Vec<u8> {
+    let mut aad = header.to_vec();
+    aad.push(last as u8);
+    aad
+}
+
+/// Reads until `buf` is full or the reader is exhausted
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Header of a new encrypted stream, with a random nonce prefix
+fn new_header() -> [u8; HEADER_LEN] {
+    let mut header = [0u8; HEADER_LEN];
+    header[..MAGIC.len()].copy_from_slice(MAGIC);
+    header[MAGIC.len()] = VERSION;
+    rand::rngs::OsRng.fill_bytes(&mut header[MAGIC.len() + 1..]);
+    header
+}
+
+/// Encrypts everything from `reader` into `writer`
+pub fn encrypt_stream(key: &[u8; KEY_LEN], reader: &mut impl Read, writer: &mut impl Write) -> Result<(), String> {
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+    let header = new_header();
+    writer.write_all(&header).map_err(|e| format!("Write error: {}", e))?;
+
+    let prefix = &header[MAGIC.len() + 1..];
+    let mut current = vec![0u8; CHUNK_LEN];
+    let mut next = vec![0u8; CHUNK_LEN];
+    let mut current_len = read_full(reader, &mut current).map_err(|e| format!("Read error: {}", e))?;
+    let mut index = 0u64;
+    loop {
+        // Look ahead one chunk to know whether the current one is the last
+        let next_len = read_full(reader, &mut next).map_err(|e| format!("Read error: {}", e))?;
+        let last = next_len == 0;
+        let sealed = cipher
+            .encrypt(
+                &chunk_nonce(prefix, index),
+                Payload { msg: &current[..current_len], aad: &chunk_aad(&header, last) },
+            )
+            .map_err(|_| "Encryption failed".to_string())?;
+        writer.write_all(&sealed).map_err(|e| format!("Write error: {}", e))?;
+        if last {
+            return Ok(());
+        }
+        std::mem::swap(&mut current, &mut next);
+        current_len = next_len;
+        index += 1;
+    }
+}
+
+/// Writing counterpart of `encrypt_stream` (same layout), for data that is produced by a
+/// writer. `finish` seals the last chunk; a writer dropped without it leaves a truncated stream.
+pub struct EncryptingWriter<W: Write> {
+    cipher: XChaCha20Poly1305,
+    header: [u8; HEADER_LEN],
+    inner: W,
+    chunk: Vec<u8>,
+    index: u64,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    pub fn new(key: &[u8; KEY_LEN], mut inner: W) -> Result<Self, String> {
+        let header = new_header();
+        inner.write_all(&header).map_err(|e| format!("Write error: {}", e))?;
+        Ok(Self {
+            cipher: XChaCha20Poly1305::new(Key::from_slice(key)),
+            header,
+            inner,
+            chunk: Vec::with_capacity(CHUNK_LEN),
+            index: 0,
+        })
+    }
+
+    fn seal_chunk(&mut self, last: bool) -> std::io::Result<()> {
+        let sealed = self
+            .cipher
+            .encrypt(
+                &chunk_nonce(&self.header[MAGIC.len() + 1..], self.index),
+                Payload { msg: &self.chunk, aad: &chunk_aad(&self.header, last) },
+            )
+            .map_err(|_| std::io::Error::other("Encryption failed"))?;
+        self.inner.write_all(&sealed)?;
+        self.chunk.clear();
+        self.index += 1;
+        Ok(())
+    }
+
+    /// Seals the last chunk and returns the inner writer
+    pub fn finish(mut self) -> Result<W, String> {
+        self.seal_chunk(true).map_err(|e| format!("Write error: {}", e))?;
+        self.inner.flush().map_err(|e| format!("Write error: {}", e))?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // A full chunk is sealed once more data arrives, so only `finish` seals the last one
+        if self.chunk.len() == CHUNK_LEN {
+            self.seal_chunk(false)?;
+        }
+        let len = buf.len().min(CHUNK_LEN - self.chunk.len());
+        self.chunk.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decrypts a stream produced by `encrypt_stream`
+pub fn decrypt_stream(key: &[u8; KEY_LEN], reader: &mut impl Read, writer: &mut impl Write) -> Result<(), String> {
+    let mut header = [0u8; HEADER_LEN];
+    if read_full(reader, &mut header).map_err(|e| format!("Read error: {}", e))? < HEADER_LEN
+        || &header[..MAGIC.len()] != MAGIC
+    {
+        return Err("Not an encrypted library file".to_string());
+    }
+    if header[MAGIC.len()] != VERSION {
+        return Err(format!("Unsupported encrypted file version {}", header[MAGIC.len()]));
+    }
+
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+    let prefix = &header[MAGIC.len() + 1..];
+    let mut current = vec![0u8; CHUNK_LEN + TAG_LEN];
+    let mut next = vec![0u8; CHUNK_LEN + TAG_LEN];
+    let mut current_len = read_full(reader, &mut current).map_err(|e| format!("Read error: {}", e))?;
+    let mut index = 0u64;
+    loop {
+        let next_len = read_full(reader, &mut next).map_err(|e| format!("Read error: {}", e))?;
+        let last = next_len == 0;
+        let plaintext = cipher
+            .decrypt(
+                &chunk_nonce(prefix, index),
+                Payload { msg: &current[..current_len], aad: &chunk_aad(&header, last) },
+            )
+            .map_err(|_| "Decryption failed (wrong key, or the file is damaged or truncated)".to_string())?;
+        writer.write_all(&plaintext).map_err(|e| format!("Write error: {}", e))?;
+        if last {
+            return Ok(());
+        }
+        std::mem::swap(&mut current, &mut next);
+        current_len = next_len;
+        index += 1;
+    }
+}
+
+fn library_key() -> Result<[u8; KEY_LEN], String> {
+    super::library_key().ok_or_else(|| "The encrypted library is locked".to_string())
+}
+
+pub fn is_encrypted_path(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some(ENCRYPTED_EXTENSION)
+}
+
+/// `path` with `.enc` appended
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(ENCRYPTED_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Path of the plaintext file an encrypted path stands for (`audio.mp4.enc` -> `audio.mp4`)
+pub fn plaintext_path(path: &Path) -> PathBuf {
+    if is_encrypted_path(path) {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Whether the file exists, in plaintext or encrypted
+pub fn exists(path: &Path) -> bool {
+    path.exists() || encrypted_path(path).exists()
+}
+
+/// Reads a library file, decrypting it when it is stored encrypted (`path` may name either form)
+pub fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+    let encrypted = if is_encrypted_path(path) {
+        path.to_path_buf()
+    } else if path.exists() {
+        return std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e));
+    } else {
+        encrypted_path(path)
+    };
+
+    let key = library_key()?;
+    let file = std::fs::File::open(&encrypted).map_err(|e| format!("Failed to read {}: {}", encrypted.display(), e))?;
+    let mut plaintext = Vec::new();
+    decrypt_stream(&key, &mut std::io::BufReader::new(file), &mut plaintext)
+        .map_err(|e| format!("{}: {}", encrypted.display(), e))?;
+    Ok(plaintext)
+}
+
+pub fn read_to_string(path: &Path) -> Result<String, String> {
+    String::from_utf8(read_file(path)?).map_err(|e| format!("{} is not valid UTF-8: {}", path.display(), e))
+}
+
+/// Temporary sibling of `path` (`.name.tmp`) used for atomic writes
+fn temp_sibling(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!(".{}.tmp", name))
+}
+
+/// Atomically writes a library file; encrypted (as `path.enc`) while the library is encrypted
+pub fn write_file(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if !super::is_enabled() {
+        let temp_path = temp_sibling(path);
+        std::fs::write(&temp_path, contents).map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+        return std::fs::rename(&temp_path, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e));
+    }
+
+    let key = library_key()?;
+    let destination = encrypted_path(path);
+    let temp_path = temp_sibling(&destination);
+    let mut sealed = Vec::with_capacity(contents.len() + HEADER_LEN + TAG_LEN);
+    encrypt_stream(&key, &mut &contents[..], &mut sealed)?;
+    std::fs::write(&temp_path, sealed).map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    std::fs::rename(&temp_path, &destination)
+        .map_err(|e| format!("Failed to replace {}: {}", destination.display(), e))?;
+    if path.exists() {
+        std::fs::remove_file(path).map_err(|e| format!("Failed to remove plaintext {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Encrypts a plaintext file in place (`path` -> `path.enc`) while the library is encrypted.
+/// Returns the path the file is now stored at.
+pub fn seal_file(path: &Path) -> Result<PathBuf, String> {
+    if !super::is_enabled() || is_encrypted_path(path) || !path.exists() {
+        return Ok(path.to_path_buf());
+    }
+    let key = library_key()?;
+    let destination = encrypted_path(path);
+    let temp_path = temp_sibling(&destination);
+
+    let result = (|| {
+        let input = std::fs::File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let output =
+            std::fs::File::create(&temp_path).map_err(|e| format!("Failed to create {}: {}", temp_path.display(), e))?;
+        let mut writer = std::io::BufWriter::new(output);
+        encrypt_stream(&key, &mut std::io::BufReader::new(input), &mut writer)?;
+        writer
+            .into_inner()
+            .map_err(|e| format!("Write error: {}", e))?
+            .sync_all()
+            .map_err(|e| format!("Write error: {}", e))?;
+        std::fs::rename(&temp_path, &destination).map_err(|e| format!("Failed to replace {}: {}", destination.display(), e))
+    })();
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    std::fs::remove_file(path).map_err(|e| format!("Failed to remove plaintext {}: {}", path.display(), e))?;
+    Ok(destination)
+}
+
+/// Private directory for decrypted copies, outside the library (only the user can read it)
+fn plaintext_copies_dir() -> Result<PathBuf, String> {
+    let dir = dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("IQcapture")
+        .join("plain");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))
+            .map_err(|e| format!("Failed to restrict {}: {}", dir.display(), e))?;
+    }
+    Ok(dir)
+}
+
+/// Temporary directory for decrypted working files (e.g. a database export) in the private
+/// directory of decrypted copies; removed when dropped, or by the next startup after a crash
+pub fn plaintext_work_dir() -> Result<tempfile::TempDir, String> {
+    tempfile::Builder::new()
+        .prefix(PLAINTEXT_COPY_PREFIX)
+        .tempdir_in(plaintext_copies_dir()?)
+        .map_err(|e| format!("Failed to create temporary directory: {}", e))
+}
+
+/// Decrypts an encrypted file into a temporary file in a private directory outside the
+/// library (deleted when the guard drops), for tools like ffmpeg that need a plain file path
+pub fn decrypted_copy(path: &Path) -> Result<tempfile::TempPath, String> {
+    let key = library_key()?;
+    let suffix = plaintext_path(path)
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let mut temp = tempfile::Builder::new()
+        .prefix(PLAINTEXT_COPY_PREFIX)
+        .suffix(&suffix)
+        .tempfile_in(plaintext_copies_dir()?)
+        .map_err(|e| format!("Failed to create temporary file: {}", e))?;
+    let input = std::fs::File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    decrypt_stream(&key, &mut std::io::BufReader::new(input), temp.as_file_mut())
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(temp.into_temp_path())
+}
+
+fn is_library_file(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if name.starts_with('.') {
+        return false;
+    }
+    LIBRARY_FILES.contains(&name.as_str())
+        || path
+            .extension()
+            .map(|e| AUDIO_EXTENSIONS.contains(&e.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false)
+}
+
+fn remove_plaintext_copies_in(dir: &Path) -> usize {
+    let mut removed = 0;
+    for path in std::fs::read_dir(dir).into_iter().flatten().flatten().map(|entry| entry.path()) {
+        let is_copy = path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with(PLAINTEXT_COPY_PREFIX));
+        if !is_copy {
+            continue;
+        }
+        let result = if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        match result {
+            Ok(()) => removed += 1,
+            Err(e) => warn!("Failed to remove decrypted copy {}: {}", path.display(), e),
+        }
+    }
+    removed
+}
+
+/// Removes decrypted copies left behind by a crash: the private copies directory, and copies
+/// that older versions wrote into meeting folders and their checkpoints. Must only run
+/// before any copy of this session is made.
+pub fn remove_stale_plaintext_copies(folders: &[PathBuf]) -> usize {
+    let mut removed = plaintext_copies_dir().map(|dir| remove_plaintext_copies_in(&dir)).unwrap_or(0);
+    for folder in folders {
+        removed += remove_plaintext_copies_in(folder);
+        removed += remove_plaintext_copies_in(&folder.join(".checkpoints"));
+    }
+    removed
+}
+
+/// Encrypts the plaintext meeting content (audio, transcript and metadata files, audio
+/// checkpoints) of a meeting folder. Returns the number of files encrypted.
+pub fn seal_folder(folder: &Path) -> usize {
+    if !super::is_enabled() || !folder.is_dir() {
+        return 0;
+    }
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(folder)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_library_file(path))
+        .collect();
+    candidates.extend(
+        std::fs::read_dir(folder.join(".checkpoints"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().and_then(|e| e.to_str()) == Some("mp4")
+                    && !path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.'))
+            }),
+    );
+
+    let mut sealed = 0;
+    for path in candidates {
+        match seal_file(&path) {
+            Ok(_) => sealed += 1,
+            Err(e) => warn!("Failed to encrypt {}: {}", path.display(), e),
+        }
+    }
+    if sealed > 0 {
+        info!("Encrypted {} files in {}", sealed, folder.display());
+    }
+    sealed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(len: usize) {
+        let key = [7u8; KEY_LEN];
+        let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        let mut sealed = Vec::new();
+        encrypt_stream(&key, &mut &data[..], &mut sealed).unwrap();
+        let chunks = len.div_ceil(CHUNK_LEN).max(1);
+        assert_eq!(sealed.len(), HEADER_LEN + len + chunks * TAG_LEN);
+
+        let mut opened = Vec::new();
+        decrypt_stream(&key, &mut &sealed[..], &mut opened).unwrap();
+        assert_eq!(opened, data);
+    }
+
+    #[test]
+    fn test_stream_roundtrip_across_chunk_sizes() {
+        for len in [0, 1, CHUNK_LEN - 1, CHUNK_LEN, CHUNK_LEN + 1, 3 * CHUNK_LEN + 17] {
+            roundtrip(len);
+        }
+    }
+
+    #[test]
+    fn test_encrypting_writer_matches_stream_layout() {
+        let key = [7u8; KEY_LEN];
+        for len in [0, 1, CHUNK_LEN, CHUNK_LEN + 1, 3 * CHUNK_LEN + 17] {
+            let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let mut writer = EncryptingWriter::new(&key, Vec::new()).unwrap();
+            // Odd write sizes cross the chunk boundaries
+            for piece in data.chunks(1000) {
+                writer.write_all(piece).unwrap();
+            }
+            let sealed = writer.finish().unwrap();
+            assert_eq!(sealed.len(), HEADER_LEN + len + len.div_ceil(CHUNK_LEN).max(1) * TAG_LEN);
+
+            let mut opened = Vec::new();
+            decrypt_stream(&key, &mut &sealed[..], &mut opened).unwrap();
+            assert_eq!(opened, data);
+        }
+    }
+
+    #[test]
+    fn test_decrypt_rejects_truncation_reordering_and_wrong_key() {
+        let key = [7u8; KEY_LEN];
+        let data = vec![42u8; 2 * CHUNK_LEN + 10];
+        let mut sealed = Vec::new();
+        encrypt_stream(&key, &mut &data[..], &mut sealed).unwrap();
+
+        // Dropping the last chunk leaves a valid-looking but non-final chunk at the end
+        let truncated = &sealed[..HEADER_LEN + 2 * (CHUNK_LEN + TAG_LEN)];
+        assert!(decrypt_stream(&key, &mut &truncated[..], &mut Vec::new()).is_err());
+
+        let mut swapped = sealed.clone();
+        let (a, b) = (HEADER_LEN, HEADER_LEN + CHUNK_LEN + TAG_LEN);
+        let first: Vec<u8> = swapped[a..b].to_vec();
+        let second: Vec<u8> = swapped[b..b + CHUNK_LEN + TAG_LEN].to_vec();
+        swapped[a..b].copy_from_slice(&second);
+        swapped[b..b + CHUNK_LEN + TAG_LEN].copy_from_slice(&first);
+        assert!(decrypt_stream(&key, &mut &swapped[..], &mut Vec::new()).is_err());
+
+        assert!(decrypt_stream(&[8u8; KEY_LEN], &mut &sealed[..], &mut Vec::new()).is_err());
+        assert!(decrypt_stream(&key, &mut &b"plain mp4 data"[..], &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_path_names() {
+        let path = Path::new("/meetings/Standup/audio.mp4");
+        let encrypted = encrypted_path(path);
+        assert_eq!(encrypted, Path::new("/meetings/Standup/audio.mp4.enc"));
+        assert!(is_encrypted_path(&encrypted));
+        assert!(!is_encrypted_path(path));
+        assert_eq!(plaintext_path(&encrypted), path);
+        assert_eq!(plaintext_path(path), path);
+    }
+}
@@ -0,0 +1,313 @@
+//! Optional encrypted library mode.
+//!
+//! When enabled, the meeting database is encrypted with SQLCipher and meeting folder content
+//! (audio, audio checkpoints, transcripts.json, metadata.json, ...) is stored encrypted (see
+//! `files`), all under one 256-bit library key. The key is either random and kept in the secret
+//! store (`crate::secrets`), or derived from a passphrase with Argon2id and asked for at startup.
+//! `library_encryption.json` in the app data directory records the mode, the salt and a key
+//! check value; it holds no key material.
+
+pub mod commands;
+pub mod database;
+pub mod files;
+
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::secrets::cipher::{self, KEY_LEN};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+const CONFIG_FILE: &str = "library_encryption.json";
+
+/// Secret store entry holding the library key (secret store mode)
+const LIBRARY_KEY_SECRET: &str = "library.key";
+
+/// Sealed with the library key to tell a wrong passphrase from the right one
+const KEY_CHECK_PLAINTEXT: &[u8] = b"iqcapture-library-key";
+
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Where the library key comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// Random key held in the OS keyring or secrets vault
+    SecretStore,
+    /// Derived from a passphrase entered at startup
+    Passphrase,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptionConfig {
+    enabled: bool,
+    key_source: KeySource,
+    /// Argon2 salt (passphrase mode), hex
+    #[serde(default)]
+    salt: Option<String>,
+    /// `KEY_CHECK_PLAINTEXT` sealed with the library key, hex
+    key_check: String,
+    enabled_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryEncryptionStatus {
+    pub enabled: bool,
+    pub key_source: Option<KeySource>,
+    /// Enabled but the passphrase has not been entered yet
+    pub locked: bool,
+    /// Enabled during this session: meeting files are encrypted, but the open database stays
+    /// plaintext until the app is restarted
+    pub pending_restart: bool,
+}
+
+#[derive(Default)]
+struct LibraryState {
+    config_path: Option<PathBuf>,
+    config: Option<EncryptionConfig>,
+    key: Option<[u8; KEY_LEN]>,
+    /// Enabled while the plaintext database was open; it is converted on the next start
+    database_pending: bool,
+}
+
+static STATE: Lazy<RwLock<LibraryState>> = Lazy::new(|| RwLock::new(LibraryState::default()));
+
+// Decrypted copies are only made once the library is open, so the first sweep finds leftovers only
+static STALE_COPIES_REMOVED: AtomicBool = AtomicBool::new(false);
+
+/// Loads the encryption mode. Runs at startup after `secrets::init` and before the database
+/// is opened; in secret store mode the library is unlocked right away.
+pub fn init(app_data_dir: &Path) {
+    let config_path = app_data_dir.join(CONFIG_FILE);
+    let config = match std::fs::read_to_string(&config_path) {
+        Ok(json) => match serde_json::from_str::<EncryptionConfig>(&json) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                warn!("Ignoring unreadable {}: {}", CONFIG_FILE, e);
+                None
+            }
+        },
+        Err(_) => None,
+    };
+
+    let mut key = None;
+    if let Some(config) = config.as_ref().filter(|c| c.enabled && c.key_source == KeySource::SecretStore) {
+        match load_secret_key(config) {
+            Ok(loaded) => key = Some(loaded),
+            Err(e) => warn!("Encrypted library stays locked: {}", e),
+        }
+    }
+
+    if let Some(config) = config.as_ref().filter(|c| c.enabled) {
+        info!(
+            "Encrypted library mode ({:?}), {}",
+            config.key_source,
+            if key.is_some() { "unlocked" } else { "locked" }
+        );
+    }
+    if let Ok(mut state) = STATE.write() {
+        *state = LibraryState {
+            config_path: Some(config_path),
+            config,
+            key,
+            database_pending: false,
+        };
+    }
+}
+
+fn load_secret_key(config: &EncryptionConfig) -> Result<[u8; KEY_LEN], String> {
+    let encoded = crate::secrets::get_secret(LIBRARY_KEY_SECRET)?
+        .ok_or_else(|| "library key is missing from the secret store".to_string())?;
+    let key = decode_key(&encoded)?;
+    verify_key(config, &key)?;
+    Ok(key)
+}
+
+fn decode_key(encoded: &str) -> Result<[u8; KEY_LEN], String> {
+    hex::decode(encoded)
+        .ok()
+        .and_then(|bytes| <[u8; KEY_LEN]>::try_from(bytes).ok())
+        .ok_or_else(|| "library key is malformed".to_string())
+}
+
+fn verify_key(config: &EncryptionConfig, key: &[u8; KEY_LEN]) -> Result<(), String> {
+    let sealed = hex::decode(&config.key_check).map_err(|_| "key check value is malformed".to_string())?;
+    match cipher::open(key, &sealed, b"") {
+        Ok(plaintext) if plaintext == KEY_CHECK_PLAINTEXT => Ok(()),
+        _ => Err("wrong library key".to_string()),
+    }
+}
+
+pub fn is_enabled() -> bool {
+    STATE
+        .read()
+        .map(|state| state.config.as_ref().is_some_and(|c| c.enabled))
+        .unwrap_or(false)
+}
+
+/// Enabled but not unlocked yet (passphrase mode before the passphrase was entered)
+pub fn is_locked() -> bool {
+    STATE
+        .read()
+        .map(|state| state.config.as_ref().is_some_and(|c| c.enabled) && state.key.is_none())
+        .unwrap_or(false)
+}
+
+/// The library key, None when encryption is off or the library is locked
+pub fn library_key() -> Option<[u8; KEY_LEN]> {
+    STATE.read().ok().and_then(|state| state.key)
+}
+
+pub fn status() -> LibraryEncryptionStatus {
+    let state = STATE.read();
+    let config = state.as_ref().ok().and_then(|s| s.config.clone()).filter(|c| c.enabled);
+    let unlocked = state.as_ref().map(|s| s.key.is_some()).unwrap_or(false);
+    let database_pending = state.as_ref().map(|s| s.database_pending).unwrap_or(false);
+    LibraryEncryptionStatus {
+        enabled: config.is_some(),
+        key_source: config.as_ref().map(|c| c.key_source),
+        locked: config.is_some() && !unlocked,
+        pending_restart: config.is_some() && database_pending,
+    }
+}
+
+/// Turns on encrypted library mode with a new key (from `passphrase`, or random and kept in
+/// the secret store). Existing meeting files are encrypted by `spawn_library_sweep`; the
+/// database is converted the next time it is opened, which the status reports as
+/// `pending_restart` until then.
+pub fn enable(passphrase: Option<&str>) -> Result<(), String> {
+    let mut state = STATE.write().map_err(|_| "Encryption state lock is poisoned".to_string())?;
+    if state.config.as_ref().is_some_and(|c| c.enabled) {
+        return Err("The library is already encrypted".to_string());
+    }
+    let config_path = state
+        .config_path
+        .clone()
+        .ok_or_else(|| "Library encryption is not initialized".to_string())?;
+
+    let (key, salt, key_source) = match passphrase {
+        Some(passphrase) => {
+            if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+                return Err(format!("The passphrase needs at least {} characters", MIN_PASSPHRASE_LEN));
+            }
+            let salt = cipher::random_salt();
+            let key = cipher::derive_key(passphrase.as_bytes(), &salt)?;
+            (key, Some(hex::encode(salt)), KeySource::Passphrase)
+        }
+        None => {
+            let mut key = [0u8; KEY_LEN];
+            rand::rngs::OsRng.fill_bytes(&mut key);
+            crate::secrets::set_secret(LIBRARY_KEY_SECRET, &hex::encode(key))?;
+            (key, None, KeySource::SecretStore)
+        }
+    };
+
+    let config = EncryptionConfig {
+        enabled: true,
+        key_source,
+        salt,
+        key_check: hex::encode(cipher::seal(&key, KEY_CHECK_PLAINTEXT, b"")?),
+        enabled_at: Utc::now(),
+    };
+    write_config(&config_path, &config)?;
+    info!("Encrypted library mode enabled ({:?})", key_source);
+
+    state.config = Some(config);
+    state.key = Some(key);
+    state.database_pending = true;
+    Ok(())
+}
+
+/// Unlocks a passphrase-protected library
+pub fn unlock(passphrase: &str) -> Result<(), String> {
+    let mut state = STATE.write().map_err(|_| "Encryption state lock is poisoned".to_string())?;
+    let config = state
+        .config
+        .clone()
+        .filter(|c| c.enabled)
+        .ok_or_else(|| "The library is not encrypted".to_string())?;
+    if state.key.is_some() {
+        return Ok(());
+    }
+    let salt = config
+        .salt
+        .as_deref()
+        .and_then(|salt| hex::decode(salt).ok())
+        .ok_or_else(|| "The library is not passphrase-protected".to_string())?;
+    let key = cipher::derive_key(passphrase.as_bytes(), &salt)?;
+    verify_key(&config, &key).map_err(|_| "Wrong passphrase".to_string())?;
+
+    state.key = Some(key);
+    info!("Encrypted library unlocked");
+    Ok(())
+}
+
+/// Loads the library key of a secret store mode library that stayed locked because the secret
+/// store was not open at startup. Returns true when this unlocked the library.
+pub fn unlock_from_secret_store() -> Result<bool, String> {
+    let mut state = STATE.write().map_err(|_| "Encryption state lock is poisoned".to_string())?;
+    let Some(config) = state
+        .config
+        .clone()
+        .filter(|c| c.enabled && c.key_source == KeySource::SecretStore)
+    else {
+        return Ok(false);
+    };
+    if state.key.is_some() {
+        return Ok(false);
+    }
+    state.key = Some(load_secret_key(&config)?);
+    info!("Encrypted library unlocked from the secret store");
+    Ok(true)
+}
+
+fn write_config(path: &Path, config: &EncryptionConfig) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(config).map_err(|e| format!("Serialize error: {}", e))?;
+    // Atomic write: temp file then rename
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, json).map_err(|e| format!("Write error: {}", e))?;
+    std::fs::rename(&temp_path, path).map_err(|e| format!("Rename error: {}", e))
+}
+
+/// Encrypts plaintext files left in the folders of all meetings (e.g. written before the
+/// library was encrypted, or by an import) in the background. The first sweep of a session
+/// (at startup or unlock) also removes decrypted copies left behind by a crash.
+pub fn spawn_library_sweep(pool: SqlitePool) {
+    if !is_enabled() || is_locked() {
+        return;
+    }
+    let remove_stale_copies = !STALE_COPIES_REMOVED.swap(true, Ordering::SeqCst);
+    tauri::async_runtime::spawn(async move {
+        let meetings = match MeetingsRepository::get_meetings(&pool).await {
+            Ok(meetings) => meetings,
+            Err(e) => {
+                warn!("Library encryption sweep skipped, failed to list meetings: {}", e);
+                return;
+            }
+        };
+        let folders: Vec<PathBuf> = meetings
+            .into_iter()
+            .filter_map(|m| m.folder_path)
+            .map(PathBuf::from)
+            .collect();
+        let sealed = tokio::task::spawn_blocking(move || {
+            if remove_stale_copies {
+                let removed = files::remove_stale_plaintext_copies(&folders);
+                if removed > 0 {
+                    warn!("Removed {} decrypted copies left behind by an earlier session", removed);
+                }
+            }
+            folders.iter().map(|f| files::seal_folder(f)).sum::<usize>()
+        })
+        .await
+        .unwrap_or(0);
+        if sealed > 0 {
+            info!("Library encryption sweep encrypted {} files", sealed);
+        }
+    });
+}
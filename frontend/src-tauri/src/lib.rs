@@ -38,9 +38,13 @@ pub(crate) use perf_trace;
 pub mod analytics;
 pub mod api;
 pub mod audio;
+pub mod backup;
+pub mod chapters;
 pub mod config;
 pub mod console_utils;
+pub mod conversation_analytics;
 pub mod database;
+pub mod encryption;
 pub mod notifications;
 pub mod ollama;
 pub mod onboarding;
@@ -49,9 +53,13 @@ pub mod anthropic;
 pub mod groq;
 pub mod openrouter;
 pub mod parakeet_engine;
+pub mod retention;
+pub mod secrets;
 pub mod state;
 pub mod summary;
+pub mod sync;
 pub mod tray;
+pub mod translation;
 pub mod utils;
 pub mod mongodb_client;
 pub mod device_registry;
@@ -64,7 +72,7 @@ use audio::{list_audio_devices, AudioDevice, trigger_audio_permission};
 use log::{error as log_error, info as log_info};
 use notifications::commands::NotificationManagerState;
 use std::sync::Arc;
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
 use tokio::sync::RwLock;
 
 static RECORDING_FLAG: AtomicBool = AtomicBool::new(false);
@@ -226,7 +234,8 @@ fn get_transcription_status() -> TranscriptionStatus {
 
 #[tauri::command]
 fn read_audio_file(file_path: String) -> Result<Vec<u8>, String> {
-    match std::fs::read(&file_path) {
+    // Decrypts transparently when the recording is stored encrypted
+    match encryption::files::read_file(std::path::Path::new(&file_path)) {
         Ok(data) => Ok(data),
         Err(e) => Err(format!("Failed to read audio file: {}", e)),
     }
@@ -403,6 +412,19 @@ fn test_panic() {
     panic!("test crash for PostHog verification");
 }
 
+/// Starts the background tasks that need the database. Each task bails out while the
+/// app state is missing, so this runs again wherever the state gets managed later.
+pub fn start_background_tasks<R: Runtime>(app: &AppHandle<R>) {
+    // Resume the persistent import queue and start scanning watched folders
+    audio::import_queue::start_import_queue(app.clone());
+
+    // Apply the meeting retention rules in the background when enabled
+    retention::start_retention_task(app.clone());
+
+    // Sync meetings with the configured server in the background when enabled
+    sync::start_sync_task(app.clone());
+}
+
 pub fn run() {
     analytics::panic_hook::setup_panic_hook();
     log::set_max_level(log::LevelFilter::Info);
@@ -493,6 +515,26 @@ pub fn run() {
             //     });
             // }
 
+            // Initialize the secret store and library encryption before anything reads API keys,
+            // auth tokens or the database
+            match _app.handle().path().app_data_dir() {
+                Ok(app_data_dir) => {
+                    secrets::init(&app_data_dir);
+                    let secrets_status = secrets::status();
+                    if secrets_status.locked || secrets_status.error.is_some() {
+                        // The frontend asks for the vault passphrase or offers a reset
+                        let app_handle = _app.handle().clone();
+                        tauri::async_runtime::spawn(async move {
+                            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                            let _ = app_handle.emit("secrets-locked", &secrets_status);
+                        });
+                    }
+                    auth_store::migrate_plaintext_tokens(&app_data_dir);
+                    encryption::init(&app_data_dir);
+                }
+                Err(e) => log::error!("Failed to resolve app data dir for secrets: {}", e),
+            }
+
             // Initialize database (handles first launch detection and conditional setup)
             tauri::async_runtime::block_on(async {
                 database::setup::initialize_database_on_startup(&_app.handle()).await
@@ -536,12 +578,13 @@ pub fn run() {
                 log::warn!("Failed to resolve data directory for user brand templates");
             }
 
-            // Non-blocking startup sync of templates from backend (MongoDB)
+            // Non-blocking startup sync of templates from the configured sources
             // Delay to allow Windows Firewall prompts to be accepted before connecting
-            tauri::async_runtime::spawn(async {
+            let app_for_template_sync = _app.handle().clone();
+            tauri::async_runtime::spawn(async move {
                 tokio::time::sleep(std::time::Duration::from_secs(5)).await;
                 log::info!("Starting background template sync...");
-                let result = summary::sync_templates_internal().await;
+                let result = summary::sync_templates_internal(&app_for_template_sync).await;
                 if result.is_online {
                     log::info!(
                         "Template sync completed: {} synced, {} failed",
@@ -552,7 +595,7 @@ pub fn run() {
                     // Retry once after a delay in case firewall was just approved
                     log::info!("Template sync: first attempt failed, retrying in 10s...");
                     tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-                    let retry = summary::sync_templates_internal().await;
+                    let retry = summary::sync_templates_internal(&app_for_template_sync).await;
                     if retry.is_online {
                         log::info!(
                             "Template sync retry succeeded: {} synced, {} failed",
@@ -568,6 +611,9 @@ pub fn run() {
             // Initialize usage event buffer (loads any pending events from disk)
             usage_buffer::initialize(&_app.handle());
 
+            // Start the import queue, retention and sync tasks
+            start_background_tasks(_app.handle());
+
             // Device registration — upsert into MongoDB `devices` collection
             // and start polling the `advanced_logs` flag
             let app_for_device_reg = _app.handle().clone();
@@ -672,6 +718,9 @@ pub fn run() {
             // Reload sync commands (retrieve transcript history and meeting name)
             audio::recording_commands::get_transcript_history,
             audio::recording_commands::get_recording_meeting_name,
+            audio::recording_commands::add_recording_marker,
+            audio::recording_commands::get_recording_markers,
+            summary::live::get_live_summary,
             // Device monitoring commands (AirPods/Bluetooth disconnect/reconnect)
             audio::recording_commands::poll_audio_device_events,
             audio::recording_commands::get_reconnection_status,
@@ -730,11 +779,58 @@ pub fn run() {
             summary::api_get_summary,
             summary::api_save_meeting_summary,
             summary::api_cancel_summary,
+            // Redaction of personal details before LLM requests
+            summary::redaction::commands::api_get_redaction_settings,
+            summary::redaction::commands::api_save_redaction_settings,
+            summary::redaction::commands::api_preview_redaction,
+            summary::redaction::commands::api_get_redaction_audits,
+            // Automatic template selection
+            summary::template_selection::commands::api_get_template_selection_settings,
+            summary::template_selection::commands::api_save_template_selection_settings,
+            summary::template_selection::commands::api_suggest_template,
+            summary::template_selection::commands::api_get_template_selection,
+            // Translation commands
+            translation::commands::api_translate_meeting,
+            translation::commands::api_cancel_translation,
+            translation::commands::api_get_meeting_translation,
+            translation::commands::api_list_meeting_translations,
+            translation::commands::api_delete_meeting_translation,
+            // Chapter commands
+            chapters::commands::api_generate_meeting_chapters,
+            chapters::commands::api_get_meeting_chapters,
+            chapters::commands::api_delete_meeting_chapters,
+            chapters::commands::api_export_meeting_chapters,
+            // Conversation analytics commands
+            conversation_analytics::commands::api_get_meeting_analytics,
+            conversation_analytics::commands::api_get_speaker_trend,
+            conversation_analytics::commands::api_list_analytics_speakers,
+            // Recording marker commands
+            audio::markers::api_get_meeting_markers,
+            audio::markers::api_update_meeting_marker,
+            audio::markers::api_delete_meeting_marker,
+            audio::markers::api_export_marker_clips,
             // Template commands
             summary::api_list_templates,
             summary::api_get_template_details,
             summary::api_validate_template,
             summary::api_sync_templates,
+            summary::template_sources::commands::api_get_template_sources,
+            summary::template_sources::commands::api_save_template_sources,
+            summary::template_sources::commands::api_get_template_conflicts,
+            summary::template_sources::commands::api_resolve_template_conflict,
+            // User template commands
+            summary::api_check_template,
+            summary::api_list_user_templates,
+            summary::api_get_user_template,
+            summary::api_create_user_template,
+            summary::api_update_user_template,
+            summary::api_duplicate_template,
+            summary::api_delete_user_template,
+            summary::api_list_template_versions,
+            summary::api_restore_template_version,
+            summary::api_export_template,
+            summary::api_import_template,
+            summary::api_preview_template,
             // Brand template commands
             summary::api_list_brand_templates,
             summary::api_get_brand_template,
@@ -780,6 +876,7 @@ pub fn run() {
             // System audio capture commands
             audio::system_audio_commands::start_system_audio_capture_command,
             audio::system_audio_commands::list_system_audio_devices_command,
+            audio::system_audio_commands::list_system_audio_applications_command,
             audio::system_audio_commands::check_system_audio_permissions_command,
             audio::system_audio_commands::start_system_audio_monitoring,
             audio::system_audio_commands::stop_system_audio_monitoring,
@@ -814,6 +911,15 @@ pub fn run() {
             auth_store::auth_clear_tokens,
             auth_store::auth_save_user_id,
             auth_store::auth_get_user_id,
+            // Encrypted library commands
+            encryption::commands::api_get_library_encryption_status,
+            encryption::commands::api_enable_library_encryption,
+            encryption::commands::api_unlock_library,
+            // Secrets vault
+            secrets::commands::api_get_secrets_status,
+            secrets::commands::api_unlock_secrets,
+            secrets::commands::api_set_secrets_passphrase,
+            secrets::commands::api_reset_secrets_vault,
             // Usage buffer commands
             usage_buffer::usage_track_event,
             usage_buffer::usage_flush_events,
@@ -826,12 +932,77 @@ pub fn run() {
             audio::retranscription::start_retranscription_command,
             audio::retranscription::cancel_retranscription_command,
             audio::retranscription::is_retranscription_in_progress_command,
+            // Post-recording quality pass / transcript version commands
+            audio::quality_pass::get_transcript_versions_command,
+            audio::quality_pass::accept_transcript_version_command,
+            audio::quality_pass::revert_transcript_version_command,
+            audio::quality_pass::discard_transcript_version_command,
+            audio::quality_pass::cancel_quality_pass_command,
+            // Segment repair commands
+            audio::segment_repair::start_segment_repair_command,
+            audio::segment_repair::cancel_segment_repair_command,
+            // Custom vocabulary commands
+            audio::transcription::vocabulary::get_vocabulary_terms,
+            audio::transcription::vocabulary::add_vocabulary_term,
+            audio::transcription::vocabulary::update_vocabulary_term,
+            audio::transcription::vocabulary::delete_vocabulary_term,
+            audio::transcription::vocabulary::set_recording_vocabulary,
+            // Per-segment language identification commands
+            audio::transcription::language::get_language_detection_settings,
+            audio::transcription::language::set_language_detection_settings,
             // Import audio commands
             audio::import::select_and_validate_audio_command,
             audio::import::validate_audio_file_command,
             audio::import::start_import_audio_command,
             audio::import::cancel_import_command,
             audio::import::is_import_in_progress_command,
+            audio::import::get_meeting_source_video,
+            // Watch-folder import queue commands
+            audio::import_queue::get_import_watch_folders,
+            audio::import_queue::add_import_watch_folder,
+            audio::import_queue::set_import_watch_folder_enabled,
+            audio::import_queue::remove_import_watch_folder,
+            audio::import_queue::get_import_queue,
+            audio::import_queue::enqueue_import_files,
+            audio::import_queue::retry_import_queue_item,
+            audio::import_queue::remove_import_queue_item,
+            audio::import_queue::get_meeting_import_source,
+            // Meeting edit commands
+            audio::meeting_edit::merge_meetings_command,
+            audio::meeting_edit::split_meeting_command,
+            audio::meeting_edit::trim_meeting_command,
+            // Transcript segment editing commands
+            audio::transcript_edit::edit_transcript_segment,
+            audio::transcript_edit::insert_transcript_segment,
+            audio::transcript_edit::delete_transcript_segment,
+            audio::transcript_edit::split_transcript_segment,
+            audio::transcript_edit::join_transcript_segments,
+            audio::transcript_edit::realign_transcript_segments,
+            audio::transcript_edit::get_transcript_revisions,
+            audio::transcript_edit::get_transcript_word_timings,
+            // Redacted copies of meetings for sharing
+            audio::redacted_copy::api_suggest_redaction_spans,
+            audio::redacted_copy::api_create_redacted_copy,
+            audio::redacted_copy::api_get_redacted_copies,
+            // Retention rules and storage management
+            retention::commands::api_get_retention_settings,
+            retention::commands::api_save_retention_policy,
+            retention::commands::api_preview_retention,
+            retention::commands::api_apply_retention,
+            retention::commands::api_set_meeting_starred,
+            retention::commands::api_get_starred_meetings,
+            retention::commands::api_get_storage_usage,
+            retention::commands::api_clean_up_storage,
+            // Library backup and restore
+            backup::commands::api_create_backup,
+            backup::commands::api_inspect_backup,
+            backup::commands::api_restore_backup,
+            // Multi-device sync
+            sync::commands::api_get_sync_settings,
+            sync::commands::api_save_sync_settings,
+            sync::commands::api_sync_now,
+            sync::commands::api_get_sync_conflicts,
+            sync::commands::api_resolve_sync_conflict,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
@@ -0,0 +1,131 @@
+use super::storage::{self, CleanupResult, StorageReport};
+use super::{RetentionPolicy, RetentionReport};
+use crate::audio::recording_preferences::{get_default_recordings_folder, load_recording_preferences};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::state::AppState;
+use log::{info, warn};
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// The recordings folder from the recording preferences; only it is scanned for orphans
+async fn recordings_folder<R: Runtime>(app: &AppHandle<R>) -> PathBuf {
+    match load_recording_preferences(app).await {
+        Ok(preferences) => preferences.save_folder,
+        Err(e) => {
+            warn!("Failed to load recording preferences: {}, using the default recordings folder", e);
+            get_default_recordings_folder()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionSettings {
+    pub policy: RetentionPolicy,
+    /// Result of the last run (background or on demand)
+    pub last_run: Option<RetentionReport>,
+}
+
+#[tauri::command]
+pub async fn api_get_retention_settings<R: Runtime>(app: AppHandle<R>) -> Result<RetentionSettings, String> {
+    Ok(RetentionSettings {
+        policy: super::load_policy(&app),
+        last_run: super::last_run(&app),
+    })
+}
+
+#[tauri::command]
+pub async fn api_save_retention_policy<R: Runtime>(
+    app: AppHandle<R>,
+    policy: RetentionPolicy,
+) -> Result<RetentionPolicy, String> {
+    if policy.rules.iter().any(|rule| rule.older_than_days == 0) {
+        return Err("Retention rules must keep meetings for at least one day".to_string());
+    }
+    super::save_policy(&app, &policy)?;
+    info!(
+        "Saved retention policy (enabled: {}, {} rules)",
+        policy.enabled,
+        policy.rules.len()
+    );
+    Ok(policy)
+}
+
+/// What the given rules would delete now, without deleting anything
+#[tauri::command]
+pub async fn api_preview_retention<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    policy: Option<RetentionPolicy>,
+) -> Result<RetentionReport, String> {
+    let policy = policy.unwrap_or_else(|| super::load_policy(&app));
+    super::run(&app, state.db_manager.pool(), &policy, true).await
+}
+
+/// Applies the saved retention rules now
+#[tauri::command]
+pub async fn api_apply_retention<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<RetentionReport, String> {
+    let policy = super::load_policy(&app);
+    super::run(&app, state.db_manager.pool(), &policy, false).await
+}
+
+#[tauri::command]
+pub async fn api_set_meeting_starred(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    starred: bool,
+) -> Result<(), String> {
+    match MeetingsRepository::set_starred(state.db_manager.pool(), &meeting_id, starred).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!("Meeting not found: {}", meeting_id)),
+        Err(e) => Err(format!("Failed to update meeting: {}", e)),
+    }
+}
+
+#[tauri::command]
+pub async fn api_get_starred_meetings(state: tauri::State<'_, AppState>) -> Result<Vec<String>, String> {
+    MeetingsRepository::get_starred_meeting_ids(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load starred meetings: {}", e))
+}
+
+/// Disk usage per meeting (audio, checkpoints, other files), orphaned folders and models
+#[tauri::command]
+pub async fn api_get_storage_usage<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<StorageReport, String> {
+    let meetings = MeetingsRepository::get_retention_info(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?;
+    let models_dir = app.path().app_data_dir().ok().map(|dir| dir.join("models"));
+    let recordings_root = recordings_folder(&app).await;
+    tokio::task::spawn_blocking(move || storage::storage_report(&meetings, &recordings_root, models_dir.as_deref()))
+        .await
+        .map_err(|e| format!("Storage scan task join error: {}", e))
+}
+
+/// Moves orphaned meeting folders to the trash folder and deletes leftover recording checkpoints
+#[tauri::command]
+pub async fn api_clean_up_storage<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<CleanupResult, String> {
+    // A recording's folder has no meeting until the recording is saved
+    let _guard = super::ensure_idle().await?;
+    let meetings = MeetingsRepository::get_retention_info(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?;
+    let recordings_root = recordings_folder(&app).await;
+    let result = tokio::task::spawn_blocking(move || storage::clean_up(&meetings, &recordings_root))
+        .await
+        .map_err(|e| format!("Cleanup task join error: {}", e))?;
+    info!(
+        "Storage cleanup moved {} folders to the trash ({} bytes) and removed {} checkpoint directories ({} bytes)",
+        result.removed_folders, result.trashed_bytes, result.removed_checkpoints, result.freed_bytes
+    );
+    Ok(result)
+}
@@ -0,0 +1,391 @@
+//! Retention rules for meetings and storage management.
+//!
+//! Rules delete the audio of meetings older than a number of days (keeping transcript, summary
+//! and notes) or whole meetings, folder included. Starred meetings are skipped unless a rule
+//! says otherwise. The rules are applied by a background task when enabled, on demand, or as
+//! a dry run that only reports what would be deleted. `storage` breaks down the disk usage,
+//! moves orphaned meeting folders to a trash folder and removes leftover checkpoints.
+
+pub mod commands;
+pub mod storage;
+
+use crate::audio::recording_commands::is_recording;
+use crate::audio::retranscription::is_retranscription_in_progress;
+use crate::database::models::MeetingRetentionInfo;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::encryption::files as library_files;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "retention.json";
+const POLICY_KEY: &str = "policy";
+const LAST_RUN_KEY: &str = "last_run";
+
+/// First background run after startup, then every ENFORCE_INTERVAL
+const STARTUP_DELAY: Duration = Duration::from_secs(10 * 60);
+const ENFORCE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+static TASK_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Only one run at a time (background or on demand)
+static RUN_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    /// Delete the audio and checkpoints; transcript, summary and notes stay
+    DeleteAudio,
+    /// Delete the meeting and its folder
+    DeleteMeeting,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionRule {
+    pub action: RetentionAction,
+    pub older_than_days: u32,
+    #[serde(default = "default_keep_starred")]
+    pub keep_starred: bool,
+}
+
+fn default_keep_starred() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Apply the rules in the background
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rules: Vec<RetentionRule>,
+}
+
+/// What a run did (or, in a dry run, would do) to one meeting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionItem {
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub action: RetentionAction,
+    pub freed_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub items: Vec<RetentionItem>,
+    pub freed_bytes: u64,
+    pub ran_at: DateTime<Utc>,
+}
+
+/// The action the rules call for on a meeting; deleting the meeting wins over deleting audio
+fn action_for(
+    rules: &[RetentionRule],
+    created_at: DateTime<Utc>,
+    starred: bool,
+    has_audio: bool,
+    now: DateTime<Utc>,
+) -> Option<RetentionAction> {
+    let age_days = (now - created_at).num_days();
+    let matching = |action: RetentionAction| {
+        rules.iter().any(|rule| {
+            rule.action == action && age_days >= i64::from(rule.older_than_days) && !(starred && rule.keep_starred)
+        })
+    };
+    if matching(RetentionAction::DeleteMeeting) {
+        Some(RetentionAction::DeleteMeeting)
+    } else if has_audio && matching(RetentionAction::DeleteAudio) {
+        Some(RetentionAction::DeleteAudio)
+    } else {
+        None
+    }
+}
+
+pub fn load_policy<R: Runtime>(app: &AppHandle<R>) -> RetentionPolicy {
+    let store = match app.store(STORE_FILE) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to access retention store: {}, using defaults", e);
+            return RetentionPolicy::default();
+        }
+    };
+    match store.get(POLICY_KEY) {
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            warn!("Failed to deserialize retention policy: {}, using defaults", e);
+            RetentionPolicy::default()
+        }),
+        None => RetentionPolicy::default(),
+    }
+}
+
+fn save_to_store<R: Runtime, T: Serialize>(app: &AppHandle<R>, key: &str, value: &T) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to access retention store: {}", e))?;
+    let value = serde_json::to_value(value).map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+    store.set(key, value);
+    store.save().map_err(|e| format!("Failed to save retention store: {}", e))
+}
+
+fn save_policy<R: Runtime>(app: &AppHandle<R>, policy: &RetentionPolicy) -> Result<(), String> {
+    save_to_store(app, POLICY_KEY, policy)
+}
+
+fn last_run<R: Runtime>(app: &AppHandle<R>) -> Option<RetentionReport> {
+    let value = app.store(STORE_FILE).ok()?.get(LAST_RUN_KEY)?;
+    serde_json::from_value(value).ok()
+}
+
+/// RAII guard for RUN_IN_PROGRESS
+struct RunGuard;
+
+impl RunGuard {
+    fn acquire() -> Result<Self, String> {
+        if RUN_IN_PROGRESS
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err("Retention rules are already being applied".to_string());
+        }
+        Ok(RunGuard)
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        RUN_IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Files and folders do not change under a recording, retranscription or retention run
+async fn ensure_idle() -> Result<RunGuard, String> {
+    if is_recording().await {
+        return Err("Not available while recording".to_string());
+    }
+    if is_retranscription_in_progress() {
+        return Err("Not available while a retranscription is running".to_string());
+    }
+    RunGuard::acquire()
+}
+
+/// Meetings the rules apply to with their action and the space it frees (blocking)
+fn plan(policy: &RetentionPolicy, meetings: &[MeetingRetentionInfo], now: DateTime<Utc>) -> Vec<RetentionItem> {
+    meetings
+        .iter()
+        .filter_map(|meeting| {
+            let folder = meeting.folder_path.as_deref().map(Path::new).filter(|f| f.is_dir());
+            let audio = folder.map(storage::audio_files).unwrap_or_default();
+            let action = action_for(&policy.rules, meeting.created_at, meeting.starred, !audio.is_empty(), now)?;
+            let freed_bytes = match action {
+                RetentionAction::DeleteMeeting => folder.map(storage::size_of).unwrap_or(0),
+                RetentionAction::DeleteAudio => {
+                    audio.iter().map(|path| storage::size_of(path)).sum::<u64>()
+                        + folder.map(|f| storage::size_of(&f.join(storage::CHECKPOINTS_DIR))).unwrap_or(0)
+                }
+            };
+            Some(RetentionItem {
+                meeting_id: meeting.id.clone(),
+                title: meeting.title.clone(),
+                created_at: meeting.created_at,
+                action,
+                freed_bytes,
+                error: None,
+            })
+        })
+        .collect()
+}
+
+/// Removes the audio and checkpoints of a meeting folder and notes it in metadata.json
+fn delete_audio(folder: &Path) -> Result<(), String> {
+    for audio in storage::audio_files(folder) {
+        std::fs::remove_file(&audio).map_err(|e| format!("Failed to delete {}: {}", audio.display(), e))?;
+    }
+    let checkpoints = folder.join(storage::CHECKPOINTS_DIR);
+    if checkpoints.is_dir() {
+        std::fs::remove_dir_all(&checkpoints)
+            .map_err(|e| format!("Failed to delete {}: {}", checkpoints.display(), e))?;
+    }
+
+    let metadata_path = folder.join("metadata.json");
+    if let Ok(existing) = library_files::read_to_string(&metadata_path) {
+        if let Ok(mut json) = serde_json::from_str::<serde_json::Value>(&existing) {
+            if let Some(obj) = json.as_object_mut() {
+                obj.remove("audio_file");
+                obj.insert("audio_deleted_at".to_string(), serde_json::json!(Utc::now().to_rfc3339()));
+            }
+            let json_string = serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?;
+            library_files::write_file(&metadata_path, json_string.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+async fn apply_item(pool: &SqlitePool, meetings: &[MeetingRetentionInfo], item: &RetentionItem) -> Result<(), String> {
+    let meeting = meetings
+        .iter()
+        .find(|m| m.id == item.meeting_id)
+        .ok_or_else(|| "Meeting not found".to_string())?;
+    let folder = meeting.folder_path.clone().map(std::path::PathBuf::from);
+
+    match item.action {
+        RetentionAction::DeleteAudio => {
+            let folder = folder.ok_or_else(|| "Meeting has no folder".to_string())?;
+            tokio::task::spawn_blocking(move || delete_audio(&folder))
+                .await
+                .map_err(|e| format!("Audio deletion task join error: {}", e))?
+        }
+        RetentionAction::DeleteMeeting => {
+            MeetingsRepository::delete_meeting(pool, &meeting.id)
+                .await
+                .map_err(|e| format!("Failed to delete meeting: {}", e))?;
+            // Another meeting may still point at the folder
+            let shared = meetings
+                .iter()
+                .any(|m| m.id != meeting.id && m.folder_path == meeting.folder_path);
+            match folder.filter(|f| f.is_dir() && !shared) {
+                Some(folder) => tokio::task::spawn_blocking(move || std::fs::remove_dir_all(&folder))
+                    .await
+                    .map_err(|e| format!("Folder deletion task join error: {}", e))?
+                    .map_err(|e| format!("Meeting deleted, but its folder could not be removed: {}", e)),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// Applies the rules of `policy`, or with `dry_run` only reports what they would delete
+pub async fn run<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    policy: &RetentionPolicy,
+    dry_run: bool,
+) -> Result<RetentionReport, String> {
+    let _guard = if dry_run { None } else { Some(ensure_idle().await?) };
+    let meetings = MeetingsRepository::get_retention_info(pool)
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?;
+
+    let now = Utc::now();
+    let (plan_policy, plan_meetings) = (policy.clone(), meetings.clone());
+    let mut items = tokio::task::spawn_blocking(move || plan(&plan_policy, &plan_meetings, now))
+        .await
+        .map_err(|e| format!("Retention planning task join error: {}", e))?;
+
+    if !dry_run {
+        for item in &mut items {
+            if let Err(e) = apply_item(pool, &meetings, item).await {
+                warn!("Retention failed for meeting {}: {}", item.meeting_id, e);
+                item.error = Some(e);
+            }
+        }
+    }
+
+    let report = RetentionReport {
+        dry_run,
+        freed_bytes: items.iter().filter(|i| i.error.is_none()).map(|i| i.freed_bytes).sum(),
+        items,
+        ran_at: now,
+    };
+    if !dry_run {
+        info!(
+            "Retention rules applied to {} meetings, {} bytes freed",
+            report.items.len(),
+            report.freed_bytes
+        );
+        if let Err(e) = save_to_store(app, LAST_RUN_KEY, &report) {
+            warn!("{}", e);
+        }
+        if !report.items.is_empty() {
+            let _ = app.emit("retention-applied", &report);
+        }
+    }
+    Ok(report)
+}
+
+/// Applies the retention rules in the background while they are enabled
+pub fn start_retention_task<R: Runtime>(app: AppHandle<R>) {
+    if TASK_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        // Unlocking an encrypted library manages the state and starts the task again
+        let Some(pool) = app.try_state::<AppState>().map(|state| state.db_manager.pool().clone()) else {
+            info!("Retention task not started: app state unavailable");
+            TASK_STARTED.store(false, Ordering::SeqCst);
+            return;
+        };
+
+        loop {
+            let policy = load_policy(&app);
+            if policy.enabled && !policy.rules.is_empty() {
+                if let Err(e) = run(&app, &pool, &policy, false).await {
+                    info!("Retention run skipped: {}", e);
+                }
+            }
+            tokio::time::sleep(ENFORCE_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(action: RetentionAction, older_than_days: u32, keep_starred: bool) -> RetentionRule {
+        RetentionRule {
+            action,
+            older_than_days,
+            keep_starred,
+        }
+    }
+
+    #[test]
+    fn test_action_for() {
+        let now = Utc::now();
+        let days_ago = |days: i64| now - chrono::Duration::days(days);
+        let rules = vec![
+            rule(RetentionAction::DeleteAudio, 30, true),
+            rule(RetentionAction::DeleteMeeting, 365, true),
+        ];
+
+        assert_eq!(action_for(&rules, days_ago(10), false, true, now), None);
+        assert_eq!(
+            action_for(&rules, days_ago(30), false, true, now),
+            Some(RetentionAction::DeleteAudio)
+        );
+        // Nothing left to delete once the audio is gone
+        assert_eq!(action_for(&rules, days_ago(90), false, false, now), None);
+        assert_eq!(
+            action_for(&rules, days_ago(400), false, false, now),
+            Some(RetentionAction::DeleteMeeting)
+        );
+        assert_eq!(action_for(&rules, days_ago(400), true, true, now), None);
+
+        // A rule may apply to starred meetings too
+        let rules = vec![rule(RetentionAction::DeleteAudio, 30, false)];
+        assert_eq!(
+            action_for(&rules, days_ago(31), true, true, now),
+            Some(RetentionAction::DeleteAudio)
+        );
+    }
+
+    #[test]
+    fn test_policy_defaults() {
+        let policy: RetentionPolicy =
+            serde_json::from_str(r#"{"rules": [{"action": "delete_audio", "older_than_days": 30}]}"#).unwrap();
+        assert!(!policy.enabled);
+        assert_eq!(policy.rules, vec![rule(RetentionAction::DeleteAudio, 30, true)]);
+    }
+}
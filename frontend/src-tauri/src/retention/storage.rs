@@ -0,0 +1,397 @@
+//! Disk usage of meeting folders and models, and cleanup of what no meeting uses.
+//!
+//! An orphaned folder is a folder in the recordings folder that the app wrote but no meeting
+//! points at, e.g. after a meeting was deleted: its metadata.json names a meeting that is not in
+//! the database, or it holds recording checkpoints. Other folders are never touched, whatever
+//! files they hold. Orphaned folders are moved to a trash folder inside the recordings folder
+//! rather than deleted. Leftover checkpoints are `.checkpoints` directories of meetings whose
+//! audio was finalized.
+
+use crate::audio::constants::AUDIO_EXTENSIONS;
+use crate::database::models::MeetingRetentionInfo;
+use crate::encryption::files as library_files;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub const CHECKPOINTS_DIR: &str = ".checkpoints";
+
+/// Folders changed more recently than this are never orphans; an import or recording may be
+/// writing them before its meeting is saved
+const ORPHAN_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Folder in the recordings folder that orphaned meeting folders are moved to
+pub const TRASH_DIR: &str = ".iqcapture-trash";
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MeetingStorage {
+    pub meeting_id: String,
+    pub title: String,
+    pub folder_path: Option<String>,
+    /// The meeting points at a folder that does not exist
+    pub folder_missing: bool,
+    pub starred: bool,
+    pub audio_bytes: u64,
+    pub checkpoint_bytes: u64,
+    /// Transcripts, metadata and anything else in the folder
+    pub other_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderUsage {
+    pub path: String,
+    pub bytes: u64,
+}
+
+/// Downloaded transcription and summary models, per entry of the models directory
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelStorage {
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageReport {
+    pub meetings: Vec<MeetingStorage>,
+    pub orphan_folders: Vec<FolderUsage>,
+    pub leftover_checkpoints: Vec<FolderUsage>,
+    pub models: Vec<ModelStorage>,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CleanupResult {
+    /// Orphaned folders moved to the trash folder
+    pub removed_folders: usize,
+    pub removed_checkpoints: usize,
+    /// Bytes of the deleted checkpoints
+    pub freed_bytes: u64,
+    /// Bytes moved to the trash folder, freed once it is emptied
+    pub trashed_bytes: u64,
+    pub trash_folder: Option<String>,
+    pub errors: Vec<String>,
+}
+
+/// Size of a file or directory tree; symlinks are not followed
+pub fn size_of(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| size_of(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Whether `path` is a recording, encrypted or not
+pub fn is_audio_file(path: &Path) -> bool {
+    path.is_file()
+        && library_files::plaintext_path(path)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Audio files at the top of a meeting folder
+pub fn audio_files(folder: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(folder)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| is_audio_file(path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// (audio, checkpoints, other) bytes of a meeting folder
+fn folder_breakdown(folder: &Path) -> (u64, u64, u64) {
+    let (mut audio, mut checkpoints, mut other) = (0, 0, 0);
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return (0, 0, 0);
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let size = size_of(&path);
+        if entry.file_name() == CHECKPOINTS_DIR {
+            checkpoints += size;
+        } else if is_audio_file(&path) {
+            audio += size;
+        } else {
+            other += size;
+        }
+    }
+    (audio, checkpoints, other)
+}
+
+/// Meeting id in the metadata.json the app writes to each meeting folder
+fn metadata_meeting_id(folder: &Path) -> Option<String> {
+    let metadata = library_files::read_to_string(&folder.join("metadata.json")).ok()?;
+    let metadata: serde_json::Value = serde_json::from_str(&metadata).ok()?;
+    metadata
+        .get("meeting_id")
+        .and_then(|id| id.as_str())
+        .filter(|id| !id.trim().is_empty())
+        .map(str::to_string)
+}
+
+/// Whether the app wrote `path` for a meeting that no longer exists. Audio or transcripts
+/// alone do not count: a user's own folder may hold any of them.
+fn is_orphaned_meeting_folder(path: &Path, meeting_ids: &HashSet<&str>) -> bool {
+    if path.join(CHECKPOINTS_DIR).is_dir() {
+        return true;
+    }
+    metadata_meeting_id(path).is_some_and(|id| !meeting_ids.contains(id.as_str()))
+}
+
+/// Comparable form of a folder path
+fn normalized(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn changed_within(path: &Path, age: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(|modified| SystemTime::now().duration_since(modified).unwrap_or_default() < age)
+        .unwrap_or(true)
+}
+
+/// Orphaned meeting folders directly inside the recordings folder
+fn find_orphans(recordings_root: &Path, meetings: &[MeetingRetentionInfo], min_age: Duration) -> Vec<PathBuf> {
+    let referenced: HashSet<PathBuf> = meetings
+        .iter()
+        .filter_map(|m| m.folder_path.as_deref())
+        .map(|folder| normalized(Path::new(folder)))
+        .collect();
+    let meeting_ids: HashSet<&str> = meetings.iter().map(|m| m.id.as_str()).collect();
+
+    let Ok(entries) = std::fs::read_dir(recordings_root) else {
+        return Vec::new();
+    };
+    let mut orphans = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        // Symlinked folders may point anywhere
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        if hidden || !is_dir || referenced.contains(&normalized(&path)) {
+            continue;
+        }
+        if is_orphaned_meeting_folder(&path, &meeting_ids) && !changed_within(&path, min_age) {
+            orphans.push(path);
+        }
+    }
+    orphans.sort();
+    orphans
+}
+
+/// Moves a folder into the trash folder under a name not taken yet
+fn move_to_trash(folder: &Path, trash: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(trash).map_err(|e| format!("Failed to create {}: {}", trash.display(), e))?;
+    let name = folder
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid folder {}", folder.display()))?;
+    let mut target = trash.join(&name);
+    let mut n = 2;
+    while target.exists() {
+        target = trash.join(format!("{} ({})", name, n));
+        n += 1;
+    }
+    std::fs::rename(folder, &target).map_err(|e| e.to_string())
+}
+
+/// Checkpoint directories of meetings that have their final audio
+fn find_leftover_checkpoints(meetings: &[MeetingRetentionInfo]) -> Vec<PathBuf> {
+    meetings
+        .iter()
+        .filter_map(|m| m.folder_path.as_deref().map(Path::new))
+        .filter(|folder| !audio_files(folder).is_empty())
+        .map(|folder| folder.join(CHECKPOINTS_DIR))
+        .filter(|checkpoints| checkpoints.is_dir())
+        .collect()
+}
+
+fn usage(paths: Vec<PathBuf>) -> Vec<FolderUsage> {
+    paths
+        .into_iter()
+        .map(|path| FolderUsage {
+            bytes: size_of(&path),
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect()
+}
+
+/// Breakdown of the disk space used by meetings and models (blocking)
+pub fn storage_report(
+    meetings: &[MeetingRetentionInfo],
+    recordings_root: &Path,
+    models_dir: Option<&Path>,
+) -> StorageReport {
+    let meeting_storage: Vec<MeetingStorage> = meetings
+        .iter()
+        .map(|meeting| {
+            let folder = meeting.folder_path.as_deref().map(Path::new);
+            let (audio_bytes, checkpoint_bytes, other_bytes) = folder.map(folder_breakdown).unwrap_or((0, 0, 0));
+            MeetingStorage {
+                meeting_id: meeting.id.clone(),
+                title: meeting.title.clone(),
+                folder_path: meeting.folder_path.clone(),
+                folder_missing: folder.is_some_and(|folder| !folder.is_dir()),
+                starred: meeting.starred,
+                audio_bytes,
+                checkpoint_bytes,
+                other_bytes,
+            }
+        })
+        .collect();
+
+    let orphan_folders = usage(find_orphans(recordings_root, meetings, ORPHAN_MIN_AGE));
+    let leftover_checkpoints = usage(find_leftover_checkpoints(meetings));
+
+    let mut models: Vec<ModelStorage> = models_dir
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| ModelStorage {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    bytes: size_of(&entry.path()),
+                })
+                .filter(|model| model.bytes > 0)
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+
+    let total_bytes = meeting_storage
+        .iter()
+        .map(|m| m.audio_bytes + m.checkpoint_bytes + m.other_bytes)
+        .chain(orphan_folders.iter().map(|f| f.bytes))
+        .chain(models.iter().map(|m| m.bytes))
+        .sum();
+
+    StorageReport {
+        meetings: meeting_storage,
+        orphan_folders,
+        leftover_checkpoints,
+        models,
+        total_bytes,
+    }
+}
+
+/// Moves orphaned folders to the trash folder and deletes leftover checkpoints (blocking).
+/// Must not run while recording.
+pub fn clean_up(meetings: &[MeetingRetentionInfo], recordings_root: &Path) -> CleanupResult {
+    let trash = recordings_root.join(TRASH_DIR);
+    let mut result = CleanupResult::default();
+
+    for folder in find_orphans(recordings_root, meetings, ORPHAN_MIN_AGE) {
+        let bytes = size_of(&folder);
+        match move_to_trash(&folder, &trash) {
+            Ok(()) => {
+                log::info!("Moved orphaned meeting folder {} to {}", folder.display(), trash.display());
+                result.removed_folders += 1;
+                result.trashed_bytes += bytes;
+                result.trash_folder = Some(trash.to_string_lossy().to_string());
+            }
+            Err(e) => result.errors.push(format!("{}: {}", folder.display(), e)),
+        }
+    }
+    for checkpoints in find_leftover_checkpoints(meetings) {
+        let bytes = size_of(&checkpoints);
+        match std::fs::remove_dir_all(&checkpoints) {
+            Ok(()) => {
+                log::info!("Removed leftover checkpoints {}", checkpoints.display());
+                result.removed_checkpoints += 1;
+                result.freed_bytes += bytes;
+            }
+            Err(e) => result.errors.push(format!("{}: {}", checkpoints.display(), e)),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meeting(id: &str, folder: &Path) -> MeetingRetentionInfo {
+        MeetingRetentionInfo {
+            id: id.to_string(),
+            title: id.to_string(),
+            created_at: chrono::Utc::now(),
+            folder_path: Some(folder.to_string_lossy().to_string()),
+            starred: false,
+        }
+    }
+
+    #[test]
+    fn test_breakdown_orphans_and_leftover_checkpoints() {
+        let root = tempfile::tempdir().unwrap();
+        let kept = root.path().join("kept");
+        std::fs::create_dir_all(kept.join(CHECKPOINTS_DIR)).unwrap();
+        std::fs::write(kept.join("audio.mp4"), vec![0u8; 300]).unwrap();
+        std::fs::write(kept.join(CHECKPOINTS_DIR).join("audio_chunk_000.mp4"), vec![0u8; 50]).unwrap();
+        std::fs::write(kept.join("transcripts.json"), b"[]").unwrap();
+
+        let orphan = root.path().join("deleted meeting");
+        std::fs::create_dir_all(&orphan).unwrap();
+        std::fs::write(orphan.join("metadata.json"), br#"{"meeting_id": "meeting-deleted"}"#).unwrap();
+        let unrelated = root.path().join("holiday photos");
+        std::fs::create_dir_all(&unrelated).unwrap();
+        std::fs::write(unrelated.join("beach.jpg"), b"jpg").unwrap();
+
+        assert_eq!(folder_breakdown(&kept), (300, 50, 2));
+
+        let meetings = vec![meeting("kept", &kept)];
+        assert_eq!(find_orphans(root.path(), &meetings, Duration::ZERO), vec![orphan]);
+        // Freshly written folders may belong to a recording or import in progress
+        assert!(find_orphans(root.path(), &meetings, ORPHAN_MIN_AGE).is_empty());
+        assert_eq!(find_leftover_checkpoints(&meetings), vec![kept.join(CHECKPOINTS_DIR)]);
+
+        // Checkpoints are the only audio of a meeting that was never finalized
+        std::fs::remove_file(kept.join("audio.mp4")).unwrap();
+        assert!(find_leftover_checkpoints(&meetings).is_empty());
+    }
+
+    #[test]
+    fn test_user_folders_are_never_orphans() {
+        let root = tempfile::tempdir().unwrap();
+        let kept = root.path().join("kept");
+        std::fs::create_dir_all(&kept).unwrap();
+        std::fs::write(kept.join("metadata.json"), br#"{"meeting_id": "kept"}"#).unwrap();
+        std::fs::write(kept.join("audio.mp4"), b"mp4").unwrap();
+
+        // A music folder next to the meetings, and a folder with someone else's metadata.json
+        let music = root.path().join("Album");
+        std::fs::create_dir_all(&music).unwrap();
+        std::fs::write(music.join("track01.mp3"), b"mp3").unwrap();
+        std::fs::write(music.join("transcripts.json"), b"[]").unwrap();
+        let other = root.path().join("project");
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(other.join("metadata.json"), br#"{"name": "project"}"#).unwrap();
+        // Saved under another path, but its meeting still exists
+        let moved = root.path().join("moved");
+        std::fs::create_dir_all(&moved).unwrap();
+        std::fs::write(moved.join("metadata.json"), br#"{"meeting_id": "kept"}"#).unwrap();
+
+        let meetings = vec![meeting("kept", &kept)];
+        assert!(find_orphans(root.path(), &meetings, Duration::ZERO).is_empty());
+
+        let crashed = root.path().join("crashed recording");
+        std::fs::create_dir_all(crashed.join(CHECKPOINTS_DIR)).unwrap();
+        assert_eq!(find_orphans(root.path(), &meetings, Duration::ZERO), vec![crashed.clone()]);
+
+        // Orphans are moved to the trash folder, never deleted
+        let trash = root.path().join(TRASH_DIR);
+        move_to_trash(&crashed, &trash).unwrap();
+        assert!(!crashed.exists() && trash.join("crashed recording").join(CHECKPOINTS_DIR).is_dir());
+        assert!(music.join("track01.mp3").exists() && other.join("metadata.json").exists());
+        assert!(find_orphans(root.path(), &meetings, Duration::ZERO).is_empty());
+    }
+}
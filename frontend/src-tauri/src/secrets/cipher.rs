@@ -0,0 +1,90 @@
+//! Key derivation and authenticated encryption shared by the secrets vault.
+//!
+//! Keys are derived with Argon2id (default parameters) and data is sealed with
+//! XChaCha20-Poly1305. A sealed blob is the random 24-byte nonce followed by the ciphertext.
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+
+pub const KEY_LEN: usize = 32;
+pub const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Fresh random salt for `derive_key`
+pub fn random_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+/// Derives an encryption key from a passphrase with Argon2id
+pub fn derive_key(passphrase: &[u8], salt: &[u8]) -> Result<[u8; KEY_LEN], String> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase, salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypts `plaintext`; `aad` is authenticated but not encrypted and must match on `open`
+pub fn seal(key: &[u8; KEY_LEN], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, Payload { msg: plaintext, aad })
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    let mut sealed = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Decrypts a blob produced by `seal`; fails on a wrong key, wrong `aad` or modified data
+pub fn open(key: &[u8; KEY_LEN], sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    if sealed.len() < NONCE_LEN {
+        return Err("Encrypted data is truncated".to_string());
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(XNonce::from_slice(nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| "Decryption failed (wrong key or corrupted data)".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let key = derive_key(b"passphrase", &random_salt()).unwrap();
+        let sealed = seal(&key, b"sk-secret", b"header").unwrap();
+        assert_ne!(&sealed[NONCE_LEN..], b"sk-secret");
+        assert_eq!(open(&key, &sealed, b"header").unwrap(), b"sk-secret");
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_key_aad_and_tampering() {
+        let salt = random_salt();
+        let key = derive_key(b"passphrase", &salt).unwrap();
+        let other = derive_key(b"other", &salt).unwrap();
+        let sealed = seal(&key, b"sk-secret", b"header").unwrap();
+
+        assert!(open(&other, &sealed, b"header").is_err());
+        assert!(open(&key, &sealed, b"other header").is_err());
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(open(&key, &tampered, b"header").is_err());
+        assert!(open(&key, &sealed[..10], b"header").is_err());
+    }
+
+    #[test]
+    fn test_derive_key_depends_on_salt() {
+        let a = derive_key(b"passphrase", b"salt-one-1234567").unwrap();
+        let b = derive_key(b"passphrase", b"salt-two-1234567").unwrap();
+        assert_eq!(a, derive_key(b"passphrase", b"salt-one-1234567").unwrap());
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,45 @@
+use super::SecretsStatus;
+use log::warn;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn api_get_secrets_status() -> Result<SecretsStatus, String> {
+    Ok(super::status())
+}
+
+/// Opens the secrets vault with its passphrase; a library whose key is kept in the secret
+/// store is opened too
+#[tauri::command]
+pub async fn api_unlock_secrets(app: AppHandle, passphrase: String) -> Result<SecretsStatus, String> {
+    super::unlock(&passphrase)?;
+    open_library(&app).await;
+    Ok(super::status())
+}
+
+/// Sets the passphrase of a new secrets vault, or of a vault made by an older version
+#[tauri::command]
+pub async fn api_set_secrets_passphrase(app: AppHandle, passphrase: String) -> Result<SecretsStatus, String> {
+    super::set_passphrase(&passphrase)?;
+    open_library(&app).await;
+    Ok(super::status())
+}
+
+/// Starts an empty secrets vault when the old one cannot be opened; returns where the old
+/// vault was kept
+#[tauri::command]
+pub async fn api_reset_secrets_vault(passphrase: String) -> Result<String, String> {
+    super::reset_vault(&passphrase)
+}
+
+/// Opens an encrypted library that waited for the secret store
+async fn open_library(app: &AppHandle) {
+    match crate::encryption::unlock_from_secret_store() {
+        Ok(true) => {
+            if let Err(e) = crate::encryption::commands::open_unlocked_library(app).await {
+                warn!("Failed to open the library after unlocking the secrets vault: {}", e);
+            }
+        }
+        Ok(false) => {}
+        Err(e) => warn!("Encrypted library stays locked: {}", e),
+    }
+}
@@ -0,0 +1,57 @@
+//! OS keyring backend (macOS Keychain, Windows Credential Manager, Secret Service on Linux)
+
+use super::{SecretBackend, SecretStore};
+use keyring::Entry;
+
+/// Keyring service the app's entries are filed under
+const SERVICE: &str = "com.iqcapture.ai";
+
+const PROBE_ENTRY: &str = "keyring_probe";
+
+pub(super) struct KeyringStore;
+
+impl KeyringStore {
+    /// Returns the keyring store if entries can be written and read back on this system
+    pub(super) fn probe() -> Result<Self, String> {
+        let store = KeyringStore;
+        store.set(PROBE_ENTRY, "ok")?;
+        let read = store.get(PROBE_ENTRY)?;
+        let _ = store.delete(PROBE_ENTRY);
+        if read.as_deref() == Some("ok") {
+            Ok(store)
+        } else {
+            Err("Keyring did not return the stored value".to_string())
+        }
+    }
+
+    fn entry(name: &str) -> Result<Entry, String> {
+        Entry::new(SERVICE, name).map_err(|e| format!("Keyring entry {} unavailable: {}", name, e))
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn get(&self, name: &str) -> Result<Option<String>, String> {
+        match Self::entry(name)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(format!("Failed to read {} from keyring: {}", name, e)),
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), String> {
+        Self::entry(name)?
+            .set_password(value)
+            .map_err(|e| format!("Failed to store {} in keyring: {}", name, e))
+    }
+
+    fn delete(&self, name: &str) -> Result<(), String> {
+        match Self::entry(name)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to delete {} from keyring: {}", name, e)),
+        }
+    }
+
+    fn backend(&self) -> SecretBackend {
+        SecretBackend::Keyring
+    }
+}
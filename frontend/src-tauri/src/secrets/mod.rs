@@ -0,0 +1,313 @@
+//! Encrypted storage for credentials (provider API keys, auth tokens).
+//!
+//! Secrets are kept in the OS keyring when it can be used. Otherwise they go to
+//! `secrets.vault` in the app data directory, encrypted with a key derived (Argon2id) from a
+//! passphrase the user sets, and entered at startup to unlock the vault. A vault that cannot
+//! be opened is never replaced silently: the user unlocks it, or explicitly starts a new one.
+//! API keys saved by older versions in the `settings`/`transcript_settings` columns are moved
+//! here at startup (see `SettingsRepository::migrate_api_keys_to_secrets`).
+
+pub mod cipher;
+pub mod commands;
+mod keyring_store;
+mod vault;
+
+use crate::utils::unique_path;
+use keyring_store::KeyringStore;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use vault::VaultStore;
+
+/// Vault file in the app data directory (fallback backend)
+const VAULT_FILE: &str = "secrets.vault";
+
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Secret name of the custom OpenAI-compatible endpoint's API key
+pub const CUSTOM_OPENAI_API_KEY: &str = "api_key.summary.custom-openai";
+
+/// Where secrets are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretBackend {
+    Keyring,
+    Vault,
+}
+
+pub trait SecretStore: Send + Sync {
+    fn get(&self, name: &str) -> Result<Option<String>, String>;
+    fn set(&self, name: &str, value: &str) -> Result<(), String>;
+    fn delete(&self, name: &str) -> Result<(), String>;
+    fn backend(&self) -> SecretBackend;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecretsStatus {
+    /// None while no store can be used
+    pub backend: Option<SecretBackend>,
+    /// The vault waits for its passphrase
+    pub locked: bool,
+    /// A passphrase must be set before secrets can be stored in the vault (no vault yet, or a
+    /// machine-bound vault from an older version)
+    pub needs_passphrase: bool,
+    /// Why the vault could not be opened; the vault is kept until it is unlocked or reset
+    pub error: Option<String>,
+}
+
+/// The vault fallback: its path, the open vault and why it could not be opened
+#[derive(Default)]
+struct VaultState {
+    path: Option<PathBuf>,
+    open: Option<Arc<VaultStore>>,
+    error: Option<String>,
+}
+
+static STORE: Lazy<RwLock<Option<Arc<dyn SecretStore>>>> = Lazy::new(|| RwLock::new(None));
+
+static VAULT: Lazy<RwLock<VaultState>> = Lazy::new(|| RwLock::new(VaultState::default()));
+
+/// Secret name of a summary provider's API key
+pub fn summary_api_key_name(provider: &str) -> String {
+    format!("api_key.summary.{}", provider)
+}
+
+/// Secret name of a transcription provider's API key
+pub fn transcript_api_key_name(provider: &str) -> String {
+    format!("api_key.transcript.{}", provider)
+}
+
+/// Selects the secret backend. Must run before anything reads credentials. A passphrase vault
+/// stays locked (and a vault that cannot be opened stays in place) until the user acts.
+pub fn init(app_data_dir: &Path) {
+    let vault_path = app_data_dir.join(VAULT_FILE);
+    let keyring = KeyringStore::probe();
+    let mut vault_state = VaultState {
+        path: Some(vault_path.clone()),
+        ..VaultState::default()
+    };
+
+    let store: Option<Arc<dyn SecretStore>> = if !vault_path.exists() {
+        match keyring {
+            Ok(keyring) => Some(Arc::new(keyring)),
+            Err(e) => {
+                info!("OS keyring unavailable ({}), secrets need a vault passphrase", e);
+                None
+            }
+        }
+    } else if VaultStore::is_legacy_file(&vault_path) {
+        match open_legacy_vault(&vault_path) {
+            Ok(vault) => match keyring {
+                Ok(keyring) => match move_into_keyring(&vault, &keyring) {
+                    Ok(()) => Some(Arc::new(keyring)),
+                    Err(e) => {
+                        warn!("Keeping secrets in the vault, moving them to the keyring failed: {}", e);
+                        let vault = Arc::new(vault);
+                        vault_state.open = Some(vault.clone());
+                        Some(vault)
+                    }
+                },
+                Err(_) => {
+                    info!("Secrets vault of an older version is open; a passphrase must be set for it");
+                    let vault = Arc::new(vault);
+                    vault_state.open = Some(vault.clone());
+                    Some(vault)
+                }
+            },
+            Err(e) => {
+                warn!("Secrets vault could not be opened: {}", e);
+                vault_state.error = Some(format!(
+                    "The secrets vault of an older version could not be opened ({}). It was bound to \
+                     this computer's name and account, which may have changed.",
+                    e
+                ));
+                None
+            }
+        }
+    } else {
+        if let Err(e) = VaultStore::file_version(&vault_path) {
+            warn!("Secrets vault could not be opened: {}", e);
+            vault_state.error = Some(format!("The secrets vault could not be opened: {}", e));
+        } else {
+            info!("Secrets vault is locked - waiting for the passphrase");
+        }
+        None
+    };
+
+    match &store {
+        Some(store) => info!("Secrets store ready ({:?})", store.backend()),
+        None => warn!("No secrets store available until the vault is unlocked or set up"),
+    }
+    if let Ok(mut current) = STORE.write() {
+        *current = store;
+    }
+    if let Ok(mut current) = VAULT.write() {
+        *current = vault_state;
+    }
+}
+
+/// Opens a machine-bound vault written by an older version
+fn open_legacy_vault(path: &Path) -> Result<VaultStore, String> {
+    VaultStore::open(path.to_path_buf(), machine_passphrase().as_bytes())
+}
+
+fn move_into_keyring(vault: &VaultStore, keyring: &KeyringStore) -> Result<(), String> {
+    let entries = vault.entries();
+    for (name, value) in &entries {
+        keyring.set(name, value)?;
+    }
+    std::fs::remove_file(vault.path()).map_err(|e| format!("Failed to remove vault file: {}", e))?;
+    info!("Moved {} secrets from the vault into the OS keyring", entries.len());
+    Ok(())
+}
+
+/// Values older versions derived the vault key from; only used to open their vaults
+fn machine_passphrase() -> String {
+    let machine_id = std::fs::read_to_string("/etc/machine-id").unwrap_or_default();
+    let host = sysinfo::System::host_name().unwrap_or_default();
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default();
+    let home = dirs::home_dir().map(|h| h.to_string_lossy().to_string()).unwrap_or_default();
+    format!("iqcapture-vault|{}|{}|{}|{}", machine_id.trim(), host, user, home)
+}
+
+fn check_passphrase(passphrase: &str) -> Result<(), String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!("The passphrase needs at least {} characters", MIN_PASSPHRASE_LEN));
+    }
+    Ok(())
+}
+
+/// Makes `vault` the store, or moves its secrets into the keyring once that works
+fn use_vault(state: &mut VaultState, vault: VaultStore) -> Result<(), String> {
+    let store: Arc<dyn SecretStore> = match KeyringStore::probe() {
+        Ok(keyring) => {
+            move_into_keyring(&vault, &keyring)?;
+            state.open = None;
+            Arc::new(keyring)
+        }
+        Err(_) => {
+            let vault = Arc::new(vault);
+            state.open = Some(vault.clone());
+            vault
+        }
+    };
+    state.error = None;
+    info!("Secrets store ready ({:?})", store.backend());
+    *STORE.write().map_err(|_| "Secrets store lock is poisoned".to_string())? = Some(store);
+    Ok(())
+}
+
+pub fn status() -> SecretsStatus {
+    let backend = backend();
+    let Ok(state) = VAULT.read() else {
+        return SecretsStatus { backend, locked: false, needs_passphrase: false, error: None };
+    };
+    let vault_exists = state.path.as_ref().is_some_and(|path| path.exists());
+    SecretsStatus {
+        backend,
+        locked: backend.is_none() && vault_exists && state.error.is_none(),
+        needs_passphrase: (backend.is_none() && !vault_exists) || state.open.as_ref().is_some_and(|v| v.is_legacy()),
+        error: state.error.clone(),
+    }
+}
+
+/// Opens the vault with its passphrase
+pub fn unlock(passphrase: &str) -> Result<(), String> {
+    let mut state = VAULT.write().map_err(|_| "Secrets vault lock is poisoned".to_string())?;
+    if backend().is_some() {
+        return Ok(());
+    }
+    let path = state
+        .path
+        .clone()
+        .filter(|path| path.exists())
+        .ok_or_else(|| "There is no secrets vault to unlock".to_string())?;
+    if VaultStore::is_legacy_file(&path) {
+        return Err("This vault was made by an older version and has no passphrase".to_string());
+    }
+    let vault = VaultStore::open(path, passphrase.as_bytes()).map_err(|_| "Wrong passphrase".to_string())?;
+    use_vault(&mut state, vault)?;
+    info!("Secrets vault unlocked");
+    Ok(())
+}
+
+/// Sets the passphrase of a new vault, or of a machine-bound vault from an older version
+/// (whose secrets are kept)
+pub fn set_passphrase(passphrase: &str) -> Result<(), String> {
+    check_passphrase(passphrase)?;
+    let mut state = VAULT.write().map_err(|_| "Secrets vault lock is poisoned".to_string())?;
+    let path = state
+        .path
+        .clone()
+        .ok_or_else(|| "Secrets store is not initialized".to_string())?;
+    let entries = match &state.open {
+        Some(vault) if vault.is_legacy() => vault.entries(),
+        _ if backend().is_none() && !path.exists() => BTreeMap::new(),
+        _ => return Err("The secrets vault already has a passphrase".to_string()),
+    };
+    let vault = VaultStore::create(path, passphrase.as_bytes(), entries)?;
+    use_vault(&mut state, vault)?;
+    info!("Secrets vault passphrase set");
+    Ok(())
+}
+
+/// Recovery for a vault that cannot be opened (forgotten passphrase, or a machine-bound vault
+/// after the machine changed): keeps the old file as `secrets.vault.unreadable` (or
+/// `secrets.vault.unreadable_2`, ...) and starts an empty vault with `passphrase`. Saved API keys and sign-ins have to be entered again.
+pub fn reset_vault(passphrase: &str) -> Result<String, String> {
+    check_passphrase(passphrase)?;
+    let mut state = VAULT.write().map_err(|_| "Secrets vault lock is poisoned".to_string())?;
+    if backend().is_some() {
+        return Err("The secrets store is open; there is nothing to reset".to_string());
+    }
+    let path = state
+        .path
+        .clone()
+        .filter(|path| path.exists())
+        .ok_or_else(|| "There is no secrets vault to reset".to_string())?;
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let unreadable = unique_path(
+        path.parent().unwrap_or_else(|| Path::new("")),
+        &format!("{}.unreadable", file_name),
+    );
+    std::fs::rename(&path, &unreadable).map_err(|e| format!("Failed to move the old vault aside: {}", e))?;
+    warn!("Secrets vault reset by the user; the old vault was kept at {}", unreadable.display());
+
+    let vault = VaultStore::create(path, passphrase.as_bytes(), BTreeMap::new())?;
+    use_vault(&mut state, vault)?;
+    Ok(unreadable.to_string_lossy().to_string())
+}
+
+fn store() -> Result<Arc<dyn SecretStore>, String> {
+    STORE
+        .read()
+        .map_err(|_| "Secrets store lock is poisoned".to_string())?
+        .clone()
+        .ok_or_else(|| "Secrets store is not available (the secrets vault is locked or not set up)".to_string())
+}
+
+pub fn get_secret(name: &str) -> Result<Option<String>, String> {
+    store()?.get(name)
+}
+
+/// Stores a secret; an empty value deletes it
+pub fn set_secret(name: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return delete_secret(name);
+    }
+    store()?.set(name, value)
+}
+
+pub fn delete_secret(name: &str) -> Result<(), String> {
+    store()?.delete(name)
+}
+
+/// Backend in use, None before `init` or when no backend works
+pub fn backend() -> Option<SecretBackend> {
+    store().ok().map(|store| store.backend())
+}
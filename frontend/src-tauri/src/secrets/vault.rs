@@ -0,0 +1,188 @@
+//! Encrypted vault file, used when the OS keyring is not available.
+//!
+//! File layout: `IQSV`, a version byte, the 16-byte Argon2 salt, then the sealed JSON map of
+//! secret name to value. The header is authenticated together with the contents. The key of a
+//! version 2 vault is derived from the user's passphrase; version 1 vaults (older versions)
+//! derived it from machine values and are only opened to move their secrets on.
+
+use super::cipher::{self, KEY_LEN, SALT_LEN};
+use super::{SecretBackend, SecretStore};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const MAGIC: &[u8; 4] = b"IQSV";
+const VERSION: u8 = 2;
+/// Machine-bound vault of older versions
+const LEGACY_VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + SALT_LEN;
+
+pub(super) struct VaultStore {
+    path: PathBuf,
+    header: [u8; HEADER_LEN],
+    key: [u8; KEY_LEN],
+    entries: Mutex<BTreeMap<String, String>>,
+}
+
+impl VaultStore {
+    /// Writes a new vault at `path` protected by `passphrase`, holding `entries`
+    pub(super) fn create(
+        path: PathBuf,
+        passphrase: &[u8],
+        entries: BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        let mut header = [0u8; HEADER_LEN];
+        header[..MAGIC.len()].copy_from_slice(MAGIC);
+        header[MAGIC.len()] = VERSION;
+        let salt = cipher::random_salt();
+        header[MAGIC.len() + 1..].copy_from_slice(&salt);
+        let key = cipher::derive_key(passphrase, &salt)?;
+        let vault = Self {
+            path,
+            header,
+            key,
+            entries: Mutex::new(BTreeMap::new()),
+        };
+        vault.write(&entries)?;
+        *vault.entries.lock().map_err(|_| "Secrets vault lock is poisoned".to_string())? = entries;
+        Ok(vault)
+    }
+
+    /// Version byte of the vault file at `path`
+    pub(super) fn file_version(path: &Path) -> Result<u8, String> {
+        let data = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if data.len() < HEADER_LEN || &data[..MAGIC.len()] != MAGIC {
+            return Err(format!("{} is not a secrets vault", path.display()));
+        }
+        match data[MAGIC.len()] {
+            version @ (VERSION | LEGACY_VERSION) => Ok(version),
+            version => Err(format!("Unsupported secrets vault version {}", version)),
+        }
+    }
+
+    /// Whether the vault file at `path` is a machine-bound vault of an older version
+    pub(super) fn is_legacy_file(path: &Path) -> bool {
+        Self::file_version(path).is_ok_and(|version| version == LEGACY_VERSION)
+    }
+
+    /// Opens the existing vault at `path`; fails on a wrong passphrase
+    pub(super) fn open(path: PathBuf, passphrase: &[u8]) -> Result<Self, String> {
+        Self::file_version(&path)?;
+        let data = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut header = [0u8; HEADER_LEN];
+        header.copy_from_slice(&data[..HEADER_LEN]);
+        let key = cipher::derive_key(passphrase, &header[MAGIC.len() + 1..])?;
+        let plaintext = cipher::open(&key, &data[HEADER_LEN..], &header)?;
+        let entries: BTreeMap<String, String> =
+            serde_json::from_slice(&plaintext).map_err(|e| format!("Secrets vault is unreadable: {}", e))?;
+
+        Ok(Self {
+            path,
+            header,
+            key,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    pub(super) fn is_legacy(&self) -> bool {
+        self.header[MAGIC.len()] == LEGACY_VERSION
+    }
+
+    /// All stored secrets (for moving them into the keyring)
+    pub(super) fn entries(&self) -> BTreeMap<String, String> {
+        self.entries.lock().map(|entries| entries.clone()).unwrap_or_default()
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, change: impl FnOnce(&mut BTreeMap<String, String>)) -> Result<(), String> {
+        let mut entries = self
+            .entries
+            .lock()
+            .map_err(|_| "Secrets vault lock is poisoned".to_string())?;
+        let mut updated = entries.clone();
+        change(&mut updated);
+        self.write(&updated)?;
+        *entries = updated;
+        Ok(())
+    }
+
+    fn write(&self, entries: &BTreeMap<String, String>) -> Result<(), String> {
+        let json = serde_json::to_vec(entries).map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+        let mut data = self.header.to_vec();
+        data.extend(cipher::seal(&self.key, &json, &self.header)?);
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        // Atomic write: temp file then rename
+        let temp_path = self.path.with_extension("vault.tmp");
+        std::fs::write(&temp_path, &data).map_err(|e| format!("Failed to write secrets vault: {}", e))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o600));
+        }
+        std::fs::rename(&temp_path, &self.path).map_err(|e| format!("Failed to replace secrets vault: {}", e))
+    }
+}
+
+impl SecretStore for VaultStore {
+    fn get(&self, name: &str) -> Result<Option<String>, String> {
+        let entries = self
+            .entries
+            .lock()
+            .map_err(|_| "Secrets vault lock is poisoned".to_string())?;
+        Ok(entries.get(name).cloned())
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), String> {
+        self.update(|entries| {
+            entries.insert(name.to_string(), value.to_string());
+        })
+    }
+
+    fn delete(&self, name: &str) -> Result<(), String> {
+        if self.get(name)?.is_none() {
+            return Ok(());
+        }
+        self.update(|entries| {
+            entries.remove(name);
+        })
+    }
+
+    fn backend(&self) -> SecretBackend {
+        SecretBackend::Vault
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_persists_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.vault");
+
+        assert!(VaultStore::open(path.clone(), b"passphrase").is_err());
+        let vault = VaultStore::create(path.clone(), b"passphrase", BTreeMap::new()).unwrap();
+        assert!(path.exists());
+        assert!(!vault.is_legacy());
+        vault.set("api_key.summary.openai", "sk-live-123").unwrap();
+        vault.set("api_key.summary.groq", "gsk-456").unwrap();
+        vault.delete("api_key.summary.groq").unwrap();
+
+        let raw = std::fs::read(&path).unwrap();
+        assert!(raw.starts_with(MAGIC));
+        assert!(!String::from_utf8_lossy(&raw).contains("sk-live-123"));
+
+        let reopened = VaultStore::open(path.clone(), b"passphrase").unwrap();
+        assert_eq!(reopened.get("api_key.summary.openai").unwrap().as_deref(), Some("sk-live-123"));
+        assert_eq!(reopened.get("api_key.summary.groq").unwrap(), None);
+
+        assert!(VaultStore::open(path, b"wrong passphrase").is_err());
+    }
+}
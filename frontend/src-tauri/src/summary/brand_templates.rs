@@ -72,7 +72,8 @@ fn get_bundled_dir() -> Option<PathBuf> {
     BUNDLED_BRAND_TEMPLATES_DIR.read().ok()?.clone()
 }
 
-fn get_user_dir() -> Option<PathBuf> {
+/// Directory of the user's own brand templates, once set at startup
+pub fn get_user_dir() -> Option<PathBuf> {
     USER_BRAND_TEMPLATES_DIR.read().ok()?.clone()
 }
 
@@ -1,9 +1,10 @@
 use crate::database::repositories::{
     meeting::MeetingsRepository, summary::SummaryProcessesRepository,
-    transcript_chunk::TranscriptChunksRepository,
+    transcript::TranscriptsRepository, transcript_chunk::TranscriptChunksRepository,
 };
 use crate::state::AppState;
 use crate::summary::service::SummaryService;
+use crate::summary::template_selection::{CalendarContext, AUTO_TEMPLATE_ID};
 use log::{error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
@@ -176,6 +177,8 @@ pub async fn api_process_transcript<R: Runtime>(
     custom_prompt: Option<String>,
     template_id: Option<String>,
     _auth_token: Option<String>,
+    transcript_track: Option<String>,
+    calendar_event: Option<CalendarContext>,
 ) -> Result<ProcessTranscriptResponse, String> {
     use uuid::Uuid;
 
@@ -187,8 +190,28 @@ pub async fn api_process_transcript<R: Runtime>(
     );
 
     let pool = state.db_manager.pool().clone();
+
+    // Multilingual meetings can be summarized from the English translation track
+    // ("original" or absent keeps the text sent by the frontend)
+    let text = match transcript_track.as_deref() {
+        Some("translation") => {
+            let translated = TranscriptsRepository::get_meeting_text(&pool, &m_id, true)
+                .await
+                .map_err(|e| format!("Failed to load translated transcript: {}", e))?;
+            if translated.trim().is_empty() {
+                log_warn!("No stored transcript for {}, summarizing the provided text", &m_id);
+                text
+            } else {
+                log_info!("Summarizing translation track for meeting_id: {}", &m_id);
+                translated
+            }
+        }
+        _ => text,
+    };
+
     let final_prompt = custom_prompt.unwrap_or_else(|| "".to_string());
-    let final_template_id = template_id.unwrap_or_else(|| "daily_standup".to_string());
+    // Without a template the meeting is classified before summarization
+    let final_template_id = template_id.unwrap_or_else(|| AUTO_TEMPLATE_ID.to_string());
 
     // Create or reset the process entry in the database
     SummaryProcessesRepository::create_or_reset_process(&pool, &m_id)
@@ -227,6 +250,7 @@ pub async fn api_process_transcript<R: Runtime>(
             model_name,
             final_prompt,
             final_template_id,
+            calendar_event,
         )
         .await;
     });
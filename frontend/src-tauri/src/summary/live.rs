@@ -0,0 +1,461 @@
+//! Rolling live summary during long recordings.
+//!
+//! When enabled in the recording preferences, a background task wakes every few minutes while
+//! recording, sends only the transcript segments that arrived since the last update (plus the
+//! current running summary) to the configured summary model, and emits the updated summary
+//! as a `live-summary-updated` event. Each update is also written to live_summary.json in the
+//! meeting folder; when the meeting is saved it is stored with the meeting, and the final
+//! summary then reads the running summary plus the remaining transcript instead of the whole
+//! transcript.
+
+use crate::audio::recording_saver::TranscriptSegment;
+use crate::database::models::{MeetingLiveSummary, TranscriptVersionSegment};
+use crate::database::repositories::{
+    live_summary::LiveSummariesRepository, meeting::MeetingsRepository, redaction_audit::RedactionAuditsRepository,
+    setting::SettingsRepository, transcript_version::TranscriptVersionsRepository,
+};
+use crate::state::AppState;
+use crate::summary::llm_client::generate_summary;
+use crate::summary::processor::{clean_llm_markdown_output, rough_token_count};
+use crate::summary::redaction;
+use crate::summary::service::{SummaryService, HTTP_CLIENT};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// Default minutes between live summary updates
+pub const DEFAULT_INTERVAL_MINUTES: u64 = 5;
+
+/// New transcript sent per update (rough tokens); the rest waits for the next update
+const MAX_NEW_TOKENS: usize = 3000;
+
+/// Updates with fewer new words than this are skipped
+const MIN_NEW_WORDS: usize = 40;
+
+/// Length limit given to the model for the running summary
+const MAX_SUMMARY_WORDS: usize = 250;
+
+/// Completion budget, for providers that accept one
+const MAX_COMPLETION_TOKENS: u32 = 600;
+
+/// File in the meeting folder holding the latest running summary
+const LIVE_SUMMARY_FILE: &str = "live_summary.json";
+
+const UPDATE_SYSTEM_PROMPT: &str = "You keep a running summary of a meeting that is still in progress. \
+You get the current summary and the newest part of the transcript. Reply with the updated summary only: \
+markdown bullet points under short bold topic headings. Keep decisions, action items with owners and open \
+questions; drop details that no longer matter. Write in the language of the transcript.";
+
+/// Running summary of the recording in progress
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LiveSummary {
+    pub summary: String,
+    /// Recording time (seconds) up to which the transcript is summarized
+    pub covered_until: f64,
+    pub updates: u32,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Live segments already summarized
+    #[serde(skip)]
+    covered: HashSet<u64>,
+}
+
+struct LiveSummaryTask {
+    cancel: CancellationToken,
+    state: Arc<Mutex<LiveSummary>>,
+}
+
+static LIVE_SUMMARY_TASK: Lazy<Mutex<Option<LiveSummaryTask>>> = Lazy::new(|| Mutex::new(None));
+
+/// Segments not summarized yet, in recording order, within the token budget. The first new
+/// segment is always included so an update never stalls on one long segment.
+fn select_new_segments<'a>(
+    segments: &'a [TranscriptSegment],
+    covered: &HashSet<u64>,
+    max_tokens: usize,
+) -> Vec<&'a TranscriptSegment> {
+    let mut pending: Vec<&TranscriptSegment> = segments
+        .iter()
+        .filter(|s| !covered.contains(&s.sequence_id) && !s.text.trim().is_empty())
+        .collect();
+    pending.sort_by(|a, b| a.audio_start_time.total_cmp(&b.audio_start_time));
+
+    let mut tokens = 0;
+    let mut selected = Vec::new();
+    for segment in pending {
+        let segment_tokens = rough_token_count(&segment.text);
+        if !selected.is_empty() && tokens + segment_tokens > max_tokens {
+            break;
+        }
+        tokens += segment_tokens;
+        selected.push(segment);
+    }
+    selected
+}
+
+fn update_prompt(current_summary: &str, new_transcript: &str) -> String {
+    let current = if current_summary.trim().is_empty() {
+        "Nothing yet - the meeting just started."
+    } else {
+        current_summary.trim()
+    };
+    format!(
+        "Update the summary with the new transcript. Use at most {} words.\n\n<current_summary>\n{}\n</current_summary>\n\n<new_transcript>\n{}\n</new_transcript>",
+        MAX_SUMMARY_WORDS, current, new_transcript
+    )
+}
+
+/// MM:SS, or H:MM:SS from one hour on
+fn format_time(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    if total >= 3600 {
+        format!("{}:{:02}:{:02}", total / 3600, (total / 60) % 60, total % 60)
+    } else {
+        format!("{:02}:{:02}", total / 60, total % 60)
+    }
+}
+
+/// Final-summary input built from the running summary and the transcript after it
+fn seeded_transcript(summary: &str, covered_until: f64, tail: &[TranscriptVersionSegment]) -> String {
+    let tail_text = tail
+        .iter()
+        .map(|s| s.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "Summary of the meeting up to {time}, written during the recording:\n\n{}\n\nTranscript from {time} to the end:\n\n{}",
+        summary.trim(),
+        if tail_text.is_empty() { "(nothing further was said)" } else { tail_text.as_str() },
+        time = format_time(covered_until)
+    )
+}
+
+/// Starts the live summary task for the recording that just started, if enabled
+pub fn start_live_summary<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let interval_minutes = match crate::audio::recording_preferences::load_recording_preferences(&app).await {
+            Ok(prefs) if prefs.live_summary_enabled => prefs.live_summary_interval_minutes.max(1),
+            Ok(_) => return,
+            Err(e) => {
+                warn!("Live summary skipped, failed to load recording preferences: {}", e);
+                return;
+            }
+        };
+        let Some(state) = app.try_state::<AppState>() else {
+            warn!("Live summary skipped, app state not available");
+            return;
+        };
+        let pool = state.db_manager.pool().clone();
+        let (provider, model) = match SettingsRepository::get_model_config(&pool).await {
+            Ok(Some(setting)) if !setting.provider.is_empty() && !setting.model.is_empty() => {
+                (setting.provider, setting.model)
+            }
+            Ok(_) => {
+                warn!("Live summary skipped, no summary model is configured");
+                return;
+            }
+            Err(e) => {
+                warn!("Live summary skipped, failed to load model config: {}", e);
+                return;
+            }
+        };
+
+        let cancel = CancellationToken::new();
+        let state = Arc::new(Mutex::new(LiveSummary {
+            provider: Some(provider.clone()),
+            model: Some(model.clone()),
+            ..Default::default()
+        }));
+        if let Ok(mut task) = LIVE_SUMMARY_TASK.lock() {
+            if let Some(previous) = task.replace(LiveSummaryTask {
+                cancel: cancel.clone(),
+                state: state.clone(),
+            }) {
+                previous.cancel.cancel();
+            }
+        }
+        info!(
+            "Live summary started ({} / {}, every {} min)",
+            provider, model, interval_minutes
+        );
+
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(interval_minutes * 60)) => {}
+                _ = cancel.cancelled() => break,
+            }
+            if crate::audio::recording_commands::is_recording_paused().await {
+                continue;
+            }
+            if let Err(e) = update_once(&app, &pool, &state, &provider, &model, &cancel).await {
+                if cancel.is_cancelled() {
+                    break;
+                }
+                warn!("Live summary update failed: {}", e);
+                let _ = app.emit("live-summary-error", serde_json::json!({ "error": e }));
+            }
+        }
+        info!("Live summary stopped");
+    });
+}
+
+/// Stops the live summary task; the last update stays in live_summary.json
+pub fn stop_live_summary() {
+    if let Ok(mut task) = LIVE_SUMMARY_TASK.lock() {
+        if let Some(task) = task.take() {
+            task.cancel.cancel();
+        }
+    }
+}
+
+async fn update_once<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    state: &Arc<Mutex<LiveSummary>>,
+    provider: &str,
+    model: &str,
+    cancel: &CancellationToken,
+) -> Result<(), String> {
+    let segments = crate::audio::recording_commands::get_transcript_history().await?;
+    let (current_summary, covered) = {
+        let state = state.lock().map_err(|_| "Live summary state is poisoned".to_string())?;
+        (state.summary.clone(), state.covered.clone())
+    };
+
+    let new_segments = select_new_segments(&segments, &covered, MAX_NEW_TOKENS);
+    let new_text = new_segments
+        .iter()
+        .map(|s| s.text.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if new_text.split_whitespace().count() < MIN_NEW_WORDS {
+        return Ok(());
+    }
+
+    let connection = SummaryService::resolve_connection(pool, provider).await?;
+    let prompt = update_prompt(&current_summary, &new_text);
+    let mut redactor = redaction::redactor_for(app, pool, &connection.provider, &[prompt.as_str()]).await?;
+    let (system_prompt, prompt) = match redactor.as_mut() {
+        Some(redactor) => (
+            format!("{} {}", UPDATE_SYSTEM_PROMPT, redaction::PLACEHOLDER_NOTE),
+            redactor.redact(&prompt),
+        ),
+        None => (UPDATE_SYSTEM_PROMPT.to_string(), prompt),
+    };
+
+    let app_data_dir = app.path().app_data_dir().ok();
+    let client = HTTP_CLIENT.clone();
+    let reply = generate_summary(
+        &client,
+        &connection.provider,
+        model,
+        &connection.api_key,
+        &system_prompt,
+        &prompt,
+        connection.ollama_endpoint.as_deref(),
+        connection.custom_openai_endpoint.as_deref(),
+        Some(connection.max_tokens.map_or(MAX_COMPLETION_TOKENS, |t| t.min(MAX_COMPLETION_TOKENS))),
+        connection.temperature,
+        connection.top_p,
+        app_data_dir.as_ref(),
+        Some(cancel),
+    )
+    .await;
+    // Linked to the meeting when the recording is saved (see import_live_summary)
+    if let Some(redactor) = &redactor {
+        redaction::record_audit(pool, None, "live_summary", provider, model, redactor).await;
+    }
+    let reply = match &redactor {
+        Some(redactor) => redactor.restore(&reply?),
+        None => reply?,
+    };
+    let summary = clean_llm_markdown_output(&reply);
+    if summary.trim().is_empty() {
+        return Err("The model returned an empty summary".to_string());
+    }
+
+    let snapshot = {
+        let mut state = state.lock().map_err(|_| "Live summary state is poisoned".to_string())?;
+        state.summary = summary;
+        state.updates += 1;
+        state.updated_at = Some(Utc::now());
+        for segment in &new_segments {
+            state.covered.insert(segment.sequence_id);
+            state.covered_until = state.covered_until.max(segment.audio_end_time);
+        }
+        state.clone()
+    };
+
+    if let Ok(Some(folder)) = crate::audio::recording_commands::get_meeting_folder_path().await {
+        let path = PathBuf::from(folder).join(LIVE_SUMMARY_FILE);
+        match serde_json::to_string_pretty(&snapshot) {
+            Ok(json) => {
+                if let Err(e) = crate::encryption::files::write_file(&path, json.as_bytes()) {
+                    warn!("Failed to write {}: {}", path.display(), e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize live summary: {}", e),
+        }
+    }
+
+    info!(
+        "Live summary update {} covers {:.0}s of the recording",
+        snapshot.updates, snapshot.covered_until
+    );
+    let _ = app.emit("live-summary-updated", &snapshot);
+    Ok(())
+}
+
+/// Stores the running summary written during recording (live_summary.json in the meeting
+/// folder) with the saved meeting
+pub async fn import_live_summary(pool: &SqlitePool, meeting_id: &str, folder_path: Option<&str>) {
+    let Some(path) = folder_path.map(|folder| PathBuf::from(folder).join(LIVE_SUMMARY_FILE)) else {
+        return;
+    };
+    if !crate::encryption::files::exists(&path) {
+        return;
+    }
+    match RedactionAuditsRepository::assign_recording_audits(pool, meeting_id).await {
+        Ok(0) => {}
+        Ok(linked) => info!("Linked {} live summary redaction audits to meeting {}", linked, meeting_id),
+        Err(e) => warn!("Failed to link live summary redaction audits to meeting {}: {}", meeting_id, e),
+    }
+    let live = match crate::encryption::files::read_to_string(&path)
+        .and_then(|json| serde_json::from_str::<LiveSummary>(&json).map_err(|e| e.to_string()))
+    {
+        Ok(live) if !live.summary.trim().is_empty() => live,
+        Ok(_) => return,
+        Err(e) => {
+            warn!("Failed to read {}: {}", path.display(), e);
+            return;
+        }
+    };
+
+    let row = MeetingLiveSummary {
+        meeting_id: meeting_id.to_string(),
+        summary: live.summary,
+        covered_until: live.covered_until,
+        updates: live.updates as i64,
+        provider: live.provider,
+        model: live.model,
+        created_at: Utc::now(),
+    };
+    if let Err(e) = LiveSummariesRepository::save_live_summary(pool, &row).await {
+        warn!("Failed to save live summary for meeting {}: {}", meeting_id, e);
+    }
+}
+
+/// Final-summary input seeded with the meeting's running summary, so only the transcript after
+/// it is read again. None when there is no running summary or the meeting changed since.
+pub async fn seeded_summary_input(pool: &SqlitePool, meeting_id: &str) -> Option<String> {
+    let live = LiveSummariesRepository::get_live_summary(pool, meeting_id).await.ok()??;
+    if live.summary.trim().is_empty() || live.covered_until <= 0.0 {
+        return None;
+    }
+    let meeting = MeetingsRepository::get_meeting_metadata(pool, meeting_id).await.ok()??;
+    if meeting.updated_at.0 > live.created_at {
+        info!("Live summary of meeting {} predates transcript changes, not used", meeting_id);
+        return None;
+    }
+
+    let segments = TranscriptVersionsRepository::get_current_segments(pool, meeting_id).await.ok()?;
+    if segments.iter().any(|s| s.audio_start_time.is_none()) {
+        return None;
+    }
+    let tail: Vec<TranscriptVersionSegment> = segments
+        .into_iter()
+        .filter(|s| s.audio_end_time.unwrap_or_default() > live.covered_until)
+        .collect();
+
+    info!(
+        "Seeding summary of meeting {} with its live summary ({:.0}s covered, {} segments after)",
+        meeting_id,
+        live.covered_until,
+        tail.len()
+    );
+    Some(seeded_transcript(&live.summary, live.covered_until, &tail))
+}
+
+/// Running summary of the recording in progress (for syncing the UI after a reload)
+#[tauri::command]
+pub async fn get_live_summary() -> Result<Option<LiveSummary>, String> {
+    let task = LIVE_SUMMARY_TASK
+        .lock()
+        .map_err(|_| "Live summary state is poisoned".to_string())?;
+    Ok(task
+        .as_ref()
+        .and_then(|task| task.state.lock().ok().map(|state| state.clone()))
+        .filter(|state| state.updates > 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::segment;
+
+    fn live_segment(sequence_id: u64, text: &str, start: f64) -> TranscriptSegment {
+        TranscriptSegment {
+            id: format!("seg_{}", sequence_id),
+            text: text.to_string(),
+            audio_start_time: start,
+            audio_end_time: start + 5.0,
+            duration: 5.0,
+            display_time: String::new(),
+            confidence: 1.0,
+            sequence_id,
+            language: None,
+            translation: None,
+        }
+    }
+
+    #[test]
+    fn test_select_new_segments() {
+        let segments = vec![
+            live_segment(3, "third part", 20.0),
+            live_segment(1, "first part", 0.0),
+            live_segment(2, "second part", 10.0),
+            live_segment(4, "   ", 30.0),
+        ];
+        let covered: HashSet<u64> = [1].into_iter().collect();
+        let ids: Vec<u64> = select_new_segments(&segments, &covered, 1000)
+            .iter()
+            .map(|s| s.sequence_id)
+            .collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        // Over budget: stops early but always takes the first new segment
+        let ids: Vec<u64> = select_new_segments(&segments, &covered, 1)
+            .iter()
+            .map(|s| s.sequence_id)
+            .collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn test_update_prompt() {
+        let first = update_prompt("", "hello everyone");
+        assert!(first.contains("Nothing yet"));
+        assert!(first.contains("<new_transcript>\nhello everyone\n</new_transcript>"));
+        assert!(update_prompt("- **Budget**: approved", "next").contains("- **Budget**: approved"));
+    }
+
+    #[test]
+    fn test_seeded_transcript() {
+        let tail = vec![segment("t1", " Let's wrap up. ", 600.0, 605.0)];
+        let seeded = seeded_transcript("- Budget approved", 598.4, &tail);
+        assert!(seeded.starts_with("Summary of the meeting up to 09:58, written during the recording:\n\n- Budget approved"));
+        assert!(seeded.ends_with("Transcript from 09:58 to the end:\n\nLet's wrap up."));
+        assert!(seeded_transcript("x", 10.0, &[]).ends_with("(nothing further was said)"));
+    }
+}
@@ -4,7 +4,10 @@
 /// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter, CustomOpenAI)
 /// - Processor for chunking transcripts and generating summaries
 /// - Service layer for orchestrating summary generation
-/// - Templates for structured meeting summary generation
+/// - Rolling live summary during recording, which seeds the final summary
+/// - Redaction of personal details before transcripts are sent to a provider
+/// - Templates for structured meeting summary generation, synced from configurable sources
+/// - Automatic template selection by meeting classification
 /// - Tauri commands for frontend integration
 
 use serde::{Deserialize, Serialize};
@@ -32,11 +35,15 @@ pub struct CustomOpenAIConfig {
 
 pub mod brand_templates;
 pub mod commands;
+pub mod live;
 pub mod llm_client;
 pub mod processor;
+pub mod redaction;
 pub mod service;
 pub mod summary_engine;
 pub mod template_commands;
+pub mod template_selection;
+pub mod template_sources;
 pub mod templates;
 
 // Re-export Tauri commands (with their generated __cmd__ variants)
@@ -53,6 +60,18 @@ pub use template_commands::{
     api_sync_templates, api_validate_template, sync_templates_internal,
 };
 
+// Re-export user template commands
+pub use template_commands::{
+    __cmd__api_check_template, __cmd__api_create_user_template, __cmd__api_delete_user_template,
+    __cmd__api_duplicate_template, __cmd__api_export_template, __cmd__api_get_user_template,
+    __cmd__api_import_template, __cmd__api_list_template_versions, __cmd__api_list_user_templates,
+    __cmd__api_preview_template, __cmd__api_restore_template_version,
+    __cmd__api_update_user_template, api_check_template, api_create_user_template,
+    api_delete_user_template, api_duplicate_template, api_export_template, api_get_user_template,
+    api_import_template, api_list_template_versions, api_list_user_templates,
+    api_preview_template, api_restore_template_version, api_update_user_template,
+};
+
 // Re-export brand template commands
 pub use brand_templates::{
     __cmd__api_delete_brand_template, __cmd__api_get_brand_template,
@@ -0,0 +1,125 @@
+use super::{RedactedEntity, RedactionSettings};
+use crate::database::repositories::redaction_audit::RedactionAuditsRepository;
+use crate::state::AppState;
+use crate::summary::llm_client::LLMProvider;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+use tracing::{info, warn};
+
+/// A redacted request as shown in the audit view
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactionAuditRecord {
+    pub id: String,
+    pub meeting_id: Option<String>,
+    pub purpose: String,
+    pub provider: String,
+    pub model: String,
+    pub ner_model: Option<String>,
+    pub entities: Vec<RedactedEntity>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactionPreview {
+    pub text: String,
+    pub entities: Vec<RedactedEntity>,
+}
+
+/// Trimmed, non-empty, case-insensitively unique terms in the user's order
+fn normalize_terms(terms: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for term in terms {
+        let term = term.trim().to_string();
+        if !term.is_empty() && !normalized.iter().any(|t| t.eq_ignore_ascii_case(&term)) {
+            normalized.push(term);
+        }
+    }
+    normalized
+}
+
+#[tauri::command]
+pub async fn api_get_redaction_settings<R: Runtime>(app: AppHandle<R>) -> Result<RedactionSettings, String> {
+    Ok(super::load_settings(&app))
+}
+
+#[tauri::command]
+pub async fn api_save_redaction_settings<R: Runtime>(
+    app: AppHandle<R>,
+    mut settings: RedactionSettings,
+) -> Result<RedactionSettings, String> {
+    settings.custom_terms = normalize_terms(settings.custom_terms);
+    settings.allowed_terms = normalize_terms(settings.allowed_terms);
+    let mut kinds = Vec::with_capacity(settings.kinds.len());
+    for kind in settings.kinds {
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    settings.kinds = kinds;
+    settings.ner_provider = settings.ner_provider.filter(|p| !p.trim().is_empty());
+    settings.ner_model = settings.ner_model.filter(|m| !m.trim().is_empty());
+
+    if let Some(provider) = &settings.ner_provider {
+        if !matches!(LLMProvider::from_str(provider)?, LLMProvider::Ollama | LLMProvider::BuiltInAI) {
+            return Err("The NER model must run locally (Ollama or the built-in model)".to_string());
+        }
+        if settings.ner_model.is_none() {
+            return Err("Choose the model to use for name detection".to_string());
+        }
+    }
+
+    super::save_settings(&app, &settings)?;
+    info!(
+        "Saved redaction settings (enabled: {}, {} kinds, {} custom terms, NER: {:?})",
+        settings.enabled,
+        settings.kinds.len(),
+        settings.custom_terms.len(),
+        settings.ner_model
+    );
+    Ok(settings)
+}
+
+/// Shows what the current settings would redact in `text` (whether or not redaction is enabled)
+#[tauri::command]
+pub async fn api_preview_redaction<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    text: String,
+) -> Result<RedactionPreview, String> {
+    let settings = super::load_settings(&app);
+    let mut redactor = super::build_redactor(&app, state.db_manager.pool(), &settings, &[text.as_str()]).await?;
+    let text = redactor.redact(&text);
+    Ok(RedactionPreview {
+        text,
+        entities: redactor.entities().to_vec(),
+    })
+}
+
+/// What was redacted from the LLM requests of a meeting, newest first
+#[tauri::command]
+pub async fn api_get_redaction_audits(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<RedactionAuditRecord>, String> {
+    let audits = RedactionAuditsRepository::get_audits(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load redaction audits: {}", e))?;
+
+    Ok(audits
+        .into_iter()
+        .map(|audit| RedactionAuditRecord {
+            entities: serde_json::from_str(&audit.entities).unwrap_or_else(|e| {
+                warn!("Unreadable entities in redaction audit {}: {}", audit.id, e);
+                Vec::new()
+            }),
+            id: audit.id,
+            meeting_id: audit.meeting_id,
+            purpose: audit.purpose,
+            provider: audit.provider,
+            model: audit.model,
+            ner_model: audit.ner_model,
+            created_at: audit.created_at,
+        })
+        .collect())
+}
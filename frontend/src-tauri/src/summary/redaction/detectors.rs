@@ -0,0 +1,202 @@
+//! Regex detectors for personal details in transcripts.
+//!
+//! Numbers are validated where a checksum exists (Luhn for payment cards, modulus 11 for NHS
+//! numbers) so ordinary figures are left alone. Identifiers that have no fixed shape (medical
+//! record numbers, dates of birth) are only detected after a keyword such as "MRN" or
+//! "date of birth".
+
+use super::{Detection, Detector, EntityKind};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static EMAIL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b").expect("valid email pattern"));
+
+static PHONE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{1,4}\)[\s.-]?)?\d{2,4}(?:[\s.-]?\d{2,4}){2,4}")
+        .expect("valid phone pattern")
+});
+
+static PAYMENT_CARD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:\d[ -]?){12,18}\d\b").expect("valid card pattern"));
+
+static US_SSN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").expect("valid SSN pattern"));
+
+static UK_NINO: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b[A-CEGHJ-PR-TW-Z][A-CEGHJ-NPR-TW-Z] ?\d{2} ?\d{2} ?\d{2} ?[A-D]\b").expect("valid NINO pattern")
+});
+
+static NHS_NUMBER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b\d{3}[ -]?\d{3}[ -]?\d{4}\b").expect("valid NHS number pattern"));
+
+static MEDICAL_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:mrn|medical record(?: number)?|patient (?:id|number)|nhs number|health card(?: number)?|insurance (?:id|number)|member id|policy number)(?: is|\s*[:#])?\s*(?:no\.?\s*)?([a-z0-9-]*\d[a-z0-9-]*)",
+    )
+    .expect("valid medical id pattern")
+});
+
+static DATE_OF_BIRTH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:date of birth|d\.?o\.?b\.?|born on|birthday is)(?: is| was|\s*:)?\s*(\d{1,2}[/.-]\d{1,2}[/.-]\d{2,4}|\d{4}-\d{2}-\d{2}|(?:\d{1,2}(?:st|nd|rd|th)?\s+)?(?:jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*\.?(?:\s+\d{1,2}(?:st|nd|rd|th)?)?,?\s+\d{4})",
+    )
+    .expect("valid date of birth pattern")
+});
+
+static IP_ADDRESS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}\b").expect("valid IP address pattern"));
+
+static STREET_ADDRESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b\d{1,5}\s+(?:[A-Z][a-zA-Z'-]*\s+){1,3}(?:Street|St|Avenue|Ave|Road|Rd|Boulevard|Blvd|Lane|Ln|Drive|Dr|Court|Ct|Way|Place|Pl|Terrace|Close|Crescent|Square|Highway|Hwy|Parkway)\b\.?",
+    )
+    .expect("valid street address pattern")
+});
+
+static UK_POSTCODE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b[A-Z]{1,2}\d[A-Z\d]? ?\d[A-Z]{2}\b").expect("valid postcode pattern"));
+
+static TITLED_NAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:Mr|Mrs|Ms|Miss|Mx|Dr|Doctor|Prof|Professor|Nurse)\.?\s+([A-Z][a-z'-]+(?:\s+[A-Z][a-z'-]+)?)")
+        .expect("valid titled name pattern")
+});
+
+fn digits(value: &str) -> Vec<u32> {
+    value.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+fn luhn_valid(value: &str) -> bool {
+    let digits = digits(value);
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 1 { if d * 2 > 9 { d * 2 - 9 } else { d * 2 } } else { d })
+        .sum();
+    (13..=19).contains(&digits.len()) && sum % 10 == 0
+}
+
+fn nhs_number_valid(value: &str) -> bool {
+    let digits = digits(value);
+    if digits.len() != 10 {
+        return false;
+    }
+    let sum: u32 = digits[..9].iter().enumerate().map(|(i, d)| d * (10 - i as u32)).sum();
+    let check = 11 - sum % 11;
+    match check {
+        11 => digits[9] == 0,
+        10 => false,
+        check => digits[9] == check,
+    }
+}
+
+fn phone_valid(text: &str, start: usize, value: &str) -> bool {
+    let count = digits(value).len();
+    // Must not continue a longer number or word ("+" and "(" are outside the \b of the pattern)
+    let preceded = text[..start].chars().next_back().is_some_and(|c| c.is_alphanumeric());
+    let iso_date = value.as_bytes().get(4) == Some(&b'-')
+        && value.get(..4).is_some_and(|year| year.chars().all(|c| c.is_ascii_digit()));
+    !preceded && !iso_date && (count >= 10 || (count == 9 && value.starts_with('+'))) && count <= 15
+}
+
+fn ip_valid(value: &str) -> bool {
+    value.split('.').all(|octet| octet.parse::<u8>().is_ok())
+}
+
+/// All detections of the enabled kinds; overlapping detections are resolved by the caller
+pub(super) fn detect(text: &str, kinds: &[EntityKind]) -> Vec<Detection> {
+    let mut detections = Vec::new();
+    let mut push = |kind: EntityKind, start: usize, end: usize| {
+        if kinds.contains(&kind) {
+            detections.push(Detection {
+                start,
+                end,
+                kind,
+                detector: Detector::Pattern,
+            });
+        }
+    };
+
+    for m in EMAIL.find_iter(text) {
+        push(EntityKind::Email, m.start(), m.end());
+    }
+    for m in PAYMENT_CARD.find_iter(text).filter(|m| luhn_valid(m.as_str())) {
+        push(EntityKind::PaymentCard, m.start(), m.end());
+    }
+    for m in US_SSN.find_iter(text).chain(UK_NINO.find_iter(text)) {
+        push(EntityKind::NationalId, m.start(), m.end());
+    }
+    for m in NHS_NUMBER.find_iter(text).filter(|m| nhs_number_valid(m.as_str())) {
+        push(EntityKind::MedicalId, m.start(), m.end());
+    }
+    for caps in MEDICAL_ID.captures_iter(text) {
+        if let Some(id) = caps.get(1).filter(|id| id.as_str().len() >= 4) {
+            push(EntityKind::MedicalId, id.start(), id.end());
+        }
+    }
+    for caps in DATE_OF_BIRTH.captures_iter(text) {
+        if let Some(date) = caps.get(1) {
+            push(EntityKind::DateOfBirth, date.start(), date.end());
+        }
+    }
+    for m in PHONE.find_iter(text).filter(|m| phone_valid(text, m.start(), m.as_str())) {
+        push(EntityKind::Phone, m.start(), m.end());
+    }
+    for m in IP_ADDRESS.find_iter(text).filter(|m| ip_valid(m.as_str())) {
+        push(EntityKind::IpAddress, m.start(), m.end());
+    }
+    for m in STREET_ADDRESS.find_iter(text).chain(UK_POSTCODE.find_iter(text)) {
+        push(EntityKind::Address, m.start(), m.end());
+    }
+    for caps in TITLED_NAME.captures_iter(text) {
+        if let Some(name) = caps.get(1) {
+            push(EntityKind::Person, name.start(), name.end());
+        }
+    }
+    detections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(text: &str) -> Vec<(EntityKind, &str)> {
+        detect(text, EntityKind::ALL)
+            .into_iter()
+            .map(|d| (d.kind, &text[d.start..d.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert!(luhn_valid("4111 1111 1111 1111"));
+        assert!(!luhn_valid("4111 1111 1111 1112"));
+        assert!(nhs_number_valid("943 476 5919"));
+        assert!(!nhs_number_valid("943 476 5918"));
+    }
+
+    #[test]
+    fn test_detects_contact_details() {
+        let text = "Mail jane.doe@example.org or call +44 20 7946 0958 about 12 Baker Street, NW1 6XE.";
+        let found = found(text);
+        assert!(found.contains(&(EntityKind::Email, "jane.doe@example.org")));
+        assert!(found.contains(&(EntityKind::Phone, "+44 20 7946 0958")));
+        assert!(found.contains(&(EntityKind::Address, "12 Baker Street")));
+        assert!(found.contains(&(EntityKind::Address, "NW1 6XE")));
+    }
+
+    #[test]
+    fn test_detects_identifiers_after_keywords() {
+        let text = "Patient ID: A-20391, date of birth 3 March 1980, seen by Dr. Alvarez.";
+        let found = found(text);
+        assert!(found.contains(&(EntityKind::MedicalId, "A-20391")));
+        assert!(found.contains(&(EntityKind::DateOfBirth, "3 March 1980")));
+        assert!(found.contains(&(EntityKind::Person, "Alvarez")));
+    }
+
+    #[test]
+    fn test_ignores_ordinary_numbers() {
+        let text = "Revenue grew 12.5% to 3,400,000 in 2024, meeting at 10:30 on 2024-05-01 10am, ticket 4111 1111 1111 1112.";
+        assert!(found(text).is_empty(), "{:?}", found(text));
+    }
+}
@@ -0,0 +1,541 @@
+//! Redaction of personal details (PII/PHI) before transcripts are sent to an LLM provider.
+//!
+//! When enabled, the transcript, the user context and the template variables of a summary
+//! request (and each live summary update) are run through regex detectors (see `detectors`),
+//! user-listed terms and, optionally, a local NER model (see `ner`). Every detected value is
+//! replaced by a placeholder such as `[PERSON_1]`; the same value gets the same placeholder
+//! throughout a request. Placeholders in the model's reply are swapped back for the original
+//! values, and each redacted request is recorded in `redaction_audits`.
+//!
+//! By default only requests to cloud providers are redacted; Ollama and the built-in model run
+//! on this machine.
+
+pub mod commands;
+mod detectors;
+mod ner;
+
+use crate::database::models::RedactionAudit;
+use crate::database::repositories::redaction_audit::RedactionAuditsRepository;
+use crate::summary::llm_client::LLMProvider;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+const STORE_FILE: &str = "redaction.json";
+const STORE_KEY: &str = "settings";
+
+/// Appended to the instructions of redacted requests
+pub const PLACEHOLDER_NOTE: &str = "Personal details in the transcript were replaced by placeholders such as \
+[PERSON_1] or [PHONE_2]. Keep placeholders exactly as written, brackets included, wherever you refer to \
+those details, and never guess what they stand for.";
+
+static PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[([A-Za-z]+(?:_[A-Za-z]+)*)_(\d+)\]").expect("valid placeholder pattern"));
+
+/// Kind of personal detail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Person,
+    Email,
+    Phone,
+    Address,
+    MedicalId,
+    NationalId,
+    PaymentCard,
+    IpAddress,
+    DateOfBirth,
+    /// A term from the user's list (`RedactionSettings::custom_terms`)
+    CustomTerm,
+}
+
+impl EntityKind {
+    pub const ALL: &'static [EntityKind] = &[
+        EntityKind::Person,
+        EntityKind::Email,
+        EntityKind::Phone,
+        EntityKind::Address,
+        EntityKind::MedicalId,
+        EntityKind::NationalId,
+        EntityKind::PaymentCard,
+        EntityKind::IpAddress,
+        EntityKind::DateOfBirth,
+        EntityKind::CustomTerm,
+    ];
+
+    /// Placeholder prefix
+    fn label(self) -> &'static str {
+        match self {
+            EntityKind::Person => "PERSON",
+            EntityKind::Email => "EMAIL",
+            EntityKind::Phone => "PHONE",
+            EntityKind::Address => "ADDRESS",
+            EntityKind::MedicalId => "MEDICAL_ID",
+            EntityKind::NationalId => "NATIONAL_ID",
+            EntityKind::PaymentCard => "CARD",
+            EntityKind::IpAddress => "IP",
+            EntityKind::DateOfBirth => "DOB",
+            EntityKind::CustomTerm => "TERM",
+        }
+    }
+}
+
+/// What found a redacted value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Detector {
+    Pattern,
+    CustomTerm,
+    Ner,
+}
+
+/// A span of text to redact
+#[derive(Debug, Clone)]
+struct Detection {
+    start: usize,
+    end: usize,
+    kind: EntityKind,
+    detector: Detector,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Kinds of details to redact
+    #[serde(default = "default_kinds")]
+    pub kinds: Vec<EntityKind>,
+    /// Names and other terms that are always redacted (case-insensitive, whole words)
+    #[serde(default)]
+    pub custom_terms: Vec<String>,
+    /// Values that are never redacted, e.g. the company name
+    #[serde(default)]
+    pub allowed_terms: Vec<String>,
+    /// Also redact requests to Ollama and the built-in model
+    #[serde(default)]
+    pub redact_local_providers: bool,
+    /// Local model that finds names and addresses the patterns miss ("ollama" or "builtin-ai")
+    #[serde(default)]
+    pub ner_provider: Option<String>,
+    #[serde(default)]
+    pub ner_model: Option<String>,
+}
+
+fn default_kinds() -> Vec<EntityKind> {
+    EntityKind::ALL.to_vec()
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kinds: default_kinds(),
+            custom_terms: Vec::new(),
+            allowed_terms: Vec::new(),
+            redact_local_providers: false,
+            ner_provider: None,
+            ner_model: None,
+        }
+    }
+}
+
+impl RedactionSettings {
+    /// Whether requests to `provider` are redacted
+    pub fn applies_to(&self, provider: &LLMProvider) -> bool {
+        let local = matches!(provider, LLMProvider::Ollama | LLMProvider::BuiltInAI);
+        self.enabled && (self.redact_local_providers || !local)
+    }
+
+    fn ner(&self) -> Option<(&str, &str)> {
+        match (self.ner_provider.as_deref(), self.ner_model.as_deref()) {
+            (Some(provider), Some(model)) if !provider.is_empty() && !model.is_empty() => Some((provider, model)),
+            _ => None,
+        }
+    }
+}
+
+/// A redacted value with its placeholder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedactedEntity {
+    pub placeholder: String,
+    pub kind: EntityKind,
+    pub detector: Detector,
+    pub value: String,
+    pub occurrences: usize,
+}
+
+/// Redacts the texts of one request with shared placeholders and restores the reply
+pub struct Redactor {
+    kinds: Vec<EntityKind>,
+    custom_terms: Vec<Regex>,
+    allowed: HashSet<String>,
+    ner_terms: Vec<(Regex, EntityKind)>,
+    ner_model: Option<String>,
+    entities: Vec<RedactedEntity>,
+    index: HashMap<(EntityKind, String), usize>,
+}
+
+/// Case-insensitive whole-word pattern for a literal term
+fn term_pattern(term: &str) -> Option<Regex> {
+    let term = term.trim();
+    if term.chars().count() < 2 {
+        return None;
+    }
+    Regex::new(&format!(r"(?i)\b{}\b", regex::escape(term))).ok()
+}
+
+/// Key under which equal values share a placeholder
+fn normalize(kind: EntityKind, value: &str) -> String {
+    match kind {
+        EntityKind::Phone | EntityKind::PaymentCard | EntityKind::NationalId | EntityKind::MedicalId => value
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_uppercase)
+            .collect(),
+        _ => value.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase(),
+    }
+}
+
+/// Merges overlapping detections into their union, so no part of either reaches the provider.
+/// The merged span takes the kind of the highest-ranked detector (user terms, then patterns).
+fn resolve_overlaps(mut detections: Vec<Detection>) -> Vec<Detection> {
+    let rank = |d: &Detection| match d.detector {
+        Detector::CustomTerm => 0,
+        Detector::Pattern => 1,
+        Detector::Ner => 2,
+    };
+    detections.sort_by(|a, b| {
+        a.start
+            .cmp(&b.start)
+            .then((b.end - b.start).cmp(&(a.end - a.start)))
+            .then(rank(a).cmp(&rank(b)))
+    });
+
+    let mut kept: Vec<Detection> = Vec::new();
+    for detection in detections {
+        match kept.last_mut() {
+            Some(last) if detection.start < last.end => {
+                last.end = last.end.max(detection.end);
+                if rank(&detection) < rank(last) {
+                    last.kind = detection.kind;
+                    last.detector = detection.detector;
+                }
+            }
+            _ => kept.push(detection),
+        }
+    }
+    kept
+}
+
+impl Redactor {
+    pub fn new(settings: &RedactionSettings) -> Self {
+        Self {
+            kinds: settings.kinds.clone(),
+            custom_terms: settings.custom_terms.iter().filter_map(|t| term_pattern(t)).collect(),
+            allowed: settings
+                .allowed_terms
+                .iter()
+                .map(|t| t.trim().to_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            ner_terms: Vec::new(),
+            ner_model: None,
+            entities: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Adds values found by the NER model; they are redacted wherever they occur
+    fn with_ner_terms(mut self, model: &str, terms: Vec<(String, EntityKind)>) -> Self {
+        self.ner_model = Some(model.to_string());
+        self.ner_terms = terms
+            .into_iter()
+            .filter(|(_, kind)| self.kinds.contains(kind))
+            .filter_map(|(term, kind)| term_pattern(&term).map(|pattern| (pattern, kind)))
+            .collect();
+        self
+    }
+
+    fn detections(&self, text: &str) -> Vec<Detection> {
+        let mut detections = detectors::detect(text, &self.kinds);
+        if self.kinds.contains(&EntityKind::CustomTerm) {
+            for pattern in &self.custom_terms {
+                detections.extend(pattern.find_iter(text).map(|m| Detection {
+                    start: m.start(),
+                    end: m.end(),
+                    kind: EntityKind::CustomTerm,
+                    detector: Detector::CustomTerm,
+                }));
+            }
+        }
+        for (pattern, kind) in &self.ner_terms {
+            detections.extend(pattern.find_iter(text).map(|m| Detection {
+                start: m.start(),
+                end: m.end(),
+                kind: *kind,
+                detector: Detector::Ner,
+            }));
+        }
+        detections.retain(|d| !self.allowed.contains(&text[d.start..d.end].trim().to_lowercase()));
+        resolve_overlaps(detections)
+    }
+
+    fn placeholder(&mut self, detection: &Detection, value: &str) -> String {
+        let key = (detection.kind, normalize(detection.kind, value));
+        if let Some(&i) = self.index.get(&key) {
+            self.entities[i].occurrences += 1;
+            return self.entities[i].placeholder.clone();
+        }
+        let number = self.entities.iter().filter(|e| e.kind == detection.kind).count() + 1;
+        let placeholder = format!("[{}_{}]", detection.kind.label(), number);
+        self.index.insert(key, self.entities.len());
+        self.entities.push(RedactedEntity {
+            placeholder: placeholder.clone(),
+            kind: detection.kind,
+            detector: detection.detector,
+            value: value.to_string(),
+            occurrences: 1,
+        });
+        placeholder
+    }
+
+    /// Replaces the personal details in `text` with placeholders
+    pub fn redact(&mut self, text: &str) -> String {
+        let mut redacted = String::with_capacity(text.len());
+        let mut position = 0;
+        for detection in self.detections(text) {
+            redacted.push_str(&text[position..detection.start]);
+            let placeholder = self.placeholder(&detection, &text[detection.start..detection.end]);
+            redacted.push_str(&placeholder);
+            position = detection.end;
+        }
+        redacted.push_str(&text[position..]);
+        redacted
+    }
+
+    /// Byte ranges of the personal details in `text`, without redacting them
+    pub fn find(&self, text: &str) -> Vec<(std::ops::Range<usize>, EntityKind, Detector)> {
+        self.detections(text)
+            .into_iter()
+            .map(|d| (d.start..d.end, d.kind, d.detector))
+            .collect()
+    }
+
+    /// Puts the original values back in place of the placeholders in a model reply
+    pub fn restore(&self, text: &str) -> String {
+        let values: HashMap<String, &str> = self
+            .entities
+            .iter()
+            .map(|e| (e.placeholder.to_uppercase(), e.value.as_str()))
+            .collect();
+        PLACEHOLDER
+            .replace_all(text, |caps: &regex::Captures| {
+                let placeholder = caps[0].to_uppercase();
+                values
+                    .get(&placeholder)
+                    .map(|value| value.to_string())
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+
+    pub fn entities(&self) -> &[RedactedEntity] {
+        &self.entities
+    }
+}
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> RedactionSettings {
+    let store = match app.store(STORE_FILE) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to access redaction settings store: {}, using defaults", e);
+            return RedactionSettings::default();
+        }
+    };
+    match store.get(STORE_KEY) {
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            warn!("Failed to deserialize redaction settings: {}, using defaults", e);
+            RedactionSettings::default()
+        }),
+        None => RedactionSettings::default(),
+    }
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &RedactionSettings) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to access redaction settings store: {}", e))?;
+    let value = serde_json::to_value(settings).map_err(|e| format!("Failed to serialize redaction settings: {}", e))?;
+    store.set(STORE_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save redaction settings: {}", e))
+}
+
+/// Builds the redactor for a request to `provider`, None when redaction does not apply. With a
+/// NER model configured it first runs over `texts`; a failing NER model fails the request
+/// rather than letting names through.
+pub async fn redactor_for<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    provider: &LLMProvider,
+    texts: &[&str],
+) -> Result<Option<Redactor>, String> {
+    let settings = load_settings(app);
+    if !settings.applies_to(provider) {
+        return Ok(None);
+    }
+    build_redactor(app, pool, &settings, texts).await.map(Some)
+}
+
+/// Builds a redactor from `settings` whether or not redaction is enabled for LLM requests
+pub(crate) async fn build_redactor<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    settings: &RedactionSettings,
+    texts: &[&str],
+) -> Result<Redactor, String> {
+    let redactor = Redactor::new(settings);
+    match settings.ner() {
+        Some((provider, model)) => {
+            let terms = ner::find_entities(app, pool, provider, model, texts)
+                .await
+                .map_err(|e| format!("Redaction failed, the local NER model is not available: {}", e))?;
+            Ok(redactor.with_ner_terms(model, terms))
+        }
+        None => Ok(redactor),
+    }
+}
+
+/// Records what was redacted in a request; failures are logged
+pub async fn record_audit(
+    pool: &SqlitePool,
+    meeting_id: Option<&str>,
+    purpose: &str,
+    provider: &str,
+    model: &str,
+    redactor: &Redactor,
+) {
+    let entities = match serde_json::to_string(redactor.entities()) {
+        Ok(json) => json,
+        Err(e) => {
+            warn!("Failed to serialize redaction audit: {}", e);
+            return;
+        }
+    };
+    info!(
+        "Redacted {} values from {} request to {} / {}",
+        redactor.entities().len(),
+        purpose,
+        provider,
+        model
+    );
+    let audit = RedactionAudit {
+        id: format!("redaction-{}", Uuid::new_v4()),
+        meeting_id: meeting_id.map(str::to_string),
+        purpose: purpose.to_string(),
+        provider: provider.to_string(),
+        model: model.to_string(),
+        ner_model: redactor.ner_model.clone(),
+        entity_count: redactor.entities().len() as i64,
+        entities,
+        created_at: Utc::now(),
+    };
+    if let Err(e) = RedactionAuditsRepository::save_audit(pool, &audit).await {
+        warn!("Failed to save redaction audit: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> RedactionSettings {
+        RedactionSettings {
+            enabled: true,
+            custom_terms: vec!["Priya".to_string()],
+            allowed_terms: vec!["Acme".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_placeholders_are_stable_and_restored() {
+        let mut redactor = Redactor::new(&settings());
+        let text = "Priya will call 020 7946 0958. Reach priya at PRIYA@EXAMPLE.COM or 020-7946-0958.";
+        let redacted = redactor.redact(text);
+        assert_eq!(
+            redacted,
+            "[TERM_1] will call [PHONE_1]. Reach [TERM_1] at [EMAIL_1] or [PHONE_1]."
+        );
+        assert_eq!(redactor.entities().len(), 3);
+        assert_eq!(redactor.entities()[0].occurrences, 2);
+
+        // Later texts of the same request share the placeholders
+        assert_eq!(redactor.redact("Ask Priya"), "Ask [TERM_1]");
+
+        let reply = "- [term_1] owns the follow-up call to [PHONE_1]\n- Unknown [PERSON_9] stays";
+        assert_eq!(
+            redactor.restore(reply),
+            "- Priya owns the follow-up call to 020 7946 0958\n- Unknown [PERSON_9] stays"
+        );
+    }
+
+    #[test]
+    fn test_allowed_terms_and_kinds() {
+        let mut settings = settings();
+        settings.custom_terms.push("Acme".to_string());
+        settings.kinds.retain(|k| *k != EntityKind::Email);
+        let mut redactor = Redactor::new(&settings);
+        assert_eq!(redactor.redact("Acme support: help@acme.com"), "Acme support: help@acme.com");
+    }
+
+    #[test]
+    fn test_ner_terms() {
+        let mut redactor = Redactor::new(&settings()).with_ner_terms(
+            "llama3",
+            vec![
+                ("Jonas Berg".to_string(), EntityKind::Person),
+                ("Berg".to_string(), EntityKind::Person),
+            ],
+        );
+        assert_eq!(
+            redactor.redact("Jonas Berg met Dr. Berg."),
+            "[PERSON_1] met Dr. [PERSON_2]."
+        );
+        assert_eq!(redactor.entities()[1].detector, Detector::Pattern);
+    }
+
+    #[test]
+    fn test_overlapping_detections_are_merged() {
+        let detection = |start, end, kind, detector| Detection { start, end, kind, detector };
+        let merged = resolve_overlaps(vec![
+            detection(0, 10, EntityKind::Person, Detector::Ner),
+            detection(5, 30, EntityKind::Email, Detector::Pattern),
+            detection(40, 45, EntityKind::Person, Detector::Ner),
+        ]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!((merged[0].start, merged[0].end), (0, 30));
+        assert_eq!((merged[0].kind, merged[0].detector), (EntityKind::Email, Detector::Pattern));
+        assert_eq!((merged[1].start, merged[1].end), (40, 45));
+
+        let mut redactor = Redactor::new(&settings())
+            .with_ner_terms("llama3", vec![("John Smith".to_string(), EntityKind::Person)]);
+        assert_eq!(redactor.redact("Mail John Smith@example.com today"), "Mail [EMAIL_1] today");
+    }
+
+    #[test]
+    fn test_applies_to() {
+        let settings = settings();
+        assert!(settings.applies_to(&LLMProvider::OpenAI));
+        assert!(!settings.applies_to(&LLMProvider::Ollama));
+        assert!(!RedactionSettings::default().applies_to(&LLMProvider::Claude));
+    }
+}
@@ -0,0 +1,136 @@
+//! Named-entity recognition with a local model (Ollama or the built-in model).
+//!
+//! The model lists the names, addresses and identifiers in each chunk of the text as JSON.
+//! Only values that actually occur in the chunk are kept, so a model that invents entities
+//! cannot cause unrelated text to be redacted.
+
+use super::EntityKind;
+use crate::summary::llm_client::{generate_summary, LLMProvider};
+use crate::summary::processor::chunk_text;
+use crate::summary::service::{SummaryService, HTTP_CLIENT};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::info;
+
+/// Text per NER request (rough tokens); small local models lose entities in long inputs
+const NER_CHUNK_TOKENS: usize = 1200;
+const NER_CHUNK_OVERLAP_TOKENS: usize = 50;
+
+const MAX_COMPLETION_TOKENS: u32 = 800;
+
+const NER_SYSTEM_PROMPT: &str = "You find personal details in text. List every name of a person, every \
+street address or place of residence, and every identification number (medical record, insurance, patient \
+or ID numbers). Reply with a JSON array only, for example [{\"text\": \"Jane Doe\", \"type\": \"PERSON\"}], \
+using the types PERSON, ADDRESS and ID. Copy each text exactly as it appears. Reply [] when there are none.";
+
+#[derive(Debug, Deserialize)]
+struct NerEntity {
+    text: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+fn entity_kind(label: &str) -> Option<EntityKind> {
+    match label.trim().to_uppercase().as_str() {
+        "PERSON" | "PER" | "NAME" => Some(EntityKind::Person),
+        "ADDRESS" | "LOCATION" | "LOC" => Some(EntityKind::Address),
+        "ID" | "MEDICAL_ID" | "IDENTIFIER" => Some(EntityKind::MedicalId),
+        _ => None,
+    }
+}
+
+/// Entities of a model reply that occur in `chunk`
+fn parse_entities(reply: &str, chunk: &str) -> Vec<(String, EntityKind)> {
+    let (Some(start), Some(end)) = (reply.find('['), reply.rfind(']')) else {
+        return Vec::new();
+    };
+    if end < start {
+        return Vec::new();
+    }
+    let entities: Vec<NerEntity> = serde_json::from_str(&reply[start..=end]).unwrap_or_default();
+    entities
+        .into_iter()
+        .filter_map(|entity| {
+            let text = entity.text.trim().trim_matches(|c: char| c.is_ascii_punctuation()).to_string();
+            let kind = entity_kind(&entity.kind)?;
+            (text.chars().count() >= 2 && chunk.contains(&text)).then_some((text, kind))
+        })
+        .collect()
+}
+
+/// Names, addresses and identifiers in `texts` according to the local model
+pub(super) async fn find_entities<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    provider: &str,
+    model: &str,
+    texts: &[&str],
+) -> Result<Vec<(String, EntityKind)>, String> {
+    let connection = SummaryService::resolve_connection(pool, provider).await?;
+    if !matches!(connection.provider, LLMProvider::Ollama | LLMProvider::BuiltInAI) {
+        return Err(format!("{} is not a local provider", provider));
+    }
+    let app_data_dir = app.path().app_data_dir().ok();
+    let client = HTTP_CLIENT.clone();
+
+    let mut entities: Vec<(String, EntityKind)> = Vec::new();
+    let chunks: Vec<String> = texts
+        .iter()
+        .filter(|text| !text.trim().is_empty())
+        .flat_map(|text| chunk_text(text, NER_CHUNK_TOKENS, NER_CHUNK_OVERLAP_TOKENS))
+        .collect();
+    for chunk in &chunks {
+        let reply = generate_summary(
+            &client,
+            &connection.provider,
+            model,
+            &connection.api_key,
+            NER_SYSTEM_PROMPT,
+            &format!("<text>\n{}\n</text>", chunk),
+            connection.ollama_endpoint.as_deref(),
+            None,
+            Some(MAX_COMPLETION_TOKENS),
+            None,
+            None,
+            app_data_dir.as_ref(),
+            None,
+        )
+        .await?;
+        for entity in parse_entities(&reply, chunk) {
+            if !entities.contains(&entity) {
+                entities.push(entity);
+            }
+        }
+    }
+
+    info!(
+        "NER model {} found {} entities in {} chunks",
+        model,
+        entities.len(),
+        chunks.len()
+    );
+    Ok(entities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entities() {
+        let chunk = "Jonas Berg lives at Elm Court 4; his MRN is 44-1029.";
+        let reply = "Here you go:\n```json\n[{\"text\": \"Jonas Berg\", \"type\": \"PERSON\"}, \
+                     {\"text\": \"Elm Court 4\", \"type\": \"address\"}, {\"text\": \"44-1029.\", \"type\": \"ID\"}, \
+                     {\"text\": \"Maria Lund\", \"type\": \"PERSON\"}, {\"text\": \"Acme\", \"type\": \"ORG\"}]\n```";
+        assert_eq!(
+            parse_entities(reply, chunk),
+            vec![
+                ("Jonas Berg".to_string(), EntityKind::Person),
+                ("Elm Court 4".to_string(), EntityKind::Address),
+                ("44-1029".to_string(), EntityKind::MedicalId),
+            ]
+        );
+        assert!(parse_entities("No entities found.", chunk).is_empty());
+    }
+}
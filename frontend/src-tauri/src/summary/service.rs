@@ -1,8 +1,11 @@
 use crate::database::repositories::{
-    meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
+    chapter::ChaptersRepository, marker::MarkersRepository, meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
+    transcript_version::TranscriptVersionsRepository,
 };
 use crate::summary::llm_client::LLMProvider;
 use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary};
+use crate::summary::redaction;
+use crate::summary::template_selection::{self, CalendarContext, ClassifierModel};
 use crate::ollama::metadata::ModelMetadataCache;
 use sqlx::SqlitePool;
 use std::collections::HashMap;
@@ -19,7 +22,7 @@ static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
 });
 
 // Reusable HTTP client — avoids per-summary DNS resolution, connection pool init, and TLS setup
-static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+pub(crate) static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
     reqwest::Client::builder()
         .pool_max_idle_per_host(4)
         .build()
@@ -30,6 +33,17 @@ static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
 static CANCELLATION_REGISTRY: Lazy<Arc<Mutex<HashMap<String, CancellationToken>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+/// Provider settings needed to call an LLM (see `SummaryService::resolve_connection`)
+pub(crate) struct LlmConnection {
+    pub provider: LLMProvider,
+    pub api_key: String,
+    pub ollama_endpoint: Option<String>,
+    pub custom_openai_endpoint: Option<String>,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+}
+
 /// Summary service - handles all summary generation logic
 pub struct SummaryService;
 
@@ -66,71 +80,66 @@ impl SummaryService {
         }
     }
 
-    /// Processes transcript in the background and generates summary
-    ///
-    /// This function is designed to be spawned as an async task and does not block
-    /// the main thread. It updates the database with progress and results.
-    ///
-    /// # Arguments
-    /// * `_app` - Tauri app handle (for future use)
-    /// * `pool` - SQLx connection pool
-    /// * `meeting_id` - Unique identifier for the meeting
-    /// * `text` - Full transcript text
-    /// * `model_provider` - LLM provider name (e.g., "ollama", "openai")
-    /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
-    /// * `custom_prompt` - Optional user-provided context
-    /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
-    pub async fn process_transcript_background<R: tauri::Runtime>(
-        _app: AppHandle<R>,
-        pool: SqlitePool,
-        meeting_id: String,
-        text: String,
-        model_provider: String,
-        model_name: String,
-        custom_prompt: String,
-        template_id: String,
-    ) {
-        let start_time = Instant::now();
-        info!(
-            "Starting background processing for meeting_id: {}",
-            meeting_id
-        );
-
-        // Register cancellation token for this meeting
-        let cancellation_token = Self::register_cancellation_token(&meeting_id);
-
-        // Parse provider
-        let provider = match LLMProvider::from_str(&model_provider) {
-            Ok(p) => p,
+    /// Meeting data available to templates as `{{name}}` placeholders. `markers` is empty when
+    /// nothing was marked during recording.
+    async fn template_variables(pool: &SqlitePool, meeting_id: &str) -> HashMap<String, String> {
+        let chapters = match ChaptersRepository::get_chapters(pool, meeting_id).await {
+            Ok(chapters) if !chapters.is_empty() => crate::chapters::export::to_template_variable(&chapters),
+            Ok(_) => "No chapters have been generated for this meeting.".to_string(),
             Err(e) => {
-                Self::update_process_failed(&pool, &meeting_id, &e).await;
-                return;
+                warn!("Failed to load chapters for meeting {}: {}", meeting_id, e);
+                "No chapters available.".to_string()
+            }
+        };
+        let markers = match MarkersRepository::get_markers(pool, meeting_id).await {
+            Ok(markers) if !markers.is_empty() => {
+                match TranscriptVersionsRepository::get_current_segments(pool, meeting_id).await {
+                    Ok(segments) => crate::audio::markers::to_summary_hints(&markers, &segments),
+                    Err(e) => {
+                        warn!("Failed to load transcript for marker hints of meeting {}: {}", meeting_id, e);
+                        crate::audio::markers::to_summary_hints(&markers, &[])
+                    }
+                }
+            }
+            Ok(_) => String::new(),
+            Err(e) => {
+                warn!("Failed to load markers for meeting {}: {}", meeting_id, e);
+                String::new()
             }
         };
+        HashMap::from([
+            ("chapters".to_string(), chapters),
+            ("markers".to_string(), markers),
+        ])
+    }
+
+    /// Resolves the provider, API key and endpoint settings for an LLM request.
+    /// Shared by summary generation and other LLM-backed features (e.g. translation).
+    pub(crate) async fn resolve_connection(
+        pool: &SqlitePool,
+        model_provider: &str,
+    ) -> Result<LlmConnection, String> {
+        let provider = LLMProvider::from_str(model_provider)?;
 
         // Validate and setup api_key, Flexible for Ollama, BuiltInAI, and CustomOpenAI
         let api_key = if provider == LLMProvider::Ollama || provider == LLMProvider::BuiltInAI || provider == LLMProvider::CustomOpenAI {
             // These providers don't require API keys from the standard database column
             String::new()
         } else {
-            match SettingsRepository::get_api_key(&pool, &model_provider).await {
+            match SettingsRepository::get_api_key(pool, model_provider).await {
                 Ok(Some(key)) if !key.is_empty() => key,
                 Ok(None) | Ok(Some(_)) => {
-                    let err_msg = format!("API key not found for {}", &model_provider);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
+                    return Err(format!("API key not found for {}", model_provider));
                 }
                 Err(e) => {
-                    let err_msg = format!("Failed to retrieve API key for {}: {}", &model_provider, e);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
+                    return Err(format!("Failed to retrieve API key for {}: {}", model_provider, e));
                 }
             }
         };
 
         // Get Ollama endpoint if provider is Ollama
         let ollama_endpoint = if provider == LLMProvider::Ollama {
-            match SettingsRepository::get_model_config(&pool).await {
+            match SettingsRepository::get_model_config(pool).await {
                 Ok(Some(config)) => config.ollama_endpoint,
                 Ok(None) => None,
                 Err(e) => {
@@ -145,7 +154,7 @@ impl SummaryService {
         // Get CustomOpenAI config if provider is CustomOpenAI
         let (custom_openai_endpoint, custom_openai_api_key, custom_openai_max_tokens, custom_openai_temperature, custom_openai_top_p) =
             if provider == LLMProvider::CustomOpenAI {
-                match SettingsRepository::get_custom_openai_config(&pool).await {
+                match SettingsRepository::get_custom_openai_config(pool).await {
                     Ok(Some(config)) => {
                         info!("✓ Using custom OpenAI endpoint: {}", config.endpoint);
                         (
@@ -157,14 +166,10 @@ impl SummaryService {
                         )
                     }
                     Ok(None) => {
-                        let err_msg = "Custom OpenAI provider selected but no configuration found";
-                        Self::update_process_failed(&pool, &meeting_id, err_msg).await;
-                        return;
+                        return Err("Custom OpenAI provider selected but no configuration found".to_string());
                     }
                     Err(e) => {
-                        let err_msg = format!("Failed to retrieve custom OpenAI config: {}", e);
-                        Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                        return;
+                        return Err(format!("Failed to retrieve custom OpenAI config: {}", e));
                     }
                 }
             } else {
@@ -178,6 +183,96 @@ impl SummaryService {
             api_key
         };
 
+        Ok(LlmConnection {
+            provider,
+            api_key: final_api_key,
+            ollama_endpoint,
+            custom_openai_endpoint,
+            max_tokens: custom_openai_max_tokens,
+            temperature: custom_openai_temperature,
+            top_p: custom_openai_top_p,
+        })
+    }
+
+    /// Processes transcript in the background and generates summary
+    ///
+    /// This function is designed to be spawned as an async task and does not block
+    /// the main thread. It updates the database with progress and results.
+    ///
+    /// # Arguments
+    /// * `app` - Tauri app handle (template selection, redaction, app data directory)
+    /// * `pool` - SQLx connection pool
+    /// * `meeting_id` - Unique identifier for the meeting
+    /// * `text` - Full transcript text
+    /// * `model_provider` - LLM provider name (e.g., "ollama", "openai")
+    /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
+    /// * `custom_prompt` - Optional user-provided context
+    /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting"), or "auto"
+    ///   to classify the meeting first
+    /// * `calendar` - Calendar event of the meeting, used when classifying
+    pub async fn process_transcript_background<R: tauri::Runtime>(
+        app: AppHandle<R>,
+        pool: SqlitePool,
+        meeting_id: String,
+        text: String,
+        model_provider: String,
+        model_name: String,
+        custom_prompt: String,
+        template_id: String,
+        calendar: Option<CalendarContext>,
+    ) {
+        let start_time = Instant::now();
+        info!(
+            "Starting background processing for meeting_id: {}",
+            meeting_id
+        );
+
+        // Register cancellation token for this meeting
+        let cancellation_token = Self::register_cancellation_token(&meeting_id);
+
+        let connection = match Self::resolve_connection(&pool, &model_provider).await {
+            Ok(connection) => connection,
+            Err(e) => {
+                Self::update_process_failed(&pool, &meeting_id, &e).await;
+                return;
+            }
+        };
+
+        // Meetings summarized without a template are classified first (see template_selection)
+        let selection = template_selection::select_template(
+            &app,
+            &pool,
+            &meeting_id,
+            &template_id,
+            calendar,
+            &text,
+            ClassifierModel {
+                connection: &connection,
+                provider: &model_provider,
+                model: &model_name,
+                cancel: &cancellation_token,
+            },
+        )
+        .await;
+        template_selection::record_selection(&pool, &meeting_id, &selection).await;
+        let template_id = selection.template_id;
+
+        let LlmConnection {
+            provider,
+            api_key: final_api_key,
+            ollama_endpoint,
+            custom_openai_endpoint,
+            max_tokens: custom_openai_max_tokens,
+            temperature: custom_openai_temperature,
+            top_p: custom_openai_top_p,
+        } = connection;
+
+        // A running summary from the recording replaces the part of the transcript it covers
+        let text = match crate::summary::live::seeded_summary_input(&pool, &meeting_id).await {
+            Some(seeded) => seeded,
+            None => text,
+        };
+
         // Dynamically fetch context size based on provider and model
         let token_threshold = if provider == LLMProvider::Ollama {
             match METADATA_CACHE.get_or_fetch(&model_name, ollama_endpoint.as_deref()).await {
@@ -249,7 +344,30 @@ impl SummaryService {
         }
 
         // Get app data directory for BuiltInAI provider
-        let app_data_dir = _app.path().app_data_dir().ok();
+        let app_data_dir = app.path().app_data_dir().ok();
+
+        let mut template_variables = Self::template_variables(&pool, &meeting_id).await;
+
+        // Personal details are replaced by placeholders before the request leaves the machine
+        let mut redactor = match redaction::redactor_for(&app, &pool, &provider, &[text.as_str(), custom_prompt.as_str()]).await {
+            Ok(redactor) => redactor,
+            Err(e) => {
+                Self::cleanup_cancellation_token(&meeting_id);
+                Self::update_process_failed(&pool, &meeting_id, &e).await;
+                return;
+            }
+        };
+        let (text, custom_prompt) = match redactor.as_mut() {
+            Some(redactor) => {
+                let redacted = (redactor.redact(&text), redactor.redact(&custom_prompt));
+                for value in template_variables.values_mut() {
+                    *value = redactor.redact(value);
+                }
+                template_variables.insert("redaction".to_string(), redaction::PLACEHOLDER_NOTE.to_string());
+                redacted
+            }
+            None => (text, custom_prompt),
+        };
 
         // Reuse global HTTP client (avoids 50-200ms connection setup per summary)
         let client = HTTP_CLIENT.clone();
@@ -261,6 +379,7 @@ impl SummaryService {
             &text,
             &custom_prompt,
             &template_id,
+            &template_variables,
             token_threshold,
             ollama_endpoint.as_deref(),
             custom_openai_endpoint.as_deref(),
@@ -277,6 +396,14 @@ impl SummaryService {
         // Clean up cancellation token regardless of outcome
         Self::cleanup_cancellation_token(&meeting_id);
 
+        if let Some(redactor) = &redactor {
+            redaction::record_audit(&pool, Some(&meeting_id), "summary", &model_provider, &model_name, redactor).await;
+        }
+        let result = match &redactor {
+            Some(redactor) => result.map(|(markdown, num_chunks)| (redactor.restore(&markdown), num_chunks)),
+            None => result,
+        };
+
         match result {
             Ok((mut final_markdown, num_chunks)) => {
                 if num_chunks == 0 && final_markdown.is_empty() {
@@ -1,8 +1,11 @@
-use crate::summary::templates;
+use crate::summary::processor;
+use crate::summary::templates::user::{self, TemplateSaveError, TemplateVersionInfo, UserTemplateInfo};
+use crate::summary::templates::TemplateIssue;
+use crate::summary::{template_sources, templates};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tauri::Runtime;
-use tracing::{error, info, warn};
+use tracing::{info, warn};
 
 /// Template metadata for UI display
 #[derive(Debug, Serialize, Deserialize)]
@@ -124,207 +127,187 @@ pub async fn api_validate_template<R: Runtime>(
     }
 }
 
-/// Result of a template sync operation
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SyncResult {
-    pub synced_count: u32,
-    pub failed_count: u32,
-    pub is_online: bool,
+/// Transcript used by the template preview when none is given
+const SAMPLE_TRANSCRIPT: &str = "[00:00] Alex: Morning everyone, let's go round quickly.\n\
+[00:05] Sam: Yesterday I finished the export dialog. Today I'm on the import errors. No blockers.\n\
+[00:18] Priya: I'm still waiting on the API keys from the vendor, that's blocking the billing work.\n\
+[00:30] Alex: I'll chase the vendor today. Let's also decide: we ship the beta on Friday.\n\
+[00:41] Sam: Agreed, Friday works.";
+
+/// Checks template JSON from the editor, listing every problem with the section it is in
+///
+/// # Returns
+/// The problems found (empty when the template is valid)
+#[tauri::command]
+pub async fn api_check_template(template_json: String) -> Result<Vec<TemplateIssue>, String> {
+    Ok(templates::check_template_json(&template_json).err().unwrap_or_default())
 }
 
-/// Sync response shape from backend API
-#[derive(Debug, Deserialize)]
-struct BackendSyncResponse {
-    templates: Vec<BackendTemplate>,
+#[tauri::command]
+pub async fn api_list_user_templates() -> Result<Vec<UserTemplateInfo>, String> {
+    user::list_user_templates()
 }
 
-#[derive(Debug, Deserialize)]
-struct BackendTemplate {
+#[tauri::command]
+pub async fn api_get_user_template(template_id: String) -> Result<templates::Template, String> {
+    user::get_user_template(&template_id)
+}
+
+/// Creates a user template; fails if a user template with this id exists
+#[tauri::command]
+pub async fn api_create_user_template(
     template_id: String,
-    name: String,
-    description: String,
-    sections: Vec<serde_json::Value>,
-    #[serde(default)]
-    global_instruction: Option<String>,
-    #[serde(default)]
-    clinical_safety_rules: Option<Vec<String>>,
-    #[serde(default)]
-    version: Option<u32>,
-    #[serde(default)]
-    updated_at: Option<String>,
+    template_json: String,
+) -> Result<templates::Template, TemplateSaveError> {
+    let template = templates::check_template_json(&template_json)?;
+    user::create_user_template(&template_id, template)
 }
 
-/// Sync templates from MongoDB directly.
-/// Uses bson::Document to avoid schema mismatches with BSON types (e.g. DateTime).
-async fn sync_templates_from_mongodb() -> Result<SyncResult, String> {
-    use mongodb::bson::{doc, Document};
-    use futures_util::TryStreamExt;
-
-    let collection =
-        crate::mongodb_client::get_collection::<Document>("templates").await?;
-
-    let filter = doc! { "client_id": "default", "is_active": true };
-    let mut cursor = collection
-        .find(filter)
-        .await
-        .map_err(|e| format!("MongoDB template query failed: {e}"))?;
-
-    let mut synced_count = 0u32;
-    let mut failed_count = 0u32;
-
-    while let Some(doc) = cursor.try_next().await.map_err(|e| format!("MongoDB cursor error: {e}"))? {
-        let template_id = match doc.get_str("template_id") {
-            Ok(id) => id.to_string(),
-            Err(_) => {
-                warn!("Skipping MongoDB template without template_id");
-                failed_count += 1;
-                continue;
-            }
-        };
-
-        // Convert BSON sections array to serde_json::Value for local schema
-        let sections: Vec<serde_json::Value> = doc
-            .get_array("sections")
-            .unwrap_or(&vec![])
-            .iter()
-            .filter_map(|v| {
-                let json_str = serde_json::to_string(&v).ok()?;
-                serde_json::from_str(&json_str).ok()
-            })
-            .collect();
+/// Saves a new version of a user template
+///
+/// # Arguments
+/// * `expected_version` - Version the editor loaded; saving fails if another version was
+///   saved since
+#[tauri::command]
+pub async fn api_update_user_template(
+    template_id: String,
+    template_json: String,
+    expected_version: Option<u32>,
+) -> Result<templates::Template, TemplateSaveError> {
+    let template = templates::check_template_json(&template_json)?;
+    user::update_user_template(&template_id, template, expected_version)
+}
 
-        let local_json = serde_json::json!({
-            "name": doc.get_str("name").unwrap_or_default(),
-            "description": doc.get_str("description").unwrap_or_default(),
-            "sections": sections,
-            "global_instruction": doc.get_str("global_instruction").ok(),
-            "clinical_safety_rules": doc.get_array("clinical_safety_rules").ok().map(|arr| {
-                arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect::<Vec<_>>()
-            }),
-            "version": doc.get_i32("version").ok(),
-        });
-
-        let json_string = serde_json::to_string_pretty(&local_json).unwrap_or_default();
-
-        match templates::validate_and_parse_template(&json_string) {
-            Ok(_) => match templates::save_synced_template(&template_id, &json_string) {
-                Ok(_) => synced_count += 1,
-                Err(e) => {
-                    error!("Failed to save synced template '{}': {}", template_id, e);
-                    failed_count += 1;
-                }
-            },
-            Err(e) => {
-                warn!("Skipping invalid template '{}' from MongoDB: {}", template_id, e);
-                failed_count += 1;
-            }
-        }
-    }
+/// Copies any template (built-in, synced or user) to a new user template
+#[tauri::command]
+pub async fn api_duplicate_template(
+    source_id: String,
+    new_id: String,
+    new_name: Option<String>,
+) -> Result<templates::Template, TemplateSaveError> {
+    user::duplicate_template(&source_id, &new_id, new_name.as_deref())
+}
 
-    info!("MongoDB template sync: {} synced, {} failed", synced_count, failed_count);
-    Ok(SyncResult {
-        synced_count,
-        failed_count,
-        is_online: true,
-    })
+#[tauri::command]
+pub async fn api_delete_user_template(template_id: String) -> Result<(), String> {
+    user::delete_user_template(&template_id)
 }
 
-/// Sync templates from backend HTTP API (fallback).
-async fn sync_templates_from_api() -> SyncResult {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build();
+/// Saved versions of a user template, newest first
+#[tauri::command]
+pub async fn api_list_template_versions(template_id: String) -> Result<Vec<TemplateVersionInfo>, String> {
+    user::list_versions(&template_id)
+}
 
-    let client = match client {
-        Ok(c) => c,
-        Err(e) => {
-            warn!("Failed to create HTTP client for template sync: {}", e);
-            return SyncResult { synced_count: 0, failed_count: 0, is_online: false };
-        }
-    };
+/// Saves an earlier version of a user template as its newest version
+#[tauri::command]
+pub async fn api_restore_template_version(
+    template_id: String,
+    version: u32,
+) -> Result<templates::Template, TemplateSaveError> {
+    user::restore_version(&template_id, version)
+}
 
-    let response = client
-        .get(format!("{}/api/templates?client_id=default", crate::api::api::APP_SERVER_URL))
-        .send()
-        .await;
+/// Writes a template to a single shareable file
+#[tauri::command]
+pub async fn api_export_template(template_id: String, destination: String) -> Result<(), String> {
+    let content = user::export_template(&template_id)?;
+    std::fs::write(&destination, content).map_err(|e| format!("Failed to write {}: {}", destination, e))?;
+    info!("Exported template '{}' to {}", template_id, destination);
+    Ok(())
+}
 
-    let response = match response {
-        Ok(r) => r,
-        Err(e) => {
-            info!("Template sync: backend not reachable ({}), using cached/bundled templates", e);
-            return SyncResult { synced_count: 0, failed_count: 0, is_online: false };
-        }
-    };
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportedTemplate {
+    pub template_id: String,
+    pub template: templates::Template,
+}
 
-    if !response.status().is_success() {
-        warn!("Template sync: backend returned status {}", response.status());
-        return SyncResult { synced_count: 0, failed_count: 0, is_online: false };
-    }
+/// Imports an exported template file (or a bare template JSON file) as a user template
+///
+/// # Arguments
+/// * `template_id` - Id to use instead of the one in the file
+/// * `overwrite` - Replace a user template with the same id (kept in its history) instead of
+///   importing under a new id
+#[tauri::command]
+pub async fn api_import_template(
+    source: String,
+    template_id: Option<String>,
+    overwrite: bool,
+) -> Result<ImportedTemplate, TemplateSaveError> {
+    let (template_id, template) =
+        user::import_template(std::path::Path::new(&source), template_id.as_deref(), overwrite)?;
+    info!("Imported template '{}' from {}", template_id, source);
+    Ok(ImportedTemplate { template_id, template })
+}
 
-    let body = match response.json::<BackendSyncResponse>().await {
-        Ok(b) => b,
-        Err(e) => {
-            warn!("Template sync: failed to parse response: {}", e);
-            return SyncResult { synced_count: 0, failed_count: 0, is_online: true };
-        }
-    };
+/// What the LLM would receive for a template
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplatePreview {
+    pub markdown_structure: String,
+    pub section_instructions: String,
+    pub system_prompt: String,
+    pub user_prompt: String,
+    /// Rough size of both prompts
+    pub estimated_tokens: usize,
+}
 
-    let mut synced_count = 0u32;
-    let mut failed_count = 0u32;
-
-    for tmpl in &body.templates {
-        let local_json = serde_json::json!({
-            "name": tmpl.name,
-            "description": tmpl.description,
-            "sections": tmpl.sections,
-            "global_instruction": tmpl.global_instruction,
-            "clinical_safety_rules": tmpl.clinical_safety_rules,
-            "version": tmpl.version,
-            "updated_at": tmpl.updated_at,
-        });
-
-        let json_string = serde_json::to_string_pretty(&local_json).unwrap_or_default();
-
-        match templates::validate_and_parse_template(&json_string) {
-            Ok(_) => {
-                match templates::save_synced_template(&tmpl.template_id, &json_string) {
-                    Ok(_) => synced_count += 1,
-                    Err(e) => {
-                        error!("Failed to save synced template '{}': {}", tmpl.template_id, e);
-                        failed_count += 1;
-                    }
-                }
-            }
-            Err(e) => {
-                warn!("Skipping invalid template '{}' from backend: {}", tmpl.template_id, e);
-                failed_count += 1;
-            }
-        }
-    }
+/// Renders a template (saved or still in the editor) into the prompts a summary would use,
+/// for a sample transcript; nothing is sent to a provider
+#[tauri::command]
+pub async fn api_preview_template(
+    template_json: String,
+    sample_transcript: Option<String>,
+) -> Result<TemplatePreview, TemplateSaveError> {
+    let template = templates::check_template_json(&template_json)?;
+    let transcript = sample_transcript
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| SAMPLE_TRANSCRIPT.to_string());
+    let (system_prompt, user_prompt) =
+        processor::build_report_prompts(&template, &transcript, "", &HashMap::new());
+    Ok(TemplatePreview {
+        markdown_structure: template.to_markdown_structure(),
+        section_instructions: template.to_section_instructions(),
+        estimated_tokens: processor::rough_token_count(&system_prompt) + processor::rough_token_count(&user_prompt),
+        system_prompt,
+        user_prompt,
+    })
+}
 
-    info!("API template sync: {} synced, {} failed", synced_count, failed_count);
-    SyncResult { synced_count, failed_count, is_online: true }
+/// Result of a template sync operation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncResult {
+    pub synced_count: u32,
+    pub failed_count: u32,
+    pub is_online: bool,
+    /// Templates already up to date (or provided by an earlier source)
+    #[serde(default)]
+    pub skipped_count: u32,
+    /// Open conflicts between upstream templates and local edits
+    #[serde(default)]
+    pub conflict_count: u32,
+    /// Sources that could not be reached
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
 
 /// Internal sync function (called from Tauri command and startup).
-/// Tries MongoDB first, falls back to HTTP API.
-pub async fn sync_templates_internal() -> SyncResult {
-    let (result, source) = if crate::mongodb_client::is_configured() {
-        match sync_templates_from_mongodb().await {
-            Ok(r) => (r, "mongodb"),
-            Err(e) => {
-                warn!("MongoDB template sync failed, falling back to API: {}", e);
-                (sync_templates_from_api().await, "api")
-            }
-        }
-    } else {
-        (sync_templates_from_api().await, "api")
-    };
+/// Syncs every configured template source (by default the organization's MongoDB, falling
+/// back to the backend API).
+pub async fn sync_templates_internal<R: Runtime>(app: &tauri::AppHandle<R>) -> SyncResult {
+    let settings = template_sources::load_settings(app);
+    let result = template_sources::sync_templates(&settings).await;
 
     // Fire PostHog analytics event
     if let Some(client) = crate::analytics::commands::get_analytics_client() {
+        let sources: Vec<&str> = settings
+            .sources
+            .iter()
+            .filter(|s| s.enabled)
+            .map(|s| s.id.as_str())
+            .collect();
         let mut props = HashMap::new();
-        props.insert("source".to_string(), source.to_string());
+        props.insert("source".to_string(), sources.join(","));
         props.insert("synced_count".to_string(), result.synced_count.to_string());
         props.insert("failed_count".to_string(), result.failed_count.to_string());
         props.insert("is_online".to_string(), result.is_online.to_string());
@@ -334,13 +317,13 @@ pub async fn sync_templates_internal() -> SyncResult {
     result
 }
 
-/// Syncs templates from the backend (MongoDB) to the local synced cache
+/// Syncs templates from the configured sources to the local synced cache
 #[tauri::command]
 pub async fn api_sync_templates<R: Runtime>(
-    _app: tauri::AppHandle<R>,
+    app: tauri::AppHandle<R>,
 ) -> Result<SyncResult, String> {
     info!("api_sync_templates called");
-    Ok(sync_templates_internal().await)
+    Ok(sync_templates_internal(&app).await)
 }
 
 #[cfg(test)]
@@ -0,0 +1,69 @@
+use super::{CalendarContext, TemplateSelection, TemplateSelectionSettings};
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::state::AppState;
+use crate::summary::templates;
+use tauri::{AppHandle, Runtime};
+use tracing::info;
+
+#[tauri::command]
+pub async fn api_get_template_selection_settings<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<TemplateSelectionSettings, String> {
+    Ok(super::load_settings(&app))
+}
+
+#[tauri::command]
+pub async fn api_save_template_selection_settings<R: Runtime>(
+    app: AppHandle<R>,
+    mut settings: TemplateSelectionSettings,
+) -> Result<TemplateSelectionSettings, String> {
+    settings.fallback_template_id = settings.fallback_template_id.trim().to_string();
+    for rule in &mut settings.rules {
+        rule.title_contains = rule.title_contains.trim().to_string();
+        rule.template_id = rule.template_id.trim().to_string();
+    }
+    settings.validate()?;
+    let template_ids = std::iter::once(&settings.fallback_template_id).chain(settings.rules.iter().map(|r| &r.template_id));
+    for template_id in template_ids {
+        templates::get_template(template_id).map_err(|_| format!("Template '{}' not found", template_id))?;
+    }
+
+    super::save_settings(&app, &settings)?;
+    info!(
+        "Saved template selection settings (enabled: {}, model: {}, {} rules)",
+        settings.enabled,
+        settings.use_llm,
+        settings.rules.len()
+    );
+    Ok(settings)
+}
+
+/// Template the rules and heuristics would choose for a meeting (without asking the model)
+#[tauri::command]
+pub async fn api_suggest_template<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    calendar_event: Option<CalendarContext>,
+) -> Result<TemplateSelection, String> {
+    let settings = super::load_settings(&app);
+    let signals = super::gather_signals(state.db_manager.pool(), &meeting_id, calendar_event).await;
+    Ok(super::classify_meeting(&settings, signals, &templates::list_templates()))
+}
+
+/// How the template of the meeting's last summary was chosen, if recorded
+#[tauri::command]
+pub async fn api_get_template_selection(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<TemplateSelection>, String> {
+    let process = SummaryProcessesRepository::get_summary_data(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary process: {}", e))?;
+    let selection = process
+        .and_then(|p| p.metadata)
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .and_then(|mut metadata| metadata.get_mut(super::METADATA_KEY).map(serde_json::Value::take))
+        .and_then(|value| serde_json::from_value(value).ok());
+    Ok(selection)
+}
@@ -0,0 +1,766 @@
+//! Automatic template selection by meeting classification.
+//!
+//! A summary requested without a template (or with the template id `auto`) is classified
+//! before summarization, in this order:
+//!
+//! 1. the user's rules, e.g. "titles containing 'retro' use retrospective" (first match wins)
+//! 2. cheap heuristics: keywords in the meeting and calendar event titles, the number of
+//!    participants, the duration and whether the calendar event recurs
+//! 3. optionally, a short classification prompt to the summary model when the heuristics are
+//!    not conclusive
+//! 4. the fallback template from the settings
+//!
+//! Only templates returned by `templates::list_templates()` are chosen. The choice and its
+//! rationale are stored under `template_selection` in `summary_processes.metadata`.
+
+pub mod commands;
+
+use crate::database::repositories::{
+    meeting::MeetingsRepository, summary::SummaryProcessesRepository,
+    transcript_version::TranscriptVersionsRepository,
+};
+use crate::summary::llm_client::generate_summary;
+use crate::summary::redaction;
+use crate::summary::service::{LlmConnection, HTTP_CLIENT};
+use crate::summary::templates;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+const STORE_FILE: &str = "template_selection.json";
+const STORE_KEY: &str = "settings";
+
+/// Template id that asks for automatic selection
+pub const AUTO_TEMPLATE_ID: &str = "auto";
+
+/// Key of the selection in `summary_processes.metadata`
+pub const METADATA_KEY: &str = "template_selection";
+
+/// Heuristic score a template needs (a title keyword) to be chosen without asking the model
+const MIN_HEURISTIC_SCORE: u32 = 3;
+
+/// Transcript sent with the classification prompt
+const MAX_EXCERPT_WORDS: usize = 300;
+
+/// Completion budget, for providers that accept one
+const MAX_COMPLETION_TOKENS: u32 = 150;
+
+/// Meetings up to this long count as short (stand-ups)
+const SHORT_MEETING_MINUTES: f64 = 20.0;
+
+/// Title keywords of the built-in templates with their weight
+const BUILTIN_KEYWORDS: &[(&str, u32, &[&str])] = &[
+    ("daily_standup", 4, &["standup", "stand up", "daily", "scrum", "huddle"]),
+    (
+        "project_sync",
+        3,
+        &["sync", "status", "project", "sprint", "milestone", "roadmap", "steering", "progress"],
+    ),
+    (
+        "care_consultation",
+        4,
+        &["consultation", "patient", "care", "appointment", "intake", "assessment"],
+    ),
+    ("internal_meeting", 3, &["internal", "team meeting", "all hands", "one on one", "1 1"]),
+    ("standard_meeting", 2, &["meeting", "call", "review", "kickoff", "kick off", "workshop"]),
+];
+
+/// Template name words too generic to classify a meeting by
+const GENERIC_WORDS: &[&str] = &[
+    "meeting", "meetings", "summary", "notes", "template", "standard", "update", "general", "call",
+];
+
+const CLASSIFY_SYSTEM_PROMPT: &str = "You choose the summary template that best fits a meeting. \
+Reply with JSON only, in the form {\"template_id\": \"<one of the listed ids>\", \"reason\": \"<one short sentence>\"}.";
+
+/// Rule mapping meetings to a template, checked before the heuristics
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemplateRule {
+    /// Text the meeting or calendar event title contains (case-insensitive)
+    pub title_contains: String,
+    pub template_id: String,
+    /// Only applies when at least this many people take part
+    #[serde(default)]
+    pub min_participants: Option<usize>,
+    /// Only applies when at most this many people take part
+    #[serde(default)]
+    pub max_participants: Option<usize>,
+}
+
+impl TemplateRule {
+    fn matches(&self, signals: &MeetingSignals) -> bool {
+        let needle = self.title_contains.trim().to_lowercase();
+        let title_matches = signals.titles().any(|title| title.to_lowercase().contains(&needle));
+        if needle.is_empty() || !title_matches {
+            return false;
+        }
+        // Participant limits only apply when the number of participants is known
+        match signals.participants() {
+            Some(count) => {
+                self.min_participants.map_or(true, |min| count >= min)
+                    && self.max_participants.map_or(true, |max| count <= max)
+            }
+            None => self.min_participants.is_none() && self.max_participants.is_none(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateSelectionSettings {
+    /// Classify meetings summarized without a template; otherwise the fallback is used
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Ask the summary model when the rules and heuristics are not conclusive
+    #[serde(default)]
+    pub use_llm: bool,
+    /// Template used when the meeting could not be classified
+    #[serde(default = "default_fallback")]
+    pub fallback_template_id: String,
+    #[serde(default)]
+    pub rules: Vec<TemplateRule>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_fallback() -> String {
+    "standard_meeting".to_string()
+}
+
+impl Default for TemplateSelectionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            use_llm: false,
+            fallback_template_id: default_fallback(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl TemplateSelectionSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !templates::is_valid_template_id(&self.fallback_template_id) {
+            return Err(format!("Invalid fallback template '{}'", self.fallback_template_id));
+        }
+        for (index, rule) in self.rules.iter().enumerate() {
+            if rule.title_contains.trim().is_empty() {
+                return Err(format!("Rule {} needs the text the title contains", index + 1));
+            }
+            if !templates::is_valid_template_id(&rule.template_id) {
+                return Err(format!("Rule {} uses the invalid template '{}'", index + 1, rule.template_id));
+            }
+            if let (Some(min), Some(max)) = (rule.min_participants, rule.max_participants) {
+                if min > max {
+                    return Err(format!("Rule {} has more minimum than maximum participants", index + 1));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Calendar event of the meeting, as known to the frontend
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalendarContext {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub attendee_count: Option<usize>,
+    #[serde(default)]
+    pub recurring: Option<bool>,
+}
+
+/// What is known about a meeting when it is classified
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MeetingSignals {
+    pub title: Option<String>,
+    /// Distinct named speakers in the transcript
+    pub speaker_count: Option<usize>,
+    pub duration_minutes: Option<f64>,
+    pub calendar: Option<CalendarContext>,
+}
+
+impl MeetingSignals {
+    fn titles(&self) -> impl Iterator<Item = &str> {
+        let calendar_title = self.calendar.as_ref().and_then(|c| c.title.as_deref());
+        self.title.as_deref().into_iter().chain(calendar_title)
+    }
+
+    /// Attendees of the calendar event, or else the speakers of the transcript
+    fn participants(&self) -> Option<usize> {
+        self.calendar
+            .as_ref()
+            .and_then(|c| c.attendee_count)
+            .or(self.speaker_count)
+    }
+
+    fn recurring(&self) -> bool {
+        self.calendar.as_ref().and_then(|c| c.recurring).unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionMethod {
+    /// The template was chosen when requesting the summary
+    Requested,
+    Rule,
+    Heuristic,
+    Llm,
+    Fallback,
+}
+
+/// The template used for a summary and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateSelection {
+    pub template_id: String,
+    pub method: SelectionMethod,
+    pub rationale: String,
+    #[serde(default)]
+    pub signals: Option<MeetingSignals>,
+    pub selected_at: DateTime<Utc>,
+}
+
+impl TemplateSelection {
+    fn new(template_id: &str, method: SelectionMethod, rationale: String, signals: Option<MeetingSignals>) -> Self {
+        Self {
+            template_id: template_id.to_string(),
+            method,
+            rationale,
+            signals,
+            selected_at: Utc::now(),
+        }
+    }
+}
+
+/// Summary model used for the classification prompt
+pub(crate) struct ClassifierModel<'a> {
+    pub connection: &'a LlmConnection,
+    pub provider: &'a str,
+    pub model: &'a str,
+    pub cancel: &'a CancellationToken,
+}
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> TemplateSelectionSettings {
+    let store = match app.store(STORE_FILE) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to access template selection store: {}, using defaults", e);
+            return TemplateSelectionSettings::default();
+        }
+    };
+    match store.get(STORE_KEY) {
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            warn!("Failed to deserialize template selection settings: {}, using defaults", e);
+            TemplateSelectionSettings::default()
+        }),
+        None => TemplateSelectionSettings::default(),
+    }
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &TemplateSelectionSettings) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to access template selection store: {}", e))?;
+    let value = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize template selection settings: {}", e))?;
+    store.set(STORE_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save template selection settings: {}", e))
+}
+
+/// Whether `template_id` asks for automatic selection
+pub fn is_auto(template_id: &str) -> bool {
+    let template_id = template_id.trim();
+    template_id.is_empty() || template_id.eq_ignore_ascii_case(AUTO_TEMPLATE_ID)
+}
+
+/// Title, speakers and duration of a stored meeting; what cannot be loaded is left out
+pub async fn gather_signals(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    calendar: Option<CalendarContext>,
+) -> MeetingSignals {
+    let title = match MeetingsRepository::get_meeting_metadata(pool, meeting_id).await {
+        Ok(meeting) => meeting.map(|m| m.title).filter(|t| !t.trim().is_empty()),
+        Err(e) => {
+            warn!("Failed to load meeting {} for template selection: {}", meeting_id, e);
+            None
+        }
+    };
+    let (speaker_count, duration_minutes) = match TranscriptVersionsRepository::get_current_segments(pool, meeting_id).await {
+        Ok(segments) => {
+            // "mic" and "system" name the audio source, not a person
+            let speakers: HashSet<&str> = segments
+                .iter()
+                .filter_map(|s| s.speaker.as_deref().map(str::trim))
+                .filter(|s| !s.is_empty() && *s != "mic" && *s != "system")
+                .collect();
+            let end = segments
+                .iter()
+                .filter_map(|s| s.audio_end_time)
+                .fold(0.0_f64, f64::max);
+            (
+                Some(speakers.len()).filter(|count| *count > 0),
+                Some(end / 60.0).filter(|minutes| *minutes > 0.0),
+            )
+        }
+        Err(e) => {
+            warn!("Failed to load transcript of {} for template selection: {}", meeting_id, e);
+            (None, None)
+        }
+    };
+    MeetingSignals {
+        title,
+        speaker_count,
+        duration_minutes,
+        calendar,
+    }
+}
+
+/// Lowercase words separated by single spaces, padded with a space on both sides
+fn normalize_words(text: &str) -> String {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+/// Name words of a template that can identify a meeting (e.g. "retrospective")
+fn distinctive_words(template_id: &str, name: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for word in normalize_words(&format!("{} {}", template_id, name)).split_whitespace() {
+        if word.chars().count() >= 4 && !GENERIC_WORDS.contains(&word) && !words.iter().any(|w| w == word) {
+            words.push(word.to_string());
+        }
+    }
+    words
+}
+
+/// Heuristic score of one template with the reasons that add to it
+fn score_template(template_id: &str, name: &str, signals: &MeetingSignals) -> (u32, Vec<String>) {
+    let titles = normalize_words(&signals.titles().collect::<Vec<_>>().join(" "));
+    let description = signals
+        .calendar
+        .as_ref()
+        .and_then(|c| c.description.as_deref())
+        .map(normalize_words)
+        .unwrap_or_default();
+    let keywords = BUILTIN_KEYWORDS.iter().find(|(id, _, _)| *id == template_id);
+
+    let mut score = 0;
+    let mut reasons = Vec::new();
+
+    let keyword_match = keywords.and_then(|(_, weight, words)| {
+        words
+            .iter()
+            .find(|word| titles.contains(&format!(" {} ", word)))
+            .map(|word| (*weight, word.to_string()))
+    });
+    // "Retro" in a title matches a template named "Retrospective"
+    let name_match = || {
+        let title_words: Vec<&str> = titles.split_whitespace().filter(|w| w.chars().count() >= 4).collect();
+        distinctive_words(template_id, name).into_iter().find(|word| {
+            title_words
+                .iter()
+                .any(|title_word| word.starts_with(title_word) || title_word.starts_with(word.as_str()))
+        })
+    };
+    if let Some((weight, word)) = keyword_match {
+        score += weight;
+        reasons.push(format!("title mentions '{}'", word));
+    } else if let Some(word) = name_match() {
+        score += MIN_HEURISTIC_SCORE;
+        reasons.push(format!("title matches the template name ('{}')", word));
+    }
+
+    if let Some((_, _, words)) = keywords {
+        if let Some(word) = words.iter().find(|word| description.contains(&format!(" {} ", word))) {
+            score += 1;
+            reasons.push(format!("calendar description mentions '{}'", word));
+        }
+    }
+
+    match template_id {
+        "daily_standup" => {
+            if let Some(minutes) = signals.duration_minutes.filter(|m| *m <= SHORT_MEETING_MINUTES) {
+                score += 1;
+                reasons.push(format!("short meeting ({:.0} min)", minutes));
+            }
+            if signals.recurring() {
+                score += 1;
+                reasons.push("recurring calendar event".to_string());
+            }
+        }
+        "project_sync" if signals.recurring() => {
+            score += 1;
+            reasons.push("recurring calendar event".to_string());
+        }
+        "care_consultation" if signals.participants() == Some(2) => {
+            score += 1;
+            reasons.push("two participants".to_string());
+        }
+        _ => {}
+    }
+    (score, reasons)
+}
+
+/// Classifies a meeting by the user's rules and the heuristics. None when nothing is
+/// conclusive: no template reaches the minimum score, or two share the best score.
+fn classify(
+    signals: &MeetingSignals,
+    available: &[(String, String, String)],
+    rules: &[TemplateRule],
+) -> Option<(String, SelectionMethod, String)> {
+    let is_available = |id: &str| available.iter().any(|(available_id, _, _)| available_id == id);
+
+    for rule in rules {
+        if !rule.matches(signals) {
+            continue;
+        }
+        if !is_available(&rule.template_id) {
+            warn!(
+                "Skipping template rule '{}': template '{}' not found",
+                rule.title_contains, rule.template_id
+            );
+            continue;
+        }
+        return Some((
+            rule.template_id.clone(),
+            SelectionMethod::Rule,
+            format!("Your rule: titles containing '{}' use {}", rule.title_contains.trim(), rule.template_id),
+        ));
+    }
+
+    let mut scored: Vec<(u32, &str, Vec<String>)> = available
+        .iter()
+        .map(|(id, name, _)| {
+            let (score, reasons) = score_template(id, name, signals);
+            (score, id.as_str(), reasons)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    let (best_score, best_id, reasons) = scored.first()?;
+    let runner_up = scored.get(1).map_or(0, |(score, _, _)| *score);
+    if *best_score < MIN_HEURISTIC_SCORE || *best_score == runner_up {
+        return None;
+    }
+    Some((best_id.to_string(), SelectionMethod::Heuristic, reasons.join("; ")))
+}
+
+/// Selection from the settings without asking a model
+pub fn classify_meeting(
+    settings: &TemplateSelectionSettings,
+    signals: MeetingSignals,
+    available: &[(String, String, String)],
+) -> TemplateSelection {
+    match classify(&signals, available, &settings.rules) {
+        Some((template_id, method, rationale)) => TemplateSelection::new(&template_id, method, rationale, Some(signals)),
+        None => TemplateSelection::new(
+            &settings.fallback_template_id,
+            SelectionMethod::Fallback,
+            "No rule or heuristic identified the kind of meeting".to_string(),
+            Some(signals),
+        ),
+    }
+}
+
+/// What the classification prompt says about the meeting
+fn describe_meeting(signals: &MeetingSignals, transcript: &str) -> String {
+    let mut lines = Vec::new();
+    if let Some(title) = &signals.title {
+        lines.push(format!("Meeting title: {}", title));
+    }
+    if let Some(count) = signals.participants() {
+        lines.push(format!("Participants: {}", count));
+    }
+    if let Some(minutes) = signals.duration_minutes {
+        lines.push(format!("Duration: {:.0} minutes", minutes));
+    }
+    if let Some(calendar) = &signals.calendar {
+        if let Some(title) = &calendar.title {
+            lines.push(format!("Calendar event: {}", title));
+        }
+        if let Some(description) = calendar.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            let description: String = description.chars().take(500).collect();
+            lines.push(format!("Calendar description: {}", description));
+        }
+        if calendar.recurring == Some(true) {
+            lines.push("Recurring event".to_string());
+        }
+    }
+    let excerpt = transcript
+        .split_whitespace()
+        .take(MAX_EXCERPT_WORDS)
+        .collect::<Vec<_>>()
+        .join(" ");
+    lines.push(format!("\nTranscript excerpt:\n{}", excerpt));
+    lines.join("\n")
+}
+
+fn template_list(available: &[(String, String, String)]) -> String {
+    available
+        .iter()
+        .map(|(id, name, description)| format!("- {}: {} ({})", id, name, description))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Deserialize)]
+struct LlmChoice {
+    template_id: String,
+    #[serde(default)]
+    reason: String,
+}
+
+/// Template id and reason from the model's reply; a bare template id is accepted too
+fn parse_llm_choice(reply: &str, available: &[(String, String, String)]) -> Option<(String, String)> {
+    let is_available = |id: &str| available.iter().any(|(available_id, _, _)| available_id == id);
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => serde_json::from_str::<LlmChoice>(&reply[start..=end]).ok(),
+        _ => None,
+    };
+    if let Some(choice) = json {
+        let template_id = choice.template_id.trim().to_string();
+        return is_available(&template_id).then(|| (template_id, choice.reason.trim().to_string()));
+    }
+
+    // The longest id first, so "standup" does not win over "daily_standup"
+    let mut ids: Vec<&str> = available.iter().map(|(id, _, _)| id.as_str()).collect();
+    ids.sort_by_key(|id| std::cmp::Reverse(id.len()));
+    ids.into_iter()
+        .find(|id| reply.contains(id))
+        .map(|id| (id.to_string(), String::new()))
+}
+
+async fn classify_with_llm<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    classifier: &ClassifierModel<'_>,
+    signals: &MeetingSignals,
+    transcript: &str,
+    available: &[(String, String, String)],
+) -> Result<Option<(String, String)>, String> {
+    let connection = classifier.connection;
+    let meeting = describe_meeting(signals, transcript);
+    let mut redactor = redaction::redactor_for(app, pool, &connection.provider, &[meeting.as_str()]).await?;
+    let (system_prompt, meeting) = match redactor.as_mut() {
+        Some(redactor) => (
+            format!("{} {}", CLASSIFY_SYSTEM_PROMPT, redaction::PLACEHOLDER_NOTE),
+            redactor.redact(&meeting),
+        ),
+        None => (CLASSIFY_SYSTEM_PROMPT.to_string(), meeting),
+    };
+    let prompt = format!("Templates:\n{}\n\n{}", template_list(available), meeting);
+
+    let app_data_dir = app.path().app_data_dir().ok();
+    let client = HTTP_CLIENT.clone();
+    let reply = generate_summary(
+        &client,
+        &connection.provider,
+        classifier.model,
+        &connection.api_key,
+        &system_prompt,
+        &prompt,
+        connection.ollama_endpoint.as_deref(),
+        connection.custom_openai_endpoint.as_deref(),
+        Some(connection.max_tokens.map_or(MAX_COMPLETION_TOKENS, |t| t.min(MAX_COMPLETION_TOKENS))),
+        connection.temperature,
+        connection.top_p,
+        app_data_dir.as_ref(),
+        Some(classifier.cancel),
+    )
+    .await;
+    if let Some(redactor) = &redactor {
+        redaction::record_audit(pool, Some(meeting_id), "template_selection", classifier.provider, classifier.model, redactor)
+            .await;
+    }
+    let reply = match &redactor {
+        Some(redactor) => redactor.restore(&reply?),
+        None => reply?,
+    };
+    Ok(parse_llm_choice(&reply, available))
+}
+
+/// Template for a summary request: `requested` unless it asks for automatic selection
+pub(crate) async fn select_template<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    requested: &str,
+    calendar: Option<CalendarContext>,
+    transcript: &str,
+    classifier: ClassifierModel<'_>,
+) -> TemplateSelection {
+    if !is_auto(requested) {
+        return TemplateSelection::new(
+            requested.trim(),
+            SelectionMethod::Requested,
+            "Chosen when the summary was requested".to_string(),
+            None,
+        );
+    }
+    let settings = load_settings(app);
+    if !settings.enabled {
+        return TemplateSelection::new(
+            &settings.fallback_template_id,
+            SelectionMethod::Fallback,
+            "Automatic template selection is turned off".to_string(),
+            None,
+        );
+    }
+
+    let available = templates::list_templates();
+    let signals = gather_signals(pool, meeting_id, calendar).await;
+    let selection = classify_meeting(&settings, signals, &available);
+    if selection.method != SelectionMethod::Fallback || !settings.use_llm || available.is_empty() {
+        return selection;
+    }
+
+    let signals = selection.signals.clone().unwrap_or_default();
+    match classify_with_llm(app, pool, meeting_id, &classifier, &signals, transcript, &available).await {
+        Ok(Some((template_id, reason))) => {
+            let rationale = if reason.is_empty() {
+                "Chosen by the summary model".to_string()
+            } else {
+                reason
+            };
+            TemplateSelection::new(&template_id, SelectionMethod::Llm, rationale, Some(signals))
+        }
+        Ok(None) => {
+            warn!("The model did not name a known template for {}, using the fallback", meeting_id);
+            selection
+        }
+        Err(e) => {
+            warn!("Template classification failed for {}: {}, using the fallback", meeting_id, e);
+            selection
+        }
+    }
+}
+
+/// Stores the selection in the summary process metadata; failures are logged
+pub async fn record_selection(pool: &SqlitePool, meeting_id: &str, selection: &TemplateSelection) {
+    info!(
+        "Template '{}' selected for {} ({:?}): {}",
+        selection.template_id, meeting_id, selection.method, selection.rationale
+    );
+    let value = match serde_json::to_value(selection) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Failed to serialize template selection: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = SummaryProcessesRepository::set_metadata_entry(pool, meeting_id, METADATA_KEY, &value).await {
+        warn!("Failed to record template selection for {}: {}", meeting_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn available() -> Vec<(String, String, String)> {
+        [
+            ("daily_standup", "Daily Standup"),
+            ("project_sync", "Project Sync / Status Update"),
+            ("standard_meeting", "Standard Meeting Notes"),
+            ("retrospective", "Sprint Retrospective"),
+        ]
+        .iter()
+        .map(|(id, name)| (id.to_string(), name.to_string(), String::new()))
+        .collect()
+    }
+
+    fn titled(title: &str) -> MeetingSignals {
+        MeetingSignals {
+            title: Some(title.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rules_win_over_heuristics() {
+        let rules = vec![
+            TemplateRule {
+                title_contains: "Retro".to_string(),
+                template_id: "missing_template".to_string(),
+                min_participants: None,
+                max_participants: None,
+            },
+            TemplateRule {
+                title_contains: "retro".to_string(),
+                template_id: "project_sync".to_string(),
+                min_participants: None,
+                max_participants: Some(8),
+            },
+        ];
+        let mut retro = titled("Daily retro");
+        retro.speaker_count = Some(5);
+        let (id, method, _) = classify(&retro, &available(), &rules).unwrap();
+        assert_eq!((id.as_str(), method), ("project_sync", SelectionMethod::Rule));
+
+        // Outside the participant limit (or with an unknown count) the heuristics decide
+        retro.speaker_count = Some(12);
+        let (id, method, _) = classify(&retro, &available(), &rules).unwrap();
+        assert_eq!((id.as_str(), method), ("daily_standup", SelectionMethod::Heuristic));
+        retro.speaker_count = None;
+        assert_eq!(classify(&retro, &available(), &rules).unwrap().1, SelectionMethod::Heuristic);
+    }
+
+    #[test]
+    fn test_heuristics() {
+        let mut standup = titled("Team stand-up");
+        standup.duration_minutes = Some(12.0);
+        let (id, _, rationale) = classify(&standup, &available(), &[]).unwrap();
+        assert_eq!(id, "daily_standup");
+        assert!(rationale.contains("short meeting"));
+
+        let (id, _, rationale) = classify(&titled("Q3 retro"), &available(), &[]).unwrap();
+        assert_eq!(id, "retrospective");
+        assert!(rationale.contains("retrospective"));
+
+        let calendar = MeetingSignals {
+            calendar: Some(CalendarContext {
+                title: Some("Sprint 14 status".to_string()),
+                recurring: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(classify(&calendar, &available(), &[]).unwrap().0, "project_sync");
+
+        // Nothing to go on, or two templates equally likely
+        assert!(classify(&titled("Meeting 2026-10-18"), &available(), &[]).is_none());
+        assert!(classify(&titled("Daily project"), &available(), &[]).is_some());
+        assert!(classify(&titled("Sprint sync"), &available(), &[]).is_none());
+    }
+
+    #[test]
+    fn test_parse_llm_choice() {
+        let reply = "Sure! {\"template_id\": \"project_sync\", \"reason\": \"Weekly milestone review\"}";
+        assert_eq!(
+            parse_llm_choice(reply, &available()),
+            Some(("project_sync".to_string(), "Weekly milestone review".to_string()))
+        );
+        assert_eq!(
+            parse_llm_choice("daily_standup", &available()).map(|(id, _)| id),
+            Some("daily_standup".to_string())
+        );
+        assert_eq!(parse_llm_choice("{\"template_id\": \"unknown\"}", &available()), None);
+        assert!(is_auto(" Auto ") && is_auto("") && !is_auto("daily_standup"));
+    }
+}
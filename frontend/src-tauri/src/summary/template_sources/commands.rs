@@ -0,0 +1,43 @@
+use super::{TemplateConflict, TemplateSourceSettings};
+use tauri::{AppHandle, Runtime};
+use tracing::info;
+
+#[tauri::command]
+pub async fn api_get_template_sources<R: Runtime>(app: AppHandle<R>) -> Result<TemplateSourceSettings, String> {
+    Ok(super::load_settings(&app))
+}
+
+/// Saves the template sources and the organization id; takes effect with the next sync
+#[tauri::command]
+pub async fn api_save_template_sources<R: Runtime>(
+    app: AppHandle<R>,
+    settings: TemplateSourceSettings,
+) -> Result<TemplateSourceSettings, String> {
+    settings.validate()?;
+    super::save_settings(&app, &settings)?;
+    info!(
+        "Saved template sources for organization '{}' ({} sources)",
+        settings.client_id,
+        settings.sources.len()
+    );
+    Ok(settings)
+}
+
+/// Upstream template updates held back because of local edits
+#[tauri::command]
+pub async fn api_get_template_conflicts() -> Result<Vec<TemplateConflict>, String> {
+    Ok(super::list_conflicts())
+}
+
+/// Keeps the local template (`take_remote: false`) or replaces it with the upstream version,
+/// backing up the local file
+#[tauri::command]
+pub async fn api_resolve_template_conflict(template_id: String, take_remote: bool) -> Result<(), String> {
+    super::resolve_conflict(&template_id, take_remote)?;
+    info!(
+        "Resolved template conflict for '{}' ({})",
+        template_id,
+        if take_remote { "took upstream" } else { "kept local" }
+    );
+    Ok(())
+}
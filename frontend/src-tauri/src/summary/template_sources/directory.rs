@@ -0,0 +1,51 @@
+use super::{RemoteTemplate, TemplateSource};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Templates in a directory, one `<template_id>.json` per template
+pub struct DirectorySource {
+    path: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// Reads the `*.json` templates of a directory (blocking); unreadable files are skipped
+pub(super) fn read_templates(dir: &Path) -> Result<Vec<RemoteTemplate>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let mut templates = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let is_template = path.is_file()
+            && path.extension().and_then(|e| e.to_str()) == Some("json")
+            && !path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.'));
+        if !is_template {
+            continue;
+        }
+        let Some(template_id) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(json) => templates.push(RemoteTemplate { template_id, json }),
+            Err(e) => warn!("Skipping unreadable template {}: {}", path.display(), e),
+        }
+    }
+    templates.sort_by(|a, b| a.template_id.cmp(&b.template_id));
+    Ok(templates)
+}
+
+#[async_trait]
+impl TemplateSource for DirectorySource {
+    async fn fetch(&self) -> Result<Vec<RemoteTemplate>, String> {
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || read_templates(&path))
+            .await
+            .map_err(|e| format!("Template directory task join error: {}", e))?
+    }
+}
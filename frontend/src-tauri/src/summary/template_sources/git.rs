@@ -0,0 +1,122 @@
+use super::directory::read_templates;
+use super::{RemoteTemplate, TemplateSource};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Templates in a git repository, kept in a shallow checkout in the work directory.
+/// Uses the `git` command line, so the user's credentials and SSH setup apply.
+pub struct GitSource {
+    pub url: String,
+    pub branch: Option<String>,
+    /// Directory of the templates in the repository
+    pub subdir: Option<String>,
+    pub checkout_dir: PathBuf,
+}
+
+/// Only remote transports git handles itself (no `ext::` commands or local helpers)
+pub(super) fn check_url(url: &str) -> Result<(), String> {
+    let allowed = ["https://", "http://", "ssh://", "git@", "file://"];
+    if url.starts_with('-') || !allowed.iter().any(|prefix| url.starts_with(prefix)) {
+        return Err(format!("Unsupported git repository URL '{}'", url));
+    }
+    Ok(())
+}
+
+fn check_relative(subdir: &str) -> Result<(), String> {
+    let path = Path::new(subdir);
+    let escapes = path.is_absolute()
+        || path
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)));
+    if escapes {
+        return Err(format!("Invalid template directory '{}' in the repository", subdir));
+    }
+    Ok(())
+}
+
+async fn git(args: &[&str], dir: Option<&Path>) -> Result<(), String> {
+    let mut command = tokio::process::Command::new("git");
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    command
+        .args(args)
+        // Never wait for a credential prompt in the background
+        .env("GIT_TERMINAL_PROMPT", "0");
+
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command
+        .output()
+        .await
+        .map_err(|e| format!("git not found or not in PATH: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args[0], stderr.trim()));
+    }
+    Ok(())
+}
+
+impl GitSource {
+    /// Clones the repository, or updates the existing checkout to the latest commit
+    async fn update_checkout(&self) -> Result<(), String> {
+        check_url(&self.url)?;
+        if self.checkout_dir.join(".git").is_dir() {
+            let refspec = self.branch.as_deref().unwrap_or("HEAD");
+            git(&["fetch", "--depth", "1", "origin", refspec], Some(&self.checkout_dir)).await?;
+            git(&["reset", "--hard", "FETCH_HEAD"], Some(&self.checkout_dir)).await?;
+            return Ok(());
+        }
+
+        if let Some(parent) = self.checkout_dir.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let _ = std::fs::remove_dir_all(&self.checkout_dir);
+        let checkout = self.checkout_dir.to_string_lossy().to_string();
+        let mut args = vec!["clone", "--depth", "1"];
+        if let Some(branch) = &self.branch {
+            args.extend(["--branch", branch.as_str()]);
+        }
+        args.extend(["--", self.url.as_str(), checkout.as_str()]);
+        git(&args, None).await?;
+        info!("Cloned template repository {}", self.url);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TemplateSource for GitSource {
+    async fn fetch(&self) -> Result<Vec<RemoteTemplate>, String> {
+        let templates_dir = match self.subdir.as_deref().filter(|s| !s.is_empty()) {
+            Some(subdir) => {
+                check_relative(subdir)?;
+                self.checkout_dir.join(subdir)
+            }
+            None => self.checkout_dir.clone(),
+        };
+        self.update_checkout().await?;
+        tokio::task::spawn_blocking(move || read_templates(&templates_dir))
+            .await
+            .map_err(|e| format!("Template repository task join error: {}", e))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_url() {
+        assert!(check_url("https://git.example.com/org/templates.git").is_ok());
+        assert!(check_url("git@git.example.com:org/templates.git").is_ok());
+        assert!(check_url("ext::sh -c touch% /tmp/pwned").is_err());
+        assert!(check_url("--upload-pack=touch").is_err());
+        assert!(check_relative("templates/meetings").is_ok());
+        assert!(check_relative("../outside").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Templates published as a signed index over HTTPS.
+//!
+//! The index is `{"manifest": "<manifest JSON>", "signature": "<hex>"}`, where the signature is
+//! the organization's Ed25519 signature of the manifest string. The manifest lists the
+//! templates with their SHA-256:
+//!
+//! ```json
+//! {"client_id": "acme", "templates": [{"template_id": "standup", "url": "standup.json", "sha256": "…"}]}
+//! ```
+//!
+//! Template URLs are resolved against the index URL. A template whose content does not match
+//! the manifest is rejected, as is a manifest that does not name this organization.
+
+use super::{RemoteTemplate, TemplateSource};
+use async_trait::async_trait;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tracing::warn;
+
+pub struct HttpsIndexSource {
+    pub index_url: String,
+    /// Ed25519 public key of the organization (hex)
+    pub public_key: String,
+    pub client_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignedIndex {
+    manifest: String,
+    signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    client_id: String,
+    templates: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestEntry {
+    template_id: String,
+    url: String,
+    sha256: String,
+}
+
+pub(super) fn check_index_url(index_url: &str) -> Result<url::Url, String> {
+    let url = url::Url::parse(index_url).map_err(|e| format!("Invalid template index URL: {}", e))?;
+    if url.scheme() != "https" {
+        return Err("The template index must be served over HTTPS".to_string());
+    }
+    Ok(url)
+}
+
+pub(super) fn parse_public_key(public_key: &str) -> Result<VerifyingKey, String> {
+    let bytes: [u8; 32] = hex::decode(public_key.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| "The template signing key must be 32 bytes of hex".to_string())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| format!("Invalid template signing key: {}", e))
+}
+
+/// Checks the index signature and returns the manifest it covers
+fn verify_index(index: &SignedIndex, key: &VerifyingKey, client_id: &str) -> Result<Manifest, String> {
+    let signature: [u8; 64] = hex::decode(index.signature.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| "The template index signature is malformed".to_string())?;
+    key.verify(index.manifest.as_bytes(), &Signature::from_bytes(&signature))
+        .map_err(|_| "The template index signature does not match the organization's key".to_string())?;
+    let manifest: Manifest =
+        serde_json::from_str(&index.manifest).map_err(|e| format!("Invalid template manifest: {}", e))?;
+    // A manifest without client_id could be replayed to any organization using the same key
+    if manifest.client_id != client_id {
+        return Err(format!(
+            "The template index is for organization '{}', not '{}'",
+            manifest.client_id, client_id
+        ));
+    }
+    Ok(manifest)
+}
+
+#[async_trait]
+impl TemplateSource for HttpsIndexSource {
+    async fn fetch(&self) -> Result<Vec<RemoteTemplate>, String> {
+        let index_url = check_index_url(&self.index_url)?;
+        let key = parse_public_key(&self.public_key)?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(20))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        let index: SignedIndex = client
+            .get(index_url.clone())
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Template index not reachable: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid template index: {}", e))?;
+        let manifest = verify_index(&index, &key, &self.client_id)?;
+
+        let mut templates = Vec::new();
+        for entry in manifest.templates {
+            let url = match index_url.join(&entry.url) {
+                Ok(url) if url.scheme() == "https" => url,
+                _ => {
+                    warn!("Skipping template '{}' with invalid URL '{}'", entry.template_id, entry.url);
+                    continue;
+                }
+            };
+            let bytes = client
+                .get(url)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| format!("Template '{}' not reachable: {}", entry.template_id, e))?
+                .bytes()
+                .await
+                .map_err(|e| format!("Template '{}' download interrupted: {}", entry.template_id, e))?;
+            if hex::encode(Sha256::digest(&bytes)) != entry.sha256.to_lowercase() {
+                warn!("Skipping template '{}': content does not match the signed manifest", entry.template_id);
+                continue;
+            }
+            match serde_json::from_slice(&bytes) {
+                Ok(json) => templates.push(RemoteTemplate {
+                    template_id: entry.template_id,
+                    json,
+                }),
+                Err(e) => warn!("Skipping unreadable template '{}': {}", entry.template_id, e),
+            }
+        }
+        Ok(templates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_verify_index() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = hex::encode(signing_key.verifying_key().to_bytes());
+        let key = parse_public_key(&public_key).unwrap();
+        let manifest = r#"{"client_id": "acme", "templates": [{"template_id": "standup", "url": "standup.json", "sha256": "00"}]}"#;
+        let index = SignedIndex {
+            manifest: manifest.to_string(),
+            signature: hex::encode(signing_key.sign(manifest.as_bytes()).to_bytes()),
+        };
+
+        let verified = verify_index(&index, &key, "acme").unwrap();
+        assert_eq!(verified.templates[0].template_id, "standup");
+        assert!(verify_index(&index, &key, "other-org").is_err());
+
+        let tampered = SignedIndex {
+            manifest: manifest.replace("standup.json", "evil.json"),
+            signature: index.signature.clone(),
+        };
+        assert!(verify_index(&tampered, &key, "acme").is_err());
+
+        let unbound = r#"{"templates": [{"template_id": "standup", "url": "standup.json", "sha256": "00"}]}"#;
+        let unbound = SignedIndex {
+            manifest: unbound.to_string(),
+            signature: hex::encode(signing_key.sign(unbound.as_bytes()).to_bytes()),
+        };
+        assert!(verify_index(&unbound, &key, "acme").is_err());
+        assert!(parse_public_key("abcd").is_err());
+        assert!(check_index_url("http://templates.example.com/index.json").is_err());
+    }
+}
@@ -0,0 +1,631 @@
+//! Sources the synced templates come from.
+//!
+//! A source lists templates in the local schema (`templates::Template`): a local directory,
+//! a git repository, an HTTPS index with a signed manifest, or the organization backend
+//! (MongoDB, with the backend API as fallback). The sources and the organization's
+//! `client_id` are configured at runtime in `template_sources.json`.
+//!
+//! Sync is version-aware: a template is written to the synced templates directory only when
+//! its `version` (or else `updated_at`, or else its content) is newer than what was synced
+//! before. A synced template the user edited in place is not overwritten, and a newer
+//! upstream version of a template the user overrides with a custom one is reported; both
+//! become conflicts the user resolves by keeping the local version or taking the remote one.
+
+pub mod commands;
+mod directory;
+mod git;
+mod https;
+mod organization;
+
+use super::template_commands::SyncResult;
+use super::templates::{self, Template};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+use tracing::{info, warn};
+
+const STORE_FILE: &str = "template_sources.json";
+const SETTINGS_KEY: &str = "settings";
+const STATE_FILE: &str = "state.json";
+
+/// Organization whose templates are synced when none is configured
+pub const DEFAULT_CLIENT_ID: &str = "default";
+
+/// Where a source reads its templates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TemplateSourceConfig {
+    /// `*.json` templates in a local (or network) directory; the file name is the template id
+    Directory { path: PathBuf },
+    /// `*.json` templates in a git repository, optionally in a subdirectory of it
+    Git {
+        url: String,
+        #[serde(default)]
+        branch: Option<String>,
+        #[serde(default)]
+        subdir: Option<String>,
+    },
+    /// An index of templates served over HTTPS whose manifest is signed with the
+    /// organization's Ed25519 key (`public_key`, hex). `{client_id}` in the URL is replaced.
+    Https { index_url: String, public_key: String },
+    /// The organization's MongoDB `templates` collection, or the backend API when MongoDB is
+    /// not configured or unreachable
+    #[serde(rename = "mongodb")]
+    MongoDb,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSourceEntry {
+    /// Identifies the source in the sync state and in conflicts
+    pub id: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub config: TemplateSourceConfig,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSourceSettings {
+    /// Organization whose templates the backend and HTTPS sources serve
+    #[serde(default = "default_client_id")]
+    pub client_id: String,
+    /// Sources in priority order: when several provide a template id, the first one wins
+    #[serde(default)]
+    pub sources: Vec<TemplateSourceEntry>,
+}
+
+fn default_client_id() -> String {
+    DEFAULT_CLIENT_ID.to_string()
+}
+
+impl Default for TemplateSourceSettings {
+    fn default() -> Self {
+        Self {
+            client_id: default_client_id(),
+            sources: vec![TemplateSourceEntry {
+                id: "organization".to_string(),
+                enabled: true,
+                config: TemplateSourceConfig::MongoDb,
+            }],
+        }
+    }
+}
+
+impl TemplateSourceSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !templates::is_valid_template_id(&self.client_id) {
+            return Err(format!("Invalid organization id '{}'", self.client_id));
+        }
+        let mut ids = HashSet::new();
+        for source in &self.sources {
+            if !templates::is_valid_template_id(&source.id) {
+                return Err(format!("Invalid template source id '{}'", source.id));
+            }
+            if !ids.insert(source.id.as_str()) {
+                return Err(format!("Template source id '{}' is used twice", source.id));
+            }
+            match &source.config {
+                TemplateSourceConfig::Directory { path } if !path.is_absolute() => {
+                    return Err(format!("Template source '{}' needs an absolute directory", source.id));
+                }
+                TemplateSourceConfig::Git { url, .. } => git::check_url(url)?,
+                TemplateSourceConfig::Https { index_url, public_key } => {
+                    https::check_index_url(index_url)?;
+                    https::parse_public_key(public_key)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A template offered by a source
+#[derive(Debug, Clone)]
+pub struct RemoteTemplate {
+    pub template_id: String,
+    /// Template JSON in the local schema
+    pub json: serde_json::Value,
+}
+
+#[async_trait]
+pub trait TemplateSource: Send + Sync {
+    /// Every template the source currently offers
+    async fn fetch(&self) -> Result<Vec<RemoteTemplate>, String>;
+}
+
+fn source_for(entry: &TemplateSourceEntry, client_id: &str, work_dir: &Path) -> Box<dyn TemplateSource> {
+    match &entry.config {
+        TemplateSourceConfig::Directory { path } => Box::new(directory::DirectorySource::new(path.clone())),
+        TemplateSourceConfig::Git { url, branch, subdir } => Box::new(git::GitSource {
+            url: url.clone(),
+            branch: branch.clone(),
+            subdir: subdir.clone(),
+            checkout_dir: work_dir.join("git").join(&entry.id),
+        }),
+        TemplateSourceConfig::Https { index_url, public_key } => Box::new(https::HttpsIndexSource {
+            index_url: index_url.replace("{client_id}", client_id),
+            public_key: public_key.clone(),
+            client_id: client_id.to_string(),
+        }),
+        TemplateSourceConfig::MongoDb => Box::new(organization::OrganizationSource {
+            client_id: client_id.to_string(),
+        }),
+    }
+}
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> TemplateSourceSettings {
+    let store = match app.store(STORE_FILE) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to access template source store: {}, using defaults", e);
+            return TemplateSourceSettings::default();
+        }
+    };
+    match store.get(SETTINGS_KEY) {
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            warn!("Failed to deserialize template source settings: {}, using defaults", e);
+            TemplateSourceSettings::default()
+        }),
+        None => TemplateSourceSettings::default(),
+    }
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &TemplateSourceSettings) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to access template source store: {}", e))?;
+    let value = serde_json::to_value(settings).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    store.set(SETTINGS_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save template source store: {}", e))
+}
+
+/// Sync state, git checkouts and conflict backups:
+/// - macOS: ~/Library/Application Support/IQcapture/template_sync/
+/// - Windows: %APPDATA%\IQcapture\template_sync\
+/// - Linux: ~/.config/IQcapture/template_sync/
+pub fn get_work_dir() -> Option<PathBuf> {
+    let mut path = dirs::data_dir()?;
+    path.push("IQcapture");
+    path.push("template_sync");
+    Some(path)
+}
+
+/// What was last synced for a template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncedTemplateState {
+    pub source_id: String,
+    #[serde(default)]
+    pub version: Option<u32>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// SHA-256 of the last synced upstream content
+    pub remote_sha256: String,
+    /// SHA-256 of the file written to the synced directory, to notice edits made to it
+    pub written_sha256: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// The synced copy was edited locally after it was synced
+    EditedLocally,
+    /// A custom template with the same id overrides the synced one
+    CustomOverride,
+}
+
+/// A newer upstream version that was not applied over a local edit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateConflict {
+    pub template_id: String,
+    pub source_id: String,
+    pub kind: ConflictKind,
+    #[serde(default)]
+    pub remote_version: Option<u32>,
+    #[serde(default)]
+    pub remote_updated_at: Option<String>,
+    /// The upstream template (local schema)
+    pub remote_json: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    #[serde(default)]
+    templates: BTreeMap<String, SyncedTemplateState>,
+    #[serde(default)]
+    conflicts: BTreeMap<String, TemplateConflict>,
+}
+
+impl SyncState {
+    fn load(work_dir: &Path) -> Self {
+        match std::fs::read_to_string(work_dir.join(STATE_FILE)) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable template sync state: {}", e);
+                SyncState::default()
+            }),
+            Err(_) => SyncState::default(),
+        }
+    }
+
+    fn save(&self, work_dir: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(work_dir).map_err(|e| format!("Failed to create {}: {}", work_dir.display(), e))?;
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize sync state: {}", e))?;
+        let temp = work_dir.join(format!("{}.tmp", STATE_FILE));
+        std::fs::write(&temp, json).map_err(|e| format!("Failed to write sync state: {}", e))?;
+        std::fs::rename(&temp, work_dir.join(STATE_FILE)).map_err(|e| format!("Failed to write sync state: {}", e))
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn parse_timestamp(value: Option<&str>) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value?).ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// Whether an upstream template is newer than what was synced: by version, then by
+/// `updated_at`, then by content. Older versions are ignored.
+fn is_newer(remote: &Template, remote_sha256: &str, known: &SyncedTemplateState) -> bool {
+    if let (Some(remote_version), Some(known_version)) = (remote.version, known.version) {
+        if remote_version != known_version {
+            return remote_version > known_version;
+        }
+    }
+    let remote_updated = parse_timestamp(remote.updated_at.as_deref());
+    let known_updated = parse_timestamp(known.updated_at.as_deref());
+    if let (Some(remote_updated), Some(known_updated)) = (remote_updated, known_updated) {
+        if remote_updated != known_updated {
+            return remote_updated > known_updated;
+        }
+    }
+    remote_sha256 != known.remote_sha256
+}
+
+/// Directories the sync writes to (passed in so tests can use temporary ones)
+struct SyncDirs<'a> {
+    synced: &'a Path,
+    custom: Option<&'a Path>,
+    work: &'a Path,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ApplyCounts {
+    synced: u32,
+    skipped: u32,
+    failed: u32,
+    conflicts: u32,
+}
+
+fn write_template(dirs: &SyncDirs, template_id: &str, json: &str) -> Result<(), String> {
+    std::fs::create_dir_all(dirs.synced)
+        .map_err(|e| format!("Failed to create synced templates directory: {}", e))?;
+    let path = dirs.synced.join(format!("{}.json", template_id));
+    let temp = dirs.synced.join(format!(".{}.json.tmp", template_id));
+    std::fs::write(&temp, json).map_err(|e| format!("Failed to write synced template '{}': {}", template_id, e))?;
+    std::fs::rename(&temp, &path).map_err(|e| format!("Failed to write synced template '{}': {}", template_id, e))
+}
+
+/// Moves a local template out of the way into the work directory's backups
+fn back_up(dirs: &SyncDirs, path: &Path, template_id: &str) -> Result<PathBuf, String> {
+    let backups = dirs.work.join("backups");
+    std::fs::create_dir_all(&backups).map_err(|e| format!("Failed to create {}: {}", backups.display(), e))?;
+    let target = backups.join(format!("{}_{}.json", template_id, Utc::now().format("%Y%m%d_%H%M%S")));
+    std::fs::rename(path, &target)
+        .or_else(|_| std::fs::copy(path, &target).and_then(|_| std::fs::remove_file(path)))
+        .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+    Ok(target)
+}
+
+/// Writes the new and newer templates of one source to the synced directory
+fn apply_templates(
+    dirs: &SyncDirs,
+    state: &mut SyncState,
+    source_id: &str,
+    remote_templates: Vec<RemoteTemplate>,
+    claimed: &mut HashSet<String>,
+) -> ApplyCounts {
+    let mut counts = ApplyCounts::default();
+    for remote in remote_templates {
+        let template_id = remote.template_id;
+        if !templates::is_valid_template_id(&template_id) {
+            warn!("Skipping template with invalid id '{}' from source '{}'", template_id, source_id);
+            counts.failed += 1;
+            continue;
+        }
+        if !claimed.insert(template_id.clone()) {
+            // An earlier source provides this template
+            counts.skipped += 1;
+            continue;
+        }
+        let json = serde_json::to_string_pretty(&remote.json).unwrap_or_default();
+        let template = match templates::validate_and_parse_template(&json) {
+            Ok(template) => template,
+            Err(e) => {
+                warn!("Skipping invalid template '{}' from source '{}': {}", template_id, source_id, e);
+                counts.failed += 1;
+                continue;
+            }
+        };
+        let remote_sha256 = sha256_hex(json.as_bytes());
+
+        let known = state.templates.get(&template_id).cloned();
+        if let Some(known) = &known {
+            if known.source_id == source_id && !is_newer(&template, &remote_sha256, known) {
+                counts.skipped += 1;
+                continue;
+            }
+        }
+        let synced_path = dirs.synced.join(format!("{}.json", template_id));
+        let edited_locally = match (&known, std::fs::read(&synced_path)) {
+            (Some(known), Ok(current)) => sha256_hex(&current) != known.written_sha256,
+            _ => false,
+        };
+        let overridden = dirs
+            .custom
+            .is_some_and(|custom| custom.join(format!("{}.json", template_id)).is_file());
+
+        let conflict = |kind| TemplateConflict {
+            template_id: template_id.clone(),
+            source_id: source_id.to_string(),
+            kind,
+            remote_version: template.version,
+            remote_updated_at: template.updated_at.clone(),
+            remote_json: json.clone(),
+            detected_at: Utc::now(),
+        };
+        let acknowledged = SyncedTemplateState {
+            source_id: source_id.to_string(),
+            version: template.version,
+            updated_at: template.updated_at.clone(),
+            remote_sha256: remote_sha256.clone(),
+            written_sha256: known.as_ref().map(|k| k.written_sha256.clone()).unwrap_or_default(),
+        };
+
+        if edited_locally {
+            info!("Template '{}' was edited locally; keeping the edit until resolved", template_id);
+            state.conflicts.insert(template_id.clone(), conflict(ConflictKind::EditedLocally));
+            // The same upstream version does not conflict again
+            state.templates.insert(template_id, acknowledged);
+            counts.conflicts += 1;
+            continue;
+        }
+
+        if let Err(e) = write_template(dirs, &template_id, &json) {
+            warn!("{}", e);
+            counts.failed += 1;
+            continue;
+        }
+        state.templates.insert(
+            template_id.clone(),
+            SyncedTemplateState {
+                written_sha256: remote_sha256.clone(),
+                ..acknowledged
+            },
+        );
+        counts.synced += 1;
+
+        if overridden && known.is_some() {
+            // The custom template still wins; the user decides whether to take the update
+            state.conflicts.insert(template_id.clone(), conflict(ConflictKind::CustomOverride));
+            counts.conflicts += 1;
+        } else {
+            state.conflicts.remove(&template_id);
+        }
+    }
+    counts
+}
+
+/// Syncs the templates of every enabled source into the synced templates directory
+pub async fn sync_templates(settings: &TemplateSourceSettings) -> SyncResult {
+    let mut result = SyncResult {
+        synced_count: 0,
+        failed_count: 0,
+        is_online: false,
+        skipped_count: 0,
+        conflict_count: 0,
+        errors: Vec::new(),
+    };
+    let (Some(synced_dir), Some(work_dir)) = (templates::get_synced_templates_dir(), get_work_dir()) else {
+        result.errors.push("Synced templates directory not initialised".to_string());
+        return result;
+    };
+    let custom_dir = templates::get_custom_templates_dir();
+    let dirs = SyncDirs {
+        synced: &synced_dir,
+        custom: custom_dir.as_deref(),
+        work: &work_dir,
+    };
+
+    let mut state = SyncState::load(&work_dir);
+    let mut claimed = HashSet::new();
+    for entry in settings.sources.iter().filter(|s| s.enabled) {
+        let source = source_for(entry, &settings.client_id, &work_dir);
+        match source.fetch().await {
+            Ok(remote_templates) => {
+                result.is_online = true;
+                let counts = apply_templates(&dirs, &mut state, &entry.id, remote_templates, &mut claimed);
+                info!(
+                    "Template source '{}': {} synced, {} unchanged, {} failed, {} conflicts",
+                    entry.id, counts.synced, counts.skipped, counts.failed, counts.conflicts
+                );
+                result.synced_count += counts.synced;
+                result.skipped_count += counts.skipped;
+                result.failed_count += counts.failed;
+            }
+            Err(e) => {
+                info!("Template source '{}' not available: {}", entry.id, e);
+                result.errors.push(format!("{}: {}", entry.id, e));
+            }
+        }
+    }
+    result.conflict_count = state.conflicts.len() as u32;
+    if let Err(e) = state.save(&work_dir) {
+        warn!("{}", e);
+    }
+    result
+}
+
+/// Open conflicts between upstream templates and local edits
+pub fn list_conflicts() -> Vec<TemplateConflict> {
+    get_work_dir()
+        .map(|dir| SyncState::load(&dir).conflicts.into_values().collect())
+        .unwrap_or_default()
+}
+
+/// Settles a conflict by keeping the local template or taking the upstream one (the local
+/// file is moved to the backups)
+fn resolve(dirs: &SyncDirs, state: &mut SyncState, template_id: &str, take_remote: bool) -> Result<(), String> {
+    let conflict = state
+        .conflicts
+        .remove(template_id)
+        .ok_or_else(|| format!("No template conflict for '{}'", template_id))?;
+    if !take_remote {
+        return Ok(());
+    }
+    let local_path = match conflict.kind {
+        ConflictKind::EditedLocally => dirs.synced.join(format!("{}.json", template_id)),
+        ConflictKind::CustomOverride => dirs
+            .custom
+            .ok_or_else(|| "Custom templates directory not available".to_string())?
+            .join(format!("{}.json", template_id)),
+    };
+    if local_path.is_file() {
+        let backup = back_up(dirs, &local_path, template_id)?;
+        info!("Backed up local template '{}' to {:?}", template_id, backup);
+    }
+    if conflict.kind == ConflictKind::EditedLocally {
+        write_template(dirs, template_id, &conflict.remote_json)?;
+        if let Some(known) = state.templates.get_mut(template_id) {
+            known.written_sha256 = sha256_hex(conflict.remote_json.as_bytes());
+        }
+    }
+    Ok(())
+}
+
+pub fn resolve_conflict(template_id: &str, take_remote: bool) -> Result<(), String> {
+    let synced_dir =
+        templates::get_synced_templates_dir().ok_or_else(|| "Synced templates directory not initialised".to_string())?;
+    let work_dir = get_work_dir().ok_or_else(|| "App data directory not available".to_string())?;
+    let custom_dir = templates::get_custom_templates_dir();
+    let dirs = SyncDirs {
+        synced: &synced_dir,
+        custom: custom_dir.as_deref(),
+        work: &work_dir,
+    };
+    let mut state = SyncState::load(&work_dir);
+    resolve(&dirs, &mut state, template_id, take_remote)?;
+    state.save(&work_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn remote(template_id: &str, version: u32, instruction: &str) -> RemoteTemplate {
+        RemoteTemplate {
+            template_id: template_id.to_string(),
+            json: json!({
+                "name": "Standup",
+                "description": "Daily standup",
+                "sections": [{"title": "Updates", "instruction": instruction, "format": "list"}],
+                "version": version,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_settings_defaults_and_validation() {
+        let settings: TemplateSourceSettings = serde_json::from_str(
+            r#"{"sources": [{"id": "team", "kind": "git", "url": "https://git.example.com/templates.git"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(settings.client_id, DEFAULT_CLIENT_ID);
+        assert!(settings.sources[0].enabled);
+        assert!(settings.validate().is_ok());
+
+        let mut duplicate = settings.clone();
+        duplicate.sources.push(duplicate.sources[0].clone());
+        assert!(duplicate.validate().is_err());
+
+        let relative: TemplateSourceSettings =
+            serde_json::from_str(r#"{"sources": [{"id": "dir", "kind": "directory", "path": "templates"}]}"#).unwrap();
+        assert!(relative.validate().is_err());
+        assert_eq!(
+            TemplateSourceSettings::default().sources[0].config,
+            TemplateSourceConfig::MongoDb
+        );
+    }
+
+    #[test]
+    fn test_version_aware_sync() {
+        let root = tempfile::tempdir().unwrap();
+        let (synced, custom, work) = (root.path().join("synced"), root.path().join("custom"), root.path().join("work"));
+        let dirs = SyncDirs { synced: &synced, custom: Some(custom.as_path()), work: &work };
+        let mut state = SyncState::default();
+        let apply = |state: &mut SyncState, templates: Vec<RemoteTemplate>| {
+            apply_templates(&dirs, state, "org", templates, &mut HashSet::new())
+        };
+
+        assert_eq!(apply(&mut state, vec![remote("standup", 2, "List updates")]).synced, 1);
+        // Same version again, and an older one, change nothing
+        assert_eq!(apply(&mut state, vec![remote("standup", 2, "List updates")]).skipped, 1);
+        assert_eq!(apply(&mut state, vec![remote("standup", 1, "Old")]).skipped, 1);
+        assert_eq!(apply(&mut state, vec![remote("standup", 3, "List updates by person")]).synced, 1);
+        let synced_json = std::fs::read_to_string(synced.join("standup.json")).unwrap();
+        assert!(synced_json.contains("List updates by person"));
+
+        // A local edit of the synced copy is kept and the update becomes a conflict
+        std::fs::write(synced.join("standup.json"), synced_json.replace("by person", "by team")).unwrap();
+        let counts = apply(&mut state, vec![remote("standup", 4, "Version four")]);
+        assert_eq!((counts.synced, counts.conflicts), (0, 1));
+        assert!(std::fs::read_to_string(synced.join("standup.json")).unwrap().contains("by team"));
+        assert_eq!(state.conflicts["standup"].kind, ConflictKind::EditedLocally);
+        // The acknowledged version does not conflict again
+        assert_eq!(apply(&mut state, vec![remote("standup", 4, "Version four")]).skipped, 1);
+
+        resolve(&dirs, &mut state, "standup", true).unwrap();
+        assert!(std::fs::read_to_string(synced.join("standup.json")).unwrap().contains("Version four"));
+        assert_eq!(std::fs::read_dir(work.join("backups")).unwrap().count(), 1);
+        assert!(state.conflicts.is_empty());
+        assert_eq!(apply(&mut state, vec![remote("standup", 5, "Version five")]).synced, 1);
+    }
+
+    #[test]
+    fn test_custom_override_and_source_priority() {
+        let root = tempfile::tempdir().unwrap();
+        let (synced, custom, work) = (root.path().join("synced"), root.path().join("custom"), root.path().join("work"));
+        let dirs = SyncDirs { synced: &synced, custom: Some(custom.as_path()), work: &work };
+        let mut state = SyncState::default();
+        let mut claimed = HashSet::new();
+
+        apply_templates(&dirs, &mut state, "org", vec![remote("retro", 1, "What went well")], &mut claimed);
+        // A later source offering the same id does not replace it
+        let counts = apply_templates(&dirs, &mut state, "team", vec![remote("retro", 9, "Other")], &mut claimed);
+        assert_eq!(counts.skipped, 1);
+        assert_eq!(state.templates["retro"].source_id, "org");
+
+        std::fs::create_dir_all(&custom).unwrap();
+        std::fs::write(custom.join("retro.json"), "{}").unwrap();
+        let counts = apply_templates(&dirs, &mut state, "org", vec![remote("retro", 2, "Went well")], &mut HashSet::new());
+        assert_eq!((counts.synced, counts.conflicts), (1, 1));
+        assert_eq!(state.conflicts["retro"].kind, ConflictKind::CustomOverride);
+
+        resolve(&dirs, &mut state, "retro", false).unwrap();
+        assert!(custom.join("retro.json").is_file());
+        assert!(resolve(&dirs, &mut state, "retro", false).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use super::{RemoteTemplate, TemplateSource};
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+/// The organization's templates from the MongoDB `templates` collection, or from the backend
+/// API when MongoDB is not configured or unreachable
+pub struct OrganizationSource {
+    pub client_id: String,
+}
+
+/// Sync response shape from backend API
+#[derive(Debug, Deserialize)]
+struct BackendSyncResponse {
+    templates: Vec<BackendTemplate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BackendTemplate {
+    template_id: String,
+    name: String,
+    description: String,
+    sections: Vec<serde_json::Value>,
+    #[serde(default)]
+    global_instruction: Option<String>,
+    #[serde(default)]
+    clinical_safety_rules: Option<Vec<String>>,
+    #[serde(default)]
+    version: Option<u32>,
+    #[serde(default)]
+    updated_at: Option<String>,
+}
+
+/// Templates from MongoDB directly.
+/// Uses bson::Document to avoid schema mismatches with BSON types (e.g. DateTime).
+async fn fetch_from_mongodb(client_id: &str) -> Result<Vec<RemoteTemplate>, String> {
+    use futures_util::TryStreamExt;
+    use mongodb::bson::{doc, Bson, Document};
+
+    let collection = crate::mongodb_client::get_collection::<Document>("templates").await?;
+
+    let filter = doc! { "client_id": client_id, "is_active": true };
+    let mut cursor = collection
+        .find(filter)
+        .await
+        .map_err(|e| format!("MongoDB template query failed: {e}"))?;
+
+    let mut templates = Vec::new();
+    while let Some(doc) = cursor.try_next().await.map_err(|e| format!("MongoDB cursor error: {e}"))? {
+        let template_id = match doc.get_str("template_id") {
+            Ok(id) => id.to_string(),
+            Err(_) => {
+                warn!("Skipping MongoDB template without template_id");
+                continue;
+            }
+        };
+
+        // Convert BSON sections array to serde_json::Value for local schema
+        let sections: Vec<serde_json::Value> = doc
+            .get_array("sections")
+            .unwrap_or(&vec![])
+            .iter()
+            .filter_map(|v| {
+                let json_str = serde_json::to_string(&v).ok()?;
+                serde_json::from_str(&json_str).ok()
+            })
+            .collect();
+
+        let version = match doc.get("version") {
+            Some(Bson::Int32(v)) => u32::try_from(*v).ok(),
+            Some(Bson::Int64(v)) => u32::try_from(*v).ok(),
+            _ => None,
+        };
+        let updated_at = match doc.get("updated_at") {
+            Some(Bson::DateTime(t)) => t.try_to_rfc3339_string().ok(),
+            Some(Bson::String(s)) => Some(s.clone()),
+            _ => None,
+        };
+
+        templates.push(RemoteTemplate {
+            template_id,
+            json: serde_json::json!({
+                "name": doc.get_str("name").unwrap_or_default(),
+                "description": doc.get_str("description").unwrap_or_default(),
+                "sections": sections,
+                "global_instruction": doc.get_str("global_instruction").ok(),
+                "clinical_safety_rules": doc.get_array("clinical_safety_rules").ok().map(|arr| {
+                    arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect::<Vec<_>>()
+                }),
+                "version": version,
+                "updated_at": updated_at,
+            }),
+        });
+    }
+
+    info!("MongoDB template source: {} templates", templates.len());
+    Ok(templates)
+}
+
+/// Templates from the backend HTTP API (fallback).
+async fn fetch_from_api(client_id: &str) -> Result<Vec<RemoteTemplate>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client for template sync: {}", e))?;
+
+    let response = client
+        .get(format!("{}/api/templates", crate::api::api::APP_SERVER_URL))
+        .query(&[("client_id", client_id)])
+        .send()
+        .await
+        .map_err(|e| format!("backend not reachable ({})", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("backend returned status {}", response.status()));
+    }
+
+    let body = response
+        .json::<BackendSyncResponse>()
+        .await
+        .map_err(|e| format!("failed to parse response: {}", e))?;
+
+    Ok(body
+        .templates
+        .into_iter()
+        .map(|tmpl| RemoteTemplate {
+            template_id: tmpl.template_id,
+            json: serde_json::json!({
+                "name": tmpl.name,
+                "description": tmpl.description,
+                "sections": tmpl.sections,
+                "global_instruction": tmpl.global_instruction,
+                "clinical_safety_rules": tmpl.clinical_safety_rules,
+                "version": tmpl.version,
+                "updated_at": tmpl.updated_at,
+            }),
+        })
+        .collect())
+}
+
+#[async_trait]
+impl TemplateSource for OrganizationSource {
+    async fn fetch(&self) -> Result<Vec<RemoteTemplate>, String> {
+        if crate::mongodb_client::is_configured() {
+            match fetch_from_mongodb(&self.client_id).await {
+                Ok(templates) => return Ok(templates),
+                Err(e) => warn!("MongoDB template sync failed, falling back to API: {}", e),
+            }
+        }
+        fetch_from_api(&self.client_id).await
+    }
+}
@@ -1,5 +1,5 @@
 use super::defaults;
-use super::types::Template;
+use super::types::{Template, TemplateIssue, TemplateSection};
 use std::path::PathBuf;
 use tracing::{debug, info, warn};
 use once_cell::sync::Lazy;
@@ -27,13 +27,27 @@ pub fn set_synced_templates_dir(path: PathBuf) {
     }
 }
 
+/// The synced templates directory, once set at startup
+pub fn get_synced_templates_dir() -> Option<PathBuf> {
+    SYNCED_TEMPLATES_DIR.read().ok()?.clone()
+}
+
+/// Whether a template identifier is safe to use as a file name (letters, digits, `_` and `-`)
+pub fn is_valid_template_id(template_id: &str) -> bool {
+    !template_id.is_empty()
+        && template_id.len() <= 100
+        && template_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
 /// Get the user's custom templates directory path
 ///
 /// Returns the platform-specific application data directory for custom templates:
 /// - macOS: ~/Library/Application Support/IQcapture/templates/
 /// - Windows: %APPDATA%\IQcapture\templates\
 /// - Linux: ~/.config/IQcapture/templates/
-fn get_custom_templates_dir() -> Option<PathBuf> {
+pub fn get_custom_templates_dir() -> Option<PathBuf> {
     let mut path = dirs::data_dir()?;
     path.push("IQcapture");
     path.push("templates");
@@ -131,6 +145,14 @@ pub fn save_synced_template(template_id: &str, json_content: &str) -> Result<(),
     Ok(())
 }
 
+/// Whether a template is provided by the app or a template source (built-in, bundled or
+/// synced), whether or not a custom template overrides it
+pub fn has_shipped_template(template_id: &str) -> bool {
+    defaults::get_builtin_template(template_id).is_some()
+        || load_bundled_template(template_id).is_some()
+        || load_synced_template(template_id).is_some()
+}
+
 /// Load and parse a template by identifier
 ///
 /// This function implements a 4-tier fallback strategy:
@@ -189,6 +211,52 @@ pub fn validate_and_parse_template(json_content: &str) -> Result<Template, Strin
     Ok(template)
 }
 
+/// Field named in a serde error such as "missing field `instruction`"
+fn field_in_error(message: &str) -> Option<String> {
+    let start = message.find('`')? + 1;
+    let end = start + message[start..].find('`')?;
+    Some(message[start..end].to_string())
+}
+
+/// Parses and validates template JSON, reporting every problem with the section it is in
+/// (for the template editor; `validate_and_parse_template` stops at the first one)
+pub fn check_template_json(json_content: &str) -> Result<Template, Vec<TemplateIssue>> {
+    let value: serde_json::Value = serde_json::from_str(json_content).map_err(|e| {
+        vec![TemplateIssue::template(
+            "json",
+            format!("Invalid JSON at line {}, column {}: {}", e.line(), e.column(), e),
+        )]
+    })?;
+
+    // Sections one by one, so a broken section is named
+    let mut issues = Vec::new();
+    if let Some(sections) = value.get("sections").and_then(|s| s.as_array()) {
+        for (i, section) in sections.iter().enumerate() {
+            if let Err(e) = serde_json::from_value::<TemplateSection>(section.clone()) {
+                let message = e.to_string();
+                let field = field_in_error(&message).unwrap_or_else(|| "section".to_string());
+                let title = section.get("title").and_then(|t| t.as_str());
+                issues.push(TemplateIssue::section(i, title, &field, format!("Section {}: {}", i, message)));
+            }
+        }
+    }
+    if !issues.is_empty() {
+        return Err(issues);
+    }
+
+    let template: Template = serde_json::from_value(value).map_err(|e| {
+        let message = e.to_string();
+        let field = field_in_error(&message).unwrap_or_else(|| "template".to_string());
+        vec![TemplateIssue::template(&field, format!("Invalid template: {}", message))]
+    })?;
+    let issues = template.issues();
+    if issues.is_empty() {
+        Ok(template)
+    } else {
+        Err(issues)
+    }
+}
+
 /// List all available template identifiers
 ///
 /// Returns a combined list of:
@@ -325,6 +393,41 @@ mod tests {
         assert!(ids.contains(&"standard_meeting".to_string()));
     }
 
+    #[test]
+    fn test_is_valid_template_id() {
+        assert!(is_valid_template_id("daily_standup"));
+        assert!(is_valid_template_id("team-retro-2"));
+        assert!(!is_valid_template_id(""));
+        assert!(!is_valid_template_id("../secrets"));
+        assert!(!is_valid_template_id("notes.json"));
+    }
+
+    #[test]
+    fn test_check_template_json_points_to_section() {
+        let json = r#"{
+            "name": "Retro",
+            "description": "Team retrospective",
+            "sections": [
+                {"title": "Went well", "instruction": "List what went well", "format": "list"},
+                {"title": "To improve", "format": "list"},
+                {"title": "Actions", "instruction": "List actions", "format": "table"}
+            ]
+        }"#;
+        let issues = check_template_json(json).unwrap_err();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].section_index, Some(1));
+        assert_eq!(issues[0].section_title.as_deref(), Some("To improve"));
+        assert_eq!(issues[0].field, "instruction");
+
+        let fixed = json.replace(r#"{"title": "To improve", "format": "list"}"#, r#"{"title": "To improve", "instruction": "List", "format": "list"}"#);
+        let issues = check_template_json(&fixed).unwrap_err();
+        assert_eq!((issues[0].section_index, issues[0].field.as_str()), (Some(2), "format"));
+
+        let issues = check_template_json("{\"name\": ").unwrap_err();
+        assert_eq!(issues[0].field, "json");
+        assert!(check_template_json(&fixed.replace("table", "list")).is_ok());
+    }
+
     #[test]
     fn test_validate_invalid_json() {
         let result = validate_and_parse_template("invalid json");
@@ -35,18 +35,22 @@
 //! - Windows: `%APPDATA%\IQcapture\templates\`
 //! - Linux: `~/.config/IQcapture/templates/`
 //!
-//! Custom templates must follow the JSON schema defined in `types::Template`.
+//! Custom templates must follow the JSON schema defined in `types::Template`. Templates created
+//! in the app's editor are stored there too, with their earlier versions (see `user`).
 
 mod defaults;
 mod loader;
 mod types;
+pub mod user;
 
 // Re-export public API
 pub use loader::{
-    get_template, list_template_ids, list_templates, save_synced_template,
-    set_bundled_templates_dir, set_synced_templates_dir, validate_and_parse_template,
+    check_template_json, get_custom_templates_dir, get_synced_templates_dir, get_template,
+    has_shipped_template, is_valid_template_id, list_template_ids, list_templates,
+    save_synced_template, set_bundled_templates_dir, set_synced_templates_dir,
+    validate_and_parse_template,
 };
-pub use types::{Template, TemplateSection};
+pub use types::{Template, TemplateIssue, TemplateSection};
 
 #[cfg(test)]
 mod tests {
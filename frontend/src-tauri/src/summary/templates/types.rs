@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Represents a single section in a meeting template
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,40 +51,130 @@ pub struct Template {
     pub updated_at: Option<String>,
 }
 
+/// A problem found in a template, pointing to the section it is in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateIssue {
+    /// Position of the failing section (None for problems outside the sections)
+    pub section_index: Option<usize>,
+    pub section_title: Option<String>,
+    /// Field with the problem (e.g. "instruction"), or "json" when the JSON does not parse
+    pub field: String,
+    pub message: String,
+}
+
+impl TemplateIssue {
+    pub(crate) fn template(field: &str, message: String) -> Self {
+        Self {
+            section_index: None,
+            section_title: None,
+            field: field.to_string(),
+            message,
+        }
+    }
+
+    pub(crate) fn section(index: usize, title: Option<&str>, field: &str, message: String) -> Self {
+        Self {
+            section_index: Some(index),
+            section_title: title.filter(|t| !t.is_empty()).map(str::to_string),
+            field: field.to_string(),
+            message,
+        }
+    }
+}
+
 impl Template {
     /// Validates the template structure
     pub fn validate(&self) -> Result<(), String> {
+        match self.issues().into_iter().next() {
+            Some(issue) => Err(issue.message),
+            None => Ok(()),
+        }
+    }
+
+    /// Every structural problem of the template, in order
+    pub fn issues(&self) -> Vec<TemplateIssue> {
+        let mut issues = Vec::new();
         if self.name.is_empty() {
-            return Err("Template name cannot be empty".to_string());
+            issues.push(TemplateIssue::template("name", "Template name cannot be empty".to_string()));
         }
 
         if self.description.is_empty() {
-            return Err("Template description cannot be empty".to_string());
+            issues.push(TemplateIssue::template(
+                "description",
+                "Template description cannot be empty".to_string(),
+            ));
         }
 
         if self.sections.is_empty() {
-            return Err("Template must have at least one section".to_string());
+            issues.push(TemplateIssue::template(
+                "sections",
+                "Template must have at least one section".to_string(),
+            ));
         }
 
         for (i, section) in self.sections.iter().enumerate() {
+            let title = Some(section.title.as_str());
             if section.title.is_empty() {
-                return Err(format!("Section {} has empty title", i));
+                issues.push(TemplateIssue::section(i, title, "title", format!("Section {} has empty title", i)));
             }
 
             if section.instruction.is_empty() {
-                return Err(format!("Section '{}' has empty instruction", section.title));
+                issues.push(TemplateIssue::section(
+                    i,
+                    title,
+                    "instruction",
+                    format!("Section '{}' has empty instruction", section.title),
+                ));
             }
 
             match section.format.as_str() {
                 "paragraph" | "list" | "string" => {},
-                other => return Err(format!(
-                    "Section '{}' has invalid format '{}'. Must be 'paragraph', 'list', or 'string'",
-                    section.title, other
+                other => issues.push(TemplateIssue::section(
+                    i,
+                    title,
+                    "format",
+                    format!(
+                        "Section '{}' has invalid format '{}'. Must be 'paragraph', 'list', or 'string'",
+                        section.title, other
+                    ),
                 )),
             }
         }
 
-        Ok(())
+        issues
+    }
+
+    /// Replaces `{{name}}` placeholders in section instructions, item formats and the global
+    /// instruction with meeting data (e.g. `{{chapters}}`). Unknown placeholders are kept.
+    /// The text is scanned once, so placeholders inside substituted values stay as they are.
+    pub fn with_variables(&self, variables: &HashMap<String, String>) -> Template {
+        let render = |text: &str| {
+            let mut rendered = String::with_capacity(text.len());
+            let mut rest = text;
+            while let Some(open) = rest.find("{{") {
+                let Some(close) = rest[open + 2..].find("}}") else {
+                    break;
+                };
+                let end = open + 2 + close + 2;
+                rendered.push_str(&rest[..open]);
+                match variables.get(&rest[open + 2..end - 2]) {
+                    Some(value) => rendered.push_str(value),
+                    None => rendered.push_str(&rest[open..end]),
+                }
+                rest = &rest[end..];
+            }
+            rendered.push_str(rest);
+            rendered
+        };
+
+        let mut template = self.clone();
+        for section in &mut template.sections {
+            section.instruction = render(&section.instruction);
+            section.item_format = section.item_format.as_deref().map(render);
+            section.example_item_format = section.example_item_format.as_deref().map(render);
+        }
+        template.global_instruction = template.global_instruction.as_deref().map(render);
+        template
     }
 
     /// Generates a clean markdown template structure
@@ -129,6 +220,41 @@ impl Template {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_with_variables() {
+        let template = Template {
+            name: "Chaptered".to_string(),
+            description: "Uses chapters".to_string(),
+            sections: vec![TemplateSection {
+                title: "Agenda".to_string(),
+                instruction: "Follow these chapters:\n{{chapters}}\nKeep {{unknown}} as is".to_string(),
+                format: "list".to_string(),
+                item_format: None,
+                example_item_format: None,
+            }],
+            global_instruction: Some("Chapters: {{chapters}}".to_string()),
+            clinical_safety_rules: None,
+            version: None,
+            updated_at: None,
+        };
+        let variables = HashMap::from([("chapters".to_string(), "- [00:00-05:00] Intro".to_string())]);
+
+        let rendered = template.with_variables(&variables);
+        assert_eq!(
+            rendered.sections[0].instruction,
+            "Follow these chapters:\n- [00:00-05:00] Intro\nKeep {{unknown}} as is"
+        );
+        assert_eq!(rendered.global_instruction.as_deref(), Some("Chapters: - [00:00-05:00] Intro"));
+
+        // Values are inserted as they are, whatever order the variables are in
+        let variables = HashMap::from([
+            ("chapters".to_string(), "see {{markers}}".to_string()),
+            ("markers".to_string(), "- [01:00] Decision".to_string()),
+        ]);
+        let rendered = template.with_variables(&variables);
+        assert_eq!(rendered.global_instruction.as_deref(), Some("Chapters: see {{markers}}"));
+    }
+
     #[test]
     fn test_validate_valid_template() {
         let template = Template {
@@ -0,0 +1,461 @@
+//! Templates authored by the user, stored in the custom templates directory.
+//!
+//! Each save bumps the template's `version` and keeps the previous version under
+//! `.history/<template_id>/v<version>.json`, so earlier versions can be listed and restored.
+//! Templates are shared as a single file (`TemplateExport`) holding the template and its id.
+
+use super::loader::{self, check_template_json, is_valid_template_id};
+use super::types::{Template, TemplateIssue};
+use crate::utils::unique_name;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+const HISTORY_DIR: &str = ".history";
+
+/// Identifies the export file format
+pub const EXPORT_FORMAT: &str = "iqcapture-template";
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// A template as shared between users
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateExport {
+    pub format: String,
+    pub format_version: u32,
+    pub template_id: String,
+    pub exported_at: String,
+    pub template: Template,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserTemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub version: Option<u32>,
+    pub updated_at: Option<String>,
+    /// A built-in, bundled or synced template with the same id is overridden by this one
+    pub overrides_shipped: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVersionInfo {
+    pub version: u32,
+    pub name: String,
+    pub updated_at: Option<String>,
+}
+
+/// Why a user template could not be saved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateSaveError {
+    pub message: String,
+    /// Problems in the template, each pointing to its section
+    #[serde(default)]
+    pub issues: Vec<TemplateIssue>,
+}
+
+impl From<String> for TemplateSaveError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            issues: Vec::new(),
+        }
+    }
+}
+
+impl From<Vec<TemplateIssue>> for TemplateSaveError {
+    fn from(issues: Vec<TemplateIssue>) -> Self {
+        let message = match issues.len() {
+            1 => issues[0].message.clone(),
+            n => format!("{} (and {} more problems)", issues[0].message, n - 1),
+        };
+        Self { message, issues }
+    }
+}
+
+fn user_dir() -> Result<PathBuf, String> {
+    loader::get_custom_templates_dir().ok_or_else(|| "App data directory not available".to_string())
+}
+
+fn check_id(template_id: &str) -> Result<(), String> {
+    if is_valid_template_id(template_id) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid template id '{}': use letters, digits, '_' and '-'",
+            template_id
+        ))
+    }
+}
+
+fn template_path(dir: &Path, template_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", template_id))
+}
+
+fn history_dir(dir: &Path, template_id: &str) -> PathBuf {
+    dir.join(HISTORY_DIR).join(template_id)
+}
+
+fn read(path: &Path) -> Result<Template, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, template: &Template) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(template).map_err(|e| format!("Failed to serialize template: {}", e))?;
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    std::fs::rename(&temp, path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Writes a new version of a template, keeping the current one in the history.
+/// `expected_version` guards against overwriting a version saved elsewhere since the editor
+/// loaded it.
+fn save_in(
+    dir: &Path,
+    template_id: &str,
+    mut template: Template,
+    expected_version: Option<u32>,
+    create: bool,
+) -> Result<Template, TemplateSaveError> {
+    check_id(template_id)?;
+    let issues = template.issues();
+    if !issues.is_empty() {
+        return Err(issues.into());
+    }
+
+    let path = template_path(dir, template_id);
+    let current = if path.is_file() { Some(read(&path)?) } else { None };
+    if create && current.is_some() {
+        return Err(format!("A template with id '{}' already exists", template_id).into());
+    }
+    if let (Some(expected), Some(current)) = (expected_version, &current) {
+        if current.version.unwrap_or(0) != expected {
+            return Err(format!(
+                "Template '{}' was changed elsewhere (now version {}); reload it before saving",
+                template_id,
+                current.version.unwrap_or(0)
+            )
+            .into());
+        }
+    }
+
+    let previous_version = current.as_ref().and_then(|c| c.version).unwrap_or(0);
+    if let Some(current) = &current {
+        let archived = history_dir(dir, template_id).join(format!("v{}.json", previous_version));
+        write(&archived, current)?;
+    }
+    template.version = Some(previous_version + 1);
+    template.updated_at = Some(Utc::now().to_rfc3339());
+    write(&path, &template)?;
+    info!("Saved user template '{}' version {}", template_id, previous_version + 1);
+    Ok(template)
+}
+
+fn list_in(dir: &Path, shipped_ids: &[String]) -> Vec<UserTemplateInfo> {
+    let mut templates: Vec<UserTemplateInfo> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_string_lossy().to_string();
+            let template = read(&path).ok()?;
+            Some(UserTemplateInfo {
+                overrides_shipped: shipped_ids.contains(&id),
+                id,
+                name: template.name,
+                description: template.description,
+                version: template.version,
+                updated_at: template.updated_at,
+            })
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    templates
+}
+
+fn versions_in(dir: &Path, template_id: &str) -> Result<Vec<TemplateVersionInfo>, String> {
+    check_id(template_id)?;
+    let mut versions: Vec<TemplateVersionInfo> = std::fs::read_dir(history_dir(dir, template_id))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let version = name.strip_prefix('v')?.strip_suffix(".json")?.parse().ok()?;
+            let template = read(&entry.path()).ok()?;
+            Some(TemplateVersionInfo {
+                version,
+                name: template.name,
+                updated_at: template.updated_at,
+            })
+        })
+        .collect();
+    if let Ok(current) = read(&template_path(dir, template_id)) {
+        versions.push(TemplateVersionInfo {
+            version: current.version.unwrap_or(0),
+            name: current.name,
+            updated_at: current.updated_at,
+        });
+    }
+    versions.sort_by(|a, b| b.version.cmp(&a.version));
+    Ok(versions)
+}
+
+fn restore_in(dir: &Path, template_id: &str, version: u32) -> Result<Template, TemplateSaveError> {
+    check_id(template_id)?;
+    let archived = history_dir(dir, template_id).join(format!("v{}.json", version));
+    if !archived.is_file() {
+        return Err(format!("Template '{}' has no version {}", template_id, version).into());
+    }
+    let template = read(&archived)?;
+    save_in(dir, template_id, template, None, false)
+}
+
+fn delete_in(dir: &Path, template_id: &str) -> Result<(), String> {
+    check_id(template_id)?;
+    let path = template_path(dir, template_id);
+    if !path.is_file() {
+        return Err(format!("User template '{}' not found", template_id));
+    }
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to delete template '{}': {}", template_id, e))?;
+    let history = history_dir(dir, template_id);
+    if history.is_dir() {
+        std::fs::remove_dir_all(&history)
+            .map_err(|e| format!("Failed to delete the history of template '{}': {}", template_id, e))?;
+    }
+    info!("Deleted user template '{}'", template_id);
+    Ok(())
+}
+
+/// Template ids provided by the app or the template sources (not by the user)
+fn shipped_template_ids() -> Vec<String> {
+    loader::list_template_ids()
+        .into_iter()
+        .filter(|id| loader::has_shipped_template(id))
+        .collect()
+}
+
+pub fn list_user_templates() -> Result<Vec<UserTemplateInfo>, String> {
+    let dir = user_dir()?;
+    Ok(list_in(&dir, &shipped_template_ids()))
+}
+
+pub fn get_user_template(template_id: &str) -> Result<Template, String> {
+    check_id(template_id)?;
+    read(&template_path(&user_dir()?, template_id))
+}
+
+pub fn create_user_template(template_id: &str, template: Template) -> Result<Template, TemplateSaveError> {
+    save_in(&user_dir()?, template_id, template, None, true)
+}
+
+pub fn update_user_template(
+    template_id: &str,
+    template: Template,
+    expected_version: Option<u32>,
+) -> Result<Template, TemplateSaveError> {
+    save_in(&user_dir()?, template_id, template, expected_version, false)
+}
+
+/// Copies any template (built-in, bundled, synced or user) to a new user template
+pub fn duplicate_template(source_id: &str, new_id: &str, new_name: Option<&str>) -> Result<Template, TemplateSaveError> {
+    let mut template = loader::get_template(source_id)?;
+    template.name = match new_name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => format!("{} (copy)", template.name),
+    };
+    template.version = None;
+    template.updated_at = None;
+    save_in(&user_dir()?, new_id, template, None, true)
+}
+
+pub fn delete_user_template(template_id: &str) -> Result<(), String> {
+    delete_in(&user_dir()?, template_id)
+}
+
+pub fn list_versions(template_id: &str) -> Result<Vec<TemplateVersionInfo>, String> {
+    versions_in(&user_dir()?, template_id)
+}
+
+pub fn restore_version(template_id: &str, version: u32) -> Result<Template, TemplateSaveError> {
+    restore_in(&user_dir()?, template_id, version)
+}
+
+/// The shareable single-file form of any template
+pub fn export_template(template_id: &str) -> Result<String, String> {
+    let template = loader::get_template(template_id)?;
+    let export = TemplateExport {
+        format: EXPORT_FORMAT.to_string(),
+        format_version: EXPORT_FORMAT_VERSION,
+        template_id: template_id.to_string(),
+        exported_at: Utc::now().to_rfc3339(),
+        template,
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to serialize template: {}", e))
+}
+
+/// Reads an export file (or a bare template JSON) into its suggested id and template
+fn parse_import(content: &str, fallback_id: &str) -> Result<(String, Template), TemplateSaveError> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("The file is not valid JSON: {}", e))?;
+    if value.get("format").is_none() {
+        return Ok((fallback_id.to_string(), check_template_json(content)?));
+    }
+
+    if value.get("format").and_then(|f| f.as_str()) != Some(EXPORT_FORMAT) {
+        return Err("The file is not an exported template".to_string().into());
+    }
+    let format_version = value.get("format_version").and_then(|v| v.as_u64()).unwrap_or(0);
+    if format_version > u64::from(EXPORT_FORMAT_VERSION) {
+        return Err("The template was exported by a newer version of the app".to_string().into());
+    }
+    let template_id = value
+        .get("template_id")
+        .and_then(|id| id.as_str())
+        .unwrap_or(fallback_id)
+        .to_string();
+    let template_json = value
+        .get("template")
+        .map(|t| t.to_string())
+        .ok_or_else(|| "The export file has no template".to_string())?;
+    Ok((template_id, check_template_json(&template_json)?))
+}
+
+/// Id for an imported template that does not replace an existing user template
+fn free_id(dir: &Path, template_id: &str) -> String {
+    unique_name(template_id, |id| template_path(dir, id).exists())
+}
+
+fn import_in(
+    dir: &Path,
+    content: &str,
+    fallback_id: &str,
+    template_id: Option<&str>,
+    overwrite: bool,
+) -> Result<(String, Template), TemplateSaveError> {
+    let (suggested_id, template) = parse_import(content, fallback_id)?;
+    let requested = template_id.map(str::to_string).unwrap_or(suggested_id);
+    check_id(&requested)?;
+    let target_id = if overwrite { requested } else { free_id(dir, &requested) };
+    // Numbered as a new version in this library; the history keeps a replaced template
+    let saved = save_in(dir, &target_id, template, None, false)?;
+    Ok((target_id, saved))
+}
+
+/// Imports an export file (or a bare template JSON file) as a user template. Without
+/// `overwrite`, an existing user template with the same id is kept and the import gets a new id.
+pub fn import_template(
+    source: &Path,
+    template_id: Option<&str>,
+    overwrite: bool,
+) -> Result<(String, Template), TemplateSaveError> {
+    let content =
+        std::fs::read_to_string(source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    let fallback_id: String = source
+        .file_stem()
+        .map(|s| s.to_string_lossy().trim_end_matches(".template").to_string())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    import_in(&user_dir()?, &content, &fallback_id, template_id, overwrite)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::templates::TemplateSection;
+
+    fn template(name: &str, instruction: &str) -> Template {
+        Template {
+            name: name.to_string(),
+            description: "Weekly team retrospective".to_string(),
+            sections: vec![TemplateSection {
+                title: "Went well".to_string(),
+                instruction: instruction.to_string(),
+                format: "list".to_string(),
+                item_format: None,
+                example_item_format: None,
+            }],
+            global_instruction: None,
+            clinical_safety_rules: None,
+            version: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_save_versions_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let v1 = save_in(dir, "retro", template("Retro", "List wins"), None, true).unwrap();
+        assert_eq!(v1.version, Some(1));
+        assert!(save_in(dir, "retro", template("Retro", "Again"), None, true).is_err());
+
+        let v2 = save_in(dir, "retro", template("Retro", "List wins by person"), Some(1), false).unwrap();
+        assert_eq!(v2.version, Some(2));
+        // Saved from an editor that loaded version 1
+        let stale = save_in(dir, "retro", template("Retro", "Stale"), Some(1), false).unwrap_err();
+        assert!(stale.message.contains("changed elsewhere"));
+
+        let versions: Vec<u32> = versions_in(dir, "retro").unwrap().iter().map(|v| v.version).collect();
+        assert_eq!(versions, vec![2, 1]);
+        let v3 = restore_in(dir, "retro", 1).unwrap();
+        assert_eq!((v3.version, v3.sections[0].instruction.as_str()), (Some(3), "List wins"));
+
+        let listed = list_in(dir, &["retro".to_string()]);
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].overrides_shipped);
+
+        delete_in(dir, "retro").unwrap();
+        assert!(list_in(dir, &[]).is_empty());
+        assert!(!history_dir(dir, "retro").exists());
+        assert!(delete_in(dir, "../retro").is_err());
+    }
+
+    #[test]
+    fn test_save_reports_failing_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let error = save_in(dir.path(), "retro", template("Retro", ""), None, true).unwrap_err();
+        assert_eq!(error.issues.len(), 1);
+        assert_eq!(error.issues[0].section_index, Some(0));
+        assert_eq!(error.issues[0].field, "instruction");
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let export = TemplateExport {
+            format: EXPORT_FORMAT.to_string(),
+            format_version: EXPORT_FORMAT_VERSION,
+            template_id: "retro".to_string(),
+            exported_at: Utc::now().to_rfc3339(),
+            template: template("Retro", "List wins"),
+        };
+        let content = serde_json::to_string(&export).unwrap();
+
+        let (id, imported) = import_in(dir, &content, "file", None, false).unwrap();
+        assert_eq!((id.as_str(), imported.version), ("retro", Some(1)));
+        // Importing again keeps the existing template
+        let (id, _) = import_in(dir, &content, "file", None, false).unwrap();
+        assert_eq!(id, "retro_2");
+        let (id, replaced) = import_in(dir, &content, "file", None, true).unwrap();
+        assert_eq!((id.as_str(), replaced.version), ("retro", Some(2)));
+
+        // A bare template file takes its id from the file name
+        let bare = serde_json::to_string(&template("Bare", "List")).unwrap();
+        assert_eq!(import_in(dir, &bare, "shared_retro", None, false).unwrap().0, "shared_retro");
+
+        let newer = content.replace("\"format_version\":1", "\"format_version\":9");
+        assert!(import_in(dir, &newer, "file", None, false).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use super::engine::{self, ConflictResolution, SyncReport};
+use super::protocol::{RecordKind, SyncRecord};
+use super::SyncSettings;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::sync::SyncRepository;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use tauri::{AppHandle, Runtime};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncSettingsView {
+    pub settings: SyncSettings,
+    /// Whether an access token is stored (the token itself is not returned)
+    pub has_token: bool,
+    pub device_id: String,
+    /// Result of the last sync (background or on demand)
+    pub last_report: Option<SyncReport>,
+}
+
+/// A summary or notes changed on this and another device, with both versions
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncConflictView {
+    pub kind: RecordKind,
+    pub meeting_id: String,
+    pub meeting_title: Option<String>,
+    /// This device's version (None if it has none any more)
+    pub local_content: Option<Value>,
+    pub remote_content: Option<Value>,
+    pub remote_modified_at: DateTime<Utc>,
+    pub remote_modified_by: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[tauri::command]
+pub async fn api_get_sync_settings<R: Runtime>(app: AppHandle<R>) -> Result<SyncSettingsView, String> {
+    Ok(SyncSettingsView {
+        settings: super::load_settings(&app),
+        has_token: crate::secrets::get_secret(super::TOKEN_SECRET)?.is_some(),
+        device_id: super::device_id(&app)?,
+        last_report: super::last_report(&app),
+    })
+}
+
+/// Saves the sync settings; `token` replaces the stored access token when given (empty removes it)
+#[tauri::command]
+pub async fn api_save_sync_settings<R: Runtime>(
+    app: AppHandle<R>,
+    mut settings: SyncSettings,
+    token: Option<String>,
+) -> Result<SyncSettings, String> {
+    if !settings.server_url.trim().is_empty() {
+        settings.server_url = super::normalize_server_url(&settings.server_url)?;
+    } else if settings.enabled {
+        return Err("A sync server URL is required to enable sync".to_string());
+    } else {
+        settings.server_url.clear();
+    }
+    if settings.interval_minutes == 0 {
+        return Err("The sync interval must be at least one minute".to_string());
+    }
+    if let Some(token) = token {
+        crate::secrets::set_secret(super::TOKEN_SECRET, token.trim())?;
+    }
+    super::save_settings(&app, &settings)?;
+    info!(
+        "Saved sync settings (enabled: {}, audio: {}, every {} minutes)",
+        settings.enabled, settings.include_audio, settings.interval_minutes
+    );
+    Ok(settings)
+}
+
+/// Syncs now with the configured server
+#[tauri::command]
+pub async fn api_sync_now<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<SyncReport, String> {
+    super::run_sync(&app, state.db_manager.pool()).await
+}
+
+#[tauri::command]
+pub async fn api_get_sync_conflicts(state: tauri::State<'_, AppState>) -> Result<Vec<SyncConflictView>, String> {
+    let pool = state.db_manager.pool();
+    let conflicts = SyncRepository::get_conflicts(pool)
+        .await
+        .map_err(|e| format!("Failed to load sync conflicts: {}", e))?;
+    let titles: HashMap<String, String> = MeetingsRepository::get_retention_info(pool)
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?
+        .into_iter()
+        .map(|meeting| (meeting.id, meeting.title))
+        .collect();
+
+    let mut views = Vec::with_capacity(conflicts.len());
+    for conflict in conflicts {
+        let Some(kind) = RecordKind::parse(&conflict.kind) else {
+            continue;
+        };
+        let remote: SyncRecord = match serde_json::from_str(&conflict.remote_record) {
+            Ok(remote) => remote,
+            Err(e) => {
+                warn!("Skipping unreadable sync conflict for meeting {}: {}", conflict.meeting_id, e);
+                continue;
+            }
+        };
+        views.push(SyncConflictView {
+            kind,
+            meeting_title: titles.get(&conflict.meeting_id).cloned(),
+            local_content: conflict
+                .local_content
+                .as_deref()
+                .and_then(|json| serde_json::from_str(json).ok()),
+            remote_content: remote.content,
+            remote_modified_at: remote.modified_at,
+            remote_modified_by: remote.modified_by,
+            detected_at: conflict.detected_at,
+            meeting_id: conflict.meeting_id,
+        });
+    }
+    Ok(views)
+}
+
+/// Settles a conflict; the chosen version is sent to the other devices with the next sync
+#[tauri::command]
+pub async fn api_resolve_sync_conflict<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    kind: RecordKind,
+    meeting_id: String,
+    resolution: ConflictResolution,
+) -> Result<(), String> {
+    let device_id = super::device_id(&app)?;
+    engine::resolve_conflict(state.db_manager.pool(), &device_id, kind, &meeting_id, resolution).await
+}
@@ -0,0 +1,1022 @@
+//! Sync of the local library with a server.
+//!
+//! A run scans the library for local changes (by content hash), pulls the server's change
+//! feed, uploads audio and pushes local changes, then downloads audio of meetings changed
+//! elsewhere. Each record carries a revision vector; concurrent changes are settled by
+//! last-writer-wins, except summaries and notes, which wait in `sync_conflicts` until the user
+//! picks a version.
+
+use super::protocol::{Causality, PushOutcome, RecordKind, RevisionVector, SyncRecord};
+use super::records::{self, AudioContent};
+use super::transport::SyncTransport;
+use crate::database::models::{MeetingRetentionInfo, SyncRecordRow};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::sync::SyncRepository;
+use crate::encryption::files as library_files;
+use crate::utils::unique_path;
+use chrono::Utc;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Audio is transferred in chunks of this size; an interrupted transfer resumes at a chunk
+pub const CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+const PUSH_BATCH: usize = 100;
+const PULL_BATCH: usize = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub started_at: Option<chrono::DateTime<Utc>>,
+    pub finished_at: Option<chrono::DateTime<Utc>>,
+    pub pushed: usize,
+    pub pulled: usize,
+    /// Concurrent changes settled by last-writer-wins
+    pub auto_resolved: usize,
+    /// Summaries and notes waiting for the user
+    pub conflicts: usize,
+    pub uploaded_audio: usize,
+    pub downloaded_audio: usize,
+    /// Records or files that failed; the rest of the run went on
+    pub errors: Vec<String>,
+}
+
+/// Size and modification time of a meeting's audio file with its hash, so unchanged files are
+/// not hashed again (stored in `sync_records.source_stamp`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct AudioStamp {
+    file_size: u64,
+    modified_ms: i64,
+    audio: AudioContent,
+}
+
+type RecordKey = (RecordKind, String);
+
+fn db_error(e: sqlx::Error) -> String {
+    format!("Database error: {}", e)
+}
+
+fn parse_revision(row: &SyncRecordRow) -> RevisionVector {
+    serde_json::from_str(&row.revision).unwrap_or_default()
+}
+
+fn revision_json(revision: &RevisionVector) -> String {
+    serde_json::to_string(revision).unwrap_or_else(|_| "{}".to_string())
+}
+
+fn file_stamp(path: &Path) -> Option<(u64, i64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_millis() as i64;
+    Some((metadata.len(), modified))
+}
+
+/// Folder name for a meeting that arrives without one, unique under `root`
+fn new_meeting_folder(root: &Path, title: &str, meeting_id: &str) -> PathBuf {
+    let safe: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let safe = safe.trim();
+    let short_id: String = meeting_id.trim_start_matches("meeting-").chars().take(8).collect();
+    let base = if safe.is_empty() {
+        format!("synced_{}", short_id)
+    } else {
+        format!("{}_{}", safe, short_id)
+    };
+    unique_path(root, &base)
+}
+
+/// Uploads a file in chunks, resuming where the server left off (also after a failed chunk)
+pub async fn upload_file(transport: &dyn SyncTransport, path: &Path, sha256: &str, size: u64) -> Result<bool, String> {
+    let mut session = transport.begin_upload(sha256, size).await?;
+    if session.complete {
+        return Ok(false);
+    }
+    let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut offset = session.received;
+    let mut retried = false;
+    while offset < size {
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE.min(size - offset) as usize);
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| (&mut file).take(CHUNK_SIZE).read_to_end(&mut chunk))
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if chunk.is_empty() {
+            return Err(format!("{} is shorter than expected", path.display()));
+        }
+        match transport.upload_chunk(&session.upload_id, offset, chunk).await {
+            Ok(receipt) => {
+                offset = receipt.received;
+                retried = false;
+            }
+            Err(e) if !retried => {
+                warn!("Audio upload chunk failed, resuming: {}", e);
+                session = transport.begin_upload(sha256, size).await?;
+                offset = session.received;
+                retried = true;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    transport.finish_upload(&session.upload_id).await?;
+    Ok(true)
+}
+
+/// Downloads an uploaded file to `target`, resuming a partial download left next to it
+pub async fn download_file(transport: &dyn SyncTransport, audio: &AudioContent, target: &Path) -> Result<(), String> {
+    let partial = target.with_file_name(format!(
+        ".{}.partial",
+        target.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+    ));
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&partial)
+        .map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+    let mut offset = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if offset > audio.size {
+        file.set_len(0).map_err(|e| e.to_string())?;
+        offset = 0;
+    }
+    while offset < audio.size {
+        let bytes = transport
+            .download_chunk(&audio.sha256, offset, CHUNK_SIZE.min(audio.size - offset))
+            .await?;
+        if bytes.is_empty() {
+            return Err("The server sent no data for the audio".to_string());
+        }
+        file.write_all(&bytes).map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+        offset += bytes.len() as u64;
+    }
+    file.flush().map_err(|e| e.to_string())?;
+    drop(file);
+
+    let (sha256, _) = records::hash_file(&partial)?;
+    if sha256 != audio.sha256 {
+        let _ = std::fs::remove_file(&partial);
+        return Err("Downloaded audio does not match its checksum".to_string());
+    }
+    std::fs::rename(&partial, target).map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+}
+
+pub struct SyncEngine<'a> {
+    pub pool: &'a SqlitePool,
+    pub transport: &'a dyn SyncTransport,
+    pub device_id: &'a str,
+    /// Key of the change feed position (the server URL)
+    pub server_url: &'a str,
+    pub include_audio: bool,
+    /// Where folders are created for meetings whose audio arrives from another device
+    pub recordings_root: PathBuf,
+}
+
+impl SyncEngine<'_> {
+    pub async fn run(&self) -> Result<SyncReport, String> {
+        let mut report = SyncReport {
+            started_at: Some(Utc::now()),
+            ..Default::default()
+        };
+
+        let cursor = match SyncRepository::get_cursor(self.pool, self.server_url).await.map_err(db_error)? {
+            Some(cursor) => cursor.max(0) as u64,
+            None => {
+                // A new server gets everything
+                let marked = SyncRepository::mark_all_dirty(self.pool).await.map_err(db_error)?;
+                info!("First sync with {}: {} records to send", self.server_url, marked);
+                0
+            }
+        };
+
+        self.scan_local().await?;
+        let mut downloads = Vec::new();
+        let cursor = self.pull(cursor, &mut report, &mut downloads).await?;
+        SyncRepository::save_cursor(self.pool, self.server_url, cursor as i64)
+            .await
+            .map_err(db_error)?;
+        self.push(&mut report, &mut downloads).await?;
+        if self.include_audio {
+            self.download_audio(downloads, &mut report).await;
+        }
+
+        report.conflicts = SyncRepository::get_conflicts(self.pool).await.map_err(db_error)?.len();
+        report.finished_at = Some(Utc::now());
+        Ok(report)
+    }
+
+    async fn load_rows(&self) -> Result<HashMap<RecordKey, SyncRecordRow>, String> {
+        Ok(SyncRepository::get_records(self.pool)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .filter_map(|row| Some(((RecordKind::parse(&row.kind)?, row.meeting_id.clone()), row)))
+            .collect())
+    }
+
+    async fn meetings(&self) -> Result<HashMap<String, MeetingRetentionInfo>, String> {
+        Ok(MeetingsRepository::get_retention_info(self.pool)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(|meeting| (meeting.id.clone(), meeting))
+            .collect())
+    }
+
+    /// Audio record content of a meeting with the stamp to cache, hashing the file only when
+    /// it changed since the last scan
+    async fn local_audio(
+        &self,
+        meeting: &MeetingRetentionInfo,
+        row: Option<&SyncRecordRow>,
+    ) -> Result<Option<(Value, AudioStamp)>, String> {
+        let Some(folder) = meeting.folder_path.as_deref().map(Path::new).filter(|f| f.is_dir()) else {
+            return Ok(None);
+        };
+        let Some(path) = records::main_audio_file(folder) else {
+            return Ok(None);
+        };
+        let Some((file_size, modified_ms)) = file_stamp(&path) else {
+            return Ok(None);
+        };
+        let cached = row
+            .and_then(|row| row.source_stamp.as_deref())
+            .and_then(|json| serde_json::from_str::<AudioStamp>(json).ok())
+            .filter(|stamp| stamp.file_size == file_size && stamp.modified_ms == modified_ms);
+        let stamp = match cached {
+            Some(stamp) => stamp,
+            None => {
+                let hashed = path.clone();
+                let (sha256, size) = tokio::task::spawn_blocking(move || records::hash_file(&hashed))
+                    .await
+                    .map_err(|e| format!("Audio hashing task join error: {}", e))??;
+                let file_name = library_files::plaintext_path(&path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                AudioStamp {
+                    file_size,
+                    modified_ms,
+                    audio: AudioContent { file_name, size, sha256 },
+                }
+            }
+        };
+        let content = serde_json::to_value(&stamp.audio).map_err(|e| e.to_string())?;
+        Ok(Some((content, stamp)))
+    }
+
+    /// Current content of a local record (None if there is none)
+    async fn local_content(
+        &self,
+        kind: RecordKind,
+        meeting: Option<&MeetingRetentionInfo>,
+        row: Option<&SyncRecordRow>,
+    ) -> Result<Option<Value>, String> {
+        let Some(meeting) = meeting else {
+            return Ok(None);
+        };
+        match kind {
+            RecordKind::Audio => Ok(self.local_audio(meeting, row).await?.map(|(content, _)| content)),
+            _ => records::load_content(self.pool, kind, meeting).await,
+        }
+    }
+
+    /// Records local changes since the last run as new revisions of this device
+    async fn scan_local(&self) -> Result<(), String> {
+        let rows = self.load_rows().await?;
+        let meetings = self.meetings().await?;
+        let now = Utc::now();
+        let mut present: HashSet<RecordKey> = HashSet::new();
+
+        for meeting in meetings.values() {
+            for kind in RecordKind::ALL {
+                let key = (kind, meeting.id.clone());
+                let row = rows.get(&key);
+                let (content, stamp) = match kind {
+                    RecordKind::Audio if !self.include_audio => continue,
+                    RecordKind::Audio => match self.local_audio(meeting, row).await? {
+                        Some((content, stamp)) => (content, Some(stamp)),
+                        None => continue,
+                    },
+                    _ => match records::load_content(self.pool, kind, meeting).await? {
+                        Some(content) => (content, None),
+                        None => continue,
+                    },
+                };
+                present.insert(key);
+                let hash = records::content_hash(&content);
+                let source_stamp = stamp.and_then(|s| serde_json::to_string(&s).ok());
+
+                let updated = match row {
+                    Some(row) if !row.deleted && row.content_hash.as_deref() == Some(hash.as_str()) => {
+                        if source_stamp.is_none() || row.source_stamp == source_stamp {
+                            continue;
+                        }
+                        // Same audio, only the cached stamp changed
+                        SyncRecordRow { source_stamp, ..row.clone() }
+                    }
+                    Some(row) => {
+                        let mut revision = parse_revision(row);
+                        revision.increment(self.device_id);
+                        SyncRecordRow {
+                            revision: revision_json(&revision),
+                            content_hash: Some(hash),
+                            modified_at: now,
+                            modified_by: self.device_id.to_string(),
+                            deleted: false,
+                            dirty: true,
+                            source_stamp,
+                            ..row.clone()
+                        }
+                    }
+                    None => {
+                        let mut revision = RevisionVector::default();
+                        revision.increment(self.device_id);
+                        SyncRecordRow {
+                            kind: kind.as_str().to_string(),
+                            meeting_id: meeting.id.clone(),
+                            revision: revision_json(&revision),
+                            content_hash: Some(hash),
+                            modified_at: now,
+                            modified_by: self.device_id.to_string(),
+                            deleted: false,
+                            dirty: true,
+                            source_stamp,
+                        }
+                    }
+                };
+                SyncRepository::save_record(self.pool, &updated).await.map_err(db_error)?;
+            }
+        }
+
+        // Records that disappeared locally become deletions
+        for (key, row) in &rows {
+            if row.deleted || present.contains(key) {
+                continue;
+            }
+            let (kind, meeting_id) = key;
+            if *kind == RecordKind::Audio && (!self.include_audio || row.source_stamp.is_none()) {
+                // Audio that was never on this device (not downloaded yet) was not deleted here
+                continue;
+            }
+            if *kind != RecordKind::Meeting && !meetings.contains_key(meeting_id) {
+                // Goes with the meeting's deletion
+                SyncRepository::delete_record(self.pool, kind.as_str(), meeting_id)
+                    .await
+                    .map_err(db_error)?;
+                continue;
+            }
+            let mut revision = parse_revision(row);
+            revision.increment(self.device_id);
+            let tombstone = SyncRecordRow {
+                revision: revision_json(&revision),
+                content_hash: None,
+                modified_at: now,
+                modified_by: self.device_id.to_string(),
+                deleted: true,
+                dirty: true,
+                source_stamp: None,
+                ..row.clone()
+            };
+            SyncRepository::save_record(self.pool, &tombstone).await.map_err(db_error)?;
+        }
+        Ok(())
+    }
+
+    async fn pull(
+        &self,
+        mut cursor: u64,
+        report: &mut SyncReport,
+        downloads: &mut Vec<(String, AudioContent)>,
+    ) -> Result<u64, String> {
+        loop {
+            let batch = self.transport.changes(cursor, PULL_BATCH).await?;
+            let mut remote_records = batch.records;
+            // Meetings before their transcripts, summaries and notes
+            remote_records.sort_by_key(|r| (r.kind != RecordKind::Meeting, r.seq));
+            for remote in remote_records {
+                if let Err(e) = self.reconcile(remote, report, downloads).await {
+                    report.errors.push(e);
+                }
+            }
+            cursor = batch.cursor;
+            if !batch.has_more {
+                return Ok(cursor);
+            }
+        }
+    }
+
+    /// Brings a server record together with the local one
+    async fn reconcile(
+        &self,
+        remote: SyncRecord,
+        report: &mut SyncReport,
+        downloads: &mut Vec<(String, AudioContent)>,
+    ) -> Result<(), String> {
+        let kind = remote.kind;
+        let row = SyncRepository::get_record(self.pool, kind.as_str(), &remote.meeting_id)
+            .await
+            .map_err(db_error)?;
+        let Some(row) = row else {
+            return self.apply_remote(&remote, remote.revision.clone(), false, report, downloads).await;
+        };
+        let local_revision = parse_revision(&row);
+
+        match remote.revision.compare(&local_revision) {
+            Causality::Equal | Causality::Before => {
+                if row.dirty && remote.revision == local_revision {
+                    // The server already has this version (e.g. an earlier push whose reply was lost)
+                    SyncRepository::save_record(self.pool, &SyncRecordRow { dirty: false, ..row })
+                        .await
+                        .map_err(db_error)?;
+                }
+                Ok(())
+            }
+            Causality::After => {
+                SyncRepository::delete_conflict(self.pool, kind.as_str(), &remote.meeting_id)
+                    .await
+                    .map_err(db_error)?;
+                self.apply_remote(&remote, remote.revision.clone(), false, report, downloads).await
+            }
+            Causality::Concurrent => {
+                let merged = local_revision.merged(&remote.revision);
+                let meetings = self.meetings().await?;
+                let local_content = self
+                    .local_content(kind, meetings.get(&remote.meeting_id), Some(&row))
+                    .await?;
+                let same_content = row.deleted == remote.deleted
+                    && local_content.as_ref().map(records::content_hash)
+                        == remote.content.as_ref().map(records::content_hash);
+                if same_content {
+                    // Both sides made the same change; push the merged revision
+                    let converged = SyncRecordRow {
+                        revision: revision_json(&merged),
+                        dirty: true,
+                        ..row
+                    };
+                    return SyncRepository::save_record(self.pool, &converged).await.map_err(db_error);
+                }
+
+                if kind.needs_manual_resolution() && !row.deleted && !remote.deleted {
+                    let local_json = local_content.map(|c| c.to_string());
+                    let remote_json = serde_json::to_string(&remote).map_err(|e| e.to_string())?;
+                    SyncRepository::save_conflict(self.pool, kind.as_str(), &remote.meeting_id, local_json.as_deref(), &remote_json)
+                        .await
+                        .map_err(db_error)?;
+                    info!("Sync conflict on the {} of meeting {}", kind.as_str(), remote.meeting_id);
+                    return Ok(());
+                }
+
+                report.auto_resolved += 1;
+                let local = SyncRecord {
+                    kind,
+                    meeting_id: row.meeting_id.clone(),
+                    revision: local_revision,
+                    modified_at: row.modified_at,
+                    modified_by: row.modified_by.clone(),
+                    deleted: row.deleted,
+                    content: None,
+                    seq: 0,
+                };
+                if remote.wins_over(&local) {
+                    self.apply_remote(&remote, merged, true, report, downloads).await
+                } else {
+                    let kept = SyncRecordRow {
+                        revision: revision_json(&merged),
+                        dirty: true,
+                        ..row
+                    };
+                    SyncRepository::save_record(self.pool, &kept).await.map_err(db_error)
+                }
+            }
+        }
+    }
+
+    /// Writes a server record locally and records `revision` for it; `dirty` pushes the
+    /// (merged) revision back so other devices converge
+    async fn apply_remote(
+        &self,
+        remote: &SyncRecord,
+        revision: RevisionVector,
+        dirty: bool,
+        report: &mut SyncReport,
+        downloads: &mut Vec<(String, AudioContent)>,
+    ) -> Result<(), String> {
+        let kind = remote.kind;
+        let meetings = self.meetings().await?;
+        let meeting_exists = meetings.contains_key(&remote.meeting_id);
+        if kind != RecordKind::Meeting && !meeting_exists && !remote.deleted {
+            warn!(
+                "Skipping the {} of meeting {}: the meeting is not on this device",
+                kind.as_str(),
+                remote.meeting_id
+            );
+            return Ok(());
+        }
+
+        if kind == RecordKind::Audio {
+            if !remote.deleted && self.include_audio {
+                let audio: AudioContent = remote
+                    .content
+                    .clone()
+                    .map(serde_json::from_value)
+                    .transpose()
+                    .map_err(|e| format!("Invalid audio record: {}", e))?
+                    .ok_or_else(|| "The audio record has no content".to_string())?;
+                downloads.push((remote.meeting_id.clone(), audio));
+            }
+            // Deleted audio stays on devices that have it
+        } else if meeting_exists || !remote.deleted {
+            records::apply_content(self.pool, kind, &remote.meeting_id, remote.deleted, remote.content.as_ref()).await?;
+        }
+
+        let content_hash = match kind {
+            // Hashes as the next scan will see the applied content
+            RecordKind::Audio => remote.content.as_ref().map(records::content_hash),
+            _ => {
+                let meetings = self.meetings().await?;
+                self.local_content(kind, meetings.get(&remote.meeting_id), None)
+                    .await?
+                    .as_ref()
+                    .map(records::content_hash)
+            }
+        };
+        let existing = SyncRepository::get_record(self.pool, kind.as_str(), &remote.meeting_id)
+            .await
+            .map_err(db_error)?;
+        let row = SyncRecordRow {
+            kind: kind.as_str().to_string(),
+            meeting_id: remote.meeting_id.clone(),
+            revision: revision_json(&revision),
+            content_hash,
+            modified_at: remote.modified_at,
+            modified_by: remote.modified_by.clone(),
+            deleted: remote.deleted,
+            dirty,
+            source_stamp: existing.and_then(|r| r.source_stamp),
+        };
+        SyncRepository::save_record(self.pool, &row).await.map_err(db_error)?;
+        report.pulled += 1;
+        Ok(())
+    }
+
+    async fn push(&self, report: &mut SyncReport, downloads: &mut Vec<(String, AudioContent)>) -> Result<(), String> {
+        let conflicts: HashSet<RecordKey> = SyncRepository::get_conflicts(self.pool)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .filter_map(|c| Some((RecordKind::parse(&c.kind)?, c.meeting_id)))
+            .collect();
+        let mut dirty: Vec<(RecordKey, SyncRecordRow)> = self
+            .load_rows()
+            .await?
+            .into_iter()
+            .filter(|(key, row)| row.dirty && !conflicts.contains(key))
+            .filter(|((kind, _), _)| *kind != RecordKind::Audio || self.include_audio)
+            .collect();
+        dirty.sort_by(|(a, _), (b, _)| (a.0 != RecordKind::Meeting, &a.1, a.0).cmp(&(b.0 != RecordKind::Meeting, &b.1, b.0)));
+        let meetings = self.meetings().await?;
+
+        let mut records = Vec::new();
+        for ((kind, meeting_id), row) in dirty {
+            let content = if row.deleted {
+                None
+            } else {
+                match self.local_content(kind, meetings.get(&meeting_id), Some(&row)).await? {
+                    Some(content) => Some(content),
+                    // Gone since the scan; the next run sends the deletion
+                    None => continue,
+                }
+            };
+            if kind == RecordKind::Audio && !row.deleted {
+                if let Err(e) = self.upload_audio(&meetings[&meeting_id], content.as_ref(), report).await {
+                    report.errors.push(format!("Audio of meeting {}: {}", meeting_id, e));
+                    continue;
+                }
+            }
+            records.push(SyncRecord {
+                kind,
+                meeting_id,
+                revision: parse_revision(&row),
+                modified_at: row.modified_at,
+                modified_by: row.modified_by.clone(),
+                deleted: row.deleted,
+                content,
+                seq: 0,
+            });
+        }
+
+        for batch in records.chunks(PUSH_BATCH) {
+            let response = self.transport.push(batch.to_vec()).await?;
+            for (record, outcome) in batch.iter().zip(response.outcomes) {
+                match outcome {
+                    PushOutcome::Accepted { .. } => {
+                        if let Some(row) = SyncRepository::get_record(self.pool, record.kind.as_str(), &record.meeting_id)
+                            .await
+                            .map_err(db_error)?
+                        {
+                            // Unless it changed again meanwhile
+                            if parse_revision(&row) == record.revision {
+                                SyncRepository::save_record(self.pool, &SyncRecordRow { dirty: false, ..row })
+                                    .await
+                                    .map_err(db_error)?;
+                            }
+                        }
+                        report.pushed += 1;
+                    }
+                    PushOutcome::Rejected { current } => {
+                        if let Err(e) = self.reconcile(current, report, downloads).await {
+                            report.errors.push(e);
+                        }
+                    }
+                    PushOutcome::Invalid { message } => {
+                        report.errors.push(format!(
+                            "The server refused the {} of meeting {}: {}",
+                            record.kind.as_str(),
+                            record.meeting_id,
+                            message
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn upload_audio(
+        &self,
+        meeting: &MeetingRetentionInfo,
+        content: Option<&Value>,
+        report: &mut SyncReport,
+    ) -> Result<(), String> {
+        let audio: AudioContent = content
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "no audio".to_string())?;
+        let folder = meeting.folder_path.as_deref().map(Path::new).ok_or("no folder")?;
+        let path = records::main_audio_file(folder).ok_or("audio file missing")?;
+        // An encrypted file is uploaded from a plaintext copy
+        let _plain_copy;
+        let upload_path = if library_files::is_encrypted_path(&path) {
+            let copy = library_files::decrypted_copy(&path)?;
+            let copy_path = copy.to_path_buf();
+            _plain_copy = copy;
+            copy_path
+        } else {
+            path
+        };
+        if upload_file(self.transport, &upload_path, &audio.sha256, audio.size).await? {
+            report.uploaded_audio += 1;
+        }
+        Ok(())
+    }
+
+    async fn download_audio(&self, downloads: Vec<(String, AudioContent)>, report: &mut SyncReport) {
+        for (meeting_id, audio) in downloads {
+            if let Err(e) = self.download_meeting_audio(&meeting_id, &audio).await {
+                report.errors.push(format!("Audio of meeting {}: {}", meeting_id, e));
+            } else {
+                report.downloaded_audio += 1;
+            }
+        }
+    }
+
+    async fn download_meeting_audio(&self, meeting_id: &str, audio: &AudioContent) -> Result<(), String> {
+        let meetings = self.meetings().await?;
+        let meeting = meetings.get(meeting_id).ok_or("the meeting is not on this device")?;
+        if audio.file_name.is_empty() || audio.file_name.contains(['/', '\\']) || audio.file_name.starts_with('.') {
+            return Err(format!("invalid audio file name {}", audio.file_name));
+        }
+
+        let existing_folder = meeting.folder_path.as_deref().map(PathBuf::from).filter(|f| f.is_dir());
+        if let Some(folder) = &existing_folder {
+            if let Some(local) = records::main_audio_file(folder) {
+                let local_path = local.clone();
+                let (sha256, _) = tokio::task::spawn_blocking(move || records::hash_file(&local_path))
+                    .await
+                    .map_err(|e| e.to_string())??;
+                if sha256 == audio.sha256 {
+                    return Ok(());
+                }
+            }
+        }
+        let folder = match existing_folder {
+            Some(folder) => folder,
+            None => {
+                let folder = new_meeting_folder(&self.recordings_root, &meeting.title, meeting_id);
+                std::fs::create_dir_all(&folder).map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+                MeetingsRepository::update_folder_path(self.pool, meeting_id, &folder.to_string_lossy())
+                    .await
+                    .map_err(db_error)?;
+                folder
+            }
+        };
+
+        let target = folder.join(&audio.file_name);
+        download_file(self.transport, audio, &target).await?;
+        // An encrypted library keeps the audio encrypted; the stale encrypted copy goes
+        let _ = std::fs::remove_file(library_files::encrypted_path(&target));
+        library_files::seal_folder(&folder);
+        info!("Downloaded audio of meeting {} ({} bytes)", meeting_id, audio.size);
+
+        // Remember the file as local so the next scan neither re-hashes nor re-sends it
+        let stored = records::main_audio_file(&folder).filter(|p| library_files::plaintext_path(p) == target);
+        if let (Some(path), Some(row)) = (
+            stored,
+            SyncRepository::get_record(self.pool, RecordKind::Audio.as_str(), meeting_id)
+                .await
+                .map_err(db_error)?,
+        ) {
+            if let Some((file_size, modified_ms)) = file_stamp(&path) {
+                let stamp = AudioStamp { file_size, modified_ms, audio: audio.clone() };
+                let row = SyncRecordRow {
+                    source_stamp: serde_json::to_string(&stamp).ok(),
+                    ..row
+                };
+                SyncRepository::save_record(self.pool, &row).await.map_err(db_error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What the user chose for a conflicting summary or notes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "choice", rename_all = "snake_case")]
+pub enum ConflictResolution {
+    KeepLocal,
+    KeepRemote,
+    /// Content combined by the user, in the record's format
+    Merged { content: Value },
+}
+
+/// Settles a conflict with the user's choice as a new revision of this device, sent with the
+/// next sync
+pub async fn resolve_conflict(
+    pool: &SqlitePool,
+    device_id: &str,
+    kind: RecordKind,
+    meeting_id: &str,
+    resolution: ConflictResolution,
+) -> Result<(), String> {
+    let conflict = SyncRepository::get_conflict(pool, kind.as_str(), meeting_id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| "This conflict no longer exists".to_string())?;
+    let remote: SyncRecord =
+        serde_json::from_str(&conflict.remote_record).map_err(|e| format!("Invalid conflict record: {}", e))?;
+    let row = SyncRepository::get_record(pool, kind.as_str(), meeting_id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| "The record is not tracked for sync".to_string())?;
+
+    match &resolution {
+        ConflictResolution::KeepLocal => {}
+        ConflictResolution::KeepRemote => {
+            records::apply_content(pool, kind, meeting_id, remote.deleted, remote.content.as_ref()).await?;
+        }
+        ConflictResolution::Merged { content } => {
+            records::validate_content(kind, content)?;
+            records::apply_content(pool, kind, meeting_id, false, Some(content)).await?;
+        }
+    }
+
+    let meeting = MeetingsRepository::get_retention_info(pool)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .find(|m| m.id == meeting_id);
+    let content = match &meeting {
+        Some(meeting) => records::load_content(pool, kind, meeting).await?,
+        None => None,
+    };
+    let mut revision = parse_revision(&row).merged(&remote.revision);
+    revision.increment(device_id);
+    let resolved = SyncRecordRow {
+        revision: revision_json(&revision),
+        content_hash: content.as_ref().map(records::content_hash),
+        modified_at: Utc::now(),
+        modified_by: device_id.to_string(),
+        deleted: content.is_none(),
+        dirty: true,
+        ..row
+    };
+    SyncRepository::save_record(pool, &resolved).await.map_err(db_error)?;
+    SyncRepository::delete_conflict(pool, kind.as_str(), meeting_id)
+        .await
+        .map_err(db_error)?;
+    info!("Resolved sync conflict on the {} of meeting {}", kind.as_str(), meeting_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::TranscriptVersionSegment;
+    use crate::database::repositories::transcript_revision::TranscriptRevisionsRepository;
+    use crate::database::repositories::transcript_version::TranscriptVersionsRepository;
+    use crate::sync::local_server::LocalSyncServer;
+    use sha2::{Digest, Sha256};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::time::Duration;
+
+    async fn device_pool() -> SqlitePool {
+        // One connection, so every query sees the same in-memory database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    async fn sync(pool: &SqlitePool, server: &LocalSyncServer, device_id: &str) -> SyncReport {
+        let engine = SyncEngine {
+            pool,
+            transport: server,
+            device_id,
+            server_url: "file:///sync-test",
+            include_audio: false,
+            recordings_root: std::env::temp_dir(),
+        };
+        let report = engine.run().await.unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        report
+    }
+
+    fn segment(id: &str, text: &str) -> TranscriptVersionSegment {
+        crate::database::models::segment(id, text, 0.0, 5.0)
+    }
+
+    async fn title(pool: &SqlitePool) -> Option<String> {
+        sqlx::query_scalar("SELECT title FROM meetings WHERE id = 'm'")
+            .fetch_optional(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn rename(pool: &SqlitePool, title: &str) {
+        sqlx::query("UPDATE meetings SET title = ? WHERE id = 'm'")
+            .bind(title)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn transcript(pool: &SqlitePool) -> Vec<String> {
+        TranscriptVersionsRepository::get_current_segments(pool, "m")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| s.text)
+            .collect()
+    }
+
+    async fn summary(pool: &SqlitePool) -> Option<String> {
+        sqlx::query_scalar("SELECT result FROM summary_processes WHERE meeting_id = 'm'")
+            .fetch_optional(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn notes(pool: &SqlitePool) -> Option<String> {
+        MeetingsRepository::get_meeting_notes(pool, "m")
+            .await
+            .unwrap()
+            .and_then(|(markdown, _)| markdown)
+    }
+
+    /// Later edits must carry later timestamps for last-writer-wins to pick them
+    async fn tick() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    #[tokio::test]
+    async fn two_devices_converge() {
+        let server_dir = tempfile::tempdir().unwrap();
+        let server = LocalSyncServer::open(server_dir.path()).unwrap();
+        let laptop = device_pool().await;
+        let desktop = device_pool().await;
+
+        // A meeting recorded on the laptop reaches the desktop
+        SyncRepository::upsert_meeting(&laptop, "m", "Standup", Utc::now(), false).await.unwrap();
+        TranscriptVersionsRepository::replace_current_segments(&laptop, "m", &[segment("a", "hello team")])
+            .await
+            .unwrap();
+        SyncRepository::upsert_summary_result(&laptop, "m", r#"{"overview":"v1"}"#).await.unwrap();
+        SyncRepository::upsert_notes(&laptop, "m", Some("laptop notes"), None).await.unwrap();
+        assert_eq!(sync(&laptop, &server, "laptop").await.pushed, 4);
+        assert_eq!(sync(&desktop, &server, "desktop").await.pulled, 4);
+        assert_eq!(title(&desktop).await.as_deref(), Some("Standup"));
+        assert_eq!(transcript(&desktop).await, ["hello team"]);
+        assert_eq!(summary(&desktop).await.as_deref(), Some(r#"{"overview":"v1"}"#));
+        assert_eq!(notes(&desktop).await.as_deref(), Some("laptop notes"));
+
+        // Concurrent title and transcript edits: the later one wins on both devices
+        rename(&laptop, "Laptop title").await;
+        TranscriptVersionsRepository::replace_current_segments(&laptop, "m", &[segment("a", "hello laptop")])
+            .await
+            .unwrap();
+        sync(&laptop, &server, "laptop").await;
+        tick().await;
+        rename(&desktop, "Desktop title").await;
+        TranscriptVersionsRepository::replace_current_segments(&desktop, "m", &[segment("a", "hello desktop")])
+            .await
+            .unwrap();
+        let report = sync(&desktop, &server, "desktop").await;
+        assert_eq!((report.auto_resolved, report.conflicts), (2, 0));
+        sync(&laptop, &server, "laptop").await;
+        for pool in [&laptop, &desktop] {
+            assert_eq!(title(pool).await.as_deref(), Some("Desktop title"));
+            assert_eq!(transcript(pool).await, ["hello desktop"]);
+        }
+
+        // The laptop recorded the incoming transcript and dropped the summary of the old one;
+        // the deletion reaches the desktop as a tombstone
+        let revisions = TranscriptRevisionsRepository::list_revisions(&laptop, "m", 10).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].author.as_deref(), Some("sync"));
+        assert_eq!(summary(&laptop).await, None);
+        sync(&laptop, &server, "laptop").await;
+        sync(&desktop, &server, "desktop").await;
+        assert_eq!(summary(&desktop).await, None);
+
+        // Concurrent summaries and notes wait for the user instead
+        SyncRepository::upsert_summary_result(&laptop, "m", r#"{"overview":"laptop"}"#).await.unwrap();
+        SyncRepository::upsert_notes(&laptop, "m", Some("laptop agenda"), None).await.unwrap();
+        sync(&laptop, &server, "laptop").await;
+        tick().await;
+        SyncRepository::upsert_summary_result(&desktop, "m", r#"{"overview":"desktop"}"#).await.unwrap();
+        SyncRepository::upsert_notes(&desktop, "m", Some("desktop agenda"), None).await.unwrap();
+        assert_eq!(sync(&desktop, &server, "desktop").await.conflicts, 2);
+        assert_eq!(notes(&desktop).await.as_deref(), Some("desktop agenda"));
+        assert_eq!(summary(&desktop).await.as_deref(), Some(r#"{"overview":"desktop"}"#));
+
+        resolve_conflict(&desktop, "desktop", RecordKind::Notes, "m", ConflictResolution::KeepRemote)
+            .await
+            .unwrap();
+        resolve_conflict(&desktop, "desktop", RecordKind::Summary, "m", ConflictResolution::KeepLocal)
+            .await
+            .unwrap();
+        assert_eq!(sync(&desktop, &server, "desktop").await.conflicts, 0);
+        assert_eq!(sync(&laptop, &server, "laptop").await.conflicts, 0);
+        for pool in [&laptop, &desktop] {
+            assert_eq!(notes(pool).await.as_deref(), Some("laptop agenda"));
+            assert_eq!(summary(pool).await.as_deref(), Some(r#"{"overview":"desktop"}"#));
+        }
+
+        // Deleting the meeting on one device deletes it everywhere
+        MeetingsRepository::delete_meeting(&laptop, "m").await.unwrap();
+        sync(&laptop, &server, "laptop").await;
+        sync(&desktop, &server, "desktop").await;
+        assert_eq!(title(&desktop).await, None);
+    }
+
+    #[tokio::test]
+    async fn audio_upload_and_download_resume() {
+        let server_dir = tempfile::tempdir().unwrap();
+        let server = LocalSyncServer::open(server_dir.path()).unwrap();
+        let library = tempfile::tempdir().unwrap();
+        let data: Vec<u8> = (0..(CHUNK_SIZE as usize + 1000)).map(|i| (i % 251) as u8).collect();
+        let source = library.path().join("audio.mp4");
+        std::fs::write(&source, &data).unwrap();
+        let audio = AudioContent {
+            file_name: "audio.mp4".to_string(),
+            size: data.len() as u64,
+            sha256: hex::encode(Sha256::digest(&data)),
+        };
+
+        // An earlier upload stopped after the first 1000 bytes
+        let session = server.begin_upload(&audio.sha256, audio.size).await.unwrap();
+        server.upload_chunk(&session.upload_id, 0, data[..1000].to_vec()).await.unwrap();
+        assert!(upload_file(&server, &source, &audio.sha256, audio.size).await.unwrap());
+        // Already there: nothing to send
+        assert!(!upload_file(&server, &source, &audio.sha256, audio.size).await.unwrap());
+
+        // An earlier download stopped after the first 10 bytes
+        let target_dir = tempfile::tempdir().unwrap();
+        let target = target_dir.path().join("audio.mp4");
+        std::fs::write(target_dir.path().join(".audio.mp4.partial"), &data[..10]).unwrap();
+        download_file(&server, &audio, &target).await.unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), data);
+        assert!(!target_dir.path().join(".audio.mp4.partial").exists());
+    }
+
+    #[test]
+    fn names_folders_for_synced_meetings() {
+        let root = tempfile::tempdir().unwrap();
+        let folder = new_meeting_folder(root.path(), "Team sync: Q3/Q4", "meeting-1234567890ab");
+        assert_eq!(folder, root.path().join("Team sync_ Q3_Q4_12345678"));
+        std::fs::create_dir(&folder).unwrap();
+        let second = new_meeting_folder(root.path(), "Team sync: Q3/Q4", "meeting-1234567890ab");
+        assert_eq!(second, root.path().join("Team sync_ Q3_Q4_12345678_2"));
+    }
+}
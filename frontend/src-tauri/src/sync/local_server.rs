@@ -0,0 +1,264 @@
+//! Sync server kept in a local directory.
+//!
+//! Implements the server side of the protocol (revision checks, change feed, resumable
+//! content-addressed audio uploads) without a network. Used by the tests and by `file://`
+//! server URLs, e.g. a folder on a network drive used by one device at a time.
+//!
+//! Layout: `records.json` (every record's latest version and the feed position),
+//! `uploads/<sha256>.partial` (audio being uploaded), `blobs/<sha256>` (uploaded audio).
+
+use super::protocol::{
+    Causality, ChangeBatch, ChunkReceipt, PushOutcome, PushResponse, RecordKind, SyncRecord, UploadSession,
+};
+use super::records::AudioContent;
+use super::transport::SyncTransport;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+const RECORDS_FILE: &str = "records.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ServerState {
+    last_seq: u64,
+    /// Latest version of each record by "<kind>/<meeting_id>"
+    records: BTreeMap<String, SyncRecord>,
+}
+
+pub struct LocalSyncServer {
+    dir: PathBuf,
+    state: Mutex<ServerState>,
+}
+
+fn record_key(kind: RecordKind, meeting_id: &str) -> String {
+    format!("{}/{}", kind.as_str(), meeting_id)
+}
+
+/// Upload ids are the file's SHA-256, so only lowercase hex reaches the filesystem
+fn checked_sha256(value: &str) -> Result<&str, String> {
+    if value.len() == 64 && value.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)) {
+        Ok(value)
+    } else {
+        Err(format!("Invalid audio id: {}", value))
+    }
+}
+
+impl LocalSyncServer {
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        for sub in ["uploads", "blobs"] {
+            std::fs::create_dir_all(dir.join(sub))
+                .map_err(|e| format!("Failed to create sync directory {}: {}", dir.display(), e))?;
+        }
+        let state = match std::fs::read_to_string(dir.join(RECORDS_FILE)) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid {}: {}", RECORDS_FILE, e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ServerState::default(),
+            Err(e) => return Err(format!("Failed to read {}: {}", RECORDS_FILE, e)),
+        };
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            state: Mutex::new(state),
+        })
+    }
+
+    fn save(&self, state: &ServerState) -> Result<(), String> {
+        let path = self.dir.join(RECORDS_FILE);
+        let temp = self.dir.join(format!("{}.tmp", RECORDS_FILE));
+        let json = serde_json::to_vec(state).map_err(|e| format!("Failed to serialize records: {}", e))?;
+        std::fs::write(&temp, json).map_err(|e| format!("Failed to write {}: {}", temp.display(), e))?;
+        std::fs::rename(&temp, &path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn partial_path(&self, sha256: &str) -> PathBuf {
+        self.dir.join("uploads").join(format!("{}.partial", sha256))
+    }
+
+    fn blob_path(&self, sha256: &str) -> PathBuf {
+        self.dir.join("blobs").join(sha256)
+    }
+
+    /// Why a record cannot be stored, if it can't
+    fn invalid_reason(&self, record: &SyncRecord) -> Option<String> {
+        if record.kind != RecordKind::Audio || record.deleted {
+            return None;
+        }
+        let audio: AudioContent = match record.content.clone().map(serde_json::from_value) {
+            Some(Ok(audio)) => audio,
+            _ => return Some("Invalid audio record".to_string()),
+        };
+        match checked_sha256(&audio.sha256) {
+            Ok(sha256) if self.blob_path(sha256).is_file() => None,
+            Ok(_) => Some(format!("Audio {} was not uploaded", audio.sha256)),
+            Err(e) => Some(e),
+        }
+    }
+}
+
+#[async_trait]
+impl SyncTransport for LocalSyncServer {
+    async fn push(&self, records: Vec<SyncRecord>) -> Result<PushResponse, String> {
+        let mut state = self.state.lock().await;
+        let mut outcomes = Vec::with_capacity(records.len());
+        for mut record in records {
+            if let Some(message) = self.invalid_reason(&record) {
+                outcomes.push(PushOutcome::Invalid { message });
+                continue;
+            }
+            let key = record_key(record.kind, &record.meeting_id);
+            let outcome = match state.records.get(&key) {
+                Some(current) => match record.revision.compare(&current.revision) {
+                    Causality::After => None,
+                    Causality::Equal => Some(PushOutcome::Accepted { seq: current.seq }),
+                    Causality::Before | Causality::Concurrent => Some(PushOutcome::Rejected {
+                        current: current.clone(),
+                    }),
+                },
+                None => None,
+            };
+            let outcome = outcome.unwrap_or_else(|| {
+                state.last_seq += 1;
+                record.seq = state.last_seq;
+                state.records.insert(key, record);
+                PushOutcome::Accepted { seq: state.last_seq }
+            });
+            outcomes.push(outcome);
+        }
+        self.save(&state)?;
+        Ok(PushResponse { outcomes })
+    }
+
+    async fn changes(&self, since: u64, limit: usize) -> Result<ChangeBatch, String> {
+        let state = self.state.lock().await;
+        let mut changed: Vec<&SyncRecord> = state.records.values().filter(|r| r.seq > since).collect();
+        changed.sort_by_key(|r| r.seq);
+        let has_more = changed.len() > limit;
+        let records: Vec<SyncRecord> = changed.into_iter().take(limit).cloned().collect();
+        let cursor = records.last().map(|r| r.seq).unwrap_or(since);
+        Ok(ChangeBatch { records, cursor, has_more })
+    }
+
+    async fn begin_upload(&self, sha256: &str, size: u64) -> Result<UploadSession, String> {
+        let sha256 = checked_sha256(sha256)?;
+        if self.blob_path(sha256).is_file() {
+            return Ok(UploadSession { upload_id: sha256.to_string(), received: size, complete: true });
+        }
+        let received = std::fs::metadata(self.partial_path(sha256)).map(|m| m.len()).unwrap_or(0);
+        if received > size {
+            // Left over from a different upload; start again
+            let _ = std::fs::remove_file(self.partial_path(sha256));
+            return Ok(UploadSession { upload_id: sha256.to_string(), received: 0, complete: false });
+        }
+        Ok(UploadSession { upload_id: sha256.to_string(), received, complete: false })
+    }
+
+    async fn upload_chunk(&self, upload_id: &str, offset: u64, bytes: Vec<u8>) -> Result<ChunkReceipt, String> {
+        let path = self.partial_path(checked_sha256(upload_id)?);
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open upload: {}", e))?;
+        let received = file.metadata().map(|m| m.len()).map_err(|e| e.to_string())?;
+        if received != offset {
+            return Err(format!("Upload offset {} does not match the {} bytes received", offset, received));
+        }
+        file.write_all(&bytes).map_err(|e| format!("Failed to store upload: {}", e))?;
+        Ok(ChunkReceipt { received: received + bytes.len() as u64 })
+    }
+
+    async fn finish_upload(&self, upload_id: &str) -> Result<(), String> {
+        let sha256 = checked_sha256(upload_id)?;
+        let path = self.partial_path(sha256);
+        let bytes = std::fs::read(&path).map_err(|e| format!("Upload {} not found: {}", sha256, e))?;
+        if hex::encode(Sha256::digest(&bytes)) != sha256 {
+            let _ = std::fs::remove_file(&path);
+            return Err("Uploaded audio does not match its checksum".to_string());
+        }
+        std::fs::rename(&path, self.blob_path(sha256)).map_err(|e| format!("Failed to store upload: {}", e))
+    }
+
+    async fn download_chunk(&self, sha256: &str, offset: u64, max_len: u64) -> Result<Vec<u8>, String> {
+        let path = self.blob_path(checked_sha256(sha256)?);
+        let mut file = std::fs::File::open(&path).map_err(|e| format!("Audio {} not found: {}", sha256, e))?;
+        file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+        let mut bytes = Vec::new();
+        file.take(max_len)
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read audio: {}", e))?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::protocol::RevisionVector;
+
+    fn record(meeting_id: &str, counters: &[(&str, u64)]) -> SyncRecord {
+        SyncRecord {
+            kind: RecordKind::Notes,
+            meeting_id: meeting_id.to_string(),
+            revision: RevisionVector(counters.iter().map(|(d, c)| (d.to_string(), *c)).collect()),
+            modified_at: chrono::Utc::now(),
+            modified_by: counters[0].0.to_string(),
+            deleted: false,
+            content: Some(serde_json::json!({"markdown": "x", "json": null})),
+            seq: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn accepts_newer_and_rejects_concurrent_revisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = LocalSyncServer::open(dir.path()).unwrap();
+
+        let outcomes = server.push(vec![record("m1", &[("laptop", 1)])]).await.unwrap().outcomes;
+        assert_eq!(outcomes, vec![PushOutcome::Accepted { seq: 1 }]);
+
+        let outcomes = server
+            .push(vec![
+                record("m1", &[("laptop", 2)]),
+                record("m2", &[("desktop", 1)]),
+            ])
+            .await
+            .unwrap()
+            .outcomes;
+        assert_eq!(outcomes, vec![PushOutcome::Accepted { seq: 2 }, PushOutcome::Accepted { seq: 3 }]);
+
+        // Edited on the desktop from revision 1 while the laptop moved to 2
+        let outcomes = server.push(vec![record("m1", &[("laptop", 1), ("desktop", 1)])]).await.unwrap().outcomes;
+        assert!(matches!(&outcomes[0], PushOutcome::Rejected { current } if current.seq == 2));
+
+        // Feed survives a restart and pages by sequence
+        let server = LocalSyncServer::open(dir.path()).unwrap();
+        let batch = server.changes(0, 1).await.unwrap();
+        assert_eq!((batch.records[0].meeting_id.as_str(), batch.cursor, batch.has_more), ("m1", 2, true));
+        let batch = server.changes(batch.cursor, 10).await.unwrap();
+        assert_eq!((batch.records.len(), batch.cursor, batch.has_more), (1, 3, false));
+    }
+
+    #[tokio::test]
+    async fn audio_records_need_their_upload() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = LocalSyncServer::open(dir.path()).unwrap();
+        let data = b"audio bytes".to_vec();
+        let sha256 = hex::encode(Sha256::digest(&data));
+        let mut audio = record("m1", &[("laptop", 1)]);
+        audio.kind = RecordKind::Audio;
+        audio.content = Some(serde_json::json!({"file_name": "audio.mp4", "size": data.len(), "sha256": sha256}));
+
+        let outcomes = server.push(vec![audio.clone()]).await.unwrap().outcomes;
+        assert!(matches!(outcomes[0], PushOutcome::Invalid { .. }));
+
+        let session = server.begin_upload(&sha256, data.len() as u64).await.unwrap();
+        server.upload_chunk(&session.upload_id, 0, data.clone()).await.unwrap();
+        server.finish_upload(&session.upload_id).await.unwrap();
+        let outcomes = server.push(vec![audio]).await.unwrap().outcomes;
+        assert_eq!(outcomes, vec![PushOutcome::Accepted { seq: 1 }]);
+        assert_eq!(server.download_chunk(&sha256, 6, 100).await.unwrap(), b"bytes");
+        assert!(server.begin_upload("../records.json", 1).await.is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Sync of meetings between devices through a self-hosted server.
+//!
+//! Meetings, transcripts, summaries, notes and (optionally) audio are replicated as records
+//! with per-device revision vectors (`protocol`). Local changes are found by hashing each
+//! record's content at sync time, so the rest of the app writes to the database as before.
+//! Concurrent changes are settled by last-writer-wins, except summaries and notes, which are
+//! kept as conflicts for the user to resolve. Audio is uploaded in resumable chunks, keyed by
+//! its SHA-256.
+//!
+//! The server is reached over HTTP (`transport`), or is a local directory for `file://` URLs
+//! (`local_server`). Sync runs in the background at an interval while enabled, or on demand.
+
+pub mod commands;
+pub mod engine;
+pub mod local_server;
+pub mod protocol;
+pub mod records;
+pub mod transport;
+
+use crate::audio::recording_commands::is_recording;
+use crate::audio::recording_preferences::load_recording_preferences;
+use crate::state::AppState;
+use engine::{SyncEngine, SyncReport};
+use local_server::LocalSyncServer;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+use transport::{HttpTransport, SyncTransport};
+
+const STORE_FILE: &str = "sync.json";
+const SETTINGS_KEY: &str = "settings";
+const DEVICE_ID_KEY: &str = "device_id";
+const LAST_REPORT_KEY: &str = "last_report";
+/// Secret holding the server's access token
+const TOKEN_SECRET: &str = "sync.server_token";
+
+const STARTUP_DELAY: Duration = Duration::from_secs(2 * 60);
+/// How often the background task checks whether a sync is due
+const TICK: Duration = Duration::from_secs(60);
+
+static TASK_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Only one sync at a time (background or on demand)
+static SYNC_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// `https://…` for a sync server, `file:///…` for a shared directory
+    #[serde(default)]
+    pub server_url: String,
+    /// Also replicate the meetings' audio files
+    #[serde(default)]
+    pub include_audio: bool,
+    #[serde(default = "default_interval_minutes")]
+    pub interval_minutes: u32,
+}
+
+fn default_interval_minutes() -> u32 {
+    15
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            server_url: String::new(),
+            include_audio: false,
+            interval_minutes: default_interval_minutes(),
+        }
+    }
+}
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> SyncSettings {
+    let store = match app.store(STORE_FILE) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to access sync store: {}, using defaults", e);
+            return SyncSettings::default();
+        }
+    };
+    match store.get(SETTINGS_KEY) {
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            warn!("Failed to deserialize sync settings: {}, using defaults", e);
+            SyncSettings::default()
+        }),
+        None => SyncSettings::default(),
+    }
+}
+
+fn save_to_store<R: Runtime, T: Serialize>(app: &AppHandle<R>, key: &str, value: &T) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to access sync store: {}", e))?;
+    let value = serde_json::to_value(value).map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+    store.set(key, value);
+    store.save().map_err(|e| format!("Failed to save sync store: {}", e))
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &SyncSettings) -> Result<(), String> {
+    save_to_store(app, SETTINGS_KEY, settings)
+}
+
+fn last_report<R: Runtime>(app: &AppHandle<R>) -> Option<SyncReport> {
+    let value = app.store(STORE_FILE).ok()?.get(LAST_REPORT_KEY)?;
+    serde_json::from_value(value).ok()
+}
+
+/// This installation's id in revision vectors, created on first use
+pub fn device_id<R: Runtime>(app: &AppHandle<R>) -> Result<String, String> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| format!("Failed to access sync store: {}", e))?;
+    if let Some(id) = store.get(DEVICE_ID_KEY).and_then(|v| v.as_str().map(str::to_string)) {
+        return Ok(id);
+    }
+    let id = format!("device-{}", uuid::Uuid::new_v4());
+    save_to_store(app, DEVICE_ID_KEY, &id)?;
+    info!("Created sync device id {}", id);
+    Ok(id)
+}
+
+/// Checks a server URL and returns it without a trailing slash
+fn normalize_server_url(server_url: &str) -> Result<String, String> {
+    let trimmed = server_url.trim().trim_end_matches('/');
+    let parsed = url::Url::parse(trimmed).map_err(|e| format!("Invalid sync server URL: {}", e))?;
+    match parsed.scheme() {
+        "http" | "https" | "file" => Ok(trimmed.to_string()),
+        scheme => Err(format!("Unsupported sync server URL scheme: {}", scheme)),
+    }
+}
+
+fn transport_for(server_url: &str, token: Option<String>) -> Result<Box<dyn SyncTransport>, String> {
+    let parsed = url::Url::parse(server_url).map_err(|e| format!("Invalid sync server URL: {}", e))?;
+    if parsed.scheme() == "file" {
+        let dir = parsed
+            .to_file_path()
+            .map_err(|_| format!("Invalid sync directory: {}", server_url))?;
+        return Ok(Box::new(LocalSyncServer::open(&dir)?));
+    }
+    Ok(Box::new(HttpTransport::new(server_url, token)?))
+}
+
+/// RAII guard for SYNC_IN_PROGRESS
+struct SyncGuard;
+
+impl SyncGuard {
+    fn acquire() -> Result<Self, String> {
+        if SYNC_IN_PROGRESS
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err("A sync is already running".to_string());
+        }
+        Ok(SyncGuard)
+    }
+}
+
+impl Drop for SyncGuard {
+    fn drop(&mut self) {
+        SYNC_IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Syncs the library with the configured server
+pub async fn run_sync<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool) -> Result<SyncReport, String> {
+    let settings = load_settings(app);
+    if settings.server_url.is_empty() {
+        return Err("No sync server is configured".to_string());
+    }
+    // The meeting being recorded is still changing
+    if is_recording().await {
+        return Err("Not available while recording".to_string());
+    }
+    let _guard = SyncGuard::acquire()?;
+
+    let device_id = device_id(app)?;
+    let token = crate::secrets::get_secret(TOKEN_SECRET)?;
+    let transport = transport_for(&settings.server_url, token)?;
+    let recordings_root = load_recording_preferences(app)
+        .await
+        .map(|prefs| prefs.save_folder)
+        .map_err(|e| format!("Failed to load recording preferences: {}", e))?;
+
+    let engine = SyncEngine {
+        pool,
+        transport: transport.as_ref(),
+        device_id: &device_id,
+        server_url: &settings.server_url,
+        include_audio: settings.include_audio,
+        recordings_root,
+    };
+    let report = engine.run().await?;
+    info!(
+        "Sync finished: {} pushed, {} pulled, {} auto-resolved, {} conflicts, {} errors",
+        report.pushed,
+        report.pulled,
+        report.auto_resolved,
+        report.conflicts,
+        report.errors.len()
+    );
+    for error in &report.errors {
+        warn!("Sync: {}", error);
+    }
+    if let Err(e) = save_to_store(app, LAST_REPORT_KEY, &report) {
+        warn!("{}", e);
+    }
+    let _ = app.emit("sync-completed", &report);
+    Ok(report)
+}
+
+/// Syncs in the background at the configured interval while sync is enabled
+pub fn start_sync_task<R: Runtime>(app: AppHandle<R>) {
+    if TASK_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        // Unlocking an encrypted library manages the state and starts the task again
+        let Some(pool) = app.try_state::<AppState>().map(|state| state.db_manager.pool().clone()) else {
+            info!("Background sync not started: app state unavailable");
+            TASK_STARTED.store(false, Ordering::SeqCst);
+            return;
+        };
+
+        let mut last_attempt: Option<std::time::Instant> = None;
+        loop {
+            let settings = load_settings(&app);
+            let interval = Duration::from_secs(u64::from(settings.interval_minutes.max(1)) * 60);
+            let due = last_attempt.map_or(true, |at| at.elapsed() >= interval);
+            if settings.enabled && due {
+                last_attempt = Some(std::time::Instant::now());
+                if let Err(e) = run_sync(&app, &pool).await {
+                    info!("Background sync skipped: {}", e);
+                }
+            }
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_server_url() {
+        assert_eq!(
+            normalize_server_url(" https://sync.example.com/ ").unwrap(),
+            "https://sync.example.com"
+        );
+        assert_eq!(normalize_server_url("file:///mnt/share/iq").unwrap(), "file:///mnt/share/iq");
+        assert!(normalize_server_url("ftp://sync.example.com").is_err());
+        assert!(normalize_server_url("sync.example.com").is_err());
+    }
+}
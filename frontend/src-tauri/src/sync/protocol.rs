@@ -0,0 +1,231 @@
+//! Records and messages exchanged with a sync server.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// What a sync record holds; each meeting has at most one record of each kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordKind {
+    /// Title, creation time and star
+    Meeting,
+    Transcript,
+    Summary,
+    Notes,
+    /// Name, size and SHA-256 of the meeting's audio; the file itself is uploaded separately
+    Audio,
+}
+
+impl RecordKind {
+    pub const ALL: [RecordKind; 5] = [
+        RecordKind::Meeting,
+        RecordKind::Transcript,
+        RecordKind::Summary,
+        RecordKind::Notes,
+        RecordKind::Audio,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordKind::Meeting => "meeting",
+            RecordKind::Transcript => "transcript",
+            RecordKind::Summary => "summary",
+            RecordKind::Notes => "notes",
+            RecordKind::Audio => "audio",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+
+    /// Concurrent edits are kept for the user to resolve instead of last-writer-wins
+    pub fn needs_manual_resolution(&self) -> bool {
+        matches!(self, RecordKind::Summary | RecordKind::Notes)
+    }
+}
+
+/// How two revisions of a record relate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Causality {
+    Equal,
+    /// The other revision contains every change of this one and more
+    Before,
+    /// This revision contains every change of the other one and more
+    After,
+    /// Both have changes the other lacks
+    Concurrent,
+}
+
+/// Per-device change counters of a record
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RevisionVector(pub BTreeMap<String, u64>);
+
+impl RevisionVector {
+    /// Records a change made on `device_id`
+    pub fn increment(&mut self, device_id: &str) {
+        *self.0.entry(device_id.to_string()).or_insert(0) += 1;
+    }
+
+    /// Counter-wise maximum: a revision that follows both
+    pub fn merged(&self, other: &RevisionVector) -> RevisionVector {
+        let mut merged = self.clone();
+        for (device, &counter) in &other.0 {
+            let entry = merged.0.entry(device.clone()).or_insert(0);
+            *entry = (*entry).max(counter);
+        }
+        merged
+    }
+
+    pub fn compare(&self, other: &RevisionVector) -> Causality {
+        let (mut ahead, mut behind) = (false, false);
+        for device in self.0.keys().chain(other.0.keys()) {
+            let mine = self.0.get(device).copied().unwrap_or(0);
+            let theirs = other.0.get(device).copied().unwrap_or(0);
+            ahead |= mine > theirs;
+            behind |= mine < theirs;
+        }
+        match (ahead, behind) {
+            (false, false) => Causality::Equal,
+            (false, true) => Causality::Before,
+            (true, false) => Causality::After,
+            (true, true) => Causality::Concurrent,
+        }
+    }
+}
+
+/// A record as stored on the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRecord {
+    pub kind: RecordKind,
+    pub meeting_id: String,
+    pub revision: RevisionVector,
+    pub modified_at: DateTime<Utc>,
+    /// Device that made the last change
+    pub modified_by: String,
+    #[serde(default)]
+    pub deleted: bool,
+    /// Kind-specific content (see `records`); None for deletions
+    #[serde(default)]
+    pub content: Option<serde_json::Value>,
+    /// Position in the server's change feed, assigned by the server
+    #[serde(default)]
+    pub seq: u64,
+}
+
+impl SyncRecord {
+    /// Whether this version wins over `other` under last-writer-wins
+    pub fn wins_over(&self, other: &SyncRecord) -> bool {
+        (self.modified_at, &self.modified_by) > (other.modified_at, &other.modified_by)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PushOutcome {
+    Accepted { seq: u64 },
+    /// The server has a revision this push does not follow; `current` is handled like a
+    /// pulled change
+    Rejected { current: SyncRecord },
+    /// The record cannot be stored (e.g. its audio was not uploaded)
+    Invalid { message: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushRequest {
+    pub records: Vec<SyncRecord>,
+}
+
+/// One outcome per pushed record, in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushResponse {
+    pub outcomes: Vec<PushOutcome>,
+}
+
+/// Records changed after a feed position, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeBatch {
+    pub records: Vec<SyncRecord>,
+    /// Feed position to ask from next time
+    pub cursor: u64,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadRequest {
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// An audio upload; uploads are keyed by content, so starting the same file again resumes it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadSession {
+    pub upload_id: String,
+    /// Bytes the server already has
+    pub received: u64,
+    /// The server already has the whole file
+    pub complete: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkReceipt {
+    pub received: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revision(counters: &[(&str, u64)]) -> RevisionVector {
+        RevisionVector(counters.iter().map(|(d, c)| (d.to_string(), *c)).collect())
+    }
+
+    #[test]
+    fn compares_revisions() {
+        let base = revision(&[("laptop", 2), ("desktop", 1)]);
+        let mut laptop = base.clone();
+        laptop.increment("laptop");
+        let mut desktop = base.clone();
+        desktop.increment("desktop");
+
+        assert_eq!(base.compare(&base), Causality::Equal);
+        assert_eq!(base.compare(&laptop), Causality::Before);
+        assert_eq!(laptop.compare(&base), Causality::After);
+        assert_eq!(laptop.compare(&desktop), Causality::Concurrent);
+        assert_eq!(RevisionVector::default().compare(&base), Causality::Before);
+
+        let merged = laptop.merged(&desktop);
+        assert_eq!(merged, revision(&[("laptop", 3), ("desktop", 2)]));
+        assert_eq!(merged.compare(&laptop), Causality::After);
+        assert_eq!(merged.compare(&desktop), Causality::After);
+    }
+
+    #[test]
+    fn last_writer_wins_by_time_then_device() {
+        let record = |device: &str, secs: i64| SyncRecord {
+            kind: RecordKind::Transcript,
+            meeting_id: "m".to_string(),
+            revision: RevisionVector::default(),
+            modified_at: DateTime::from_timestamp(secs, 0).unwrap(),
+            modified_by: device.to_string(),
+            deleted: false,
+            content: None,
+            seq: 0,
+        };
+        assert!(record("a", 20).wins_over(&record("b", 10)));
+        assert!(!record("a", 10).wins_over(&record("b", 20)));
+        assert!(record("b", 10).wins_over(&record("a", 10)));
+    }
+
+    #[test]
+    fn record_kinds_round_trip() {
+        for kind in RecordKind::ALL {
+            assert_eq!(RecordKind::parse(kind.as_str()), Some(kind));
+            assert_eq!(serde_json::to_value(kind).unwrap(), serde_json::json!(kind.as_str()));
+        }
+        assert!(RecordKind::Notes.needs_manual_resolution());
+        assert!(!RecordKind::Transcript.needs_manual_resolution());
+    }
+}
@@ -0,0 +1,221 @@
+//! Content of sync records, read from and written to the local database.
+
+use super::protocol::RecordKind;
+use crate::database::models::{MeetingRetentionInfo, TranscriptVersionSegment};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::database::repositories::sync::SyncRepository;
+use crate::database::repositories::transcript_version::TranscriptVersionsRepository;
+use crate::encryption::files as library_files;
+use crate::retention::storage::audio_files;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeetingContent {
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub starred: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptContent {
+    pub segments: Vec<TranscriptVersionSegment>,
+}
+
+/// A completed summary (the stored result JSON)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryContent {
+    pub result: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotesContent {
+    pub markdown: Option<String>,
+    pub json: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioContent {
+    /// File name in the meeting folder
+    pub file_name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+fn db_error(e: sqlx::Error) -> String {
+    format!("Database error: {}", e)
+}
+
+fn to_value<T: Serialize>(content: &T) -> Result<Value, String> {
+    serde_json::to_value(content).map_err(|e| format!("Failed to serialize sync record: {}", e))
+}
+
+fn from_value<T: serde::de::DeserializeOwned>(kind: RecordKind, content: Option<&Value>) -> Result<T, String> {
+    let content = content.ok_or_else(|| format!("The {} record has no content", kind.as_str()))?;
+    serde_json::from_value(content.clone()).map_err(|e| format!("Invalid {} record: {}", kind.as_str(), e))
+}
+
+/// SHA-256 of a record's content, to notice local changes between syncs
+pub fn content_hash(content: &Value) -> String {
+    hex::encode(Sha256::digest(content.to_string().as_bytes()))
+}
+
+/// Current content of a record of `meeting`, or None if the meeting has nothing of this kind.
+/// Audio is read by `local_audio` instead.
+pub async fn load_content(
+    pool: &SqlitePool,
+    kind: RecordKind,
+    meeting: &MeetingRetentionInfo,
+) -> Result<Option<Value>, String> {
+    match kind {
+        RecordKind::Meeting => to_value(&MeetingContent {
+            title: meeting.title.clone(),
+            created_at: meeting.created_at,
+            starred: meeting.starred,
+        })
+        .map(Some),
+        RecordKind::Transcript => {
+            let segments = TranscriptVersionsRepository::get_current_segments(pool, &meeting.id)
+                .await
+                .map_err(db_error)?;
+            if segments.is_empty() {
+                return Ok(None);
+            }
+            to_value(&TranscriptContent { segments }).map(Some)
+        }
+        RecordKind::Summary => {
+            let process = SummaryProcessesRepository::get_summary_data(pool, &meeting.id)
+                .await
+                .map_err(db_error)?;
+            let result = process
+                .filter(|p| p.status == "completed")
+                .and_then(|p| p.result)
+                .and_then(|json| serde_json::from_str::<Value>(&json).ok())
+                .filter(|result| !result.is_null());
+            result.map(|result| to_value(&SummaryContent { result })).transpose()
+        }
+        RecordKind::Notes => {
+            let notes = MeetingsRepository::get_meeting_notes(pool, &meeting.id)
+                .await
+                .map_err(db_error)?;
+            let has_text = |text: &Option<String>| text.as_deref().is_some_and(|t| !t.trim().is_empty());
+            match notes {
+                Some((markdown, json)) if has_text(&markdown) || has_text(&json) => {
+                    to_value(&NotesContent { markdown, json }).map(Some)
+                }
+                _ => Ok(None),
+            }
+        }
+        RecordKind::Audio => Ok(None),
+    }
+}
+
+/// Writes a record received from another device (or chosen in a conflict) into the database.
+/// Audio files are transferred by the engine.
+pub async fn apply_content(
+    pool: &SqlitePool,
+    kind: RecordKind,
+    meeting_id: &str,
+    deleted: bool,
+    content: Option<&Value>,
+) -> Result<(), String> {
+    match kind {
+        RecordKind::Meeting if deleted => {
+            MeetingsRepository::delete_meeting(pool, meeting_id).await.map_err(db_error)?;
+        }
+        RecordKind::Meeting => {
+            let meeting: MeetingContent = from_value(kind, content)?;
+            SyncRepository::upsert_meeting(pool, meeting_id, &meeting.title, meeting.created_at, meeting.starred)
+                .await
+                .map_err(db_error)?;
+        }
+        RecordKind::Transcript => {
+            let segments = if deleted {
+                Vec::new()
+            } else {
+                from_value::<TranscriptContent>(kind, content)?.segments
+            };
+            SyncRepository::apply_transcript(pool, meeting_id, &segments)
+                .await
+                .map_err(db_error)?;
+        }
+        RecordKind::Summary if deleted => {
+            SyncRepository::delete_summary(pool, meeting_id).await.map_err(db_error)?;
+        }
+        RecordKind::Summary => {
+            let summary: SummaryContent = from_value(kind, content)?;
+            SyncRepository::upsert_summary_result(pool, meeting_id, &summary.result.to_string())
+                .await
+                .map_err(db_error)?;
+        }
+        RecordKind::Notes if deleted => {
+            SyncRepository::delete_notes(pool, meeting_id).await.map_err(db_error)?;
+        }
+        RecordKind::Notes => {
+            let notes: NotesContent = from_value(kind, content)?;
+            SyncRepository::upsert_notes(pool, meeting_id, notes.markdown.as_deref(), notes.json.as_deref())
+                .await
+                .map_err(db_error)?;
+        }
+        RecordKind::Audio => {}
+    }
+    Ok(())
+}
+
+/// Checks that content chosen by the user when resolving a conflict has the record's shape
+pub fn validate_content(kind: RecordKind, content: &Value) -> Result<(), String> {
+    match kind {
+        RecordKind::Meeting => from_value::<MeetingContent>(kind, Some(content)).map(|_| ()),
+        RecordKind::Transcript => from_value::<TranscriptContent>(kind, Some(content)).map(|_| ()),
+        RecordKind::Summary => from_value::<SummaryContent>(kind, Some(content)).map(|_| ()),
+        RecordKind::Notes => from_value::<NotesContent>(kind, Some(content)).map(|_| ()),
+        RecordKind::Audio => Err("Audio conflicts are resolved automatically".to_string()),
+    }
+}
+
+/// The meeting's main audio file: `audio.*` if present, otherwise the first audio file
+pub fn main_audio_file(folder: &Path) -> Option<PathBuf> {
+    let mut files: Vec<PathBuf> = audio_files(folder)
+        .into_iter()
+        .filter(|path| !path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')))
+        .collect();
+    files.sort();
+    let is_main = |path: &PathBuf| {
+        library_files::plaintext_path(path)
+            .file_stem()
+            .is_some_and(|stem| stem == "audio")
+    };
+    files.iter().position(is_main).map(|i| files.swap_remove(i)).or_else(|| files.into_iter().next())
+}
+
+/// SHA-256 and size of a file's plaintext (decrypting it when stored encrypted) (blocking)
+pub fn hash_file(path: &Path) -> Result<(String, u64), String> {
+    struct HashWriter(Sha256, u64);
+    impl std::io::Write for HashWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.update(buf);
+            self.1 += buf.len() as u64;
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut reader = std::io::BufReader::new(file);
+    let mut writer = HashWriter(Sha256::new(), 0);
+    if library_files::is_encrypted_path(path) {
+        let key = crate::encryption::library_key().ok_or_else(|| "The encrypted library is locked".to_string())?;
+        library_files::decrypt_stream(&key, &mut reader, &mut writer)?;
+    } else {
+        std::io::copy(&mut reader, &mut writer).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    }
+    Ok((hex::encode(writer.0.finalize()), writer.1))
+}
@@ -0,0 +1,144 @@
+//! Access to a sync server.
+//!
+//! A self-hosted server speaks JSON over HTTP under `<server>/sync/v1`:
+//!
+//! | Request | Body | Response |
+//! |---|---|---|
+//! | `POST /push` | `PushRequest` | `PushResponse` |
+//! | `GET /changes?since=<cursor>&limit=<n>` | | `ChangeBatch` |
+//! | `POST /uploads` | `UploadRequest` | `UploadSession` |
+//! | `PUT /uploads/<id>?offset=<n>` | file bytes | `ChunkReceipt` |
+//! | `POST /uploads/<id>/finish` | | |
+//! | `GET /blobs/<sha256>` with `Range: bytes=<start>-<end>` | | file bytes |
+//!
+//! Requests carry the configured token as a bearer token. `LocalSyncServer` implements the
+//! same operations on a directory.
+
+use super::protocol::{ChangeBatch, ChunkReceipt, PushRequest, PushResponse, SyncRecord, UploadRequest, UploadSession};
+use async_trait::async_trait;
+use std::time::Duration;
+
+#[async_trait]
+pub trait SyncTransport: Send + Sync {
+    /// Offers local changes; returns one outcome per record
+    async fn push(&self, records: Vec<SyncRecord>) -> Result<PushResponse, String>;
+    /// Records changed after `since` in the change feed
+    async fn changes(&self, since: u64, limit: usize) -> Result<ChangeBatch, String>;
+    /// Starts (or resumes) uploading an audio file
+    async fn begin_upload(&self, sha256: &str, size: u64) -> Result<UploadSession, String>;
+    /// Appends bytes at `offset`, which must be what the server has received so far
+    async fn upload_chunk(&self, upload_id: &str, offset: u64, bytes: Vec<u8>) -> Result<ChunkReceipt, String>;
+    /// Verifies the uploaded file against its SHA-256
+    async fn finish_upload(&self, upload_id: &str) -> Result<(), String>;
+    /// Up to `max_len` bytes of an uploaded audio file from `offset`
+    async fn download_chunk(&self, sha256: &str, offset: u64, max_len: u64) -> Result<Vec<u8>, String>;
+}
+
+pub struct HttpTransport {
+    client: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl HttpTransport {
+    pub fn new(server_url: &str, token: Option<String>) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(120))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        Ok(Self {
+            client,
+            base_url: format!("{}/sync/v1", server_url.trim_end_matches('/')),
+            token,
+        })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, format!("{}{}", self.base_url, path));
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, String> {
+        let response = request.send().await.map_err(|e| format!("Sync server unreachable: {}", e))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(match status {
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                "The sync server rejected the access token".to_string()
+            }
+            _ => format!("Sync server error {}: {}", status, body.chars().take(200).collect::<String>()),
+        })
+    }
+
+    async fn send_json<T: serde::de::DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T, String> {
+        self.send(request)
+            .await?
+            .json::<T>()
+            .await
+            .map_err(|e| format!("Invalid response from sync server: {}", e))
+    }
+}
+
+#[async_trait]
+impl SyncTransport for HttpTransport {
+    async fn push(&self, records: Vec<SyncRecord>) -> Result<PushResponse, String> {
+        self.send_json(self.request(reqwest::Method::POST, "/push").json(&PushRequest { records }))
+            .await
+    }
+
+    async fn changes(&self, since: u64, limit: usize) -> Result<ChangeBatch, String> {
+        self.send_json(
+            self.request(reqwest::Method::GET, "/changes")
+                .query(&[("since", since.to_string()), ("limit", limit.to_string())]),
+        )
+        .await
+    }
+
+    async fn begin_upload(&self, sha256: &str, size: u64) -> Result<UploadSession, String> {
+        let body = UploadRequest { sha256: sha256.to_string(), size };
+        self.send_json(self.request(reqwest::Method::POST, "/uploads").json(&body))
+            .await
+    }
+
+    async fn upload_chunk(&self, upload_id: &str, offset: u64, bytes: Vec<u8>) -> Result<ChunkReceipt, String> {
+        self.send_json(
+            self.request(reqwest::Method::PUT, &format!("/uploads/{}", upload_id))
+                .query(&[("offset", offset.to_string())])
+                .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+                .body(bytes),
+        )
+        .await
+    }
+
+    async fn finish_upload(&self, upload_id: &str) -> Result<(), String> {
+        self.send(self.request(reqwest::Method::POST, &format!("/uploads/{}/finish", upload_id)))
+            .await
+            .map(|_| ())
+    }
+
+    async fn download_chunk(&self, sha256: &str, offset: u64, max_len: u64) -> Result<Vec<u8>, String> {
+        let range = format!("bytes={}-{}", offset, offset + max_len.max(1) - 1);
+        let response = self
+            .send(self.request(reqwest::Method::GET, &format!("/blobs/{}", sha256)).header(reqwest::header::RANGE, range))
+            .await?;
+        let partial = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Audio download interrupted: {}", e))?;
+        if partial {
+            return Ok(bytes.to_vec());
+        }
+        // The server ignored the range and sent the whole file
+        let start = (offset as usize).min(bytes.len());
+        let end = (offset.saturating_add(max_len) as usize).min(bytes.len());
+        Ok(bytes[start..end].to_vec())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::database::models::MeetingTranslation;
+use crate::database::repositories::translation::TranslationsRepository;
+use crate::state::AppState;
+use crate::translation::service::{TranslationRequest, TranslationService};
+use log::info as log_info;
+use tauri::{AppHandle, Runtime};
+
+fn normalize_language(language: &str) -> Result<String, String> {
+    let language = language.trim().to_lowercase();
+    if language.is_empty() {
+        return Err("Target language is required".to_string());
+    }
+    Ok(language)
+}
+
+/// Starts translating a meeting's transcript (and optionally its summary) into a target language
+///
+/// Runs in the background; progress is reported through translation-progress events.
+#[tauri::command]
+pub async fn api_translate_meeting<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    target_language: String,
+    model: String,
+    model_name: String,
+    include_summary: Option<bool>,
+) -> Result<(), String> {
+    let language = normalize_language(&target_language)?;
+    log_info!(
+        "api_translate_meeting called for meeting_id: {}, language: {}, model: {}",
+        meeting_id,
+        language,
+        model
+    );
+
+    let request = TranslationRequest {
+        meeting_id,
+        language,
+        model_provider: model,
+        model_name,
+        include_summary: include_summary.unwrap_or(true),
+    };
+
+    let pool = state.db_manager.pool().clone();
+    tauri::async_runtime::spawn(async move {
+        TranslationService::translate_meeting_background(app, pool, request).await;
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn api_cancel_translation(meeting_id: String, target_language: String) -> Result<bool, String> {
+    let language = normalize_language(&target_language)?;
+    Ok(TranslationService::cancel_translation(&meeting_id, &language))
+}
+
+#[tauri::command]
+pub async fn api_get_meeting_translation(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    target_language: String,
+) -> Result<Option<MeetingTranslation>, String> {
+    let language = normalize_language(&target_language)?;
+    TranslationsRepository::get_translation(state.db_manager.pool(), &meeting_id, &language)
+        .await
+        .map_err(|e| format!("Failed to load translation: {}", e))
+}
+
+#[tauri::command]
+pub async fn api_list_meeting_translations(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<MeetingTranslation>, String> {
+    TranslationsRepository::list_translations(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to list translations: {}", e))
+}
+
+#[tauri::command]
+pub async fn api_delete_meeting_translation(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    target_language: String,
+) -> Result<bool, String> {
+    let language = normalize_language(&target_language)?;
+    TranslationService::cancel_translation(&meeting_id, &language);
+    TranslationsRepository::delete_translation(state.db_manager.pool(), &meeting_id, &language)
+        .await
+        .map_err(|e| format!("Failed to delete translation: {}", e))
+}
@@ -0,0 +1,14 @@
+/// Translation module - translated transcript and summary tracks
+///
+/// This module contains:
+/// - Service layer that translates a meeting's transcript (batched over segments, timestamps
+///   preserved) and its summary into a target language with the configured LLM provider,
+///   including the built-in local model
+/// - Tauri commands for starting, cancelling, reading and deleting translations
+///
+/// Tracks are stored per meeting and language in the `meeting_translations` table.
+
+pub mod commands;
+pub mod service;
+
+pub use service::TranslationService;
@@ -0,0 +1,564 @@
+use crate::database::models::{TranscriptVersionSegment, TranslatedSegment};
+use crate::database::repositories::{
+    summary::SummaryProcessesRepository, transcript_version::TranscriptVersionsRepository,
+    translation::TranslationsRepository,
+};
+use crate::summary::llm_client::{generate_summary, LLMProvider};
+use crate::summary::processor::{clean_llm_markdown_output, rough_token_count};
+use crate::summary::redaction::{self, Redactor};
+use crate::summary::service::{LlmConnection, SummaryService, HTTP_CLIENT};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+// Cancellation tokens of running translations, keyed by "meeting_id:language"
+static CANCELLATION_REGISTRY: Lazy<Arc<Mutex<HashMap<String, CancellationToken>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+/// Segment batch size (input tokens) for cloud providers
+const CLOUD_BATCH_TOKENS: usize = 2500;
+
+/// Segment batch size (input tokens) for Ollama models with unknown context
+const OLLAMA_BATCH_TOKENS: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationProgress {
+    pub meeting_id: String,
+    pub language: String,
+    pub stage: String,
+    pub progress: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationComplete {
+    pub meeting_id: String,
+    pub language: String,
+    pub segments: usize,
+    /// Segments the model did not return a translation for (original text kept)
+    pub untranslated: usize,
+    pub summary_translated: bool,
+}
+
+/// What to translate and with which model
+#[derive(Debug, Clone)]
+pub struct TranslationRequest {
+    pub meeting_id: String,
+    pub language: String,
+    pub model_provider: String,
+    pub model_name: String,
+    pub include_summary: bool,
+}
+
+/// Translation service - translated transcript and summary tracks
+pub struct TranslationService;
+
+impl TranslationService {
+    fn registry_key(meeting_id: &str, language: &str) -> String {
+        format!("{}:{}", meeting_id, language)
+    }
+
+    fn register_cancellation_token(meeting_id: &str, language: &str) -> CancellationToken {
+        let token = CancellationToken::new();
+        if let Ok(mut registry) = CANCELLATION_REGISTRY.lock() {
+            registry.insert(Self::registry_key(meeting_id, language), token.clone());
+        }
+        token
+    }
+
+    fn cleanup_cancellation_token(meeting_id: &str, language: &str) {
+        if let Ok(mut registry) = CANCELLATION_REGISTRY.lock() {
+            registry.remove(&Self::registry_key(meeting_id, language));
+        }
+    }
+
+    /// Cancels a running translation
+    pub fn cancel_translation(meeting_id: &str, language: &str) -> bool {
+        if let Ok(registry) = CANCELLATION_REGISTRY.lock() {
+            if let Some(token) = registry.get(&Self::registry_key(meeting_id, language)) {
+                info!("Cancelling {} translation for meeting: {}", language, meeting_id);
+                token.cancel();
+                return true;
+            }
+        }
+        warn!("No active {} translation found for meeting: {}", language, meeting_id);
+        false
+    }
+
+    /// Translates a meeting's transcript (and optionally its summary) in the background.
+    /// Emits translation-progress, translation-complete and translation-error events.
+    pub async fn translate_meeting_background<R: Runtime>(
+        app: AppHandle<R>,
+        pool: SqlitePool,
+        request: TranslationRequest,
+    ) {
+        let meeting_id = request.meeting_id.clone();
+        let language = request.language.clone();
+        let cancellation_token = Self::register_cancellation_token(&meeting_id, &language);
+
+        let result = Self::translate_meeting(&app, &pool, &request, &cancellation_token).await;
+        Self::cleanup_cancellation_token(&meeting_id, &language);
+
+        match result {
+            Ok(complete) => {
+                info!(
+                    "Translated meeting {} into {}: {} segments ({} untranslated), summary: {}",
+                    meeting_id, language, complete.segments, complete.untranslated, complete.summary_translated
+                );
+                let _ = app.emit("translation-complete", &complete);
+            }
+            Err(e) => {
+                let status = if cancellation_token.is_cancelled() { "cancelled" } else { "failed" };
+                error!("Translation of meeting {} into {} {}: {}", meeting_id, language, status, e);
+                if let Err(db_err) =
+                    TranslationsRepository::finish_with_status(&pool, &meeting_id, &language, status, Some(&e)).await
+                {
+                    error!("Failed to update translation status for {}: {}", meeting_id, db_err);
+                }
+                let _ = app.emit(
+                    "translation-error",
+                    serde_json::json!({
+                        "meeting_id": meeting_id,
+                        "language": language,
+                        "status": status,
+                        "error": e,
+                    }),
+                );
+            }
+        }
+    }
+
+    async fn translate_meeting<R: Runtime>(
+        app: &AppHandle<R>,
+        pool: &SqlitePool,
+        request: &TranslationRequest,
+        cancellation_token: &CancellationToken,
+    ) -> Result<TranslationComplete, String> {
+        let meeting_id = request.meeting_id.as_str();
+        let language = request.language.as_str();
+
+        TranslationsRepository::start_translation(
+            pool,
+            meeting_id,
+            language,
+            &request.model_provider,
+            &request.model_name,
+        )
+        .await
+        .map_err(|e| format!("Failed to start translation: {}", e))?;
+
+        let connection = SummaryService::resolve_connection(pool, &request.model_provider).await?;
+        let segments = TranscriptVersionsRepository::get_current_segments(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load transcript: {}", e))?;
+        let summary = if request.include_summary {
+            let summary = load_summary_markdown(pool, meeting_id).await?;
+            if summary.is_none() {
+                warn!("Meeting {} has no summary to translate", meeting_id);
+            }
+            summary
+        } else {
+            None
+        };
+
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).chain(summary.as_deref()).collect();
+        let redactor = redaction::redactor_for(app, pool, &connection.provider, &texts).await?;
+        let mut llm = TranslationLlm {
+            connection,
+            model_name: request.model_name.clone(),
+            app_data_dir: app.path().app_data_dir().ok(),
+            cancellation_token: cancellation_token.clone(),
+            redactor,
+        };
+
+        let result = Self::translate_track(app, pool, request, &mut llm, &segments, summary.as_deref()).await;
+        if let Some(redactor) = &llm.redactor {
+            redaction::record_audit(
+                pool,
+                Some(meeting_id),
+                "translation",
+                &request.model_provider,
+                &request.model_name,
+                redactor,
+            )
+            .await;
+        }
+        result
+    }
+
+    async fn translate_track<R: Runtime>(
+        app: &AppHandle<R>,
+        pool: &SqlitePool,
+        request: &TranslationRequest,
+        llm: &mut TranslationLlm,
+        segments: &[TranscriptVersionSegment],
+        summary: Option<&str>,
+    ) -> Result<TranslationComplete, String> {
+        let meeting_id = request.meeting_id.as_str();
+        let language = request.language.as_str();
+        let budget = batch_token_budget(&llm.connection.provider, &request.model_name);
+        let target = language_name(language);
+
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        let batches = build_batches(&texts, budget);
+        let mut translated: Vec<Option<String>> = vec![None; segments.len()];
+
+        for (b, batch) in batches.iter().enumerate() {
+            let progress = if request.include_summary { 5 + b * 80 / batches.len() } else { 5 + b * 90 / batches.len() };
+            emit_progress(
+                app,
+                request,
+                "transcript",
+                progress as u32,
+                &format!("Translating transcript ({} of {})...", b + 1, batches.len()),
+            );
+
+            let lines = &texts[batch.clone()];
+            let output = llm
+                .complete(&transcript_system_prompt(&target), &format_numbered_lines(lines))
+                .await?;
+            let parsed = parse_numbered_lines(&output, lines.len());
+
+            for (offset, line) in parsed.into_iter().enumerate() {
+                translated[batch.start + offset] = line;
+            }
+
+            // Models occasionally merge or drop lines; retry those one at a time
+            for index in batch.clone() {
+                if translated[index].is_none() && !texts[index].trim().is_empty() {
+                    let output = llm
+                        .complete(&transcript_system_prompt(&target), &format_numbered_lines(&texts[index..index + 1]))
+                        .await?;
+                    translated[index] = parse_numbered_lines(&output, 1).pop().flatten();
+                }
+            }
+        }
+
+        let (track, untranslated) = build_track(segments, translated);
+
+        let summary = match summary {
+            Some(markdown) => {
+                emit_progress(app, request, "summary", 90, "Translating summary...");
+                Some(translate_markdown(llm, markdown, &target, budget).await?)
+            }
+            None => None,
+        };
+
+        TranslationsRepository::complete_translation(pool, meeting_id, language, &track, summary.as_deref())
+            .await
+            .map_err(|e| format!("Failed to save translation: {}", e))?;
+        emit_progress(app, request, "complete", 100, "Translation complete");
+
+        Ok(TranslationComplete {
+            meeting_id: meeting_id.to_string(),
+            language: language.to_string(),
+            segments: track.len(),
+            untranslated,
+            summary_translated: summary.is_some(),
+        })
+    }
+}
+
+/// Resolved LLM settings for one translation run
+struct TranslationLlm {
+    connection: LlmConnection,
+    model_name: String,
+    app_data_dir: Option<PathBuf>,
+    cancellation_token: CancellationToken,
+    /// Shared by every request of the run so placeholders stay consistent
+    redactor: Option<Redactor>,
+}
+
+impl TranslationLlm {
+    async fn complete(&mut self, system_prompt: &str, user_prompt: &str) -> Result<String, String> {
+        let (system_prompt, user_prompt) = match self.redactor.as_mut() {
+            Some(redactor) => (
+                format!("{} {}", system_prompt, redaction::PLACEHOLDER_NOTE),
+                redactor.redact(user_prompt),
+            ),
+            None => (system_prompt.to_string(), user_prompt.to_string()),
+        };
+        let client = HTTP_CLIENT.clone();
+        let reply = generate_summary(
+            &client,
+            &self.connection.provider,
+            &self.model_name,
+            &self.connection.api_key,
+            &system_prompt,
+            &user_prompt,
+            self.connection.ollama_endpoint.as_deref(),
+            self.connection.custom_openai_endpoint.as_deref(),
+            self.connection.max_tokens,
+            self.connection.temperature,
+            self.connection.top_p,
+            self.app_data_dir.as_ref(),
+            Some(&self.cancellation_token),
+        )
+        .await?;
+        Ok(match &self.redactor {
+            Some(redactor) => redactor.restore(&reply),
+            None => reply,
+        })
+    }
+}
+
+fn emit_progress<R: Runtime>(
+    app: &AppHandle<R>,
+    request: &TranslationRequest,
+    stage: &str,
+    progress: u32,
+    message: &str,
+) {
+    let _ = app.emit(
+        "translation-progress",
+        TranslationProgress {
+            meeting_id: request.meeting_id.clone(),
+            language: request.language.clone(),
+            stage: stage.to_string(),
+            progress,
+            message: message.to_string(),
+        },
+    );
+}
+
+/// Input tokens per request; the translation needs roughly as many output tokens again
+fn batch_token_budget(provider: &LLMProvider, model_name: &str) -> usize {
+    match provider {
+        LLMProvider::BuiltInAI => crate::summary::summary_engine::models::get_model_by_name(model_name)
+            .map(|m| (m.context_size as usize).saturating_sub(600) / 3)
+            .unwrap_or(500),
+        LLMProvider::Ollama => OLLAMA_BATCH_TOKENS,
+        _ => CLOUD_BATCH_TOKENS,
+    }
+}
+
+/// English name for common language codes (used in prompts); unknown codes are used as-is
+pub fn language_name(code: &str) -> String {
+    let name = match code.to_lowercase().as_str() {
+        "en" => "English",
+        "de" => "German",
+        "es" => "Spanish",
+        "fr" => "French",
+        "it" => "Italian",
+        "pt" => "Portuguese",
+        "nl" => "Dutch",
+        "pl" => "Polish",
+        "sv" => "Swedish",
+        "da" => "Danish",
+        "no" | "nb" => "Norwegian",
+        "fi" => "Finnish",
+        "cs" => "Czech",
+        "tr" => "Turkish",
+        "ru" => "Russian",
+        "uk" => "Ukrainian",
+        "ar" => "Arabic",
+        "he" => "Hebrew",
+        "hi" => "Hindi",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        "zh" => "Chinese (Simplified)",
+        "zh-tw" => "Chinese (Traditional)",
+        _ => return code.to_string(),
+    };
+    name.to_string()
+}
+
+fn transcript_system_prompt(target: &str) -> String {
+    format!(
+        "You are a professional meeting translator. Translate each numbered line of the meeting \
+         transcript into {}. Output exactly one line per input line in the form \"[n] translation\", \
+         keeping the numbers. Keep names, product names and figures unchanged. Output nothing else.",
+        target
+    )
+}
+
+fn summary_system_prompt(target: &str) -> String {
+    format!(
+        "You are a professional translator. Translate the following meeting summary written in Markdown \
+         into {}. Preserve the Markdown structure (headings, lists, tables, emphasis) exactly and translate \
+         only the text. Output only the translated Markdown.",
+        target
+    )
+}
+
+/// Group consecutive segments into batches whose text stays under `max_tokens`
+fn build_batches(texts: &[&str], max_tokens: usize) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0;
+
+    for (i, text) in texts.iter().enumerate() {
+        let cost = rough_token_count(text) + 3; // "[n] " prefix and newline
+        if i > start && tokens + cost > max_tokens {
+            batches.push(start..i);
+            start = i;
+            tokens = 0;
+        }
+        tokens += cost;
+    }
+    if start < texts.len() {
+        batches.push(start..texts.len());
+    }
+    batches
+}
+
+fn format_numbered_lines(lines: &[&str]) -> String {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("[{}] {}", i + 1, line.replace('\n', " ").trim()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse "[n] text" lines; returns one entry per expected line (None when missing)
+fn parse_numbered_lines(output: &str, expected: usize) -> Vec<Option<String>> {
+    let mut parsed: Vec<Option<String>> = vec![None; expected];
+
+    for line in output.lines() {
+        let line = line.trim();
+        let rest = match line.strip_prefix('[') {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let (number, text) = match rest.split_once(']') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let index = match number.trim().parse::<usize>() {
+            Ok(n) if n >= 1 && n <= expected => n - 1,
+            _ => continue,
+        };
+
+        let text = text.trim();
+        if !text.is_empty() && parsed[index].is_none() {
+            parsed[index] = Some(text.to_string());
+        }
+    }
+
+    parsed
+}
+
+/// Translated track with the original ids and timestamps. Returns (track, untranslated count);
+/// segments without a translation keep their original text.
+fn build_track(
+    segments: &[TranscriptVersionSegment],
+    translated: Vec<Option<String>>,
+) -> (Vec<TranslatedSegment>, usize) {
+    let mut untranslated = 0;
+    let track = segments
+        .iter()
+        .zip(translated)
+        .map(|(segment, text)| {
+            let text = text.unwrap_or_else(|| {
+                if !segment.text.trim().is_empty() {
+                    untranslated += 1;
+                }
+                segment.text.clone()
+            });
+            TranslatedSegment {
+                id: segment.id.clone(),
+                text,
+                audio_start_time: segment.audio_start_time,
+                audio_end_time: segment.audio_end_time,
+            }
+        })
+        .collect();
+    (track, untranslated)
+}
+
+async fn load_summary_markdown(pool: &SqlitePool, meeting_id: &str) -> Result<Option<String>, String> {
+    let process = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?;
+
+    Ok(process
+        .and_then(|p| p.result)
+        .and_then(|result| serde_json::from_str::<serde_json::Value>(&result).ok())
+        .and_then(|json| json.get("markdown").and_then(|m| m.as_str()).map(str::to_string))
+        .filter(|markdown| !markdown.trim().is_empty()))
+}
+
+/// Translate markdown in blocks (split at blank lines) that fit the model's budget
+async fn translate_markdown(
+    llm: &mut TranslationLlm,
+    markdown: &str,
+    target: &str,
+    budget: usize,
+) -> Result<String, String> {
+    let blocks: Vec<&str> = markdown.split("\n\n").collect();
+    let mut translated = Vec::new();
+
+    for range in build_batches(&blocks, budget) {
+        let chunk = blocks[range].join("\n\n");
+        let output = llm.complete(&summary_system_prompt(target), &chunk).await?;
+        translated.push(clean_llm_markdown_output(&output));
+    }
+
+    Ok(translated.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::segment;
+
+    #[test]
+    fn test_parse_numbered_lines() {
+        let output = "Here you go:\n[1] Hallo zusammen\n[3]   Danke.\n[2] Wie geht's?\n[9] extra\n[2] duplicate";
+        let parsed = parse_numbered_lines(output, 3);
+        assert_eq!(
+            parsed,
+            vec![
+                Some("Hallo zusammen".to_string()),
+                Some("Wie geht's?".to_string()),
+                Some("Danke.".to_string()),
+            ]
+        );
+
+        let missing = parse_numbered_lines("[1] Hola", 2);
+        assert_eq!(missing, vec![Some("Hola".to_string()), None]);
+    }
+
+    #[test]
+    fn test_build_batches_respects_budget() {
+        let long = "word ".repeat(40);
+        let texts = vec!["short", long.as_str(), long.as_str(), "short"];
+        let batches = build_batches(&texts, 60);
+
+        assert_eq!(batches.first().map(|b| b.start), Some(0));
+        assert_eq!(batches.last().map(|b| b.end), Some(texts.len()));
+        assert!(batches.len() >= 2);
+        // Batches are contiguous
+        for pair in batches.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert!(build_batches(&[], 60).is_empty());
+    }
+
+    #[test]
+    fn test_build_track_keeps_timing() {
+        let segments = vec![segment("t-1", "Good morning", 0.0, 1.2), segment("t-2", "Let's start", 1.5, 2.4)];
+
+        let (track, untranslated) = build_track(&segments, vec![Some("Guten Morgen".to_string()), None]);
+        assert_eq!(untranslated, 1);
+        assert_eq!(track[0].text, "Guten Morgen");
+        assert_eq!(track[0].audio_end_time, Some(1.2));
+        assert_eq!(track[1].id, "t-2");
+        assert_eq!(track[1].text, "Let's start");
+    }
+
+    #[test]
+    fn test_language_name() {
+        assert_eq!(language_name("DE"), "German");
+        assert_eq!(language_name("ja"), "Japanese");
+        assert_eq!(language_name("tlh"), "tlh");
+    }
+}
@@ -40,6 +40,7 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, item_id: &str) {
         "pause_recording" => pause_recording_handler(app),
         "resume_recording" => resume_recording_handler(app),
         "stop_recording" => stop_recording_handler(app),
+        "add_marker" => add_marker_handler(app),
         "open_window" => focus_main_window(app),
         "settings" => {
             focus_main_window(app);
@@ -200,6 +201,17 @@ fn stop_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     });
 }
 
+fn add_marker_handler<R: Runtime>(app: &AppHandle<R>) {
+    // No window focus: marking should not interrupt the call
+    let app_clone = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::audio::recording_commands::add_recording_marker(app_clone, None).await {
+            Ok(marker) => log::info!("Tray: Marker added at {:.1}s", marker.time_seconds),
+            Err(e) => log::error!("Tray: Failed to add marker: {}", e),
+        }
+    });
+}
+
 fn check_updates_handler<R: Runtime>(app: &AppHandle<R>) {
     focus_main_window(app);
     if let Some(window) = app.get_webview_window("main") {
@@ -342,6 +354,7 @@ fn build_menu<R: Runtime>(
             }
             RecordingState::Recording => {
                 builder = builder
+                    .item(&MenuItemBuilder::with_id("add_marker", "🔖 Add Marker").build(app)?)
                     .item(&MenuItemBuilder::with_id("pause_recording", "⏸ Pause Recording").build(app)?)
                     .item(&MenuItemBuilder::with_id("stop_recording", "⏹ Stop Recording").build(app)?);
             }
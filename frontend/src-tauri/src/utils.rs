@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 pub fn format_timestamp(seconds: f64) -> String {
     let total_seconds = seconds as u64;
     let hours = total_seconds / 3600;
@@ -6,6 +8,22 @@ pub fn format_timestamp(seconds: f64) -> String {
     format!("{:02}:{:02}:{:02}", hours, minutes, secs)
 }
 
+/// `base`, or `base_2`, `base_3`... the first name `taken` reports as free
+pub fn unique_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}_{}", base, n))
+        .find(|name| !taken(name))
+        .expect("unbounded range")
+}
+
+/// `root/base`, or `root/base_2`, `root/base_3`... if that already exists
+pub fn unique_path(root: &Path, base: &str) -> PathBuf {
+    root.join(unique_name(base, |name| root.join(name).exists()))
+}
+
 /// Opens macOS System Settings to a specific privacy preference pane
 #[cfg(target_os = "macos")]
 #[tauri::command]
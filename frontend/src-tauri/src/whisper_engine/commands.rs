@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use std::path::PathBuf;
 use tauri::{command, Emitter, Manager, AppHandle, Runtime};
 use crate::config::WHISPER_MODEL_CATALOG;
+use crate::audio::transcription::vocabulary::Vocabulary;
 
 // Global whisper engine
 pub static WHISPER_ENGINE: Mutex<Option<Arc<WhisperEngine>>> = Mutex::new(None);
@@ -394,8 +395,9 @@ pub async fn whisper_transcribe_audio(audio_data: Vec<f32>) -> Result<String, St
     if let Some(engine) = engine {
         // Get language preference
         let language = crate::get_language_preference_internal();
+        // Ad-hoc transcription outside a meeting: no custom vocabulary applies
         engine
-            .transcribe_audio(audio_data, language)
+            .transcribe_audio(audio_data, language, &Vocabulary::default())
             .await
             .map_err(|e| format!("Transcription failed: {}", e))
     } else {
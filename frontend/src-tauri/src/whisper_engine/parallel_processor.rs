@@ -7,6 +7,7 @@ use serde::{Serialize, Deserialize};
 
 use super::whisper_engine::WhisperEngine;
 use super::system_monitor::SystemMonitor;
+use crate::audio::transcription::vocabulary::Vocabulary;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioChunk {
@@ -343,8 +344,11 @@ impl ParallelProcessor {
         // Get language preference
         let language = crate::get_language_preference_internal();
 
+        // Not tied to a meeting, so no custom vocabulary applies
+        let vocabulary = Vocabulary::default();
+
         // Transcribe with timeout to prevent hanging
-        let transcription_future = engine.transcribe_audio(chunk.data.clone(), language);
+        let transcription_future = engine.transcribe_audio(chunk.data.clone(), language, &vocabulary);
         let timeout_duration = tokio::time::Duration::from_secs(120); // 2 minute timeout per chunk
 
         let text = tokio::time::timeout(timeout_duration, transcription_future)
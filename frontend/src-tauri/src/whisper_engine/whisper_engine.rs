@@ -11,6 +11,57 @@ use reqwest::Client;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use crate::config::WHISPER_MODEL_CATALOG;
+use crate::audio::transcription::language::{self, MultilingualTranscript, SegmentLanguage};
+use crate::audio::transcription::vocabulary::Vocabulary;
+
+/// Longest audio forced alignment accepts: one Whisper window of 30s at 16kHz
+const FORCED_ALIGNMENT_MAX_SAMPLES: usize = 30 * 16000;
+
+/// Tokens of the text being force-aligned, read by `force_expected_tokens` during decoding
+struct ForcedTokens {
+    tokens: Vec<i32>,
+    token_eot: i32,
+    token_beg: i32,
+    n_vocab: usize,
+}
+
+/// Logits filter for forced alignment: of the text tokens only the next expected one stays
+/// possible (end of text once all were emitted); timestamp tokens are left to the decoder.
+unsafe extern "C" fn force_expected_tokens(
+    _ctx: *mut whisper_rs::whisper_rs_sys::whisper_context,
+    _state: *mut whisper_rs::whisper_rs_sys::whisper_state,
+    tokens: *const whisper_rs::whisper_rs_sys::whisper_token_data,
+    n_tokens: std::os::raw::c_int,
+    logits: *mut f32,
+    user_data: *mut std::os::raw::c_void,
+) {
+    if user_data.is_null() || logits.is_null() {
+        return;
+    }
+    let forced = &*(user_data as *const ForcedTokens);
+    let sampled = if tokens.is_null() || n_tokens <= 0 {
+        &[][..]
+    } else {
+        std::slice::from_raw_parts(tokens, n_tokens as usize)
+    };
+
+    // Text tokens sort below end of text; specials and timestamps above it
+    let emitted = sampled.iter().filter(|t| t.id < forced.token_eot).count();
+    let next = forced.tokens.get(emitted).copied().unwrap_or(forced.token_eot);
+    let logits = std::slice::from_raw_parts_mut(logits, forced.n_vocab);
+    for (id, logit) in logits.iter_mut().enumerate() {
+        let id = id as i32;
+        if id != next && id < forced.token_beg {
+            *logit = f32::NEG_INFINITY;
+        }
+    }
+}
+
+/// Initial prompt for meeting-style output, followed by the custom vocabulary glossary
+/// so Whisper's decoder is biased towards the user's terms
+fn meeting_prompt(vocabulary: &Vocabulary) -> String {
+    vocabulary.prompt("Meeting transcript. Use proper punctuation and capitalization.")
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ModelStatus {
@@ -443,7 +494,7 @@ impl WhisperEngine {
     }
 
     // Check for obviously meaningless patterns
-    fn is_meaningless_output(text: &str) -> bool {
+    pub(crate) fn is_meaningless_output(text: &str) -> bool {
         let text_lower = text.to_lowercase();
 
         // Check for common meaningless patterns
@@ -615,7 +666,12 @@ impl WhisperEngine {
     }
     
     /// Transcribe audio with streaming support for partial results and adaptive quality
-    pub async fn transcribe_audio_with_confidence(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<(String, f32, bool)> {
+    pub async fn transcribe_audio_with_confidence(
+        &self,
+        audio_data: Vec<f32>,
+        language: Option<String>,
+        vocabulary: &Vocabulary,
+    ) -> Result<(String, f32, bool)> {
         let ctx_lock = self.current_context.read().await;
         let ctx = ctx_lock.as_ref()
             .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
@@ -646,7 +702,8 @@ impl WhisperEngine {
         // Condition Whisper's decoder on meeting-style output format
         // This is OpenAI's documented #1 accuracy lever — steers output toward
         // proper punctuation, capitalization, and transcription style
-        params.set_initial_prompt("Meeting transcript. Use proper punctuation and capitalization.");
+        let prompt = meeting_prompt(vocabulary);
+        params.set_initial_prompt(&prompt);
 
         // CRITICAL: Disable timestamp tokens to prevent whisper.cpp chunking heuristics
         // The "single timestamp ending - skip entire chunk" optimization incorrectly discards
@@ -745,6 +802,218 @@ impl WhisperEngine {
         audio_data: Vec<f32>,
         language: Option<String>,
         previous_text: Option<&str>,
+        vocabulary: &Vocabulary,
+    ) -> Result<(String, f32)> {
+        self.transcribe_batch_with_beam(audio_data, language, previous_text, None, vocabulary).await
+    }
+
+    /// Batch transcription with an explicit beam size.
+    /// Used by the repair pass to re-decode doubtful segments harder than the hardware default.
+    pub async fn transcribe_batch_with_beam(
+        &self,
+        audio_data: Vec<f32>,
+        language: Option<String>,
+        previous_text: Option<&str>,
+        beam_size: Option<usize>,
+        vocabulary: &Vocabulary,
+    ) -> Result<(String, f32)> {
+        let (language_code, should_translate) = match language.as_deref() {
+            Some("auto") => (None, false),
+            None => (Some("en"), false),
+            Some("auto-translate") => (None, true),
+            Some(lang) => (Some(lang), false),
+        };
+        self.decode_batch(audio_data, language_code, should_translate, previous_text, beam_size, vocabulary).await
+    }
+
+    /// Forced alignment of `expected_text` to `audio_data`: the decoder may only emit the tokens
+    /// of the text, in order (plus timestamps), so every word gets a timing from Whisper's token
+    /// timestamps. Returns (word, start, end) in seconds from the start of `audio_data`.
+    /// Whisper decodes 30s windows; longer audio cannot be aligned in one pass.
+    pub async fn align_word_timings(
+        &self,
+        audio_data: &[f32],
+        language: Option<String>,
+        expected_text: &str,
+    ) -> Result<Vec<(String, f64, f64)>> {
+        if audio_data.len() > FORCED_ALIGNMENT_MAX_SAMPLES {
+            return Err(anyhow!("Forced alignment needs audio of at most 30 seconds"));
+        }
+
+        let ctx_lock = self.current_context.read().await;
+        let ctx = ctx_lock.as_ref()
+            .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
+
+        // Whisper's words start with a space, so the text is tokenized the same way
+        let text = format!(" {}", expected_text.split_whitespace().collect::<Vec<_>>().join(" "));
+        let forced = ForcedTokens {
+            tokens: ctx.tokenize(&text, text.len() + 16)?,
+            token_eot: ctx.token_eot(),
+            token_beg: ctx.token_beg(),
+            n_vocab: ctx.n_vocab() as usize,
+        };
+        if forced.tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+
+        let language_code = match language.as_deref() {
+            Some("auto") | Some("auto-translate") => None,
+            None => Some("en"),
+            Some(lang) => Some(lang),
+        };
+        params.set_language(language_code);
+        params.set_translate(false);
+        params.set_no_context(true);
+
+        // Token timestamps need the timestamp tokens that batch decoding disables
+        params.set_no_timestamps(false);
+        params.set_token_timestamps(true);
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        // Nothing of the expected text may be suppressed, and there is nothing to fall back to
+        params.set_suppress_blank(false);
+        params.set_suppress_non_speech_tokens(false);
+        params.set_temperature(0.0);
+        params.set_temperature_inc(0.0);
+        params.set_single_segment(false);
+        // SAFETY: `forced` outlives the decode below, which is the only caller of the callback
+        unsafe {
+            params.set_filter_logits_callback(Some(force_expected_tokens));
+            params.set_filter_logits_callback_user_data(&forced as *const ForcedTokens as *mut std::ffi::c_void);
+        }
+
+        let mut state = ctx.create_state()?;
+        state.full(params, audio_data)?;
+
+        let mut words: Vec<(String, f64, f64)> = Vec::new();
+        for i in 0..state.full_n_segments()? {
+            for t in 0..state.full_n_tokens(i)? {
+                let text = match state.full_get_token_text_lossy(i, t) {
+                    Ok(text) => text,
+                    Err(_) => continue,
+                };
+                // Special tokens ([_BEG_], [_TT_n], <|endoftext|>) carry no text
+                if text.starts_with("[_") || text.starts_with("<|") {
+                    continue;
+                }
+                let data = state.full_get_token_data(i, t)?;
+                let (start, end) = (data.t0 as f64 / 100.0, data.t1 as f64 / 100.0);
+
+                // A leading space starts a new word; other tokens continue the current one
+                match words.last_mut() {
+                    Some((word, _, word_end)) if !text.starts_with(' ') => {
+                        word.push_str(&text);
+                        *word_end = word_end.max(end);
+                    }
+                    _ => words.push((text.trim().to_string(), start, end)),
+                }
+            }
+        }
+
+        words.retain(|(word, _, _)| !word.is_empty());
+        Ok(words)
+    }
+
+    /// Identify the spoken language of a segment from its first 30 seconds.
+    /// Runs Whisper's language detector on the mel spectrogram (one encoder pass, no decoding).
+    /// Detection is restricted to `candidates` (all languages when empty); returns the code
+    /// and its probability within the candidate set, or None for English-only models.
+    pub async fn detect_language(
+        &self,
+        audio_data: &[f32],
+        candidates: &[String],
+    ) -> Result<Option<(String, f32)>> {
+        let ctx_lock = self.current_context.read().await;
+        let ctx = ctx_lock.as_ref()
+            .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
+
+        if !ctx.is_multilingual() {
+            return Ok(None);
+        }
+
+        // A single candidate needs no detection pass
+        if let [only] = candidates {
+            return Ok(Some((only.clone(), 1.0)));
+        }
+
+        // Whisper's detector only looks at one 30s window; very short clips are unreliable
+        let window = &audio_data[..audio_data.len().min(30 * 16000)];
+        if window.len() < 8000 {
+            return Ok(None);
+        }
+
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get().min(4))
+            .unwrap_or(2);
+
+        let mut state = ctx.create_state()?;
+        state.pcm_to_mel(window, threads)?;
+        let probabilities = state.lang_detect(0, threads)?;
+
+        let scores: Vec<(String, f32)> = probabilities
+            .iter()
+            .enumerate()
+            .filter_map(|(id, p)| whisper_rs::get_lang_str(id as i32).map(|code| (code.to_string(), *p)))
+            .collect();
+
+        Ok(language::pick_language(&scores, candidates))
+    }
+
+    /// Per-segment language identification for code-switched meetings: detect the language
+    /// among `candidates`, transcribe in that language and optionally add an English translation.
+    /// Only the translation costs a second decode; detection does not decode at all.
+    pub async fn transcribe_multilingual(
+        &self,
+        audio_data: Vec<f32>,
+        candidates: &[String],
+        with_translation: bool,
+        previous_text: Option<&str>,
+        vocabulary: &Vocabulary,
+    ) -> Result<MultilingualTranscript> {
+        let detected = self.detect_language(&audio_data, candidates).await?;
+        let language_code = detected.as_ref().map(|(code, _)| code.clone());
+
+        let translation = match language_code.as_deref() {
+            Some(code) if with_translation && code != "en" => {
+                let (translated, _) = self
+                    .decode_batch(audio_data.clone(), Some(code), true, None, None, vocabulary)
+                    .await?;
+                Some(translated).filter(|t| !t.is_empty())
+            }
+            _ => None,
+        };
+
+        // Unknown language (e.g. English-only model): let Whisper decide as before
+        let (text, confidence) = self
+            .decode_batch(audio_data, language_code.as_deref(), false, previous_text, None, vocabulary)
+            .await?;
+
+        if let Some((code, probability)) = &detected {
+            log::debug!("Detected segment language {} (p={:.2})", code, probability);
+        }
+
+        Ok(MultilingualTranscript {
+            text,
+            confidence,
+            language: SegmentLanguage {
+                language: language_code,
+                translation,
+            },
+        })
+    }
+
+    async fn decode_batch(
+        &self,
+        audio_data: Vec<f32>,
+        language_code: Option<&str>,
+        should_translate: bool,
+        previous_text: Option<&str>,
+        beam_size: Option<usize>,
+        vocabulary: &Vocabulary,
     ) -> Result<(String, f32)> {
         let ctx_lock = self.current_context.read().await;
         let ctx = ctx_lock.as_ref()
@@ -754,16 +1023,10 @@ impl WhisperEngine {
         let batch_config = hardware_profile.get_whisper_config_batch();
 
         let mut params = FullParams::new(SamplingStrategy::BeamSearch {
-            beam_size: batch_config.beam_size as i32,
+            beam_size: beam_size.unwrap_or(batch_config.beam_size) as i32,
             patience: 1.0
         });
 
-        let (language_code, should_translate) = match language.as_deref() {
-            Some("auto") => (None, false),
-            None => (Some("en"), false),
-            Some("auto-translate") => (None, true),
-            Some(lang) => (Some(lang), false),
-        };
         params.set_language(language_code);
         params.set_translate(should_translate);
 
@@ -775,12 +1038,9 @@ impl WhisperEngine {
                 // Take last ~200 chars of previous text for context
                 let ctx_start = prev.len().saturating_sub(200);
                 let ctx_start = prev.ceil_char_boundary(ctx_start);
-                format!(
-                    "Meeting transcript. Use proper punctuation and capitalization. {}",
-                    &prev[ctx_start..]
-                )
+                format!("{} {}", meeting_prompt(vocabulary), &prev[ctx_start..])
             }
-            _ => "Meeting transcript. Use proper punctuation and capitalization.".to_string(),
+            _ => meeting_prompt(vocabulary),
         };
         params.set_initial_prompt(&prompt);
 
@@ -846,7 +1106,7 @@ impl WhisperEngine {
         Ok((cleaned_result, avg_confidence))
     }
 
-    pub async fn transcribe_audio(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<String> {
+    pub async fn transcribe_audio(&self, audio_data: Vec<f32>, language: Option<String>, vocabulary: &Vocabulary) -> Result<String> {
         let ctx_lock = self.current_context.read().await;
         let ctx = ctx_lock.as_ref()
             .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
@@ -875,7 +1135,8 @@ impl WhisperEngine {
         params.set_translate(should_translate);
 
         // Condition Whisper's decoder on meeting-style output format
-        params.set_initial_prompt("Meeting transcript. Use proper punctuation and capitalization.");
+        let prompt = meeting_prompt(vocabulary);
+        params.set_initial_prompt(&prompt);
 
         // CRITICAL: Disable timestamp tokens to prevent whisper.cpp chunking heuristics
         // The "single timestamp ending - skip entire chunk" optimization incorrectly discards
@@ -1019,7 +1280,8 @@ impl WhisperEngine {
                 });
                 retry_params.set_language(language_code);
                 retry_params.set_translate(should_translate);
-                retry_params.set_initial_prompt("Meeting transcript. Use proper punctuation and capitalization.");
+                let retry_prompt = meeting_prompt(vocabulary);
+                retry_params.set_initial_prompt(&retry_prompt);
                 retry_params.set_no_timestamps(true);
                 retry_params.set_token_timestamps(true);
                 retry_params.set_print_special(false);